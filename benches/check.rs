@@ -0,0 +1,60 @@
+//! Parses and checks the files in `corpora` (and a larger project made from them) several times,
+//! printing the median measurements. `cargo bench -- --json` prints JSON lines instead
+//! (for tracking across commits)
+
+use std::path::{Path, PathBuf};
+
+use ezno_lib::bench::{benchmark, median, table_row, TABLE_HEADER};
+
+const ITERATIONS: usize = 10;
+
+const SMALL: &str = include_str!("./corpora/small.ts");
+const MEDIUM: &str = include_str!("./corpora/medium.ts");
+const COMPONENTS: &str = include_str!("./corpora/components.tsx");
+
+/// Modules in the large project. Each is [`MEDIUM`] and imports the next
+const LARGE_MODULES: usize = 40;
+
+fn main() {
+	let json = std::env::args().any(|argument| argument == "--json");
+
+	let large = (0..LARGE_MODULES)
+		.map(|idx| {
+			let content = if idx + 1 < LARGE_MODULES {
+				format!("import \"./module{}.ts\";\n{MEDIUM}", idx + 1)
+			} else {
+				MEDIUM.to_owned()
+			};
+			(format!("/large/module{idx}.ts"), content)
+		})
+		.collect::<Vec<_>>();
+
+	let corpora: [(&str, Vec<(String, String)>); 4] = [
+		("small", vec![("/small.ts".to_owned(), SMALL.to_owned())]),
+		("medium", vec![("/medium.ts".to_owned(), MEDIUM.to_owned())]),
+		("components (tsx)", vec![("/components.tsx".to_owned(), COMPONENTS.to_owned())]),
+		("large", large),
+	];
+
+	if !json {
+		println!("{TABLE_HEADER}");
+	}
+
+	for (name, files) in corpora {
+		let read_file = |path: &Path| {
+			files
+				.iter()
+				.find(|(file, _)| Path::new(file) == path)
+				.map(|(_, content)| content.clone())
+		};
+		let entry = PathBuf::from(&files[0].0);
+		let runs = benchmark(&[entry], &read_file, &[], ITERATIONS);
+		let run = median(runs).unwrap();
+
+		if json {
+			println!("{}", run.to_json(name));
+		} else {
+			println!("{}", table_row(name, &run));
+		}
+	}
+}
@@ -0,0 +1,66 @@
+// Components rendering a (static) table. Representative of JSX heavy code
+
+interface Column<T> {
+	header: string;
+	cell: (row: T) => string | number;
+	align?: "left" | "right";
+}
+
+interface Person {
+	name: string;
+	age: number;
+	city: string;
+}
+
+function Header({ title, subtitle }: { title: string, subtitle?: string }) {
+	return <header>
+		<h1>{title}</h1>
+		{subtitle ? <p class="subtitle">{subtitle}</p> : null}
+	</header>
+}
+
+function Row<T>({ row, columns }: { row: T, columns: Array<Column<T>> }) {
+	return <tr>
+		{columns.map(column => <td class={column.align ?? "left"}>{column.cell(row)}</td>)}
+	</tr>
+}
+
+function Table<T>({ rows, columns }: { rows: Array<T>, columns: Array<Column<T>> }) {
+	return <table>
+		<thead>
+			<tr>{columns.map(column => <th>{column.header}</th>)}</tr>
+		</thead>
+		<tbody>
+			{rows.map(row => <Row row={row} columns={columns} />)}
+		</tbody>
+	</table>
+}
+
+function Footer({ count }: { count: number }) {
+	return <footer>
+		<p>{count === 1 ? "1 person" : `${count} people`}</p>
+		<a href="#top">Back to top</a>
+	</footer>
+}
+
+const people: Array<Person> = [
+	{ name: "Ada", age: 36, city: "London" },
+	{ name: "Grace", age: 85, city: "New York" },
+	{ name: "Alan", age: 41, city: "Manchester" },
+	{ name: "Barbara", age: 73, city: "Boston" },
+];
+
+const columns: Array<Column<Person>> = [
+	{ header: "Name", cell: person => person.name },
+	{ header: "Age", cell: person => person.age, align: "right" },
+	{ header: "City", cell: person => person.city },
+];
+
+export function Page() {
+	const adults = people.filter(person => person.age >= 18);
+	return <main id="top">
+		<Header title="People" subtitle="Sorted by name" />
+		<Table rows={adults} columns={columns} />
+		<Footer count={adults.length} />
+	</main>
+}
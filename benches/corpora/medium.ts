@@ -0,0 +1,166 @@
+// An in-memory task tracker. Representative of application code: classes, generics, unions,
+// narrowing, closures and array methods
+
+export type Priority = "low" | "medium" | "high";
+
+export type Status = { kind: "open" } | { kind: "closed", at: number } | { kind: "blocked", by: Array<number> };
+
+export interface Task {
+	id: number;
+	title: string;
+	priority: Priority;
+	status: Status;
+	tags: Array<string>;
+	estimate?: number;
+}
+
+export interface Event<T> {
+	name: string;
+	payload: T;
+}
+
+type Listener<T> = (event: Event<T>) => void;
+
+export class EventEmitter<T> {
+	private listeners: Array<Listener<T>> = [];
+
+	subscribe(listener: Listener<T>): () => void {
+		this.listeners.push(listener);
+		return () => {
+			this.listeners = this.listeners.filter(other => other !== listener);
+		};
+	}
+
+	emit(name: string, payload: T) {
+		for (const listener of this.listeners) {
+			listener({ name, payload });
+		}
+	}
+}
+
+function priorityWeight(priority: Priority): number {
+	switch (priority) {
+		case "low":
+			return 1;
+		case "medium":
+			return 2;
+		case "high":
+			return 3;
+	}
+}
+
+function isOpen(task: Task): boolean {
+	return task.status.kind === "open";
+}
+
+function describeStatus(status: Status): string {
+	if (status.kind === "closed") {
+		return `closed at ${status.at}`;
+	} else if (status.kind === "blocked") {
+		return `blocked by ${status.by.join(", ")}`;
+	} else {
+		return "open";
+	}
+}
+
+export class TaskStore {
+	private tasks: Array<Task> = [];
+	private nextId: number = 1;
+	readonly events: EventEmitter<Task> = new EventEmitter();
+
+	add(title: string, priority: Priority, tags: Array<string> = [], estimate?: number): Task {
+		const task: Task = { id: this.nextId++, title, priority, status: { kind: "open" }, tags, estimate };
+		this.tasks.push(task);
+		this.events.emit("added", task);
+		return task;
+	}
+
+	get(id: number): Task | undefined {
+		return this.tasks.find(task => task.id === id);
+	}
+
+	close(id: number, at: number): boolean {
+		const task = this.get(id);
+		if (!task || !isOpen(task)) {
+			return false;
+		}
+		task.status = { kind: "closed", at };
+		this.events.emit("closed", task);
+		return true;
+	}
+
+	block(id: number, by: Array<number>) {
+		const task = this.get(id);
+		if (task) {
+			task.status = { kind: "blocked", by };
+			this.events.emit("blocked", task);
+		}
+	}
+
+	open(): Array<Task> {
+		return this.tasks.filter(isOpen);
+	}
+
+	withTag(tag: string): Array<Task> {
+		return this.tasks.filter(task => task.tags.includes(tag));
+	}
+
+	byPriority(): Array<Task> {
+		return this.tasks.slice().sort((a, b) => priorityWeight(b.priority) - priorityWeight(a.priority));
+	}
+
+	totalEstimate(): number {
+		let total = 0;
+		for (const task of this.open()) {
+			total += task.estimate ?? 1;
+		}
+		return total;
+	}
+
+	summary(): Array<string> {
+		return this.byPriority().map(task => `#${task.id} [${task.priority}] ${task.title}: ${describeStatus(task.status)}`);
+	}
+}
+
+export function groupBy<T, K extends string>(items: Array<T>, key: (item: T) => K): Map<K, Array<T>> {
+	const groups = new Map<K, Array<T>>();
+	for (const item of items) {
+		const group = key(item);
+		const existing = groups.get(group);
+		if (existing) {
+			existing.push(item);
+		} else {
+			groups.set(group, [item]);
+		}
+	}
+	return groups;
+}
+
+export function chunk<T>(items: Array<T>, size: number): Array<Array<T>> {
+	const chunks: Array<Array<T>> = [];
+	for (let i = 0; i < items.length; i += size) {
+		chunks.push(items.slice(i, i + size));
+	}
+	return chunks;
+}
+
+const store = new TaskStore();
+const log: Array<string> = [];
+const unsubscribe = store.events.subscribe(event => {
+	log.push(`${event.name}: ${event.payload.title}`);
+});
+
+const parser = store.add("Write parser", "high", ["compiler"], 5);
+const checker = store.add("Write checker", "high", ["compiler"], 8);
+const docs = store.add("Write documentation", "low", ["docs"]);
+const website = store.add("Update website", "medium", ["docs", "web"], 2);
+
+store.block(checker.id, [parser.id]);
+store.close(parser.id, 1700000000);
+unsubscribe();
+store.close(docs.id, 1700000100);
+
+export const byTag = groupBy(store.open(), task => task.priority);
+export const pages = chunk(store.summary(), 2);
+export const remaining = store.totalEstimate();
+export { store, log, website };
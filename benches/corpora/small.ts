@@ -0,0 +1,32 @@
+interface Point {
+	x: number;
+	y: number;
+}
+
+function add(a: Point, b: Point): Point {
+	return { x: a.x + b.x, y: a.y + b.y };
+}
+
+function scale(point: Point, by: number): Point {
+	return { x: point.x * by, y: point.y * by };
+}
+
+function distance(a: Point, b: Point): number {
+	const dx = a.x - b.x, dy = a.y - b.y;
+	return Math.sqrt(dx * dx + dy * dy);
+}
+
+const origin: Point = { x: 0, y: 0 };
+const points: Array<Point> = [{ x: 1, y: 2 }, { x: 3, y: 4 }, { x: -2, y: 5 }];
+
+let total = origin;
+for (const point of points) {
+	total = add(total, scale(point, 2));
+}
+
+export const furthest = points.reduce(
+	(furthest, point) => distance(origin, point) > distance(origin, furthest) ? point : furthest,
+	origin
+);
+
+export { total };
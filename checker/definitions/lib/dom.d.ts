@@ -0,0 +1,35 @@
+/// <reference lib="es2015" />
+
+// Browser globals, for `--lib dom`. Also declared in
+// `../overrides.d.ts` (which the internal definition file is generated from), so changes should
+// be made in both
+
+declare function JSXH(tag: string, attributes: any, children?: any) {
+    return { tag, attributes, children }
+}
+
+interface Document {
+    title: string
+}
+
+interface FormData {
+}
+
+interface Response {
+    ok: boolean;
+
+    json(): Promise<any>;
+}
+
+declare class Console {
+    @InputOutput
+    log(msg: any): void;
+}
+
+declare const console: Console;
+
+@InputOutput
+declare function fetch(from: string): Promise<Response>;
+
+@client
+declare const document: Document;
@@ -0,0 +1,16 @@
+/// <reference lib="es5" />
+
+// Built-in objects added in ES2015, for `--lib es2015`. Also declared in
+// `../overrides.d.ts` (which the internal definition file is generated from), so changes should
+// be made in both
+
+declare class Promise<T> { }
+
+declare class Symbol {
+    @Constant("create_symbol")
+    constructor(description?: string);
+
+    static iterator: SymbolIterator;
+    static hasInstance: SymbolHasInstance;
+    static toPrimitive: SymbolToPrimitive;
+}
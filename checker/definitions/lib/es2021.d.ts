@@ -0,0 +1,15 @@
+/// <reference lib="es2015" />
+
+// Built-in objects added up to ES2021, for `--lib es2021`. Also declared in
+// `../overrides.d.ts` (which the internal definition file is generated from), so changes should
+// be made in both
+
+declare class AggregateError extends Error {
+    errors: Array<any>;
+
+    constructor(errors: Array<any>, message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "AggregateError";
+        this.errors = errors;
+    }
+}
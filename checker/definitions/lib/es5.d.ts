@@ -0,0 +1,368 @@
+// Built-in objects from ES5, for `--lib es5`. Also declared in
+// `../overrides.d.ts` (which the internal definition file is generated from), so changes should
+// be made in both
+
+interface ArrayLike<T> {
+    length: number;
+    [index: number]: T;
+}
+
+declare class Array<T> {
+    [index: number]: T | undefined;
+
+    length: number;
+
+    // `Array(length)` or `Array(...items)`, with or without `new`
+    @Constant("array_constructor")
+    constructor(...items: Array<T>);
+
+    @Constant("array_of")
+    static of<T>(...items: Array<T>): Array<T>;
+
+    static from<T, U = T>(items: ArrayLike<T>, map?: (t: T, i: number) => U): Array<U> {
+        const { length } = items, from: Array<any> = [];
+        let i: number = 0;
+        while (i < length) {
+            const value = items[i];
+            from.push(map ? map(value, i) : value);
+            i++
+        }
+        return from;
+    }
+
+    push(item: T) {
+        this[this.length] = item;
+        return ++this.length
+    }
+
+    pop(): T | undefined {
+        if (this.length === 0) {
+            return undefined
+        } else {
+            const value = this[--this.length];
+            delete this[this.length];
+            return value
+        }
+    }
+
+    // TODO this argument
+    map<U>(cb: (t: T, i?: number) => U): Array<U> {
+        const { length } = this, mapped: Array<U> = [];
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i];
+            mapped.push(cb(value, i++))
+        }
+        return mapped;
+    }
+
+    // // TODO any is debatable
+    filter(cb: (t: T, i?: number) => any): Array<T> {
+        const { length } = this, filtered: Array<T> = [];
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i];
+            if (cb(value, i++)) {
+                filtered.push(value)
+            }
+        }
+        return filtered;
+    }
+
+    // TODO any is debatable
+    find(cb: (t: T, i?: number) => any): T | undefined {
+        const { length } = this;
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i];
+            if (cb(value, i++)) {
+                return value
+            }
+        }
+    }
+
+    // TODO any is debatable
+    every(cb: (t: T, i?: number) => any): boolean {
+        const { length } = this;
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i];
+            if (!cb(value, i++)) {
+                return false
+            }
+        }
+        // Vacuous truth
+        return true
+    }
+
+    some(cb: (t: T, i?: number) => any): boolean {
+        const { length } = this;
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i];
+            if (cb(value, i++)) {
+                return true
+            }
+        }
+        return false
+    }
+
+    concat(other: Array<T>): Array<T> {
+        const { length } = this, concatenated: Array<T> = [];
+        let i: number = 0;
+        while (i < length) {
+            concatenated.push(this[i++])
+        }
+        const { length: otherLength } = other;
+        let j: number = 0;
+        while (j < otherLength) {
+            concatenated.push(other[j++])
+        }
+        return concatenated;
+    }
+
+    slice(start?: number, end?: number): Array<T> {
+        const { length } = this, sliced: Array<T> = [];
+        let i: number = start ?? 0;
+        let stop: number = end ?? length;
+        // Negative indexes are from the end
+        if (i < 0) {
+            i += length
+        }
+        if (stop < 0) {
+            stop += length
+        }
+        if (stop > length) {
+            stop = length
+        }
+        while (i < stop) {
+            sliced.push(this[i++])
+        }
+        return sliced;
+    }
+
+    with(index: number, value: T): Array<T> {
+        const { length } = this, copy: Array<T> = [];
+        const at: number = index < 0 ? index + length : index;
+        let i: number = 0;
+        while (i < length) {
+            if (i === at) {
+                copy.push(value)
+            } else {
+                copy.push(this[i])
+            }
+            i++;
+        }
+        return copy;
+    }
+
+    // Gnome sort, as a single loop with a bound on the amount of steps. Without `compare` items are
+    // compared as strings
+    toSorted(compare?: (a: T, b: T) => number): Array<T> {
+        const { length } = this, sorted: Array<T> = [];
+        let i: number = 0;
+        while (i < length) {
+            sorted.push(this[i++])
+        }
+        const steps: number = length * length;
+        let step: number = 0;
+        let position: number = 1;
+        while (step < steps) {
+            step++;
+            if (position < length) {
+                const previous = sorted[position - 1], current = sorted[position];
+                if (compare ? compare(previous, current) > 0 : "" + previous > "" + current) {
+                    sorted[position - 1] = current;
+                    sorted[position] = previous;
+                    if (position > 1) {
+                        position--
+                    }
+                } else {
+                    position++
+                }
+            }
+        }
+        return sorted;
+    }
+
+    // TODO depth
+    flat(): Array<any> {
+        const { length } = this, flattened: Array<any> = [];
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i++];
+            if (typeof value === "object") {
+                let j: number = 0;
+                while (j < value.length) {
+                    flattened.push(value[j++])
+                }
+            } else {
+                flattened.push(value)
+            }
+        }
+        return flattened;
+    }
+
+    // includes(searchElement: T, fromIndex?: number): boolean {
+    //     const { length } = this;
+    //     // TODO this is currently broken
+    //     let i: number = fromIndex ?? 0;
+    //     while (i < length) {
+    //         const value = this[i++];
+    //         if (value === searchElement) {
+    //             return true
+    //         }
+    //     }
+    //     return false
+    // }
+
+    // join(joiner: string = ","): string {
+    //     const { length } = this;
+    //     let i: number = 1;
+    //     if (length === 0) {
+    //         return ""
+    //     }
+    //     let s: string = "" + this[0];
+    //     while (i < length) {
+    //         s += joiner;
+    //         s += this[i++];
+    //         // debug_type_independent(s)
+    //     }
+    //     return s
+    // }
+}
+
+declare class Math {
+    @Constant
+    static sin(x: number): number;
+    @Constant
+    static cos(x: number): number;
+    @Constant
+    static tan(x: number): number;
+    @Constant
+    static floor(x: number): number;
+    @Constant
+    static sqrt(x: number): number;
+    @Constant
+    static cbrt(x: number): number;
+
+    // TODO newer method
+    @Constant
+    static trunc(x: number): number;
+
+    static PI: 3.141592653589793
+}
+
+@Primitive("string")
+declare class String {
+    [index: number]: string | undefined;
+
+    @Constant
+    toUpperCase(): string;
+    @Constant
+    toLowerCase(): string;
+
+    @Constant("string_length")
+    get length(): number;
+
+    // TODO
+    slice(start: number, end?: number): string;
+
+    // TODO
+    split(splitter: string): Array<string>;
+}
+
+interface ImportMeta {
+    url: string;
+}
+
+declare class JSON {
+    // TODO any temp
+    parse(input: string): any;
+
+    // TODO any temp
+    stringify(input: any): string;
+}
+
+declare class Function {
+    @Constant
+    bind(this_ty: any, ...args: Array<any>): Function;
+
+    @Constant
+    call(this_ty: any, ...args: Array<any>): any;
+
+    @Constant
+    apply(this_ty: any, args?: Array<any>): any;
+}
+
+declare class Error {
+    name: string = "Error";
+    message: string;
+    cause: any;
+
+    constructor(message?: string, options?: { cause: any }) {
+        this.message = message ?? "";
+        if (options) {
+            this.cause = options.cause;
+        }
+    }
+
+    // Not standard, depends on the engine
+    get stack(): string;
+}
+
+declare class TypeError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "TypeError";
+    }
+}
+
+declare class RangeError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "RangeError";
+    }
+}
+
+declare class SyntaxError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "SyntaxError";
+    }
+}
+
+declare class ReferenceError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "ReferenceError";
+    }
+}
+
+declare class Object {
+    @Constant
+    static setPrototypeOf(on: object, to: object): object;
+
+    @Constant
+    static getPrototypeOf(on: object): object | null;
+
+    @Constant
+    static freeze(on: object): object;
+
+    @Constant
+    static isFrozen(on: object): boolean;
+
+    // create(prototype: object): object performs {
+    //     const n = {};
+    //     Object.setProtoTypeOf(n, prototype);
+    //     return n
+    // }
+
+    // keys(on: object): Array<string> performs {
+    //     const array = [];
+    //     for (const key in on) {
+    //         array.push(key);
+    //     }
+    //     return array
+    // }
+}
@@ -0,0 +1,24 @@
+/// <reference lib="es2015" />
+
+// Web worker globals, for `--lib webworker`. Also declared in
+// `../overrides.d.ts` (which the internal definition file is generated from), so changes should
+// be made in both
+
+interface FormData {
+}
+
+interface Response {
+    ok: boolean;
+
+    json(): Promise<any>;
+}
+
+declare class Console {
+    @InputOutput
+    log(msg: any): void;
+}
+
+declare const console: Console;
+
+@InputOutput
+declare function fetch(from: string): Promise<Response>;
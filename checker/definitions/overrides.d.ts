@@ -1,15 +1,33 @@
 @Constant
 declare function debug_type_independent(t: any): void;
 
+interface ArrayLike<T> {
+    length: number;
+    [index: number]: T;
+}
+
 declare class Array<T> {
     [index: number]: T | undefined;
 
     length: number;
 
-    // TODO WIP
-    // constructor(...items: Array<T>) {
-    //     return items;
-    // }
+    // `Array(length)` or `Array(...items)`, with or without `new`
+    @Constant("array_constructor")
+    constructor(...items: Array<T>);
+
+    @Constant("array_of")
+    static of<T>(...items: Array<T>): Array<T>;
+
+    static from<T, U = T>(items: ArrayLike<T>, map?: (t: T, i: number) => U): Array<U> {
+        const { length } = items, from: Array<any> = [];
+        let i: number = 0;
+        while (i < length) {
+            const value = items[i];
+            from.push(map ? map(value, i) : value);
+            i++
+        }
+        return from;
+    }
 
     push(item: T) {
         this[this.length] = item;
@@ -88,6 +106,102 @@ declare class Array<T> {
         return false
     }
 
+    concat(other: Array<T>): Array<T> {
+        const { length } = this, concatenated: Array<T> = [];
+        let i: number = 0;
+        while (i < length) {
+            concatenated.push(this[i++])
+        }
+        const { length: otherLength } = other;
+        let j: number = 0;
+        while (j < otherLength) {
+            concatenated.push(other[j++])
+        }
+        return concatenated;
+    }
+
+    slice(start?: number, end?: number): Array<T> {
+        const { length } = this, sliced: Array<T> = [];
+        let i: number = start ?? 0;
+        let stop: number = end ?? length;
+        // Negative indexes are from the end
+        if (i < 0) {
+            i += length
+        }
+        if (stop < 0) {
+            stop += length
+        }
+        if (stop > length) {
+            stop = length
+        }
+        while (i < stop) {
+            sliced.push(this[i++])
+        }
+        return sliced;
+    }
+
+    with(index: number, value: T): Array<T> {
+        const { length } = this, copy: Array<T> = [];
+        const at: number = index < 0 ? index + length : index;
+        let i: number = 0;
+        while (i < length) {
+            if (i === at) {
+                copy.push(value)
+            } else {
+                copy.push(this[i])
+            }
+            i++;
+        }
+        return copy;
+    }
+
+    // Gnome sort, as a single loop with a bound on the amount of steps. Without `compare` items are
+    // compared as strings
+    toSorted(compare?: (a: T, b: T) => number): Array<T> {
+        const { length } = this, sorted: Array<T> = [];
+        let i: number = 0;
+        while (i < length) {
+            sorted.push(this[i++])
+        }
+        const steps: number = length * length;
+        let step: number = 0;
+        let position: number = 1;
+        while (step < steps) {
+            step++;
+            if (position < length) {
+                const previous = sorted[position - 1], current = sorted[position];
+                if (compare ? compare(previous, current) > 0 : "" + previous > "" + current) {
+                    sorted[position - 1] = current;
+                    sorted[position] = previous;
+                    if (position > 1) {
+                        position--
+                    }
+                } else {
+                    position++
+                }
+            }
+        }
+        return sorted;
+    }
+
+    // TODO depth
+    flat(): Array<any> {
+        const { length } = this, flattened: Array<any> = [];
+        let i: number = 0;
+        while (i < length) {
+            const value = this[i++];
+            if (typeof value === "object") {
+                let j: number = 0;
+                while (j < value.length) {
+                    flattened.push(value[j++])
+                }
+            } else {
+                flattened.push(value)
+            }
+        }
+        return flattened;
+    }
+
     // includes(searchElement: T, fromIndex?: number): boolean {
     //     const { length } = this;
     //     // TODO this is currently broken
@@ -147,6 +261,7 @@ declare class String {
     @Constant
     toLowerCase(): string;
 
+    @Constant("string_length")
     get length(): number;
 
     // TODO
@@ -158,6 +273,10 @@ declare class String {
 
 declare class Promise<T> { }
 
+interface ImportMeta {
+    url: string;
+}
+
 interface Response {
     ok: boolean;
 
@@ -166,7 +285,7 @@ interface Response {
 
 declare class Console {
     @InputOutput
-    log(msg: any): void;
+    log(...data: Array<any>): void;
 }
 
 declare const console: Console;
@@ -180,12 +299,77 @@ declare class JSON {
 }
 
 declare class Function {
-    bind(this_ty: any): Function;
+    @Constant
+    bind(this_ty: any, ...args: Array<any>): Function;
+
+    @Constant
+    call(this_ty: any, ...args: Array<any>): any;
+
+    @Constant
+    apply(this_ty: any, args?: Array<any>): any;
 }
 
-declare class Symbols {
-    // TODO temp
-    iterator: 199
+declare class Symbol {
+    @Constant("create_symbol")
+    constructor(description?: string);
+
+    static iterator: SymbolIterator;
+    static hasInstance: SymbolHasInstance;
+    static toPrimitive: SymbolToPrimitive;
+}
+
+declare class Error {
+    name: string = "Error";
+    message: string;
+    cause: any = undefined;
+
+    constructor(message?: string, options?: { cause: any }) {
+        this.message = message ?? "";
+        if (options) {
+            this.cause = options.cause;
+        }
+    }
+
+    // Not standard, depends on the engine
+    get stack(): string;
+}
+
+declare class TypeError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "TypeError";
+    }
+}
+
+declare class RangeError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "RangeError";
+    }
+}
+
+declare class SyntaxError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "SyntaxError";
+    }
+}
+
+declare class ReferenceError extends Error {
+    constructor(message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "ReferenceError";
+    }
+}
+
+declare class AggregateError extends Error {
+    errors: Array<any>;
+
+    constructor(errors: Array<any>, message?: string, options?: { cause: any }) {
+        super(message, options);
+        this.name = "AggregateError";
+        this.errors = errors;
+    }
 }
 
 declare class Object {
@@ -195,6 +379,12 @@ declare class Object {
     @Constant
     static getPrototypeOf(on: object): object | null;
 
+    @Constant
+    static freeze(on: object): object;
+
+    @Constant
+    static isFrozen(on: object): boolean;
+
     // create(prototype: object): object performs {
     //     const n = {};
     //     Object.setProtoTypeOf(n, prototype);
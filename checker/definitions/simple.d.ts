@@ -107,7 +107,7 @@ interface nominal string {
 
 interface Console {
     @DoNotIncludeThis
-    log(msg: any): void;
+    log(...data: Array<any>): void;
 }
 
 interface JSON {
@@ -124,12 +124,14 @@ interface Function {
     bind(this_ty: any): Function performs const bind;
 }
 
-interface Symbols {
-    // TODO temp
-    iterator: 199
-}
+declare class Symbol {
+    @Constant("create_symbol")
+    constructor(description?: string);
 
-declare const Symbol: Symbols;
+    static iterator: SymbolIterator;
+    static hasInstance: SymbolHasInstance;
+    static toPrimitive: SymbolToPrimitive;
+}
 
 interface Object {
     @DoNotIncludeThis
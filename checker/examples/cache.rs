@@ -15,7 +15,11 @@ fn main() {
 	// This reader, doesn't lookup in the cache
 	let reader = |path: &std::path::Path| read_to_string(path).ok();
 
-	let cache = generate_cache::<_, EznoParser>(PathBuf::from(input).as_path(), &reader, ());
+	let cache = generate_cache::<_, EznoParser>(
+		PathBuf::from(input).as_path(),
+		&reader,
+		Default::default(),
+	);
 	write(output, cache).unwrap();
 	eprintln!("Cache generated 🏧💵✅")
 }
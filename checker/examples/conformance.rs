@@ -0,0 +1,217 @@
+//! Runs the checker over TypeScript's conformance tests and writes a scoreboard.
+//!
+//! ```shell
+//! cargo run -p ezno-checker --example conformance -- path/to/TypeScript/tests/cases/conformance \
+//!     --baselines path/to/TypeScript/tests/baselines/reference --output scoreboard.json --compare scoreboard.json
+//! ```
+//!
+//! A test passes if it has the same number of errors as TypeScript's `.errors.txt` baseline (no baseline
+//! means no errors). With `--compare`, tests that previously passed but no longer do are listed and the
+//! process exits with a non-zero code
+
+use std::{
+	collections::BTreeMap,
+	env, fs,
+	panic::{self, AssertUnwindSafe},
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+use ezno_checker::{synthesis::EznoParser, DiagnosticKind, TypeCheckOptions};
+use parser::ASTNode;
+use simple_json_parser::{JSONKey, RootJSONValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+	Pass,
+	DiagnosticsDiffer,
+	Panic,
+	/// Syntax the parser does not support (or multi-file tests)
+	Unsupported,
+}
+
+impl Status {
+	fn as_str(self) -> &'static str {
+		match self {
+			Status::Pass => "pass",
+			Status::DiagnosticsDiffer => "diagnostics-differ",
+			Status::Panic => "panic",
+			Status::Unsupported => "unsupported",
+		}
+	}
+}
+
+fn main() -> ExitCode {
+	let mut args = env::args().skip(1);
+	let cases = PathBuf::from(args.next().expect("expected path to conformance cases"));
+
+	let (mut baselines, mut output, mut compare) = (None, None, None);
+	while let Some(arg) = args.next() {
+		let value = args.next().map(PathBuf::from);
+		match arg.as_str() {
+			"--baselines" => baselines = value,
+			"--output" => output = value,
+			"--compare" => compare = value,
+			arg => panic!("unknown argument {arg}"),
+		}
+	}
+
+	let mut files = Vec::new();
+	collect_test_files(&cases, &mut files);
+	files.sort();
+
+	// Panics are recorded in the scoreboard
+	panic::set_hook(Box::new(|_| {}));
+
+	let mut results = BTreeMap::new();
+	for file in &files {
+		let name =
+			file.strip_prefix(&cases).unwrap_or(file).display().to_string().replace('\\', "/");
+		let expected_errors =
+			baselines.as_deref().map_or(0, |baselines| expected_error_count(file, baselines));
+		results.insert(name, run_test(file, expected_errors));
+	}
+
+	let _ = panic::take_hook();
+
+	let mut summary = BTreeMap::<Status, usize>::new();
+	for status in results.values() {
+		*summary.entry(*status).or_default() += 1;
+	}
+	for (status, count) in &summary {
+		eprintln!("{}: {count}", status.as_str());
+	}
+	eprintln!("total: {}", results.len());
+
+	let regressions = compare.map(|previous| {
+		let previous = fs::read_to_string(previous).expect("could not read scoreboard to compare");
+		let mut regressions = Vec::new();
+		let _ = simple_json_parser::parse_with_exit_signal(&previous, |path, value| {
+			if let (
+				[JSONKey::Slice("tests"), JSONKey::Slice(name)],
+				RootJSONValue::String("pass"),
+			) = (path, value)
+			{
+				if results.get(*name) != Some(&Status::Pass) {
+					regressions.push((*name).to_owned());
+				}
+			}
+			false
+		});
+		regressions
+	});
+
+	if let Some(output) = output {
+		fs::write(output, scoreboard_to_json(&summary, &results)).unwrap();
+	}
+
+	match regressions {
+		Some(regressions) if !regressions.is_empty() => {
+			eprintln!("{} regressions:", regressions.len());
+			for regression in regressions {
+				eprintln!(
+					"- {regression} ({})",
+					results.get(&regression).copied().map_or("removed", Status::as_str)
+				);
+			}
+			ExitCode::FAILURE
+		}
+		_ => ExitCode::SUCCESS,
+	}
+}
+
+fn collect_test_files(directory: &Path, files: &mut Vec<PathBuf>) {
+	let Ok(entries) = fs::read_dir(directory) else { return };
+	for entry in entries.flatten() {
+		let path = entry.path();
+		if path.is_dir() {
+			collect_test_files(&path, files);
+		} else if path.extension().is_some_and(|ext| ext == "ts" || ext == "tsx") {
+			files.push(path);
+		}
+	}
+}
+
+/// Counts the `file(line,column): error TS...` lines at the top of the `.errors.txt` baseline
+fn expected_error_count(file: &Path, baselines: &Path) -> usize {
+	let Some(stem) = file.file_stem().and_then(|stem| stem.to_str()) else { return 0 };
+	let Ok(baseline) = fs::read_to_string(baselines.join(format!("{stem}.errors.txt"))) else {
+		return 0;
+	};
+	baseline
+		.lines()
+		.take_while(|line| !line.starts_with("===="))
+		.filter(|line| line.contains("): error TS") || line.starts_with("error TS"))
+		.count()
+}
+
+fn run_test(file: &Path, expected_errors: usize) -> Status {
+	let Ok(content) = fs::read_to_string(file) else { return Status::Unsupported };
+
+	// Multi-file tests are not split up
+	if content.lines().any(|line| line.trim_start().to_lowercase().starts_with("// @filename")) {
+		return Status::Unsupported;
+	}
+
+	let parse_options = parser::ParseOptions::all_features();
+	if parser::Module::from_string(content.clone(), parse_options).is_err() {
+		return Status::Unsupported;
+	}
+
+	let result = panic::catch_unwind(AssertUnwindSafe(|| {
+		let reader = |path: &Path| {
+			if path == file {
+				Some(content.clone())
+			} else {
+				fs::read_to_string(path).ok()
+			}
+		};
+		ezno_checker::check_project::<_, EznoParser>(
+			vec![file.to_path_buf()],
+			std::iter::once(ezno_checker::INTERNAL_DEFINITION_FILE_PATH.into()).collect(),
+			&reader,
+			TypeCheckOptions::default(),
+			Default::default(),
+		)
+	}));
+
+	match result {
+		Ok(output) => {
+			let errors = output
+				.diagnostics
+				.into_iter()
+				.filter(|diagnostic| matches!(diagnostic.kind(), DiagnosticKind::Error))
+				.count();
+			if errors == expected_errors {
+				Status::Pass
+			} else {
+				Status::DiagnosticsDiffer
+			}
+		}
+		Err(_) => Status::Panic,
+	}
+}
+
+fn scoreboard_to_json(
+	summary: &BTreeMap<Status, usize>,
+	results: &BTreeMap<String, Status>,
+) -> String {
+	fn escape(s: &str) -> String {
+		s.replace('\\', "\\\\").replace('"', "\\\"")
+	}
+
+	let mut buf = String::from("{\n\t\"summary\": {\n");
+	let summary = summary
+		.iter()
+		.map(|(status, count)| format!("\t\t\"{}\": {count}", status.as_str()))
+		.collect::<Vec<_>>();
+	buf.push_str(&summary.join(",\n"));
+	buf.push_str("\n\t},\n\t\"tests\": {\n");
+	let tests = results
+		.iter()
+		.map(|(name, status)| format!("\t\t\"{}\": \"{}\"", escape(name), status.as_str()))
+		.collect::<Vec<_>>();
+	buf.push_str(&tests.join(",\n"));
+	buf.push_str("\n\t}\n}\n");
+	buf
+}
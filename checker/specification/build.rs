@@ -14,33 +14,49 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 	if cfg!(not(feature = "just-staging")) {
 		let specification = read_to_string("./specification.md")?;
-		markdown_lines_append_test_to_rust(specification.lines().enumerate(), &mut out)?;
+		let ignored =
+			markdown_lines_append_test_to_rust(specification.lines().enumerate(), &mut out)?;
+		report_ignored("specification.md", ignored);
 	}
 
 	if cfg!(feature = "staging") {
 		let staging = read_to_string("./staging.md")?;
-		writeln!(&mut out, "mod staging {{ use super::check_errors; ").unwrap();
-		markdown_lines_append_test_to_rust(staging.lines().enumerate(), &mut out)?;
+		writeln!(&mut out, "mod staging {{ use super::{{check_errors, CaseFailure}}; ").unwrap();
+		let ignored = markdown_lines_append_test_to_rust(staging.lines().enumerate(), &mut out)?;
 		writeln!(&mut out, "}}").unwrap();
+		report_ignored("staging.md", ignored);
 	}
 
 	if cfg!(feature = "all") {
 		let to_implement = read_to_string("./to_implement.md")?;
-		writeln!(&mut out, "mod to_implement {{ use super::check_errors; ").unwrap();
-		markdown_lines_append_test_to_rust(to_implement.lines().enumerate(), &mut out)?;
+		writeln!(&mut out, "mod to_implement {{ use super::{{check_errors, CaseFailure}}; ").unwrap();
+		let ignored =
+			markdown_lines_append_test_to_rust(to_implement.lines().enumerate(), &mut out)?;
 		writeln!(&mut out, "}}").unwrap();
+		report_ignored("to_implement.md", ignored);
 	}
 
 	Ok(())
 }
 
+/// Marks a test as a known failure. Run them with `cargo test -- --ignored`
+const IGNORE_TAG: &str = "[ignore]";
+
+fn report_ignored(file: &str, ignored: usize) {
+	if ignored > 0 {
+		println!("cargo:warning={ignored} known failures ignored in {file}");
+	}
+}
+
 const DEFAULT_FILE_PATH: &str = "main.ts";
 
+/// Returns the number of ignored tests
 fn markdown_lines_append_test_to_rust(
 	mut lines: std::iter::Enumerate<std::str::Lines<'_>>,
 	out: &mut File,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<usize, Box<dyn Error>> {
 	let mut first_section = true;
+	let mut ignored = 0;
 
 	while let Some((heading_idx, line)) = lines.next() {
 		if let Some(section_heading) = line.strip_prefix("### ") {
@@ -57,7 +73,11 @@ fn markdown_lines_append_test_to_rust(
 			continue;
 		}
 
-		let heading = line.strip_prefix("####").unwrap().trim_start();
+		let heading = line.strip_prefix("####").unwrap().trim();
+		let (heading, ignore) = match heading.strip_suffix(IGNORE_TAG) {
+			Some(heading) => (heading.trim_end(), true),
+			None => (heading, false),
+		};
 		let test_title = heading_to_rust_identifier(heading);
 
 		let blocks = {
@@ -117,9 +137,15 @@ fn markdown_lines_append_test_to_rust(
 				acc
 			});
 
+		if ignore {
+			ignored += 1;
+			writeln!(out, "#[ignore = \"known failure\"]")?;
+		}
+
+		let heading = heading.replace('"', "\\\"");
 		writeln!(
 			out,
-			"#[test] fn {test_title}() {{ 
+			"#[test] fn {test_title}() -> Result<(), super::CaseFailure> {{ 
                 super::check_errors(\"{heading}\", {heading_idx}, &[{code}], &[{errors}])
             }}",
 		)?;
@@ -128,12 +154,19 @@ fn markdown_lines_append_test_to_rust(
 		writeln!(out, "}}").unwrap();
 	}
 
-	Ok(())
+	Ok(ignored)
 }
 
+/// So that `cargo test -- <words from heading>` (with spaces as `_`) filters to a test
 fn heading_to_rust_identifier(heading: &str) -> String {
-	heading
-		.replace([' ', '-', '/', '&'], "_")
-		.replace(['*', '\'', '`', '"', '!', '(', ')', ','], "")
-		.to_lowercase()
+	let identifier = heading
+		.replace(['*', '\'', '`', '"', '!', '(', ')', ',', '.', '?', ':'], "")
+		.replace(|chr: char| !chr.is_ascii_alphanumeric(), "_")
+		.to_lowercase();
+
+	if identifier.starts_with(|chr: char| chr.is_ascii_digit()) {
+		format!("_{identifier}")
+	} else {
+		identifier
+	}
 }
@@ -1,78 +1,108 @@
 #![allow(unused)]
 use std::{
+	cell::{Cell, RefCell},
 	collections::HashSet,
 	panic,
 	path::{Path, PathBuf},
-	sync::{Arc, Mutex},
+	sync::Once,
 };
 
 use checker::{diagnostics, synthesis::EznoParser};
 use parser::ASTNode;
 
 mod specification {
-	use super::check_errors;
+	use super::{check_errors, CaseFailure};
 
 	// from build.rs
 	include!(concat!(env!("OUT_DIR"), "/specification.rs"));
 }
 
-// fn catch_unwind_silent<F: FnOnce() -> R + panic::UnwindSafe, R>(f: F) -> std::thread::Result<R> {
-// 	let prev_hook = panic::take_hook();
-// 	let result = panic::catch_unwind(f);
-// 	panic::set_hook(prev_hook);
-// 	result
-// }
+thread_local! {
+	/// Whether a case is running on this thread. See [`capture_panics`]
+	static IN_CASE: Cell<bool> = const { Cell::new(false) };
+	/// The message and location of the last panic of a case on this thread
+	static CASE_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Cases are separate tests, so run in parallel. Panics in a case are recorded for the thread
+/// that the case is running on, rather than printed by the default hook (where the output of cases
+/// would interleave), and are returned as the failure of that case
+fn capture_panics() {
+	static SET_HOOK: Once = Once::new();
+	SET_HOOK.call_once(|| {
+		let default_hook = panic::take_hook();
+		panic::set_hook(Box::new(move |info| {
+			if IN_CASE.get() {
+				let message = info
+					.payload()
+					.downcast_ref::<&str>()
+					.map(ToString::to_string)
+					.or_else(|| info.payload().downcast_ref::<String>().cloned())
+					.unwrap_or_default();
+				let location =
+					info.location().map(|location| format!(" at {location}")).unwrap_or_default();
+				CASE_PANIC.set(Some(format!("{message}{location}")));
+			} else {
+				default_hook(info);
+			}
+		}));
+	});
+}
+
+/// Why a case failed. Printed as is (tests print the [`std::fmt::Debug`] of their error)
+struct CaseFailure(String);
+
+impl std::fmt::Debug for CaseFailure {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.0)
+	}
+}
 
 /// Called by each test
 fn check_errors(
 	heading: &'static str,
-	_line: usize,
+	line: usize,
 	// (Path, Content)
 	code: &[(&'static str, &'static str)],
 	expected_diagnostics: &[&'static str],
-) {
-	// let global_buffer = Arc::new(Mutex::new(String::new()));
-	// let old_panic_hook = panic::take_hook();
-
-	// panic::set_hook({
-	// 	let global_buffer = global_buffer.clone();
-	// 	Box::new(move |info| {
-	// 		let mut global_buffer = global_buffer.lock().unwrap();
-	// 		if let Some(position) = info.location() {
-	// 			global_buffer.push_str(&format!("panicked at {:?}", position));
-	// 		} else {
-	// 			global_buffer.push_str(&info.to_string());
-	// 		}
-	// 	})
-	// });
-
+) -> Result<(), CaseFailure> {
 	// TODO could test these
 	let type_check_options = Default::default();
 
 	// eprintln!("{:?}", code);
 
-	// let result = panic::catch_unwind(|| {
-	let result = checker::check_project::<_, EznoParser>(
-		vec![PathBuf::from("main.ts")],
-		std::iter::once(checker::INTERNAL_DEFINITION_FILE_PATH.into()).collect(),
-		|path: &Path| -> Option<Vec<u8>> {
-			if code.len() == 1 {
-				Some(code[0].1.to_owned().into())
-			} else {
-				code.iter()
-					.find_map(|(code_path, content)| {
-						(std::path::Path::new(code_path) == path)
-							.then_some(content.to_owned().to_owned())
-					})
-					.map(Into::into)
-			}
-		},
-		type_check_options,
-		(),
-	);
-	// });
+	// Panics in the checker are reported with the heading and line of the case in the markdown
+	capture_panics();
+	IN_CASE.set(true);
+	let result = panic::catch_unwind(|| {
+		checker::check_project::<_, EznoParser>(
+			vec![PathBuf::from("main.ts")],
+			std::iter::once(checker::INTERNAL_DEFINITION_FILE_PATH.into()).collect(),
+			&|path: &Path| -> Option<Vec<u8>> {
+				if code.len() == 1 {
+					Some(code[0].1.to_owned().into())
+				} else {
+					code.iter()
+						.find_map(|(code_path, content)| {
+							(std::path::Path::new(code_path) == path)
+								.then_some(content.to_owned().to_owned())
+						})
+						.map(Into::into)
+				}
+			},
+			type_check_options,
+			Default::default(),
+		)
+	});
+
+	IN_CASE.set(false);
 
-	// panic::set_hook(old_panic_hook);
+	let Ok(result) = result else {
+		let message = CASE_PANIC.take().unwrap_or_default();
+		return Err(CaseFailure(format!(
+			"checker panicked on '{heading}' (line {line}): {message}"
+		)));
+	};
 
 	let diagnostics: Vec<String> = result
 		.diagnostics
@@ -88,15 +118,12 @@ fn check_errors(
 		})
 		.collect();
 
-	if diagnostics != expected_diagnostics {
-		panic!(
-			"{}",
-			pretty_assertions::Comparison::new(expected_diagnostics, &diagnostics).to_string()
-		)
+	if diagnostics == expected_diagnostics {
+		Ok(())
+	} else {
+		Err(CaseFailure(format!(
+			"'{heading}' (line {line}):\n{}",
+			pretty_assertions::Comparison::new(expected_diagnostics, &diagnostics)
+		)))
 	}
-
-	// match result {
-	// 	Ok(result) => {}
-	// 	Err(error) => Err(Arc::into_inner(global_buffer).unwrap().into_inner().unwrap()),
-	// }
 }
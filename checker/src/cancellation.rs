@@ -0,0 +1,38 @@
+//! Stopping a check whose result is no longer needed, for example when a file is edited while it is
+//! being checked. See [`crate::TypeCheckOptions::cancellation`]
+
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc,
+};
+
+/// Shared between clones, so a check can be cancelled from another thread. Checking stops at the
+/// next module, statement, loop iteration or subtyping step after [`CancellationToken::cancel`] is
+/// called. The diagnostics of a cancelled check are incomplete
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::Relaxed);
+	}
+
+	#[must_use]
+	pub fn is_cancelled(&self) -> bool {
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+/// Tokens are not part of cached definitions
+impl crate::BinarySerializable for CancellationToken {
+	fn serialize(self, _buf: &mut Vec<u8>) {}
+
+	fn deserialize<I: Iterator<Item = u8>>(_iter: &mut I, _source: source_map::SourceId) -> Self {
+		Self::default()
+	}
+}
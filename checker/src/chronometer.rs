@@ -0,0 +1,72 @@
+//! Durations of the stages of checking and of each module. Only recorded with
+//! [`crate::TypeCheckOptions::measure_time`]
+
+use std::time::Duration;
+
+use source_map::SourceId;
+
+#[cfg(not(target_family = "wasm"))]
+pub(crate) use std::time::Instant;
+
+/// [`std::time::Instant::now`] panics under WASM, so nothing is measured there
+#[cfg(target_family = "wasm")]
+#[derive(Clone, Copy)]
+pub(crate) struct Instant;
+
+#[cfg(target_family = "wasm")]
+impl Instant {
+	pub(crate) fn now() -> Self {
+		Self
+	}
+
+	#[allow(clippy::unused_self)]
+	pub(crate) fn elapsed(&self) -> Duration {
+		Duration::ZERO
+	}
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Chronometer {
+	/// Reading and synthesising definition files
+	pub definition_files: Duration,
+	/// Parsing and checking all modules (everything after definition files)
+	pub modules_total: Duration,
+	/// In the order they were parsed
+	pub modules: Vec<ModuleTimings>,
+	/// Number of types when checking finished
+	pub types: usize,
+	/// Sum of [`ModuleTimings::check`], for subtracting modules imported while checking another
+	checked: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleTimings {
+	pub source: SourceId,
+	pub parse: Duration,
+	/// Excludes time spent checking the modules it imports
+	pub check: Duration,
+}
+
+impl Chronometer {
+	pub(crate) fn record_parse(&mut self, source: SourceId, parse: Duration) {
+		self.modules.push(ModuleTimings { source, parse, check: Duration::ZERO });
+	}
+
+	/// Returns what to pass to [`Self::record_check`] after checking
+	pub(crate) fn start_check(&self) -> (Instant, Duration) {
+		(Instant::now(), self.checked)
+	}
+
+	pub(crate) fn record_check(
+		&mut self,
+		source: SourceId,
+		(start, checked_before): (Instant, Duration),
+	) {
+		let imports = self.checked.saturating_sub(checked_before);
+		let check = start.elapsed().saturating_sub(imports);
+		self.checked += check;
+		if let Some(module) = self.modules.iter_mut().find(|module| module.source == source) {
+			module.check = check;
+		}
+	}
+}
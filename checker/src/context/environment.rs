@@ -1,5 +1,5 @@
 use source_map::{SourceId, Span, SpanWithSource};
-use std::collections::HashSet;
+use std::{borrow::Cow, collections::HashSet};
 
 use crate::{
 	diagnostics::{
@@ -9,29 +9,32 @@ use crate::{
 	events::{ApplicationResult, Event, FinalEvent, RootReference},
 	features::{
 		assignments::{Assignable, AssignmentKind, Reference},
+		iteration::{get_iterated_items, IteratedItems},
 		modules::Exported,
-		objects::SpecialObjects,
+		objects::{is_mutating_method_on_readonly_array, SpecialObjects},
 		operations::{
 			evaluate_logical_operation_with_expression,
 			evaluate_pure_binary_operation_handle_errors, MathematicalAndBitwise,
 		},
 		variables::{VariableMutability, VariableOrImport, VariableWithValue},
 	},
-	subtyping::{type_is_subtype, BasicEquality, SubTypeResult},
+	subtyping::{type_is_subtype, BasicEquality, NonEqualityReason, SubTypeResult},
 	types::{
 		is_type_truthy_falsy,
 		properties::{PropertyKey, PropertyKind, PropertyValue},
-		PolyNature, Type, TypeCombinable, TypeStore,
+		tuples, PolyNature, Type, TypeCombinable, TypeStore,
 	},
-	CheckingData, Decidable, Instance, RootContext, TypeCheckOptions, TypeId,
+	CheckingData, Decidable, Instance, RootContext, TypeCheckOptions, TypeId, VariableId,
 };
 
 use super::{
 	get_on_ctx, get_value_of_variable,
-	information::{merge_info, InformationChain, Publicity},
+	information::{
+		get_property_unbound, merge_info, InformationChain, LocalInformation, Publicity,
+	},
 	invocation::CheckThings,
 	AssignmentError, ClosedOverReferencesInScope, Context, ContextType, Environment,
-	GeneralContext, SetPropertyError,
+	GeneralContext, Logical, SetPropertyError,
 };
 
 pub type ContextLocation = Option<String>;
@@ -208,7 +211,7 @@ pub enum Scope {
 	StaticBlock {
 		this_type: TypeId,
 	},
-	/// For repl only
+	/// For repl and `CommonJS` modules. Variables are lifted into the parent
 	PassThrough {
 		source: SourceId,
 	},
@@ -469,7 +472,46 @@ impl<'a> Environment<'a> {
 				}
 			}
 			Assignable::ObjectDestructuring(_) => todo!(),
-			Assignable::ArrayDestructuring(_) => todo!(),
+			Assignable::ArrayDestructuring(items) => {
+				let value = A::synthesise_expression(
+					expression.unwrap(),
+					TypeId::ANY_TYPE,
+					self,
+					checking_data,
+				);
+				let position = assignment_span.with_source(self.get_source());
+				let iterated = get_iterated_items(value, position, self, checking_data);
+				let tuple = tuples::get_tuple(value, &checking_data.types).cloned();
+
+				for (idx, item) in items.into_iter().enumerate() {
+					let new = match (&tuple, &iterated) {
+						(Some(tuple), _) => tuple.get_element(idx, &mut checking_data.types),
+						(None, IteratedItems::Known(values)) => {
+							values.get(idx).copied().unwrap_or(TypeId::UNDEFINED_TYPE)
+						}
+						(None, IteratedItems::Unknown(item)) => *item,
+					};
+					match item {
+						Some(Assignable::Reference(Reference::Variable(name, position))) => {
+							self.assign_to_variable_handle_errors(
+								name.as_str(),
+								position,
+								new,
+								checking_data,
+							);
+						}
+						Some(_) => {
+							checking_data.raise_unimplemented_error(
+								"nested destructuring assignment",
+								position,
+							);
+						}
+						None => {}
+					}
+				}
+
+				value
+			}
 		}
 	}
 
@@ -552,20 +594,34 @@ impl<'a> Environment<'a> {
 									types,
 								);
 
-								if let SubTypeResult::IsNotSubType(_mismatches) = result {
-									return Err(AssignmentError::DoesNotMeetConstraint {
-										variable_type: TypeStringRepresentation::from_type_id(
-											reassignment_constraint,
-											self,
-											types,
-											false,
-										),
-										value_type: TypeStringRepresentation::from_type_id(
-											new_type, self, types, false,
-										),
-										variable_site,
-										value_site: assignment_position,
-									});
+								if let SubTypeResult::IsNotSubType(reason) = result {
+									let variable_type = TypeStringRepresentation::from_type_id(
+										reassignment_constraint,
+										self,
+										types,
+										false,
+									);
+									let value_type = TypeStringRepresentation::from_type_id(
+										new_type, self, types, false,
+									);
+									let value_site = assignment_position;
+									return Err(
+										if let NonEqualityReason::NoCommonProperties = reason {
+											AssignmentError::NoCommonProperties {
+												variable_type,
+												variable_site,
+												value_type,
+												value_site,
+											}
+										} else {
+											AssignmentError::DoesNotMeetConstraint {
+												variable_type,
+												variable_site,
+												value_type,
+												value_site,
+											}
+										},
+									);
 								}
 							}
 
@@ -594,6 +650,11 @@ impl<'a> Environment<'a> {
 		}
 	}
 
+	/// The variable under `name`, without reading it
+	pub(crate) fn get_variable_id(&self, name: &str) -> Option<VariableId> {
+		self.get_variable_unbound(name).map(|(_, _, variable)| variable.get_id())
+	}
+
 	pub(crate) fn get_root(&self) -> &RootContext {
 		match self.context_type.parent {
 			GeneralContext::Syntax(syntax) => syntax.get_root(),
@@ -688,15 +749,20 @@ impl<'a> Environment<'a> {
 		checking_data: &mut CheckingData<U, A>,
 		site: Span,
 	) -> Result<Instance, ()> {
-		let get_property = self.get_property(
-			on,
-			publicity,
-			key,
-			&mut checking_data.types,
-			None,
-			site,
-			&checking_data.options,
-		);
+		let get_property =
+			if is_mutating_method_on_readonly_array(on, key, self, &checking_data.types) {
+				None
+			} else {
+				self.get_property(
+					on,
+					publicity,
+					key,
+					&mut checking_data.types,
+					None,
+					site,
+					&checking_data.options,
+				)
+			};
 
 		if let Some((kind, result)) = get_property {
 			Ok(match kind {
@@ -727,6 +793,31 @@ impl<'a> Environment<'a> {
 		}
 	}
 
+	/// For properties added to the global object, which can be referenced as variables
+	fn get_global_object_property(&self, name: &str, types: &TypeStore) -> Option<TypeId> {
+		let (_, _, global_object) = self.get_variable_unbound("globalThis")?;
+		let global_object = get_value_of_variable(
+			self,
+			global_object.get_id(),
+			None::<&crate::types::poly_types::FunctionTypeArguments>,
+		)?;
+
+		let property = get_property_unbound(
+			global_object,
+			Publicity::Public,
+			&PropertyKey::String(Cow::Owned(name.to_owned())),
+			types,
+			self,
+		);
+		if let Ok(Logical::Pure(PropertyValue::Value(value))) = property {
+			Some(value)
+		} else {
+			None
+		}
+	}
+
+	/// Returns `Err` with the value if there is no variable under `name`. That is a property of the
+	/// global object (from `globalThis.name = ...`) or [`TypeId::ERROR_TYPE`] if not found
 	pub fn get_variable_handle_error<U: crate::ReadFromFS, A: crate::ASTImplementation>(
 		&mut self,
 		name: &str,
@@ -737,7 +828,16 @@ impl<'a> Environment<'a> {
 			let this = self.get_variable_unbound(name);
 			if let Some((in_root, crossed_boundary, og_var)) = this {
 				(in_root, crossed_boundary, og_var.clone())
+			} else if let Some(og_var) =
+				checking_data.modules.global_declarations.variables.get(name)
+			{
+				// From a `declare global` block. Its value is in the information of the importer
+				(true, None, og_var.clone())
 			} else {
+				if let Some(value) = self.get_global_object_property(name, &checking_data.types) {
+					return Err(value);
+				}
+
 				checking_data.diagnostics_container.add_error(
 					TypeCheckError::CouldNotFindVariable {
 						variable: name,
@@ -750,6 +850,21 @@ impl<'a> Environment<'a> {
 			}
 		};
 
+		crate::features::deprecation::warn_if_deprecated_variable(
+			name,
+			og_var.get_origin_variable_id(),
+			position,
+			checking_data,
+		);
+
+		if checking_data.options.no_unused_bindings {
+			checking_data.destructured_bindings.read.insert(og_var.get_id());
+		}
+
+		if checking_data.options.no_dead_stores {
+			checking_data.dead_stores.read(og_var.get_id());
+		}
+
 		let reference = RootReference::Variable(og_var.get_id());
 
 		if let VariableOrImport::Variable { context: Some(ref context), .. } = og_var {
@@ -808,6 +923,13 @@ impl<'a> Environment<'a> {
 								return Ok(VariableWithValue(og_var.clone(), current_value));
 							} else if let Type::Constant(_) = ty {
 								return Ok(VariableWithValue(og_var.clone(), current_value));
+							} else if crate::features::objects::is_readonly(
+								current_value,
+								self,
+								&checking_data.types,
+							) {
+								// Frozen (and `as const`) objects cannot be changed after this
+								return Ok(VariableWithValue(og_var.clone(), current_value));
 							}
 
 							crate::utils::notify!("Free variable!");
@@ -924,6 +1046,26 @@ impl<'a> Environment<'a> {
 		}
 	}
 
+	/// Variables (in this function) whose current value is `value`
+	pub(crate) fn get_variables_with_value(&self, value: TypeId) -> Vec<VariableId> {
+		let mut variables = Vec::new();
+		for info in self.get_chain_of_info() {
+			for (variable, current_value) in &info.variable_current_value {
+				if *current_value == value
+					&& !variables.contains(variable)
+					&& get_value_of_variable(
+						self,
+						*variable,
+						None::<&crate::types::poly_types::FunctionTypeArguments>,
+					) == Some(value)
+				{
+					variables.push(*variable);
+				}
+			}
+		}
+		variables
+	}
+
 	pub fn new_conditional_context<T, A, R>(
 		&mut self,
 		(condition, pos): (TypeId, Span),
@@ -952,18 +1094,37 @@ impl<'a> Environment<'a> {
 			};
 		}
 
-		let (truthy_result, truthy_info, truthy_state) = {
+		// Variables narrowed in each branch (under `advanced_numbers`)
+		let narrowed = checking_data
+			.options
+			.advanced_numbers
+			.then(|| {
+				crate::features::operations::narrow_bitmask_condition(
+					condition,
+					&mut checking_data.types,
+				)
+			})
+			.flatten()
+			.map(|(value, truthy, falsy)| (self.get_variables_with_value(value), truthy, falsy));
+
+		let (truthy_result, mut truthy_info, truthy_state) = {
 			let mut truthy_environment = self.new_lexical_environment(Scope::Conditional {
 				antecedent: condition,
 				is_switch: None,
 			});
+			if let Some((variables, truthy, _)) = &narrowed {
+				narrow_variables(&mut truthy_environment.info, variables, *truthy, true);
+			}
 			let result = then_evaluate(&mut truthy_environment, checking_data);
 
 			let Context {
 				context_type: Syntax { free_variables, closed_over_references, state, .. },
-				info,
+				mut info,
 				..
 			} = truthy_environment;
+			if let Some((variables, truthy, _)) = &narrowed {
+				narrow_variables(&mut info, variables, *truthy, false);
+			}
 
 			self.context_type.free_variables.extend(free_variables);
 			self.context_type.closed_over_references.extend(closed_over_references);
@@ -971,19 +1132,26 @@ impl<'a> Environment<'a> {
 			(result, info, state)
 		};
 
-		let (falsy_result, falsy_info, falsy_state) = if let Some(else_evaluate) = else_evaluate {
+		let (falsy_result, mut falsy_info, falsy_state) = if let Some(else_evaluate) = else_evaluate
+		{
 			let mut falsy_environment = self.new_lexical_environment(Scope::Conditional {
 				antecedent: checking_data.types.new_logical_negation_type(condition),
 				is_switch: None,
 			});
+			if let Some((variables, _, falsy)) = &narrowed {
+				narrow_variables(&mut falsy_environment.info, variables, *falsy, true);
+			}
 
 			let result = else_evaluate(&mut falsy_environment, checking_data);
 
 			let Context {
 				context_type: Syntax { free_variables, closed_over_references, state, .. },
-				info,
+				mut info,
 				..
 			} = falsy_environment;
+			if let Some((variables, _, falsy)) = &narrowed {
+				narrow_variables(&mut info, variables, *falsy, false);
+			}
 
 			self.context_type.free_variables.extend(free_variables);
 			self.context_type.closed_over_references.extend(closed_over_references);
@@ -993,6 +1161,17 @@ impl<'a> Environment<'a> {
 			(R::default(), None, ApplicationResult::Completed)
 		};
 
+		// Values assigned in a branch that does not complete (it returns, throws or calls a function
+		// that returns `never`) are not the values after the conditional
+		if truthy_state.is_it_so_over() {
+			truthy_info.variable_current_value.clear();
+			if let Some(falsy_info) = &mut falsy_info {
+				self.info.variable_current_value.extend(falsy_info.variable_current_value.drain());
+			}
+		} else if falsy_state.is_it_so_over() {
+			self.info.variable_current_value.extend(truthy_info.variable_current_value.drain());
+		}
+
 		self.context_type.state =
 			ApplicationResult::new_from_unknown_condition(condition, truthy_state, falsy_state);
 
@@ -1219,3 +1398,20 @@ impl<'a> Environment<'a> {
 		None
 	}
 }
+
+/// Sets (or, after the branch, removes) the narrowed value of `variables` in `info`. Variables
+/// assigned to in the branch keep their new value
+fn narrow_variables(
+	info: &mut LocalInformation,
+	variables: &[VariableId],
+	narrowed: TypeId,
+	set: bool,
+) {
+	for variable in variables {
+		if set {
+			info.variable_current_value.insert(*variable, narrowed);
+		} else if info.variable_current_value.get(variable) == Some(&narrowed) {
+			info.variable_current_value.remove(variable);
+		}
+	}
+}
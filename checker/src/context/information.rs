@@ -171,6 +171,13 @@ impl InformationChain for LocalInformation {
 	}
 }
 
+/// For information that is not nested in a context, e.g. a module's and then the root's
+impl<const N: usize> InformationChain for [&LocalInformation; N] {
+	fn get_chain_of_info(&self) -> impl Iterator<Item = &'_ LocalInformation> {
+		self.iter().copied()
+	}
+}
+
 /// Get all properties on a type (for printing and other non-one property uses)
 ///
 /// - TODO make aware of ands and aliases
@@ -28,8 +28,10 @@ use crate::{
 		variables::{VariableMutability, VariableOrImport},
 	},
 	types::{
-		poly_types::generic_type_arguments::StructureGenericArguments, FunctionType, PolyNature,
-		Type, TypeId, TypeStore,
+		poly_types::{
+			check_type_parameter_default, generic_type_arguments::StructureGenericArguments,
+		},
+		FunctionType, PolyNature, Type, TypeId, TypeStore,
 	},
 	CheckingData, DiagnosticsContainer, FunctionId, VariableId,
 };
@@ -144,6 +146,7 @@ impl<'a> From<&'a Environment<'a>> for GeneralContext<'a> {
 	}
 }
 
+#[derive(Default)]
 pub struct Names {
 	pub(crate) variables: HashMap<String, VariableOrImport>,
 	pub(crate) named_types: HashMap<String, TypeId>,
@@ -227,10 +230,10 @@ impl<T: ContextType> Context<T> {
 		let variable = VariableOrImport::Variable { declared_at, mutability, context: None };
 
 		let existing = match self.variables.entry(name.to_owned()) {
-			Entry::Occupied(_) => true,
+			Entry::Occupied(occupied) => Some(occupied.get().get_declared_at()),
 			Entry::Vacant(vacant) => {
 				vacant.insert(variable);
-				false
+				None
 			}
 		};
 
@@ -240,8 +243,8 @@ impl<T: ContextType> Context<T> {
 			self.info.variable_current_value.insert(id, initial_value);
 		}
 
-		if existing {
-			Err(CannotRedeclareVariable { name })
+		if let Some(existing) = existing {
+			Err(CannotRedeclareVariable { name, existing })
 		} else {
 			Ok(())
 		}
@@ -254,10 +257,13 @@ impl<T: ContextType> Context<T> {
 		declared_at: SpanWithSource,
 		diagnostics_container: &mut DiagnosticsContainer,
 	) {
-		if let Err(_err) = self.register_variable(name, declared_at, argument) {
+		if let Err(CannotRedeclareVariable { existing, .. }) =
+			self.register_variable(name, declared_at, argument)
+		{
 			diagnostics_container.add_error(TypeCheckError::CannotRedeclareVariable {
 				name: name.to_owned(),
 				position: declared_at,
+				existing,
 			});
 		}
 	}
@@ -356,7 +362,7 @@ impl<T: ContextType> Context<T> {
 	/// Similar to [`Context::get_this_unbound`]
 	///
 	/// First `bool` is whether this variable is on [`Context<Root>`]
-	fn get_variable_unbound(
+	pub(crate) fn get_variable_unbound(
 		&self,
 		variable_name: &str,
 	) -> Option<(bool, Option<Boundary>, &VariableOrImport)> {
@@ -389,7 +395,10 @@ impl<T: ContextType> Context<T> {
 			let is_dynamic_boundary =
 				self.context_type.as_syntax().and_then(|scope| scope.scope.is_dynamic_boundary());
 
-			if let Some(DynamicBoundaryKind::Loop) = is_dynamic_boundary {
+			// Variables from outside a function are free variables, which have a value by the time
+			// the function is called
+			if let (Some(DynamicBoundaryKind::Loop), None) = (is_dynamic_boundary, parent_boundary)
+			{
 				if !self
 					.get_chain_of_info()
 					.any(|info| info.variable_current_value.contains_key(&var.get_id()))
@@ -422,6 +431,27 @@ impl<T: ContextType> Context<T> {
 		}
 	}
 
+	/// Whether the variable is declared in this context or a parent. Variables local to (already
+	/// synthesised) functions are not
+	pub(crate) fn variable_is_in_scope(&self, id: VariableId) -> bool {
+		self.parents_iter()
+			.any(|env| get_on_ctx!(&env.variables).values().any(|variable| variable.get_id() == id))
+	}
+
+	/// Whether `ty` is the condition of an enclosing branch (so is truthy here). For `x.a` in
+	/// `if (x) { ... }`. Does not look outside of functions
+	pub(crate) fn is_antecedent(&self, ty: TypeId) -> bool {
+		for ctx in self.parents_iter() {
+			let GeneralContext::Syntax(syn) = ctx else { break };
+			match &syn.context_type.scope {
+				Scope::Conditional { antecedent, .. } if *antecedent == ty => return true,
+				Scope::Function(_) => break,
+				_ => {}
+			}
+		}
+		false
+	}
+
 	pub fn as_general_context(&self) -> GeneralContext {
 		T::as_general_context(self)
 	}
@@ -697,7 +727,10 @@ impl<T: ContextType> Context<T> {
 		pos: SpanWithSource,
 		checking_data: &mut CheckingData<U, A>,
 	) -> TypeId {
-		if let Some(val) = self.get_type_from_name(name) {
+		if let Some(val) = self
+			.get_type_from_name(name)
+			.or_else(|| checking_data.modules.global_declarations.named_types.get(name).copied())
+		{
 			val
 		} else {
 			checking_data
@@ -787,6 +820,9 @@ impl<T: ContextType> Context<T> {
 					"Promise" => {
 						return TypeId::PROMISE_TYPE;
 					}
+					"Function" => {
+						return TypeId::FUNCTION_TYPE;
+					}
 					"String" => {
 						return TypeId::STRING_TYPE;
 					}
@@ -831,8 +867,8 @@ impl<T: ContextType> Context<T> {
 		// Doing this as may be a bit faster maybe?
 		let mut env = self.new_lexical_environment(Scope::TypeAlias);
 
-		let (parameters, to) = if let Some(parameters) = parameters {
-			let parameters = parameters
+		let (parameters, to) = if let Some(type_parameters) = parameters {
+			let parameters = type_parameters
 				.iter()
 				.map(|parameter| {
 					let name = A::type_parameter_name(parameter).to_owned();
@@ -845,7 +881,26 @@ impl<T: ContextType> Context<T> {
 					env.named_types.insert(name, ty);
 					ty
 				})
-				.collect();
+				.collect::<Vec<_>>();
+
+			for (parameter, ty) in type_parameters.iter().zip(&parameters) {
+				if let Some(default) = A::type_parameter_default(parameter) {
+					let default = A::synthesise_type_annotation(default, &mut env, checking_data);
+					if let Some(extends) = A::type_parameter_extends(parameter) {
+						let extends =
+							A::synthesise_type_annotation(extends, &mut env, checking_data);
+						check_type_parameter_default(
+							A::type_parameter_name(parameter),
+							extends,
+							default,
+							A::type_parameter_position(parameter).with_source(env.get_source()),
+							&env,
+							checking_data,
+						);
+					}
+					checking_data.types.set_type_parameter_default(*ty, default);
+				}
+			}
 
 			let to = A::synthesise_type_annotation(to, &mut env, checking_data);
 			(Some(parameters), to)
@@ -907,23 +962,25 @@ impl<T: ContextType> Context<T> {
 
 		let kind = VariableMutability::Constant;
 		let variable = VariableOrImport::Variable { declared_at, mutability: kind, context };
-		let entry = self.variables.entry(name.to_owned());
-		if let Entry::Vacant(vacant) = entry {
-			vacant.insert(variable);
+		match self.variables.entry(name.to_owned()) {
+			Entry::Vacant(vacant) => {
+				vacant.insert(variable);
 
-			// TODO unsure ...
-			let ty = if let Type::SpecialObject(SpecialObjects::Function(..)) =
-				types.get_type_by_id(variable_ty)
-			{
-				variable_ty
-			} else {
-				types.register_type(Type::RootPolyType(PolyNature::Open(variable_ty)))
-			};
+				// TODO unsure ...
+				let ty = if let Type::SpecialObject(SpecialObjects::Function(..)) =
+					types.get_type_by_id(variable_ty)
+				{
+					variable_ty
+				} else {
+					types.register_type(Type::RootPolyType(PolyNature::Open(variable_ty)))
+				};
 
-			self.info.variable_current_value.insert(id, ty);
-			Ok(ty)
-		} else {
-			Err(CannotRedeclareVariable { name })
+				self.info.variable_current_value.insert(id, ty);
+				Ok(ty)
+			}
+			Entry::Occupied(occupied) => {
+				Err(CannotRedeclareVariable { name, existing: occupied.get().get_declared_at() })
+			}
 		}
 	}
 
@@ -952,7 +1009,7 @@ impl<T: ContextType> Context<T> {
 						_ => None,
 					}
 				} else {
-					crate::utils::notify!("TODO get root this type, returning ERROR_TYPE for now");
+					crate::utils::notify!(Warn; "TODO get root this type, returning ERROR_TYPE for now");
 					Some(TypeId::ERROR_TYPE)
 				}
 			})
@@ -1077,6 +1134,14 @@ pub enum AssignmentError {
 		value_type: TypeStringRepresentation,
 		value_site: SpanWithSource,
 	},
+	/// [`AssignmentError::DoesNotMeetConstraint`] where `variable_type` only has optional properties
+	/// and the value has none of them
+	NoCommonProperties {
+		variable_type: TypeStringRepresentation,
+		variable_site: SpanWithSource,
+		value_type: TypeStringRepresentation,
+		value_site: SpanWithSource,
+	},
 	PropertyConstraint {
 		property_constraint: TypeStringRepresentation,
 		value_type: TypeStringRepresentation,
@@ -1,15 +1,17 @@
-use super::{ClosedOverReferencesInScope, Context, ContextId, ContextType};
+use super::{
+	information::Publicity, ClosedOverReferencesInScope, Context, ContextId, ContextType, Names,
+};
 use crate::{
 	events::ApplicationResult,
 	features::{
 		modules::{Exported, SynthesisedModule},
 		variables::VariableOrImport,
 	},
-	types::TypeId,
-	CheckingData, GeneralContext,
+	types::{properties::PropertyKey, TypeId, TypeStore},
+	CheckingData, GeneralContext, ModuleEvent, PropertyValue,
 };
-use source_map::SourceId;
-use std::{collections::HashMap, iter::FromIterator, mem};
+use source_map::{FileSystem, Nullable, SourceId, SpanWithSource};
+use std::{borrow::Cow, collections::HashMap, iter::FromIterator, mem};
 
 pub type RootContext = Context<Root>;
 
@@ -56,15 +58,22 @@ impl RootContext {
 			("number".to_owned(), TypeId::NUMBER_TYPE),
 			("string".to_owned(), TypeId::STRING_TYPE),
 			("boolean".to_owned(), TypeId::BOOLEAN_TYPE),
+			("bigint".to_owned(), TypeId::BIGINT_TYPE),
 			("null".to_owned(), TypeId::NULL_TYPE),
 			("undefined".to_owned(), TypeId::UNDEFINED_TYPE),
 			("void".to_owned(), TypeId::VOID_TYPE),
+			("never".to_owned(), TypeId::NEVER_TYPE),
 			("Array".to_owned(), TypeId::ARRAY_TYPE),
 			("Promise".to_owned(), TypeId::PROMISE_TYPE),
 			("Function".to_owned(), TypeId::FUNCTION_TYPE),
 			("object".to_owned(), TypeId::OBJECT_TYPE),
+			("SymbolToPrimitive".to_owned(), TypeId::SYMBOL_TO_PRIMITIVE),
+			("SymbolIterator".to_owned(), TypeId::SYMBOL_ITERATOR),
+			("SymbolHasInstance".to_owned(), TypeId::SYMBOL_HAS_INSTANCE),
 			("Literal".to_owned(), TypeId::LITERAL_RESTRICTION),
 			("Readonly".to_owned(), TypeId::READONLY_RESTRICTION),
+			("Nominal".to_owned(), TypeId::NOMINAL_RESTRICTION),
+			("Not".to_owned(), TypeId::NOT_RESTRICTION),
 		]);
 
 		let mut info = crate::LocalInformation::default();
@@ -97,6 +106,48 @@ impl RootContext {
 		}
 	}
 
+	/// Creates the global object with a property for each global variable and registers it under
+	/// `globalThis`, `window` and `self`. Should be run after the definition files are added
+	pub(crate) fn register_global_object(&mut self, types: &mut TypeStore) {
+		let global_object = self.info.new_object(None, types, false, false);
+
+		let properties = self
+			.variables
+			.iter()
+			.filter_map(|(name, variable)| {
+				let value = self.info.variable_current_value.get(&variable.get_id())?;
+				Some((name.clone(), *value))
+			})
+			.collect::<Vec<_>>();
+
+		for (name, value) in properties {
+			self.info.register_property(
+				global_object,
+				Publicity::Public,
+				PropertyKey::String(Cow::Owned(name)),
+				PropertyValue::Value(value),
+				false,
+				None,
+			);
+		}
+
+		// Different (null source) positions so that the variable ids are different to `undefined`
+		for (idx, name) in ["globalThis", "window", "self"].into_iter().enumerate() {
+			if self.variables.contains_key(name) {
+				continue;
+			}
+			#[allow(clippy::cast_possible_truncation)]
+			let start = idx as u32 + 1;
+			let variable = VariableOrImport::Variable {
+				mutability: crate::features::variables::VariableMutability::Constant,
+				declared_at: SpanWithSource { start, end: start, source: SourceId::NULL },
+				context: None,
+			};
+			self.info.variable_current_value.insert(variable.get_id(), global_object);
+			self.variables.insert(name.to_owned(), variable);
+		}
+	}
+
 	pub fn new_module_context<'a, T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		&self,
 		source: SourceId,
@@ -105,7 +156,23 @@ impl RootContext {
 	) -> &'a SynthesisedModule<A::OwnedModule> {
 		let module_scope = crate::Scope::Module { source, exported: Exported::default() };
 		let mut environment = self.new_lexical_environment(module_scope);
+		let path = checking_data.modules.files.get_file_path(source);
+		checking_data.options.module_progress.emit(ModuleEvent::Started(&path));
+		let start =
+			checking_data.options.measure_time.then(|| checking_data.chronometer.start_check());
+		// Modules can be imported during hoisting, so the mappings of the importer are kept
+		let importer_mappings = mem::take(&mut checking_data.local_type_mappings);
 		A::synthesise_module(&module, source, &mut environment, checking_data);
+		let mappings = mem::replace(&mut checking_data.local_type_mappings, importer_mappings);
+		if let Some(start) = start {
+			checking_data.chronometer.record_check(source, start);
+		}
+		checking_data.options.module_progress.emit(ModuleEvent::Finished(&path));
+
+		// Still synthesised as (without annotations) exports are inferred from the bodies
+		if checking_data.modules.skipped_sources.contains(&source) {
+			checking_data.diagnostics_container.remove_diagnostics_in(source);
+		}
 
 		let crate::Scope::Module { exported, .. } = environment.context_type.scope else {
 			unreachable!()
@@ -114,9 +181,15 @@ impl RootContext {
 		let module = SynthesisedModule {
 			content: A::owned_module_from_module(module),
 			exported,
+			is_common_js: checking_data.modules.common_js_sources.contains(&source),
+			has_top_level_await: checking_data.modules.top_level_await_sources.contains(&source),
 			info: environment.info,
-			// TODO temp
-			mappings: mem::take(&mut checking_data.local_type_mappings),
+			mappings,
+			names: Names {
+				variables: environment.variables,
+				named_types: environment.named_types,
+				variable_names: environment.variable_names,
+			},
 		};
 
 		// TODO better way to do this?
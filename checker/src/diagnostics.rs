@@ -7,14 +7,16 @@ use crate::{
 	diagnostics,
 	types::{printing::print_type_with_type_arguments, GenericChain, GenericChainLink},
 };
-use source_map::{SourceId, SpanWithSource};
+use source_map::{FileSystem, MapFileStore, Nullable, SourceId, SpanWithSource, WithPathMap};
 use std::{
 	fmt::{self, Debug, Display},
 	iter,
 	path::PathBuf,
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, binary_serialize_derive::BinarySerializable,
+)]
 #[cfg_attr(feature = "serde-serialize", derive(serde::Serialize), serde(rename_all = "lowercase"))]
 #[cfg_attr(target_family = "wasm", derive(tsify::Tsify))]
 pub enum DiagnosticKind {
@@ -89,6 +91,28 @@ impl Diagnostic {
 		}
 	}
 
+	#[must_use]
+	pub fn position(&self) -> Option<SpanWithSource> {
+		match self {
+			Diagnostic::Global { .. } => None,
+			Diagnostic::Position { position, .. }
+			| Diagnostic::PositionWithAdditionalLabels { position, .. } => Some(*position),
+		}
+	}
+
+	/// Applies `f` to the reason and the label messages. For translating
+	pub fn map_messages(&mut self, mut f: impl FnMut(&mut String)) {
+		match self {
+			Diagnostic::Global { reason, .. } | Diagnostic::Position { reason, .. } => f(reason),
+			Diagnostic::PositionWithAdditionalLabels { reason, labels, .. } => {
+				f(reason);
+				for (message, _) in labels {
+					f(message);
+				}
+			}
+		}
+	}
+
 	#[must_use]
 	pub fn kind(&self) -> DiagnosticKind {
 		match self {
@@ -135,6 +159,41 @@ impl DiagnosticsContainer {
 		self.has_error
 	}
 
+	/// For files that are skipped
+	pub(crate) fn remove_diagnostics_in(&mut self, source: SourceId) {
+		self.diagnostics.retain(|diagnostic| {
+			!diagnostic.position().is_some_and(|position| position.source == source)
+		});
+		self.has_error =
+			self.diagnostics.iter().any(|diagnostic| diagnostic.kind() == DiagnosticKind::Error);
+	}
+
+	/// Orders by (file path, start, kind, reason) so output does not depend on the order files were checked in.
+	/// Diagnostics without a position go first. Sorting is stable
+	pub fn sort(&mut self, files: &MapFileStore<WithPathMap>) {
+		let key = |diagnostic: &Diagnostic| {
+			diagnostic.position().map(|position| {
+				let path = (position.source != SourceId::NULL)
+					.then(|| files.get_file_path(position.source));
+				(path, position.start)
+			})
+		};
+		self.diagnostics.sort_by(|a, b| {
+			key(a)
+				.cmp(&key(b))
+				.then_with(|| a.kind().cmp(&b.kind()))
+				.then_with(|| a.reason().cmp(b.reason()))
+		});
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+		self.diagnostics.iter()
+	}
+
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Diagnostic> {
+		self.diagnostics.iter_mut()
+	}
+
 	pub fn sources(&self) -> impl Iterator<Item = SourceId> + '_ {
 		self.diagnostics.iter().flat_map(diagnostics::Diagnostic::sources)
 	}
@@ -274,9 +333,10 @@ mod defined_errors_and_warnings {
 	use crate::{
 		context::AssignmentError,
 		features::{modules::CouldNotOpenFile, operations::MathematicalAndBitwise},
-		types::calling::FunctionCallingError,
+		types::calling::{FunctionCallingError, InferredGeneric},
 	};
 	use source_map::SpanWithSource;
+	use std::path::PathBuf;
 
 	use crate::Diagnostic;
 
@@ -363,6 +423,8 @@ mod defined_errors_and_warnings {
 		CannotRedeclareVariable {
 			name: String,
 			position: SpanWithSource,
+			/// Of the first declaration
+			existing: SpanWithSource,
 		},
 		// TODO parameter position
 		GenericArgumentDoesNotMeetRestriction {
@@ -373,6 +435,15 @@ mod defined_errors_and_warnings {
 		NotDefinedOperator(&'static str, SpanWithSource),
 		PropertyNotWriteable(SpanWithSource),
 		NotTopLevelImport(SpanWithSource),
+		/// `await` outside of an `async` function or the top level of an (ES) module
+		AwaitInNonAsyncContext(SpanWithSource),
+		/// `Nominal` without a type and a string literal name
+		InvalidNominalType(SpanWithSource),
+		/// From `declare module "..." { }` where the module has not been imported (so can't be augmented)
+		CannotAugmentNonImportedModule {
+			module: &'a str,
+			position: SpanWithSource,
+		},
 		DoubleDefaultExport(SpanWithSource),
 		CannotOpenFile {
 			file: CouldNotOpenFile,
@@ -402,6 +473,16 @@ mod defined_errors_and_warnings {
 			from: TypeStringRepresentation,
 			to: TypeStringRepresentation,
 		},
+		/// For `for of` on a value without a `[Symbol.iterator]` method
+		NotIterable {
+			iterable: TypeStringRepresentation,
+			position: SpanWithSource,
+		},
+		InvalidDefaultParameter {
+			expected: TypeStringRepresentation,
+			found: TypeStringRepresentation,
+			position: SpanWithSource,
+		},
 		/// TODO Position = Function body position. Could it be better
 		/// TODO maybe warning?
 		UnreachableVariableClosedOver(String, SpanWithSource),
@@ -417,6 +498,31 @@ mod defined_errors_and_warnings {
 			base: TypeStringRepresentation,
 			overload: TypeStringRepresentation,
 		},
+		/// Methods and properties (getters and setters can be paired)
+		DuplicateClassMember {
+			name: String,
+			position: SpanWithSource,
+			existing: SpanWithSource,
+		},
+		/// Overloads and the implementation of a function must have the same name
+		FunctionImplementationNameMismatch {
+			expected: String,
+			position: SpanWithSource,
+			overload_position: SpanWithSource,
+		},
+		/// Implicit conversions that throw a `TypeError` (symbols to strings, `bigint`s to numbers)
+		CannotConvert {
+			operand: TypeStringRepresentation,
+			to: &'static str,
+			position: SpanWithSource,
+		},
+		/// `<T extends C = D>` where `D` is not assignable to `C`
+		InvalidDefaultTypeParameter {
+			parameter: String,
+			default: TypeStringRepresentation,
+			constraint: TypeStringRepresentation,
+			position: SpanWithSource,
+		},
 	}
 
 	impl From<TypeCheckError<'_>> for Diagnostic {
@@ -455,6 +561,7 @@ mod defined_errors_and_warnings {
 						argument_position,
 						parameter_position,
 						restriction,
+						inferred,
 					} => {
 						if let Some((restriction_pos, restriction)) = restriction {
 							Diagnostic::PositionWithAdditionalLabels {
@@ -471,15 +578,30 @@ mod defined_errors_and_warnings {
 								kind,
 							}
 						} else {
+							let mut labels = vec![(
+								format!("Parameter has type {parameter_type}"),
+								Some(parameter_position),
+							)];
+							if let Some(InferredGeneric { constraint, inferred_from }) = inferred {
+								labels.push((
+									format!("{parameter_type} is constrained to {constraint}"),
+									None,
+								));
+								if let Some((inferred, position)) = inferred_from {
+									labels.push((
+										format!(
+											"{parameter_type} was inferred as {inferred} from this argument"
+										),
+										Some(position),
+									));
+								}
+							}
 							Diagnostic::PositionWithAdditionalLabels {
 								reason: format!(
 									"Argument of type {argument_type} is not assignable to parameter of type {parameter_type}",
 								),
 								position: argument_position,
-								labels: vec![(
-									format!("Parameter has type {parameter_type}"),
-									Some(parameter_position),
-								)],
+								labels,
 								kind,
 							}
 						}
@@ -502,6 +624,19 @@ mod defined_errors_and_warnings {
 							kind,
 						}
 					}
+					FunctionCallingError::SpreadMayNotCoverParameter {
+						parameter_position,
+						argument_position,
+					} => Diagnostic::PositionWithAdditionalLabels {
+						reason: "Spread argument of unknown length might not cover required parameter"
+							.into(),
+						position: argument_position,
+						kind,
+						labels: vec![(
+							"(non-optional) Parameter declared here".into(),
+							Some(parameter_position),
+						)],
+					},
 					FunctionCallingError::NotCallable { calling, call_site } => {
 						Diagnostic::Position {
 							reason: format!("Cannot call type {calling}"),
@@ -538,6 +673,15 @@ mod defined_errors_and_warnings {
 							Some(position),
 						)],
 					},
+					FunctionCallingError::AssignmentTDZ { error: TDZ { position, variable_name }, call_site } => Diagnostic::PositionWithAdditionalLabels {
+						reason: format!("Cannot assign to '{variable_name}' before declaration"),
+						position: call_site.unwrap(),
+						kind,
+						labels: vec![(
+							"Variable assigned here".to_owned(),
+							Some(position),
+						)],
+					},
 					FunctionCallingError::SetPropertyConstraint { property_type, value_type, assignment_position, call_site } => Diagnostic::PositionWithAdditionalLabels {
 						reason: "Invalid assignment to parameter".to_owned(),
 						position: call_site.unwrap(),
@@ -576,6 +720,22 @@ mod defined_errors_and_warnings {
 						)],
 						kind,
 					},
+					AssignmentError::NoCommonProperties {
+						variable_type,
+						variable_site,
+						value_type,
+						value_site,
+					} => Diagnostic::PositionWithAdditionalLabels {
+						reason: format!(
+							"Type {value_type} has no properties in common with weak type {variable_type}",
+						),
+						position: value_site,
+						labels: vec![(
+							format!("Variable declared with type {variable_type}"),
+							Some(variable_site),
+						)],
+						kind,
+					},
 					AssignmentError::PropertyConstraint {
 						property_constraint: property_type,
 						value_type,
@@ -705,10 +865,11 @@ mod defined_errors_and_warnings {
 					position: at,
 					kind,
 				},
-				TypeCheckError::CannotRedeclareVariable { name, position } => {
-					Diagnostic::Position {
+				TypeCheckError::CannotRedeclareVariable { name, position, existing } => {
+					Diagnostic::PositionWithAdditionalLabels {
 						reason: format!("Cannot redeclare variable '{name}'"),
 						position,
+						labels: vec![(format!("'{name}' first declared here"), Some(existing))],
 						kind,
 					}
 				}
@@ -738,6 +899,23 @@ mod defined_errors_and_warnings {
 					position,
 					kind,
 				},
+				TypeCheckError::AwaitInNonAsyncContext(position) => Diagnostic::Position {
+					reason: "Cannot use await in non-async context".to_owned(),
+					position,
+					kind,
+				},
+				TypeCheckError::InvalidNominalType(position) => Diagnostic::Position {
+					reason: "Nominal takes a type and a string literal name".to_owned(),
+					position,
+					kind,
+				},
+				TypeCheckError::CannotAugmentNonImportedModule { module, position } => {
+					Diagnostic::Position {
+						reason: format!("Cannot augment module '{module}' as it has not been imported"),
+						position,
+						kind,
+					}
+				}
 				TypeCheckError::DoubleDefaultExport(_) => todo!(),
 				TypeCheckError::CannotOpenFile { file, position } => if let Some(position) = position {
 					Diagnostic::Position {
@@ -792,6 +970,20 @@ mod defined_errors_and_warnings {
 						kind,
 					}
 				}
+				TypeCheckError::NotIterable { iterable, position } => Diagnostic::Position {
+					reason: format!("{iterable} is not iterable"),
+					position,
+					kind,
+				},
+				TypeCheckError::InvalidDefaultParameter { expected, found, position } => {
+					Diagnostic::Position {
+						reason: format!(
+							"Default value {found} is not assignable to parameter of type {expected}"
+						),
+						position,
+						kind,
+					}
+				}
 				TypeCheckError::UnreachableVariableClosedOver(name, function_position) => {
 					Diagnostic::Position {
 						reason: format!("Function contains unreachable closed over variable '{name}'"),
@@ -821,6 +1013,41 @@ mod defined_errors_and_warnings {
 				position: overload_position,
 				kind,
 			},
+				TypeCheckError::DuplicateClassMember { name, position, existing } => {
+					Diagnostic::PositionWithAdditionalLabels {
+						reason: format!("Duplicate class member '{name}'"),
+						position,
+						labels: vec![(format!("'{name}' first declared here"), Some(existing))],
+						kind,
+					}
+				}
+				TypeCheckError::FunctionImplementationNameMismatch {
+					expected,
+					position,
+					overload_position,
+				} => Diagnostic::PositionWithAdditionalLabels {
+					reason: format!("Function implementation name must be '{expected}'"),
+					position,
+					labels: vec![("Overload declared here".to_owned(), Some(overload_position))],
+					kind,
+				},
+				TypeCheckError::CannotConvert { operand, to, position } => Diagnostic::Position {
+					reason: format!("Cannot implicitly convert {operand} to a {to}"),
+					position,
+					kind,
+				},
+				TypeCheckError::InvalidDefaultTypeParameter {
+					parameter,
+					default,
+					constraint,
+					position,
+				} => Diagnostic::Position {
+					reason: format!(
+						"Default type {default} of {parameter} is not assignable to its constraint {constraint}"
+					),
+					position,
+					kind,
+				},
 			}
 		}
 	}
@@ -851,6 +1078,96 @@ mod defined_errors_and_warnings {
 		},
 		InvalidOrUnimplementedDefinitionFileItem(SpanWithSource),
 		Unreachable(SpanWithSource),
+		InvalidUtf8 {
+			path: PathBuf,
+		},
+		FileTooLarge {
+			path: PathBuf,
+			size: usize,
+			maximum: usize,
+		},
+		/// From [`crate::TypeCheckOptions::no_error_any_propagation`]
+		AnyFromError {
+			variable: String,
+			position: SpanWithSource,
+		},
+		/// From [`crate::TypeCheckOptions::no_missing_import_any`]
+		AnyFromMissingImport {
+			variable: String,
+			position: SpanWithSource,
+		},
+		/// From [`crate::TypeCheckOptions::no_explicit_any`]
+		ExplicitAny(SpanWithSource),
+		/// Use of an item marked with `@deprecated`
+		Deprecated {
+			name: String,
+			message: String,
+			position: SpanWithSource,
+		},
+		/// From [`crate::TypeCheckOptions::no_unused_bindings`]
+		UnusedBinding {
+			name: String,
+			position: SpanWithSource,
+		},
+		/// From [`crate::TypeCheckOptions::no_floating_promises`]
+		FloatingPromise(SpanWithSource),
+		/// Only the last value of the key is used
+		DuplicateObjectKey {
+			key: String,
+			position: SpanWithSource,
+			existing: SpanWithSource,
+		},
+		/// The case can never be reached as a previous case has the same value
+		DuplicateSwitchCase {
+			value: TypeStringRepresentation,
+			position: SpanWithSource,
+			existing: SpanWithSource,
+		},
+		/// The previous cases match every member of the (union) type of the discriminant
+		UnreachableSwitchCase {
+			discriminant: TypeStringRepresentation,
+			position: SpanWithSource,
+		},
+		/// A non-empty case that continues into the next case, without a `falls through` comment
+		SwitchCaseFallthrough(SpanWithSource),
+		/// From [`crate::TypeCheckOptions::advanced_numbers`]. `value & mask` is always `0`
+		ImpossibleBitmask {
+			value: TypeStringRepresentation,
+			mask: TypeStringRepresentation,
+			position: SpanWithSource,
+		},
+		/// An object (without a `[Symbol.toPrimitive]` method) or a non-number used where a primitive
+		/// or number is expected, e.g. `[] + 1` and `+"2"`
+		ImplicitConversion {
+			operand: TypeStringRepresentation,
+			to: &'static str,
+			operator: &'static str,
+			position: SpanWithSource,
+		},
+		/// Relational operators on operands that are not both numbers or both strings
+		IncompatibleComparison {
+			lhs: TypeStringRepresentation,
+			rhs: TypeStringRepresentation,
+			operator: &'static str,
+			position: SpanWithSource,
+		},
+		/// `===` or `!==` on operands with types that have no values in common
+		NoOverlapInEquality {
+			lhs: TypeStringRepresentation,
+			rhs: TypeStringRepresentation,
+			operator: &'static str,
+			result: bool,
+			position: SpanWithSource,
+		},
+		/// From [`crate::TypeCheckOptions::no_dead_stores`]. The assigned value is never read
+		DeadStore {
+			name: String,
+			position: SpanWithSource,
+			overwritten_at: SpanWithSource,
+		},
+		/// From [`crate::TypeCheckOptions::no_dead_stores`]. An expression statement without side
+		/// effects
+		UnusedExpression(SpanWithSource),
 	}
 
 	impl From<TypeCheckWarning> for Diagnostic {
@@ -912,6 +1229,137 @@ mod defined_errors_and_warnings {
 					position,
 					kind,
 				},
+				TypeCheckWarning::InvalidUtf8 { path } => Diagnostic::Global {
+					reason: format!(
+						"{} is not valid UTF-8, invalid sequences have been replaced",
+						path.display()
+					),
+					kind,
+				},
+				TypeCheckWarning::FileTooLarge { path, size, maximum } => Diagnostic::Global {
+					reason: format!(
+						"Skipped {} as it is {size} bytes, which is over the maximum of {maximum} bytes",
+						path.display()
+					),
+					kind,
+				},
+				TypeCheckWarning::AnyFromError { variable, position } => Diagnostic::Position {
+					reason: format!("'{variable}' is any because of a previous error"),
+					position,
+					kind,
+				},
+				TypeCheckWarning::AnyFromMissingImport { variable, position } => {
+					Diagnostic::Position {
+						reason: format!("'{variable}' is any as its module could not be imported"),
+						position,
+						kind,
+					}
+				}
+				TypeCheckWarning::ExplicitAny(position) => Diagnostic::Position {
+					reason: "Explicit 'any' annotation".to_owned(),
+					position,
+					kind,
+				},
+				TypeCheckWarning::Deprecated { name, message, position } => Diagnostic::Position {
+					reason: if message.is_empty() {
+						format!("'{name}' is deprecated")
+					} else {
+						format!("'{name}' is deprecated: {message}")
+					},
+					position,
+					kind,
+				},
+				TypeCheckWarning::UnusedBinding { name, position } => Diagnostic::Position {
+					reason: format!("'{name}' is declared but never read"),
+					position,
+					kind,
+				},
+				TypeCheckWarning::FloatingPromise(position) => Diagnostic::Position {
+					reason: "Promise is not awaited or handled".to_owned(),
+					position,
+					kind,
+				},
+				TypeCheckWarning::DuplicateObjectKey { key, position, existing } => {
+					Diagnostic::PositionWithAdditionalLabels {
+						reason: format!("Duplicate key '{key}' in object literal"),
+						position,
+						labels: vec![(
+							format!("'{key}' first set here. The last value is used"),
+							Some(existing),
+						)],
+						kind,
+					}
+				}
+				TypeCheckWarning::DuplicateSwitchCase { value, position, existing } => {
+					Diagnostic::PositionWithAdditionalLabels {
+						reason: format!("Duplicate case {value} in switch statement"),
+						position,
+						labels: vec![(format!("{value} first matched here"), Some(existing))],
+						kind,
+					}
+				}
+				TypeCheckWarning::UnreachableSwitchCase { discriminant, position } => {
+					Diagnostic::Position {
+						reason: format!(
+							"Case is unreachable as previous cases match every value of {discriminant}"
+						),
+						position,
+						kind,
+					}
+				}
+				TypeCheckWarning::ImpossibleBitmask { value, mask, position } => {
+					Diagnostic::Position {
+						reason: format!(
+							"{value} has no bits in common with mask {mask}, so the result is always 0"
+						),
+						position,
+						kind,
+					}
+				}
+				TypeCheckWarning::SwitchCaseFallthrough(position) => Diagnostic::Position {
+					reason: "Case falls through to the next case. Add a break or a '// falls through' comment".to_owned(),
+					position,
+					kind,
+				},
+				TypeCheckWarning::ImplicitConversion { operand, to, operator, position } => {
+					Diagnostic::Position {
+						reason: format!("{operand} is implicitly converted to a {to} by `{operator}`"),
+						position,
+						kind,
+					}
+				}
+				TypeCheckWarning::IncompatibleComparison { lhs, rhs, operator, position } => {
+					Diagnostic::Position {
+						reason: format!("{lhs} and {rhs} are not comparable with `{operator}`"),
+						position,
+						kind,
+					}
+				}
+				TypeCheckWarning::NoOverlapInEquality { lhs, rhs, operator, result, position } => {
+					Diagnostic::Position {
+						reason: format!(
+							"{lhs} and {rhs} have no overlap, so `{operator}` is always {result}"
+						),
+						position,
+						kind,
+					}
+				}
+				TypeCheckWarning::DeadStore { name, position, overwritten_at } => {
+					Diagnostic::PositionWithAdditionalLabels {
+						reason: format!("Value assigned to '{name}' is never read"),
+						position,
+						labels: vec![(
+							"Overwritten here before being read".to_owned(),
+							Some(overwritten_at),
+						)],
+						kind,
+					}
+				}
+				TypeCheckWarning::UnusedExpression(position) => Diagnostic::Position {
+					reason: "Value of expression is not used".to_owned(),
+					position,
+					kind,
+				},
 			}
 		}
 	}
@@ -951,4 +1399,6 @@ impl From<EntryPointNotFound> for Diagnostic {
 #[derive(Debug)]
 pub struct CannotRedeclareVariable<'a> {
 	pub name: &'a str,
+	/// Of the first declaration
+	pub existing: SpanWithSource,
 }
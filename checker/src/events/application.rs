@@ -13,7 +13,7 @@ use crate::{
 		objects::SpecialObjects,
 	},
 	types::{
-		calling::FunctionCallingError,
+		calling::{CalledWithNew, FunctionCallingError},
 		functions::SynthesisedArgument,
 		get_constraint, is_type_truthy_falsy,
 		poly_types::FunctionTypeArguments,
@@ -68,6 +68,20 @@ pub(crate) fn apply_event(
 			}
 		}
 		Event::SetsVariable(variable, value, position) => {
+			// Variables local to the function are not in scope here, so only free variables (that are
+			// declared later in the scope of the call) are checked
+			if environment.variable_is_in_scope(variable)
+				&& get_value_of_variable(environment, variable, Some(&*type_arguments)).is_none()
+			{
+				errors.errors.push(crate::types::calling::FunctionCallingError::AssignmentTDZ {
+					error: TDZ {
+						variable_name: environment.get_variable_name(variable).to_owned(),
+						position,
+					},
+					call_site: None,
+				});
+			}
+
 			let new_value = substitute(value, type_arguments, environment, types);
 
 			// TODO temp assigns to many contexts, which is bad.
@@ -95,9 +109,24 @@ pub(crate) fn apply_event(
 				under @ PropertyKey::String(_) => under,
 			};
 
-			let Some((_, value)) =
+			// The get was checked when synthesised, which may have been in a branch where `on` is
+			// not `undefined`
+			let value =
 				get_property(on, publicity, &under, None, environment, target, types, position)
-			else {
+					.or_else(|| {
+						let on = crate::types::remove_undefined(on, types)?;
+						get_property(
+							on,
+							publicity,
+							&under,
+							None,
+							environment,
+							target,
+							types,
+							position,
+						)
+					});
+			let Some((_, value)) = value else {
 				panic!(
 					"could not get property {under:?} at {position:?} on {}, (inference or some checking failed)",
 					print_type(on, types, environment, true)
@@ -217,6 +246,17 @@ pub(crate) fn apply_event(
 
 			// crate::utils::notify!("was {:?} now {:?}", was, on);
 
+			// For `super` calls (e.g. in mixins), `this` is the object created in this application
+			let called_with_new = match called_with_new {
+				CalledWithNew::New { on } => {
+					CalledWithNew::New { on: substitute(on, type_arguments, environment, types) }
+				}
+				CalledWithNew::SpecialSuperCall { this_type } => CalledWithNew::SpecialSuperCall {
+					this_type: substitute(this_type, type_arguments, environment, types),
+				},
+				CalledWithNew::None => CalledWithNew::None,
+			};
+
 			let with = with
 				.iter()
 				.map(|argument| SynthesisedArgument {
@@ -252,7 +292,7 @@ pub(crate) fn apply_event(
 							}
 						}
 						Err(mut calling_errors) => {
-							crate::utils::notify!("inference and or checking failed at function");
+							crate::utils::notify!(Trace; "inference and or checking failed at function");
 							errors.errors.append(&mut calling_errors);
 							if let Some(reflects_dependency) = reflects_dependency {
 								type_arguments.set_id_from_event_application(
@@ -386,7 +426,7 @@ pub(crate) fn apply_event(
 						if let (ApplicationResult::Interrupt(_), ApplicationResult::Completed) =
 							(&*truthy, &*otherwise)
 						{
-							crate::utils::notify!("Here left interrupt, right completed");
+							crate::utils::notify!(Trace; "Here left interrupt, right completed");
 
 							// Evaluate the rest of the events conditionally
 							while let Some(event) = rest_of_events.next() {
@@ -413,7 +453,7 @@ pub(crate) fn apply_event(
 								}
 							}
 
-							crate::utils::notify!("Rest of events did not exit");
+							crate::utils::notify!(Trace; "Rest of events did not exit");
 							if let ApplicationResult::Interrupt(i) = *truthy {
 								truthy_info.events.push(i.into());
 							}
@@ -424,7 +464,7 @@ pub(crate) fn apply_event(
 
 							ApplicationResult::Conditionally { on, truthy, otherwise }
 						} else {
-							crate::utils::notify!("TODO here in unknown conditional");
+							crate::utils::notify!(Trace; "TODO here in unknown conditional");
 
 							if let ApplicationResult::Interrupt(i) = *truthy {
 								truthy_info.events.push(i.into());
@@ -482,7 +522,8 @@ pub(crate) fn apply_event(
 				}
 				FinalEvent::Throw { thrown, position } => {
 					let substituted_thrown = substitute(thrown, type_arguments, environment, types);
-					if target.in_unconditional() {
+					// `never` is from calling a function that returns `never`, which is not reported
+					if target.in_unconditional() && substituted_thrown != TypeId::NEVER_TYPE {
 						let value = TypeStringRepresentation::from_type_id(
 							substituted_thrown,
 							// TODO is this okay?
@@ -514,7 +555,16 @@ pub(crate) fn apply_event(
 			// TODO
 			let is_under_dyn = true;
 
+			// Without `new` (`new.target` is `undefined`) the prototype of the `this` object is
+			// not known. The object is not used in that case (`this` is the free variable)
+			let called_without_new = is_function_this
+				&& type_arguments.local_arguments.get(&TypeId::NEW_TARGET_ARG)
+					== Some(&TypeId::UNDEFINED_TYPE);
+
 			let new_object_id = match prototype {
+				PrototypeArgument::Yeah(_) if called_without_new => target
+					.get_latest_info(environment)
+					.new_object(None, types, is_under_dyn, is_function_this),
 				PrototypeArgument::Yeah(prototype) => {
 					let prototype = substitute(prototype, type_arguments, environment, types);
 					target.get_latest_info(environment).new_object(
@@ -603,7 +653,7 @@ pub(crate) fn apply_event_unknown(
 				(reflects_dependency, reference)
 			{
 				// TODO this is okay for loops, not sure about other cases of this function
-				crate::utils::notify!("Setting loop variable here {:?}", reflects_dependency);
+				crate::utils::notify!(Trace; "Setting loop variable here {:?}", reflects_dependency);
 				target
 					.get_latest_info(environment)
 					.variable_current_value
@@ -611,10 +661,10 @@ pub(crate) fn apply_event_unknown(
 			}
 		}
 		Event::Getter { .. } => {
-			crate::utils::notify!("Run getters");
+			crate::utils::notify!(Trace; "Run getters");
 		}
 		Event::SetsVariable(_variable, _value, _) => {
-			crate::utils::notify!("Here");
+			crate::utils::notify!(Trace; "Here");
 			// let new_value = get_constraint(value, types)
 			// 	.map(|value| {
 			// 		types.register_type(Type::RootPolyType(crate::types::PolyNature::Open(value)))
@@ -649,7 +699,7 @@ pub(crate) fn apply_event_unknown(
 			environment.info.register_property(on, publicity, under, new_value, false, position);
 		}
 		Event::CallsType { .. } => {
-			crate::utils::notify!("TODO ?");
+			crate::utils::notify!(Trace; "TODO ?");
 		}
 		Event::Conditionally { true_events, else_events, .. } => {
 			// TODO think this is correct...?
@@ -0,0 +1,135 @@
+//! Short explanations of diagnostics, for `ezno explain <code>`.
+//!
+//! Diagnostics do not carry a code, so [`Explanation::matches`] picks out diagnostics by their reason
+
+use crate::Diagnostic;
+
+pub struct Explanation {
+	/// `EZ1xxx` for errors, `EZ2xxx` for warnings
+	pub code: &'static str,
+	pub title: &'static str,
+	pub explanation: &'static str,
+	/// TypeScript that raises the diagnostic
+	pub example: &'static str,
+	pub matches: fn(&str) -> bool,
+}
+
+pub const EXPLANATIONS: &[Explanation] = &[
+	Explanation {
+		code: "EZ1001",
+		title: "Could not find variable",
+		explanation: "The variable is not declared in this scope or any of its parents. Check the spelling or add an import or declaration",
+		example: "const a = 2;\nconsole.log(b);",
+		matches: |reason| reason.starts_with("Could not find variable"),
+	},
+	Explanation {
+		code: "EZ1002",
+		title: "Type is not assignable",
+		explanation: "The value does not meet the type annotation of the variable or property it is assigned to. Either change the value or widen the annotation",
+		example: "const a: number = \"hello\";",
+		matches: |reason| reason.starts_with("Type ") && reason.contains(" is not assignable to type "),
+	},
+	Explanation {
+		code: "EZ1003",
+		title: "Argument is not assignable to parameter",
+		explanation: "The argument does not meet the type of the parameter in the called function's signature",
+		example: "function func(a: number) {}\nfunc(\"hello\");",
+		matches: |reason| {
+			reason.starts_with("Argument of type ") && reason.contains(" is not assignable to parameter")
+		},
+	},
+	Explanation {
+		code: "EZ1004",
+		title: "Missing or excess argument",
+		explanation: "The function is called with fewer arguments than it has required parameters, or more arguments than it has parameters",
+		example: "function func(a: number) {}\nfunc();\nfunc(1, 2);",
+		matches: |reason| matches!(reason, "Missing argument" | "Excess argument"),
+	},
+	Explanation {
+		code: "EZ1005",
+		title: "Property does not exist",
+		explanation: "The property is not defined on the type. Check the spelling or add the property to the type",
+		example: "const obj = { a: 2 };\nobj.b;",
+		matches: |reason| reason.starts_with("No property "),
+	},
+	Explanation {
+		code: "EZ1006",
+		title: "`satisfies` is not met",
+		explanation: "The expression before `satisfies` is not a subtype of the type after it",
+		example: "2 satisfies string;",
+		matches: |reason| reason.starts_with("Expected ") && reason.contains(", found "),
+	},
+	Explanation {
+		code: "EZ1007",
+		title: "Cannot assign to constant",
+		explanation: "Variables declared with `const` cannot be reassigned. Use `let` if the variable needs to change",
+		example: "const a = 2;\na = 3;",
+		matches: |reason| reason == "Cannot assign to constant",
+	},
+	Explanation {
+		code: "EZ1008",
+		title: "Variable used before declaration",
+		explanation: "`let`, `const` and `class` declarations cannot be used before the line they are declared on (the temporal dead zone)",
+		example: "console.log(a);\nconst a = 2;",
+		matches: |reason| reason.ends_with("before declaration"),
+	},
+	Explanation {
+		code: "EZ1009",
+		title: "`await` outside of an async context",
+		explanation: "`await` can only be used in `async` functions and at the top level of modules",
+		example: "function func() {\n\tawait fetch(\"/\");\n}",
+		matches: |reason| reason == "Cannot use await in non-async context",
+	},
+	Explanation {
+		code: "EZ1010",
+		title: "Cannot redeclare variable",
+		explanation: "A variable with the same name is already declared in this scope. Rename one of them",
+		example: "let a = 2;\nlet a = 3;",
+		matches: |reason| reason.starts_with("Cannot redeclare variable"),
+	},
+	Explanation {
+		code: "EZ2001",
+		title: "Unused binding",
+		explanation: "The variable is declared but never read. Remove it or prefix its name with `_`. Only raised with `--no-unused-bindings`",
+		example: "const a = 2;",
+		matches: |reason| reason.ends_with("is declared but never read"),
+	},
+	Explanation {
+		code: "EZ2002",
+		title: "Unreachable statement",
+		explanation: "The statement is after a `return`, `throw`, `break` or `continue` and so is never run",
+		example: "function func() {\n\treturn 2;\n\tconsole.log(\"here\");\n}",
+		matches: |reason| reason == "Unreachable statement",
+	},
+	Explanation {
+		code: "EZ2003",
+		title: "Unnecessary `await`",
+		explanation: "The awaited value is not a promise, so the `await` does nothing",
+		example: "async function func() {\n\tawait 2;\n}",
+		matches: |reason| reason == "Unnecessary await expression / type is not promise",
+	},
+	Explanation {
+		code: "EZ2004",
+		title: "Floating promise",
+		explanation: "The promise is not awaited, returned or stored, so errors in it are not handled. Add `await`, `.catch(...)` or `void` (if it is intentional). Only raised with `--no-floating-promises`",
+		example: "fetch(\"/\");",
+		matches: |reason| reason == "Promise is not awaited or handled",
+	},
+];
+
+/// Case insensitive
+#[must_use]
+pub fn get_explanation(code: &str) -> Option<&'static Explanation> {
+	EXPLANATIONS.iter().find(|explanation| explanation.code.eq_ignore_ascii_case(code))
+}
+
+impl Diagnostic {
+	/// The code of the [`Explanation`] for this diagnostic
+	#[must_use]
+	pub fn code(&self) -> Option<&'static str> {
+		EXPLANATIONS
+			.iter()
+			.find(|explanation| (explanation.matches)(self.reason()))
+			.map(|explanation| explanation.code)
+	}
+}
@@ -1,21 +1,32 @@
+use std::{collections::HashMap, panic::RefUnwindSafe, sync::Arc};
+
 use crate::{
-	context::{get_on_ctx, information::InformationChain},
+	context::{
+		get_on_ctx,
+		information::{InformationChain, Publicity},
+	},
 	// subtyping::check_satisfies,
 	types::{
 		functions::SynthesisedArgument,
-		printing::{debug_effects, print_type},
-		Constructor, FunctionEffect, StructureGenerics, Type, TypeRestrictions, TypeStore,
+		get_larger_type,
+		printing::{debug_function_effect, print_type},
+		properties::{PropertyKey, PropertyValue},
+		Constructor, StructureGenerics, Type, TypeRestrictions, TypeStore,
 	},
 	Constant,
 	Environment,
 	TypeId,
 };
 
-use super::{functions::ThisValue, objects::SpecialObjects};
+use super::{
+	functions::ThisValue,
+	objects::{ObjectBuilder, SpecialObjects},
+};
 
 // TODO ...
-pub(crate) enum ConstantOutput {
+pub enum ConstantOutput {
 	Value(TypeId),
+	/// Raised as an info diagnostic at the call site. The call returns `undefined`
 	Diagnostic(String),
 }
 
@@ -25,6 +36,77 @@ pub enum ConstantFunctionError {
 	BadCall,
 }
 
+/// Logic for a function marked with `@Constant` (or `@constant("name")`) in a definition file.
+/// Only called when none of the arguments are dependent
+pub trait ConstantFunction {
+	/// Return [`ConstantFunctionError::BadCall`] to fall back to the return type in the definition
+	fn call(
+		&self,
+		this_argument: ThisValue,
+		arguments: &[SynthesisedArgument],
+		types: &mut TypeStore,
+		environment: &mut Environment,
+	) -> Result<ConstantOutput, ConstantFunctionError>;
+}
+
+impl<F> ConstantFunction for F
+where
+	F: Fn(
+		ThisValue,
+		&[SynthesisedArgument],
+		&mut TypeStore,
+		&mut Environment,
+	) -> Result<ConstantOutput, ConstantFunctionError>,
+{
+	fn call(
+		&self,
+		this_argument: ThisValue,
+		arguments: &[SynthesisedArgument],
+		types: &mut TypeStore,
+		environment: &mut Environment,
+	) -> Result<ConstantOutput, ConstantFunctionError> {
+		self(this_argument, arguments, types, environment)
+	}
+}
+
+/// A registered [`ConstantFunction`]. Thread safe (and unwind safe) so that
+/// [`crate::TypeCheckOptions`] can be sent between threads and used across `catch_unwind`
+type RegisteredConstantFunction = Arc<dyn ConstantFunction + Send + Sync + RefUnwindSafe>;
+
+/// Constant functions registered by the embedder (via [`crate::TypeCheckOptions::constant_functions`]).
+/// These are used for identifiers that do not have built-in logic
+#[derive(Default, Clone)]
+pub struct ConstantFunctionRegistry(HashMap<String, RegisteredConstantFunction>);
+
+impl ConstantFunctionRegistry {
+	pub fn register(
+		&mut self,
+		identifier: impl Into<String>,
+		function: impl ConstantFunction + Send + Sync + RefUnwindSafe + 'static,
+	) {
+		self.0.insert(identifier.into(), Arc::new(function));
+	}
+
+	pub(crate) fn get(&self, identifier: &str) -> Option<RegisteredConstantFunction> {
+		self.0.get(identifier).cloned()
+	}
+}
+
+impl std::fmt::Debug for ConstantFunctionRegistry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_set().entries(self.0.keys()).finish()
+	}
+}
+
+/// Registrations are not part of cached definitions
+impl crate::BinarySerializable for ConstantFunctionRegistry {
+	fn serialize(self, _buf: &mut Vec<u8>) {}
+
+	fn deserialize<I: Iterator<Item = u8>>(_iter: &mut I, _source: source_map::SourceId) -> Self {
+		Self::default()
+	}
+}
+
 /// From when
 /// ```typescript
 /// f<number>(2, 3)
@@ -195,20 +277,9 @@ pub(crate) fn call_constant_function(
 				if id.ends_with("rust") {
 					Ok(ConstantOutput::Diagnostic(format!("{effects:#?}")))
 				} else {
-					match effects {
-						FunctionEffect::SideEffects { events, .. } => {
-							let mut buf = String::new();
-							debug_effects(&mut buf, events, types, environment, true);
-							Ok(ConstantOutput::Diagnostic(buf))
-						}
-						FunctionEffect::Constant(identifier) => {
-							Ok(ConstantOutput::Diagnostic(format!("Constant: {identifier}")))
-						}
-						FunctionEffect::InputOutput(identifier) => {
-							Ok(ConstantOutput::Diagnostic(format!("InputOutput: {identifier}")))
-						}
-						FunctionEffect::Unknown => Ok(ConstantOutput::Diagnostic("unknown".into())),
-					}
+					let mut buf = String::new();
+					debug_function_effect(&mut buf, effects, types, environment, true);
+					Ok(ConstantOutput::Diagnostic(buf))
 				}
 			} else {
 				Ok(ConstantOutput::Diagnostic(format!("{get_type_by_id:?} is not a function")))
@@ -237,6 +308,9 @@ pub(crate) fn call_constant_function(
 				Err(ConstantFunctionError::BadCall)
 			}
 		}
+		// Calling the function is done in `FunctionType::call`. Here when `this` is not a known function.
+		// Checking the argument of `satisfies` against `T` is done by the standard calling logic
+		"call" | "apply" | "satisfies" => Err(ConstantFunctionError::BadCall),
 		"setPrototypeOf" => {
 			if let [first, second] = arguments {
 				let _prototype = environment
@@ -263,6 +337,27 @@ pub(crate) fn call_constant_function(
 				Err(ConstantFunctionError::BadCall)
 			}
 		}
+		"freeze" => {
+			if let Some(on) = arguments.first() {
+				let on = on.non_spread_type().map_err(|()| ConstantFunctionError::BadCall)?;
+				crate::features::objects::freeze(on, false, environment, types);
+				Ok(ConstantOutput::Value(on))
+			} else {
+				Err(ConstantFunctionError::BadCall)
+			}
+		}
+		"isFrozen" => {
+			if let Some(on) = arguments.first() {
+				let on = on.non_spread_type().map_err(|()| ConstantFunctionError::BadCall)?;
+				if let Type::Object(..) = types.get_type_by_id(on) {
+					Ok(ConstantOutput::Value(environment.is_frozen(on).unwrap_or(TypeId::FALSE)))
+				} else {
+					Err(ConstantFunctionError::BadCall)
+				}
+			} else {
+				Err(ConstantFunctionError::BadCall)
+			}
+		}
 		"create_proxy" => {
 			if let [object, trap] = arguments {
 				// TODO checking for both, what about spreading
@@ -277,6 +372,25 @@ pub(crate) fn call_constant_function(
 				Err(ConstantFunctionError::BadCall)
 			}
 		}
+		"create_symbol" => {
+			// Every call creates a new (unique) symbol
+			let key = match arguments.first().map(|argument| types.get_type_by_id(argument.value)) {
+				Some(Type::Constant(Constant::String(description))) => description.clone(),
+				_ => String::new(),
+			};
+			Ok(ConstantOutput::Value(types.new_constant_type(Constant::Symbol { key })))
+		}
+		// `Array(length)` (with or without `new`) creates an array of holes
+		"array_constructor" => {
+			if let [SynthesisedArgument { spread: false, value: length, .. }] = arguments {
+				if get_larger_type(*length, types) == TypeId::NUMBER_TYPE {
+					let array = create_array(&[], Some(*length), types, environment);
+					return Ok(ConstantOutput::Value(array));
+				}
+			}
+			Ok(ConstantOutput::Value(create_array(arguments, None, types, environment)))
+		}
+		"array_of" => Ok(ConstantOutput::Value(create_array(arguments, None, types, environment))),
 		// "satisfies" => {
 		// 	let ty = arguments
 		// 		.first()
@@ -337,8 +451,55 @@ pub(crate) fn call_constant_function(
 		// 	}
 		// }
 		func => {
-			// Sometimes a bad definition file could occur so...
-			Err(ConstantFunctionError::NoLogicForIdentifier(func.to_owned()))
+			if let Some(registered) = types.constant_functions.get(func) {
+				registered.call(this_argument, arguments, types, environment)
+			} else {
+				// Sometimes a bad definition file could occur so...
+				Err(ConstantFunctionError::NoLogicForIdentifier(func.to_owned()))
+			}
 		}
 	}
 }
+
+/// An array object with `items` as its elements. If `length` is given (for `Array(length)`) it has
+/// no elements (rather than `undefined` ones)
+fn create_array(
+	items: &[SynthesisedArgument],
+	length: Option<TypeId>,
+	types: &mut TypeStore,
+	environment: &mut Environment,
+) -> TypeId {
+	let mut array = ObjectBuilder::new(Some(TypeId::ARRAY_TYPE), types, &mut environment.info);
+	let mut known_length = Some(0);
+	for item in items {
+		let key = if let Some(idx) = known_length {
+			PropertyKey::from_usize(idx)
+		} else {
+			PropertyKey::Type(TypeId::NUMBER_TYPE)
+		};
+		array.append(
+			environment,
+			Publicity::Public,
+			key,
+			PropertyValue::Value(item.value),
+			Some(item.position),
+		);
+		// After a spread of unknown length, the indexes of items are unknown
+		known_length = known_length.filter(|_| !item.spread).map(|idx| idx + 1);
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	let length = length.unwrap_or_else(|| {
+		known_length.map_or(TypeId::NUMBER_TYPE, |length| {
+			types.new_constant_type(Constant::Number((length as f64).try_into().unwrap()))
+		})
+	});
+	array.append(
+		environment,
+		Publicity::Public,
+		PropertyKey::String("length".into()),
+		PropertyValue::Value(length),
+		None,
+	);
+	array.build_object()
+}
@@ -0,0 +1,71 @@
+//! `@deprecated` `JSDoc` tags on declarations. Uses of deprecated items raise
+//! [`crate::diagnostics::TypeCheckWarning::Deprecated`]
+
+use std::collections::HashMap;
+
+use source_map::SpanWithSource;
+
+use crate::{diagnostics::TypeCheckWarning, CheckingData, TypeId, VariableId};
+
+/// Deprecated declarations (from all modules and definition files) with their messages
+#[derive(Default, Debug)]
+pub struct Deprecations {
+	pub variables: HashMap<VariableId, String>,
+	pub types: HashMap<TypeId, String>,
+}
+
+/// Reads the message of a `@deprecated` tag from the content of a `JSDoc` comment
+/// (`/** ... */`). The message may be empty
+#[must_use]
+pub fn get_deprecation_message(comment: &str) -> Option<String> {
+	// JSDoc comments start with `/**`
+	let comment = comment.strip_prefix('*')?;
+	let mut lines = comment.lines().map(|line| line.trim().trim_start_matches('*').trim());
+
+	let first = lines.by_ref().find_map(|line| {
+		let rest = line.strip_prefix("@deprecated")?;
+		(rest.is_empty() || rest.starts_with(char::is_whitespace)).then_some(rest.trim())
+	})?;
+
+	// The message continues until the next tag or a blank line
+	let rest = lines.take_while(|line| !line.is_empty() && !line.starts_with('@'));
+	let message = std::iter::once(first)
+		.chain(rest)
+		.filter(|line| !line.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ");
+
+	Some(message)
+}
+
+pub(crate) fn warn_if_deprecated_variable<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	name: &str,
+	variable: VariableId,
+	position: SpanWithSource,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	if let Some(message) = checking_data.deprecations.variables.get(&variable) {
+		let warning = TypeCheckWarning::Deprecated {
+			name: name.to_owned(),
+			message: message.clone(),
+			position,
+		};
+		checking_data.diagnostics_container.add_warning(warning);
+	}
+}
+
+pub(crate) fn warn_if_deprecated_type<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	name: &str,
+	ty: TypeId,
+	position: SpanWithSource,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	if let Some(message) = checking_data.deprecations.types.get(&ty) {
+		let warning = TypeCheckWarning::Deprecated {
+			name: name.to_owned(),
+			message: message.clone(),
+			position,
+		};
+		checking_data.diagnostics_container.add_warning(warning);
+	}
+}
@@ -9,10 +9,12 @@ use crate::{
 	context::{
 		environment::{ContextLocation, ExpectedReturnType, FunctionScope},
 		get_on_ctx, get_value_of_variable,
-		information::{merge_info, LocalInformation},
-		CanReferenceThis, ContextType, Syntax,
+		information::{merge_info, LocalInformation, Publicity},
+		CanReferenceThis, ContextType, Syntax, VariableRegisterArguments,
 	},
+	diagnostics::{TypeCheckError, TypeStringRepresentation},
 	events::RootReference,
+	features::objects::ObjectBuilder,
 	types::{
 		self,
 		classes::ClassValue,
@@ -170,7 +172,11 @@ pub fn function_to_property(
 		GetterSetter::Getter => PropertyValue::Getter(Box::new(function)),
 		GetterSetter::Setter => PropertyValue::Setter(Box::new(function)),
 		GetterSetter::None => PropertyValue::Value(
-			if is_declare && matches!(function.effect, FunctionEffect::Unknown) {
+			if is_declare
+				&& matches!(
+					function.effect,
+					FunctionEffect::Unknown | FunctionEffect::Declared { .. }
+				) {
 				types.new_hoisted_function_type(function)
 			} else {
 				types.new_function_type(function)
@@ -194,6 +200,8 @@ pub fn synthesise_function_default_value<'a, T: crate::ReadFromFS, A: ASTImpleme
 		},
 	);
 
+	check_default_value(value, parameter_constraint, expression, environment, checking_data);
+
 	// Abstraction of `typeof parameter === "undefined"` to generate less types.
 	let is_undefined_condition = checking_data.types.register_type(Type::Constructor(
 		Constructor::TypeRelationOperator(types::TypeRelationOperator::Extends {
@@ -229,6 +237,54 @@ pub fn synthesise_function_default_value<'a, T: crate::ReadFromFS, A: ASTImpleme
 	result
 }
 
+/// Checks the default value meets the parameter annotation
+fn check_default_value<'a, T: crate::ReadFromFS, A: ASTImplementation>(
+	value: TypeId,
+	parameter_constraint: TypeId,
+	expression: &'a A::Expression<'a>,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	use crate::types::subtyping::{type_is_subtype, BasicEquality, SubTypeResult};
+
+	let position = A::expression_position(expression).with_source(environment.get_source());
+
+	let mut basic_equality = BasicEquality {
+		add_property_restrictions: false,
+		position,
+		object_constraints: Default::default(),
+		allow_errors: true,
+	};
+
+	let result = type_is_subtype(
+		parameter_constraint,
+		value,
+		&mut basic_equality,
+		environment,
+		&checking_data.types,
+	);
+
+	if let SubTypeResult::IsNotSubType(_) = result {
+		let expected = TypeStringRepresentation::from_type_id(
+			parameter_constraint,
+			environment,
+			&checking_data.types,
+			checking_data.options.debug_types,
+		);
+		let found = TypeStringRepresentation::from_type_id(
+			value,
+			environment,
+			&checking_data.types,
+			checking_data.options.debug_types,
+		);
+		checking_data.diagnostics_container.add_error(TypeCheckError::InvalidDefaultParameter {
+			expected,
+			found,
+			position,
+		});
+	}
+}
+
 /// TODO different place
 /// TODO maybe generic
 #[derive(Clone, Copy, Debug, binary_serialize_derive::BinarySerializable)]
@@ -374,6 +430,7 @@ pub enum FunctionRegisterBehavior<'a, A: crate::ASTImplementation> {
 		/// Is this is_some then can use `super()`
 		super_type: Option<TypeId>,
 		properties: ClassPropertiesToRegister<'a, A>,
+		internal_marker: Option<InternalFunctionEffect>,
 	},
 }
 
@@ -419,6 +476,65 @@ pub trait ClosureChain {
 		T: Fn(ClosureId) -> Option<R>;
 }
 
+/// The legacy `arguments` object. Items are the values of the parameters, with any extra items
+/// being from the rest parameter
+fn register_arguments_object(
+	parameters: &SynthesisedParameters,
+	function_position: SpanWithSource,
+	environment: &mut Environment,
+	types: &mut TypeStore,
+) {
+	let mut object = ObjectBuilder::new(None, types, &mut environment.info);
+
+	// Added first as the latest matching property is used, so the parameter indexes take precedence
+	let rest_item =
+		parameters.rest_parameter.as_ref().map_or(TypeId::UNDEFINED_TYPE, |rest| rest.item_type);
+	object.append(
+		environment,
+		Publicity::Public,
+		PropertyKey::Type(TypeId::NUMBER_TYPE),
+		PropertyValue::Value(rest_item),
+		None,
+	);
+
+	for (idx, parameter) in parameters.parameters.iter().enumerate() {
+		object.append(
+			environment,
+			Publicity::Public,
+			PropertyKey::from_usize(idx),
+			PropertyValue::Value(parameter.ty),
+			None,
+		);
+	}
+
+	// Any number of arguments can be passed
+	object.append(
+		environment,
+		Publicity::Public,
+		PropertyKey::String(Cow::Borrowed("length")),
+		PropertyValue::Value(TypeId::NUMBER_TYPE),
+		None,
+	);
+
+	// At the closing brace so the id does not clash with a variable starting at the function
+	let declared_at = SpanWithSource {
+		start: function_position.end - 1,
+		end: function_position.end,
+		source: function_position.source,
+	};
+
+	// A parameter named `arguments` takes precedence
+	let _ = environment.register_variable(
+		"arguments",
+		declared_at,
+		VariableRegisterArguments {
+			constant: true,
+			space: None,
+			initial_value: Some(object.build_object()),
+		},
+	);
+}
+
 pub(crate) fn synthesise_function<T, A, F>(
 	function: &F,
 	behavior: FunctionRegisterBehavior<A>,
@@ -445,7 +561,12 @@ where
 
 	// unfold information from the behavior
 	let kind: FunctionKind<A> = match behavior {
-		FunctionRegisterBehavior::Constructor { super_type, prototype, properties } => {
+		FunctionRegisterBehavior::Constructor {
+			super_type,
+			prototype,
+			properties,
+			internal_marker,
+		} => {
 			FunctionKind {
 				behavior: FunctionBehavior::Constructor {
 					non_super_prototype: super_type.is_some().then_some(prototype),
@@ -456,7 +577,7 @@ where
 					type_of_super: super_type,
 					this_object_type: TypeId::ERROR_TYPE,
 				},
-				internal: None,
+				internal: internal_marker,
 				constructor: Some((prototype, properties)),
 				expected_parameters: None,
 				// TODO
@@ -767,6 +888,16 @@ where
 			expected_parameters.as_ref(),
 		);
 
+		// Arrow functions use the `arguments` of the function they are in
+		if !matches!(behavior, FunctionBehavior::ArrowFunction { .. }) {
+			register_arguments_object(
+				&synthesised_parameters,
+				function.get_position().with_source(function_environment.get_source()),
+				&mut function_environment,
+				&mut checking_data.types,
+			);
+		}
+
 		let return_type_annotation =
 			function.return_type_annotation(&mut function_environment, checking_data);
 
@@ -944,15 +1075,8 @@ where
 			}
 		}
 
-		let effect = match internal {
-			Some(InternalFunctionEffect::Constant(identifier)) => {
-				FunctionEffect::Constant(identifier)
-			}
-			Some(InternalFunctionEffect::InputOutput(identifier)) => {
-				FunctionEffect::InputOutput(identifier)
-			}
-			None => FunctionEffect::Unknown,
-		};
+		let effect = internal
+			.map_or(FunctionEffect::Unknown, |internal| internal.into_function_effect(&parameters));
 
 		FunctionType { id, type_parameters, parameters, return_type, behavior, effect }
 	}
@@ -1,18 +1,28 @@
-use std::{collections::HashMap, iter};
+use std::{borrow::Cow, collections::HashMap, iter};
+
+use source_map::SpanWithSource;
 
 use crate::{
 	context::{
-		environment::Label, get_value_of_variable, information::get_properties_on_type,
-		invocation::InvocationContext, CallCheckingBehavior, ClosedOverReferencesInScope,
+		environment::Label,
+		get_value_of_variable,
+		information::{get_properties_on_type, InformationChain, Publicity},
+		invocation::InvocationContext,
+		CallCheckingBehavior, ClosedOverReferencesInScope,
 	},
+	diagnostics::{TypeCheckError, TypeStringRepresentation},
 	events::{
 		application::{apply_event_unknown, ErrorsAndInfo},
 		apply_event, ApplicationResult, Event, FinalEvent, InitialVariables, RootReference,
 	},
-	features::operations::CanonicalEqualityAndInequality,
+	features::{operations::CanonicalEqualityAndInequality, symbols::call_method},
 	types::{
+		get_constraint,
 		poly_types::{generic_type_arguments::TypeArgumentStore, FunctionTypeArguments},
-		substitute, Constructor, ObjectNature, PolyNature, TypeStore,
+		properties::PropertyKey,
+		substitute,
+		tuples::{self, TupleElementKind},
+		Constructor, ObjectNature, PolyNature, StructureGenerics, TypeStore,
 	},
 	CheckingData, Constant, Environment, LocalInformation, Scope, Type, TypeId, VariableId,
 };
@@ -108,7 +118,7 @@ pub fn synthesise_iteration<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 			);
 
 			if let ApplicationResult::Interrupt(early_return) = run_iteration_block {
-				crate::utils::notify!("Loop returned {:?}", early_return);
+				crate::utils::notify!(Trace; "Loop returned {:?}", early_return);
 				environment.info.events.push(Event::FinalEvent(early_return));
 			}
 
@@ -186,7 +196,7 @@ pub fn synthesise_iteration<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 				&mut checking_data.types,
 			);
 			if let ApplicationResult::Interrupt(early_return) = run_iteration_block {
-				todo!("{early_return:?}")
+				environment.info.events.push(Event::FinalEvent(early_return));
 			}
 		}
 		IterationBehavior::For { initialiser, condition, afterthought } => {
@@ -319,7 +329,7 @@ pub fn synthesise_iteration<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 				&mut checking_data.types,
 			);
 			if let ApplicationResult::Interrupt(early_return) = run_iteration_block {
-				todo!("{early_return:?}")
+				environment.info.events.push(Event::FinalEvent(early_return));
 			}
 		}
 		IterationBehavior::ForIn { lhs, rhs } => {
@@ -359,10 +369,180 @@ pub fn synthesise_iteration<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 				&mut checking_data.types,
 			);
 			if let ApplicationResult::Interrupt(early_return) = run_iteration_block {
-				todo!("{early_return:?}")
+				environment.info.events.push(Event::FinalEvent(early_return));
+			}
+		}
+		IterationBehavior::ForOf { lhs, rhs } => {
+			let iterable =
+				A::synthesise_expression(rhs, TypeId::ANY_TYPE, environment, checking_data);
+			let position = A::expression_position(rhs).with_source(environment.get_source());
+
+			let item = get_iterated_type(iterable, position, environment, checking_data);
+
+			let variable = checking_data
+				.types
+				.register_type(Type::RootPolyType(PolyNature::Parameter { fixed_to: item }));
+
+			let ((), result, ..) = environment.new_lexical_environment_fold_into_parent(
+				Scope::Iteration { label },
+				checking_data,
+				|environment, checking_data| {
+					A::declare_and_assign_to_fields(lhs, environment, checking_data, variable);
+					loop_body(environment, checking_data);
+				},
+			);
+
+			let (LocalInformation { events, .. }, closes_over) = result.unwrap();
+
+			let run_iteration_block = run_iteration_block(
+				IterationKind::Iterator { on: iterable, variable },
+				events,
+				InitialVariablesInput::Compute(closes_over),
+				&mut FunctionTypeArguments::new_arguments_for_use_in_loop(),
+				environment,
+				&mut InvocationContext::new_empty(),
+				// TODO shouldn't be needed
+				&mut Default::default(),
+				&mut checking_data.types,
+			);
+			if let ApplicationResult::Interrupt(early_return) = run_iteration_block {
+				environment.info.events.push(Event::FinalEvent(early_return));
 			}
 		}
-		IterationBehavior::ForOf { lhs: _, rhs: _ } => todo!(),
+	}
+}
+
+/// Result of [`get_iterated_items`]
+pub(crate) enum IteratedItems {
+	/// Iterables with a known amount of items (arrays and string constants)
+	Known(Vec<TypeId>),
+	/// The type of every item, where the amount of items is not known
+	Unknown(TypeId),
+}
+
+/// The items when iterating over `iterable` (in `for of`, array spread and array destructuring).
+///
+/// Arrays and strings are iterated over directly, else the `[Symbol.iterator]` method is used
+/// (`iterable[Symbol.iterator]().next().value`). Raises [`TypeCheckError::NotIterable`] if
+/// `iterable` has no iterator
+pub(crate) fn get_iterated_items<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	iterable: TypeId,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> IteratedItems {
+	if let Some(tuple) = tuples::get_tuple(iterable, &checking_data.types) {
+		let known = tuple.elements.iter().all(|element| element.kind == TupleElementKind::Required);
+		let items = tuple.elements.iter().map(|element| element.ty).collect::<Vec<_>>();
+		return if known {
+			IteratedItems::Known(items)
+		} else {
+			let item = items
+				.into_iter()
+				.reduce(|acc, item| checking_data.types.new_or_type(acc, item))
+				.unwrap_or(TypeId::NEVER_TYPE);
+			IteratedItems::Unknown(item)
+		};
+	}
+
+	let is_array = environment
+		.get_chain_of_info()
+		.find_map(|info| info.prototypes.get(&iterable))
+		.is_some_and(|prototype| *prototype == TypeId::ARRAY_TYPE);
+
+	match checking_data.types.get_type_by_id(iterable) {
+		Type::Object(ObjectNature::RealDeal) if is_array => {
+			let mut items = get_properties_on_type(iterable, &checking_data.types, environment)
+				.into_iter()
+				.filter_map(|(_, key, value)| Some((key.as_number(&checking_data.types)?, value)))
+				.collect::<Vec<_>>();
+
+			items.sort_by_key(|(idx, _)| *idx);
+			return IteratedItems::Known(items.into_iter().map(|(_, value)| value).collect());
+		}
+		Type::Constant(Constant::String(value)) => {
+			let characters =
+				value.chars().map(|c| Constant::String(c.to_string())).collect::<Vec<_>>();
+			return IteratedItems::Known(
+				characters
+					.into_iter()
+					.map(|character| checking_data.types.new_constant_type(character))
+					.collect(),
+			);
+		}
+		_ => {}
+	}
+
+	let iterator = call_method(
+		iterable,
+		&PropertyKey::Type(TypeId::SYMBOL_ITERATOR),
+		Vec::new(),
+		position,
+		environment,
+		checking_data,
+	);
+
+	if let Some(iterator) = iterator {
+		let next = PropertyKey::String(Cow::Borrowed("next"));
+		let Some(result) =
+			call_method(iterator, &next, Vec::new(), position, environment, checking_data)
+		else {
+			return IteratedItems::Unknown(TypeId::ANY_TYPE);
+		};
+
+		let value = environment
+			.get_property(
+				result,
+				Publicity::Public,
+				&PropertyKey::String(Cow::Borrowed("value")),
+				&mut checking_data.types,
+				None,
+				position.without_source(),
+				&checking_data.options,
+			)
+			.map_or(TypeId::ANY_TYPE, |(_, value)| value);
+
+		return IteratedItems::Unknown(value);
+	}
+
+	let constraint = get_constraint(iterable, &checking_data.types).unwrap_or(iterable);
+	let item = match checking_data.types.get_type_by_id(constraint) {
+		Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::ARRAY_TYPE,
+			arguments,
+		})) => arguments.get_structure_restriction(TypeId::T_TYPE).unwrap_or(TypeId::ANY_TYPE),
+		_ if constraint == TypeId::STRING_TYPE => TypeId::STRING_TYPE,
+		Type::Constant(_) | Type::Object(_) | Type::SpecialObject(_) => {
+			checking_data.diagnostics_container.add_error(TypeCheckError::NotIterable {
+				iterable: TypeStringRepresentation::from_type_id(
+					iterable,
+					environment,
+					&checking_data.types,
+					checking_data.options.debug_types,
+				),
+				position,
+			});
+			TypeId::ERROR_TYPE
+		}
+		_ => TypeId::ANY_TYPE,
+	};
+
+	IteratedItems::Unknown(item)
+}
+
+/// The type of any item of `iterable`. See [`get_iterated_items`]
+pub(crate) fn get_iterated_type<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	iterable: TypeId,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> TypeId {
+	match get_iterated_items(iterable, position, environment, checking_data) {
+		IteratedItems::Known(items) => items
+			.into_iter()
+			.reduce(|acc, item| checking_data.types.new_or_type(acc, item))
+			.unwrap_or(TypeId::NEVER_TYPE),
+		IteratedItems::Unknown(item) => item,
 	}
 }
 
@@ -445,6 +625,10 @@ pub(crate) fn run_iteration_block(
 				}
 
 				for _ in 0..iterations {
+					if types.cancellation.is_cancelled() {
+						break;
+					}
+
 					let result = evaluate_single_loop_iteration(
 						&events,
 						type_arguments,
@@ -467,7 +651,7 @@ pub(crate) fn run_iteration_block(
 								})
 							}
 							FinalEvent::Break { carry, position } => {
-								crate::utils::notify!("Here {}", carry);
+								crate::utils::notify!(Trace; "Here {}", carry);
 								return ApplicationResult::Interrupt(FinalEvent::Break {
 									carry: carry - 1,
 									position,
@@ -498,7 +682,7 @@ pub(crate) fn run_iteration_block(
 				for (_publicity, property, _value) in
 					get_properties_on_type(on, types, top_environment)
 				{
-					crate::utils::notify!("Property: {:?}", property);
+					crate::utils::notify!(Trace; "Property: {:?}", property);
 
 					let property_key_as_type = match property {
 						crate::types::properties::PropertyKey::String(str) => {
@@ -557,7 +741,16 @@ pub(crate) fn run_iteration_block(
 				)
 			}
 		}
-		IterationKind::Iterator { .. } => todo!(),
+		// Iterators are not evaluated
+		IterationKind::Iterator { .. } => evaluate_unknown_iteration_for_loop(
+			events,
+			initial,
+			condition,
+			type_arguments,
+			invocation_context,
+			top_environment,
+			types,
+		),
 	}
 }
 
@@ -584,15 +777,16 @@ fn evaluate_unknown_iteration_for_loop(
 		InitialVariablesInput::Compute(closed_over_variables) => {
 			closed_over_variables
 				.into_iter()
-				.map(|reference| {
+				.filter_map(|reference| {
 					match reference {
 						RootReference::Variable(variable_id) => {
+							// Free variables of the function (such as a class referenced in its
+							// own methods) may not have a value yet
 							let value_before_iterations = get_value_of_variable(
 								top_environment,
 								variable_id,
 								None::<&crate::types::poly_types::FunctionTypeArguments>,
-							)
-							.unwrap();
+							)?;
 
 							// crate::utils::notify!(
 							// 	"setting '{}' to have initial type {}",
@@ -605,12 +799,12 @@ fn evaluate_unknown_iteration_for_loop(
 							// 	)
 							// );
 
-							(variable_id, value_before_iterations)
+							Some((variable_id, value_before_iterations))
 
 							// top_environment.info.variable_current_value.insert(variable_id, *free_variable_id);
 						}
 						RootReference::This => {
-							crate::utils::notify!("Loop uses `this`");
+							crate::utils::notify!(Trace; "Loop uses `this`");
 							todo!()
 						}
 					}
@@ -672,7 +866,7 @@ fn evaluate_single_loop_iteration(
 
 	if !errors.errors.is_empty() {
 		// unreachable!("errors when calling loop")
-		crate::utils::notify!("errors when calling loop");
+		crate::utils::notify!(Trace; "errors when calling loop");
 	}
 
 	final_event
@@ -753,7 +947,7 @@ fn calculate_result_of_loop(
 ) -> Result<LoopStructure, ()> {
 	let condition_ty = types.get_type_by_id(condition);
 
-	crate::utils::notify!("condition is {:?}", condition_ty);
+	crate::utils::notify!(Trace; "condition is {:?}", condition_ty);
 
 	// TODO some other cases
 	// - and for less than equal
@@ -783,16 +977,16 @@ fn calculate_result_of_loop(
 					let changed = if let Some((_start, _end)) =
 						loop_variables.as_ref().and_then(|vs| vs.get(roof_id).copied())
 					{
-						crate::utils::notify!("Found loop variables");
+						crate::utils::notify!(Trace; "Found loop variables");
 						false
 					} else if let Some(inside) = inside_loop.variable_values.get(roof_id) {
-						crate::utils::notify!(
+						crate::utils::notify!(Trace;
 							"Found loop here {:?}",
 							types.get_type_by_id(*inside)
 						);
 						false
 					} else {
-						crate::utils::notify!("Here, roof not changed");
+						crate::utils::notify!(Trace; "Here, roof not changed");
 						true
 					};
 
@@ -805,7 +999,7 @@ fn calculate_result_of_loop(
 							*parent_environment.info.variable_current_value.get(roof_id).unwrap()
 						}
 					} else {
-						crate::utils::notify!("Roof changed in loop");
+						crate::utils::notify!(Trace; "Roof changed in loop");
 						return Err(());
 					}
 				} else if let Type::Constant(_) = roof_ty {
@@ -863,10 +1057,10 @@ fn calculate_result_of_loop(
 					return Ok(LoopStructure { start, roof, increment_by: *increments_by });
 				}
 			} else {
-				crate::utils::notify!("{:?} has no max", roof);
+				crate::utils::notify!(Trace; "{:?} has no max", roof);
 			}
 		} else {
-			crate::utils::notify!("LHS {:?} is not free variable ", reference_ty);
+			crate::utils::notify!(Trace; "LHS {:?} is not free variable ", reference_ty);
 		}
 	}
 	Err(())
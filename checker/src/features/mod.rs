@@ -1,7 +1,7 @@
 use source_map::SpanWithSource;
 
 use crate::{
-	types::{get_constraint, StructureGenerics, TypeStore},
+	types::{get_constraint, get_larger_type, StructureGenerics, TypeStore},
 	CheckingData, Environment, Type, TypeId,
 };
 
@@ -16,11 +16,15 @@ use self::objects::SpecialObjects;
 /// - Internal structures
 pub mod assignments;
 pub mod constant_functions;
+pub mod deprecation;
 pub mod functions;
 pub mod iteration;
 pub mod modules;
 pub mod objects;
 pub mod operations;
+pub mod resolution;
+pub mod semantic_tokens;
+pub mod symbols;
 pub mod template_literal;
 pub mod variables;
 
@@ -30,6 +34,7 @@ pub fn type_of_operator(on: TypeId, types: &mut TypeStore) -> TypeId {
 			TypeId::NUMBER_TYPE => "number",
 			TypeId::STRING_TYPE => "string",
 			TypeId::BOOLEAN_TYPE => "boolean",
+			TypeId::BIGINT_TYPE => "bigint",
 			TypeId::SYMBOL_TYPE => "symbol",
 			_constraint => {
 				return types.register_type(crate::Type::Constructor(
@@ -46,6 +51,7 @@ pub fn type_of_operator(on: TypeId, types: &mut TypeStore) -> TypeId {
 				crate::Constant::NaN | crate::Constant::Number(_) => "number",
 				crate::Constant::String(_) => "string",
 				crate::Constant::Boolean(_) => "boolean",
+				crate::Constant::BigInt(_) => "bigint",
 				crate::Constant::Symbol { key: _ } => "symbol",
 				crate::Constant::Undefined => "undefined",
 				crate::Constant::Null => "object",
@@ -57,7 +63,7 @@ pub fn type_of_operator(on: TypeId, types: &mut TypeStore) -> TypeId {
 		} else if let crate::Type::Object(..) | crate::Type::SpecialObject(..) = ty {
 			types.new_constant_type(crate::Constant::String("object".to_owned()))
 		} else {
-			crate::utils::notify!("Cannot `typeof {:?}`", on);
+			crate::utils::notify!(Warn; "Cannot `typeof {:?}`", on);
 			TypeId::ERROR_TYPE
 		}
 	}
@@ -74,6 +80,7 @@ pub fn as_cast(on: TypeId, cast_to: TypeId, types: &mut TypeStore) -> Result<Typ
 				Constructor::CanonicalRelationOperator { .. }
 				| Constructor::UnaryOperator { .. }
 				| Constructor::StructureGenerics(_)
+				| Constructor::TemplateLiteral(_)
 				| Constructor::BinaryOperator { .. } => false,
 				Constructor::TypeOperator(_) => todo!(),
 				Constructor::TypeRelationOperator(_) => todo!(),
@@ -86,7 +93,11 @@ pub fn as_cast(on: TypeId, cast_to: TypeId, types: &mut TypeStore) -> Result<Typ
 		}
 	}
 
-	let can_cast = on == TypeId::ERROR_TYPE || can_cast_type(types.get_type_by_id(on));
+	// Values can be cast to types from `Nominal` with the same base
+	let is_nominal_of_value =
+		types.get_nominal_base(cast_to).is_some_and(|base| base == get_larger_type(on, types));
+	let can_cast =
+		on == TypeId::ERROR_TYPE || is_nominal_of_value || can_cast_type(types.get_type_by_id(on));
 
 	if can_cast {
 		// TSC compat around `any`
@@ -101,10 +112,22 @@ pub fn as_cast(on: TypeId, cast_to: TypeId, types: &mut TypeStore) -> Result<Typ
 /// TODO await ors etc
 pub fn await_expression<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 	on: TypeId,
-	_environment: &mut Environment,
+	environment: &mut Environment,
 	checking_data: &mut CheckingData<T, A>,
 	position: SpanWithSource,
 ) -> TypeId {
+	match get_await_context(environment) {
+		Some(AwaitContext::TopLevel(source)) => {
+			checking_data.modules.top_level_await_sources.insert(source);
+		}
+		Some(AwaitContext::AsyncFunction | AwaitContext::Repl) => {}
+		None => {
+			checking_data
+				.diagnostics_container
+				.add_error(crate::diagnostics::TypeCheckError::AwaitInNonAsyncContext(position));
+		}
+	}
+
 	if let Some(constraint) = get_constraint(on, &checking_data.types) {
 		// TODO mark type as awaited
 		let inner_type = get_promise_value(constraint, &checking_data.types);
@@ -125,12 +148,61 @@ pub fn await_expression<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 			);
 			on
 		}
+	} else if let Some(result) = get_promise_value(on, &checking_data.types) {
+		// A promise that is not dependent (for example from `import(...)`)
+		result
+	} else if let Type::Constant(_) = checking_data.types.get_type_by_id(on) {
+		// Constants are not thenable, so the value is kept as is
+		checking_data
+			.diagnostics_container
+			.add_warning(crate::diagnostics::TypeCheckWarning::AwaitUsedOnNonPromise(position));
+		on
 	} else {
 		checking_data.raise_unimplemented_error("await on object", position);
 		TypeId::ERROR_TYPE
 	}
 }
 
+enum AwaitContext {
+	AsyncFunction,
+	/// The top level of the ES module with this source
+	TopLevel(source_map::SourceId),
+	Repl,
+}
+
+/// Finds where an `await` is. `None` if it is not allowed (in a non `async` function or the top
+/// level of a `CommonJS` module)
+fn get_await_context(environment: &Environment) -> Option<AwaitContext> {
+	use crate::{context::environment::FunctionScope, GeneralContext, Scope};
+
+	let mut in_pass_through = false;
+	for ctx in environment.parents_iter() {
+		let GeneralContext::Syntax(ctx) = ctx else { break };
+		match &ctx.context_type.scope {
+			Scope::Function(
+				FunctionScope::ArrowFunction { is_async, .. }
+				| FunctionScope::MethodFunction { is_async, .. }
+				| FunctionScope::Function { is_async, .. },
+			) => {
+				return is_async.then_some(AwaitContext::AsyncFunction);
+			}
+			Scope::Function(FunctionScope::Constructor { .. }) | Scope::StaticBlock { .. } => {
+				return None;
+			}
+			// CommonJS modules are wrapped in a (non `async`) function
+			Scope::Module { source, .. } => {
+				return (!in_pass_through).then_some(AwaitContext::TopLevel(*source));
+			}
+			Scope::PassThrough { .. } => {
+				in_pass_through = true;
+			}
+			_ => {}
+		}
+	}
+	// The REPL is directly under the root
+	Some(AwaitContext::Repl)
+}
+
 fn get_promise_value(constraint: TypeId, types: &TypeStore) -> Option<TypeId> {
 	if let Type::Constructor(crate::types::Constructor::StructureGenerics(StructureGenerics {
 		on: TypeId::PROMISE_TYPE,
@@ -1,16 +1,29 @@
-use std::path::{Path, PathBuf};
+use std::{
+	collections::HashSet,
+	path::{Path, PathBuf},
+};
 
 use super::variables::{VariableMutability, VariableOrImport};
 use crate::{
 	context::{
-		information::{get_value_of_constant_import_variable, LocalInformation},
-		VariableRegisterArguments,
+		information::{
+			get_properties_on_type, get_value_of_constant_import_variable, LocalInformation,
+			Publicity,
+		},
+		Names, VariableRegisterArguments,
+	},
+	events::{Event, FinalEvent},
+	features::{
+		deprecation::{warn_if_deprecated_type, warn_if_deprecated_variable},
+		objects::ObjectBuilder,
+		resolution::{candidate_paths, resolve_package, resolve_path_alias},
 	},
-	get_source, CheckingData, Environment, Scope, Type, TypeId, TypeMappings, VariableId,
+	get_source,
+	types::properties::PropertyKey,
+	CheckingData, Environment, PropertyValue, Scope, Type, TypeId, TypeMappings, VariableId,
 };
 
-use simple_json_parser::{JSONKey, RootJSONValue};
-use source_map::{FileSystem, Span};
+use source_map::{FileSystem, SourceId, Span, SpanWithSource};
 
 #[derive(Debug)]
 pub struct NamePair<'a> {
@@ -32,9 +45,50 @@ pub enum ImportKind<'a, T: Iterator<Item = NamePair<'a>>> {
 pub struct SynthesisedModule<M> {
 	pub content: M,
 	pub exported: Exported,
+	/// Exports are from `module.exports`. See [`declare_common_js_variables`]
+	pub is_common_js: bool,
+	/// Uses `await` at the top level. Modules that import it wait for it to finish
+	pub has_top_level_await: bool,
 	/// TODO ...
 	pub info: LocalInformation,
 	pub mappings: TypeMappings,
+	/// The variables and types declared in the module, for re-checking parts of it
+	pub(crate) names: Names,
+}
+
+impl<M> SynthesisedModule<M> {
+	/// Whether running the module body (at `source`) changes anything outside of it. From the events
+	/// of the body: calls (that were not computed), `throw`, assignments to variables from other
+	/// modules and assignments to properties of objects the module did not create
+	#[must_use]
+	pub fn has_top_level_side_effects(&self, source: SourceId) -> bool {
+		let mut created_objects = HashSet::new();
+		events_have_side_effects(self.info.get_events(), source, &mut created_objects)
+	}
+}
+
+fn events_have_side_effects(
+	events: &[Event],
+	source: SourceId,
+	created_objects: &mut HashSet<TypeId>,
+) -> bool {
+	events.iter().any(|event| match event {
+		Event::CreateObject { referenced_in_scope_as, .. } => {
+			created_objects.insert(*referenced_in_scope_as);
+			false
+		}
+		Event::SetsVariable(variable, ..) => variable.0 != source,
+		Event::Setter { on, .. } => !created_objects.contains(on),
+		Event::Conditionally { true_events, else_events, .. } => {
+			events_have_side_effects(true_events, source, created_objects)
+				|| events_have_side_effects(else_events, source, created_objects)
+		}
+		Event::Iterate { iterate_over, .. } => {
+			events_have_side_effects(iterate_over, source, created_objects)
+		}
+		Event::CallsType { .. } | Event::FinalEvent(FinalEvent::Throw { .. }) => true,
+		Event::ReadsReference { .. } | Event::Getter { .. } | Event::FinalEvent(_) => false,
+	})
 }
 
 /// TODO tidy
@@ -44,10 +98,38 @@ pub struct Exported {
 	/// Mutability purely for the mutation thingy
 	pub named: Vec<(String, (VariableId, VariableMutability))>,
 	pub named_types: Vec<(String, TypeId)>,
+	/// Where each export (named, type or `default`) is declared or re-exported
+	pub positions: Vec<(String, SpanWithSource)>,
 }
 
 pub type ExportedVariable = (VariableId, VariableMutability);
 
+/// An entry of the public surface of a module. From [`crate::CheckOutput::get_exports`]
+#[derive(Debug, Clone)]
+pub struct ModuleExport {
+	pub name: String,
+	pub kind: ExportKind,
+	/// The annotation (or otherwise the value) of constants, the reassignment constraint of mutable
+	/// variables and the type of types
+	pub ty: TypeId,
+	/// Where it is declared or re-exported
+	pub position: Option<SpanWithSource>,
+	/// For re-exported variables, the module the variable is declared in
+	pub re_exported_from: Option<SourceId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+	Default,
+	Function,
+	Class,
+	Variable {
+		constant: bool,
+	},
+	/// Interfaces, type aliases and classes (as types)
+	Type,
+}
+
 pub enum TypeOrVariable {
 	ExportedVariable(),
 	Type(TypeId),
@@ -82,6 +164,53 @@ pub type FinalModule<M> = Result<SynthesisedModule<M>, InvalidModule>;
 #[derive(Debug, Clone)]
 pub struct CouldNotOpenFile(pub PathBuf);
 
+/// A dependency of a definition file on another, from a `/// <reference ... />` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionFileReference {
+	/// Relative to the definition file
+	Path(String),
+	/// The definitions of a package (including those under `@types`)
+	Types(String),
+	/// A library from [`crate::libraries`]
+	Lib(String),
+}
+
+/// The definition file that `reference` (in the definition file at `from`) is to
+pub(crate) fn resolve_definition_file_reference<T: crate::ReadFromFS>(
+	reference: &DefinitionFileReference,
+	from: &Path,
+	cwd: &Path,
+	fs_reader: &T,
+) -> Option<PathBuf> {
+	let path = match reference {
+		DefinitionFileReference::Path(path) => resolve_relative_import(from, path),
+		DefinitionFileReference::Types(name) => {
+			let package = resolve_package(name, from, cwd, fs_reader)?;
+			package.definition_file.unwrap_or(package.path)
+		}
+		DefinitionFileReference::Lib(name) => {
+			return crate::libraries::Lib::from_name(name).map(crate::libraries::Lib::path);
+		}
+	};
+	candidate_paths(&path).into_iter().find(|candidate| fs_reader.read_file(candidate).is_some())
+}
+
+/// For [`crate::TypeCheckOptions::no_missing_import_any`]
+fn warn_any_from_missing_import<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	variable: &str,
+	position: SpanWithSource,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	if checking_data.options.no_missing_import_any {
+		checking_data.diagnostics_container.add_warning(
+			crate::diagnostics::TypeCheckWarning::AnyFromMissingImport {
+				variable: variable.to_owned(),
+				position,
+			},
+		);
+	}
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn import_items<
 	'b,
@@ -137,6 +266,11 @@ pub fn import_items<
 				todo!("emit 'no default export' diagnostic")
 			}
 		} else {
+			warn_any_from_missing_import(
+				default_name,
+				position.with_source(current_source),
+				checking_data,
+			);
 			environment.register_variable_handle_error(
 				default_name,
 				VariableRegisterArguments {
@@ -178,6 +312,14 @@ pub fn import_items<
 							&mut checking_data.diagnostics_container,
 						);
 					}
+					// Also for re-exports
+					let position = part.position.with_source(current_source);
+					if let Some((variable, _)) = exported_variable {
+						warn_if_deprecated_variable(part.value, variable, position, checking_data);
+					} else if let Some(ty) = exported_type {
+						warn_if_deprecated_type(part.value, ty, position, checking_data);
+					}
+
 					if let Some((variable, mutability)) = exported_variable {
 						let constant = match mutability {
 							VariableMutability::Constant => {
@@ -206,18 +348,39 @@ pub fn import_items<
 								environment.context_type.scope
 							{
 								exported.named.push((part.r#as.to_owned(), (variable, mutability)));
+								exported.positions.push((part.r#as.to_owned(), position));
 							}
 						}
 					}
 
 					if let Some(ty) = exported_type {
 						let existing = environment.named_types.insert(part.r#as.to_owned(), ty);
-						assert!(existing.is_none(), "TODO exception");
+						// A type can be both imported and re-exported (`export type { T } from ...`)
+						assert!(existing.is_none() || existing == Some(ty), "TODO exception");
+						if also_export {
+							if let Scope::Module { ref mut exported, .. } =
+								environment.context_type.scope
+							{
+								if exported_variable.is_none() {
+									exported.positions.push((part.r#as.to_owned(), position));
+								}
+								exported.named_types.push((part.r#as.to_owned(), ty));
+							}
+						}
+					}
+
+					// Types of an exported namespace, e.g. `Namespace.Type`
+					let prefix = format!("{}.", part.value);
+					for (name, ty) in &exports.named_types {
+						if let Some(member) = name.strip_prefix(&prefix) {
+							environment.named_types.insert(format!("{}.{member}", part.r#as), *ty);
+						}
 					}
 				} else {
 					// This happens if imported is an invalid file (syntax issue, doesn't exist etc)
 					// Don't need to emit an error here
 					let declared_at = part.position.with_source(environment.get_source());
+					warn_any_from_missing_import(part.r#as, declared_at, checking_data);
 					environment.register_variable_handle_error(
 						part.r#as,
 						VariableRegisterArguments {
@@ -249,6 +412,11 @@ pub fn import_items<
 				);
 			} else {
 				crate::utils::notify!("TODO :?");
+				warn_any_from_missing_import(
+					under,
+					position.with_source(current_source),
+					checking_data,
+				);
 				environment.register_variable_handle_error(
 					under,
 					VariableRegisterArguments {
@@ -267,6 +435,9 @@ pub fn import_items<
 					// TODO are variables put into scope?
 					if let Scope::Module { ref mut exported, .. } = environment.context_type.scope {
 						exported.named.push((name.clone(), (*variable, *mutability)));
+						exported
+							.positions
+							.push((name.clone(), import_position.with_source(current_source)));
 					}
 				}
 			} else {
@@ -276,6 +447,139 @@ pub fn import_items<
 	}
 }
 
+/// Modules that are built into the checker (rather than read from the file system)
+pub const INTRINSIC_MODULE_PREFIX: &str = "ezno:";
+
+/// Exports of `ezno:debug` and the root (constant) function they alias
+///
+/// ```ts
+/// import { printType, debugType, assertType } from "ezno:debug";
+/// ```
+pub const DEBUG_MODULE_EXPORTS: &[(&str, &str)] =
+	&[("printType", "print_type"), ("debugType", "debug_type"), ("assertType", "satisfies")];
+
+fn get_intrinsic_module_exports(name: &str, environment: &Environment) -> Option<Exported> {
+	match name {
+		"debug" => {
+			let root = environment.get_root();
+			let named = DEBUG_MODULE_EXPORTS
+				.iter()
+				.filter_map(|(export, internal)| {
+					let variable = root.variables.get(*internal)?.get_id();
+					Some(((*export).to_owned(), (variable, VariableMutability::Constant)))
+				})
+				.collect();
+
+			Some(Exported { named, ..Default::default() })
+		}
+		_ => None,
+	}
+}
+
+/// The type of the default export of an import of an asset, from [`crate::TypeCheckOptions::asset_modules`].
+/// Query strings and fragments (`./icon.svg?url`) are ignored. Type names that are not found are
+/// `any`
+fn get_asset_module_type(
+	specifier: &str,
+	asset_modules: &[(String, String)],
+	environment: &Environment,
+) -> Option<TypeId> {
+	let path = specifier.split(['?', '#']).next().unwrap_or(specifier);
+	let extension = Path::new(path).extension()?.to_str()?;
+	let (_, type_name) = asset_modules
+		.iter()
+		.find(|(asset_extension, _)| asset_extension.trim_start_matches('.') == extension)?;
+	Some(environment.get_type_from_name(type_name).unwrap_or(TypeId::ANY_TYPE))
+}
+
+/// For `CommonJS` modules (for example dependencies that only publish `CommonJS`). Declares `module`
+/// and `exports` objects and `require` (which is currently an error type, so calls are not
+/// checked). These are not declared in the source, so they have positions past its end.
+///
+/// Returns the `module` variable for [`export_common_js_module_exports`]
+pub(crate) fn declare_common_js_variables<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> VariableId {
+	let source = environment.get_source();
+	let exports =
+		ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info).build_object();
+	let mut module = ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info);
+	module.append(
+		environment,
+		Publicity::Public,
+		PropertyKey::String("exports".into()),
+		PropertyValue::Value(exports),
+		None,
+	);
+	let module = module.build_object();
+
+	let variables = [
+		("module", module, false),
+		("exports", exports, false),
+		("require", TypeId::ERROR_TYPE, true),
+	];
+	for (offset, (name, value, constant)) in (0u32..).zip(variables) {
+		let position = u32::MAX - offset;
+		environment.register_variable_handle_error(
+			name,
+			VariableRegisterArguments { constant, space: None, initial_value: Some(value) },
+			SpanWithSource { start: position, end: position, source },
+			&mut checking_data.diagnostics_container,
+		);
+	}
+
+	VariableId(source, u32::MAX)
+}
+
+/// `module.exports` becomes the default export and its (string keyed) properties named exports.
+/// Unless it has `__esModule` (from a compiler), in which case the `default` property is the default export
+pub(crate) fn export_common_js_module_exports<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	module_variable: VariableId,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	let Some(module) = environment.info.variable_current_value.get(&module_variable).copied()
+	else {
+		return;
+	};
+	let exports = get_properties_on_type(module, &checking_data.types, environment)
+		.into_iter()
+		.find_map(|(_, key, value)| {
+			matches!(key, PropertyKey::String(ref name) if name == "exports").then_some(value)
+		});
+	let Some(exports) = exports else { return };
+
+	let properties = get_properties_on_type(exports, &checking_data.types, environment)
+		.into_iter()
+		.filter_map(|(publicity, key, value)| match (publicity, key) {
+			(Publicity::Public, PropertyKey::String(name)) => Some((name.into_owned(), value)),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+
+	let has_es_module_marker = properties.iter().any(|(name, _)| name == "__esModule");
+	let default = if has_es_module_marker {
+		properties.iter().find_map(|(name, value)| (name == "default").then_some(*value))
+	} else {
+		Some(exports)
+	};
+
+	let source = environment.get_source();
+	let mut named = Vec::new();
+	// After the variables from `declare_common_js_variables`
+	for (offset, (name, value)) in (3u32..).zip(properties) {
+		let id = VariableId(source, u32::MAX - offset);
+		environment.info.variable_current_value.insert(id, value);
+		named.push((name, (id, VariableMutability::Constant)));
+	}
+
+	if let Scope::Module { ref mut exported, .. } = environment.context_type.scope {
+		exported.default = default;
+		exported.named = named;
+	}
+}
+
 pub fn import_file<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 	to_import: &str,
 	environment: &mut Environment,
@@ -287,8 +591,23 @@ pub fn import_file<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		environment: &mut Environment,
 		checking_data: &'a mut CheckingData<T, A>,
 	) -> Option<Result<&'a SynthesisedModule<A::OwnedModule>, A::ParseError>> {
+		let full_importer = &canonicalise_path(
+			full_importer,
+			checking_data.options.preserve_symlinks,
+			checking_data.modules.file_reader,
+		);
 		let existing = checking_data.modules.files.get_source_at_path(full_importer);
 		if let Some(existing) = existing {
+			// An entry point that has been parsed but not yet synthesised
+			if let Some(module) = checking_data.modules.parsed_entry_points.remove(&existing) {
+				return match module {
+					Ok(module) => {
+						let root = &environment.get_root();
+						Some(Ok(root.new_module_context(existing, module, checking_data)))
+					}
+					Err(err) => Some(Err(err)),
+				};
+			}
 			Some(Ok(checking_data
 				.modules
 				.synthesised_modules
@@ -296,9 +615,16 @@ pub fn import_file<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 				.expect("existing file, but not synthesised")))
 		} else {
 			let content = checking_data.modules.file_reader.read_file(full_importer);
+			let content = content.and_then(|content| {
+				crate::decode_file_content(
+					full_importer,
+					content,
+					checking_data.options.maximum_file_size,
+					&mut checking_data.diagnostics_container,
+				)
+			});
 			if let Some(content) = content {
-				let (source, module) =
-					get_source(checking_data, full_importer, String::from_utf8(content).unwrap());
+				let (source, module) = get_source(checking_data, full_importer, content);
 
 				match module {
 					Ok(module) => {
@@ -315,80 +641,81 @@ pub fn import_file<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		}
 	}
 
-	fn get_package_from_node_modules<T: crate::ReadFromFS>(
-		name: &str,
-		cwd: &Path,
-		fs_reader: &T,
-	) -> Result<(PathBuf, Option<PathBuf>), ()> {
-		// TODO support non `node_modules` or is that over ?
-		let package_directory = cwd.join("node_modules");
-		let package_root = package_directory.join(name);
-		let package_json_path = package_root.join("package.json");
-		// TODO error
-		let package_json = fs_reader.read_file(&PathBuf::from(&package_json_path)).ok_or(())?;
-		let package_json = String::from_utf8(package_json).unwrap();
-
-		let (mut file_path, mut definition_file_path) = (None::<PathBuf>, None::<PathBuf>);
-
-		// TODO JSON parse error
-		let _res = simple_json_parser::parse_with_exit_signal(&package_json, |path, value| {
-			// if let Some(ref export) = export {
-			// 	todo!()
-			// } else {
-			if let [JSONKey::Slice("main")] = path {
-				if let RootJSONValue::String(s) = value {
-					file_path = Some(s.to_owned().into());
-				} else {
-					// invalid type
-				}
-			} else if let [JSONKey::Slice("types")] = path {
-				if let RootJSONValue::String(s) = value {
-					definition_file_path = Some(s.to_owned().into());
-				} else {
-					// invalid type
-				}
-			}
-			// }
-			file_path.is_some() && definition_file_path.is_some()
-		});
-
-		file_path.ok_or(()).map(|entry| {
-			(package_root.join(entry), definition_file_path.map(|dfp| package_root.join(dfp)))
+	/// The first of [`candidate_paths`] that is a file. Only checks that it exists, so the module is
+	/// not synthesised here
+	fn find_module<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+		path: &Path,
+		checking_data: &CheckingData<T, A>,
+	) -> Option<PathBuf> {
+		// TODO change parse options based on extension
+		let preserve_symlinks = checking_data.options.preserve_symlinks;
+		candidate_paths(path).into_iter().find(|candidate| {
+			let candidate =
+				canonicalise_path(candidate, preserve_symlinks, checking_data.modules.file_reader);
+			checking_data.modules.files.get_source_at_path(&candidate).is_some()
+				|| checking_data.modules.file_reader.read_file(&candidate).is_some()
 		})
 	}
 
+	fn get_module_with_extensions<'a, T: crate::ReadFromFS, A: crate::ASTImplementation>(
+		path: &Path,
+		environment: &mut Environment,
+		checking_data: &'a mut CheckingData<T, A>,
+	) -> Option<Result<&'a SynthesisedModule<A::OwnedModule>, A::ParseError>> {
+		let path = find_module(path, checking_data)?;
+		get_module(&path, None, environment, checking_data)
+	}
+
+	if let Some(intrinsic) = to_import.strip_prefix(INTRINSIC_MODULE_PREFIX) {
+		return get_intrinsic_module_exports(intrinsic, environment)
+			.map(Ok)
+			.ok_or_else(|| CouldNotOpenFile(PathBuf::from(to_import.to_owned())));
+	}
+
+	if let Some(default) =
+		get_asset_module_type(to_import, &checking_data.options.asset_modules, environment)
+	{
+		return Ok(Ok(Exported { default: Some(default), ..Default::default() }));
+	}
+
+	let from_path = checking_data.modules.files.get_file_path(environment.get_source()).clone();
 	let result = if to_import.starts_with('.') {
-		let from_path = checking_data.modules.files.get_file_path(environment.get_source());
-		let from = PathBuf::from(to_import);
-		let mut full_importer =
-			path_absolutize::Absolutize::absolutize_from(&from, from_path.parent().unwrap())
-				.unwrap()
-				.to_path_buf();
-
-		if full_importer.extension().is_some() {
-			get_module(&full_importer, None, environment, checking_data)
-		} else {
-			let mut result = None;
-			for ext in ["ts", "tsx", "js"] {
-				full_importer.set_extension(ext);
-				// TODO change parse options based on extension
-				result = get_module(&full_importer, None, environment, checking_data);
-				if result.is_some() {
-					break;
-				}
-			}
-			result
-		}
+		let full_importer = resolve_relative_import(&from_path, to_import);
+		get_module_with_extensions(&full_importer, environment, checking_data)
 	} else {
+		let aliased = resolve_path_alias(
+			to_import,
+			&checking_data.options.paths,
+			&checking_data.modules.current_working_directory,
+		);
+		let aliased_path = aliased.iter().find_map(|path| find_module(path, checking_data));
+
 		crate::utils::notify!("Here {}", to_import);
-		let result = get_package_from_node_modules(
+		if let Some(path) = aliased_path {
+			get_module(&path, None, environment, checking_data)
+		} else if let Some(package) = resolve_package(
 			to_import,
+			&from_path,
 			&checking_data.modules.current_working_directory,
 			checking_data.modules.file_reader,
-		);
-		if let Ok((path, definition_file)) = result {
-			crate::utils::notify!("Reading path from package {}", path.display());
-			get_module(&path, definition_file.as_deref(), environment, checking_data)
+		) {
+			crate::utils::notify!(Info; "Reading path from package {}", package.path.display());
+			let path = find_module(&package.path, checking_data);
+			let is_common_js = path.as_ref().is_some_and(|path| {
+				matches!(
+					get_module(path, None, environment, checking_data),
+					Some(Ok(module)) if module.is_common_js
+				)
+			});
+			// Exports from CommonJS are best effort, so use the definition file if there is one
+			let definition_file = package
+				.definition_file
+				.filter(|_| is_common_js || path.is_none())
+				.and_then(|definition_file| find_module(&definition_file, checking_data));
+			match definition_file.or(path) {
+				Some(path) => get_module(&path, None, environment, checking_data),
+				None => None,
+			}
 		} else {
 			None
 		}
@@ -406,3 +733,165 @@ pub fn import_file<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		None => Err(CouldNotOpenFile(PathBuf::from(to_import.to_owned()))),
 	}
 }
+
+/// The namespace object resolved by `import(...)`. Its properties are the values of the exports
+pub(crate) fn create_namespace_object<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	exports: &Exported,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> TypeId {
+	let mut object = ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info);
+	let named = exports
+		.named
+		.iter()
+		.map(|(name, (variable, _))| {
+			(name.clone(), get_value_of_constant_import_variable(*variable, environment))
+		})
+		.collect::<Vec<_>>();
+	let default = exports.default.map(|value| ("default".to_owned(), value));
+	for (name, value) in named.into_iter().chain(default) {
+		object.append(
+			environment,
+			Publicity::Public,
+			PropertyKey::String(name.into()),
+			PropertyValue::Value(value),
+			None,
+		);
+	}
+	object.build_object()
+}
+
+/// Strips Windows verbatim (`\\?\`) prefixes and uses the platform separator throughout. This is so that a file
+/// is registered under a single path (which also reads better in diagnostics)
+#[must_use]
+pub fn normalise_path(path: &Path) -> PathBuf {
+	let path = path.to_string_lossy();
+	let path = if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+		format!(r"\\{rest}")
+	} else if let Some(rest) = path.strip_prefix(r"\\?\") {
+		rest.to_owned()
+	} else {
+		path.into_owned()
+	};
+
+	if cfg!(target_family = "windows") {
+		PathBuf::from(path.replace('/', "\\"))
+	} else {
+		PathBuf::from(path)
+	}
+}
+
+/// Resolves symlinks (and casing, on file systems that do) with [`crate::ReadFromFS::canonicalize`] so
+/// that the same file is not checked twice under different [`crate::SourceId`]s
+#[must_use]
+pub fn canonicalise_path(
+	path: &Path,
+	preserve_symlinks: bool,
+	fs: &impl crate::ReadFromFS,
+) -> PathBuf {
+	if preserve_symlinks {
+		path.to_path_buf()
+	} else {
+		normalise_path(&fs.canonicalize(path))
+	}
+}
+
+/// Comment directive for [`should_skip_checking`]
+pub const SKIP_FILE_DIRECTIVE: &str = "@ezno-skip-file";
+
+/// Skipped files are still synthesised for their exports, but their diagnostics are dropped. For
+/// incrementally adopting checking across a project.
+///
+/// A file is skipped if it starts with a `// @ezno-skip-file` comment, or if its path matches one of
+/// [`crate::TypeCheckOptions::skip_checking`] and none of [`crate::TypeCheckOptions::always_check`]
+#[must_use]
+pub fn should_skip_checking(
+	path: &Path,
+	content: &str,
+	options: &crate::TypeCheckOptions,
+	current_working_directory: &Path,
+) -> bool {
+	let has_directive = content
+		.lines()
+		.map(str::trim)
+		.take_while(|line| line.is_empty() || line.starts_with("//") || line.starts_with("#!"))
+		.filter_map(|line| line.strip_prefix("//"))
+		.any(|comment| comment.trim() == SKIP_FILE_DIRECTIVE);
+
+	if has_directive {
+		return true;
+	}
+	if options.skip_checking.is_empty() {
+		return false;
+	}
+
+	let relative = path.strip_prefix(current_working_directory).unwrap_or(path);
+	let relative = relative.to_string_lossy().replace('\\', "/");
+	let matches = |pattern: &String| glob_matches(pattern.as_bytes(), relative.as_bytes());
+
+	options.skip_checking.iter().any(matches) && !options.always_check.iter().any(matches)
+}
+
+/// Supports `*` (within a directory), `**` (across directories) and `?`
+#[must_use]
+pub fn glob_matches(pattern: &[u8], path: &[u8]) -> bool {
+	match (pattern, path) {
+		([], []) => true,
+		([b'*', b'*', rest @ ..], _) => {
+			// `**/` also matches no directories
+			let rest = rest.strip_prefix(b"/").unwrap_or(rest);
+			(0..=path.len()).any(|idx| glob_matches(rest, &path[idx..]))
+		}
+		([b'*', rest @ ..], _) => {
+			let end = path.iter().position(|c| *c == b'/').unwrap_or(path.len());
+			(0..=end).any(|idx| glob_matches(rest, &path[idx..]))
+		}
+		([b'?', pattern_rest @ ..], [c, path_rest @ ..]) if *c != b'/' => {
+			glob_matches(pattern_rest, path_rest)
+		}
+		([p, pattern_rest @ ..], [c, path_rest @ ..]) if p == c => {
+			glob_matches(pattern_rest, path_rest)
+		}
+		_ => false,
+	}
+}
+
+fn resolve_relative_import(from_path: &Path, to_import: &str) -> PathBuf {
+	let from = PathBuf::from(to_import);
+	let full_importer =
+		path_absolutize::Absolutize::absolutize_from(&from, from_path.parent().unwrap()).unwrap();
+	normalise_path(&full_importer)
+}
+
+/// Returns the exports of a module that has **already been synthesised** (for example through an import).
+/// Used for module augmentation, where the module should not be synthesised again
+pub fn get_synthesised_module_exports<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	to_import: &str,
+	environment: &Environment,
+	checking_data: &CheckingData<T, A>,
+) -> Option<Exported> {
+	let files = &checking_data.modules.files;
+	let from_path = files.get_file_path(environment.get_source());
+	let path = if to_import.starts_with('.') {
+		resolve_relative_import(&from_path, to_import)
+	} else {
+		resolve_package(
+			to_import,
+			&from_path,
+			&checking_data.modules.current_working_directory,
+			checking_data.modules.file_reader,
+		)?
+		.path
+	};
+
+	let preserve_symlinks = checking_data.options.preserve_symlinks;
+	let source = candidate_paths(&path).iter().find_map(|candidate| {
+		files.get_source_at_path(&canonicalise_path(
+			candidate,
+			preserve_symlinks,
+			checking_data.modules.file_reader,
+		))
+	})?;
+
+	checking_data.modules.synthesised_modules.get(&source).map(|module| module.exported.clone())
+}
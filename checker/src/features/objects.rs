@@ -2,14 +2,15 @@ use source_map::SpanWithSource;
 
 use crate::{
 	context::{
-		information::{LocalInformation, Publicity},
+		information::{InformationChain, LocalInformation, Publicity},
 		Environment,
 	},
 	types::{
+		get_readonly_inner,
 		properties::{PropertyKey, PropertyValue},
-		TypeStore,
+		Constructor, ObjectNature, StructureGenerics, TypeStore,
 	},
-	FunctionId, TypeId,
+	FunctionId, Type, TypeId,
 };
 
 use super::functions::ThisValue;
@@ -47,6 +48,82 @@ impl ObjectBuilder {
 	}
 }
 
+/// Methods on `Array.prototype` that modify the array, so are not available on `readonly T[]`
+const MUTATING_ARRAY_METHODS: &[&str] =
+	&["push", "pop", "shift", "unshift", "splice", "sort", "reverse", "fill", "copyWithin"];
+
+/// Whether the properties of `on` cannot be assigned to. From [`freeze`] or a `Readonly<T>` type
+/// (including `readonly T[]` and `ReadonlyArray<T>`)
+pub(crate) fn is_readonly(on: TypeId, environment: &Environment, types: &TypeStore) -> bool {
+	environment.is_frozen(on) == Some(TypeId::TRUE) || get_readonly_inner(on, types).is_some()
+}
+
+/// Whether `under` is a method like `push` and `on` is a readonly array
+pub(crate) fn is_mutating_method_on_readonly_array(
+	on: TypeId,
+	under: &PropertyKey,
+	environment: &Environment,
+	types: &TypeStore,
+) -> bool {
+	let PropertyKey::String(name) = under else { return false };
+	if !MUTATING_ARRAY_METHODS.contains(&name.as_ref()) {
+		return false;
+	}
+
+	if let Some(inner) = get_readonly_inner(on, types) {
+		is_array_like(inner, environment, types)
+	} else {
+		environment.is_frozen(on) == Some(TypeId::TRUE) && is_array_like(on, environment, types)
+	}
+}
+
+/// `Array<T>`, tuples and array objects
+pub(crate) fn is_array_like(ty: TypeId, info: &impl InformationChain, types: &TypeStore) -> bool {
+	let is_array_object = || {
+		info.get_chain_of_info()
+			.find_map(|info| info.prototypes.get(&ty))
+			.is_some_and(|prototype| *prototype == TypeId::ARRAY_TYPE)
+	};
+	let is_array_generic = matches!(
+		types.get_type_by_id(ty),
+		Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::ARRAY_TYPE,
+			..
+		}))
+	);
+	is_array_generic || types.get_tuple(ty).is_some() || is_array_object()
+}
+
+/// For `Object.freeze` and `as const` (which is `deep`). Properties of frozen objects cannot be
+/// assigned to
+pub(crate) fn freeze(on: TypeId, deep: bool, environment: &mut Environment, types: &TypeStore) {
+	let is_object = matches!(types.get_type_by_id(on), Type::Object(ObjectNature::RealDeal));
+	if !is_object || environment.is_frozen(on).is_some() {
+		return;
+	}
+
+	environment.info.frozen.insert(on, TypeId::TRUE);
+
+	if deep {
+		let mut values = Vec::new();
+		for info in environment.get_chain_of_info() {
+			if let Some(properties) = info.current_properties.get(&on) {
+				values.extend(properties.iter().filter_map(|(_, _, value)| {
+					if let PropertyValue::Value(value) = value {
+						Some(*value)
+					} else {
+						None
+					}
+				}));
+			}
+		}
+
+		for value in values {
+			freeze(value, true, environment, types);
+		}
+	}
+}
+
 #[derive(Clone, Debug, binary_serialize_derive::BinarySerializable)]
 pub enum SpecialObjects {
 	Promise {
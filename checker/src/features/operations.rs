@@ -2,15 +2,17 @@ use derive_enum_from_into::EnumFrom;
 use source_map::{Span, SpanWithSource};
 
 use crate::{
-	diagnostics::{TypeCheckError, TypeStringRepresentation},
+	context::information::{get_properties_on_type, InformationChain},
+	diagnostics::{TypeCheckError, TypeCheckWarning, TypeStringRepresentation},
 	types::{
-		cast_as_number, cast_as_string, is_type_truthy_falsy, new_logical_or_type, Constructor,
-		StructureGenerics, TypeStore,
+		cast_as_number, cast_as_string, get_larger_type, is_type_truthy_falsy, new_logical_or_type,
+		properties::PropertyKey, remove_undefined, Constructor, ObjectNature, StructureGenerics,
+		TypeStore,
 	},
 	CheckingData, Constant, Decidable, Environment, Type, TypeId,
 };
 
-use super::objects::SpecialObjects;
+use super::{objects::SpecialObjects, symbols::to_primitive};
 
 #[derive(Clone, Copy, Debug, binary_serialize_derive::BinarySerializable)]
 pub enum MathematicalAndBitwise {
@@ -28,6 +30,26 @@ pub enum MathematicalAndBitwise {
 	BitwiseOr,
 }
 
+impl MathematicalAndBitwise {
+	#[must_use]
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Add => "+",
+			Self::Subtract => "-",
+			Self::Multiply => "*",
+			Self::Divide => "/",
+			Self::Modulo => "%",
+			Self::Exponent => "**",
+			Self::BitwiseShiftLeft => "<<",
+			Self::BitwiseShiftRight => ">>",
+			Self::BitwiseShiftRightUnsigned => ">>>",
+			Self::BitwiseAnd => "&",
+			Self::BitwiseXOr => "^",
+			Self::BitwiseOr => "|",
+		}
+	}
+}
+
 #[derive(Clone, Copy, Debug, EnumFrom)]
 pub enum PureBinaryOperation {
 	MathematicalAndBitwise(MathematicalAndBitwise),
@@ -48,6 +70,72 @@ pub fn evaluate_pure_binary_operation_handle_errors<
 ) -> TypeId {
 	match operator {
 		PureBinaryOperation::MathematicalAndBitwise(operator) => {
+			// `+` can also concatenate strings, so uses the default hint
+			let hint =
+				if let MathematicalAndBitwise::Add = operator { "default" } else { "number" };
+			let lhs = to_primitive(lhs, hint, lhs_pos, environment, checking_data);
+			let rhs = to_primitive(rhs, hint, rhs_pos, environment, checking_data);
+			let position = lhs_pos
+				.without_source()
+				.union(rhs_pos.without_source())
+				.with_source(environment.get_source());
+
+			// Objects without `[Symbol.toPrimitive]` are converted with `toString`. The operation is
+			// done on the converted values so that checking can continue
+			let converted_lhs =
+				convert_object_operand((lhs, lhs_pos), operator, environment, checking_data);
+			let converted_rhs =
+				convert_object_operand((rhs, rhs_pos), operator, environment, checking_data);
+			if converted_lhs.is_some() || converted_rhs.is_some() {
+				let lhs = converted_lhs.unwrap_or(lhs);
+				let rhs = converted_rhs.unwrap_or(rhs);
+				return evaluate_mathematical_operation(
+					lhs,
+					operator,
+					rhs,
+					&mut checking_data.types,
+					checking_data.options.strict_casts,
+				)
+				.unwrap_or(if let MathematicalAndBitwise::Add = operator {
+					TypeId::STRING_TYPE
+				} else {
+					TypeId::NUMBER_TYPE
+				});
+			}
+
+			// Symbols cannot be implicitly converted, so these throw a `TypeError`
+			let symbol_operand = [(lhs, lhs_pos, rhs), (rhs, rhs_pos, lhs)]
+				.into_iter()
+				.find(|(operand, ..)| is_symbol(*operand, &checking_data.types));
+			if let Some((operand, operand_position, other)) = symbol_operand {
+				let is_concatenation = matches!(operator, MathematicalAndBitwise::Add)
+					&& get_larger_type(other, &checking_data.types) == TypeId::STRING_TYPE;
+				checking_data.diagnostics_container.add_error(TypeCheckError::CannotConvert {
+					operand: TypeStringRepresentation::from_type_id(
+						operand,
+						environment,
+						&checking_data.types,
+						false,
+					),
+					to: if is_concatenation { "string" } else { "number" },
+					position: operand_position,
+				});
+				return if is_concatenation { TypeId::STRING_TYPE } else { TypeId::NUMBER_TYPE };
+			}
+
+			if checking_data.options.advanced_numbers {
+				if let Some(result) = evaluate_bitwise_operation_on_unions(
+					lhs,
+					operator,
+					rhs,
+					position,
+					environment,
+					checking_data,
+				) {
+					return result;
+				}
+			}
+
 			let result = evaluate_mathematical_operation(
 				lhs,
 				operator,
@@ -73,10 +161,7 @@ pub fn evaluate_pure_binary_operation_handle_errors<
 								&checking_data.types,
 								false,
 							),
-							position: lhs_pos
-								.without_source()
-								.union(rhs_pos.without_source())
-								.with_source(environment.get_source()),
+							position,
 						},
 					);
 					TypeId::ERROR_TYPE
@@ -84,6 +169,37 @@ pub fn evaluate_pure_binary_operation_handle_errors<
 			}
 		}
 		PureBinaryOperation::EqualityAndInequality(operator) => {
+			let is_relational = matches!(
+				operator,
+				EqualityAndInequality::GreaterThan
+					| EqualityAndInequality::LessThan
+					| EqualityAndInequality::LessThanOrEqual
+					| EqualityAndInequality::GreaterThanOrEqual
+			);
+			let (lhs, rhs) = if is_relational {
+				let lhs = to_primitive(lhs, "number", lhs_pos, environment, checking_data);
+				let rhs = to_primitive(rhs, "number", rhs_pos, environment, checking_data);
+				check_comparison_operands(
+					(lhs, lhs_pos),
+					operator,
+					(rhs, rhs_pos),
+					environment,
+					checking_data,
+				);
+				(lhs, rhs)
+			} else {
+				if let Some(result) = evaluate_equality_of_disjoint_operands(
+					(lhs, lhs_pos),
+					operator,
+					(rhs, rhs_pos),
+					environment,
+					checking_data,
+				) {
+					return result;
+				}
+				(lhs, rhs)
+			};
+
 			evaluate_equality_inequality_operation(
 				lhs,
 				&operator,
@@ -91,11 +207,165 @@ pub fn evaluate_pure_binary_operation_handle_errors<
 				&mut checking_data.types,
 				checking_data.options.strict_casts,
 			)
-			.unwrap_or(TypeId::ERROR_TYPE)
+			// Relational operators always result in a boolean, even if the operands are converted
+			.unwrap_or(if is_relational { TypeId::BOOLEAN_TYPE } else { TypeId::ERROR_TYPE })
+		}
+	}
+}
+
+fn is_symbol(ty: TypeId, types: &TypeStore) -> bool {
+	matches!(types.get_type_by_id(ty), Type::Constant(Constant::Symbol { .. }))
+		|| get_larger_type(ty, types) == TypeId::SYMBOL_TYPE
+}
+
+/// For an operand that is an object after [`to_primitive`], warns and returns the value it is
+/// converted to: `"[object Object]"` for objects without a prototype or `toString` and `valueOf`
+/// properties, otherwise `string`. `None` if the operand is not an object (or
+/// [`crate::TypeCheckOptions::strict_casts`] is enabled)
+fn convert_object_operand<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	(operand, position): (TypeId, SpanWithSource),
+	operator: MathematicalAndBitwise,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<TypeId> {
+	if checking_data.options.strict_casts {
+		return None;
+	}
+
+	let on = get_larger_type(operand, &checking_data.types);
+	let converted = match checking_data.types.get_type_by_id(on) {
+		Type::Object(ObjectNature::RealDeal) if on == operand => {
+			let has_prototype =
+				environment.get_chain_of_info().any(|info| info.prototypes.contains_key(&on));
+			let is_conversion_method = |key: &PropertyKey| matches!(key, PropertyKey::String(key) if key == "toString" || key == "valueOf");
+			let has_conversion_method =
+				get_properties_on_type(on, &checking_data.types, environment)
+					.iter()
+					.any(|(_, key, _)| is_conversion_method(key));
+			if has_prototype || has_conversion_method {
+				TypeId::STRING_TYPE
+			} else {
+				checking_data
+					.types
+					.new_constant_type(Constant::String("[object Object]".to_owned()))
+			}
+		}
+		Type::Object(_) | Type::SpecialObject(_) => TypeId::STRING_TYPE,
+		_ => return None,
+	};
+
+	checking_data.diagnostics_container.add_warning(TypeCheckWarning::ImplicitConversion {
+		operand: TypeStringRepresentation::from_type_id(
+			operand,
+			environment,
+			&checking_data.types,
+			false,
+		),
+		to: "primitive",
+		operator: operator.as_str(),
+		position,
+	});
+
+	Some(converted)
+}
+
+/// What a relational operator compares operands of this type as. `None` if unknown
+fn comparison_kind(ty: TypeId, types: &TypeStore) -> Option<&'static str> {
+	let on = get_larger_type(ty, types);
+	match on {
+		TypeId::NUMBER_TYPE | TypeId::BIGINT_TYPE => Some("number"),
+		TypeId::STRING_TYPE => Some("string"),
+		TypeId::BOOLEAN_TYPE | TypeId::UNDEFINED_TYPE | TypeId::NULL_TYPE | TypeId::SYMBOL_TYPE => {
+			Some("other")
 		}
+		_ => matches!(types.get_type_by_id(on), Type::Object(_) | Type::SpecialObject(_))
+			.then_some("object"),
 	}
 }
 
+/// Relational operators convert operands that are not both strings to numbers. Warns if this
+/// conversion happens on values that are not numbers
+fn check_comparison_operands<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	(lhs, lhs_pos): (TypeId, SpanWithSource),
+	operator: EqualityAndInequality,
+	(rhs, rhs_pos): (TypeId, SpanWithSource),
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	let (Some(lhs_kind), Some(rhs_kind)) =
+		(comparison_kind(lhs, &checking_data.types), comparison_kind(rhs, &checking_data.types))
+	else {
+		return;
+	};
+
+	let comparable = lhs_kind == rhs_kind && matches!(lhs_kind, "number" | "string");
+	if !comparable {
+		let operator = match operator {
+			EqualityAndInequality::GreaterThan => ">",
+			EqualityAndInequality::LessThan => "<",
+			EqualityAndInequality::LessThanOrEqual => "<=",
+			EqualityAndInequality::GreaterThanOrEqual => ">=",
+			_ => unreachable!(),
+		};
+		checking_data.diagnostics_container.add_warning(TypeCheckWarning::IncompatibleComparison {
+			lhs: TypeStringRepresentation::from_type_id(
+				lhs,
+				environment,
+				&checking_data.types,
+				false,
+			),
+			rhs: TypeStringRepresentation::from_type_id(
+				rhs,
+				environment,
+				&checking_data.types,
+				false,
+			),
+			operator,
+			position: lhs_pos
+				.without_source()
+				.union(rhs_pos.without_source())
+				.with_source(environment.get_source()),
+		});
+	}
+}
+
+/// `===` and `!==` where the operand types have no values in common (using
+/// [`TypeStore::is_disjoint`]). Warns and returns the known result. Only applies when an operand is
+/// dependent, as comparisons of constants are already evaluated
+fn evaluate_equality_of_disjoint_operands<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	(lhs, lhs_pos): (TypeId, SpanWithSource),
+	operator: EqualityAndInequality,
+	(rhs, rhs_pos): (TypeId, SpanWithSource),
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<TypeId> {
+	let (operator, result) = match operator {
+		EqualityAndInequality::StrictEqual => ("===", false),
+		EqualityAndInequality::StrictNotEqual => ("!==", true),
+		_ => return None,
+	};
+
+	let types = &checking_data.types;
+	let is_dependent =
+		types.get_type_by_id(lhs).is_dependent() || types.get_type_by_id(rhs).is_dependent();
+	if !is_dependent || !types.is_disjoint(lhs, rhs, environment) {
+		return None;
+	}
+
+	checking_data.diagnostics_container.add_warning(TypeCheckWarning::NoOverlapInEquality {
+		lhs: TypeStringRepresentation::from_type_id(lhs, environment, types, false),
+		rhs: TypeStringRepresentation::from_type_id(rhs, environment, types, false),
+		operator,
+		result,
+		position: lhs_pos
+			.without_source()
+			.union(rhs_pos.without_source())
+			.with_source(environment.get_source()),
+	});
+
+	Some(if result { TypeId::TRUE } else { TypeId::FALSE })
+}
+
 pub fn evaluate_mathematical_operation(
 	lhs: TypeId,
 	operator: MathematicalAndBitwise,
@@ -110,6 +380,23 @@ pub fn evaluate_mathematical_operation(
 		types: &mut TypeStore,
 		strict_casts: bool,
 	) -> Result<TypeId, ()> {
+		match (get_big_int(lhs, types), get_big_int(rhs, types)) {
+			(Some(lhs), Some(rhs)) => {
+				return attempt_big_int_operation(lhs, operator, rhs, types);
+			}
+			// Mixing `bigint` and other values throws a `TypeError`, apart from concatenation
+			(Some(_), None) | (None, Some(_)) => {
+				let is_string =
+					|ty| matches!(types.get_type_by_id(ty), Type::Constant(Constant::String(_)));
+				if !(matches!(operator, MathematicalAndBitwise::Add)
+					&& (is_string(lhs) || is_string(rhs)))
+				{
+					return Err(());
+				}
+			}
+			(None, None) => {}
+		}
+
 		if let MathematicalAndBitwise::Add = operator {
 			let constant = match (types.get_type_by_id(lhs), types.get_type_by_id(rhs)) {
 				(Type::Constant(Constant::Number(lhs)), Type::Constant(Constant::Number(rhs))) => {
@@ -128,6 +415,10 @@ pub fn evaluate_mathematical_operation(
 		} else {
 			match (types.get_type_by_id(lhs), types.get_type_by_id(rhs)) {
 				(Type::Constant(c1), Type::Constant(c2)) => {
+					// Symbols cannot be converted to numbers
+					if let (Constant::Symbol { .. }, _) | (_, Constant::Symbol { .. }) = (c1, c2) {
+						return Err(());
+					}
 					let lhs = cast_as_number(c1, strict_casts).unwrap_or(f64::NAN);
 					let rhs = cast_as_number(c2, strict_casts).unwrap_or(f64::NAN);
 					// TODO hopefully Rust implementation is the same as JS
@@ -183,6 +474,237 @@ pub fn evaluate_mathematical_operation(
 	attempt_constant_math_operator(lhs, operator, rhs, types, strict_casts)
 }
 
+/// For [`crate::TypeCheckOptions::advanced_numbers`]. `None` if `operator` is not `&`, `|` or `^`,
+/// an operand is not a union of integer constants or an operand is dependent (in which case
+/// the operation is evaluated when it is substituted)
+fn evaluate_bitwise_operation_on_unions<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	lhs: TypeId,
+	operator: MathematicalAndBitwise,
+	rhs: TypeId,
+	position: SpanWithSource,
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<TypeId> {
+	/// Limit on the number of results
+	const MAXIMUM_COMBINATIONS: usize = 64;
+
+	let operation: fn(i32, i32) -> i32 = match operator {
+		MathematicalAndBitwise::BitwiseAnd => |lhs, rhs| lhs & rhs,
+		MathematicalAndBitwise::BitwiseOr => |lhs, rhs| lhs | rhs,
+		MathematicalAndBitwise::BitwiseXOr => |lhs, rhs| lhs ^ rhs,
+		_ => return None,
+	};
+
+	let types = &checking_data.types;
+	// Dependent operands use their constraint (for example the annotation of a parameter)
+	let lhs_base = crate::types::get_constraint(lhs, types).unwrap_or(lhs);
+	let rhs_base = crate::types::get_constraint(rhs, types).unwrap_or(rhs);
+	let lhs_members = get_integer_constant_members(lhs_base, types)?;
+	let rhs_members = get_integer_constant_members(rhs_base, types)?;
+	if lhs_members.len() * rhs_members.len() > MAXIMUM_COMBINATIONS {
+		return None;
+	}
+
+	let mut results = Vec::new();
+	for lhs in &lhs_members {
+		for rhs in &rhs_members {
+			let result = operation(*lhs, *rhs);
+			if !results.contains(&result) {
+				results.push(result);
+			}
+		}
+	}
+
+	if let (MathematicalAndBitwise::BitwiseAnd, [0]) = (operator, results.as_slice()) {
+		let debug = checking_data.options.debug_types;
+		checking_data.diagnostics_container.add_warning(TypeCheckWarning::ImpossibleBitmask {
+			value: TypeStringRepresentation::from_type_id(lhs_base, environment, types, debug),
+			mask: TypeStringRepresentation::from_type_id(rhs_base, environment, types, debug),
+			position,
+		});
+	}
+
+	if is_substituted(lhs, types) || is_substituted(rhs, types) {
+		return None;
+	}
+
+	let types = &mut checking_data.types;
+	let results = results
+		.into_iter()
+		.map(|result| types.new_constant_type(Constant::Number(result.into())))
+		.collect::<Vec<_>>();
+	results.into_iter().reduce(|acc, ty| new_logical_or_type(acc, ty, types))
+}
+
+/// For [`crate::TypeCheckOptions::advanced_numbers`]. When `condition` is `value & mask` (or it
+/// compared to `0`), where `value` is dependent and constrained to a union of integer constants,
+/// splits the members of `value` by whether the condition holds. Returns `value` and its narrowed
+/// type in the truthy and falsy branch. `None` if some, but not all, members are in each branch
+pub(crate) fn narrow_bitmask_condition(
+	condition: TypeId,
+	types: &mut TypeStore,
+) -> Option<(TypeId, TypeId, TypeId)> {
+	/// `value`, the mask and whether the condition is `value & mask !== 0`
+	fn get_bitmask(condition: TypeId, types: &TypeStore) -> Option<(TypeId, Vec<i32>, bool)> {
+		match types.get_type_by_id(condition) {
+			Type::Constructor(Constructor::UnaryOperator {
+				operator: PureUnary::LogicalNot,
+				operand,
+			}) => {
+				get_bitmask(*operand, types).map(|(value, mask, non_zero)| (value, mask, !non_zero))
+			}
+			Type::Constructor(Constructor::CanonicalRelationOperator {
+				lhs,
+				operator: CanonicalEqualityAndInequality::StrictEqual,
+				rhs,
+			}) => {
+				let ((TypeId::ZERO, operation) | (operation, TypeId::ZERO)) = (*lhs, *rhs) else {
+					return None;
+				};
+				get_bitmask(operation, types)
+					.map(|(value, mask, non_zero)| (value, mask, !non_zero))
+			}
+			Type::Constructor(Constructor::BinaryOperator {
+				lhs,
+				operator: MathematicalAndBitwise::BitwiseAnd,
+				rhs,
+			}) => {
+				let (value, mask) = if types.get_type_by_id(*lhs).is_dependent() {
+					(*lhs, *rhs)
+				} else {
+					(*rhs, *lhs)
+				};
+				if types.get_type_by_id(mask).is_dependent() {
+					return None;
+				}
+				Some((value, get_integer_constant_members(mask, types)?, true))
+			}
+			_ => None,
+		}
+	}
+
+	let (value, mask, non_zero) = get_bitmask(condition, types)?;
+	let members = get_integer_constant_members(crate::types::get_constraint(value, types)?, types)?;
+
+	let (mut truthy, mut falsy) = (Vec::new(), Vec::new());
+	for member in members {
+		if mask.iter().any(|mask| member & mask != 0) == non_zero {
+			truthy.push(member);
+		} else {
+			falsy.push(member);
+		}
+	}
+
+	if truthy.is_empty() || falsy.is_empty() {
+		return None;
+	}
+
+	let narrow = |members: &[i32], types: &mut TypeStore| {
+		let members = members
+			.iter()
+			.map(|member| types.new_constant_type(Constant::Number((*member).into())))
+			.collect::<Vec<_>>();
+		let union = members.into_iter().reduce(|acc, ty| types.new_or_type(acc, ty)).unwrap();
+		types.register_type(Type::RootPolyType(crate::types::PolyNature::Open(union)))
+	};
+	let truthy = narrow(&truthy, types);
+	let falsy = narrow(&falsy, types);
+	Some((value, truthy, falsy))
+}
+
+/// Whether `ty` is dependent on something that is replaced when calling the function it is in. The
+/// results of calling functions with no dependent arguments (such as declared functions) are not
+fn is_substituted(ty: TypeId, types: &TypeStore) -> bool {
+	match types.get_type_by_id(ty) {
+		Type::Constructor(Constructor::Image { on, with, .. }) => {
+			types.get_type_by_id(*on).is_dependent()
+				|| with.iter().any(|argument| types.get_type_by_id(argument.value).is_dependent())
+		}
+		ty => ty.is_dependent(),
+	}
+}
+
+/// `None` if `ty` is not a number constant (that is an [`i32`]) or a union of them
+fn get_integer_constant_members(ty: TypeId, types: &TypeStore) -> Option<Vec<i32>> {
+	match types.get_type_by_id(ty) {
+		// `as` saturates, so the value is only used if it is unchanged
+		#[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+		Type::Constant(Constant::Number(value)) => {
+			let value = value.into_inner();
+			let integer = value as i32;
+			(f64::from(integer) == value).then(|| vec![integer])
+		}
+		Type::Or(lhs, rhs) => {
+			let mut members = get_integer_constant_members(*lhs, types)?;
+			members.extend(get_integer_constant_members(*rhs, types)?);
+			Some(members)
+		}
+		Type::AliasTo { to, parameters: None, .. } => get_integer_constant_members(*to, types),
+		_ => None,
+	}
+}
+
+/// `Some(None)` for `bigint` values that are not known
+#[allow(clippy::option_option)]
+fn get_big_int(ty: TypeId, types: &TypeStore) -> Option<Option<i128>> {
+	match types.get_type_by_id(ty) {
+		Type::Constant(Constant::BigInt(value)) => Some(Some(*value)),
+		_ if ty == TypeId::BIGINT_TYPE => Some(None),
+		_ => None,
+	}
+}
+
+/// Results that do not fit in an [`i128`] are [`TypeId::BIGINT_TYPE`]. Errors for operations that throw
+/// (division by zero, negative exponents and `>>>`)
+fn attempt_big_int_operation(
+	lhs: Option<i128>,
+	operator: MathematicalAndBitwise,
+	rhs: Option<i128>,
+	types: &mut TypeStore,
+) -> Result<TypeId, ()> {
+	/// Arithmetic (so rounds towards negative infinity for negative `shift`)
+	fn shift_left(value: i128, shift: i128) -> Option<i128> {
+		if shift < 0 {
+			let shift = u32::try_from(shift.checked_neg()?).unwrap_or(u32::MAX);
+			Some(value >> shift.min(i128::BITS - 1))
+		} else {
+			2i128.checked_pow(u32::try_from(shift).ok()?)?.checked_mul(value)
+		}
+	}
+
+	let (Some(lhs), Some(rhs)) = (lhs, rhs) else {
+		return if let MathematicalAndBitwise::BitwiseShiftRightUnsigned = operator {
+			Err(())
+		} else {
+			Ok(TypeId::BIGINT_TYPE)
+		};
+	};
+	let value = match operator {
+		MathematicalAndBitwise::Add => lhs.checked_add(rhs),
+		MathematicalAndBitwise::Subtract => lhs.checked_sub(rhs),
+		MathematicalAndBitwise::Multiply => lhs.checked_mul(rhs),
+		MathematicalAndBitwise::Divide | MathematicalAndBitwise::Modulo if rhs == 0 => {
+			return Err(());
+		}
+		// Both truncate (like JS)
+		MathematicalAndBitwise::Divide => lhs.checked_div(rhs),
+		MathematicalAndBitwise::Modulo => lhs.checked_rem(rhs),
+		MathematicalAndBitwise::Exponent if rhs < 0 => return Err(()),
+		MathematicalAndBitwise::Exponent => {
+			u32::try_from(rhs).ok().and_then(|rhs| lhs.checked_pow(rhs))
+		}
+		MathematicalAndBitwise::BitwiseShiftLeft => shift_left(lhs, rhs),
+		MathematicalAndBitwise::BitwiseShiftRight => {
+			rhs.checked_neg().and_then(|rhs| shift_left(lhs, rhs))
+		}
+		MathematicalAndBitwise::BitwiseAnd => Some(lhs & rhs),
+		MathematicalAndBitwise::BitwiseXOr => Some(lhs ^ rhs),
+		MathematicalAndBitwise::BitwiseOr => Some(lhs | rhs),
+		MathematicalAndBitwise::BitwiseShiftRightUnsigned => return Err(()),
+	};
+	Ok(value.map_or(TypeId::BIGINT_TYPE, |value| types.new_constant_type(Constant::BigInt(value))))
+}
+
 /// Not canonical / reducible
 #[derive(Clone, Copy, Debug)]
 pub enum EqualityAndInequality {
@@ -421,14 +943,55 @@ pub fn evaluate_logical_operation_with_expression<
 				&mut checking_data.types,
 				checking_data.options.strict_casts,
 			)?;
-			Ok(environment.new_conditional_context(
-				(is_lhs_null, lhs.1),
+			let is_lhs_undefined = evaluate_equality_inequality_operation(
+				lhs.0,
+				&EqualityAndInequality::StrictEqual,
+				TypeId::UNDEFINED_TYPE,
+				&mut checking_data.types,
+				checking_data.options.strict_casts,
+			)?;
+			let condition = match (is_lhs_null, is_lhs_undefined) {
+				(TypeId::FALSE, other) | (other, TypeId::FALSE) => other,
+				(is_lhs_null, is_lhs_undefined) => {
+					new_logical_or_type(is_lhs_null, is_lhs_undefined, &mut checking_data.types)
+				}
+			};
+			// The left hand side is only the result when it is not `undefined`. Dependent values are
+			// kept (so they are substituted), with `undefined` removed from the constraint instead
+			let defined_lhs = remove_undefined(lhs.0, &mut checking_data.types);
+			let is_dependent = checking_data.types.get_type_by_id(lhs.0).is_dependent();
+			let otherwise_result = if is_dependent { lhs.0 } else { defined_lhs.unwrap_or(lhs.0) };
+			let result = environment.new_conditional_context(
+				(condition, lhs.1),
 				|env: &mut Environment, data: &mut CheckingData<T, A>| {
 					A::synthesise_expression(rhs, TypeId::ANY_TYPE, env, data)
 				},
-				Some(|_env: &mut Environment, _data: &mut CheckingData<T, A>| lhs.0),
+				Some(|_env: &mut Environment, _data: &mut CheckingData<T, A>| otherwise_result),
 				checking_data,
-			))
+			);
+			let (true, Some(defined_lhs)) = (is_dependent, defined_lhs) else {
+				return Ok(result);
+			};
+			let types = &mut checking_data.types;
+			if let Type::Constructor(Constructor::ConditionalResult {
+				condition,
+				truthy_result,
+				otherwise_result,
+				result_union: _,
+			}) = types.get_type_by_id(result)
+			{
+				let (condition, truthy_result, otherwise_result) =
+					(*condition, *truthy_result, *otherwise_result);
+				let result_union = types.new_or_type(truthy_result, defined_lhs);
+				Ok(types.register_type(Type::Constructor(Constructor::ConditionalResult {
+					condition,
+					truthy_result,
+					otherwise_result,
+					result_union,
+				})))
+			} else {
+				Ok(result)
+			}
 		}
 	}
 }
@@ -464,7 +1027,16 @@ pub fn evaluate_pure_unary_operator(
 			}
 		}
 		PureUnary::Negation | PureUnary::BitwiseNot => {
-			if let Type::Constant(cst) = types.get_type_by_id(operand) {
+			if let Type::Constant(Constant::BigInt(value)) = types.get_type_by_id(operand) {
+				let value = match operator {
+					PureUnary::LogicalNot => unreachable!(),
+					PureUnary::Negation => value.checked_neg(),
+					PureUnary::BitwiseNot => Some(!value),
+				};
+				Ok(value.map_or(TypeId::BIGINT_TYPE, |value| {
+					types.new_constant_type(Constant::BigInt(value))
+				}))
+			} else if let Type::Constant(cst) = types.get_type_by_id(operand) {
 				let value = cast_as_number(cst, strict_casts)?;
 				let value = match operator {
 					PureUnary::LogicalNot => unreachable!(),
@@ -485,6 +1057,50 @@ pub fn evaluate_pure_unary_operator(
 	}
 }
 
+/// `+operand`, which converts `operand` to a number. Warns if `operand` is not a number
+pub fn evaluate_unary_plus<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	(operand, position): (TypeId, SpanWithSource),
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> TypeId {
+	let operand = to_primitive(operand, "number", position, environment, checking_data);
+	let on = get_larger_type(operand, &checking_data.types);
+	if operand == TypeId::ERROR_TYPE || on == TypeId::NUMBER_TYPE {
+		return operand;
+	}
+	if on == TypeId::ANY_TYPE {
+		return TypeId::NUMBER_TYPE;
+	}
+
+	let operand_representation =
+		TypeStringRepresentation::from_type_id(operand, environment, &checking_data.types, false);
+	if on == TypeId::BIGINT_TYPE || is_symbol(operand, &checking_data.types) {
+		checking_data.diagnostics_container.add_error(TypeCheckError::CannotConvert {
+			operand: operand_representation,
+			to: "number",
+			position,
+		});
+		return TypeId::NUMBER_TYPE;
+	}
+
+	checking_data.diagnostics_container.add_warning(TypeCheckWarning::ImplicitConversion {
+		operand: operand_representation,
+		to: "number",
+		operator: "+",
+		position,
+	});
+
+	if let Type::Constant(constant) = checking_data.types.get_type_by_id(operand) {
+		let value = cast_as_number(constant, false).unwrap_or(f64::NAN);
+		match ordered_float::NotNan::try_from(value) {
+			Ok(value) => checking_data.types.new_constant_type(Constant::Number(value)),
+			Err(_) => TypeId::NAN_TYPE,
+		}
+	} else {
+		TypeId::NUMBER_TYPE
+	}
+}
+
 fn attempt_constant_equality(
 	lhs: TypeId,
 	rhs: TypeId,
@@ -496,7 +1112,8 @@ fn attempt_constant_equality(
 		let lhs = types.get_type_by_id(lhs);
 		let rhs = types.get_type_by_id(rhs);
 		if let (Type::Constant(cst1), Type::Constant(cst2)) = (lhs, rhs) {
-			cst1 == cst2
+			// Symbols are unique, so are only equal to themselves (the `lhs == rhs` case)
+			cst1 == cst2 && !matches!(cst1, Constant::Symbol { .. })
 		} else if let (Type::Object(..) | Type::SpecialObject(SpecialObjects::Function(..)), _)
 		| (_, Type::Object(..) | Type::SpecialObject(SpecialObjects::Function(..))) = (lhs, rhs)
 		{
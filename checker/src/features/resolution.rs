@@ -0,0 +1,252 @@
+//! Resolving import specifiers to files, following Node. Packages are found in the `node_modules`
+//! directories of the importing file's directory and its ancestors, with their entry from the
+//! `exports`, `main` and `types` fields of `package.json`. Paths without an extension are probed
+//! with [`EXTENSIONS`] and as directories (with `index` files). Aliases from
+//! [`crate::TypeCheckOptions::paths`] are tried before packages.
+//!
+//! Files are only read through [`crate::ReadFromFS`], so resolution works on virtual file systems
+
+use std::path::{Path, PathBuf};
+
+use simple_json_parser::{JSONKey, RootJSONValue};
+
+use super::modules::normalise_path;
+
+/// Extensions tried (in order) for paths without one
+pub const EXTENSIONS: [&str; 4] = ["ts", "tsx", "d.ts", "js"];
+
+/// Paths with these extensions are not probed with [`EXTENSIONS`]
+const SCRIPT_EXTENSIONS: [&str; 9] = ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "json"];
+
+/// Conditions in `exports` that match. As in Node, the first matching condition (in the order they
+/// are written in `package.json`) is used
+const CONDITIONS: [&str; 5] = ["types", "import", "require", "node", "default"];
+
+/// The files that `path` may refer to, in order of preference
+#[must_use]
+pub fn candidate_paths(path: &Path) -> Vec<PathBuf> {
+	let extension = path.extension().and_then(std::ffi::OsStr::to_str);
+	if let Some(extension) = extension.filter(|extension| SCRIPT_EXTENSIONS.contains(extension)) {
+		let mut candidates = vec![path.to_path_buf()];
+		// TypeScript sources are imported with the extension of their output
+		if extension == "js" {
+			candidates
+				.extend(["ts", "tsx", "d.ts"].map(|extension| path.with_extension(extension)));
+		}
+		candidates
+	} else {
+		let with_extensions = EXTENSIONS.iter().map(|extension| {
+			let mut path = path.as_os_str().to_owned();
+			path.push(".");
+			path.push(extension);
+			PathBuf::from(path)
+		});
+		let index_files =
+			EXTENSIONS.iter().map(|extension| path.join(format!("index.{extension}")));
+		with_extensions.chain(index_files).collect()
+	}
+}
+
+/// The entry of a package for an import
+#[derive(Debug, PartialEq, Eq)]
+pub struct ResolvedPackage {
+	/// Probed with [`candidate_paths`]
+	pub path: PathBuf,
+	/// From `types` (or the `types` condition in `exports`). Used when `path` is a `CommonJS` module
+	pub definition_file: Option<PathBuf>,
+}
+
+/// Resolves a package specifier (`name`, `name/sub/path`, `@scope/name` or `@scope/name/sub/path`)
+/// imported from the file at `from`. Relative `from` paths are from `cwd`.
+///
+/// If the package is not found, its definitions are looked for in `@types`
+pub fn resolve_package<T: crate::ReadFromFS>(
+	specifier: &str,
+	from: &Path,
+	cwd: &Path,
+	fs_reader: &T,
+) -> Option<ResolvedPackage> {
+	let (name, subpath) = split_package_specifier(specifier)?;
+	let from = cwd.join(from);
+	let mut directories = from.parent()?.ancestors();
+
+	let types_name = name.strip_prefix('@').map_or_else(
+		|| name.to_owned(),
+		// `@scope/name` has definitions under `@types/scope__name`
+		|scoped| scoped.replacen('/', "__", 1),
+	);
+
+	directories
+		.clone()
+		.find_map(|directory| {
+			let root = directory.join("node_modules").join(name);
+			resolve_in_package(&root, &subpath, fs_reader)
+		})
+		.or_else(|| {
+			directories.find_map(|directory| {
+				let root = directory.join("node_modules").join("@types").join(&types_name);
+				resolve_in_package(&root, &subpath, fs_reader)
+			})
+		})
+}
+
+/// Paths for a (non relative) import from [`crate::TypeCheckOptions::paths`], with targets relative
+/// to `cwd`. As in TypeScript, a pattern without a `*` that is the specifier is used over patterns
+/// with one, and otherwise the pattern with the longest prefix before the `*` is used
+#[must_use]
+pub fn resolve_path_alias(
+	specifier: &str,
+	paths: &[(String, Vec<String>)],
+	cwd: &Path,
+) -> Vec<PathBuf> {
+	let exact = paths.iter().find(|(pattern, _)| pattern == specifier);
+	let (targets, matched) = if let Some((_, targets)) = exact {
+		(targets, "")
+	} else {
+		let longest = paths
+			.iter()
+			.filter_map(|(pattern, targets)| {
+				let (prefix, suffix) = pattern.split_once('*')?;
+				let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+				Some((prefix.len(), targets, matched))
+			})
+			// The first of the longest
+			.rev()
+			.max_by_key(|(prefix_length, ..)| *prefix_length);
+		let Some((_, targets, matched)) = longest else { return Vec::new() };
+		(targets, matched)
+	};
+	targets.iter().map(|target| cwd.join(target.replacen('*', matched, 1))).collect()
+}
+
+/// `None` for array items
+fn object_key(key: &JSONKey) -> Option<String> {
+	match key {
+		JSONKey::Slice(key) => Some((*key).to_owned()),
+		JSONKey::Index(_) => None,
+	}
+}
+
+/// `(name, subpath)` where `subpath` is `"."` for the package itself and otherwise `"./sub/path"`
+fn split_package_specifier(specifier: &str) -> Option<(&str, String)> {
+	let name_end = if specifier.starts_with('@') {
+		let scope_end = specifier.find('/')?;
+		specifier[scope_end + 1..].find('/').map(|idx| scope_end + 1 + idx)
+	} else {
+		specifier.find('/')
+	};
+	Some(match name_end {
+		Some(end) => (&specifier[..end], format!(".{}", &specifier[end..])),
+		None => (specifier, ".".to_owned()),
+	})
+}
+
+/// The fields of `package.json` used for resolution
+#[derive(Default)]
+struct PackageFields {
+	main: Option<String>,
+	types: Option<String>,
+	/// The keys to each target in `exports`. `None` keys are array items (fallbacks)
+	exports: Vec<(Vec<Option<String>>, String)>,
+}
+
+fn resolve_in_package<T: crate::ReadFromFS>(
+	root: &Path,
+	subpath: &str,
+	fs_reader: &T,
+) -> Option<ResolvedPackage> {
+	let package_json = fs_reader.read_file(&root.join("package.json"))?;
+	let package_json = String::from_utf8(package_json).ok()?;
+
+	let mut fields = PackageFields::default();
+	// TODO JSON parse error
+	let _res = simple_json_parser::parse_with_exit_signal(&package_json, |keys, value| {
+		let RootJSONValue::String(value) = value else { return false };
+		match keys {
+			[JSONKey::Slice("main")] => {
+				fields.main = Some(value.to_owned());
+			}
+			[JSONKey::Slice("types" | "typings")] => {
+				fields.types.get_or_insert_with(|| value.to_owned());
+			}
+			[JSONKey::Slice("exports"), keys @ ..] => {
+				let keys = keys.iter().map(object_key).collect();
+				fields.exports.push((keys, value.to_owned()));
+			}
+			_ => {}
+		}
+		false
+	});
+
+	let (path, types) = if fields.exports.is_empty() {
+		if subpath == "." {
+			// Packages with only definitions (such as those in `@types`) do not have a `main`
+			let main = fields.main.or_else(|| fields.types.clone());
+			(Some(main.unwrap_or_else(|| "index".to_owned())), fields.types)
+		} else {
+			(Some(subpath.to_owned()), None)
+		}
+	} else {
+		// Only what is in `exports` can be imported
+		let (path, types) = resolve_exports(&fields.exports, subpath);
+		(path, types.or(fields.types.filter(|_| subpath == ".")))
+	};
+
+	let join = |path: &str| normalise_path(&root.join(path.trim_start_matches("./")));
+	let path = path.or_else(|| types.clone())?;
+	Some(ResolvedPackage { path: join(&path), definition_file: types.as_deref().map(join) })
+}
+
+/// The `(path, definition file)` targets of `subpath`. Exact subpaths are preferred over patterns
+fn resolve_exports(
+	exports: &[(Vec<Option<String>>, String)],
+	subpath: &str,
+) -> (Option<String>, Option<String>) {
+	// Either an object of subpaths (keys starting with `.`) or the conditions (or target) for `.`
+	let has_subpaths = exports
+		.iter()
+		.any(|(keys, _)| matches!(keys.first(), Some(Some(key)) if key.starts_with('.')));
+
+	let find = |patterns: bool| {
+		let (mut path, mut types) = (None, None);
+		for (keys, target) in exports {
+			let (conditions, target) = if has_subpaths {
+				let Some(Some(key)) = keys.first() else { continue };
+				if key.contains('*') != patterns {
+					continue;
+				}
+				let Some(target) = match_subpath(key, subpath, target) else { continue };
+				(&keys[1..], target)
+			} else if subpath == "." && !patterns {
+				(keys.as_slice(), target.clone())
+			} else {
+				continue;
+			};
+
+			let mut is_types = false;
+			let matches = conditions.iter().flatten().all(|condition| {
+				is_types |= condition == "types";
+				CONDITIONS.contains(&condition.as_str())
+			});
+			if matches {
+				if is_types { &mut types } else { &mut path }.get_or_insert(target);
+			}
+		}
+		(path, types)
+	};
+
+	match find(false) {
+		(None, None) => find(true),
+		found => found,
+	}
+}
+
+/// The target for `subpath` if it matches `key`, which can contain a `*`
+fn match_subpath(key: &str, subpath: &str, target: &str) -> Option<String> {
+	if let Some((prefix, suffix)) = key.split_once('*') {
+		let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+		Some(target.replace('*', matched))
+	} else {
+		(key == subpath).then(|| target.to_owned())
+	}
+}
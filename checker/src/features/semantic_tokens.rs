@@ -0,0 +1,110 @@
+//! Classification of identifiers by what they resolve to (for semantic highlighting). Recorded
+//! while synthesising when [`crate::TypeCheckOptions::store_expression_type_mappings`] is enabled.
+//! References to variables, types and properties are recorded, along with the declarations of
+//! parameters and enum members
+
+use source_map::{SourceId, Span, SpanWithSource};
+
+use crate::{
+	features::{objects::SpecialObjects, variables::VariableOrImport},
+	types::{PolyNature, TypeStore},
+	CheckOutput, CheckingData, Type, TypeId,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize), serde(rename_all = "camelCase"))]
+pub enum SemanticTokenKind {
+	Variable,
+	Parameter,
+	Function,
+	Class,
+	Interface,
+	TypeAlias,
+	TypeParameter,
+	EnumMember,
+	Property,
+	/// On a frozen object or a `Readonly<T>` (including `readonly T[]`)
+	ReadonlyProperty,
+	/// A variable or type that could not be found (or a variable used where it is not defined)
+	Unresolved,
+}
+
+/// An identifier in a module. From [`CheckOutput::get_semantic_tokens`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+pub struct SemanticToken {
+	pub position: SpanWithSource,
+	pub kind: SemanticTokenKind,
+}
+
+impl<A: crate::ASTImplementation> CheckOutput<A> {
+	/// The classified identifiers of the module at `path`, in order. `None` if the module was not
+	/// checked. Empty unless [`crate::TypeCheckOptions::store_expression_type_mappings`]
+	#[must_use]
+	pub fn get_semantic_tokens(&self, path: &str) -> Option<Vec<SemanticToken>> {
+		let source = self.module_contents.get_source_at_path(path.as_ref())?;
+		let module = self.modules.get(&source)?;
+		let mut tokens: Vec<SemanticToken> = Vec::new();
+		for (range, kind) in module.mappings.semantic_tokens.iter() {
+			// The first for a position is kept (some parts are synthesised more than once)
+			if tokens.last().is_some_and(|last| last.position.start == range.start) {
+				continue;
+			}
+			let position = SpanWithSource { start: range.start, end: range.end, source };
+			tokens.push(SemanticToken { position, kind: *kind });
+		}
+		Some(tokens)
+	}
+}
+
+impl<T: crate::ReadFromFS, A: crate::ASTImplementation> CheckingData<'_, T, A> {
+	pub(crate) fn add_semantic_token(&mut self, position: Span, kind: SemanticTokenKind) {
+		if self.options.store_expression_type_mappings {
+			self.local_type_mappings.semantic_tokens.push(position, kind);
+		}
+	}
+
+	/// For a reference in `source` to `variable` (which has `value`). Global object properties do
+	/// not have a variable
+	pub(crate) fn get_variable_token_kind(
+		&self,
+		variable: Option<&VariableOrImport>,
+		value: TypeId,
+		source: SourceId,
+	) -> SemanticTokenKind {
+		if value == TypeId::ERROR_TYPE && variable.is_none() {
+			return SemanticTokenKind::Unresolved;
+		}
+		if let Some(VariableOrImport::Variable { declared_at, .. }) = variable {
+			// Parameters are recorded when they are registered (before references to them)
+			let declared_in_module = declared_at.source == source;
+			if declared_in_module
+				&& self.local_type_mappings.semantic_tokens.get_exact(*declared_at)
+					== Some(&SemanticTokenKind::Parameter)
+			{
+				return SemanticTokenKind::Parameter;
+			}
+		}
+		match self.types.get_type_by_id(value) {
+			Type::SpecialObject(SpecialObjects::ClassConstructor { .. }) => {
+				SemanticTokenKind::Class
+			}
+			Type::SpecialObject(SpecialObjects::Function(..)) | Type::FunctionReference(..) => {
+				SemanticTokenKind::Function
+			}
+			_ => SemanticTokenKind::Variable,
+		}
+	}
+}
+
+/// For a reference to the type `ty`
+pub(crate) fn get_type_token_kind(ty: TypeId, types: &TypeStore) -> SemanticTokenKind {
+	match types.get_type_by_id(ty) {
+		Type::AliasTo { .. } => SemanticTokenKind::TypeAlias,
+		Type::Class { .. } => SemanticTokenKind::Class,
+		Type::RootPolyType(
+			PolyNature::StructureGeneric { .. } | PolyNature::FunctionGeneric { .. },
+		) => SemanticTokenKind::TypeParameter,
+		_ => SemanticTokenKind::Interface,
+	}
+}
@@ -0,0 +1,267 @@
+//! Protocols that dispatch on well-known symbols: `Symbol.iterator` (`for of`),
+//! `Symbol.toPrimitive` (coercion in operators) and `Symbol.hasInstance` (`instanceof`, with the
+//! ordinary prototype chain check as the fallback)
+
+use std::borrow::Cow;
+
+use source_map::SpanWithSource;
+
+use crate::{
+	context::{
+		information::{get_property_unbound, InformationChain, Publicity},
+		invocation::CheckThings,
+		Logical,
+	},
+	diagnostics::TypeCheckError,
+	features::objects::SpecialObjects,
+	types::{
+		calling::{call_type, CalledWithNew, CallingInput},
+		get_constraint,
+		properties::PropertyKey,
+		Constructor, ObjectNature, SynthesisedArgument, TypeStore,
+	},
+	CheckingData, Constant, Environment, PropertyValue, Type, TypeId,
+};
+
+/// Calls the method under `key` on `on`. Returns `None` if there is no property under `key`
+pub(crate) fn call_method<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	on: TypeId,
+	key: &PropertyKey,
+	arguments: Vec<SynthesisedArgument>,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<TypeId> {
+	let (_, method) = environment.get_property(
+		on,
+		Publicity::Public,
+		key,
+		&mut checking_data.types,
+		None,
+		position.without_source(),
+		&checking_data.options,
+	)?;
+
+	let input = CallingInput {
+		called_with_new: CalledWithNew::None,
+		call_site: position,
+		call_site_type_arguments: None,
+	};
+	let mut check_things = CheckThings { debug_types: checking_data.options.debug_types };
+
+	match call_type(
+		method,
+		arguments,
+		&input,
+		environment,
+		&mut check_things,
+		&mut checking_data.types,
+	) {
+		Ok(result) => Some(result.returned_type),
+		Err(errors) => {
+			for error in errors {
+				checking_data
+					.diagnostics_container
+					.add_error(TypeCheckError::FunctionCallingError(error));
+			}
+			Some(TypeId::ERROR_TYPE)
+		}
+	}
+}
+
+/// <https://tc39.es/ecma262/multipage/abstract-operations.html#sec-toprimitive>
+///
+/// `hint` is one of `"default"`, `"number"` or `"string"`. Objects are converted with their
+/// `[Symbol.toPrimitive]` method, else with [`ordinary_to_primitive`]. Everything else (and objects
+/// that cannot be converted) is returned as is
+pub(crate) fn to_primitive<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	value: TypeId,
+	hint: &'static str,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> TypeId {
+	if !matches!(checking_data.types.get_type_by_id(value), Type::Object(..)) {
+		return value;
+	}
+
+	let hint_type = checking_data.types.new_constant_type(Constant::String(hint.to_owned()));
+	let arguments = vec![SynthesisedArgument { value: hint_type, position, spread: false }];
+
+	call_method(
+		value,
+		&PropertyKey::Type(TypeId::SYMBOL_TO_PRIMITIVE),
+		arguments,
+		position,
+		environment,
+		checking_data,
+	)
+	.or_else(|| ordinary_to_primitive(value, hint, position, environment, checking_data))
+	.unwrap_or(value)
+}
+
+/// <https://tc39.es/ecma262/multipage/abstract-operations.html#sec-ordinarytoprimitive>
+///
+/// Calls `valueOf` then `toString` (the other way around for the `"string"` hint), returning the
+/// first result that is not an object. Properties that are not functions are skipped. As the methods
+/// are called, constant method bodies give constant results
+fn ordinary_to_primitive<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	value: TypeId,
+	hint: &'static str,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<TypeId> {
+	let methods = if hint == "string" { ["toString", "valueOf"] } else { ["valueOf", "toString"] };
+
+	for method in methods {
+		let key = PropertyKey::String(Cow::Borrowed(method));
+		let property =
+			get_property_unbound(value, Publicity::Public, &key, &checking_data.types, environment);
+		let Ok(Logical::Pure(PropertyValue::Value(function))) = property else { continue };
+		if !matches!(
+			checking_data.types.get_type_by_id(function),
+			Type::SpecialObject(SpecialObjects::Function(..)) | Type::FunctionReference(..)
+		) {
+			continue;
+		}
+
+		let result = call_method(value, &key, Vec::new(), position, environment, checking_data)?;
+		if !matches!(checking_data.types.get_type_by_id(result), Type::Object(..)) {
+			return Some(result);
+		}
+	}
+	None
+}
+
+/// `lhs instanceof rhs` via `rhs[Symbol.hasInstance](lhs)`. Returns `None` if `rhs` does not
+/// have a `[Symbol.hasInstance]` method
+pub(crate) fn call_has_instance<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	(lhs, lhs_position): (TypeId, SpanWithSource),
+	rhs: TypeId,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<TypeId> {
+	let arguments = vec![SynthesisedArgument { value: lhs, position: lhs_position, spread: false }];
+
+	call_method(
+		rhs,
+		&PropertyKey::Type(TypeId::SYMBOL_HAS_INSTANCE),
+		arguments,
+		position,
+		environment,
+		checking_data,
+	)
+}
+
+/// <https://tc39.es/ecma262/multipage/abstract-operations.html#sec-ordinaryhasinstance>
+///
+/// `lhs instanceof rhs` by looking for `rhs.prototype` in the prototype chain of `lhs`. Returns `None`
+/// if `rhs` does not have a `prototype`
+pub(crate) fn ordinary_has_instance(
+	lhs: TypeId,
+	rhs: TypeId,
+	environment: &Environment,
+	types: &mut TypeStore,
+) -> Option<TypeId> {
+	let prototype = get_property_unbound(
+		rhs,
+		Publicity::Public,
+		&PropertyKey::String(Cow::Borrowed("prototype")),
+		types,
+		environment,
+	);
+
+	// Classes from a function call (for example mixins) have their arguments curried
+	let prototype = match prototype {
+		Ok(Logical::Implies { on, .. }) => Ok(*on),
+		prototype => prototype,
+	};
+
+	if let Ok(Logical::Pure(PropertyValue::Value(prototype))) = prototype {
+		Some(has_prototype_in_chain(lhs, prototype, environment, types))
+	} else {
+		None
+	}
+}
+
+fn has_prototype_in_chain(
+	value: TypeId,
+	prototype: TypeId,
+	environment: &Environment,
+	types: &mut TypeStore,
+) -> TypeId {
+	/// `None` if the chain reaches a prototype that is only known from an annotation (for example
+	/// the base of a mixin), as the actual prototype chain continues from there
+	fn chain_contains(
+		mut on: TypeId,
+		prototype: TypeId,
+		environment: &Environment,
+		types: &TypeStore,
+	) -> Option<bool> {
+		while let Some(next) =
+			environment.get_chain_of_info().find_map(|info| info.prototypes.get(&on)).copied()
+		{
+			if next == prototype {
+				return Some(true);
+			}
+			if let Type::Object(ObjectNature::AnonymousTypeAnnotation)
+			| Type::Interface { .. }
+			| Type::RootPolyType(_)
+			| Type::Constructor(_) = types.get_type_by_id(next)
+			{
+				return None;
+			}
+			on = next;
+		}
+		Some(false)
+	}
+
+	match types.get_type_by_id(value) {
+		Type::Constant(_) => TypeId::FALSE,
+		Type::Object(..) | Type::SpecialObject(..) => {
+			match chain_contains(value, prototype, environment, types) {
+				Some(true) => TypeId::TRUE,
+				Some(false) => TypeId::FALSE,
+				None => TypeId::BOOLEAN_TYPE,
+			}
+		}
+		Type::Constructor(Constructor::ConditionalResult {
+			condition,
+			truthy_result,
+			otherwise_result,
+			..
+		}) => {
+			let (condition, truthy_result, otherwise_result) =
+				(*condition, *truthy_result, *otherwise_result);
+			let truthy_result =
+				has_prototype_in_chain(truthy_result, prototype, environment, types);
+			let otherwise_result =
+				has_prototype_in_chain(otherwise_result, prototype, environment, types);
+			types.new_conditional_type(condition, truthy_result, otherwise_result)
+		}
+		Type::Or(lhs, rhs) => {
+			let (lhs, rhs) = (*lhs, *rhs);
+			let lhs = has_prototype_in_chain(lhs, prototype, environment, types);
+			let rhs = has_prototype_in_chain(rhs, prototype, environment, types);
+			if lhs == rhs {
+				lhs
+			} else {
+				TypeId::BOOLEAN_TYPE
+			}
+		}
+		Type::Constructor(Constructor::StructureGenerics(..)) => TypeId::BOOLEAN_TYPE,
+		_ => {
+			// Instances of a class (or a class that extends it) are always instances
+			let constraint = get_constraint(value, types).unwrap_or(value);
+			if constraint == prototype
+				|| chain_contains(constraint, prototype, environment, types) == Some(true)
+			{
+				TypeId::TRUE
+			} else {
+				TypeId::BOOLEAN_TYPE
+			}
+		}
+	}
+}
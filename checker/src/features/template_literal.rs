@@ -1,9 +1,9 @@
 use source_map::Span;
 
 use crate::{
-	context::invocation::CheckThings,
-	features::objects::ObjectBuilder,
-	types::{calling::CallingInput, cast_as_string, SynthesisedArgument, TypeStore},
+	context::{information::InformationChain, invocation::CheckThings},
+	features::{objects::ObjectBuilder, symbols::to_primitive},
+	types::{calling::CallingInput, cast_as_string, ObjectNature, SynthesisedArgument, TypeStore},
 	CheckingData, Constant, Environment, Type, TypeId,
 };
 
@@ -44,13 +44,30 @@ where
 					environment,
 					checking_data,
 				);
-				if let Type::Constant(cst) = checking_data.types.get_type_by_id(value) {
-					let value = cast_as_string(cst, checking_data.options.strict_casts).unwrap();
-					checking_data.types.new_constant_type(Constant::String(value))
-				} else {
-					crate::utils::notify!("Need to cast to string...");
-					value
+				let position =
+					A::expression_position(expression).with_source(environment.get_source());
+				let value = to_primitive(value, "string", position, environment, checking_data);
+				match checking_data.types.get_type_by_id(value) {
+					Type::Constant(cst) => {
+						// Symbols cannot be converted
+						if let Ok(value) = cast_as_string(cst, checking_data.options.strict_casts) {
+							return checking_data.types.new_constant_type(Constant::String(value));
+						}
+					}
+					// Objects without a prototype (and so without `Object.prototype.toString`)
+					Type::Object(ObjectNature::RealDeal)
+						if !environment
+							.get_chain_of_info()
+							.any(|info| info.prototypes.contains_key(&value)) =>
+					{
+						return checking_data
+							.types
+							.new_constant_type(Constant::String("[object Object]".to_owned()));
+					}
+					_ => {}
 				}
+				crate::utils::notify!("Need to cast to string...");
+				value
 			}
 		}
 	}
@@ -80,9 +97,16 @@ where
 					);
 					static_part_count += 1;
 				}
-				p @ TemplateLiteralPart::Dynamic(_) => {
+				TemplateLiteralPart::Dynamic(expression) => {
+					// Values are passed to the tag as is, without being converted to strings
+					let value = A::synthesise_expression(
+						expression,
+						TypeId::ANY_TYPE,
+						environment,
+						checking_data,
+					);
 					arguments.push(SynthesisedArgument {
-						value: part_to_type(p, environment, checking_data),
+						value,
 						// TODO position
 						position: source_map::Nullable::NULL,
 						spread: false,
@@ -150,40 +174,268 @@ where
 	}
 }
 
+/// Intrinsic string manipulation types (`Uppercase<T>` etc)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, binary_serialize_derive::BinarySerializable)]
+pub enum StringIntrinsic {
+	Uppercase,
+	Lowercase,
+	Capitalize,
+	Uncapitalize,
+}
+
+impl StringIntrinsic {
+	#[must_use]
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"Uppercase" => Some(Self::Uppercase),
+			"Lowercase" => Some(Self::Lowercase),
+			"Capitalize" => Some(Self::Capitalize),
+			"Uncapitalize" => Some(Self::Uncapitalize),
+			_ => None,
+		}
+	}
+
+	#[must_use]
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Uppercase => "Uppercase",
+			Self::Lowercase => "Lowercase",
+			Self::Capitalize => "Capitalize",
+			Self::Uncapitalize => "Uncapitalize",
+		}
+	}
+
+	#[must_use]
+	pub fn apply(self, value: &str) -> String {
+		match self {
+			Self::Uppercase => value.to_uppercase(),
+			Self::Lowercase => value.to_lowercase(),
+			Self::Capitalize | Self::Uncapitalize => {
+				let mut chars = value.chars();
+				let Some(first) = chars.next() else { return String::new() };
+				let mut result: String = if let Self::Capitalize = self {
+					first.to_uppercase().collect()
+				} else {
+					first.to_lowercase().collect()
+				};
+				result.push_str(chars.as_str());
+				result
+			}
+		}
+	}
+}
+
+/// Part of [`crate::types::Constructor::TemplateLiteral`]
+#[derive(Clone, Debug, PartialEq, binary_serialize_derive::BinarySerializable)]
+pub enum TemplateLiteralTypePart {
+	Static(String),
+	/// A non-literal type (`string`, `number`, a generic etc) with an optional intrinsic applied
+	Dynamic {
+		on: TypeId,
+		intrinsic: Option<StringIntrinsic>,
+	},
+}
+
+/// Cartesian products of unions larger than this become `string`
+const MAX_TEMPLATE_LITERAL_COMBINATIONS: usize = 256;
+
 /// **Expects static part first**
 /// TODO API is different to the `synthesise_template_literal_expression` above
 pub fn synthesize_template_literal_type(parts: Vec<TypeId>, types: &mut TypeStore) -> TypeId {
-	let mut parts_iter = parts.into_iter();
-	if let Some(first) = parts_iter.next() {
-		let mut acc = first;
-		for other in parts_iter {
-			// TODO unfold_alias function
-			let other = if let Type::AliasTo { to, .. } = types.get_type_by_id(other) {
-				*to
-			} else {
-				other
-			};
-			let result = super::operations::evaluate_mathematical_operation(
-				acc,
-				crate::features::operations::MathematicalAndBitwise::Add,
-				other,
+	let parts = parts
+		.into_iter()
+		.map(|on| TemplateLiteralTypePart::Dynamic { on, intrinsic: None })
+		.collect();
+	new_template_literal_type(parts, types)
+}
+
+/// For `Uppercase<T>` etc
+pub fn new_string_intrinsic_type(
+	intrinsic: StringIntrinsic,
+	on: TypeId,
+	types: &mut TypeStore,
+) -> TypeId {
+	new_template_literal_type(
+		vec![TemplateLiteralTypePart::Dynamic { on, intrinsic: Some(intrinsic) }],
+		types,
+	)
+}
+
+/// Builds a template literal type, distributing over unions in `parts`. Combinations that are only
+/// literals become string constants
+pub fn new_template_literal_type(
+	parts: Vec<TemplateLiteralTypePart>,
+	types: &mut TypeStore,
+) -> TypeId {
+	let mut combinations: Vec<Vec<TemplateLiteralTypePart>> = vec![Vec::new()];
+	for part in parts {
+		let alternatives = expand_template_literal_part(part, types);
+		if combinations.len() * alternatives.len() > MAX_TEMPLATE_LITERAL_COMBINATIONS {
+			crate::utils::notify!(Warn; "Template literal type has too many combinations");
+			return TypeId::STRING_TYPE;
+		}
+		combinations = combinations
+			.iter()
+			.flat_map(|existing| {
+				alternatives.iter().map(move |alternative| {
+					let mut combination = existing.clone();
+					for part in alternative.iter().cloned() {
+						push_template_literal_part(&mut combination, part);
+					}
+					combination
+				})
+			})
+			.collect();
+	}
+
+	let results = combinations
+		.into_iter()
+		.map(|combination| match combination.as_slice() {
+			[] => types.new_constant_type(Constant::String(String::new())),
+			[TemplateLiteralTypePart::Static(value)] => {
+				types.new_constant_type(Constant::String(value.clone()))
+			}
+			[TemplateLiteralTypePart::Dynamic { on: TypeId::STRING_TYPE, intrinsic: None }] => {
+				TypeId::STRING_TYPE
+			}
+			_ => types.register_type(Type::Constructor(
+				crate::types::Constructor::TemplateLiteral(combination),
+			)),
+		})
+		.collect::<Vec<_>>();
+
+	results.into_iter().reduce(|acc, ty| types.new_or_type(acc, ty)).unwrap_or(TypeId::NEVER_TYPE)
+}
+
+/// Merges adjacent static parts
+fn push_template_literal_part(
+	parts: &mut Vec<TemplateLiteralTypePart>,
+	part: TemplateLiteralTypePart,
+) {
+	if let (Some(TemplateLiteralTypePart::Static(existing)), TemplateLiteralTypePart::Static(new)) =
+		(parts.last_mut(), &part)
+	{
+		existing.push_str(new);
+	} else {
+		parts.push(part);
+	}
+}
+
+/// The alternatives (each a sequence of parts) that `part` can be
+fn expand_template_literal_part(
+	part: TemplateLiteralTypePart,
+	types: &TypeStore,
+) -> Vec<Vec<TemplateLiteralTypePart>> {
+	let TemplateLiteralTypePart::Dynamic { on, intrinsic } = part else {
+		return vec![vec![part]];
+	};
+	let apply = |value: &str| intrinsic.map_or_else(|| value.to_owned(), |i| i.apply(value));
+
+	if on == TypeId::BOOLEAN_TYPE {
+		return ["true", "false"]
+			.iter()
+			.map(|value| vec![TemplateLiteralTypePart::Static(apply(value))])
+			.collect();
+	}
+
+	match types.get_type_by_id(on) {
+		Type::AliasTo { to, parameters: None, .. } => expand_template_literal_part(
+			TemplateLiteralTypePart::Dynamic { on: *to, intrinsic },
+			types,
+		),
+		Type::Or(left, right) => {
+			let mut alternatives = expand_template_literal_part(
+				TemplateLiteralTypePart::Dynamic { on: *left, intrinsic },
 				types,
-				true,
 			);
-			match result {
-				Ok(result) => acc = result,
-				Err(()) => {
-					// crate::utils::notify!(
-					// 	"acc is {:?}, other is {:?}",
-					// 	types.get_type_by_id(acc),
-					// 	types.get_type_by_id(other)
-					// );
-					crate::utils::notify!("Invalid type template literal concatenation");
+			alternatives.extend(expand_template_literal_part(
+				TemplateLiteralTypePart::Dynamic { on: *right, intrinsic },
+				types,
+			));
+			alternatives
+		}
+		Type::Constant(constant) => {
+			if let Ok(value) = cast_as_string(constant, false) {
+				vec![vec![TemplateLiteralTypePart::Static(apply(&value))]]
+			} else {
+				crate::utils::notify!("Cannot convert symbol in template literal type");
+				vec![vec![part]]
+			}
+		}
+		Type::Constructor(crate::types::Constructor::TemplateLiteral(inner)) => {
+			let Some(intrinsic) = intrinsic else {
+				return vec![inner.clone()];
+			};
+			let mut parts = inner.clone();
+			let affected =
+				if let StringIntrinsic::Capitalize | StringIntrinsic::Uncapitalize = intrinsic {
+					&mut parts[..1]
+				} else {
+					&mut parts[..]
+				};
+			for part in affected {
+				match part {
+					TemplateLiteralTypePart::Static(value) => *value = intrinsic.apply(value),
+					TemplateLiteralTypePart::Dynamic { intrinsic: existing @ None, .. } => {
+						*existing = Some(intrinsic);
+					}
+					TemplateLiteralTypePart::Dynamic { .. } => {
+						crate::utils::notify!("TODO nested string intrinsics");
+					}
 				}
 			}
+			vec![parts]
 		}
-		acc
-	} else {
-		types.new_constant_type(Constant::String(String::new()))
+		_ => vec![vec![part]],
+	}
+}
+
+/// Whether the string `value` is matched by the template literal `parts`. Each dynamic part can
+/// match any substring so this backtracks
+pub(crate) fn template_literal_matches_string(
+	parts: &[TemplateLiteralTypePart],
+	value: &str,
+	types: &TypeStore,
+) -> bool {
+	match parts.split_first() {
+		None => value.is_empty(),
+		Some((TemplateLiteralTypePart::Static(prefix), rest)) => value
+			.strip_prefix(prefix.as_str())
+			.is_some_and(|value| template_literal_matches_string(rest, value, types)),
+		Some((TemplateLiteralTypePart::Dynamic { on, intrinsic }, rest)) => {
+			value.char_indices().map(|(idx, _)| idx).chain(std::iter::once(value.len())).any(
+				|idx| {
+					let (segment, after) = value.split_at(idx);
+					!intrinsic.is_some_and(|intrinsic| intrinsic.apply(segment) != segment)
+						&& type_matches_string(*on, segment, types)
+						&& template_literal_matches_string(rest, after, types)
+				},
+			)
+		}
+	}
+}
+
+fn type_matches_string(ty: TypeId, value: &str, types: &TypeStore) -> bool {
+	match ty {
+		TypeId::STRING_TYPE | TypeId::ANY_TYPE => return true,
+		TypeId::NUMBER_TYPE => {
+			return !value.is_empty() && value.trim() == value && value.parse::<f64>().is_ok();
+		}
+		TypeId::BIGINT_TYPE => return value.parse::<i128>().is_ok(),
+		TypeId::BOOLEAN_TYPE => return matches!(value, "true" | "false"),
+		_ => {}
+	}
+	match types.get_type_by_id(ty) {
+		Type::AliasTo { to, .. } => type_matches_string(*to, value, types),
+		Type::Or(left, right) => {
+			type_matches_string(*left, value, types) || type_matches_string(*right, value, types)
+		}
+		Type::Constant(constant) => cast_as_string(constant, false).is_ok_and(|cst| cst == value),
+		Type::Constructor(crate::types::Constructor::TemplateLiteral(parts)) => {
+			template_literal_matches_string(parts, value, types)
+		}
+		Type::RootPolyType(_) | Type::Constructor(_) => crate::types::get_constraint(ty, types)
+			.is_some_and(|constraint| type_matches_string(constraint, value, types)),
+		_ => false,
 	}
 }
@@ -1,14 +1,20 @@
 use source_map::{Span, SpanWithSource};
 
 use crate::context::information::{get_property_unbound, Publicity};
-use crate::context::VariableRegisterArguments;
 use crate::context::{environment::ContextLocation, AssignmentError};
-use crate::diagnostics::{PropertyRepresentation, TypeCheckError, TypeStringRepresentation};
+use crate::context::{ContextId, VariableRegisterArguments};
+use crate::diagnostics::{
+	DiagnosticsContainer, PropertyRepresentation, TypeCheckError, TypeCheckWarning,
+	TypeStringRepresentation,
+};
 use crate::types::printing::print_type;
 use crate::types::properties::PropertyKey;
 use crate::{types::TypeId, CheckingData, VariableId};
 use crate::{Environment, Instance, Logical};
-use std::fmt::Debug;
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::Debug,
+};
 
 /// A variable, that can be referenced. Can be a including class (prototypes) and functions
 ///
@@ -47,6 +53,15 @@ impl VariableOrImport {
 		}
 	}
 
+	/// Where the variable is declared or imported
+	pub(crate) fn get_declared_at(&self) -> SpanWithSource {
+		match self {
+			VariableOrImport::Variable { declared_at: pos, .. }
+			| VariableOrImport::MutableImport { import_specified_at: pos, .. }
+			| VariableOrImport::ConstantImport { import_specified_at: pos, .. } => *pos,
+		}
+	}
+
 	pub(crate) fn get_mutability(&self) -> VariableMutability {
 		match self {
 			VariableOrImport::Variable { mutability, .. } => *mutability,
@@ -85,7 +100,9 @@ pub fn check_variable_initialization<T: crate::ReadFromFS, A: crate::ASTImplemen
 	environment: &mut crate::context::Environment,
 	checking_data: &mut CheckingData<T, A>,
 ) {
-	use crate::types::subtyping::{type_is_subtype, BasicEquality, SubTypeResult};
+	use crate::types::subtyping::{
+		type_is_subtype, BasicEquality, NonEqualityReason, SubTypeResult,
+	};
 
 	let mut basic_subtyping = BasicEquality {
 		add_property_restrictions: true,
@@ -105,27 +122,74 @@ pub fn check_variable_initialization<T: crate::ReadFromFS, A: crate::ASTImplemen
 	environment
 		.add_object_constraints(basic_subtyping.object_constraints, &mut checking_data.types);
 
-	if let SubTypeResult::IsNotSubType(_matches) = type_is_subtype {
+	if let SubTypeResult::IsNotSubType(reason) = type_is_subtype {
+		let variable_type = crate::diagnostics::TypeStringRepresentation::from_type_id(
+			variable_declared_type,
+			environment,
+			&checking_data.types,
+			checking_data.options.debug_types,
+		);
+		let value_type = crate::diagnostics::TypeStringRepresentation::from_type_id(
+			expression_type,
+			environment,
+			&checking_data.types,
+			checking_data.options.debug_types,
+		);
+		let (variable_site, value_site) = (basic_subtyping.position, expression_declared_pos);
 		let error = crate::diagnostics::TypeCheckError::AssignmentError(
-			AssignmentError::DoesNotMeetConstraint {
-				variable_type: crate::diagnostics::TypeStringRepresentation::from_type_id(
-					variable_declared_type,
-					environment,
-					&checking_data.types,
-					checking_data.options.debug_types,
-				),
-				variable_site: basic_subtyping.position,
-				value_type: crate::diagnostics::TypeStringRepresentation::from_type_id(
-					expression_type,
-					environment,
-					&checking_data.types,
-					checking_data.options.debug_types,
-				),
-				value_site: expression_declared_pos,
+			if let NonEqualityReason::NoCommonProperties = reason {
+				AssignmentError::NoCommonProperties {
+					variable_type,
+					variable_site,
+					value_type,
+					value_site,
+				}
+			} else {
+				AssignmentError::DoesNotMeetConstraint {
+					variable_type,
+					variable_site,
+					value_type,
+					value_site,
+				}
 			},
 		);
 
 		checking_data.diagnostics_container.add_error(error);
+
+		// Values of the base of a `Nominal` type have to be cast
+		let nominal_base = checking_data.types.get_nominal_base(variable_declared_type);
+		if let Some(base) = nominal_base {
+			let base_accepts_value = matches!(
+				crate::types::subtyping::type_is_subtype(
+					base,
+					expression_type,
+					&mut BasicEquality {
+						add_property_restrictions: false,
+						position: source_map::Nullable::NULL,
+						object_constraints: Default::default(),
+						allow_errors: true,
+					},
+					environment,
+					&checking_data.types,
+				),
+				SubTypeResult::IsSubType
+			);
+			if base_accepts_value {
+				let name = crate::types::printing::print_type(
+					variable_declared_type,
+					&checking_data.types,
+					environment,
+					false,
+				);
+				checking_data.diagnostics_container.add_info(
+					crate::diagnostics::Diagnostic::Position {
+						reason: format!("{name} is a nominal type, use `as {name}` to create one"),
+						position: expression_declared_pos,
+						kind: crate::diagnostics::DiagnosticKind::Info,
+					},
+				);
+			}
+		}
 	}
 }
 
@@ -147,7 +211,8 @@ pub fn get_new_register_argument_under<T: crate::ReadFromFS, A: crate::ASTImplem
 		if let Ok(value) = property_constraint {
 			match value {
 				Logical::Pure(crate::PropertyValue::Value(value)) => value,
-				Logical::Pure(_) => todo!(),
+				// Optional properties are conditional on whether they are present
+				Logical::Pure(value) => value.as_get_type(),
 				Logical::Or { .. } => todo!(),
 				Logical::Implies { .. } => todo!(),
 			}
@@ -182,3 +247,116 @@ pub fn get_new_register_argument_under<T: crate::ReadFromFS, A: crate::ASTImplem
 
 	VariableRegisterArguments { constant: on.constant, space, initial_value }
 }
+
+/// Bindings introduced by destructuring and the variables that have been read.
+/// For [`crate::TypeCheckOptions::no_unused_bindings`]
+#[derive(Default, Debug)]
+pub(crate) struct DestructuredBindings {
+	pub(crate) declared: Vec<(VariableId, String, SpanWithSource)>,
+	pub(crate) read: HashSet<VariableId>,
+}
+
+impl DestructuredBindings {
+	/// Warns on the declared bindings that have not been read, in the order they are in each
+	/// module (parameters of hoisted functions are declared before the bindings above them)
+	pub(crate) fn warn_unused(self, diagnostics_container: &mut DiagnosticsContainer) {
+		let Self { mut declared, mut read } = self;
+		let mut sources = Vec::new();
+		for (_, _, position) in &declared {
+			if !sources.contains(&position.source) {
+				sources.push(position.source);
+			}
+		}
+		declared.sort_by_key(|(_, _, position)| {
+			(sources.iter().position(|source| *source == position.source), position.start)
+		});
+		for (id, name, position) in declared {
+			// `_` prefixed names are intentionally unused. Inserting into `read` skips duplicates
+			if !name.starts_with('_') && read.insert(id) {
+				diagnostics_container
+					.add_warning(TypeCheckWarning::UnusedBinding { name, position });
+			}
+		}
+	}
+}
+
+/// The variable or property an assignment is to. For [`crate::TypeCheckOptions::no_dead_stores`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum StoreTarget {
+	Variable(VariableId),
+	/// `variable.property = ...`
+	Property(VariableId, String),
+}
+
+impl StoreTarget {
+	fn variable(&self) -> VariableId {
+		match self {
+			StoreTarget::Variable(variable) | StoreTarget::Property(variable, _) => *variable,
+		}
+	}
+
+	/// Assigning to a property reads the variable it is on
+	fn reads_on_assignment(&self) -> usize {
+		usize::from(matches!(self, StoreTarget::Property(..)))
+	}
+}
+
+#[derive(Debug)]
+struct PendingStore {
+	position: SpanWithSource,
+	context: ContextId,
+	reads: usize,
+	calls: usize,
+	/// Whether this overwrote a dead store. A run of dead stores to a target is reported once
+	overwrote_dead: bool,
+}
+
+/// Assignments that have not been read yet. For [`crate::TypeCheckOptions::no_dead_stores`]
+#[derive(Default, Debug)]
+pub(crate) struct DeadStores {
+	/// Number of times each variable has been read (including to get or set properties on it)
+	reads: HashMap<VariableId, usize>,
+	/// A called function may read any variable, so stores before a call are not dead
+	calls: usize,
+	pending: HashMap<StoreTarget, PendingStore>,
+}
+
+impl DeadStores {
+	pub(crate) fn read(&mut self, variable: VariableId) {
+		*self.reads.entry(variable).or_default() += 1;
+	}
+
+	pub(crate) fn call(&mut self) {
+		self.calls += 1;
+	}
+
+	/// Records an assignment (after it has been synthesised). Returns the position of the previous
+	/// assignment to `target` if it is dead: it was in the same context and there have been no
+	/// reads or calls since. Only the first of consecutive dead stores to `target` is returned
+	pub(crate) fn assign(
+		&mut self,
+		target: StoreTarget,
+		position: SpanWithSource,
+		context: ContextId,
+		has_side_effects: bool,
+	) -> Option<SpanWithSource> {
+		let reads = self.reads.get(&target.variable()).copied().unwrap_or_default();
+		let previous = self.pending.remove(&target).filter(|previous| {
+			previous.context == context
+				&& previous.calls == self.calls
+				&& previous.reads + target.reads_on_assignment() == reads
+		});
+		let dead = previous
+			.as_ref()
+			.filter(|previous| !previous.overwrote_dead)
+			.map(|previous| previous.position);
+		// Assignments with side effects on the right hand side are kept for them
+		if !has_side_effects {
+			let overwrote_dead = previous.is_some();
+			let store =
+				PendingStore { position, context, reads, calls: self.calls, overwrote_dead };
+			self.pending.insert(target, store);
+		}
+		dead
+	}
+}
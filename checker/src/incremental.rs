@@ -0,0 +1,234 @@
+//! Caching the results of checking a project between runs, so that checking a project that has not
+//! changed is skipped.
+//!
+//! Each file that was read is recorded with a hash of its content, including files that could not be
+//! read (so that creating them, for example a module that an import now resolves to, invalidates the
+//! cache). The cache is only used if the key (see [`cache_key`]) is the same and every file has the
+//! same hash. Hashes are FNV-1a, as they are stored between runs (and [`std::hash::DefaultHasher`]
+//! is not stable across Rust versions). The cache is of the whole
+//! project: modules share a single [`crate::types::TypeStore`], so the synthesis results of
+//! unchanged modules are not reused when another module has changed
+
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+};
+
+use source_map::{FileSystem, MapFileStore, Nullable, SourceId, SpanWithSource, WithPathMap};
+
+use crate::{
+	ASTImplementation, BinarySerializable, CheckOutput, Diagnostic, DiagnosticKind,
+	DiagnosticsContainer,
+};
+
+const CHECK_CACHE_MARKER: &[u8] = b"ezno-check-cache";
+const U64_BYTES: usize = (u64::BITS / u8::BITS) as usize;
+
+/// Of the checker version and `options` (a description of everything that changes the result
+/// other than the files)
+#[must_use]
+pub fn cache_key(options: &str) -> u64 {
+	hash_content(
+		[env!("CARGO_PKG_VERSION").as_bytes(), b"\0", options.as_bytes()].concat().as_slice(),
+	)
+}
+
+/// FNV-1a of the content of a file (as read)
+#[must_use]
+pub fn hash_content(content: &[u8]) -> u64 {
+	const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+	const PRIME: u64 = 0x0100_0000_01b3;
+
+	content.iter().fold(OFFSET, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(PRIME))
+}
+
+/// Text is stored as bytes, as [`String`]s are limited to 255 bytes when serialized
+#[derive(binary_serialize_derive::BinarySerializable)]
+pub struct CheckCache {
+	files: Vec<CachedFile>,
+	diagnostics: Vec<CachedDiagnostic>,
+}
+
+#[derive(binary_serialize_derive::BinarySerializable)]
+struct CachedFile {
+	path: Vec<u8>,
+	/// `None` if the file could not be read
+	content_hash: Option<u64>,
+}
+
+#[derive(binary_serialize_derive::BinarySerializable)]
+struct CachedDiagnostic {
+	reason: Vec<u8>,
+	position: Option<CachedSpan>,
+	labels: Vec<(Vec<u8>, Option<CachedSpan>)>,
+	kind: DiagnosticKind,
+}
+
+/// [`SpanWithSource`] with the index of the file in [`CheckCache::files`], as [`SourceId`]s are
+/// only valid for a single run
+#[derive(binary_serialize_derive::BinarySerializable)]
+struct CachedSpan {
+	file: u32,
+	start: u32,
+	end: u32,
+}
+
+impl CheckCache {
+	/// `files_read` are the paths that were read, with the [`hash_content`] of what was read (`None`
+	/// if the file could not be read). The store does not list its files. `None` if the check was
+	/// cancelled, as its diagnostics are incomplete
+	#[must_use]
+	pub fn new<A: ASTImplementation>(
+		output: &CheckOutput<A>,
+		files_read: &[(PathBuf, Option<u64>)],
+	) -> Option<Self> {
+		if output.cancelled {
+			return None;
+		}
+		let CheckOutput { diagnostics, module_contents: files, .. } = output;
+
+		let mut indexes = HashMap::<SourceId, u32>::new();
+		let files = files_read
+			.iter()
+			.enumerate()
+			.map(|(idx, (path, content_hash))| {
+				if let Some(source) = content_hash.and(files.get_source_at_path(path)) {
+					indexes.insert(source, idx as u32);
+				}
+				CachedFile {
+					path: path.to_string_lossy().into_owned().into_bytes(),
+					content_hash: *content_hash,
+				}
+			})
+			.collect();
+
+		let span = |span: &SpanWithSource| {
+			indexes.get(&span.source).map(|file| CachedSpan {
+				file: *file,
+				start: span.start,
+				end: span.end,
+			})
+		};
+		let diagnostics = diagnostics
+			.iter()
+			.map(|diagnostic| {
+				let (position, labels) = match diagnostic {
+					Diagnostic::Global { .. } => (None, Vec::new()),
+					Diagnostic::Position { position, .. } => (span(position), Vec::new()),
+					Diagnostic::PositionWithAdditionalLabels { position, labels, .. } => (
+						span(position),
+						labels
+							.iter()
+							.map(|(message, position)| {
+								(message.clone().into_bytes(), position.as_ref().and_then(span))
+							})
+							.collect(),
+					),
+				};
+				CachedDiagnostic {
+					reason: diagnostic.reason().to_owned().into_bytes(),
+					position,
+					labels,
+					kind: diagnostic.kind(),
+				}
+			})
+			.collect();
+
+		Some(Self { files, diagnostics })
+	}
+
+	#[must_use]
+	pub fn to_bytes(self, key: u64) -> Vec<u8> {
+		let mut buf = CHECK_CACHE_MARKER.to_vec();
+		buf.extend_from_slice(&key.to_le_bytes());
+		self.serialize(&mut buf);
+		buf
+	}
+
+	/// `None` if `bytes` is not a cache or is from a run with a different key
+	#[must_use]
+	pub fn from_bytes(bytes: &[u8], key: u64) -> Option<Self> {
+		let rest = bytes.strip_prefix(CHECK_CACHE_MARKER)?;
+		let cached_key = rest.get(..U64_BYTES)?;
+		if u64::from_le_bytes(cached_key.try_into().ok()?) != key {
+			return None;
+		}
+		Some(Self::deserialize(&mut rest[U64_BYTES..].iter().copied(), SourceId::NULL))
+	}
+
+	/// The diagnostics, the files they were found in and the paths of those files, if none of the
+	/// files have changed
+	pub fn restore(
+		self,
+		fs: &impl crate::ReadFromFS,
+	) -> Option<(DiagnosticsContainer, MapFileStore<WithPathMap>, Vec<PathBuf>)> {
+		let (internal_path, internal_content) =
+			crate::get_definition_cache_source(crate::INTERNAL_DEFINITION_FILE);
+
+		let mut files = MapFileStore::<WithPathMap>::default();
+		let mut sources = Vec::with_capacity(self.files.len());
+		let mut paths = Vec::with_capacity(self.files.len());
+		for CachedFile { path, content_hash } in self.files {
+			let path = PathBuf::from(String::from_utf8(path).ok()?);
+			let content = if path == Path::new(&internal_path) {
+				Some(internal_content.clone().into_bytes())
+			} else {
+				fs.read_file(&path)
+			};
+			if content.as_deref().map(hash_content) != content_hash {
+				return None;
+			}
+			let source = match content {
+				Some(content) => {
+					let content = crate::decode_file_content(
+						&path,
+						content,
+						None,
+						&mut DiagnosticsContainer::new(),
+					)?;
+					Some(files.new_source_id(path.clone(), content))
+				}
+				None => None,
+			};
+			sources.push(source);
+			paths.push(path);
+		}
+
+		let span = |span: CachedSpan| {
+			sources.get(span.file as usize).copied().flatten().map(|source| SpanWithSource {
+				start: span.start,
+				end: span.end,
+				source,
+			})
+		};
+
+		let mut diagnostics = DiagnosticsContainer::new();
+		for CachedDiagnostic { reason, position, labels, kind } in self.diagnostics {
+			let reason = String::from_utf8(reason).ok()?;
+			let diagnostic = match position.map(span) {
+				None => Diagnostic::Global { reason, kind },
+				Some(position) => {
+					let position = position?;
+					if labels.is_empty() {
+						Diagnostic::Position { reason, position, kind }
+					} else {
+						let labels = labels
+							.into_iter()
+							.map(|(message, position)| {
+								Some((String::from_utf8(message).ok()?, position.and_then(span)))
+							})
+							.collect::<Option<Vec<_>>>()?;
+						Diagnostic::PositionWithAdditionalLabels { reason, position, labels, kind }
+					}
+				}
+			};
+			match kind {
+				DiagnosticKind::Error => diagnostics.add_error(diagnostic),
+				DiagnosticKind::Warning => diagnostics.add_warning(diagnostic),
+				DiagnosticKind::Info => diagnostics.add_info(diagnostic),
+			}
+		}
+
+		Some((diagnostics, files, paths))
+	}
+}
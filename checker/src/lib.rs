@@ -1,11 +1,18 @@
 #![doc = include_str!("../README.md")]
 #![allow(deprecated, clippy::new_without_default, clippy::too_many_lines, clippy::result_unit_err)]
 
+pub mod cancellation;
+pub mod chronometer;
 pub mod context;
 pub mod diagnostics;
 pub mod events;
+pub mod explanations;
 pub mod features;
+pub mod incremental;
+pub mod libraries;
+pub mod logging;
 mod options;
+pub mod progress;
 pub mod range_map;
 mod serialization;
 mod type_mappings;
@@ -24,19 +31,31 @@ pub use map_vec::Map as SmallMap;
 use diagnostics::{TypeCheckError, TypeCheckWarning};
 pub(crate) use serialization::BinarySerializable;
 
-use features::{functions::SynthesisableFunction, modules::SynthesisedModule};
+use features::{
+	functions::SynthesisableFunction,
+	modules::{DefinitionFileReference, ExportKind, ModuleExport, SynthesisedModule},
+	variables::{VariableMutability, VariableWithValue},
+};
 
 use source_map::{FileSystem, MapFileStore, Nullable, SpanWithSource, WithPathMap};
 use std::{
 	collections::{HashMap, HashSet},
 	path::{Path, PathBuf},
+	time::Duration,
 };
 
-use types::{printing::print_type, TypeStore};
+use types::{
+	printing::{print_type, TypeToStringOptions},
+	TypeStore,
+};
 
+pub use cancellation::CancellationToken;
+pub use chronometer::Chronometer;
 pub use context::{GeneralContext, Logical, RootContext};
 pub use diagnostics::{Diagnostic, DiagnosticKind, DiagnosticsContainer};
+pub use features::deprecation::Deprecations;
 pub use options::TypeCheckOptions;
+pub use progress::{ModuleEvent, ModuleProgress};
 pub use types::{calling::call_type_handle_errors, poly_types::GenericTypeParameters, subtyping};
 
 pub use type_mappings::*;
@@ -44,10 +63,41 @@ pub use types::{properties::PropertyValue, Constant, Type, TypeId};
 
 pub use context::{information::LocalInformation, Environment, Scope};
 
+/// A (possibly virtual) file system. Used by both the checker and the CLI. Closures implement it
+/// (with the default `exists` etc)
 pub trait ReadFromFS {
 	/// Returns `Vec<u8>` as this callback can return binary file
 	/// TODO this shouldn't take `&self`. Should be just `T::read_file`, doesn't need any data
 	fn read_file(&self, path: &std::path::Path) -> Option<Vec<u8>>;
+
+	/// Defaults to whether the file can be read
+	fn exists(&self, path: &std::path::Path) -> bool {
+		self.read_file(path).is_some()
+	}
+
+	/// `None` if not a directory or listing is not supported
+	fn read_directory(&self, _path: &std::path::Path) -> Option<Vec<std::path::PathBuf>> {
+		None
+	}
+
+	/// `None` if not supported
+	fn metadata(&self, _path: &std::path::Path) -> Option<FileMetadata> {
+		None
+	}
+
+	/// Resolves symlinks (see [`crate::features::modules::canonicalise_path`]). Defaults to the path
+	/// as is
+	fn canonicalize(&self, path: &std::path::Path) -> std::path::PathBuf {
+		path.to_path_buf()
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct FileMetadata {
+	pub is_directory: bool,
+	/// In bytes
+	pub size: u64,
+	pub modified: Option<std::time::SystemTime>,
 }
 
 impl<T, U> ReadFromFS for T
@@ -77,8 +127,24 @@ pub struct ModuleData<'a, FileReader, AST: ASTImplementation> {
 	pub(crate) _currently_checking_modules: HashSet<PathBuf>,
 	/// The result of checking. Includes exported variables and info
 	pub(crate) synthesised_modules: HashMap<SourceId, SynthesisedModule<AST::OwnedModule>>,
+	/// Files whose diagnostics are dropped
+	pub(crate) skipped_sources: HashSet<SourceId>,
+	/// Files synthesised as `CommonJS`
+	pub(crate) common_js_sources: HashSet<SourceId>,
+	/// Files that use `await` at the top level
+	pub(crate) top_level_await_sources: HashSet<SourceId>,
+	/// Entry points that have been parsed (see [`ASTImplementation::modules_from_strings`]) but
+	/// not yet synthesised
+	pub(crate) parsed_entry_points: HashMap<SourceId, ParseResult<AST>>,
+	/// Declarations in `declare global` blocks of modules, which are visible to modules synthesised
+	/// after them. (The root context cannot be changed while modules are being synthesised)
+	pub(crate) global_declarations: Names,
 }
 
+/// A module parsed from source, or why it could not be
+pub type ParseResult<A> =
+	Result<<A as ASTImplementation>::Module<'static>, <A as ASTImplementation>::ParseError>;
+
 pub trait ASTImplementation: Sized {
 	type ParseOptions;
 	/// Custom allocator etc
@@ -116,6 +182,32 @@ pub trait ASTImplementation: Sized {
 		parser_requirements: &mut Self::ParserRequirements,
 	) -> Result<Self::DefinitionFile<'static>, Self::ParseError>;
 
+	/// The files that a definition file depends on (from `/// <reference ... />` directives).
+	/// These are synthesised before it
+	fn definition_file_references(
+		_file: &Self::DefinitionFile<'_>,
+	) -> Vec<(DefinitionFileReference, Span)> {
+		Vec::new()
+	}
+
+	/// Parses using up to `threads` threads. Results are in the same order as `sources`, with the
+	/// time each took to parse. Defaults to [`Self::module_from_string`] on the current thread
+	fn modules_from_strings(
+		sources: Vec<(SourceId, String, Self::ParseOptions)>,
+		_threads: usize,
+		parser_requirements: &mut Self::ParserRequirements,
+	) -> Vec<(ParseResult<Self>, Duration)> {
+		sources
+			.into_iter()
+			.map(|(source_id, string, options)| {
+				let start = chronometer::Instant::now();
+				let module =
+					Self::module_from_string(source_id, string, options, parser_requirements);
+				(module, start.elapsed())
+			})
+			.collect()
+	}
+
 	#[allow(clippy::needless_lifetimes)]
 	fn synthesise_module<'a, T: crate::ReadFromFS>(
 		module: &Self::Module<'a>,
@@ -167,6 +259,18 @@ pub trait ASTImplementation: Sized {
 
 	fn parameter_constrained<'a>(parameter: &'a Self::TypeParameter<'a>) -> bool;
 
+	/// The `extends` annotation of a type parameter
+	fn type_parameter_extends<'a>(
+		parameter: &'a Self::TypeParameter<'a>,
+	) -> Option<&'a Self::TypeAnnotation<'a>>;
+
+	/// The `=` annotation of a type parameter
+	fn type_parameter_default<'a>(
+		parameter: &'a Self::TypeParameter<'a>,
+	) -> Option<&'a Self::TypeAnnotation<'a>>;
+
+	fn type_parameter_position<'a>(parameter: &'a Self::TypeParameter<'a>) -> Span;
+
 	fn parse_options(is_js: bool, parse_comments: bool, lsp_mode: bool) -> Self::ParseOptions;
 
 	fn owned_module_from_module(m: Self::Module<'static>) -> Self::OwnedModule;
@@ -195,6 +299,11 @@ where
 			files: files.unwrap_or_default(),
 			synthesised_modules: Default::default(),
 			_currently_checking_modules: Default::default(),
+			skipped_sources: Default::default(),
+			common_js_sources: Default::default(),
+			top_level_await_sources: Default::default(),
+			parsed_entry_points: Default::default(),
+			global_declarations: Names::default(),
 			// custom_module_resolvers,
 			file_reader: file_resolver,
 			current_working_directory,
@@ -202,7 +311,11 @@ where
 		}
 	}
 
-	pub(crate) fn get_file(&mut self, path: &Path) -> Option<File> {
+	pub(crate) fn get_file(
+		&mut self,
+		path: &Path,
+		diagnostics: &mut DiagnosticsContainer,
+	) -> Option<File> {
 		// TODO only internal code should be able to do this
 		if let Some("bin") = path.extension().and_then(|s| s.to_str()) {
 			return self.file_reader.read_file(path).map(|s| File::Binary(s.clone()));
@@ -213,14 +326,10 @@ where
 		} else {
 			// Load into system
 			let content = self.file_reader.read_file(path)?;
-			let content = String::from_utf8(content);
-			if let Ok(content) = content {
-				let source_id = self.files.new_source_id(path.to_path_buf(), content);
-				Some(File::Source(source_id, self.files.get_file_content(source_id)))
-			} else {
-				eprintln!("{} is not valid Utf-8", path.display());
-				None
-			}
+			// Definition files are not limited by size
+			let content = decode_file_content(path, content, None, diagnostics)?;
+			let source_id = self.files.new_source_id(path.to_path_buf(), content);
+			Some(File::Source(source_id, self.files.get_file_content(source_id)))
 		}
 	}
 }
@@ -237,10 +346,6 @@ pub struct VariableId(pub SourceId, pub u32);
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, binary_serialize_derive::BinarySerializable)]
 pub struct FunctionId(pub SourceId, pub u32);
 
-impl FunctionId {
-	pub const AUTO_CONSTRUCTOR: Self = FunctionId(source_map::Nullable::NULL, 0);
-}
-
 #[derive(Debug)]
 pub enum Decidable<T> {
 	Known(T),
@@ -278,6 +383,17 @@ pub struct CheckingData<'a, FSResolver, ModuleAST: ASTImplementation> {
 
 	/// Do not repeat emitting unimplemented parts
 	unimplemented_items: HashSet<&'static str>,
+
+	/// Only recorded with [`TypeCheckOptions::measure_time`]
+	pub(crate) chronometer: Chronometer,
+
+	pub(crate) deprecations: Deprecations,
+
+	/// Only recorded with [`TypeCheckOptions::no_unused_bindings`]
+	pub(crate) destructured_bindings: features::variables::DestructuredBindings,
+
+	/// Only recorded with [`TypeCheckOptions::no_dead_stores`]
+	pub(crate) dead_stores: features::variables::DeadStores,
 }
 
 impl<'a, T, A> CheckingData<'a, T, A>
@@ -295,14 +411,21 @@ where
 		// let custom_file_resolvers = HashMap::default();
 		let cwd = Default::default();
 		let modules = ModuleData::new(resolver, cwd, existing_files, parser_requirements);
+		let mut types = TypeStore::default();
+		types.constant_functions = options.constant_functions.clone();
+		types.cancellation = options.cancellation.clone();
 
 		Self {
 			options,
 			local_type_mappings: Default::default(),
 			diagnostics_container: Default::default(),
 			modules,
-			types: Default::default(),
+			types,
 			unimplemented_items: Default::default(),
+			chronometer: Default::default(),
+			deprecations: Default::default(),
+			destructured_bindings: Default::default(),
+			dead_stores: Default::default(),
 		}
 	}
 
@@ -391,26 +514,182 @@ pub struct CheckOutput<A: crate::ASTImplementation> {
 	pub modules: HashMap<SourceId, SynthesisedModule<A::OwnedModule>>,
 	pub diagnostics: crate::DiagnosticsContainer,
 	pub top_level_information: crate::LocalInformation,
+	/// Empty unless [`TypeCheckOptions::measure_time`]
+	pub chronometer: Chronometer,
+	pub deprecations: Deprecations,
+	/// The check was stopped by [`TypeCheckOptions::cancellation`], so the diagnostics (and modules)
+	/// are incomplete
+	pub cancelled: bool,
+	/// The global variables and types, for re-checking parts of modules
+	pub(crate) top_level_names: Names,
 }
 
 impl<A: crate::ASTImplementation> CheckOutput<A> {
+	/// For hover. Unless `debug`, the type is printed as a TypeScript annotation
 	#[must_use]
 	pub fn get_type_at_position(&self, path: &str, pos: u32, debug: bool) -> Option<String> {
 		let source_id = self.module_contents.get_source_at_path(path.as_ref())?;
-		self.modules
-			.get(&source_id)
-			.expect("no module")
-			.mappings
-			.expressions_to_instances
-			.get(pos)
-			.map(|instance| {
-				print_type(
-					instance.get_value_on_ref(),
+		let module = self.modules.get(&source_id).expect("no module");
+		// Objects created in the module have their properties on its information
+		let info = [&module.info, &self.top_level_information];
+		module.mappings.expressions_to_instances.get(pos).map(|instance| {
+			let ty = instance.get_value_on_ref();
+			if debug {
+				print_type(ty, &self.types, &info, true)
+			} else {
+				self.types.type_to_ts_string(ty, &info, TypeToStringOptions::default())
+			}
+		})
+	}
+
+	/// For hover. The `@deprecated` message of the variable referenced at the position
+	#[must_use]
+	pub fn get_deprecation_at_position(&self, path: &str, pos: u32) -> Option<&str> {
+		let source_id = self.module_contents.get_source_at_path(path.as_ref())?;
+		let instance = self.modules.get(&source_id)?.mappings.expressions_to_instances.get(pos)?;
+		if let Instance::LValue(VariableWithValue(variable, _)) = instance {
+			self.deprecations.variables.get(&variable.get_origin_variable_id()).map(String::as_str)
+		} else {
+			None
+		}
+	}
+
+	/// Whether indexing `on` (in the module `source_id`) with `key` finds a property for every
+	/// value of `key`. So `key` (or its constraint) needs to be a constant or a union of constants
+	#[must_use]
+	pub fn is_known_key(&self, source_id: SourceId, on: TypeId, key: TypeId) -> bool {
+		fn collect_constants(key: TypeId, types: &TypeStore, keys: &mut Vec<TypeId>) -> bool {
+			let key = types::get_constraint(key, types).unwrap_or(key);
+			match types.get_type_by_id(key) {
+				Type::Or(lhs, rhs) => {
+					collect_constants(*lhs, types, keys) && collect_constants(*rhs, types, keys)
+				}
+				Type::Constant(Constant::String(_) | Constant::Number(_)) => {
+					keys.push(key);
+					true
+				}
+				_ => false,
+			}
+		}
+
+		let Some(module) = self.modules.get(&source_id) else {
+			return false;
+		};
+		let info = [&module.info, &self.top_level_information];
+		let on = types::get_constraint(on, &self.types).unwrap_or(on);
+		let mut keys = Vec::new();
+		collect_constants(key, &self.types, &mut keys)
+			&& keys.into_iter().all(|key| {
+				let key = types::properties::PropertyKey::from_type(key, &self.types);
+				context::information::get_property_unbound(
+					on,
+					context::information::Publicity::Public,
+					&key,
 					&self.types,
-					&self.top_level_information,
-					debug,
+					&info,
 				)
+				.is_ok()
+			})
+	}
+
+	/// The default export, named exports and then exported types of a module, in the order they
+	/// were exported. `None` if the module was not checked
+	#[must_use]
+	pub fn get_exports(&self, source_id: SourceId) -> Option<Vec<ModuleExport>> {
+		use features::objects::SpecialObjects;
+
+		let module = self.modules.get(&source_id)?;
+		let exported = &module.exported;
+		let position_of = |name: &str| {
+			exported
+				.positions
+				.iter()
+				.find_map(|(export, position)| (export == name).then_some(*position))
+		};
+
+		let mut exports = Vec::new();
+		if let Some(ty) = exported.default {
+			let position = position_of("default");
+			exports.push(ModuleExport {
+				name: "default".to_owned(),
+				kind: ExportKind::Default,
+				ty,
+				position,
+				re_exported_from: None,
+			});
+		}
+
+		for (name, (variable, mutability)) in &exported.named {
+			let declared_in = self.modules.get(&variable.0);
+			let current_value = declared_in
+				.map(|module| &module.info)
+				.into_iter()
+				.chain(std::iter::once(&self.top_level_information))
+				.find_map(|info| info.variable_current_value.get(variable).copied());
+			let (ty, constant) = match mutability {
+				VariableMutability::Constant => {
+					let annotation = declared_in.and_then(|module| {
+						module.mappings.variable_restrictions.get(&(variable.0, variable.1))
+					});
+					(annotation.map(|(ty, _)| *ty).or(current_value), true)
+				}
+				VariableMutability::Mutable { reassignment_constraint } => {
+					(reassignment_constraint.or(current_value), false)
+				}
+			};
+			let ty = ty.unwrap_or(TypeId::ANY_TYPE);
+			let kind = match self.types.get_type_by_id(ty) {
+				Type::FunctionReference(_) | Type::SpecialObject(SpecialObjects::Function(..)) => {
+					ExportKind::Function
+				}
+				Type::SpecialObject(SpecialObjects::ClassConstructor { .. }) => ExportKind::Class,
+				_ => ExportKind::Variable { constant },
+			};
+			exports.push(ModuleExport {
+				name: name.clone(),
+				kind,
+				ty,
+				position: position_of(name),
+				re_exported_from: (variable.0 != source_id).then_some(variable.0),
+			});
+		}
+
+		for (name, ty) in &exported.named_types {
+			exports.push(ModuleExport {
+				name: name.clone(),
+				kind: ExportKind::Type,
+				ty: *ty,
+				position: position_of(name),
+				re_exported_from: None,
+			});
+		}
+
+		Some(exports)
+	}
+
+	/// For `ezno experimental events`. The start position and printed events of each function
+	/// defined in the module, ordered by position
+	#[must_use]
+	pub fn get_function_events(&self, source_id: SourceId) -> Vec<(u32, String)> {
+		let mut functions = self
+			.types
+			.functions
+			.iter()
+			.filter(|(id, _)| id.0 == source_id)
+			.map(|(id, function)| {
+				let mut buf = String::new();
+				crate::types::printing::debug_function_effect(
+					&mut buf,
+					&function.effect,
+					&self.types,
+					&self.top_level_information,
+					false,
+				);
+				(id.1, buf)
 			})
+			.collect::<Vec<_>>();
+		functions.sort_unstable_by_key(|(start, _)| *start);
+		functions
 	}
 }
 
@@ -418,45 +697,62 @@ impl<A: crate::ASTImplementation> CheckOutput<A> {
 pub fn check_project<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 	entry_points: Vec<PathBuf>,
 	type_definition_files: HashSet<PathBuf>,
-	resolver: T,
+	resolver: &T,
 	options: TypeCheckOptions,
 	parser_requirements: A::ParserRequirements,
 ) -> CheckOutput<A> {
-	let mut checking_data =
-		CheckingData::<T, A>::new(options, &resolver, None, parser_requirements);
+	let mut checking_data = CheckingData::<T, A>::new(options, resolver, None, parser_requirements);
 
 	let mut root = crate::context::RootContext::new_with_primitive_references();
 
-	crate::utils::notify!("--- Reading definition files from {:?} ---", type_definition_files);
+	let measure_time = checking_data.options.measure_time;
+
+	crate::utils::notify!(Info; "--- Reading definition files from {:?} ---", type_definition_files);
+	let start = measure_time.then(chronometer::Instant::now);
 	add_definition_files_to_root(type_definition_files, &mut root, &mut checking_data);
+	if let Some(start) = start {
+		checking_data.chronometer.definition_files = start.elapsed();
+	}
 
 	if checking_data.diagnostics_container.has_error() {
 		return CheckOutput {
+			cancelled: checking_data.types.cancellation.is_cancelled(),
 			types: checking_data.types,
 			module_contents: checking_data.modules.files,
 			modules: Default::default(),
 			diagnostics: checking_data.diagnostics_container,
 			top_level_information: Default::default(),
+			chronometer: checking_data.chronometer,
+			deprecations: checking_data.deprecations,
+			top_level_names: Names::default(),
 		};
 	}
 
-	crate::utils::notify!("--- Finished definition file ---");
+	root.register_global_object(&mut checking_data.types);
 
+	crate::utils::notify!(Info; "--- Finished definition file ---");
+
+	let start = measure_time.then(chronometer::Instant::now);
+	let mut entry_sources = Vec::new();
 	for point in &entry_points {
+		let point = &features::modules::canonicalise_path(
+			&features::modules::normalise_path(point),
+			checking_data.options.preserve_symlinks,
+			checking_data.modules.file_reader,
+		);
 		let entry_content = checking_data.modules.file_reader.read_file(point);
 
 		if let Some(content) = entry_content {
-			let (source, module) =
-				get_source(&mut checking_data, point, String::from_utf8(content).unwrap());
-
-			match module {
-				Ok(module) => {
-					root.new_module_context(source, module, &mut checking_data);
-				}
-				Err(err) => {
-					checking_data.diagnostics_container.add_error(err);
-				}
-			}
+			let Some(content) = decode_file_content(
+				point,
+				content,
+				checking_data.options.maximum_file_size,
+				&mut checking_data.diagnostics_container,
+			) else {
+				continue;
+			};
+			let (source, parse_options) = register_source(&mut checking_data, point, &content);
+			entry_sources.push((source, content, parse_options));
 		} else {
 			checking_data.diagnostics_container.add_error(TypeCheckError::CannotOpenFile {
 				file: CouldNotOpenFile(point.clone()),
@@ -465,22 +761,109 @@ pub fn check_project<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		}
 	}
 
+	let sources = entry_sources.iter().map(|(source, ..)| *source).collect::<Vec<_>>();
+	let modules = A::modules_from_strings(
+		entry_sources,
+		checking_data.options.threads,
+		&mut checking_data.modules.parser_requirements,
+	);
+	for (source, (module, parse)) in sources.iter().zip(modules) {
+		if measure_time {
+			checking_data.chronometer.record_parse(*source, parse);
+		}
+		checking_data.modules.parsed_entry_points.insert(*source, module);
+	}
+
+	// Entry points imported by a previous entry point are synthesised (and removed) then
+	for source in sources {
+		if checking_data.types.cancellation.is_cancelled() {
+			break;
+		}
+		match checking_data.modules.parsed_entry_points.remove(&source) {
+			Some(Ok(module)) => {
+				root.new_module_context(source, module, &mut checking_data);
+			}
+			Some(Err(err)) => {
+				checking_data.diagnostics_container.add_error(err);
+			}
+			None => {}
+		}
+	}
+
 	let CheckingData {
-		diagnostics_container,
+		mut diagnostics_container,
 		local_type_mappings: _,
 		modules,
-		options: _,
+		options,
 		types,
 		unimplemented_items: _,
+		mut chronometer,
+		deprecations,
+		destructured_bindings,
+		dead_stores: _,
 	} = checking_data;
 
+	if options.no_unused_bindings {
+		destructured_bindings.warn_unused(&mut diagnostics_container);
+	}
+
+	if let Some(start) = start {
+		chronometer.modules_total = start.elapsed();
+		chronometer.types = types.count_of_types();
+	}
+
 	CheckOutput {
+		cancelled: types.cancellation.is_cancelled(),
 		types,
 		module_contents: modules.files,
 		modules: modules.synthesised_modules,
 		diagnostics: diagnostics_container,
 		top_level_information: root.info,
+		chronometer,
+		deprecations,
+		top_level_names: Names {
+			variables: root.variables,
+			named_types: root.named_types,
+			variable_names: root.variable_names,
+		},
+	}
+}
+
+/// Removes a byte order mark and replaces invalid UTF-8 sequences with `U+FFFD`. Positions are
+/// then relative to the decoded content (which is what is stored in the file store).
+/// Returns `None` if the content is over `maximum_file_size`. Both cases add a warning
+pub(crate) fn decode_file_content(
+	path: &Path,
+	content: Vec<u8>,
+	maximum_file_size: Option<usize>,
+	diagnostics: &mut DiagnosticsContainer,
+) -> Option<String> {
+	const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
+	if let Some(maximum) = maximum_file_size {
+		if content.len() > maximum {
+			diagnostics.add_warning(TypeCheckWarning::FileTooLarge {
+				path: path.to_path_buf(),
+				size: content.len(),
+				maximum,
+			});
+			return None;
+		}
 	}
+
+	let mut content = match String::from_utf8(content) {
+		Ok(content) => content,
+		Err(err) => {
+			diagnostics.add_warning(TypeCheckWarning::InvalidUtf8 { path: path.to_path_buf() });
+			String::from_utf8_lossy(err.as_bytes()).into_owned()
+		}
+	};
+
+	if content.starts_with(BYTE_ORDER_MARK) {
+		content.drain(..BYTE_ORDER_MARK.len_utf8());
+	}
+
+	Some(content)
 }
 
 fn get_source<T: crate::ReadFromFS, A: crate::ASTImplementation>(
@@ -491,7 +874,38 @@ fn get_source<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 	SourceId,
 	Result<<A as ASTImplementation>::Module<'static>, <A as ASTImplementation>::ParseError>,
 ) {
-	let source = checking_data.modules.files.new_source_id(path.to_path_buf(), content.clone());
+	let (source, parse_options) = register_source(checking_data, path, &content);
+
+	let start = checking_data.options.measure_time.then(chronometer::Instant::now);
+	let module = A::module_from_string(
+		source,
+		content,
+		parse_options,
+		&mut checking_data.modules.parser_requirements,
+	);
+	if let Some(start) = start {
+		checking_data.chronometer.record_parse(source, start.elapsed());
+	}
+
+	(source, module)
+}
+
+/// Adds the file and returns the options to parse it with
+fn register_source<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	checking_data: &mut CheckingData<T, A>,
+	path: &Path,
+	content: &str,
+) -> (SourceId, A::ParseOptions) {
+	let source = checking_data.modules.files.new_source_id(path.to_path_buf(), content.to_owned());
+
+	if features::modules::should_skip_checking(
+		path,
+		content,
+		&checking_data.options,
+		&checking_data.modules.current_working_directory,
+	) {
+		checking_data.modules.skipped_sources.insert(source);
+	}
 
 	// TODO abstract using similar to import logic
 	let is_js = path.extension().and_then(|s| s.to_str()).map_or(false, |s| s.ends_with("js"));
@@ -502,14 +916,7 @@ fn get_source<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		checking_data.options.lsp_mode,
 	);
 
-	let module = A::module_from_string(
-		source,
-		content,
-		parse_options,
-		&mut checking_data.modules.parser_requirements,
-	);
-
-	(source, module)
+	(source, parse_options)
 }
 
 const CACHE_MARKER: &[u8] = b"ezno-cache-file";
@@ -530,12 +937,13 @@ pub(crate) fn add_definition_files_to_root<T: crate::ReadFromFS, A: crate::ASTIm
 	checking_data: &mut CheckingData<T, A>,
 ) {
 	let length = type_definition_files.len();
+	// Includes files found through references, so that each is only synthesised once
+	let mut added = HashSet::new();
 	for path in type_definition_files {
-		let file = if path == PathBuf::from(crate::INTERNAL_DEFINITION_FILE_PATH) {
-			File::Binary(crate::INTERNAL_DEFINITION_FILE.to_owned())
-		} else if let Some(file) = checking_data.modules.get_file(&path) {
-			file
-		} else {
+		if !added.insert(path.clone()) {
+			continue;
+		}
+		let Some(file) = get_definition_file(&path, checking_data) else {
 			checking_data.diagnostics_container.add_error(Diagnostic::Global {
 				reason: format!("could not find {}", path.display()),
 				kind: crate::DiagnosticKind::Error,
@@ -545,26 +953,10 @@ pub(crate) fn add_definition_files_to_root<T: crate::ReadFromFS, A: crate::ASTIm
 
 		match file {
 			File::Binary(mut content) => {
-				crate::utils::notify!("Using cache :)");
+				crate::utils::notify!(Info; "Using cache :)");
 				assert_eq!(length, 1, "only a single cache is current supported");
 
-				let vec = content[CACHE_MARKER.len()..(CACHE_MARKER.len() + U32_BYTES as usize)]
-					.to_owned();
-
-				let at_end =
-					<u32 as BinarySerializable>::deserialize(&mut vec.into_iter(), SourceId::NULL);
-
-				// Get source and content which is at the end.
-				let mut drain =
-					content.drain((CACHE_MARKER.len() + U32_BYTES as usize + at_end as usize)..);
-				// Okay as end
-				let (_source_id, path) = <(SourceId, String) as BinarySerializable>::deserialize(
-					&mut drain,
-					SourceId::NULL,
-				);
-
-				// Collect from end
-				let source_content = String::from_utf8(drain.collect::<Vec<_>>()).unwrap();
+				let (path, source_content) = get_definition_cache_source(&content);
 
 				// TODO unsafe set here
 				// checking_data.modules.files.update_file(id, content)
@@ -581,37 +973,119 @@ pub(crate) fn add_definition_files_to_root<T: crate::ReadFromFS, A: crate::ASTIm
 				root.variables = variables;
 				root.named_types = named_types;
 				root.info = info;
+				// Registered constant functions and the token are not part of the cache
+				let constant_functions =
+					std::mem::take(&mut checking_data.types.constant_functions);
+				let cancellation = std::mem::take(&mut checking_data.types.cancellation);
 				checking_data.types = types;
+				checking_data.types.constant_functions = constant_functions;
+				checking_data.types.cancellation = cancellation;
 			}
 			File::Source(source_id, content) => {
-				let result = A::definition_module_from_string(
-					source_id,
-					content,
-					&mut checking_data.modules.parser_requirements,
-				);
+				add_definition_source_to_root(source_id, content, root, checking_data, &mut added);
+			}
+		}
+	}
+}
 
-				match result {
-					Ok(tdm) => {
-						let (names, info) =
-							A::synthesise_definition_file(tdm, source_id, root, checking_data);
-
-						root.variables.extend(names.variables);
-						root.named_types.extend(names.named_types);
-						root.variable_names.extend(names.variable_names);
-						root.info.extend(info, None);
-					}
-					Err(err) => {
-						checking_data.diagnostics_container.add_error(err);
-						continue;
-					}
-				}
+/// The internal definition file, a library from [`libraries`] or a file read from the file system
+fn get_definition_file<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	path: &Path,
+	checking_data: &mut CheckingData<T, A>,
+) -> Option<File> {
+	if path == Path::new(INTERNAL_DEFINITION_FILE_PATH) {
+		Some(File::Binary(INTERNAL_DEFINITION_FILE.to_owned()))
+	} else if let Some(source) = libraries::get_lib_source(path) {
+		let files = &mut checking_data.modules.files;
+		let source_id = files.new_source_id(path.to_path_buf(), source.to_owned());
+		Some(File::Source(source_id, source.to_owned()))
+	} else {
+		checking_data.modules.get_file(path, &mut checking_data.diagnostics_container)
+	}
+}
+
+/// Referenced definition files (that have not already been added) are added first, as the file may
+/// use their types
+fn add_definition_source_to_root<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	source_id: SourceId,
+	content: String,
+	root: &mut RootContext,
+	checking_data: &mut CheckingData<T, A>,
+	added: &mut HashSet<PathBuf>,
+) {
+	let result = A::definition_module_from_string(
+		source_id,
+		content,
+		&mut checking_data.modules.parser_requirements,
+	);
+
+	let tdm = match result {
+		Ok(tdm) => tdm,
+		Err(err) => {
+			checking_data.diagnostics_container.add_error(err);
+			return;
+		}
+	};
+
+	let from = checking_data.modules.files.get_file_path(source_id);
+	for (reference, position) in A::definition_file_references(&tdm) {
+		// The internal definition file has all of the libraries
+		if let DefinitionFileReference::Lib(_) = reference {
+			if added.contains(Path::new(INTERNAL_DEFINITION_FILE_PATH)) {
+				continue;
 			}
 		}
+		let path = features::modules::resolve_definition_file_reference(
+			&reference,
+			&from,
+			&checking_data.modules.current_working_directory,
+			checking_data.modules.file_reader,
+		);
+		let Some(path) = path else {
+			let (DefinitionFileReference::Path(name)
+			| DefinitionFileReference::Types(name)
+			| DefinitionFileReference::Lib(name)) = reference;
+			checking_data.diagnostics_container.add_error(Diagnostic::Position {
+				reason: format!("could not find {name}"),
+				position: position.with_source(source_id),
+				kind: crate::DiagnosticKind::Error,
+			});
+			continue;
+		};
+		if !added.insert(path.clone()) {
+			continue;
+		}
+		if let Some(File::Source(source_id, content)) = get_definition_file(&path, checking_data) {
+			add_definition_source_to_root(source_id, content, root, checking_data, added);
+		}
 	}
+
+	let (names, info) = A::synthesise_definition_file(tdm, source_id, root, checking_data);
+
+	root.variables.extend(names.variables);
+	root.named_types.extend(names.named_types);
+	root.variable_names.extend(names.variable_names);
+	root.info.extend(info, None);
 }
 
 const U32_BYTES: u32 = u32::BITS / u8::BITS;
 
+/// The path and content of the definition file that `cache` (from [`generate_cache`]) was generated
+/// from. These are stored at the end
+pub(crate) fn get_definition_cache_source(cache: &[u8]) -> (String, String) {
+	let at_end = <u32 as BinarySerializable>::deserialize(
+		&mut cache[CACHE_MARKER.len()..].iter().copied(),
+		SourceId::NULL,
+	);
+
+	let mut end =
+		cache[(CACHE_MARKER.len() + U32_BYTES as usize + at_end as usize)..].iter().copied();
+	let (_source_id, path) =
+		<(SourceId, String) as BinarySerializable>::deserialize(&mut end, SourceId::NULL);
+	let content = String::from_utf8(end.collect::<Vec<_>>()).unwrap();
+	(path, content)
+}
+
 pub fn generate_cache<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 	on: &Path,
 	read: &T,
@@ -652,7 +1126,11 @@ pub fn generate_cache<T: crate::ReadFromFS, A: crate::ASTImplementation>(
 		.copy_from_slice(&(cache_len as u32).to_le_bytes());
 
 	// TODO not great
-	let Some(File::Source(source, content)) = checking_data.modules.get_file(on) else { panic!() };
+	let Some(File::Source(source, content)) =
+		checking_data.modules.get_file(on, &mut checking_data.diagnostics_container)
+	else {
+		panic!()
+	};
 
 	let path = on.to_str().unwrap().to_owned();
 	(source, path).serialize(&mut buf);
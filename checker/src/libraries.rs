@@ -0,0 +1,65 @@
+//! Built-in libraries of declarations (as `lib` in `tsconfig.json`), which can be used instead of
+//! the single [`crate::INTERNAL_DEFINITION_FILE`]. The definition files are embedded as source and
+//! added with the paths from [`Lib::path`]. Libraries include the libraries they build on through
+//! `/// <reference lib="..." />` directives
+
+use std::path::{Path, PathBuf};
+
+/// Prefix of the paths of the embedded definition files
+pub const LIB_PATH_PREFIX: &str = "ezno:lib/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lib {
+	ES5,
+	ES2015,
+	ES2021,
+	DOM,
+	WebWorker,
+}
+
+impl Lib {
+	pub const ALL: [Self; 5] = [Self::ES5, Self::ES2015, Self::ES2021, Self::DOM, Self::WebWorker];
+
+	/// Case insensitive. `esnext` is the latest ES library
+	#[must_use]
+	pub fn from_name(name: &str) -> Option<Self> {
+		let name = name.to_lowercase();
+		if name == "esnext" {
+			return Some(Self::ES2021);
+		}
+		Self::ALL.into_iter().find(|lib| lib.name() == name)
+	}
+
+	#[must_use]
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::ES5 => "es5",
+			Self::ES2015 => "es2015",
+			Self::ES2021 => "es2021",
+			Self::DOM => "dom",
+			Self::WebWorker => "webworker",
+		}
+	}
+
+	/// The path to pass (as a definition file) to [`crate::check_project`]
+	#[must_use]
+	pub fn path(self) -> PathBuf {
+		PathBuf::from(format!("{LIB_PATH_PREFIX}{}.d.ts", self.name()))
+	}
+
+	fn source(self) -> &'static str {
+		match self {
+			Self::ES5 => include_str!("../definitions/lib/es5.d.ts"),
+			Self::ES2015 => include_str!("../definitions/lib/es2015.d.ts"),
+			Self::ES2021 => include_str!("../definitions/lib/es2021.d.ts"),
+			Self::DOM => include_str!("../definitions/lib/dom.d.ts"),
+			Self::WebWorker => include_str!("../definitions/lib/webworker.d.ts"),
+		}
+	}
+}
+
+/// The source of a path from [`Lib::path`]
+pub(crate) fn get_lib_source(path: &Path) -> Option<&'static str> {
+	let name = path.to_str()?.strip_prefix(LIB_PATH_PREFIX)?.strip_suffix(".d.ts")?;
+	Lib::from_name(name).map(Lib::source)
+}
@@ -0,0 +1,199 @@
+//! Leveled, target scoped logging for tracing what the checker is doing. Off by default.
+//!
+//! Enabled with a filter from the `EZNO_LOG` environment variable or [`init`] (used by the CLI `--log` option).
+//! Filters are comma separated directives of `target=level` or just `level` (which applies to every target).
+//! Targets are module paths relative to the crate root. For example `EZNO_LOG=types::subtyping=debug,warn`.
+//!
+//! Setting the older `EZNO_DEBUG` variable is equivalent to `EZNO_LOG=debug`
+//!
+//! `debug` and `trace` logging is on hot paths (such as subtyping), so it is compiled out of
+//! release builds. See [`STATIC_MAXIMUM_LEVEL`]
+
+use std::{
+	fmt,
+	io::Write,
+	path::Path,
+	sync::{
+		atomic::{AtomicU8, Ordering},
+		Mutex,
+	},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+	Error = 1,
+	Warn,
+	Info,
+	Debug,
+	Trace,
+}
+
+impl Level {
+	#[must_use]
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name.to_ascii_lowercase().as_str() {
+			"error" => Some(Self::Error),
+			"warn" | "warning" => Some(Self::Warn),
+			"info" => Some(Self::Info),
+			"debug" => Some(Self::Debug),
+			"trace" => Some(Self::Trace),
+			_ => None,
+		}
+	}
+
+	#[must_use]
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Error => "ERROR",
+			Self::Warn => "WARN",
+			Self::Info => "INFO",
+			Self::Debug => "DEBUG",
+			Self::Trace => "TRACE",
+		}
+	}
+}
+
+/// The most verbose level that can be logged in this build. Checked by [`crate::utils::notify`]
+/// before [`enabled`], so that more verbose calls are removed at compile time
+pub const STATIC_MAXIMUM_LEVEL: Level =
+	if cfg!(debug_assertions) { Level::Trace } else { Level::Info };
+
+/// Which levels are logged for which targets
+#[derive(Debug, Default, Clone)]
+pub struct LogFilter {
+	/// For targets that do not match any in `targets`
+	default: Option<Level>,
+	/// (target prefix, maximum level)
+	targets: Vec<(String, Level)>,
+}
+
+impl LogFilter {
+	/// Parses `types::subtyping=debug,info` like filters
+	pub fn parse(filter: &str) -> Result<Self, String> {
+		let mut result = Self::default();
+		for directive in filter.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+			if let Some((target, level)) = directive.split_once('=') {
+				let level = Level::from_name(level.trim())
+					.ok_or_else(|| format!("unknown log level '{level}'"))?;
+				result.targets.push((target.trim().to_owned(), level));
+			} else if let Some(level) = Level::from_name(directive) {
+				result.default = Some(level);
+			} else {
+				// A target by itself logs everything
+				result.targets.push((directive.to_owned(), Level::Trace));
+			}
+		}
+		Ok(result)
+	}
+
+	/// From `EZNO_LOG` (or `EZNO_DEBUG`). Empty if neither are set
+	pub fn from_environment() -> Result<Self, String> {
+		if let Ok(filter) = std::env::var("EZNO_LOG") {
+			Self::parse(&filter).map_err(|reason| format!("Invalid EZNO_LOG filter: {reason}"))
+		} else if std::env::var("EZNO_DEBUG").is_ok_and(|value| !value.is_empty()) {
+			Ok(Self { default: Some(Level::Debug), targets: Vec::new() })
+		} else {
+			Ok(Self::default())
+		}
+	}
+
+	/// The most specific (longest) matching target takes precedence
+	#[must_use]
+	pub fn enabled(&self, target: &str, level: Level) -> bool {
+		let specific = self
+			.targets
+			.iter()
+			.filter(|(prefix, _)| target_matches(target, prefix))
+			.max_by_key(|(prefix, _)| prefix.len())
+			.map(|(_, level)| *level);
+
+		specific.or(self.default).is_some_and(|maximum| level <= maximum)
+	}
+
+	fn maximum_level(&self) -> Option<Level> {
+		self.targets.iter().map(|(_, level)| *level).chain(self.default).max()
+	}
+}
+
+fn target_matches(target: &str, prefix: &str) -> bool {
+	target.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+pub enum LogOutput {
+	Stderr,
+	File(std::fs::File),
+}
+
+impl LogOutput {
+	pub fn file(path: &Path) -> std::io::Result<Self> {
+		std::fs::File::create(path).map(Self::File)
+	}
+}
+
+struct Logger {
+	filter: LogFilter,
+	output: LogOutput,
+}
+
+static LOGGER: Mutex<Option<Logger>> = Mutex::new(None);
+
+const UNINITIALISED: u8 = u8::MAX;
+const OFF: u8 = 0;
+
+/// Cheap check before locking [`LOGGER`]
+static MAXIMUM_LEVEL: AtomicU8 = AtomicU8::new(UNINITIALISED);
+
+/// Sets the filter and where to write to. Overrides anything from the environment
+pub fn init(filter: LogFilter, output: LogOutput) {
+	let maximum = filter.maximum_level().map_or(OFF, |level| level as u8);
+	*LOGGER.lock().unwrap() = Some(Logger { filter, output });
+	MAXIMUM_LEVEL.store(maximum, Ordering::Relaxed);
+}
+
+/// For when [`init`] has not been called. An invalid filter logs nothing (callers can report it
+/// with [`LogFilter::from_environment`])
+fn init_from_environment() {
+	init(LogFilter::from_environment().unwrap_or_default(), LogOutput::Stderr);
+}
+
+/// `module_path!()` without the crate name
+fn target_from_module_path(module_path: &str) -> &str {
+	module_path.split_once("::").map_or("", |(_crate, rest)| rest)
+}
+
+#[must_use]
+pub fn enabled(module_path: &str, level: Level) -> bool {
+	let mut maximum = MAXIMUM_LEVEL.load(Ordering::Relaxed);
+	if maximum == UNINITIALISED {
+		init_from_environment();
+		maximum = MAXIMUM_LEVEL.load(Ordering::Relaxed);
+	}
+	if (level as u8) > maximum {
+		return false;
+	}
+
+	let target = target_from_module_path(module_path);
+	LOGGER.lock().unwrap().as_ref().is_some_and(|logger| logger.filter.enabled(target, level))
+}
+
+/// Use [`crate::utils::notify`] rather than calling this directly
+pub fn log(module_path: &str, level: Level, file: &str, line: u32, arguments: fmt::Arguments) {
+	let target = target_from_module_path(module_path);
+	let file = file.find("src").map_or(file, |idx| &file[idx..]);
+	let mut logger = LOGGER.lock().unwrap();
+	let Some(logger) = logger.as_mut() else { return };
+
+	let result = match logger.output {
+		#[cfg(not(target_family = "wasm"))]
+		LogOutput::Stderr => {
+			writeln!(std::io::stderr(), "[{} {target} {file}:{line}] {arguments}", level.as_str())
+		}
+		#[cfg(target_family = "wasm")]
+		LogOutput::Stderr => Ok(()),
+		LogOutput::File(ref mut file_output) => {
+			writeln!(file_output, "[{} {target} {file}:{line}] {arguments}", level.as_str())
+		}
+	};
+	// Logging should never stop checking
+	let _ = result;
+}
@@ -1,3 +1,7 @@
+use crate::{
+	features::constant_functions::ConstantFunctionRegistry, CancellationToken, ModuleProgress,
+};
+
 /// Options for type checking
 /// TODO figure out compat with tsc
 #[cfg_attr(feature = "serde-serialize", derive(serde::Deserialize), serde(default))]
@@ -34,6 +38,79 @@ pub struct TypeCheckOptions {
 
 	/// Allows partial syntax and collects other information for using in editor
 	pub lsp_mode: bool,
+
+	/// Source files over this many bytes are skipped (with a warning). Does not apply to definition files
+	pub maximum_file_size: Option<usize>,
+
+	/// Do not resolve symlinks when identifying modules. A symlinked file (e.g. in a pnpm `node_modules`)
+	/// is then treated as a separate module to its target
+	pub preserve_symlinks: bool,
+
+	/// Warn on variables whose value is `any` because of a previous error
+	pub no_error_any_propagation: bool,
+
+	/// Warn on imported bindings that are `any` because their module could not be found or was invalid
+	pub no_missing_import_any: bool,
+
+	/// Warn on explicit `any` type annotations
+	pub no_explicit_any: bool,
+
+	/// Warn on bindings introduced by destructuring that are never read. Names starting with `_` are ignored
+	pub no_unused_bindings: bool,
+
+	/// Warn on expression statements whose value is a `Promise`, so is not awaited, returned or
+	/// stored. Calling `.catch(...)` (or `.then(...)` with a rejection handler) on it handles it
+	pub no_floating_promises: bool,
+
+	/// `&`, `|` and `^` on unions of (integer) number constants, such as combinations of flags, are
+	/// evaluated for each member. Warns on masks that have no bits in common with the value.
+	/// Conditions on `value & mask` narrow (dependent) values in each branch
+	pub advanced_numbers: bool,
+
+	/// Warn on assignments whose value is overwritten before being read (in the same block) and on
+	/// expression statements without side effects, whose value is discarded
+	pub no_dead_stores: bool,
+
+	/// Names of functions (or methods) whose returned promise can be left floating under
+	/// [`Self::no_floating_promises`]
+	pub floating_promise_allowed_functions: Vec<String>,
+
+	/// Globs (relative to the current working directory) of files to skip checking. See
+	/// [`crate::features::modules::should_skip_checking`]
+	pub skip_checking: Vec<String>,
+
+	/// Globs of files that are checked even if they match [`Self::skip_checking`]
+	pub always_check: Vec<String>,
+
+	/// Import specifier patterns (with at most one `*`) and the paths (relative to the current
+	/// working directory) they resolve to. Like `compilerOptions.paths` in a `tsconfig.json`
+	pub paths: Vec<(String, Vec<String>)>,
+
+	/// Extensions (such as `css` or `svg`) of imports that are not read, and the name of the type
+	/// of their default export (such as `string`, or a type from a definition file). For imports
+	/// of assets handled by a bundler
+	pub asset_modules: Vec<(String, String)>,
+
+	/// Record durations of stages and modules into [`crate::CheckOutput::chronometer`]
+	pub measure_time: bool,
+
+	/// Number of threads that entry points are parsed on. Modules are still synthesised one at a
+	/// time (on the current thread) as they share a [`crate::types::TypeStore`]. Ignored under WASM
+	pub threads: usize,
+
+	/// Logic for functions marked with `@Constant` or `@constant("name")` in definition files, for
+	/// identifiers without built-in logic
+	#[cfg_attr(any(feature = "serde-serialize", target_family = "wasm"), serde(skip))]
+	pub constant_functions: ConstantFunctionRegistry,
+
+	/// Checking stops (with incomplete diagnostics) after this is cancelled. For abandoning a check
+	/// when the files have changed since it started
+	#[cfg_attr(any(feature = "serde-serialize", target_family = "wasm"), serde(skip))]
+	pub cancellation: CancellationToken,
+
+	/// Called as each module starts and finishes being synthesised, for showing progress
+	#[cfg_attr(any(feature = "serde-serialize", target_family = "wasm"), serde(skip))]
+	pub module_progress: ModuleProgress,
 }
 
 impl Default for TypeCheckOptions {
@@ -48,6 +125,26 @@ impl Default for TypeCheckOptions {
 			strict_casts: false,
 			store_expression_type_mappings: false,
 			lsp_mode: false,
+			// 10MiB. Usually generated code
+			maximum_file_size: Some(10 * 1024 * 1024),
+			preserve_symlinks: false,
+			no_error_any_propagation: false,
+			no_missing_import_any: false,
+			no_explicit_any: false,
+			no_unused_bindings: false,
+			no_floating_promises: false,
+			advanced_numbers: false,
+			no_dead_stores: false,
+			floating_promise_allowed_functions: Vec::new(),
+			skip_checking: Vec::new(),
+			always_check: Vec::new(),
+			paths: Vec::new(),
+			asset_modules: Vec::new(),
+			measure_time: false,
+			threads: 1,
+			constant_functions: ConstantFunctionRegistry::default(),
+			cancellation: CancellationToken::default(),
+			module_progress: ModuleProgress::default(),
 			// TODO false at some point hopefully!
 			allow_cast: true,
 		}
@@ -0,0 +1,36 @@
+//! Reporting which module is being synthesised, for progress output. See
+//! [`crate::TypeCheckOptions::module_progress`]
+
+use std::{panic::RefUnwindSafe, path::Path, sync::Arc};
+
+/// Modules are synthesised depth first, so a module imported during the hoisting of another starts
+/// (and finishes) before the importing module finishes
+#[derive(Debug, Clone, Copy)]
+pub enum ModuleEvent<'a> {
+	Started(&'a Path),
+	Finished(&'a Path),
+}
+
+type ModuleProgressCallback = Arc<dyn Fn(ModuleEvent) + Send + Sync + RefUnwindSafe>;
+
+/// Called as each module starts and finishes being synthesised. Does nothing by default
+#[derive(Default, Clone)]
+pub struct ModuleProgress(Option<ModuleProgressCallback>);
+
+impl ModuleProgress {
+	pub fn new(callback: impl Fn(ModuleEvent) + Send + Sync + RefUnwindSafe + 'static) -> Self {
+		Self(Some(Arc::new(callback)))
+	}
+
+	pub(crate) fn emit(&self, event: ModuleEvent) {
+		if let Some(ref callback) = self.0 {
+			callback(event);
+		}
+	}
+}
+
+impl std::fmt::Debug for ModuleProgress {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("ModuleProgress").field(&self.0.is_some()).finish()
+	}
+}
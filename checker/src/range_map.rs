@@ -38,6 +38,11 @@ impl<T> RangeMap<T> {
 			.find_map(|(_, v)| v.iter().find_map(|(e, v)| (*e > point).then_some(v)))
 	}
 
+	/// All entries, ordered by where they start
+	pub fn iter(&self) -> impl Iterator<Item = (Range<u32>, &T)> {
+		self.entries.iter().flat_map(|(start, v)| v.iter().map(|(end, item)| (*start..*end, item)))
+	}
+
 	/// Get at an exact range
 	pub fn get_exact(&self, range: impl Into<Range<u32>>) -> Option<&T> {
 		let range = range.into();
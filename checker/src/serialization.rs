@@ -250,6 +250,34 @@ impl BinarySerializable for u32 {
 	}
 }
 
+impl BinarySerializable for u64 {
+	fn serialize(self, buf: &mut Vec<u8>) {
+		buf.extend_from_slice(&self.to_le_bytes());
+	}
+
+	fn deserialize<I: Iterator<Item = u8>>(iter: &mut I, _source: SourceId) -> Self {
+		let mut bytes = [0; 8];
+		for byte in &mut bytes {
+			*byte = iter.next().unwrap();
+		}
+		u64::from_le_bytes(bytes)
+	}
+}
+
+impl BinarySerializable for i128 {
+	fn serialize(self, buf: &mut Vec<u8>) {
+		buf.extend_from_slice(&self.to_le_bytes());
+	}
+
+	fn deserialize<I: Iterator<Item = u8>>(iter: &mut I, _source: SourceId) -> Self {
+		let mut bytes = [0; 16];
+		for byte in &mut bytes {
+			*byte = iter.next().unwrap();
+		}
+		i128::from_le_bytes(bytes)
+	}
+}
+
 impl BinarySerializable for TypeId {
 	fn serialize(self, buf: &mut Vec<u8>) {
 		buf.extend_from_slice(&self.0.to_le_bytes());
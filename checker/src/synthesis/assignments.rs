@@ -1,12 +1,16 @@
 use std::borrow::Cow;
 
 use parser::{
-	ast::LHSOfAssignment, expressions::assignments::VariableOrPropertyAccess, VariableIdentifier,
+	ast::LHSOfAssignment, expressions::assignments::VariableOrPropertyAccess, Expression,
+	PropertyReference, VariableIdentifier,
 };
 
 use crate::{
 	context::{information::Publicity, Environment},
-	features::assignments::{Assignable, Reference},
+	features::{
+		assignments::{Assignable, Reference},
+		variables::StoreTarget,
+	},
 	synthesis::expressions::synthesise_expression,
 	types::properties::PropertyKey,
 	CheckingData, TypeId,
@@ -160,3 +164,27 @@ pub(crate) fn synthesise_access_to_reference<T: crate::ReadFromFS>(
 		}
 	}
 }
+
+/// For [`crate::TypeCheckOptions::no_dead_stores`]. Assignments to variables and to properties of
+/// variables (with their name for the warning)
+pub(super) fn get_store_target(
+	lhs: &LHSOfAssignment,
+	environment: &Environment,
+) -> Option<(StoreTarget, String)> {
+	match lhs {
+		LHSOfAssignment::VariableOrPropertyAccess(VariableOrPropertyAccess::Variable(name, _)) => {
+			let variable = environment.get_variable_id(name)?;
+			Some((StoreTarget::Variable(variable), name.clone()))
+		}
+		LHSOfAssignment::VariableOrPropertyAccess(VariableOrPropertyAccess::PropertyAccess {
+			parent,
+			property: PropertyReference::Standard { property, is_private: false },
+			..
+		}) => {
+			let Expression::VariableReference(on, _) = &**parent else { return None };
+			let variable = environment.get_variable_id(on)?;
+			Some((StoreTarget::Property(variable, property.clone()), format!("{on}.{property}")))
+		}
+		_ => None,
+	}
+}
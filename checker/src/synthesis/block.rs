@@ -25,13 +25,18 @@ pub(super) fn synthesise_block<T: crate::ReadFromFS>(
 				synthesise_declaration(declaration, environment, checking_data);
 			}
 			StatementOrDeclaration::Marker(_, _) => {
-				crate::utils::notify!("should be unreachable");
+				crate::utils::notify!(Warn; "should be unreachable");
 			}
 		}
 
 		if environment.context_type.state.is_it_so_over() {
 			break;
 		}
+
+		// The rest is not unreachable, so return before the warnings
+		if checking_data.types.cancellation.is_cancelled() {
+			return;
+		}
 	}
 
 	for element in elements.filter(|e| {
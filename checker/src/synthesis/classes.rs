@@ -1,3 +1,5 @@
+use std::{borrow::Cow, collections::HashMap};
+
 use parser::{
 	declarations::{classes::ClassMember, ClassDeclaration},
 	functions::MethodHeader,
@@ -7,8 +9,8 @@ use source_map::{Nullable, SpanWithSource};
 
 use crate::{
 	context::{
-		information::{InformationChain, Publicity},
-		Environment, VariableRegisterArguments,
+		information::{get_property_unbound, InformationChain, Publicity},
+		Environment, Logical, VariableRegisterArguments,
 	},
 	diagnostics::{TypeCheckError, TypeStringRepresentation},
 	features::functions::{
@@ -18,12 +20,16 @@ use crate::{
 	subtyping::{type_is_subtype, BasicEquality, SubTypeResult},
 	synthesis::{
 		definitions::get_internal_function_effect_from_decorators,
-		functions::variable_field_to_string, parser_property_key_to_checker_property_key,
-		type_annotations::synthesise_type_annotation, variables::register_variable_identifier,
+		functions::{get_rest_parameter_item_type, variable_field_to_string},
+		parser_property_key_to_checker_property_key,
+		type_annotations::synthesise_type_annotation,
+		variables::register_variable_identifier,
 	},
 	types::{
-		classes::ClassValue, properties::PropertyKey, FunctionType, PolyNature,
-		SynthesisedParameter, SynthesisedParameters, TypeStore,
+		classes::ClassValue, get_constraint, poly_types::check_type_parameter_default,
+		properties::PropertyKey, substitute, Constructor, FunctionType, PolyNature,
+		StructureGenerics, SynthesisedParameter, SynthesisedParameters, SynthesisedRestParameter,
+		TypeStore,
 	},
 	CheckingData, DiagnosticsContainer, FunctionId, PropertyValue, Scope, Type, TypeId,
 };
@@ -83,7 +89,7 @@ pub(super) fn synthesise_class_declaration<
 
 	let class_constructor = class.members.iter().find_map(|member| {
 		if let ClassMember::Constructor(c) = &member.on {
-			Some(c)
+			Some((c, &member.decorators))
 		} else {
 			None
 		}
@@ -92,14 +98,34 @@ pub(super) fn synthesise_class_declaration<
 	// From table here https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Classes/extends#description
 	// TODO explain that no prototype => prototype = Function.prototype
 	if let Some(extends) = extends {
-		environment.info.prototypes.insert(class_prototype, extends);
-		let copied =
-			environment.get_chain_of_info().find_map(|info| info.prototypes.get(&extends)).copied();
-		if let Some(extends_prototype) = copied {
-			environment.info.prototypes.insert(class_prototype, extends_prototype);
+		// `Derived.prototype` inherits from `Base.prototype` (registered below)
+		let base_prototype = get_property_unbound(
+			extends,
+			Publicity::Public,
+			&PropertyKey::String(Cow::Borrowed("prototype")),
+			&checking_data.types,
+			environment,
+		);
+		if let Ok(Logical::Pure(PropertyValue::Value(base_prototype))) = base_prototype {
+			environment.info.prototypes.insert(class_prototype, base_prototype);
+		} else if let Some(instance) =
+			get_instance_type_of_dependent_base(extends, environment, &mut checking_data.types)
+		{
+			environment.info.prototypes.insert(class_prototype, instance);
+		} else {
+			environment.info.prototypes.insert(class_prototype, extends);
+			let copied = environment
+				.get_chain_of_info()
+				.find_map(|info| info.prototypes.get(&extends))
+				.copied();
+			if let Some(extends_prototype) = copied {
+				environment.info.prototypes.insert(class_prototype, extends_prototype);
+			}
 		}
 	}
 
+	check_duplicate_members(class, environment, checking_data);
+
 	let mut properties = Vec::new();
 
 	// Property keys on `static` items
@@ -187,8 +213,19 @@ pub(super) fn synthesise_class_declaration<
 					checking_data,
 					true,
 				);
+				let ambient_type = match (&property.value, &property.type_annotation) {
+					(None, Some(type_annotation)) if is_declare => Some(
+						synthesise_type_annotation(type_annotation, environment, checking_data),
+					),
+					_ => None,
+				};
 				// TODO restriction
-				properties.push(ClassValue { publicity, key, value: property.value.as_deref() });
+				properties.push(ClassValue {
+					publicity,
+					key,
+					value: property.value.as_deref(),
+					ambient_type,
+				});
 			}
 			ClassMember::Property(true, property) => {
 				let key = parser_property_key_to_checker_property_key(
@@ -213,15 +250,17 @@ pub(super) fn synthesise_class_declaration<
 	}
 
 	// TODO abstract
-	let constructor = if let Some(constructor) = class_constructor {
+	let constructor = if let Some((constructor, decorators)) = class_constructor {
 		let behavior = FunctionRegisterBehavior::Constructor {
 			prototype: class_prototype,
 			super_type: extends,
 			properties: ClassPropertiesToRegister { properties },
+			internal_marker: get_internal_function_effect_from_decorators(decorators, &name),
 		};
 		synthesise_function(constructor, behavior, environment, checking_data)
 	} else {
 		FunctionType::new_auto_constructor(
+			class.position.with_source(environment.get_source()),
 			class_prototype,
 			extends,
 			ClassPropertiesToRegister { properties },
@@ -232,6 +271,16 @@ pub(super) fn synthesise_class_declaration<
 
 	let class_type = checking_data.types.new_class_constructor_type(name, constructor);
 
+	// `Class.prototype`. For `instanceof` and classes extending this one
+	environment.info.register_property(
+		class_type,
+		Publicity::Public,
+		PropertyKey::String(Cow::Borrowed("prototype")),
+		PropertyValue::Value(class_prototype),
+		true,
+		None,
+	);
+
 	{
 		// Static items and blocks
 		static_property_keys.reverse();
@@ -354,6 +403,42 @@ pub(super) fn synthesise_class_declaration<
 	class_type
 }
 
+/// For `class extends Base` where `Base` is dependent (for example the parameter of a mixin
+/// function), instances have the return type of the construct signature of its constraint
+fn get_instance_type_of_dependent_base(
+	base: TypeId,
+	environment: &mut Environment,
+	types: &mut TypeStore,
+) -> Option<TypeId> {
+	let mut on = get_constraint(base, types)?;
+	loop {
+		match types.get_type_by_id(on) {
+			Type::AliasTo { to, parameters: None, .. } => on = *to,
+			Type::RootPolyType(_) => on = get_constraint(on, types)?,
+			_ => break,
+		}
+	}
+
+	let (on, arguments) =
+		if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on,
+			arguments,
+		})) = types.get_type_by_id(on)
+		{
+			(*on, Some(arguments.clone()))
+		} else {
+			(on, None)
+		};
+
+	let signature = types.get_construct_signature(on)?;
+	let return_type = types.get_function_from_id(signature).return_type;
+	Some(if let Some(mut arguments) = arguments {
+		substitute(return_type, &mut arguments, environment, types)
+	} else {
+		return_type
+	})
+}
+
 /// Also sets variable
 pub(super) fn register_statement_class_with_members<T: crate::ReadFromFS>(
 	class: &ClassDeclaration<StatementPosition>,
@@ -397,9 +482,10 @@ pub(super) fn register_statement_class_with_members<T: crate::ReadFromFS>(
 	let Type::Class { name: _, parameters } = get_type_by_id else {
 		unreachable!("expected class type {:?}", get_type_by_id)
 	};
+	let parameters = parameters.clone();
 
 	// TODO also remove
-	if let Some(parameters) = parameters {
+	if let Some(ref parameters) = parameters {
 		for parameter in parameters {
 			let parameter_ty = checking_data.types.get_type_by_id(*parameter);
 			let Type::RootPolyType(PolyNature::StructureGeneric { name, constrained: _ }) =
@@ -410,6 +496,25 @@ pub(super) fn register_statement_class_with_members<T: crate::ReadFromFS>(
 
 			environment.named_types.insert(name.clone(), *parameter);
 		}
+
+		// After all parameters are in scope, as defaults can reference them
+		for (parameter, ty) in class.type_parameters.iter().flatten().zip(parameters) {
+			if let Some(ref default) = parameter.default {
+				let default = synthesise_type_annotation(default, environment, checking_data);
+				if let Some(ref extends) = parameter.extends {
+					let extends = synthesise_type_annotation(extends, environment, checking_data);
+					check_type_parameter_default(
+						&parameter.name,
+						extends,
+						default,
+						parameter.position.with_source(environment.get_source()),
+						environment,
+						checking_data,
+					);
+				}
+				checking_data.types.set_type_parameter_default(*ty, default);
+			}
+		}
 	}
 
 	// Set the class type, should be okay
@@ -497,7 +602,12 @@ pub(super) fn register_statement_class_with_members<T: crate::ReadFromFS>(
 					None,
 				);
 			}
-			ClassMember::Property(_is_static, property) => {
+			ClassMember::Property(is_static, property) => {
+				// Static properties are on the class constructor (not instances)
+				if *is_static {
+					continue;
+				}
+
 				let under = crate::synthesis::parser_property_key_to_checker_property_key(
 					property.key.get_ast_ref(),
 					environment,
@@ -545,6 +655,60 @@ pub(super) fn register_statement_class_with_members<T: crate::ReadFromFS>(
 	}
 }
 
+/// Overloads (methods without a body) are skipped and a getter and setter of the same key are not
+/// duplicates. Only checks keys that are identifiers or string literals
+fn check_duplicate_members<T: crate::ReadFromFS, P: parser::ExpressionOrStatementPosition>(
+	class: &ClassDeclaration<P>,
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	// (is static, name) -> (position, kind)
+	let mut members = HashMap::<(bool, String), (SpanWithSource, GetterSetter)>::new();
+	for member in &class.members {
+		let (is_static, key, kind) = match &member.on {
+			ClassMember::Method(is_static, method) if method.body.0.is_some() => {
+				let kind = match method.header {
+					MethodHeader::Get => GetterSetter::Getter,
+					MethodHeader::Set => GetterSetter::Setter,
+					MethodHeader::Regular { .. } => GetterSetter::None,
+				};
+				(*is_static, method.name.get_ast_ref(), kind)
+			}
+			ClassMember::Property(is_static, property) => {
+				(*is_static, property.key.get_ast_ref(), GetterSetter::None)
+			}
+			_ => continue,
+		};
+		let name = match key {
+			ParserPropertyKey::Ident(name, ..) if key.is_private() => format!("#{name}"),
+			ParserPropertyKey::Ident(name, ..) | ParserPropertyKey::StringLiteral(name, ..) => {
+				name.clone()
+			}
+			_ => continue,
+		};
+
+		let position = member.on.get_position().with_source(environment.get_source());
+		match members.insert((is_static, name.clone()), (position, kind)) {
+			Some((_, existing_kind))
+				if matches!(
+					(existing_kind, kind),
+					(GetterSetter::Getter, GetterSetter::Setter)
+						| (GetterSetter::Setter, GetterSetter::Getter)
+				) =>
+			{
+				// Paired, so another getter or setter is a duplicate
+				members.insert((is_static, name), (position, GetterSetter::None));
+			}
+			Some((existing, _)) => {
+				checking_data
+					.diagnostics_container
+					.add_error(TypeCheckError::DuplicateClassMember { name, position, existing });
+			}
+			None => {}
+		}
+	}
+}
+
 fn synthesise_shape<T: crate::ReadFromFS>(
 	method: &parser::FunctionBase<parser::ast::ClassFunctionBase>,
 	environment: &mut Environment,
@@ -581,7 +745,19 @@ fn synthesise_shape<T: crate::ReadFromFS>(
 		})
 		.collect();
 
-	let rest_parameter = method.parameters.rest_parameter.as_ref().map(|_rest_parameter| todo!());
+	let rest_parameter = method.parameters.rest_parameter.as_ref().map(|rest_parameter| {
+		let ty = rest_parameter.type_annotation.as_ref().map_or(TypeId::ANY_TYPE, |annotation| {
+			synthesise_type_annotation(annotation, environment, checking_data)
+		});
+		let item_type = get_rest_parameter_item_type(ty, environment, &mut checking_data.types);
+
+		SynthesisedRestParameter {
+			name: variable_field_to_string(&rest_parameter.name),
+			item_type,
+			ty,
+			position: rest_parameter.position.with_source(environment.get_source()),
+		}
+	});
 
 	let return_type = method.return_type.as_ref().map(|annotation| {
 		ReturnType(
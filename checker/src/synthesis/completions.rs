@@ -0,0 +1,87 @@
+//! Completions at a position in a checked module. After a `.` they are the properties of the
+//! expression before it, which needs [`crate::TypeCheckOptions::store_expression_type_mappings`]
+//! (and `lsp_mode` if nothing has been written after the `.`). Otherwise they are the variables
+//! declared at the top level of the module
+
+use parser::source_map::FileSystem;
+
+use crate::{
+	context::information::get_properties_on_type,
+	types::{printing::TypeToStringOptions, properties::PropertyKey},
+	CheckOutput, TypeId,
+};
+
+use super::EznoParser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+pub enum CompletionKind {
+	Property,
+	Variable,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+pub struct Completion {
+	pub label: String,
+	pub kind: CompletionKind,
+	/// The value as a TypeScript annotation
+	pub r#type: Option<String>,
+}
+
+impl CheckOutput<EznoParser> {
+	/// Completions for the identifier being written at `pos` (which they start with), ordered by
+	/// label. `None` if the module was not checked
+	#[must_use]
+	pub fn get_completions_at_position(&self, path: &str, pos: u32) -> Option<Vec<Completion>> {
+		let source = self.module_contents.get_source_at_path(path.as_ref())?;
+		let module = self.modules.get(&source)?;
+		let content = self.module_contents.get_file_content(source);
+		let before = content.get(..pos as usize)?;
+		let word_start = before
+			.char_indices()
+			.rev()
+			.take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '$')
+			.last()
+			.map_or(before.len(), |(idx, _)| idx);
+		let prefix = &before[word_start..];
+
+		// Objects created in the module have their properties on its information
+		let info = [&module.info, &self.top_level_information];
+		let print_type =
+			|ty: TypeId| self.types.type_to_ts_string(ty, &info, TypeToStringOptions::default());
+
+		let mut completions = Vec::new();
+		if before[..word_start].ends_with('.') {
+			let dot = word_start as u32 - 1;
+			// The outermost expression that ends before the `.`
+			let on =
+				module.mappings.expressions_to_instances.iter().find_map(|(range, instance)| {
+					(range.end == dot).then(|| instance.get_value_on_ref())
+				});
+			let properties = on.map(|on| get_properties_on_type(on, &self.types, &info));
+			for (_, key, value) in properties.into_iter().flatten() {
+				if let PropertyKey::String(name) = key {
+					completions.push(Completion {
+						label: name.into_owned(),
+						kind: CompletionKind::Property,
+						r#type: Some(print_type(value)),
+					});
+				}
+			}
+		} else {
+			for (name, variable) in &module.names.variables {
+				let value = module.info.variable_current_value.get(&variable.get_id());
+				completions.push(Completion {
+					label: name.clone(),
+					kind: CompletionKind::Variable,
+					r#type: value.map(|value| print_type(*value)),
+				});
+			}
+		}
+
+		completions.retain(|completion| completion.label.starts_with(prefix));
+		completions.sort_by(|a, b| a.label.cmp(&b.label));
+		Some(completions)
+	}
+}
@@ -58,9 +58,15 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 			synthesise_variable_declaration(declaration, environment, checking_data, false);
 		}
 		Declaration::Class(class) => {
-			let _constructor = synthesise_class_declaration(&class.on, environment, checking_data);
+			// Ambient (`declare`) classes are synthesised during hoisting
+			if !class.on.name.declare {
+				let _constructor =
+					synthesise_class_declaration(&class.on, environment, checking_data);
+			}
 		}
 		Declaration::DeclareVariable(_)
+		| Declaration::DeclareGlobal(_)
+		| Declaration::DeclareModule(_)
 		| Declaration::Function(_)
 		| Declaration::Enum(_)
 		| Declaration::Interface(_)
@@ -72,8 +78,21 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 				match exported {
 					// Skipped as this is done earlier
 					parser::declarations::export::Exportable::Class(class) => {
-						// TODO mark as exported
 						synthesise_class_declaration(class, environment, checking_data);
+
+						if let parser::VariableIdentifier::Standard(ref name, position) =
+							class.name.identifier
+						{
+							let position = position.with_source(environment.get_source());
+							let variable = crate::VariableId(position.source, position.start);
+							if let Scope::Module { ref mut exported, .. } =
+								environment.context_type.scope
+							{
+								exported
+									.named
+									.push((name.clone(), (variable, VariableMutability::Constant)));
+							}
+						}
 					}
 					parser::declarations::export::Exportable::Variable(variable) => {
 						synthesise_variable_declaration(variable, environment, checking_data, true);
@@ -83,6 +102,20 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 							let pair = super::hoisting::export_part_to_name_pair(part);
 							if let Some(pair) = pair {
 								let position = pair.position.with_source(environment.get_source());
+								// Type only exports (`export { SomeInterface }`)
+								if environment.get_variable_unbound(pair.value).is_none() {
+									if let Some(ty) = environment.get_type_from_name(pair.value) {
+										if let crate::Scope::Module { ref mut exported, .. } =
+											environment.context_type.scope
+										{
+											exported
+												.positions
+												.push((pair.r#as.to_owned(), position));
+											exported.named_types.push((pair.r#as.to_owned(), ty));
+										}
+										continue;
+									}
+								}
 								let value = environment.get_variable_handle_error(
 									pair.value,
 									position,
@@ -92,6 +125,7 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 									environment.context_type.scope
 								{
 									if let Ok(value) = value {
+										exported.positions.push((pair.r#as.to_owned(), position));
 										exported.named.push((
 											pair.r#as.to_owned(),
 											(value.0.get_id(), value.0.get_mutability()),
@@ -105,7 +139,8 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 					| parser::declarations::export::Exportable::ImportParts { .. }
 					| parser::declarations::export::Exportable::Function(_)
 					| parser::declarations::export::Exportable::Interface(_)
-					| parser::declarations::export::Exportable::TypeAlias(_) => {}
+					| parser::declarations::export::Exportable::TypeAlias(_)
+					| parser::declarations::export::Exportable::Namespace(_) => {}
 				}
 			}
 			parser::declarations::ExportDeclaration::Default { expression, position } => {
@@ -113,6 +148,7 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 				let result =
 					synthesise_expression(expression, environment, checking_data, TypeId::ANY_TYPE);
 
+				let source = environment.get_source();
 				if let Scope::Module { ref mut exported, .. } = environment.context_type.scope {
 					if exported.default.is_some() {
 						checking_data.diagnostics_container.add_error(
@@ -122,6 +158,9 @@ pub(crate) fn synthesise_declaration<T: crate::ReadFromFS>(
 						);
 					} else {
 						exported.default = Some(result);
+						exported
+							.positions
+							.push(("default".to_owned(), position.with_source(source)));
 					}
 				} else {
 					checking_data.diagnostics_container.add_error(
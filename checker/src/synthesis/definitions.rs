@@ -26,12 +26,40 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 	checking_data: &mut crate::CheckingData<T, super::EznoParser>,
 	root: &RootContext,
 ) -> (Names, LocalInformation) {
+	let mut environment = root.new_lexical_environment(crate::Scope::DefinitionModule { source });
+
+	// Definition files are already global, so items in `declare global` blocks are treated as top level
+	let items: Vec<StatementOrDeclaration> = definition
+		.items
+		.into_iter()
+		.flat_map(|item| {
+			if let StatementOrDeclaration::Declaration(Declaration::DeclareGlobal(global)) = item {
+				global.inner.0
+			} else {
+				vec![item]
+			}
+		})
+		.collect();
+
+	synthesise_definition_items(&items, &mut environment, checking_data);
+
+	let Environment { named_types, info, variable_names, variables, .. } = environment;
+	(Names { variables, named_types, variable_names }, info)
+}
+
+/// Items of a definition file or a `declare global` block in a module. Variables and functions do
+/// not need initialisers or bodies
+pub(super) fn synthesise_definition_items<T: crate::ReadFromFS>(
+	items: &[StatementOrDeclaration],
+	environment: &mut Environment,
+	checking_data: &mut crate::CheckingData<T, super::EznoParser>,
+) {
 	use parser::declarations::{DeclareVariableDeclaration, TypeAlias};
 
-	let mut environment = root.new_lexical_environment(crate::Scope::DefinitionModule { source });
+	let source = environment.get_source();
 
 	// Hoisting names of interfaces, namespaces and types
-	for statement in &definition.items {
+	for statement in items {
 		// TODO classes and exports
 		match statement {
 			StatementOrDeclaration::Declaration(Declaration::Interface(interface)) => {
@@ -71,18 +99,31 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 		}
 	}
 
-	for declaration in &definition.items {
+	super::hoisting::register_type_deprecations(items, environment, checking_data);
+
+	for declaration in items {
+		if let StatementOrDeclaration::Declaration(Declaration::Namespace(namespace)) = declaration
+		{
+			super::hoisting::synthesise_namespace(namespace, environment, checking_data);
+		}
+	}
+
+	for declaration in items {
 		// TODO more
 		match declaration {
+			// `var` (rather than `declare var`) is used in `declare global` blocks
 			StatementOrDeclaration::Declaration(Declaration::DeclareVariable(
 				DeclareVariableDeclaration { keyword: _, declarations, position: _, decorators: _ },
+			))
+			| StatementOrDeclaration::Statement(Statement::VarVariable(
+				parser::statements::VarVariableStatement { declarations, position: _ },
 			)) => {
 				for declaration in declarations {
 					// TODO is it ever `None`...?
 					let constraint = declaration.type_annotation.as_ref().map_or(
 						TypeId::ANY_TYPE,
 						|annotation| {
-							synthesise_type_annotation(annotation, &mut environment, checking_data)
+							synthesise_type_annotation(annotation, environment, checking_data)
 						},
 					);
 
@@ -91,7 +132,7 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 					));
 					crate::synthesis::variables::register_variable(
 						declaration.name.get_ast_ref(),
-						&mut environment,
+						environment,
 						checking_data,
 						VariableRegisterArguments { constant: true, space: None, initial_value },
 					);
@@ -109,12 +150,12 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 					interface.on.extends.as_deref(),
 					&interface.on.members,
 					super::interfaces::OnToType(ty),
-					&mut environment,
+					environment,
 					checking_data,
 				);
 			}
 			StatementOrDeclaration::Declaration(Declaration::Class(class)) => {
-				register_statement_class_with_members(&class.on, &mut environment, checking_data);
+				register_statement_class_with_members(&class.on, environment, checking_data);
 			}
 			StatementOrDeclaration::Declaration(Declaration::TypeAlias(TypeAlias {
 				name: _,
@@ -152,7 +193,7 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 			StatementOrDeclaration::Declaration(Declaration::Function(function)) => {
 				crate::synthesis::variables::register_variable_identifier(
 					&function.on.name.identifier,
-					&mut environment,
+					environment,
 					checking_data,
 					VariableRegisterArguments {
 						constant: FUNCTION_REASSIGNMENT_CONSTANT,
@@ -161,7 +202,10 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 					},
 				);
 			}
-			StatementOrDeclaration::Statement(Statement::Comment(..) | Statement::Empty(..)) => {}
+			StatementOrDeclaration::Statement(
+				Statement::Comment(..) | Statement::MultiLineComment(..) | Statement::Empty(..),
+			)
+			| StatementOrDeclaration::Declaration(Declaration::Namespace(_)) => {}
 			item => checking_data.diagnostics_container.add_warning(
 				TypeCheckWarning::InvalidOrUnimplementedDefinitionFileItem(
 					item.get_position().with_source(environment.get_source()),
@@ -170,11 +214,11 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 		}
 	}
 
-	for declaration in definition.items {
+	for declaration in items {
 		match declaration {
 			StatementOrDeclaration::Declaration(Declaration::Class(class)) => {
 				let class_type =
-					synthesise_class_declaration(&class.on, &mut environment, checking_data);
+					synthesise_class_declaration(&class.on, environment, checking_data);
 				let variable_id = VariableId(
 					environment.get_source(),
 					class.on.name.identifier.get_position().start,
@@ -210,7 +254,7 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 					location,
 					internal_marker,
 					&function.on,
-					&mut environment,
+					environment,
 					checking_data,
 				);
 			}
@@ -218,8 +262,7 @@ pub(super) fn type_definition_file<T: crate::ReadFromFS>(
 		}
 	}
 
-	let Environment { named_types, info, variable_names, variables, .. } = environment;
-	(Names { variables, named_types, variable_names }, info)
+	super::hoisting::register_variable_deprecations(items, environment, checking_data);
 }
 
 pub(crate) fn get_internal_function_effect_from_decorators(
@@ -230,10 +273,39 @@ pub(crate) fn get_internal_function_effect_from_decorators(
 		if d.name.len() == 1 {
 			let name = d.name.first().map(String::as_str)?;
 			match name {
-				"Constant" => Some(InternalFunctionEffect::Constant(function_name.to_owned())),
+				// `@constant("name")` for constant functions registered through
+				// `TypeCheckOptions::constant_functions` under a different identifier
+				"Constant" | "constant" => {
+					let identifier =
+						if let Some([parser::Expression::StringLiteral(identifier, ..)]) =
+							d.arguments.as_deref()
+						{
+							identifier.clone()
+						} else {
+							function_name.to_owned()
+						};
+					Some(InternalFunctionEffect::Constant(identifier))
+				}
 				"InputOutput" => {
 					Some(InternalFunctionEffect::InputOutput(function_name.to_owned()))
 				}
+				"pure" => Some(InternalFunctionEffect::Pure),
+				// `@mutates(a, b)` where `a` and `b` are parameter names
+				"mutates" => {
+					let names = d
+						.arguments
+						.iter()
+						.flatten()
+						.filter_map(|argument| {
+							if let parser::Expression::VariableReference(name, _) = argument {
+								Some(name.clone())
+							} else {
+								None
+							}
+						})
+						.collect();
+					Some(InternalFunctionEffect::Mutates(names))
+				}
 				_ => None,
 			}
 		} else {
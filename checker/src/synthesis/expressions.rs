@@ -1,4 +1,4 @@
-use std::{borrow::Cow, convert::TryInto};
+use std::{borrow::Cow, collections::HashMap, convert::TryInto};
 
 use parser::{
 	ast::TypeOrConst,
@@ -7,25 +7,28 @@ use parser::{
 		operators::{
 			BinaryOperator, IncrementOrDecrement, UnaryOperator, UnaryPrefixAssignmentOperator,
 		},
-		ArrayElement, FunctionArgument, MultipleExpression, SpecialOperators, SuperReference,
-		TemplateLiteral,
+		FunctionArgument, MultipleExpression, SpecialOperators, SuperReference, TemplateLiteral,
 	},
 	functions::MethodHeader,
 	ASTNode, Expression,
 };
+use source_map::{Span, SpanWithSource};
 
 use crate::{
 	context::{
 		information::{get_properties_on_type, get_property_unbound},
 		Logical,
 	},
-	diagnostics::{TypeCheckError, TypeStringRepresentation},
+	diagnostics::{TypeCheckError, TypeCheckWarning, TypeStringRepresentation},
 	features::{
 		self, await_expression,
 		functions::{
 			function_to_property, register_arrow_function, register_expression_function,
 			synthesise_function, GetterSetter,
 		},
+		iteration::{get_iterated_items, IteratedItems},
+		objects::is_readonly,
+		semantic_tokens::SemanticTokenKind,
 		variables::VariableWithValue,
 	},
 	synthesis::parser_property_key_to_checker_property_key,
@@ -44,7 +47,7 @@ use crate::{
 		operations::{
 			evaluate_logical_operation_with_expression,
 			evaluate_pure_binary_operation_handle_errors, evaluate_pure_unary_operator,
-			EqualityAndInequality, MathematicalAndBitwise, PureUnary,
+			evaluate_unary_plus, EqualityAndInequality, MathematicalAndBitwise, PureUnary,
 		},
 		template_literal::synthesise_template_literal_expression,
 	},
@@ -54,7 +57,9 @@ use crate::{
 };
 
 use super::{
-	assignments::{synthesise_access_to_reference, synthesise_lhs_of_assignment_to_reference},
+	assignments::{
+		get_store_target, synthesise_access_to_reference, synthesise_lhs_of_assignment_to_reference,
+	},
 	classes::synthesise_class_declaration,
 	extensions::{is_expression::synthesise_is_expression, jsx::synthesise_jsx_root},
 	type_annotations::synthesise_type_annotation,
@@ -86,62 +91,31 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 ) -> TypeId {
 	let instance: Instance = match expression {
 		Expression::StringLiteral(value, ..) => {
-			return checking_data.types.new_constant_type(Constant::String(value.clone()))
+			Instance::RValue(checking_data.types.new_constant_type(Constant::String(value.clone())))
 		}
 		Expression::RegexLiteral { pattern, flags: _, position: _ } => {
 			return checking_data.types.new_regex(pattern.clone());
 		}
-		Expression::NumberLiteral(value, ..) => {
-			let not_nan = if let Ok(v) = f64::try_from(value.clone()) {
-				v.try_into().unwrap()
+		Expression::NumberLiteral(parser::NumberRepresentation::BigInt(sign, value), ..) => {
+			return if let Some(value) = super::big_int_value(*sign, value) {
+				checking_data.types.new_constant_type(Constant::BigInt(value))
 			} else {
-				crate::utils::notify!("TODO big int");
-				return TypeId::ERROR_TYPE;
+				TypeId::BIGINT_TYPE
 			};
-			return checking_data.types.new_constant_type(Constant::Number(not_nan));
+		}
+		Expression::NumberLiteral(value, ..) => {
+			let not_nan = f64::try_from(value.clone()).unwrap().try_into().unwrap();
+			Instance::RValue(checking_data.types.new_constant_type(Constant::Number(not_nan)))
 		}
 		Expression::BooleanLiteral(value, ..) => {
-			return checking_data.types.new_constant_type(Constant::Boolean(*value))
+			Instance::RValue(checking_data.types.new_constant_type(Constant::Boolean(*value)))
 		}
 		Expression::ArrayLiteral(elements, _) => {
-			fn synthesise_array_item<T: crate::ReadFromFS>(
-				idx: &Decidable<usize>,
-				element: &ArrayElement,
-				environment: &mut Environment,
-				checking_data: &mut CheckingData<T, super::EznoParser>,
-			) -> Option<(PropertyKey<'static>, TypeId)> {
-				element.0.as_ref().map(|element| match element {
-					FunctionArgument::Standard(element) => {
-						// TODO based off above
-						let expecting = TypeId::ANY_TYPE;
-						let expression_type =
-							synthesise_expression(element, environment, checking_data, expecting);
-						(
-							PropertyKey::from_usize(match idx {
-								Decidable::Known(idx) => *idx,
-								Decidable::Unknown(_) => todo!(),
-							}),
-							expression_type,
-						)
-					}
-					FunctionArgument::Spread(_expr, position) => {
-						{
-							checking_data.raise_unimplemented_error(
-								"Spread elements",
-								position.with_source(environment.get_source()),
-							);
-						}
-						crate::utils::notify!("Skipping spread");
-						(
-							PropertyKey::from_usize(match idx {
-								Decidable::Known(idx) => *idx,
-								Decidable::Unknown(_) => todo!(),
-							}),
-							TypeId::ERROR_TYPE,
-						)
-					}
-					FunctionArgument::Comment { .. } => todo!(),
-				})
+			fn index_to_key(idx: &Decidable<usize>) -> PropertyKey<'static> {
+				match idx {
+					Decidable::Known(idx) => PropertyKey::from_usize(*idx),
+					Decidable::Unknown(ty) => PropertyKey::Type(*ty),
+				}
 			}
 
 			let mut basis = ObjectBuilder::new(
@@ -150,29 +124,87 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 				&mut environment.info,
 			);
 
-			// TODO remove enumerate, add add function and more
-			for (idx, value) in elements.iter().enumerate() {
-				let spread_expression_position =
-					value.get_position().with_source(environment.get_source());
+			// After spreading an iterable of unknown length, the indexes of items are unknown
+			let mut idx = Decidable::Known(0);
 
-				if let Some((key, value)) =
-					synthesise_array_item(&Decidable::Known(idx), value, environment, checking_data)
-				{
-					basis.append(
-						environment,
-						Publicity::Public,
-						key,
-						crate::types::properties::PropertyValue::Value(value),
-						Some(spread_expression_position),
-					);
+			for element in elements {
+				let element_position = element.get_position().with_source(environment.get_source());
+
+				match &element.0 {
+					Some(FunctionArgument::Standard(element)) => {
+						// TODO based off above
+						let expecting = TypeId::ANY_TYPE;
+						let value =
+							synthesise_expression(element, environment, checking_data, expecting);
+						basis.append(
+							environment,
+							Publicity::Public,
+							index_to_key(&idx),
+							crate::types::properties::PropertyValue::Value(value),
+							Some(element_position),
+						);
+					}
+					Some(FunctionArgument::Spread(expression, position)) => {
+						let iterable = synthesise_expression(
+							expression,
+							environment,
+							checking_data,
+							TypeId::ANY_TYPE,
+						);
+						let position = position.with_source(environment.get_source());
+						match get_iterated_items(iterable, position, environment, checking_data) {
+							IteratedItems::Known(items) => {
+								for value in items {
+									basis.append(
+										environment,
+										Publicity::Public,
+										index_to_key(&idx),
+										crate::types::properties::PropertyValue::Value(value),
+										Some(element_position),
+									);
+									if let Decidable::Known(ref mut idx) = idx {
+										*idx += 1;
+									}
+								}
+								continue;
+							}
+							IteratedItems::Unknown(value) => {
+								idx = Decidable::Unknown(TypeId::NUMBER_TYPE);
+								basis.append(
+									environment,
+									Publicity::Public,
+									index_to_key(&idx),
+									crate::types::properties::PropertyValue::Value(value),
+									Some(element_position),
+								);
+							}
+						}
+					}
+					Some(FunctionArgument::Comment { .. }) => todo!(),
+					// Holes are read as `undefined`
+					None => {
+						basis.append(
+							environment,
+							Publicity::Public,
+							index_to_key(&idx),
+							crate::types::properties::PropertyValue::Value(TypeId::UNDEFINED_TYPE),
+							Some(element_position),
+						);
+					}
+				}
+
+				if let Decidable::Known(ref mut idx) = idx {
+					*idx += 1;
 				}
 			}
 
 			{
-				// TODO spread
-				let length = checking_data.types.new_constant_type(Constant::Number(
-					(elements.len() as f64).try_into().unwrap(),
-				));
+				let length = match idx {
+					Decidable::Known(length) => checking_data
+						.types
+						.new_constant_type(Constant::Number((length as f64).try_into().unwrap())),
+					Decidable::Unknown(_) => TypeId::NUMBER_TYPE,
+				};
 
 				// TODO: Should there be a position here?
 				basis.append(
@@ -299,7 +331,19 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 		Expression::UnaryOperation { operand, operator, position } => {
 			match operator {
 				UnaryOperator::Plus => {
-					todo!("cast to number")
+					let operand_type = synthesise_expression(
+						operand,
+						environment,
+						checking_data,
+						TypeId::ANY_TYPE,
+					);
+					let operand_position =
+						ASTNode::get_position(&**operand).with_source(environment.get_source());
+					Instance::RValue(evaluate_unary_plus(
+						(operand_type, operand_position),
+						environment,
+						checking_data,
+					))
 				}
 				UnaryOperator::Negation | UnaryOperator::BitwiseNot | UnaryOperator::LogicalNot => {
 					let operand_type = synthesise_expression(
@@ -429,16 +473,41 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 			}
 		}
 		Expression::Assignment { lhs, rhs, position } => {
+			let store = if checking_data.options.no_dead_stores {
+				get_store_target(lhs, environment)
+			} else {
+				None
+			};
+
 			let lhs: Assignable =
 				synthesise_lhs_of_assignment_to_reference(lhs, environment, checking_data);
 
-			return environment.assign_to_assignable_handle_errors(
+			let value = environment.assign_to_assignable_handle_errors(
 				lhs,
 				crate::features::assignments::AssignmentKind::Assign,
 				Some(&**rhs),
 				*position,
 				checking_data,
 			);
+
+			if let Some((target, name)) = store {
+				let position = position.with_source(environment.get_source());
+				let dead = checking_data.dead_stores.assign(
+					target,
+					position,
+					environment.context_id,
+					has_side_effects(rhs),
+				);
+				if let Some(dead) = dead {
+					checking_data.diagnostics_container.add_warning(TypeCheckWarning::DeadStore {
+						name,
+						position: dead,
+						overwritten_at: position,
+					});
+				}
+			}
+
+			return value;
 		}
 		Expression::BinaryAssignmentOperation { lhs, operator, rhs, position } => {
 			let lhs: Assignable = Assignable::Reference(synthesise_access_to_reference(
@@ -524,9 +593,17 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 				checking_data,
 			);
 
+			let (variable, value) = match &get_variable_or_alternatives {
+				Ok(VariableWithValue(variable, value)) => (Some(variable), *value),
+				Err(value) => (None, *value),
+			};
+			let kind =
+				checking_data.get_variable_token_kind(variable, value, environment.get_source());
+			checking_data.add_semantic_token(*position, kind);
+
 			match get_variable_or_alternatives {
 				Ok(variable) => Instance::LValue(variable),
-				Err(_err) => Instance::RValue(TypeId::ERROR_TYPE),
+				Err(value) => Instance::RValue(value),
 			}
 		}
 		Expression::PropertyAccess { parent, position, property, .. } => {
@@ -552,6 +629,17 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 				*position,
 			);
 
+			if let (Ok(_), PropertyKey::String(name)) = (&result, &property) {
+				let kind = if is_readonly(on, environment, &checking_data.types) {
+					SemanticTokenKind::ReadonlyProperty
+				} else {
+					SemanticTokenKind::Property
+				};
+				// The property is at the end
+				let name_start = position.end - name.len() as u32;
+				checking_data.add_semantic_token(Span { start: name_start, ..*position }, kind);
+			}
+
 			match result {
 				Ok(instance) => instance,
 				Err(()) => return TypeId::ERROR_TYPE,
@@ -664,11 +752,28 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 					SuperReference::Index { indexer: _ } => todo!(),
 				}
 			} else {
-				crate::utils::notify!("TODO error");
+				crate::utils::notify!(Warn; "TODO error");
 				Instance::RValue(TypeId::ERROR_TYPE)
 			}
 		}
 		Expression::NewTarget(..) => todo!(),
+		Expression::ImportMeta(..) => {
+			// Shape from the (possibly augmented) `ImportMeta` interface in the definition files
+			if let Some(import_meta) = environment.get_type_from_name("ImportMeta") {
+				Instance::RValue(checking_data.types.new_open_type(import_meta))
+			} else {
+				let mut object =
+					ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info);
+				object.append(
+					environment,
+					Publicity::Public,
+					PropertyKey::String(Cow::Borrowed("url")),
+					crate::types::properties::PropertyValue::Value(TypeId::STRING_TYPE),
+					None,
+				);
+				Instance::RValue(object.build_object())
+			}
+		}
 		Expression::FunctionCall { function, type_arguments, arguments, position, .. } => {
 			let on = synthesise_expression(function, environment, checking_data, TypeId::ANY_TYPE);
 
@@ -765,52 +870,47 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 			SpecialOperators::AsCast { value, rhs } => {
 				let to_cast = synthesise_expression(value, environment, checking_data, expecting);
 
-				if checking_data.options.allow_cast {
-					match rhs {
-						TypeOrConst::Type(type_annotation) => {
-							let cast_to = synthesise_type_annotation(
-								type_annotation,
-								environment,
-								checking_data,
-							);
-
-							// TODO
-							let as_cast =
-								features::as_cast(to_cast, cast_to, &mut checking_data.types);
-
-							match as_cast {
-								Ok(result) => return result,
-								Err(_err) => {
-									checking_data.diagnostics_container.add_error(
-										TypeCheckError::InvalidCast {
-											position: position
-												.with_source(environment.get_source()),
-											from: TypeStringRepresentation::from_type_id(
-												to_cast,
-												environment,
-												&checking_data.types,
-												checking_data.options.debug_types,
-											),
-											to: TypeStringRepresentation::from_type_id(
-												cast_to,
-												environment,
-												&checking_data.types,
-												checking_data.options.debug_types,
-											),
-										},
-									);
-									return TypeId::ERROR_TYPE;
-								}
+				match rhs {
+					TypeOrConst::Type(type_annotation) if checking_data.options.allow_cast => {
+						let cast_to =
+							synthesise_type_annotation(type_annotation, environment, checking_data);
+
+						// TODO
+						let as_cast = features::as_cast(to_cast, cast_to, &mut checking_data.types);
+
+						match as_cast {
+							Ok(result) => return result,
+							Err(_err) => {
+								checking_data.diagnostics_container.add_error(
+									TypeCheckError::InvalidCast {
+										position: position.with_source(environment.get_source()),
+										from: TypeStringRepresentation::from_type_id(
+											to_cast,
+											environment,
+											&checking_data.types,
+											checking_data.options.debug_types,
+										),
+										to: TypeStringRepresentation::from_type_id(
+											cast_to,
+											environment,
+											&checking_data.types,
+											checking_data.options.debug_types,
+										),
+									},
+								);
+								return TypeId::ERROR_TYPE;
 							}
 						}
-						TypeOrConst::Const(_) => {
-							// TODO
-							Instance::RValue(to_cast)
-						}
 					}
-				} else {
-					// TODO emit warning
-					Instance::RValue(to_cast)
+					TypeOrConst::Type(_) => {
+						// TODO emit warning
+						Instance::RValue(to_cast)
+					}
+					TypeOrConst::Const(_) => {
+						// Values are already literals. Objects become `readonly`
+						features::objects::freeze(to_cast, true, environment, &checking_data.types);
+						Instance::RValue(to_cast)
+					}
 				}
 			}
 			SpecialOperators::Is { value: _, type_annotation: _ } => {
@@ -849,21 +949,66 @@ pub(super) fn synthesise_expression<T: crate::ReadFromFS>(
 
 				Instance::RValue(if result { TypeId::TRUE } else { TypeId::FALSE })
 			}
-			SpecialOperators::InstanceOf { .. } => {
-				checking_data.raise_unimplemented_error(
-					"instanceof expression",
-					position.with_source(environment.get_source()),
+			SpecialOperators::InstanceOf { lhs, rhs } => {
+				let lhs_position = lhs.get_position().with_source(environment.get_source());
+				let lhs = synthesise_expression(lhs, environment, checking_data, TypeId::ANY_TYPE);
+				let rhs = synthesise_expression(rhs, environment, checking_data, TypeId::ANY_TYPE);
+
+				let position = position.with_source(environment.get_source());
+				let result = crate::features::symbols::call_has_instance(
+					(lhs, lhs_position),
+					rhs,
+					position,
+					environment,
+					checking_data,
 				);
-				return TypeId::ERROR_TYPE;
+
+				let result = result.or_else(|| {
+					crate::features::symbols::ordinary_has_instance(
+						lhs,
+						rhs,
+						environment,
+						&mut checking_data.types,
+					)
+				});
+
+				if let Some(result) = result {
+					Instance::RValue(result)
+				} else {
+					checking_data.raise_unimplemented_error("instanceof expression", position);
+					return TypeId::ERROR_TYPE;
+				}
 			}
 			SpecialOperators::NonNullAssertion(_) => todo!(),
 		},
-		Expression::DynamicImport { position, .. } => {
-			checking_data.raise_unimplemented_error(
-				"dynamic import",
-				position.with_source(environment.get_source()),
-			);
-			return TypeId::ERROR_TYPE;
+		Expression::DynamicImport { path, position, .. } => {
+			let position = position.with_source(environment.get_source());
+			// Only the exports of string literal specifiers are known
+			let Expression::StringLiteral(specifier, ..) = &**path else {
+				checking_data.raise_unimplemented_error("non-constant dynamic import", position);
+				return TypeId::ERROR_TYPE;
+			};
+			let exports = match crate::features::modules::import_file(
+				specifier,
+				environment,
+				checking_data,
+			) {
+				Ok(Ok(exports)) => crate::features::modules::create_namespace_object(
+					&exports,
+					environment,
+					checking_data,
+				),
+				// Syntax errors of the module have already been emitted
+				Ok(Err(_)) => TypeId::ERROR_TYPE,
+				Err(file) => {
+					checking_data.diagnostics_container.add_error(TypeCheckError::CannotOpenFile {
+						file,
+						position: Some(position),
+					});
+					TypeId::ERROR_TYPE
+				}
+			};
+			Instance::RValue(checking_data.types.new_promise_type(exports, position))
 		}
 		Expression::IsExpression(is_expr) => {
 			Instance::RValue(synthesise_is_expression(is_expr, environment, checking_data))
@@ -975,6 +1120,10 @@ fn call_function<T: crate::ReadFromFS>(
 		})
 		.unwrap_or_default();
 
+	if checking_data.options.no_dead_stores {
+		checking_data.dead_stores.call();
+	}
+
 	crate::types::calling::call_type_handle_errors(
 		function_type_id,
 		&arguments,
@@ -998,6 +1147,9 @@ pub(super) fn synthesise_object_literal<T: crate::ReadFromFS>(
 	let mut object_builder =
 		ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info);
 
+	// For finding duplicate keys
+	let mut keys = HashMap::<String, (SpanWithSource, GetterSetter)>::new();
+
 	for member in members {
 		let member_position = member.get_position().with_source(environment.get_source());
 		match member {
@@ -1020,6 +1172,13 @@ pub(super) fn synthesise_object_literal<T: crate::ReadFromFS>(
 			}
 			ObjectLiteralMember::Shorthand(name, position) => {
 				let key = PropertyKey::String(Cow::Owned(name.clone()));
+				check_duplicate_key(
+					&mut keys,
+					&key,
+					GetterSetter::None,
+					member_position,
+					checking_data,
+				);
 				let get_variable = environment.get_variable_handle_error(
 					name,
 					position.with_source(environment.get_source()),
@@ -1027,16 +1186,7 @@ pub(super) fn synthesise_object_literal<T: crate::ReadFromFS>(
 				);
 				let value = match get_variable {
 					Ok(VariableWithValue(_variable, value)) => value,
-					Err(_err) => {
-						// checking_data.diagnostics_container.add_error(
-						// 	TypeCheckError::CouldNotFindVariable {
-						// 		variable: err.name,
-						// 		possibles: err.possibles,
-						// 		position: position.clone(),
-						// 	},
-						// );
-						TypeId::ERROR_TYPE
-					}
+					Err(value) => value,
 				};
 
 				object_builder.append(
@@ -1054,6 +1204,13 @@ pub(super) fn synthesise_object_literal<T: crate::ReadFromFS>(
 					checking_data,
 					true,
 				);
+				check_duplicate_key(
+					&mut keys,
+					&key,
+					GetterSetter::None,
+					member_position,
+					checking_data,
+				);
 
 				// TODO needs improvement
 				let property_expecting = get_property_unbound(
@@ -1131,6 +1288,7 @@ pub(super) fn synthesise_object_literal<T: crate::ReadFromFS>(
 					MethodHeader::Set => GetterSetter::Setter,
 					MethodHeader::Regular { .. } => GetterSetter::None,
 				};
+				check_duplicate_key(&mut keys, &key, kind, member_position, checking_data);
 
 				let property =
 					function_to_property(kind, function, &mut checking_data.types, false);
@@ -1148,3 +1306,100 @@ pub(super) fn synthesise_object_literal<T: crate::ReadFromFS>(
 
 	object_builder.build_object()
 }
+
+/// Warns if a (string) key has already been set in the object literal. A getter and setter of the same
+/// key is not a duplicate
+fn check_duplicate_key<T: crate::ReadFromFS>(
+	keys: &mut HashMap<String, (SpanWithSource, GetterSetter)>,
+	key: &PropertyKey,
+	kind: GetterSetter,
+	position: SpanWithSource,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	let PropertyKey::String(key) = key else {
+		return;
+	};
+	match keys.insert(key.to_string(), (position, kind)) {
+		Some((_, existing_kind))
+			if matches!(
+				(existing_kind, kind),
+				(GetterSetter::Getter, GetterSetter::Setter)
+					| (GetterSetter::Setter, GetterSetter::Getter)
+			) =>
+		{
+			// Paired, so another getter or setter is a duplicate
+			keys.insert(key.to_string(), (position, GetterSetter::None));
+		}
+		Some((existing, _)) => {
+			checking_data.diagnostics_container.add_warning(TypeCheckWarning::DuplicateObjectKey {
+				key: key.to_string(),
+				position,
+				existing,
+			});
+		}
+		None => {}
+	}
+}
+
+/// For [`crate::TypeCheckOptions::no_dead_stores`]. Conservative, so only `false` for expressions
+/// that do not call functions or assign (ignoring getters and implicit conversions)
+pub(super) fn has_side_effects(expression: &Expression) -> bool {
+	match expression {
+		Expression::NumberLiteral(..)
+		| Expression::StringLiteral(..)
+		| Expression::BooleanLiteral(..)
+		| Expression::RegexLiteral { .. }
+		| Expression::Null(..)
+		| Expression::VariableReference(..)
+		| Expression::ThisReference(..)
+		| Expression::NewTarget(..)
+		| Expression::ImportMeta(..)
+		| Expression::ArrowFunction(..)
+		| Expression::ExpressionFunction(..) => false,
+		Expression::ArrayLiteral(elements, _) => elements.iter().any(|element| match &element.0 {
+			Some(FunctionArgument::Standard(element)) => has_side_effects(element),
+			None => false,
+			// Spreading calls the iterator
+			Some(_) => true,
+		}),
+		Expression::TemplateLiteral(TemplateLiteral { tag: None, parts, .. }) => {
+			parts.iter().any(|part| {
+				matches!(part, parser::expressions::TemplateLiteralPart::Dynamic(expression) if has_side_effects(expression))
+			})
+		}
+		Expression::ParenthesizedExpression(inner, _) => multiple_expression_has_side_effects(inner),
+		Expression::BinaryOperation { lhs, rhs, .. } => {
+			has_side_effects(lhs) || has_side_effects(rhs)
+		}
+		Expression::UnaryOperation { operator, operand, .. } => {
+			matches!(
+				operator,
+				UnaryOperator::Await
+					| UnaryOperator::Delete
+					| UnaryOperator::Yield
+					| UnaryOperator::DelegatedYield
+			) || has_side_effects(operand)
+		}
+		Expression::PropertyAccess { parent, .. } => has_side_effects(parent),
+		Expression::Index { indexee, indexer, .. } => {
+			has_side_effects(indexee) || multiple_expression_has_side_effects(indexer)
+		}
+		Expression::ConditionalTernary { condition, truthy_result, falsy_result, .. } => {
+			has_side_effects(condition)
+				|| has_side_effects(truthy_result)
+				|| has_side_effects(falsy_result)
+		}
+		Expression::Comment { on, .. } => has_side_effects(on),
+		// Calls, assignments and checks such as `satisfies`
+		_ => true,
+	}
+}
+
+pub(super) fn multiple_expression_has_side_effects(expression: &MultipleExpression) -> bool {
+	match expression {
+		MultipleExpression::Multiple { lhs, rhs, .. } => {
+			multiple_expression_has_side_effects(lhs) || has_side_effects(rhs)
+		}
+		MultipleExpression::Single(expression) => has_side_effects(expression),
+	}
+}
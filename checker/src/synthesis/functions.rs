@@ -10,15 +10,19 @@ use parser::{
 
 use crate::{
 	context::{Context, ContextType, Scope, VariableRegisterArguments},
-	features::functions::{
-		synthesise_function_default_value, FunctionBehavior, ReturnType, SynthesisableFunction,
+	features::{
+		functions::{
+			synthesise_function_default_value, FunctionBehavior, ReturnType, SynthesisableFunction,
+		},
+		semantic_tokens::SemanticTokenKind,
 	},
 	types::{
+		calling::get_tuple_elements,
 		functions::{
 			FunctionType, SynthesisedParameter, SynthesisedParameters, SynthesisedRestParameter,
 		},
-		poly_types::GenericTypeParameters,
-		Constructor, StructureGenerics, Type, TypeId,
+		poly_types::{check_type_parameter_default, GenericTypeParameters},
+		Constructor, PolyNature, StructureGenerics, Type, TypeId, TypeStore,
 	},
 	CheckingData, Environment, FunctionId,
 };
@@ -26,7 +30,7 @@ use crate::{
 use super::{
 	synthesise_block,
 	type_annotations::{comment_as_type_annotation, synthesise_type_annotation},
-	variables::register_variable,
+	variables::{record_destructured_bindings, register_variable},
 };
 
 impl<U: FunctionBased + 'static> SynthesisableFunction<super::EznoParser>
@@ -207,6 +211,17 @@ pub(crate) fn synthesise_type_parameters<T: crate::ReadFromFS>(
 				.as_ref()
 				.map(|ta| synthesise_type_annotation(ta, environment, checking_data));
 
+			if let (Some(extends), Some(default_type)) = (extends, default_type) {
+				check_type_parameter_default(
+					&constraint.name,
+					extends,
+					default_type,
+					constraint.position.with_source(environment.get_source()),
+					environment,
+					checking_data,
+				);
+			}
+
 			environment.new_explicit_type_parameter(
 				&constraint.name,
 				extends,
@@ -248,6 +263,7 @@ pub(super) fn synthesise_type_annotation_function_parameters<T: crate::ReadFromF
 			let ty = checking_data.types.new_function_parameter(parameter_constraint);
 
 			if let Some(name) = &parameter.name {
+				add_parameter_token(name.get_ast_ref(), checking_data);
 				register_variable(
 					name.get_ast_ref(),
 					environment,
@@ -280,25 +296,11 @@ pub(super) fn synthesise_type_annotation_function_parameters<T: crate::ReadFromF
 		let parameter_constraint =
 			synthesise_type_annotation(&rest_parameter.type_annotation, environment, checking_data);
 
-		let item_type = if let TypeId::ERROR_TYPE = parameter_constraint {
-			TypeId::ERROR_TYPE
-		} else if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
-			on: TypeId::ARRAY_TYPE,
-			arguments,
-		})) = checking_data.types.get_type_by_id(parameter_constraint)
-		{
-			if let Some(item) = arguments.get_structure_restriction(TypeId::T_TYPE) {
-				item
-			} else {
-				unreachable!()
-			}
-		} else {
-			crate::utils::notify!("rest parameter should be array error");
-			// checking_data.diagnostics_container.add_error(
-			// 	TypeCheckError::RestParameterAnnotationShouldBeArrayType(rest_parameter.get),
-			// );
-			TypeId::ERROR_TYPE
-		};
+		let item_type = get_rest_parameter_item_type(
+			parameter_constraint,
+			environment,
+			&mut checking_data.types,
+		);
 
 		let ty = checking_data.types.new_function_parameter(parameter_constraint);
 
@@ -327,6 +329,48 @@ pub(super) fn synthesise_type_annotation_function_parameters<T: crate::ReadFromF
 	SynthesisedParameters { parameters, rest_parameter }
 }
 
+/// The type of the items of a rest parameter from its annotation. Supports `Array<T>`, tuples and
+/// generic parameters (which are inferred at the call site)
+pub(super) fn get_rest_parameter_item_type(
+	parameter_constraint: TypeId,
+	environment: &Environment,
+	types: &mut TypeStore,
+) -> TypeId {
+	match types.get_type_by_id(parameter_constraint) {
+		_ if matches!(parameter_constraint, TypeId::ERROR_TYPE | TypeId::ANY_TYPE) => {
+			parameter_constraint
+		}
+		Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::ARRAY_TYPE,
+			arguments,
+		})) => {
+			if let Some(item) = arguments.get_structure_restriction(TypeId::T_TYPE) {
+				item
+			} else {
+				unreachable!()
+			}
+		}
+		Type::RootPolyType(PolyNature::FunctionGeneric { eager_fixed, .. }) => {
+			let eager_fixed = *eager_fixed;
+			get_rest_parameter_item_type(eager_fixed, environment, types)
+		}
+		_ => {
+			if let Some(elements) = get_tuple_elements(parameter_constraint, environment, types) {
+				elements
+					.into_iter()
+					.reduce(|lhs, rhs| types.new_or_type(lhs, rhs))
+					.unwrap_or(TypeId::NEVER_TYPE)
+			} else {
+				crate::utils::notify!(Warn; "rest parameter should be array error");
+				// checking_data.diagnostics_container.add_error(
+				// 	TypeCheckError::RestParameterAnnotationShouldBeArrayType(rest_parameter.get),
+				// );
+				TypeId::ERROR_TYPE
+			}
+		}
+	}
+}
+
 fn synthesise_function_parameters<
 	T: crate::ReadFromFS,
 	L: parser::functions::LeadingParameter,
@@ -383,7 +427,10 @@ fn synthesise_function_parameters<
 			// TODO parameter_constraint is stateless to reduce redundancy
 			if !matches!(
 				parameter_constraint,
-				TypeId::NUMBER_TYPE | TypeId::STRING_TYPE | TypeId::BOOLEAN_TYPE
+				TypeId::NUMBER_TYPE
+					| TypeId::STRING_TYPE
+					| TypeId::BOOLEAN_TYPE
+					| TypeId::BIGINT_TYPE
 			) {
 				environment.info.object_constraints.insert(ty, parameter_constraint);
 			}
@@ -403,6 +450,7 @@ fn synthesise_function_parameters<
 				None => (false, ty),
 			};
 
+			add_parameter_token(parameter.name.get_ast_ref(), checking_data);
 			register_variable(
 				parameter.name.get_ast_ref(),
 				environment,
@@ -414,6 +462,7 @@ fn synthesise_function_parameters<
 					initial_value: Some(variable_ty),
 				},
 			);
+			record_destructured_bindings(parameter.name.get_ast_ref(), environment, checking_data);
 
 			let name = variable_field_to_string(parameter.name.get_ast_ref());
 
@@ -434,30 +483,17 @@ fn synthesise_function_parameters<
 				synthesise_type_annotation(annotation, environment, checking_data)
 			});
 
-		let item_type = if let TypeId::ERROR_TYPE = parameter_constraint {
-			TypeId::ERROR_TYPE
-		} else if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
-			on: TypeId::ARRAY_TYPE,
-			arguments,
-		})) = checking_data.types.get_type_by_id(parameter_constraint)
-		{
-			if let Some(item) = arguments.get_structure_restriction(TypeId::T_TYPE) {
-				item
-			} else {
-				unreachable!()
-			}
-		} else {
-			crate::utils::notify!("rest parameter should be array error");
-			// checking_data.diagnostics_container.add_error(
-			// 	TypeCheckError::RestParameterAnnotationShouldBeArrayType(rest_parameter.get),
-			// );
-			TypeId::ERROR_TYPE
-		};
+		let item_type = get_rest_parameter_item_type(
+			parameter_constraint,
+			environment,
+			&mut checking_data.types,
+		);
 
 		let variable_ty = checking_data.types.new_function_parameter(parameter_constraint);
 
 		environment.info.object_constraints.insert(variable_ty, parameter_constraint);
 
+		add_parameter_token(&rest_parameter.name, checking_data);
 		register_variable(
 			&rest_parameter.name,
 			environment,
@@ -483,6 +519,16 @@ fn synthesise_function_parameters<
 	SynthesisedParameters { parameters, rest_parameter }
 }
 
+/// For [`crate::features::semantic_tokens`]. Destructured parameters are not recorded
+fn add_parameter_token<T: crate::ReadFromFS>(
+	name: &VariableField,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	if let VariableField::Name(VariableIdentifier::Standard(_, position)) = name {
+		checking_data.add_semantic_token(*position, SemanticTokenKind::Parameter);
+	}
+}
+
 /// For parameter printing
 pub(super) fn variable_field_to_string(param: &VariableField) -> String {
 	match param {
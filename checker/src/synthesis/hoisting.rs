@@ -1,27 +1,72 @@
-use std::iter;
+use std::{
+	collections::{HashMap, HashSet},
+	iter, mem,
+};
 
 use parser::{
-	declarations::{export::Exportable, DeclareVariableDeclaration, ExportDeclaration},
+	declarations::{
+		export::Exportable, DeclareModuleDeclaration, DeclareVariableDeclaration,
+		ExportDeclaration, StatementFunction, VariableDeclaration,
+	},
+	types::{
+		enum_declaration::{EnumDeclaration, EnumMember},
+		namespace::Namespace,
+	},
 	ASTNode, Declaration, Decorated, ExpressionOrStatementPosition, Statement,
-	StatementOrDeclaration, VariableIdentifier,
+	StatementOrDeclaration, VariableField, VariableIdentifier, VariableKeyword,
 };
+use source_map::{Span, SpanWithSource};
 
 use crate::{
-	context::{Environment, VariableRegisterArguments},
+	context::{
+		get_value_of_variable, information::Publicity, Environment, VariableRegisterArguments,
+	},
+	diagnostics::TypeCheckError,
 	features::{
+		deprecation::get_deprecation_message,
 		functions::{synthesise_declare_statement_function, synthesise_hoisted_statement_function},
-		modules::{import_items, ImportKind, NamePair},
-		variables::VariableMutability,
+		modules::{get_synthesised_module_exports, import_items, Exported, ImportKind, NamePair},
+		objects::ObjectBuilder,
+		semantic_tokens::SemanticTokenKind,
+		variables::{VariableMutability, VariableOrImport},
 	},
 	synthesis::{
-		classes::register_statement_class_with_members,
+		block::synthesise_block,
+		classes::{register_statement_class_with_members, synthesise_class_declaration},
+		definitions::get_internal_function_effect_from_decorators,
 		type_annotations::get_annotation_from_declaration,
 	},
-	CheckingData, ReadFromFS, TypeId,
+	types::properties::{PropertyKey, PropertyValue},
+	CheckingData, ReadFromFS, TypeId, VariableId,
 };
 
 use super::{variables::register_variable, EznoParser};
 
+/// Name and function of (exported or not) non-`declare` function declarations
+fn get_named_statement_function(
+	item: &StatementOrDeclaration,
+) -> Option<(&str, &StatementFunction)> {
+	let function = match item {
+		StatementOrDeclaration::Declaration(Declaration::Function(function)) => &function.on,
+		StatementOrDeclaration::Declaration(Declaration::Export(exported)) => match &exported.on {
+			ExportDeclaration::Variable { exported: Exportable::Function(function), .. } => {
+				function
+			}
+			_ => return None,
+		},
+		StatementOrDeclaration::Statement(_)
+		| StatementOrDeclaration::Declaration(_)
+		| StatementOrDeclaration::Marker(..) => return None,
+	};
+	if function.name.declare {
+		return None;
+	}
+	match function.name.as_option_variable_identifier() {
+		Some(VariableIdentifier::Standard(name, ..)) => Some((name, function)),
+		_ => None,
+	}
+}
+
 pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 	items: &[StatementOrDeclaration],
 	environment: &mut Environment,
@@ -32,16 +77,26 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 		if let StatementOrDeclaration::Declaration(declaration) = item {
 			match declaration {
 				parser::Declaration::DeclareVariable(_)
+				| parser::Declaration::DeclareGlobal(_)
+				| parser::Declaration::DeclareModule(_)
+				| parser::Declaration::Namespace(_)
 				| parser::Declaration::Variable(_)
 				| parser::Declaration::Function(_) => {}
-				parser::Declaration::Enum(r#enum) => checking_data.raise_unimplemented_error(
-					"enum",
-					r#enum.on.position.with_source(environment.get_source()),
-				),
-				parser::Declaration::Namespace(ns) => checking_data.raise_unimplemented_error(
-					"namespace",
-					ns.position.with_source(environment.get_source()),
-				),
+				parser::Declaration::Enum(r#enum) => {
+					synthesise_enum_types(&r#enum.on, environment, checking_data);
+					for parser::types::enum_declaration::EnumMember::Variant {
+						name,
+						position,
+						..
+					} in &r#enum.on.members
+					{
+						let name_end = position.start + name.len() as u32;
+						checking_data.add_semantic_token(
+							Span { end: name_end, ..*position },
+							SemanticTokenKind::EnumMember,
+						);
+					}
+				}
 				parser::Declaration::Interface(interface) => {
 					let ty = environment.register_interface(
 						interface.on.name.as_option_str().unwrap_or_default(),
@@ -169,6 +224,8 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 									.types_to_types
 									.push(interface.get_position(), ty);
 
+								let position =
+									interface.position.with_source(environment.get_source());
 								if let crate::Scope::Module { ref mut exported, .. } =
 									environment.context_type.scope
 								{
@@ -177,6 +234,7 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 										.as_option_str()
 										.unwrap_or_default()
 										.to_owned();
+									exported.positions.push((name.clone(), position));
 									exported.named_types.push((name, ty));
 								}
 							}
@@ -192,13 +250,15 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 									.types_to_types
 									.push(class.get_position(), ty);
 
+								let position =
+									class.get_position().with_source(environment.get_source());
 								if let crate::Scope::Module { ref mut exported, .. } =
 									environment.context_type.scope
 								{
-									exported.named_types.push((
-										class.name.as_option_str().unwrap_or_default().to_owned(),
-										ty,
-									));
+									let name =
+										class.name.as_option_str().unwrap_or_default().to_owned();
+									exported.positions.push((name.clone(), position));
+									exported.named_types.push((name, ty));
 								}
 							}
 							Exportable::TypeAlias(alias) => {
@@ -210,13 +270,15 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 									checking_data,
 								);
 
+								let position =
+									alias.get_position().with_source(environment.get_source());
 								if let crate::Scope::Module { ref mut exported, .. } =
 									environment.context_type.scope
 								{
-									exported.named_types.push((
-										alias.name.as_option_str().unwrap_or_default().to_owned(),
-										ty,
-									));
+									let name =
+										alias.name.as_option_str().unwrap_or_default().to_owned();
+									exported.positions.push((name.clone(), position));
+									exported.named_types.push((name, ty));
 								}
 							}
 							// Other exported things are skipped
@@ -228,13 +290,81 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 		}
 	}
 
-	// Second stage: variables and function type hoisting
+	// Namespaces, after the types they may reference are registered
 	for item in items {
+		match item {
+			StatementOrDeclaration::Declaration(parser::Declaration::Namespace(namespace)) => {
+				synthesise_namespace(namespace, environment, checking_data);
+			}
+			StatementOrDeclaration::Declaration(parser::Declaration::Export(Decorated {
+				on: ExportDeclaration::Variable { exported: Exportable::Namespace(namespace), .. },
+				..
+			})) => {
+				let (id, types) = synthesise_namespace(namespace, environment, checking_data);
+				let position = namespace.position.with_source(environment.get_source());
+				export_namespace(
+					namespace_variable_name(namespace),
+					id,
+					types,
+					position,
+					environment,
+				);
+			}
+			_ => {}
+		}
+	}
+
+	register_type_deprecations(items, environment, checking_data);
+
+	// Second stage: variables and function type hoisting
+	let mut overload: Option<(&str, SpanWithSource)> = None;
+	// Names declared by `var` (`false`) or function declarations (`true`), which `var` can
+	// redeclare. A function declaration replaces a `var`, as functions are hoisted first
+	let mut var_or_function_names = HashMap::<&str, bool>::new();
+	for (idx, item) in items.iter().enumerate() {
+		if let Some((name, function)) = get_named_statement_function(item) {
+			let position = function.get_position().with_source(environment.get_source());
+			if let Some((expected, overload_position)) = overload.take() {
+				if expected != name {
+					checking_data.diagnostics_container.add_error(
+						TypeCheckError::FunctionImplementationNameMismatch {
+							expected: expected.to_owned(),
+							position,
+							overload_position,
+						},
+					);
+				}
+			}
+			if function.body.0.is_none() {
+				overload = Some((name, position));
+				// The variable is registered by the implementation (or last overload)
+				let next = items.get(idx + 1).and_then(get_named_statement_function);
+				if next.is_some_and(|(next_name, _)| next_name == name) {
+					continue;
+				}
+			}
+		} else if !matches!(
+			item,
+			StatementOrDeclaration::Statement(
+				Statement::Empty(..) | Statement::Comment(..) | Statement::MultiLineComment(..)
+			)
+		) {
+			overload = None;
+		}
+
 		match item {
 			StatementOrDeclaration::Statement(stmt) => {
 				if let Statement::VarVariable(stmt) = stmt {
 					for declaration in &stmt.declarations {
 						crate::utils::notify!("declaration.name {:?}", declaration.name);
+						if let VariableField::Name(VariableIdentifier::Standard(name, _)) =
+							declaration.name.get_ast_ref()
+						{
+							if var_or_function_names.contains_key(name.as_str()) {
+								continue;
+							}
+							var_or_function_names.insert(name, false);
+						}
 						let constraint = get_annotation_from_declaration(
 							declaration,
 							environment,
@@ -255,17 +385,46 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 				}
 			}
 			StatementOrDeclaration::Declaration(dec) => match dec {
-				parser::Declaration::Namespace(ns) => checking_data.raise_unimplemented_error(
-					"namespace",
-					ns.position.with_source(environment.get_source()),
-				),
 				parser::Declaration::Variable(declaration) => {
 					hoist_variable_declaration(declaration, environment, checking_data);
 				}
+				parser::Declaration::DeclareModule(augmentation) => {
+					synthesise_module_augmentation(augmentation, environment, checking_data);
+				}
+				parser::Declaration::DeclareGlobal(global) => {
+					// Done after the first stage so that augmentations can reference types in this module.
+					// Interfaces merge with the existing root ones (see `register_interface`). Items are
+					// ambient, as in definition files
+					let existing_variables: HashSet<String> =
+						environment.variables.keys().cloned().collect();
+					let existing_types: HashSet<String> =
+						environment.named_types.keys().cloned().collect();
+					super::definitions::synthesise_definition_items(
+						&global.inner.0,
+						environment,
+						checking_data,
+					);
+
+					// New names are visible to the modules synthesised after this one
+					let globals = &mut checking_data.modules.global_declarations;
+					for (name, variable) in &environment.variables {
+						if !existing_variables.contains(name) {
+							globals.variables.insert(name.clone(), variable.clone());
+						}
+					}
+					for (name, ty) in &environment.named_types {
+						if !existing_types.contains(name) {
+							globals.named_types.insert(name.clone(), *ty);
+						}
+					}
+				}
 				parser::Declaration::Function(func) => {
 					if let Some(VariableIdentifier::Standard(name, ..)) =
 						func.on.name.as_option_variable_identifier()
 					{
+						if var_or_function_names.insert(name, true) == Some(false) {
+							environment.variables.remove(name);
+						}
 						environment.register_variable_handle_error(
 							name,
 							VariableRegisterArguments {
@@ -280,10 +439,7 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 					}
 				}
 				parser::Declaration::Enum(r#enum) => {
-					checking_data.raise_unimplemented_error(
-						"enum",
-						r#enum.position.with_source(environment.get_source()),
-					);
+					register_enum_object(&r#enum.on, environment, checking_data);
 				}
 				parser::Declaration::Interface(interface) => {
 					let ty = *checking_data
@@ -302,11 +458,12 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 					);
 				}
 				parser::Declaration::DeclareVariable(DeclareVariableDeclaration {
-					keyword: _,
+					keyword,
 					declarations,
 					position: _,
 					decorators: _,
 				}) => {
+					let constant = matches!(keyword, VariableKeyword::Const);
 					for declaration in declarations {
 						let constraint = get_annotation_from_declaration(
 							declaration,
@@ -315,7 +472,7 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 						);
 
 						if constraint.is_none() {
-							crate::utils::notify!("constraint with no type?");
+							crate::utils::notify!(Warn; "constraint with no type?");
 						}
 
 						let value = constraint.unwrap_or(TypeId::ANY_TYPE);
@@ -324,14 +481,14 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 							crate::types::PolyNature::Open(value),
 						));
 
+						// Ambient, so has a value from the start (no TDZ)
 						register_variable(
 							declaration.name.get_ast_ref(),
 							environment,
 							checking_data,
 							VariableRegisterArguments {
-								// TODO based on keyword
-								constant: true,
-								space: None,
+								constant,
+								space: (!constant).then_some(value),
 								initial_value: Some(ty),
 							},
 						);
@@ -388,7 +545,9 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 									checking_data,
 								);
 							}
-							Exportable::TypeAlias(_) | Exportable::ImportAll { .. } => {}
+							Exportable::TypeAlias(_)
+							| Exportable::ImportAll { .. }
+							| Exportable::Namespace(_) => {}
 							// TODO
 							Exportable::Parts(..) | Exportable::ImportParts { .. } => {
 								crate::utils::notify!("TODO");
@@ -403,12 +562,16 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 				parser::Declaration::Class(class) => {
 					register_statement_class_with_members(&class.on, environment, checking_data);
 				}
-				parser::Declaration::TypeAlias(_) | parser::Declaration::Import(_) => {}
+				parser::Declaration::TypeAlias(_)
+				| parser::Declaration::Import(_)
+				| parser::Declaration::Namespace(_) => {}
 			},
 			StatementOrDeclaration::Marker(_, _) => {}
 		}
 	}
 
+	register_variable_deprecations(items, environment, checking_data);
+
 	// Third stage: functions
 	for item in items {
 		match item {
@@ -423,12 +586,17 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 				});
 
 				if function.on.name.declare {
+					// For `@pure` and `@mutates(...)` in `.d.ts` files
+					let internal_marker = get_internal_function_effect_from_decorators(
+						&function.decorators,
+						function.on.name.as_option_str().unwrap_or_default(),
+					);
 					synthesise_declare_statement_function(
 						variable_id,
 						is_async,
 						is_generator,
 						location,
-						None,
+						internal_marker,
 						&function.on,
 						environment,
 						checking_data,
@@ -445,6 +613,13 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 					);
 				}
 			}
+			StatementOrDeclaration::Declaration(Declaration::Class(Decorated {
+				on: class,
+				..
+			})) if class.name.declare => {
+				// Ambient classes have no initialisation, so are available from the start of the scope
+				synthesise_class_declaration(class, environment, checking_data);
+			}
 			StatementOrDeclaration::Declaration(Declaration::Export(Decorated {
 				on:
 					ExportDeclaration::Variable {
@@ -473,6 +648,7 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 					checking_data,
 				);
 
+				let position = function.get_position().with_source(environment.get_source());
 				if let crate::Scope::Module { ref mut exported, .. } =
 					environment.context_type.scope
 				{
@@ -480,6 +656,7 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 					if let Some(VariableIdentifier::Standard(name, ..)) =
 						function.name.as_option_variable_identifier()
 					{
+						exported.positions.push((name.clone(), position));
 						exported
 							.named
 							.push((name.clone(), (variable_id, VariableMutability::Constant)));
@@ -491,6 +668,448 @@ pub(crate) fn hoist_statements<T: crate::ReadFromFS>(
 	}
 }
 
+/// Records declarations after a `JSDoc` comment with a `@deprecated` tag. Requires that the
+/// declarations have been registered
+/// Registers `@deprecated` types, before annotations that reference them are synthesised
+pub(crate) fn register_type_deprecations<T: crate::ReadFromFS>(
+	items: &[StatementOrDeclaration],
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	for (declaration, message) in deprecated_declarations(items) {
+		let mut types = Vec::new();
+		declared_names(declaration, &mut Vec::new(), &mut types);
+		for name in types {
+			if let Some(ty) = environment.named_types.get(name) {
+				checking_data.deprecations.types.insert(*ty, message.clone());
+			}
+		}
+	}
+}
+
+/// Registers `@deprecated` variables, before function bodies are synthesised
+pub(crate) fn register_variable_deprecations<T: crate::ReadFromFS>(
+	items: &[StatementOrDeclaration],
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	for (declaration, message) in deprecated_declarations(items) {
+		let mut variables = Vec::new();
+		declared_names(declaration, &mut variables, &mut Vec::new());
+		for name in variables {
+			if let Some(variable) = environment.variables.get(name) {
+				checking_data
+					.deprecations
+					.variables
+					.insert(variable.get_origin_variable_id(), message.clone());
+			}
+		}
+	}
+}
+
+/// Declarations directly after a `JSDoc` comment with a `@deprecated` tag, with its message
+fn deprecated_declarations(
+	items: &[StatementOrDeclaration],
+) -> impl Iterator<Item = (&Declaration, String)> {
+	items.windows(2).filter_map(|pair| {
+		let [previous, StatementOrDeclaration::Declaration(declaration)] = pair else {
+			return None;
+		};
+		let StatementOrDeclaration::Statement(Statement::MultiLineComment(comment, _)) = previous
+		else {
+			return None;
+		};
+		get_deprecation_message(comment).map(|message| (declaration, message))
+	})
+}
+
+/// Names of the variables and types that `declaration` introduces
+fn declared_names<'a>(
+	declaration: &'a Declaration,
+	variables: &mut Vec<&'a str>,
+	types: &mut Vec<&'a str>,
+) {
+	fn field_name(field: &VariableField) -> Option<&str> {
+		if let VariableField::Name(identifier) = field {
+			identifier.as_option_str()
+		} else {
+			None
+		}
+	}
+
+	fn variable_declaration_names<'a>(
+		declaration: &'a VariableDeclaration,
+		variables: &mut Vec<&'a str>,
+	) {
+		match declaration {
+			VariableDeclaration::ConstDeclaration { declarations, .. } => variables
+				.extend(declarations.iter().filter_map(|item| field_name(item.name.get_ast_ref()))),
+			VariableDeclaration::LetDeclaration { declarations, .. } => variables
+				.extend(declarations.iter().filter_map(|item| field_name(item.name.get_ast_ref()))),
+		}
+	}
+
+	match declaration {
+		Declaration::Variable(declaration) => variable_declaration_names(declaration, variables),
+		Declaration::DeclareVariable(declaration) => variables.extend(
+			declaration.declarations.iter().filter_map(|item| field_name(item.name.get_ast_ref())),
+		),
+		Declaration::Function(function) => variables.extend(function.on.name.as_option_str()),
+		Declaration::Class(class) => {
+			variables.extend(class.on.name.as_option_str());
+			types.extend(class.on.name.as_option_str());
+		}
+		Declaration::Interface(interface) => types.extend(interface.on.name.as_option_str()),
+		Declaration::TypeAlias(alias) => types.extend(alias.name.as_option_str()),
+		Declaration::Namespace(namespace) => variables.push(namespace_variable_name(namespace)),
+		Declaration::Export(Decorated {
+			on: ExportDeclaration::Variable { exported, .. }, ..
+		}) => match exported {
+			Exportable::Variable(declaration) => {
+				variable_declaration_names(declaration, variables);
+			}
+			Exportable::Function(function) => variables.extend(function.name.as_option_str()),
+			Exportable::Class(class) => {
+				variables.extend(class.name.as_option_str());
+				types.extend(class.name.as_option_str());
+			}
+			Exportable::Interface(interface) => types.extend(interface.name.as_option_str()),
+			Exportable::TypeAlias(alias) => types.extend(alias.name.as_option_str()),
+			Exportable::Namespace(namespace) => variables.push(namespace_variable_name(namespace)),
+			Exportable::Parts(_)
+			| Exportable::ImportAll { .. }
+			| Exportable::ImportParts { .. } => {}
+		},
+		_ => {}
+	}
+}
+
+/// The first part of a dotted (`A.B`) namespace name
+fn namespace_variable_name(namespace: &Namespace) -> &str {
+	namespace.name.split('.').next().unwrap_or_default()
+}
+
+/// Adds a namespace (and its types) to the exports of the module `environment` is in
+fn export_namespace(
+	name: &str,
+	id: VariableId,
+	types: Vec<(String, TypeId)>,
+	position: SpanWithSource,
+	environment: &mut Environment,
+) {
+	if let crate::Scope::Module { ref mut exported, .. } = environment.context_type.scope {
+		if !exported.named.iter().any(|(existing, _)| existing == name) {
+			exported.positions.push((name.to_owned(), position));
+			exported.named.push((name.to_owned(), (id, VariableMutability::Constant)));
+		}
+		exported.named_types.extend(types);
+	}
+}
+
+/// Synthesises the body of `namespace` and adds its exports as properties of the object under its
+/// name. Namespaces with the same name merge into the first object. Exported types are registered
+/// as `Namespace.Type`. Everything in a `declare namespace` is exported
+pub(super) fn synthesise_namespace<T: crate::ReadFromFS>(
+	namespace: &Namespace,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) -> (VariableId, Vec<(String, TypeId)>) {
+	synthesise_namespace_named(&namespace.name, namespace, environment, checking_data)
+}
+
+/// `namespace A.B {}` is synthesised as `namespace A { export namespace B {} }`
+fn synthesise_namespace_named<T: crate::ReadFromFS>(
+	name: &str,
+	namespace: &Namespace,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) -> (VariableId, Vec<(String, TypeId)>) {
+	// Offset by the position in the name, so that each part has a different `VariableId`
+	let offset = (namespace.name.len() - name.len()) as u32;
+	let (name, inner_name) = match name.split_once('.') {
+		Some((name, inner_name)) => (name, Some(inner_name)),
+		None => (name, None),
+	};
+	let source = environment.get_source();
+	let position =
+		Span { start: namespace.position.start + offset, ..namespace.position }.with_source(source);
+	let scope = crate::Scope::Module { source, exported: Exported::default() };
+
+	let ((variables, types), ..) = environment.new_lexical_environment_fold_into_parent(
+		scope,
+		checking_data,
+		|environment, checking_data| {
+			if let Some(inner_name) = inner_name {
+				let (id, types) =
+					synthesise_namespace_named(inner_name, namespace, environment, checking_data);
+				let variable_name = inner_name.split('.').next().unwrap_or_default();
+				export_namespace(variable_name, id, types, position, environment);
+			} else {
+				synthesise_block(&namespace.inner.0, environment, checking_data);
+			}
+
+			let crate::Scope::Module { ref mut exported, .. } = environment.context_type.scope
+			else {
+				unreachable!()
+			};
+			let Exported { named, mut named_types, .. } = mem::take(exported);
+			let mut variables = if namespace.is_declare {
+				environment
+					.variables
+					.iter()
+					.map(|(name, variable)| (name.clone(), variable.get_id()))
+					.collect()
+			} else {
+				named.into_iter().map(|(name, (id, _))| (name, id)).collect::<Vec<_>>()
+			};
+			variables.sort_unstable_by_key(|(_, id)| id.1);
+			if namespace.is_declare {
+				named_types =
+					environment.named_types.iter().map(|(name, ty)| (name.clone(), *ty)).collect();
+				named_types.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+			}
+
+			let variables = variables
+				.into_iter()
+				.map(|(name, id)| {
+					let value = get_value_of_variable(
+						environment,
+						id,
+						None::<&crate::types::poly_types::FunctionTypeArguments>,
+					);
+					(name, value.unwrap_or(TypeId::ERROR_TYPE))
+				})
+				.collect::<Vec<_>>();
+			(variables, named_types)
+		},
+	);
+
+	let existing = environment.variables.get(name).map(VariableOrImport::get_id);
+	let existing_object = existing.and_then(|id| {
+		get_value_of_variable(
+			environment,
+			id,
+			None::<&crate::types::poly_types::FunctionTypeArguments>,
+		)
+		.filter(|value| {
+			matches!(checking_data.types.get_type_by_id(*value), crate::Type::Object(_))
+		})
+		.map(|value| (id, value))
+	});
+
+	let (id, object) = if let Some(existing) = existing_object {
+		existing
+	} else {
+		let object = ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info)
+			.build_object();
+		environment.register_variable_handle_error(
+			name,
+			VariableRegisterArguments { constant: true, space: None, initial_value: Some(object) },
+			position,
+			&mut checking_data.diagnostics_container,
+		);
+		(VariableId(source, position.start), object)
+	};
+
+	for (name, value) in variables {
+		environment.info.register_property(
+			object,
+			Publicity::Public,
+			PropertyKey::String(name.into()),
+			PropertyValue::Value(value),
+			false,
+			Some(position),
+		);
+	}
+
+	let types = types
+		.into_iter()
+		.map(|(type_name, ty)| {
+			let type_name = format!("{name}.{type_name}");
+			environment.named_types.insert(type_name.clone(), ty);
+			(type_name, ty)
+		})
+		.collect();
+
+	(id, types)
+}
+
+/// Registers the type of `r#enum`, which is the union of the values of its members, and each
+/// `Enum.Member` as the type of its value. Initialisers can reference earlier members. Members
+/// without one follow on from the previous number (starting at `0`)
+fn synthesise_enum_types<T: crate::ReadFromFS>(
+	r#enum: &EnumDeclaration,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	let source = environment.get_source();
+	let mut members = Vec::new();
+	{
+		let mut member_environment = environment.new_lexical_environment(crate::Scope::Block {});
+		let mut next = Some(0f64);
+		for EnumMember::Variant { name, value, position } in &r#enum.members {
+			let value = if let Some(value) = value {
+				super::expressions::synthesise_expression(
+					value,
+					&mut member_environment,
+					checking_data,
+					TypeId::ANY_TYPE,
+				)
+			} else if let Some(number) = next.and_then(|next| next.try_into().ok()) {
+				checking_data.types.new_constant_type(crate::Constant::Number(number))
+			} else {
+				TypeId::NUMBER_TYPE
+			};
+			next = match checking_data.types.get_type_by_id(value) {
+				crate::Type::Constant(crate::Constant::Number(number)) => {
+					Some(number.into_inner() + 1f64)
+				}
+				_ => None,
+			};
+			member_environment.register_variable_handle_error(
+				name,
+				VariableRegisterArguments {
+					constant: true,
+					space: None,
+					initial_value: Some(value),
+				},
+				position.with_source(source),
+				&mut checking_data.diagnostics_container,
+			);
+			members.push((name, value));
+		}
+	}
+
+	for (name, value) in &members {
+		environment.named_types.insert(format!("{}.{name}", r#enum.name), *value);
+	}
+
+	let union = members
+		.into_iter()
+		.map(|(_, value)| value)
+		.reduce(|acc, value| checking_data.types.new_or_type(acc, value))
+		.unwrap_or(TypeId::NEVER_TYPE);
+	let alias = crate::Type::AliasTo { to: union, name: r#enum.name.clone(), parameters: None };
+	let alias = checking_data.types.register_type(alias);
+	if environment.named_types.insert(r#enum.name.clone(), alias).is_some() {
+		checking_data.diagnostics_container.add_error(TypeCheckError::TypeAlreadyDeclared {
+			name: r#enum.name.clone(),
+			position: r#enum.position.with_source(source),
+		});
+	}
+}
+
+/// The (frozen) object under the name of `r#enum`, with the members registered by
+/// [`synthesise_enum_types`]. Numeric members of non-`const` enums also have a reverse mapping
+/// from the value to the name
+fn register_enum_object<T: crate::ReadFromFS>(
+	r#enum: &EnumDeclaration,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	let position = r#enum.position.with_source(environment.get_source());
+	let mut object = ObjectBuilder::new(None, &mut checking_data.types, &mut environment.info);
+	for EnumMember::Variant { name, position: member_position, .. } in &r#enum.members {
+		let Some(value) = environment.get_type_from_name(&format!("{}.{name}", r#enum.name)) else {
+			continue;
+		};
+		let member_position = Some(member_position.with_source(environment.get_source()));
+		object.append(
+			environment,
+			Publicity::Public,
+			PropertyKey::String(name.clone().into()),
+			PropertyValue::Value(value),
+			member_position,
+		);
+		let is_index = matches!(
+			checking_data.types.get_type_by_id(value),
+			crate::Type::Constant(crate::Constant::Number(number))
+				if number.fract() == 0f64 && number.into_inner() >= 0f64
+		);
+		if !r#enum.is_constant && is_index {
+			let name = checking_data.types.new_constant_type(crate::Constant::String(name.clone()));
+			object.append(
+				environment,
+				Publicity::Public,
+				PropertyKey::from_type(value, &checking_data.types),
+				PropertyValue::Value(name),
+				member_position,
+			);
+		}
+	}
+	let object = object.build_object();
+	crate::features::objects::freeze(object, false, environment, &checking_data.types);
+
+	environment.register_variable_handle_error(
+		&r#enum.name,
+		VariableRegisterArguments { constant: true, space: None, initial_value: Some(object) },
+		position,
+		&mut checking_data.diagnostics_container,
+	);
+}
+
+/// Adds members to interfaces exported by an already imported module. Items are applied in source order
+fn synthesise_module_augmentation<T: crate::ReadFromFS>(
+	augmentation: &DeclareModuleDeclaration,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	let Some(exported) =
+		get_synthesised_module_exports(&augmentation.name, environment, checking_data)
+	else {
+		checking_data.diagnostics_container.add_error(
+			TypeCheckError::CannotAugmentNonImportedModule {
+				module: &augmentation.name,
+				position: augmentation.position.with_source(environment.get_source()),
+			},
+		);
+		return;
+	};
+
+	for item in &augmentation.inner.0 {
+		let interface = match item {
+			StatementOrDeclaration::Declaration(
+				Declaration::Interface(Decorated { on: interface, .. })
+				| Declaration::Export(Decorated {
+					on:
+						ExportDeclaration::Variable {
+							exported: Exportable::Interface(interface), ..
+						},
+					..
+				}),
+			) => interface,
+			StatementOrDeclaration::Statement(Statement::Comment(..) | Statement::Empty(..)) => {
+				continue;
+			}
+			item => {
+				checking_data.raise_unimplemented_error(
+					"non interface item in module augmentation",
+					item.get_position().with_source(environment.get_source()),
+				);
+				continue;
+			}
+		};
+
+		let name = interface.name.as_option_str().unwrap_or_default();
+		if let (_, Some(ty)) = exported.get_export(name, true) {
+			super::interfaces::synthesise_signatures(
+				interface.type_parameters.as_deref(),
+				interface.extends.as_deref(),
+				&interface.members,
+				super::interfaces::OnToType(ty),
+				environment,
+				checking_data,
+			);
+		} else {
+			checking_data.diagnostics_container.add_error(TypeCheckError::FieldNotExported {
+				file: &augmentation.name,
+				importing: name,
+				position: interface.position.with_source(environment.get_source()),
+			});
+		}
+	}
+}
+
 fn import_part_to_name_pair(item: &parser::declarations::ImportPart) -> Option<NamePair<'_>> {
 	match item {
 		parser::declarations::ImportPart::Name(name) => {
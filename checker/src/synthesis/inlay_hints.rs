@@ -0,0 +1,219 @@
+//! Inlay hints of a checked module: the names of parameters at call sites and the inferred types of
+//! variables and of the return values of functions. Call sites and variables need
+//! [`crate::TypeCheckOptions::store_expression_type_mappings`]
+
+use parser::{
+	declarations::{export::Exportable, ExportDeclaration, VariableDeclaration},
+	expressions::FunctionArgument,
+	visiting::{BlockItem, Chain, VisitOptions, VisitorReceiver},
+	ASTNode, Declaration, Expression, FunctionBase, FunctionBased, StatementOrDeclaration,
+	VariableField, VariableIdentifier,
+};
+use source_map::{SourceId, SpanWithSource};
+
+use crate::{
+	features::objects::SpecialObjects, types::printing::TypeToStringOptions, CheckOutput,
+	FunctionId, Type, TypeId,
+};
+
+use super::EznoParser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+pub enum InlayHintKind {
+	/// `: T` after a variable name or the parameters of a function
+	Type,
+	/// `name:` before an argument
+	Parameter,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+pub struct InlayHint {
+	/// Where the label is displayed. The span is empty
+	pub position: SpanWithSource,
+	pub label: String,
+	pub kind: InlayHintKind,
+}
+
+impl CheckOutput<EznoParser> {
+	/// The hints of the module at `path`, in order. `None` if the module was not checked
+	#[must_use]
+	pub fn get_inlay_hints(&self, path: &str) -> Option<Vec<InlayHint>> {
+		let source = self.module_contents.get_source_at_path(path.as_ref())?;
+		let module = self.modules.get(&source)?;
+		let mut collector = InlayHintCollector { output: self, source, hints: Vec::new() };
+		module.content.visit(&mut collector, &mut (), &VisitOptions::default(), source);
+		let mut hints = collector.hints;
+		hints.sort_by_key(|hint| hint.position.start);
+		Some(hints)
+	}
+}
+
+struct InlayHintCollector<'a> {
+	output: &'a CheckOutput<EznoParser>,
+	source: SourceId,
+	hints: Vec<InlayHint>,
+}
+
+impl InlayHintCollector<'_> {
+	fn add(&mut self, at: u32, label: String, kind: InlayHintKind) {
+		let position = SpanWithSource { start: at, end: at, source: self.source };
+		self.hints.push(InlayHint { position, label, kind });
+	}
+
+	fn print_type(&self, ty: TypeId) -> String {
+		self.output.types.type_to_ts_string(
+			ty,
+			&self.output.top_level_information,
+			TypeToStringOptions::default(),
+		)
+	}
+
+	fn get_type_of(&self, expression: &Expression) -> Option<TypeId> {
+		let position = expression.get_position();
+		let module = self.output.modules.get(&self.source)?;
+		let instance = module.mappings.expressions_to_instances.get_exact(position)?;
+		Some(instance.get_value_on_ref())
+	}
+
+	fn add_arguments(&mut self, function: &Expression, arguments: &[FunctionArgument]) {
+		let Some(ty) = self.get_type_of(function) else {
+			return;
+		};
+		let id = match self.output.types.get_type_by_id(ty) {
+			Type::SpecialObject(SpecialObjects::Function(id, _)) | Type::FunctionReference(id) => {
+				*id
+			}
+			_ => return,
+		};
+		let Some(function) = self.output.types.functions.get(&id) else {
+			return;
+		};
+
+		let parameters = &function.parameters;
+		let arguments = arguments
+			.iter()
+			.filter(|argument| !matches!(argument, FunctionArgument::Comment { .. }));
+		for (idx, argument) in arguments.enumerate() {
+			let FunctionArgument::Standard(argument) = argument else {
+				// Spread arguments do not line up with parameters
+				return;
+			};
+			let name = if let Some(parameter) = parameters.parameters.get(idx) {
+				parameter.name.clone()
+			} else if let Some(rest) = &parameters.rest_parameter {
+				// Only before the first argument of the rest parameter
+				if idx > parameters.parameters.len() {
+					return;
+				}
+				format!("...{}", rest.name)
+			} else {
+				return;
+			};
+
+			// Not for destructured parameters or for arguments that are a variable with the name
+			let is_identifier =
+				name.trim_start_matches("...").chars().all(|c| c.is_alphanumeric() || c == '_');
+			let same_name = matches!(
+				argument,
+				Expression::VariableReference(reference, _) if *reference == name
+			);
+			if is_identifier && !same_name {
+				self.add(
+					argument.get_position().start,
+					format!("{name}:"),
+					InlayHintKind::Parameter,
+				);
+			}
+		}
+	}
+
+	fn add_variable_types(&mut self, declaration: &VariableDeclaration) {
+		let items = match declaration {
+			VariableDeclaration::ConstDeclaration { declarations, .. } => declarations
+				.iter()
+				.map(|item| {
+					(item.name.get_ast_ref(), &item.type_annotation, Some(&item.expression))
+				})
+				.collect::<Vec<_>>(),
+			VariableDeclaration::LetDeclaration { declarations, .. } => declarations
+				.iter()
+				.map(|item| {
+					(item.name.get_ast_ref(), &item.type_annotation, item.expression.as_ref())
+				})
+				.collect(),
+		};
+		for (name, type_annotation, expression) in items {
+			let (
+				VariableField::Name(VariableIdentifier::Standard(_, position)),
+				None,
+				Some(expression),
+			) = (name, type_annotation, expression)
+			else {
+				continue;
+			};
+			// Functions have their return type hinted instead
+			if matches!(
+				expression,
+				Expression::ArrowFunction(_) | Expression::ExpressionFunction(_)
+			) {
+				continue;
+			}
+			if let Some(ty) = self.get_type_of(expression) {
+				let label = format!(": {}", self.print_type(ty));
+				self.add(position.end, label, InlayHintKind::Type);
+			}
+		}
+	}
+
+	fn add_return_type<T: FunctionBased>(&mut self, function: &FunctionBase<T>) {
+		if function.return_type.is_some() {
+			return;
+		}
+		let id = FunctionId(self.source, function.position.start);
+		if let Some(function_type) = self.output.types.functions.get(&id) {
+			let label = format!(": {}", self.print_type(function_type.return_type));
+			self.add(function.parameters.position.end, label, InlayHintKind::Type);
+		}
+	}
+}
+
+impl VisitorReceiver<()> for InlayHintCollector<'_> {
+	fn visit_expression(&mut self, expression: &Expression, _data: &mut (), _chain: &Chain) {
+		match expression {
+			Expression::FunctionCall { function, arguments, .. } => {
+				self.add_arguments(function, arguments);
+			}
+			Expression::ArrowFunction(function) => self.add_return_type(function),
+			Expression::ExpressionFunction(function) => self.add_return_type(function),
+			_ => {}
+		}
+	}
+
+	fn visit_statement(&mut self, item: BlockItem, _data: &mut (), _chain: &Chain) {
+		let BlockItem::StatementOrDeclaration(StatementOrDeclaration::Declaration(declaration)) =
+			item
+		else {
+			return;
+		};
+		match declaration {
+			Declaration::Variable(declaration) => self.add_variable_types(declaration),
+			Declaration::Function(function) => self.add_return_type(&function.on),
+			Declaration::Export(export) => match &export.on {
+				ExportDeclaration::Variable {
+					exported: Exportable::Variable(declaration), ..
+				} => {
+					self.add_variable_types(declaration);
+				}
+				ExportDeclaration::Variable {
+					exported: Exportable::Function(function), ..
+				} => {
+					self.add_return_type(function);
+				}
+				_ => {}
+			},
+			_ => {}
+		}
+	}
+}
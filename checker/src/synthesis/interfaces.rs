@@ -8,6 +8,7 @@ use crate::{
 	features::functions::{self, GetterSetter},
 	synthesis::parser_property_key_to_checker_property_key,
 	types::{
+		poly_types::check_type_parameter_default,
 		properties::{PropertyKey, PropertyValue},
 		FunctionType, Type,
 	},
@@ -242,15 +243,31 @@ pub(super) fn synthesise_signatures<T: crate::ReadFromFS, B: SynthesiseInterface
 					);
 				}
 				InterfaceMember::Constructor {
-					parameters: _,
-					type_parameters: _,
-					return_type: _,
+					parameters,
+					type_parameters,
+					return_type,
 					is_readonly: _,
 					position,
-				} => checking_data.raise_unimplemented_error(
-					"interface constructor",
-					position.with_source(environment.get_source()),
-				),
+				} => {
+					let behavior = functions::FunctionBehavior::Constructor {
+						non_super_prototype: None,
+						// TODO ...
+						this_object_type: TypeId::ERROR_TYPE,
+					};
+					let function = synthesise_function_annotation(
+						type_parameters,
+						parameters,
+						return_type.as_ref(),
+						environment,
+						checking_data,
+						&position.with_source(environment.get_source()),
+						behavior,
+					);
+
+					if let Some(on) = interface_register_behavior.interface_type() {
+						checking_data.types.set_construct_signature(on, function);
+					}
+				}
 				InterfaceMember::Caller {
 					parameters: _,
 					type_parameters: _,
@@ -314,17 +331,34 @@ pub(super) fn synthesise_signatures<T: crate::ReadFromFS, B: SynthesiseInterface
 
 				if let Some(parameters) = type_parameters {
 					for (parameter, ty) in parameters.iter().zip(parameter_types.iter().flatten()) {
-						if let Some(ref extends) = parameter.extends {
+						let extends = parameter.extends.as_ref().map(|extends| {
 							let extends =
 								synthesise_type_annotation(extends, environment, checking_data);
 
 							checking_data
 								.types
 								.modify_interface_type_parameter_constraint(*ty, extends);
-						}
+							extends
+						});
 
 						// TODO set constraint by modifying type
 						environment.named_types.insert(parameter.name.clone(), *ty);
+
+						if let Some(ref default) = parameter.default {
+							let default =
+								synthesise_type_annotation(default, environment, checking_data);
+							if let Some(extends) = extends {
+								check_type_parameter_default(
+									&parameter.name,
+									extends,
+									default,
+									parameter.position.with_source(environment.get_source()),
+									environment,
+									checking_data,
+								);
+							}
+							checking_data.types.set_type_parameter_default(*ty, default);
+						}
 					}
 				}
 
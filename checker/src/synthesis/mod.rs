@@ -6,13 +6,17 @@
 mod assignments;
 pub mod block;
 pub mod classes;
+pub mod completions;
 pub mod declarations;
 pub mod definitions;
 pub mod expressions;
 mod extensions;
 pub mod functions;
 pub mod hoisting;
+pub mod inlay_hints;
 pub mod interfaces;
+pub mod outline;
+pub mod partial;
 pub mod statements;
 pub mod type_annotations;
 pub mod variables;
@@ -22,9 +26,11 @@ use parser::{
 	ASTNode, ExpressionPosition, ParseOptions, PropertyKey as ParserPropertyKey, StatementPosition,
 };
 use source_map::SourceId;
+use std::time::Duration;
 
 use crate::{
 	context::{Names, VariableRegisterArguments},
+	features::modules::DefinitionFileReference,
 	types::properties::PropertyKey,
 	CheckingData, Diagnostic, Environment, LocalInformation, RootContext, TypeId, VariableId,
 };
@@ -39,10 +45,27 @@ use self::{
 
 pub struct EznoParser;
 
+/// Transformers run over each module after it is parsed and before it is synthesised (for example
+/// substituting defined constants). Not run on definition files
+pub type PreCheckVisitors = parser::visiting::VisitorsMut<()>;
+
+fn run_pre_check_visitors(
+	module: &mut parser::Module,
+	source_id: SourceId,
+	pre_check_visitors: &mut PreCheckVisitors,
+) {
+	module.visit_mut(
+		pre_check_visitors,
+		&mut (),
+		&parser::visiting::VisitOptions::default(),
+		source_id,
+	);
+}
+
 impl crate::ASTImplementation for EznoParser {
 	type ParseOptions = parser::ParseOptions;
 	type ParseError = (parser::ParseError, SourceId);
-	type ParserRequirements = ();
+	type ParserRequirements = PreCheckVisitors;
 
 	type Module<'_a> = parser::Module;
 	type OwnedModule = parser::Module;
@@ -63,10 +86,98 @@ impl crate::ASTImplementation for EznoParser {
 		source_id: SourceId,
 		string: String,
 		options: Self::ParseOptions,
-		_parser_requirements: &mut Self::ParserRequirements,
+		pre_check_visitors: &mut Self::ParserRequirements,
 	) -> Result<Self::Module<'static>, Self::ParseError> {
-		<parser::Module as parser::ASTNode>::from_string(string, options)
-			.map_err(|err| (err, source_id))
+		let mut module = <parser::Module as parser::ASTNode>::from_string(string, options)
+			.map_err(|err| (err, source_id))?;
+		run_pre_check_visitors(&mut module, source_id, pre_check_visitors);
+		Ok(module)
+	}
+
+	/// The visitors are not [`Send`], so they are run on the current thread after parsing. If the
+	/// parser panics on a thread, its modules are returned as parse errors
+	fn modules_from_strings(
+		sources: Vec<(SourceId, String, Self::ParseOptions)>,
+		threads: usize,
+		pre_check_visitors: &mut Self::ParserRequirements,
+	) -> Vec<(crate::ParseResult<Self>, Duration)> {
+		let threads = if cfg!(target_family = "wasm") { 1 } else { threads.min(sources.len()) };
+		if threads <= 1 {
+			return sources
+				.into_iter()
+				.map(|(source_id, string, options)| {
+					let start = crate::chronometer::Instant::now();
+					let module =
+						Self::module_from_string(source_id, string, options, pre_check_visitors);
+					(module, start.elapsed())
+				})
+				.collect();
+		}
+
+		let chunk_size = sources.len().div_ceil(threads);
+		let mut sources = sources.into_iter().peekable();
+		let mut chunks = Vec::with_capacity(threads);
+		while sources.peek().is_some() {
+			chunks.push(sources.by_ref().take(chunk_size).collect::<Vec<_>>());
+		}
+
+		let parsed: Vec<_> = std::thread::scope(|scope| {
+			let handles: Vec<_> = chunks
+				.into_iter()
+				.map(|chunk| {
+					let sources =
+						chunk.iter().map(|(source_id, ..)| *source_id).collect::<Vec<_>>();
+					let handle = scope.spawn(move || {
+						chunk
+							.into_iter()
+							.map(|(source_id, string, options)| {
+								let start = crate::chronometer::Instant::now();
+								let module = parser::Module::from_string(string, options)
+									.map_err(|err| (err, source_id));
+								(source_id, module, start.elapsed())
+							})
+							.collect::<Vec<_>>()
+					});
+					(sources, handle)
+				})
+				.collect();
+
+			handles
+				.into_iter()
+				.flat_map(|(sources, handle)| {
+					handle.join().unwrap_or_else(|payload| {
+						let message = payload
+							.downcast_ref::<&str>()
+							.map(ToString::to_string)
+							.or_else(|| payload.downcast_ref::<String>().cloned())
+							.unwrap_or_default();
+						let reason = format!("parser panicked: {message}");
+						sources
+							.into_iter()
+							.map(|source_id| {
+								let error = parser::ParseError {
+									reason: reason.clone(),
+									position: source_map::Nullable::NULL,
+								};
+								(source_id, Err((error, source_id)), Duration::ZERO)
+							})
+							.collect()
+					})
+				})
+				.collect()
+		});
+
+		parsed
+			.into_iter()
+			.map(|(source_id, module, parse)| {
+				let start = crate::chronometer::Instant::now();
+				let module = module.map(|mut module| {
+					run_pre_check_visitors(&mut module, source_id, pre_check_visitors);
+					module
+				});
+				(module, parse + start.elapsed())
+			})
+			.collect()
 	}
 
 	fn definition_module_from_string(
@@ -81,13 +192,49 @@ impl crate::ASTImplementation for EznoParser {
 			.map_err(|err| (err, source_id))
 	}
 
+	fn definition_file_references(
+		file: &Self::DefinitionFile<'_>,
+	) -> Vec<(DefinitionFileReference, source_map::Span)> {
+		use parser::TripleSlashDirective;
+
+		file.triple_slash_directives()
+			.into_iter()
+			.map(|(directive, position)| match directive {
+				TripleSlashDirective::Path(path) => (DefinitionFileReference::Path(path), position),
+				TripleSlashDirective::Types(name) => {
+					(DefinitionFileReference::Types(name), position)
+				}
+				TripleSlashDirective::Lib(name) => (DefinitionFileReference::Lib(name), position),
+			})
+			.collect()
+	}
+
 	fn synthesise_module<T: crate::ReadFromFS>(
 		module: &Self::Module<'_>,
-		_source_id: SourceId,
+		source_id: SourceId,
 		module_environment: &mut Environment,
 		checking_data: &mut crate::CheckingData<T, Self>,
 	) {
-		synthesise_block(&module.items, module_environment, checking_data);
+		if is_common_js_module(module) {
+			use crate::features::modules::{
+				declare_common_js_variables, export_common_js_module_exports,
+			};
+
+			let module_variable = declare_common_js_variables(module_environment, checking_data);
+			// Not directly in the module environment, as CommonJS modules can redeclare `exports` etc
+			// (they are parameters of the function the module is wrapped in)
+			module_environment.new_lexical_environment_fold_into_parent(
+				crate::Scope::PassThrough { source: source_id },
+				checking_data,
+				|environment, checking_data| {
+					synthesise_block(&module.items, environment, checking_data);
+				},
+			);
+			export_common_js_module_exports(module_variable, module_environment, checking_data);
+			checking_data.modules.common_js_sources.insert(source_id);
+		} else {
+			synthesise_block(&module.items, module_environment, checking_data);
+		}
 	}
 
 	fn synthesise_definition_file<T: crate::ReadFromFS>(
@@ -188,6 +335,80 @@ impl crate::ASTImplementation for EznoParser {
 	fn parameter_constrained<'a>(parameter: &'a Self::TypeParameter<'a>) -> bool {
 		parameter.extends.is_some()
 	}
+
+	fn type_parameter_extends<'a>(
+		parameter: &'a Self::TypeParameter<'a>,
+	) -> Option<&'a Self::TypeAnnotation<'a>> {
+		parameter.extends.as_ref()
+	}
+
+	fn type_parameter_default<'a>(
+		parameter: &'a Self::TypeParameter<'a>,
+	) -> Option<&'a Self::TypeAnnotation<'a>> {
+		parameter.default.as_ref()
+	}
+
+	fn type_parameter_position<'a>(parameter: &'a Self::TypeParameter<'a>) -> source_map::Span {
+		parameter.position
+	}
+}
+
+/// Best effort: no `import` or `export` declarations and (at the top level) assigns to `module.exports`,
+/// `module.exports.*` or `exports.*`
+fn is_common_js_module(module: &parser::Module) -> bool {
+	use parser::{
+		expressions::{
+			assignments::{LHSOfAssignment, VariableOrPropertyAccess},
+			MultipleExpression,
+		},
+		Declaration, Expression, Statement, StatementOrDeclaration,
+	};
+
+	fn is_exports(expression: &Expression) -> bool {
+		match expression {
+			Expression::VariableReference(name, _) => name == "exports",
+			Expression::PropertyAccess {
+				parent,
+				property: parser::PropertyReference::Standard { property, .. },
+				..
+			} => {
+				property == "exports"
+					&& matches!(&**parent, Expression::VariableReference(name, _) if name == "module")
+			}
+			_ => false,
+		}
+	}
+
+	let mut assigns_exports = false;
+	for item in &module.items {
+		match item {
+			StatementOrDeclaration::Declaration(
+				Declaration::Import(_) | Declaration::Export(_),
+			) => {
+				return false;
+			}
+			StatementOrDeclaration::Statement(Statement::Expression(
+				MultipleExpression::Single(Expression::Assignment {
+					lhs:
+						LHSOfAssignment::VariableOrPropertyAccess(
+							VariableOrPropertyAccess::PropertyAccess { parent, property, .. },
+						),
+					..
+				}),
+			)) => {
+				let is_module_exports = matches!(
+					(&**parent, property),
+					(
+						Expression::VariableReference(name, _),
+						parser::PropertyReference::Standard { property, .. }
+					) if name == "module" && property == "exports"
+				);
+				assigns_exports |= is_module_exports || is_exports(parent);
+			}
+			_ => {}
+		}
+	}
+	assigns_exports
 }
 
 /// `perform_side_effect_computed` is used for hoisting
@@ -233,6 +454,18 @@ pub(super) fn parser_property_key_to_checker_property_key<
 	}
 }
 
+/// The value of a `123n` literal. `None` if it does not fit in an [`i128`]
+pub(super) fn big_int_value(sign: parser::NumberSign, value: &str) -> Option<i128> {
+	let value = value.replace('_', "");
+	let value = match value.get(..2) {
+		Some("0x" | "0X") => i128::from_str_radix(&value[2..], 16),
+		Some("0o" | "0O") => i128::from_str_radix(&value[2..], 8),
+		Some("0b" | "0B") => i128::from_str_radix(&value[2..], 2),
+		_ => value.parse(),
+	};
+	value.ok().map(|value| sign.apply(value))
+}
+
 impl From<(parser::ParseError, SourceId)> for Diagnostic {
 	fn from(parse_error: (parser::ParseError, SourceId)) -> Self {
 		Diagnostic::Position {
@@ -275,8 +508,9 @@ pub mod interactive {
 	use source_map::{FileSystem, MapFileStore, SourceId, WithPathMap};
 
 	use crate::{
-		add_definition_files_to_root, types::printing::print_type, CheckingData,
-		DiagnosticsContainer, RootContext, TypeId,
+		add_definition_files_to_root,
+		types::printing::{print_type, TypeToStringOptions},
+		CheckingData, DiagnosticsContainer, RootContext, TypeId,
 	};
 
 	use super::{block::synthesise_block, expressions::synthesise_multiple_expression};
@@ -293,8 +527,12 @@ pub mod interactive {
 			type_definition_files: HashSet<PathBuf>,
 		) -> Result<Self, (DiagnosticsContainer, MapFileStore<WithPathMap>)> {
 			let mut root = RootContext::new_with_primitive_references();
-			let mut checking_data =
-				CheckingData::new(Default::default(), resolver, Default::default(), ());
+			let mut checking_data = CheckingData::new(
+				Default::default(),
+				resolver,
+				Default::default(),
+				Default::default(),
+			);
 
 			add_definition_files_to_root(type_definition_files, &mut root, &mut checking_data);
 
@@ -310,6 +548,22 @@ pub mod interactive {
 		pub fn check_item(
 			&mut self,
 			item: &parser::Module,
+		) -> Result<(Option<String>, DiagnosticsContainer), DiagnosticsContainer> {
+			self.check(item, false)
+		}
+
+		/// Same as [`Self::check_item`] but prints the type as a TypeScript annotation (for `:type`)
+		pub fn check_item_type(
+			&mut self,
+			item: &parser::Module,
+		) -> Result<(Option<String>, DiagnosticsContainer), DiagnosticsContainer> {
+			self.check(item, true)
+		}
+
+		fn check(
+			&mut self,
+			item: &parser::Module,
+			as_annotation: bool,
 		) -> Result<(Option<String>, DiagnosticsContainer), DiagnosticsContainer> {
 			let (ty, ..) = self.root.new_lexical_environment_fold_into_parent(
 				crate::Scope::PassThrough { source: self.source },
@@ -330,7 +584,15 @@ pub mod interactive {
 							checking_data,
 							TypeId::ANY_TYPE,
 						);
-						Some(print_type(result, &checking_data.types, environment, false))
+						Some(if as_annotation {
+							checking_data.types.type_to_ts_string(
+								result,
+								environment,
+								TypeToStringOptions::default(),
+							)
+						} else {
+							print_type(result, &checking_data.types, environment, false)
+						})
 					} else {
 						synthesise_block(&item.items, environment, checking_data);
 						None
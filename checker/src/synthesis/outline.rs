@@ -0,0 +1,66 @@
+//! Document symbols of a checked module. The outline from the parser with the types of the module's
+//! variables (and functions and classes) after checking
+
+use parser::outline::{Symbol, SymbolKind};
+use source_map::SpanWithSource;
+
+use crate::{
+	features::modules::SynthesisedModule, types::printing::TypeToStringOptions, CheckOutput,
+};
+
+use super::EznoParser;
+
+/// A [`Symbol`] in a checked module
+#[derive(Debug, Clone)]
+pub struct OutlineSymbol {
+	pub name: String,
+	pub kind: SymbolKind,
+	pub position: SpanWithSource,
+	pub name_position: SpanWithSource,
+	pub is_exported: bool,
+	/// The value of the variable, function or class as a TypeScript annotation. Only for symbols
+	/// at the top level of the module
+	pub r#type: Option<String>,
+	pub children: Vec<OutlineSymbol>,
+}
+
+impl CheckOutput<EznoParser> {
+	/// The declarations of the module at `path` (for `documentSymbol`). `None` if the module was
+	/// not checked
+	#[must_use]
+	pub fn get_outline(&self, path: &str) -> Option<Vec<OutlineSymbol>> {
+		let source = self.module_contents.get_source_at_path(path.as_ref())?;
+		let module = self.modules.get(&source)?;
+		let symbols = module.content.outline().into_iter().map(|symbol| {
+			let r#type = self.get_type_of_top_level_symbol(&symbol.name, module);
+			OutlineSymbol { r#type, ..to_outline_symbol(symbol, source) }
+		});
+		Some(symbols.collect())
+	}
+
+	fn get_type_of_top_level_symbol(
+		&self,
+		name: &str,
+		module: &SynthesisedModule<parser::Module>,
+	) -> Option<String> {
+		let variable = module.names.variables.get(name)?;
+		let value = module.info.variable_current_value.get(&variable.get_id())?;
+		Some(self.types.type_to_ts_string(*value, &module.info, TypeToStringOptions::default()))
+	}
+}
+
+fn to_outline_symbol(symbol: Symbol, source: source_map::SourceId) -> OutlineSymbol {
+	OutlineSymbol {
+		name: symbol.name,
+		kind: symbol.kind,
+		position: symbol.position.with_source(source),
+		name_position: symbol.name_position.with_source(source),
+		is_exported: symbol.is_exported,
+		r#type: None,
+		children: symbol
+			.children
+			.into_iter()
+			.map(|child| to_outline_symbol(child, source))
+			.collect(),
+	}
+}
@@ -0,0 +1,178 @@
+//! Re-checking a single function of a checked project, for editors. The function is synthesised
+//! again in the scope of its module (with the variables, types and values found when the project
+//! was checked), so only its body is checked rather than the whole project. The types created by the
+//! check are removed after, so checking on every edit does not grow the [`crate::types::TypeStore`]
+
+use std::{mem, path::Path};
+
+use parser::{
+	declarations::{export::Exportable, ExportDeclaration, VariableDeclaration},
+	ASTNode, Declaration, Expression, Statement, StatementOrDeclaration,
+};
+use source_map::SourceId;
+
+use crate::{
+	context::RootContext, features::modules::Exported, CheckOutput, CheckingData,
+	DiagnosticsContainer, Scope, TypeCheckOptions, TypeId,
+};
+
+use super::{expressions::synthesise_expression, EznoParser};
+
+impl CheckOutput<EznoParser> {
+	/// Checks `source`, the new text of the function (a function declaration, function expression
+	/// or arrow function) starting at `position` in the module at `path`. Positions in the
+	/// diagnostics are from `position`, so they are correct for the edited file.
+	///
+	/// The types and values outside of the function are not changed (so other functions that call
+	/// it are not re-checked). Returns `None` if the module at `path` was not checked or if the
+	/// function is not declared at the top level of the module (the scopes of nested functions are
+	/// not kept)
+	pub fn check_function<T: crate::ReadFromFS>(
+		&mut self,
+		path: &Path,
+		position: u32,
+		source: &str,
+		resolver: &T,
+		options: TypeCheckOptions,
+	) -> Option<DiagnosticsContainer> {
+		let source_id = self.module_contents.get_source_at_path(path)?;
+		if !is_top_level_function(&self.modules.get(&source_id)?.content, position) {
+			return None;
+		}
+
+		let is_js = path
+			.extension()
+			.and_then(std::ffi::OsStr::to_str)
+			.is_some_and(|extension| extension.ends_with("js"));
+		let parse_options = parser::ParseOptions { type_annotations: !is_js, ..Default::default() };
+		let function = match Expression::from_string_with_options(
+			source.to_owned(),
+			parse_options,
+			Some(position),
+		) {
+			Ok((function, _)) => function,
+			Err(err) => {
+				let mut diagnostics = DiagnosticsContainer::new();
+				diagnostics.add_error((err, source_id));
+				return Some(diagnostics);
+			}
+		};
+
+		let mut checking_data = CheckingData::<T, EznoParser>::new(
+			options,
+			resolver,
+			Some(mem::take(&mut self.module_contents)),
+			Default::default(),
+		);
+		checking_data.types = mem::take(&mut self.types);
+		checking_data.modules.synthesised_modules = mem::take(&mut self.modules);
+
+		let mut root = RootContext::new_with_primitive_references();
+		let names = mem::take(&mut self.top_level_names);
+		root.variables = names.variables;
+		root.named_types = names.named_types;
+		root.variable_names = names.variable_names;
+		root.info = mem::take(&mut self.top_level_information);
+
+		// The function (and the functions in it) replace the existing function types, so they are
+		// kept and put back
+		let end = position + u32::try_from(source.len()).expect("function too long");
+		let in_function =
+			|id: &crate::FunctionId| id.0 == source_id && (position..end).contains(&id.1);
+		let functions = &mut checking_data.types.functions;
+		let replaced = functions.keys().copied().filter(in_function).collect::<Vec<_>>();
+		let replaced =
+			replaced.into_iter().filter_map(|id| functions.remove_entry(&id)).collect::<Vec<_>>();
+		let snapshot = checking_data.types.snapshot();
+
+		check_function_in_module(&function, source_id, &root, &mut checking_data);
+
+		checking_data.types.restore(snapshot);
+		checking_data.types.functions.extend(replaced);
+
+		let CheckingData { diagnostics_container, modules, types, .. } = checking_data;
+		self.types = types;
+		self.modules = modules.synthesised_modules;
+		self.module_contents = modules.files;
+		self.top_level_information = root.info;
+		self.top_level_names.variables = root.variables;
+		self.top_level_names.named_types = root.named_types;
+		self.top_level_names.variable_names = root.variable_names;
+
+		Some(diagnostics_container)
+	}
+}
+
+/// The function is synthesised in a child of the module environment, which is dropped after so
+/// that the function does not change the module
+fn check_function_in_module<T: crate::ReadFromFS>(
+	function: &Expression,
+	source: SourceId,
+	root: &RootContext,
+	checking_data: &mut CheckingData<T, EznoParser>,
+) {
+	let module = checking_data.modules.synthesised_modules.get_mut(&source).unwrap();
+	let mut module_environment =
+		root.new_lexical_environment(Scope::Module { source, exported: Exported::default() });
+	module_environment.variables = mem::take(&mut module.names.variables);
+	module_environment.named_types = mem::take(&mut module.names.named_types);
+	module_environment.variable_names = mem::take(&mut module.names.variable_names);
+	module_environment.info = mem::take(&mut module.info);
+
+	{
+		let mut environment = module_environment.new_lexical_environment(Scope::Block {});
+		synthesise_expression(function, &mut environment, checking_data, TypeId::ANY_TYPE);
+	}
+
+	let module = checking_data.modules.synthesised_modules.get_mut(&source).unwrap();
+	module.names.variables = module_environment.variables;
+	module.names.named_types = module_environment.named_types;
+	module.names.variable_names = module_environment.variable_names;
+	module.info = module_environment.info;
+}
+
+/// Whether the function starting at `position` is declared at the top level of `module`, so its
+/// scope is the module
+fn is_top_level_function(module: &parser::Module, position: u32) -> bool {
+	fn is_function_at(expression: &Expression, position: u32) -> bool {
+		matches!(expression, Expression::ArrowFunction(..) | Expression::ExpressionFunction(..))
+			&& expression.get_position().start == position
+	}
+
+	fn has_function_at(declaration: &VariableDeclaration, position: u32) -> bool {
+		match declaration {
+			VariableDeclaration::ConstDeclaration { declarations, .. } => {
+				declarations.iter().any(|item| is_function_at(&item.expression, position))
+			}
+			VariableDeclaration::LetDeclaration { declarations, .. } => declarations
+				.iter()
+				.filter_map(|item| item.expression.as_ref())
+				.any(|expression| is_function_at(expression, position)),
+		}
+	}
+
+	module.items.iter().any(|item| match item {
+		StatementOrDeclaration::Declaration(Declaration::Function(function)) => {
+			function.on.get_position().start == position
+		}
+		StatementOrDeclaration::Declaration(Declaration::Variable(declaration)) => {
+			has_function_at(declaration, position)
+		}
+		StatementOrDeclaration::Declaration(Declaration::Export(export)) => match &export.on {
+			ExportDeclaration::Variable { exported: Exportable::Function(function), .. } => {
+				function.get_position().start == position
+			}
+			ExportDeclaration::Variable { exported: Exportable::Variable(declaration), .. } => {
+				has_function_at(declaration, position)
+			}
+			ExportDeclaration::Default { expression, .. } => is_function_at(expression, position),
+			_ => false,
+		},
+		StatementOrDeclaration::Statement(Statement::VarVariable(statement)) => statement
+			.declarations
+			.iter()
+			.filter_map(|item| item.expression.as_ref())
+			.any(|expression| is_function_at(expression, position)),
+		_ => false,
+	})
+}
@@ -1,18 +1,27 @@
 use super::{
-	expressions::synthesise_multiple_expression,
+	expressions::{
+		multiple_expression_has_side_effects, synthesise_expression, synthesise_multiple_expression,
+	},
 	synthesise_block,
 	type_annotations::synthesise_type_annotation,
 	variables::{register_variable, synthesise_variable_declaration_item},
 };
 use crate::{
 	context::{Scope, VariableRegisterArguments},
-	diagnostics::TypeCheckError,
+	diagnostics::{TypeCheckError, TypeCheckWarning, TypeStringRepresentation},
 	features::iteration::{synthesise_iteration, IterationBehavior},
 	synthesis::EznoParser,
-	CheckingData, Environment, TypeId,
+	types::{Type, TypeStore},
+	CheckingData, Constant, Environment, TypeId,
 };
 
-use parser::{expressions::MultipleExpression, ASTNode, BlockOrSingleStatement, Statement};
+use parser::{
+	expressions::MultipleExpression,
+	statements::{SwitchBranch, SwitchStatement},
+	ASTNode, BlockOrSingleStatement, Expression, PropertyReference, Statement,
+	StatementOrDeclaration,
+};
+use source_map::{FileSystem, Span, SpanWithSource};
 use std::collections::HashMap;
 
 pub type ExportedItems = HashMap<String, crate::features::variables::VariableOrImport>;
@@ -30,12 +39,34 @@ pub(super) fn synthesise_statement<T: crate::ReadFromFS>(
 ) {
 	match statement {
 		Statement::Expression(expression) => {
-			synthesise_multiple_expression(
+			let value = synthesise_multiple_expression(
 				expression,
 				environment,
 				checking_data,
 				TypeId::ANY_TYPE,
 			);
+			if checking_data.options.no_floating_promises
+				&& is_floating_promise(
+					expression,
+					value,
+					&checking_data.types,
+					&checking_data.options.floating_promise_allowed_functions,
+				) {
+				checking_data.diagnostics_container.add_warning(TypeCheckWarning::FloatingPromise(
+					expression.get_position().with_source(environment.get_source()),
+				));
+			}
+			// String literals are directives (such as `"use strict"`)
+			if checking_data.options.no_dead_stores
+				&& !matches!(expression, MultipleExpression::Single(Expression::StringLiteral(..)))
+				&& !multiple_expression_has_side_effects(expression)
+			{
+				checking_data.diagnostics_container.add_warning(
+					TypeCheckWarning::UnusedExpression(
+						expression.get_position().with_source(environment.get_source()),
+					),
+				);
+			}
 		}
 		Statement::Return(return_statement) => {
 			environment.return_value(
@@ -98,12 +129,7 @@ pub(super) fn synthesise_statement<T: crate::ReadFromFS>(
 				checking_data,
 			);
 		}
-		Statement::Switch(stmt) => {
-			checking_data.diagnostics_container.add_error(TypeCheckError::Unsupported {
-				thing: "Switch statement",
-				at: stmt.get_position().with_source(environment.get_source()),
-			});
-		}
+		Statement::Switch(stmt) => synthesise_switch(stmt, environment, checking_data),
 		Statement::WhileLoop(stmt) => synthesise_iteration(
 			IterationBehavior::While(&stmt.condition),
 			information.and_then(|info| info.label),
@@ -220,7 +246,8 @@ pub(super) fn synthesise_statement<T: crate::ReadFromFS>(
 			);
 		}
 		Statement::VarVariable(stmt) => {
-			for declaration in &stmt.declarations {
+			// Without a value the variable is not changed (it is `undefined` from hoisting)
+			for declaration in stmt.declarations.iter().filter(|item| item.expression.is_some()) {
 				synthesise_variable_declaration_item(declaration, environment, checking_data, None);
 			}
 		}
@@ -289,3 +316,408 @@ fn synthesise_block_or_single_statement<T: crate::ReadFromFS>(
 		}
 	}
 }
+
+/// Synthesised as a chain of conditionals on `discriminant === case`, where empty cases are merged
+/// with the next case and the `default` case is the final `else`. This is only equivalent when no
+/// case falls through into the next and `break` is only used as the last statement of a case
+fn synthesise_switch<T: crate::ReadFromFS>(
+	stmt: &SwitchStatement,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	fn run_cases<T: crate::ReadFromFS>(
+		cases: &[(TypeId, Span, &[StatementOrDeclaration])],
+		default: Option<&[StatementOrDeclaration]>,
+		environment: &mut Environment,
+		checking_data: &mut CheckingData<T, EznoParser>,
+	) {
+		let [(condition, position, body), others @ ..] = cases else {
+			if let Some(default) = default {
+				let (_result, _, _) = environment.new_lexical_environment_fold_into_parent(
+					Scope::Block {},
+					checking_data,
+					|environment, checking_data| {
+						synthesise_case_body(default, environment, checking_data);
+					},
+				);
+			}
+			return;
+		};
+
+		environment.new_conditional_context(
+			(*condition, *position),
+			|env: &mut Environment, data: &mut CheckingData<T, EznoParser>| {
+				synthesise_case_body(body, env, data);
+			},
+			if !others.is_empty() || default.is_some() {
+				Some(|env: &mut Environment, data: &mut CheckingData<T, EznoParser>| {
+					run_cases(others, default, env, data);
+				})
+			} else {
+				None
+			},
+			checking_data,
+		);
+	}
+
+	/// Skips the final `break`, which can be in a trailing block
+	fn synthesise_case_body<T: crate::ReadFromFS>(
+		statements: &[StatementOrDeclaration],
+		environment: &mut Environment,
+		checking_data: &mut CheckingData<T, EznoParser>,
+	) {
+		let last = statements.iter().rposition(|item| !is_comment(item));
+		match last.map(|idx| (idx, &statements[idx])) {
+			Some((idx, StatementOrDeclaration::Statement(Statement::Break(None, _)))) => {
+				synthesise_block(&statements[..idx], environment, checking_data);
+			}
+			Some((idx, StatementOrDeclaration::Statement(Statement::Block(block)))) => {
+				synthesise_block(&statements[..idx], environment, checking_data);
+				synthesise_case_body(&block.0, environment, checking_data);
+			}
+			_ => synthesise_block(statements, environment, checking_data),
+		}
+	}
+
+	let (discriminant, values) = check_switch_cases(stmt, environment, checking_data);
+
+	let can_synthesise = stmt.branches.iter().enumerate().all(|(idx, branch)| {
+		let statements = get_branch_statements(branch);
+		let falls_through = idx + 1 < stmt.branches.len()
+			&& statements
+				.iter()
+				.rev()
+				.find(|item| !is_comment(item))
+				.is_some_and(|last| !ends_control_flow(last));
+		!falls_through && !case_has_nested_break(statements)
+	});
+
+	if !can_synthesise {
+		checking_data.diagnostics_container.add_error(TypeCheckError::Unsupported {
+			thing: "Switch statement with fallthrough or nested break",
+			at: stmt.get_position().with_source(environment.get_source()),
+		});
+		return;
+	}
+
+	let mut cases = Vec::new();
+	let mut default = None;
+	let mut pending: Option<(TypeId, Span)> = None;
+	let mut pending_default = false;
+
+	for (branch, value) in stmt.branches.iter().zip(values) {
+		if let (SwitchBranch::Case(case, _), Some(value)) = (branch, value) {
+			let equal = crate::features::operations::evaluate_equality_inequality_operation(
+				discriminant,
+				&crate::features::operations::EqualityAndInequality::StrictEqual,
+				value,
+				&mut checking_data.types,
+				checking_data.options.strict_casts,
+			)
+			.unwrap_or(TypeId::BOOLEAN_TYPE);
+
+			pending = Some(match pending {
+				Some((condition, position)) => (
+					crate::types::new_logical_or_type(condition, equal, &mut checking_data.types),
+					position,
+				),
+				None => (equal, case.get_position()),
+			});
+		} else {
+			pending_default = true;
+		}
+
+		let statements = get_branch_statements(branch);
+		if statements.iter().all(is_comment) {
+			continue;
+		}
+
+		if std::mem::take(&mut pending_default) {
+			pending = None;
+			default = Some(statements);
+		} else if let Some((condition, position)) = pending.take() {
+			cases.push((condition, position, statements));
+		}
+	}
+
+	// Trailing empty cases
+	if pending_default {
+		default = Some(&[]);
+	}
+
+	run_cases(&cases, default, environment, checking_data);
+}
+
+fn get_branch_statements(branch: &SwitchBranch) -> &[StatementOrDeclaration] {
+	match branch {
+		SwitchBranch::Default(statements) | SwitchBranch::Case(_, statements) => statements,
+	}
+}
+
+/// Whether a `break` exits the case before its end
+fn case_has_nested_break(statements: &[StatementOrDeclaration]) -> bool {
+	let Some(idx) = statements.iter().rposition(|item| !is_comment(item)) else { return false };
+	statements[..idx].iter().any(contains_unlabelled_break)
+		|| match &statements[idx] {
+			StatementOrDeclaration::Statement(Statement::Break(None, _)) => false,
+			StatementOrDeclaration::Statement(Statement::Block(block)) => {
+				case_has_nested_break(&block.0)
+			}
+			item => contains_unlabelled_break(item),
+		}
+}
+
+/// Whether `item` has a `break` that would exit the enclosing switch (so not inside a nested loop or switch)
+fn contains_unlabelled_break(item: &StatementOrDeclaration) -> bool {
+	fn statement_contains_unlabelled_break(statement: &Statement) -> bool {
+		match statement {
+			Statement::Break(None, _) => true,
+			Statement::Block(block) => block.0.iter().any(contains_unlabelled_break),
+			Statement::If(if_statement) => {
+				block_or_single_contains_unlabelled_break(&if_statement.inner)
+					|| if_statement.else_conditions.iter().any(|condition| {
+						block_or_single_contains_unlabelled_break(&condition.inner)
+					}) || if_statement
+					.trailing_else
+					.as_ref()
+					.is_some_and(|r#else| block_or_single_contains_unlabelled_break(&r#else.inner))
+			}
+			Statement::TryCatch(stmt) => {
+				[Some(&stmt.try_inner), stmt.catch_inner.as_ref(), stmt.finally_inner.as_ref()]
+					.into_iter()
+					.flatten()
+					.any(|block| block.0.iter().any(contains_unlabelled_break))
+			}
+			Statement::Labelled { statement, .. } => statement_contains_unlabelled_break(statement),
+			_ => false,
+		}
+	}
+
+	fn block_or_single_contains_unlabelled_break(inner: &BlockOrSingleStatement) -> bool {
+		match inner {
+			BlockOrSingleStatement::Braced(block) => block.0.iter().any(contains_unlabelled_break),
+			BlockOrSingleStatement::SingleStatement(statement) => {
+				statement_contains_unlabelled_break(statement)
+			}
+		}
+	}
+
+	matches!(item, StatementOrDeclaration::Statement(statement) if statement_contains_unlabelled_break(statement))
+}
+
+/// Warns on duplicate and unreachable cases (from the constant values of the case expressions)
+/// and on cases that fall through. Returns the discriminant and the values of each case (`None`
+/// for the `default` case)
+fn check_switch_cases<T: crate::ReadFromFS>(
+	stmt: &SwitchStatement,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) -> (TypeId, Vec<Option<TypeId>>) {
+	let value =
+		synthesise_multiple_expression(&stmt.case, environment, checking_data, TypeId::ANY_TYPE);
+	let discriminant = crate::types::get_constraint(value, &checking_data.types).unwrap_or(value);
+	let mut remaining = get_constant_union_members(discriminant, &checking_data.types);
+	let mut previous = Vec::<(TypeId, SpanWithSource)>::new();
+	let mut values = Vec::with_capacity(stmt.branches.len());
+
+	for (idx, branch) in stmt.branches.iter().enumerate() {
+		let statements = match branch {
+			SwitchBranch::Default(statements) => {
+				values.push(None);
+				statements
+			}
+			SwitchBranch::Case(case, statements) => {
+				let value =
+					synthesise_expression(case, environment, checking_data, TypeId::ANY_TYPE);
+				values.push(Some(value));
+				let position = case.get_position().with_source(environment.get_source());
+				let is_constant = matches!(
+					checking_data.types.get_type_by_id(value),
+					Type::Constant(constant) if !matches!(constant, Constant::NaN)
+				);
+				let existing = previous.iter().find(|(existing, _)| *existing == value);
+				let debug = checking_data.options.debug_types;
+
+				if let (true, Some((_, existing))) = (is_constant, existing) {
+					checking_data.diagnostics_container.add_warning(
+						TypeCheckWarning::DuplicateSwitchCase {
+							value: TypeStringRepresentation::from_type_id(
+								value,
+								environment,
+								&checking_data.types,
+								debug,
+							),
+							position,
+							existing: *existing,
+						},
+					);
+				} else if let Some(remaining) = &mut remaining {
+					if remaining.is_empty() {
+						checking_data.diagnostics_container.add_warning(
+							TypeCheckWarning::UnreachableSwitchCase {
+								discriminant: TypeStringRepresentation::from_type_id(
+									discriminant,
+									environment,
+									&checking_data.types,
+									debug,
+								),
+								position,
+							},
+						);
+					} else {
+						remaining.retain(|member| *member != value);
+					}
+				}
+
+				if is_constant {
+					previous.push((value, position));
+				}
+				statements
+			}
+		};
+
+		if let Some(next) = stmt.branches.get(idx + 1) {
+			let next_start = match next {
+				SwitchBranch::Case(case, _) => case.get_position().start,
+				SwitchBranch::Default(statements) => statements
+					.first()
+					.map_or(stmt.position.end, |statement| statement.get_position().start),
+			};
+			check_case_fallthrough(statements, next_start, environment, checking_data);
+		}
+	}
+
+	(value, values)
+}
+
+/// The marker comment is found from the source, as only documentation comments are parsed.
+/// `next_start` is the start of the next case
+fn check_case_fallthrough<T: crate::ReadFromFS>(
+	statements: &[StatementOrDeclaration],
+	next_start: u32,
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	let Some(last) = statements.iter().rev().find(|item| !is_comment(item)) else { return };
+	if ends_control_flow(last) {
+		return;
+	}
+
+	let position = last.get_position().with_source(environment.get_source());
+	let content = checking_data.modules.files.get_file_content(position.source);
+	let after = content.get(position.end as usize..next_start as usize).unwrap_or_default();
+	if !is_fallthrough_comment(after) {
+		checking_data
+			.diagnostics_container
+			.add_warning(TypeCheckWarning::SwitchCaseFallthrough(position));
+	}
+}
+
+/// Matches `falls through`, `fallthrough`, `fall through` etc
+fn is_fallthrough_comment(comment: &str) -> bool {
+	let comment = comment.to_lowercase();
+	let comment = comment.split_whitespace().collect::<String>();
+	comment.contains("fallthrough") || comment.contains("fallsthrough")
+}
+
+fn is_comment(item: &StatementOrDeclaration) -> bool {
+	matches!(
+		item,
+		StatementOrDeclaration::Statement(Statement::Comment(..) | Statement::MultiLineComment(..))
+	)
+}
+
+/// Whether control never continues to the statement after `item`
+fn ends_control_flow(item: &StatementOrDeclaration) -> bool {
+	fn statement_ends_control_flow(statement: &Statement) -> bool {
+		match statement {
+			Statement::Return(_)
+			| Statement::Break(..)
+			| Statement::Continue(..)
+			| Statement::Throw(_) => true,
+			Statement::Block(block) => {
+				block.0.iter().rev().find(|item| !is_comment(item)).is_some_and(ends_control_flow)
+			}
+			Statement::If(if_statement) => {
+				if_statement.trailing_else.as_ref().is_some_and(|r#else| {
+					block_or_single_ends_control_flow(&r#else.inner)
+						&& block_or_single_ends_control_flow(&if_statement.inner)
+						&& if_statement
+							.else_conditions
+							.iter()
+							.all(|condition| block_or_single_ends_control_flow(&condition.inner))
+				})
+			}
+			_ => false,
+		}
+	}
+
+	fn block_or_single_ends_control_flow(inner: &BlockOrSingleStatement) -> bool {
+		match inner {
+			BlockOrSingleStatement::Braced(block) => {
+				block.0.iter().rev().find(|item| !is_comment(item)).is_some_and(ends_control_flow)
+			}
+			BlockOrSingleStatement::SingleStatement(statement) => {
+				statement_ends_control_flow(statement)
+			}
+		}
+	}
+
+	match item {
+		StatementOrDeclaration::Statement(statement) => statement_ends_control_flow(statement),
+		_ => false,
+	}
+}
+
+/// `None` if `ty` is not a union of constants
+fn get_constant_union_members(ty: TypeId, types: &TypeStore) -> Option<Vec<TypeId>> {
+	fn collect(ty: TypeId, types: &TypeStore, members: &mut Vec<TypeId>) -> bool {
+		match types.get_type_by_id(ty) {
+			Type::Or(lhs, rhs) => collect(*lhs, types, members) && collect(*rhs, types, members),
+			Type::AliasTo { to, parameters: None, .. } => collect(*to, types, members),
+			Type::Constant(Constant::NaN) => false,
+			Type::Constant(_) => {
+				members.push(ty);
+				true
+			}
+			_ => false,
+		}
+	}
+
+	let mut members = Vec::new();
+	collect(ty, types, &mut members).then_some(members)
+}
+
+/// For [`crate::TypeCheckOptions::no_floating_promises`]. Whether the expression statement (with
+/// type `value`) is a promise without a rejection handler, that is not from one of
+/// `allowed_functions` (see [`crate::TypeCheckOptions::floating_promise_allowed_functions`])
+#[must_use]
+pub fn is_floating_promise(
+	expression: &MultipleExpression,
+	value: TypeId,
+	types: &crate::types::TypeStore,
+	allowed_functions: &[String],
+) -> bool {
+	if types.get_promise_value(value).is_none() {
+		return false;
+	}
+	let MultipleExpression::Single(Expression::FunctionCall { function, arguments, .. }) =
+		expression
+	else {
+		return true;
+	};
+	let name = match &**function {
+		Expression::VariableReference(name, _) => name,
+		Expression::PropertyAccess {
+			property: PropertyReference::Standard { property, .. },
+			..
+		} => {
+			if property == "catch" || (property == "then" && arguments.len() > 1) {
+				return false;
+			}
+			property
+		}
+		_ => return true,
+	};
+	!allowed_functions.iter().any(|allowed| allowed == name)
+}
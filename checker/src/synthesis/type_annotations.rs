@@ -20,28 +20,91 @@
 
 use std::convert::TryInto;
 
-use map_vec::Map;
 use parser::{
 	type_annotations::{
 		AnnotationWithBinder, CommonTypes, TupleElementKind, TypeCondition, TypeConditionResult,
 	},
 	ASTNode, TypeAnnotation,
 };
-use source_map::SpanWithSource;
+use source_map::{Span, SpanWithSource};
 
 use crate::{
-	context::information::Publicity,
 	diagnostics::{TypeCheckError, TypeCheckWarning, TypeStringRepresentation},
-	features::{objects::ObjectBuilder, template_literal::synthesize_template_literal_type},
+	features::{
+		deprecation::warn_if_deprecated_type,
+		semantic_tokens::{get_type_token_kind, SemanticTokenKind},
+		template_literal::{
+			new_string_intrinsic_type, synthesize_template_literal_type, StringIntrinsic,
+		},
+	},
 	synthesis::functions::synthesise_function_annotation,
 	types::{
-		poly_types::generic_type_arguments::StructureGenericArguments,
-		properties::{PropertyKey, PropertyValue},
-		Constant, Constructor, StructureGenerics, Type, TypeId,
+		poly_types::generic_type_arguments::StructureGenericArguments, tuples, Constant,
+		Constructor, StructureGenerics, Type, TypeId, TypeStore,
 	},
 	CheckingData, Environment,
 };
 
+/// Adds the defaults of `parameters` that do not have an argument. Defaults can reference earlier
+/// parameters, so are substituted with the arguments before them. Returns whether every parameter
+/// now has an argument
+fn add_default_type_arguments(
+	parameters: &[TypeId],
+	type_arguments: &mut map_vec::Map<TypeId, (TypeId, SpanWithSource)>,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	types: &mut TypeStore,
+) -> bool {
+	let mut complete = true;
+	for parameter in parameters {
+		if type_arguments.get(parameter).is_some() {
+			continue;
+		}
+		if let Some(default) = types.get_type_parameter_default(*parameter) {
+			let mut arguments =
+				StructureGenericArguments::ExplicitRestrictions(type_arguments.clone());
+			let default = crate::types::substitute(default, &mut arguments, environment, types);
+			type_arguments.insert(*parameter, (default, position));
+		} else {
+			complete = false;
+		}
+	}
+	complete
+}
+
+/// `on` with `type_arguments`. Aliases are specialised eagerly
+fn specialise_structure(
+	on: TypeId,
+	type_arguments: map_vec::Map<TypeId, (TypeId, SpanWithSource)>,
+	environment: &mut Environment,
+	types: &mut TypeStore,
+) -> TypeId {
+	let is_flattenable_alias = if let Type::AliasTo { to, .. } = types.get_type_by_id(on) {
+		// Important that these wrappers are kept as there 'wrap' holds information
+		if matches!(
+			on,
+			TypeId::LITERAL_RESTRICTION | TypeId::READONLY_RESTRICTION | TypeId::NOT_RESTRICTION
+		) {
+			None
+		} else {
+			Some(*to)
+		}
+	} else {
+		None
+	};
+
+	// Eagerly specialise for type alias. TODO don't do for object types...
+	let mut arguments = StructureGenericArguments::ExplicitRestrictions(type_arguments);
+	if let Some(to) = is_flattenable_alias {
+		crate::types::substitute(to, &mut arguments, environment, types)
+	} else {
+		let ty =
+			Type::Constructor(Constructor::StructureGenerics(StructureGenerics { on, arguments }));
+
+		types.register_type(ty)
+	}
+}
+
 /// Turns a [`parser::TypeAnnotation`] into [`TypeId`]
 ///
 /// [`CheckingData`] contains [Memory] and [`crate::ErrorAndWarningHandler`]
@@ -62,10 +125,18 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 			CommonTypes::String => TypeId::STRING_TYPE,
 			CommonTypes::Number => TypeId::NUMBER_TYPE,
 			CommonTypes::Boolean => TypeId::BOOLEAN_TYPE,
+			CommonTypes::BigInt => TypeId::BIGINT_TYPE,
 		},
 		TypeAnnotation::StringLiteral(value, ..) => {
 			checking_data.types.new_constant_type(Constant::String(value.clone()))
 		}
+		TypeAnnotation::NumberLiteral(parser::NumberRepresentation::BigInt(sign, value), _) => {
+			if let Some(value) = super::big_int_value(*sign, value) {
+				checking_data.types.new_constant_type(Constant::BigInt(value))
+			} else {
+				TypeId::BIGINT_TYPE
+			}
+		}
 		TypeAnnotation::NumberLiteral(value, _) => {
 			let constant = Constant::Number(
 				f64::try_from(value.clone()).expect("big int number type").try_into().unwrap(),
@@ -76,21 +147,53 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 			checking_data.types.new_constant_type(Constant::Boolean(*value))
 		}
 		TypeAnnotation::Name(name, pos) => match name.as_str() {
-			"any" => TypeId::ANY_TYPE,
+			"any" => {
+				if checking_data.options.no_explicit_any {
+					checking_data.diagnostics_container.add_warning(TypeCheckWarning::ExplicitAny(
+						pos.with_source(environment.get_source()),
+					));
+				}
+				TypeId::ANY_TYPE
+			}
 			"this" => todo!(), // environment.get_value_of_this(&mut checking_data.types),
 			"self" => TypeId::ANY_INFERRED_FREE_THIS,
 			name => {
-				if let Some(ty) = environment.get_type_from_name(name) {
-					// Warn if it requires parameters. e.g. Array
-					if checking_data.types.get_type_by_id(ty).get_parameters().is_some() {
-						// TODO check defaults...
-						checking_data.diagnostics_container.add_error(
-							TypeCheckError::TypeNeedsTypeArguments(
-								name,
-								pos.with_source(environment.get_source()),
-							),
+				let ty = environment.get_type_from_name(name).or_else(|| {
+					checking_data.modules.global_declarations.named_types.get(name).copied()
+				});
+				let kind = ty.map_or(SemanticTokenKind::Unresolved, |ty| {
+					get_type_token_kind(ty, &checking_data.types)
+				});
+				checking_data.add_semantic_token(*pos, kind);
+
+				if let Some(ty) = ty {
+					let position = pos.with_source(environment.get_source());
+					warn_if_deprecated_type(name, ty, position, checking_data);
+					// Warn if it requires parameters (that do not have defaults). e.g. Array
+					if let Some(parameters) =
+						checking_data.types.get_type_by_id(ty).get_parameters()
+					{
+						let mut type_arguments = map_vec::Map::new();
+						let has_defaults = add_default_type_arguments(
+							&parameters,
+							&mut type_arguments,
+							position,
+							environment,
+							&mut checking_data.types,
 						);
-						TypeId::ERROR_TYPE
+						if has_defaults {
+							specialise_structure(
+								ty,
+								type_arguments,
+								environment,
+								&mut checking_data.types,
+							)
+						} else {
+							checking_data
+								.diagnostics_container
+								.add_error(TypeCheckError::TypeNeedsTypeArguments(name, position));
+							TypeId::ERROR_TYPE
+						}
 					} else {
 						ty
 					}
@@ -160,33 +263,55 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 			// 	_ => {}
 			// }
 
-			let Some(inner_type_id) = environment.get_type_from_name(name) else {
+			let name_position = Span { end: position.start + name.len() as u32, ..*position };
+			let inner_type_id = environment.get_type_from_name(name).or_else(|| {
+				checking_data.modules.global_declarations.named_types.get(name).copied()
+			});
+			let Some(inner_type_id) = inner_type_id else {
+				// Intrinsics (that are not declared in the definition file)
+				if let (Some(intrinsic), [argument]) =
+					(StringIntrinsic::from_name(name), arguments.as_slice())
+				{
+					let argument = synthesise_type_annotation(argument, environment, checking_data);
+					return new_string_intrinsic_type(
+						intrinsic,
+						argument,
+						&mut checking_data.types,
+					);
+				}
+				if let ("ReadonlyArray", [argument]) = (name.as_str(), arguments.as_slice()) {
+					let item = synthesise_type_annotation(argument, environment, checking_data);
+					let position = position.with_source(environment.get_source());
+					let array = checking_data.types.new_array_type(item, position);
+					return checking_data.types.new_readonly_type(array, position);
+				}
+				checking_data.add_semantic_token(name_position, SemanticTokenKind::Unresolved);
 				checking_data.diagnostics_container.add_error(TypeCheckError::CouldNotFindType(
 					name,
 					position.with_source(environment.get_source()),
 				));
 				return TypeId::ERROR_TYPE;
 			};
+			let kind = get_type_token_kind(inner_type_id, &checking_data.types);
+			checking_data.add_semantic_token(name_position, kind);
+
+			let position_with_source = position.with_source(environment.get_source());
+			warn_if_deprecated_type(name, inner_type_id, position_with_source, checking_data);
+
+			if inner_type_id == TypeId::NOMINAL_RESTRICTION {
+				return synthesise_nominal_type(
+					arguments,
+					position_with_source,
+					environment,
+					checking_data,
+				);
+			}
 
 			let inner_type = checking_data.types.get_type_by_id(inner_type_id);
 
 			// crate::utils::notify!("{:?}", inner_type);
 
 			if let Some(parameters) = inner_type.get_parameters() {
-				let is_flattenable_alias = if let Type::AliasTo { to, .. } = inner_type {
-					// Important that these wrappers are kept as there 'wrap' holds information
-					if matches!(
-						inner_type_id,
-						TypeId::LITERAL_RESTRICTION | TypeId::READONLY_RESTRICTION
-					) {
-						None
-					} else {
-						Some(*to)
-					}
-				} else {
-					None
-				};
-
 				let mut type_arguments: map_vec::Map<TypeId, (TypeId, SpanWithSource)> =
 					map_vec::Map::new();
 
@@ -257,23 +382,21 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 					type_arguments.insert(parameter, (argument, with_source));
 				}
 
-				// Eagerly specialise for type alias. TODO don't do for object types...
-				let mut arguments = StructureGenericArguments::ExplicitRestrictions(type_arguments);
-				if let Some(on) = is_flattenable_alias {
-					crate::types::substitute(
-						on,
-						&mut arguments,
-						environment,
-						&mut checking_data.types,
-					)
-				} else {
-					let ty = Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
-						on: inner_type_id,
-						arguments,
-					}));
+				// Parameters without an argument take their default
+				add_default_type_arguments(
+					&parameters,
+					&mut type_arguments,
+					position_with_source,
+					environment,
+					&mut checking_data.types,
+				);
 
-					checking_data.types.register_type(ty)
-				}
+				specialise_structure(
+					inner_type_id,
+					type_arguments,
+					environment,
+					&mut checking_data.types,
+				)
 			} else {
 				checking_data.diagnostics_container.add_error(
 					TypeCheckError::TypeHasNoGenericParameters(
@@ -313,41 +436,52 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 		TypeAnnotation::Readonly(type_annotation, pos) => {
 			let underlying_type =
 				synthesise_type_annotation(type_annotation, environment, checking_data);
-
-			let restrictions = Map::from_iter([(
-				TypeId::T_TYPE,
-				(underlying_type, pos.with_source(environment.get_source())),
-			)]);
-			let ty = Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
-				on: TypeId::READONLY_RESTRICTION,
-				arguments: StructureGenericArguments::ExplicitRestrictions(restrictions),
-			}));
-
-			checking_data.types.register_type(ty)
-
-			// let ty_to_be_readonly = checking_data.types.register_type(Type::AliasTo {
-			// 	to: underlying_type,
-			// 	name: None,
-			// 	parameters: None,
-			// });
-
-			// // TODO I think Readonly == freeze...?
-			// environment.frozen.insert(ty_to_be_readonly, TypeId::TRUE);
-
-			// ty_to_be_readonly)
+			let position = pos.with_source(environment.get_source());
+			checking_data.types.new_readonly_type(underlying_type, position)
+		}
+		TypeAnnotation::NamespacedName(namespace, member, pos) => {
+			let name = format!("{namespace}.{member}");
+			if let Some(ty) = environment.get_type_from_name(&name) {
+				ty
+			} else {
+				checking_data.diagnostics_container.add_error(TypeCheckError::CannotFindType(
+					&name,
+					pos.with_source(environment.get_source()),
+				));
+				TypeId::ERROR_TYPE
+			}
 		}
-		TypeAnnotation::NamespacedName(_, _, _) => unimplemented!(),
 		TypeAnnotation::ArrayLiteral(item_annotation, _) => {
 			let item_type = synthesise_type_annotation(item_annotation, environment, checking_data);
 			let position = item_annotation.get_position().with_source(environment.get_source());
 			checking_data.types.new_array_type(item_type, position)
 		}
+		// An object type with just a construct signature
 		TypeAnnotation::ConstructorLiteral {
-			type_parameters: _,
-			parameters: _,
-			return_type: _,
-			position: _,
-		} => unimplemented!(),
+			type_parameters,
+			parameters,
+			return_type,
+			position,
+		} => {
+			let position = position.with_source(environment.get_source());
+			let function_type = synthesise_function_annotation(
+				type_parameters,
+				parameters,
+				Some(return_type),
+				environment,
+				checking_data,
+				&position,
+				crate::features::functions::FunctionBehavior::Constructor {
+					non_super_prototype: None,
+					this_object_type: TypeId::ERROR_TYPE,
+				},
+			);
+			let ty = checking_data
+				.types
+				.register_type(Type::Object(crate::types::ObjectNature::AnonymousTypeAnnotation));
+			checking_data.types.set_construct_signature(ty, function_type);
+			ty
+		}
 		// Object literals are first turned into types as if they were interface declarations and then
 		// returns reference to object literal
 		TypeAnnotation::ObjectLiteral(members, _) => {
@@ -366,59 +500,45 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 			)
 			.0
 		}
-		TypeAnnotation::TupleLiteral(members, _) => {
-			// TODO maybe should be special type
-			let mut obj = ObjectBuilder::new(
-				Some(TypeId::ARRAY_TYPE),
-				&mut checking_data.types,
-				&mut environment.info,
-			);
+		TypeAnnotation::TupleLiteral(members, position) => {
+			let mut elements = Vec::with_capacity(members.len());
 
-			for (idx, (spread, member)) in members.iter().enumerate() {
-				// TODO binder name under data...?
-				match spread {
-					TupleElementKind::Standard => {
-						let type_annotation = match member {
-							AnnotationWithBinder::Annotated { ty, .. }
-							| AnnotationWithBinder::NoAnnotation(ty) => ty,
-						};
-
-						let item_ty =
-							synthesise_type_annotation(type_annotation, environment, checking_data);
-
-						let ty_position =
-							type_annotation.get_position().with_source(environment.get_source());
+			for (kind, member) in members {
+				let (label, type_annotation) = match member {
+					AnnotationWithBinder::Annotated { name, ty, .. } => (Some(name.clone()), ty),
+					AnnotationWithBinder::NoAnnotation(ty) => (None, ty),
+				};
+				let ty = synthesise_type_annotation(type_annotation, environment, checking_data);
 
-						obj.append(
-							environment,
-							Publicity::Public,
-							PropertyKey::from_usize(idx),
-							PropertyValue::Value(item_ty),
-							Some(ty_position),
-						);
-					}
-					TupleElementKind::Optional => {
-						todo!()
-					}
+				let kind = match kind {
+					TupleElementKind::Standard => tuples::TupleElementKind::Required,
+					TupleElementKind::Optional => tuples::TupleElementKind::Optional,
 					TupleElementKind::Spread => {
-						todo!();
+						// `[...Tuple]` flattens the elements of `Tuple`
+						if let Some(tuple) = tuples::get_tuple(ty, &checking_data.types) {
+							elements.extend(tuple.elements.iter().cloned());
+							continue;
+						}
+						let item = tuples::array_as_tuple(ty, &checking_data.types)
+							.and_then(|tuple| tuple.rest())
+							.unwrap_or(TypeId::ANY_TYPE);
+						elements.push(tuples::TupleElement {
+							ty: item,
+							kind: tuples::TupleElementKind::Rest,
+							label,
+						});
+						continue;
 					}
-				}
+				};
+				elements.push(tuples::TupleElement { ty, kind, label });
 			}
 
-			let constant = Constant::Number((members.len() as f64).try_into().unwrap());
-			let length_value = checking_data.types.new_constant_type(constant);
-
-			// TODO: Does `constant` have a position? Or should it have one?
-			obj.append(
+			tuples::new_tuple_type(
+				tuples::TupleType { elements },
+				position.with_source(environment.get_source()),
 				environment,
-				Publicity::Public,
-				PropertyKey::String("length".into()),
-				PropertyValue::Value(length_value),
-				None,
-			);
-
-			obj.build_object()
+				&mut checking_data.types,
+			)
 		}
 		TypeAnnotation::ParenthesizedReference(ref reference, _) => {
 			synthesise_type_annotation(reference, environment, checking_data)
@@ -467,7 +587,7 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 		}
 		// TODO these are all work in progress
 		TypeAnnotation::Decorated(decorator, inner, _) => {
-			crate::utils::notify!("Unknown decorator skipping {:#?}", decorator.name);
+			crate::utils::notify!(Warn; "Unknown decorator skipping {:#?}", decorator.name);
 			synthesise_type_annotation(inner, environment, checking_data)
 		}
 		TypeAnnotation::TemplateLiteral(parts, _) => {
@@ -489,7 +609,11 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 
 			synthesize_template_literal_type(parts, &mut checking_data.types)
 		}
-		TypeAnnotation::Symbol { .. } => todo!(),
+		TypeAnnotation::Symbol { unique: false, .. } => TypeId::SYMBOL_TYPE,
+		// Each annotation is a new symbol
+		TypeAnnotation::Symbol { unique: true, .. } => {
+			checking_data.types.new_constant_type(Constant::Symbol { key: String::new() })
+		}
 	};
 
 	checking_data
@@ -500,6 +624,28 @@ pub(super) fn synthesise_type_annotation<T: crate::ReadFromFS>(
 	ty
 }
 
+/// `Nominal<T, Name>` (where `Name` is a string literal)
+fn synthesise_nominal_type<T: crate::ReadFromFS>(
+	arguments: &[TypeAnnotation],
+	position: SpanWithSource,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) -> TypeId {
+	let [base, name] = arguments else {
+		checking_data.diagnostics_container.add_error(TypeCheckError::InvalidNominalType(position));
+		return TypeId::ERROR_TYPE;
+	};
+	let base = synthesise_type_annotation(base, environment, checking_data);
+	let name = synthesise_type_annotation(name, environment, checking_data);
+	if let Type::Constant(Constant::String(name)) = checking_data.types.get_type_by_id(name) {
+		let name = name.clone();
+		checking_data.types.new_nominal_type(base, name)
+	} else {
+		checking_data.diagnostics_container.add_error(TypeCheckError::InvalidNominalType(position));
+		TypeId::ERROR_TYPE
+	}
+}
+
 fn synthesise_type_condition<T: crate::ReadFromFS>(
 	condition: &TypeCondition,
 	environment: &mut Environment,
@@ -543,7 +689,7 @@ pub(crate) fn comment_as_type_annotation<T: crate::ReadFromFS>(
 			annotation.get_position().with_source(source),
 		))
 	} else {
-		crate::utils::notify!("Failed comment as type annotation");
+		crate::utils::notify!(Warn; "Failed comment as type annotation");
 		// TODO warning
 		None
 	}
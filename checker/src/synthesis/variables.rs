@@ -1,18 +1,28 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, convert::TryInto};
 
 use parser::{
-	declarations::VariableDeclarationItem, ASTNode, ArrayDestructuringField,
+	declarations::VariableDeclarationItem, ASTNode, ArrayDestructuringField, Expression,
 	ObjectDestructuringField, VariableField, VariableIdentifier,
 };
 
 use super::expressions::synthesise_expression;
 use crate::{
-	context::{information::Publicity, Context, ContextType, VariableRegisterArguments},
-	diagnostics::TypeCheckError,
-	features::variables::{get_new_register_argument_under, VariableMutability},
+	context::{
+		information::{get_properties_on_type, Publicity},
+		Context, ContextType, VariableRegisterArguments,
+	},
+	diagnostics::{TypeCheckError, TypeCheckWarning},
+	features::{
+		iteration::{get_iterated_items, IteratedItems},
+		objects::ObjectBuilder,
+		variables::{get_new_register_argument_under, VariableMutability},
+	},
 	synthesis::parser_property_key_to_checker_property_key,
-	types::properties::PropertyKey,
-	CheckingData, Environment, TypeId,
+	types::{
+		properties::{PropertyKey, PropertyValue},
+		remove_undefined, tuples,
+	},
+	CheckingData, Constant, Environment, TypeId,
 };
 
 pub(crate) fn register_variable_identifier<T: crate::ReadFromFS, V: ContextType>(
@@ -57,11 +67,25 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 		parser::VariableField::Array(items, _) => {
 			for (idx, field) in items.iter().enumerate() {
 				match field.get_ast_ref() {
-					ArrayDestructuringField::Spread(variable, _pos) => {
-						// TODO
+					ArrayDestructuringField::Spread(variable, pos) => {
+						// The remaining elements of a tuple
+						let space = argument.space.map(|space| {
+							let rest = tuples::get_tuple(space, &checking_data.types)
+								.map(|tuple| tuple.slice_from(idx));
+							if let Some(rest) = rest {
+								tuples::new_tuple_type(
+									rest,
+									pos.with_source(environment.get_source()),
+									environment,
+									&mut checking_data.types,
+								)
+							} else {
+								space
+							}
+						});
 						let argument = VariableRegisterArguments {
 							constant: argument.constant,
-							space: argument.space,
+							space,
 							initial_value: argument.initial_value,
 						};
 						register_variable(
@@ -72,7 +96,7 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 							argument,
 						);
 					}
-					ArrayDestructuringField::Name(name, _initial_value) => {
+					ArrayDestructuringField::Name(name, default_value) => {
 						// TODO account for spread in `idx`
 						let key = PropertyKey::from_usize(idx);
 						let argument = get_new_register_argument_under(
@@ -82,6 +106,12 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 							checking_data,
 							name.get_position(),
 						);
+						let argument = register_argument_with_default(
+							argument,
+							default_value.as_deref(),
+							environment,
+							checking_data,
+						);
 						register_variable(name, environment, checking_data, argument);
 					}
 					ArrayDestructuringField::Comment { .. } | ArrayDestructuringField::None => {}
@@ -91,7 +121,7 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 		parser::VariableField::Object(items, _) => {
 			for field in items {
 				match field.get_ast_ref() {
-					ObjectDestructuringField::Name(variable, ..) => {
+					ObjectDestructuringField::Name(variable, default_value, _) => {
 						let name = match variable {
 							VariableIdentifier::Standard(ref name, _) => name,
 							VariableIdentifier::Marker(_, _) => "?",
@@ -104,6 +134,12 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 							checking_data,
 							variable.get_position(),
 						);
+						let argument = register_argument_with_default(
+							argument,
+							default_value.as_deref(),
+							environment,
+							checking_data,
+						);
 						register_variable_identifier(
 							variable,
 							environment,
@@ -124,17 +160,15 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 							},
 						);
 					}
-					ObjectDestructuringField::Map {
-						from,
-						name,
-						default_value: _default_value,
-						position,
-					} => {
+					ObjectDestructuringField::Map { from, name, default_value, position } => {
+						// Declarations have their keys synthesised when their value is assigned
+						let perform_side_effect_computed =
+							!matches!(argument.initial_value, None | Some(TypeId::UNDEFINED_TYPE));
 						let key = parser_property_key_to_checker_property_key(
 							from,
 							environment,
 							checking_data,
-							false,
+							perform_side_effect_computed,
 						);
 						let argument = get_new_register_argument_under(
 							&argument,
@@ -143,6 +177,12 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 							checking_data,
 							*position,
 						);
+						let argument = register_argument_with_default(
+							argument,
+							default_value.as_deref(),
+							environment,
+							checking_data,
+						);
 						register_variable(name.get_ast_ref(), environment, checking_data, argument);
 					}
 				}
@@ -151,6 +191,110 @@ pub(crate) fn register_variable<T: crate::ReadFromFS>(
 	}
 }
 
+/// Removes `undefined` from the space and initial value of a destructured binding with a default value
+fn register_argument_with_default<T: crate::ReadFromFS>(
+	argument: VariableRegisterArguments,
+	default_value: Option<&Expression>,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) -> VariableRegisterArguments {
+	let Some(default_value) = default_value else {
+		return argument;
+	};
+
+	let space =
+		argument.space.map(|space| match remove_undefined(space, &mut checking_data.types) {
+			Some(defined) if defined != TypeId::NEVER_TYPE => defined,
+			_ => space,
+		});
+
+	let initial_value = match argument.initial_value {
+		// `var` declarations are hoisted as `undefined`. The default value is synthesised when
+		// the actual value is assigned
+		None | Some(TypeId::UNDEFINED_TYPE) => argument.initial_value,
+		Some(value) => Some(value_with_default(value, default_value, environment, checking_data)),
+	};
+
+	VariableRegisterArguments { constant: argument.constant, space, initial_value }
+}
+
+/// The default value is only synthesised if `value` can be `undefined`, in which case it replaces `undefined`
+fn value_with_default<T: crate::ReadFromFS>(
+	value: TypeId,
+	default_value: &Expression,
+	environment: &mut Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) -> TypeId {
+	let Some(defined) = remove_undefined(value, &mut checking_data.types) else {
+		return value;
+	};
+
+	let default_value =
+		synthesise_expression(default_value, environment, checking_data, TypeId::ANY_TYPE);
+
+	if defined == TypeId::NEVER_TYPE {
+		default_value
+	} else {
+		checking_data.types.new_or_type(defined, default_value)
+	}
+}
+
+/// For [`crate::TypeCheckOptions::no_unused_bindings`]. Records the names introduced by destructuring
+pub(crate) fn record_destructured_bindings<T: crate::ReadFromFS>(
+	field: &VariableField,
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, super::EznoParser>,
+) {
+	fn record_name<T: crate::ReadFromFS>(
+		name: &VariableIdentifier,
+		environment: &Environment,
+		checking_data: &mut CheckingData<T, super::EznoParser>,
+	) {
+		if let VariableIdentifier::Standard(name, position) = name {
+			let position = position.with_source(environment.get_source());
+			let id = crate::VariableId(position.source, position.start);
+			checking_data.destructured_bindings.declared.push((id, name.clone(), position));
+		}
+	}
+
+	if !checking_data.options.no_unused_bindings {
+		return;
+	}
+
+	match field {
+		VariableField::Name(_) => {}
+		VariableField::Array(items, _) => {
+			for item in items {
+				match item.get_ast_ref() {
+					ArrayDestructuringField::Spread(VariableField::Name(name), _)
+					| ArrayDestructuringField::Name(VariableField::Name(name), _) => {
+						record_name(name, environment, checking_data);
+					}
+					ArrayDestructuringField::Spread(field, _)
+					| ArrayDestructuringField::Name(field, _) => {
+						record_destructured_bindings(field, environment, checking_data);
+					}
+					ArrayDestructuringField::Comment { .. } | ArrayDestructuringField::None => {}
+				}
+			}
+		}
+		VariableField::Object(items, _) => {
+			for item in items {
+				match item.get_ast_ref() {
+					ObjectDestructuringField::Name(name, ..)
+					| ObjectDestructuringField::Spread(name, _) => {
+						record_name(name, environment, checking_data);
+					}
+					ObjectDestructuringField::Map { name, .. } => match name.get_ast_ref() {
+						VariableField::Name(name) => record_name(name, environment, checking_data),
+						field => record_destructured_bindings(field, environment, checking_data),
+					},
+				}
+			}
+		}
+	}
+}
+
 /// Name already been hoisted,
 ///
 /// TODO `U::as_option_expr()`
@@ -196,6 +340,9 @@ pub(super) fn synthesise_variable_declaration_item<
 	};
 
 	let item = variable_declaration.name.get_ast_ref();
+	if exported.is_none() {
+		record_destructured_bindings(item, environment, checking_data);
+	}
 	assign_initial_to_fields(item, environment, checking_data, value_ty, exported);
 }
 
@@ -209,10 +356,33 @@ fn assign_initial_to_fields<T: crate::ReadFromFS>(
 	match item {
 		VariableField::Name(name) => {
 			let get_position = name.get_position();
-			let id = crate::VariableId(environment.get_source(), get_position.start);
+			// A `var` redeclaration assigns to the first declaration
+			let id = if let VariableIdentifier::Standard(ref name, _) = name {
+				match environment.variables.get(name) {
+					Some(crate::features::variables::VariableOrImport::Variable {
+						declared_at,
+						..
+					}) => crate::VariableId(declared_at.source, declared_at.start),
+					_ => crate::VariableId(environment.get_source(), get_position.start),
+				}
+			} else {
+				crate::VariableId(environment.get_source(), get_position.start)
+			};
 			environment.register_initial_variable_declaration_value(id, value);
 
+			if value == TypeId::ERROR_TYPE && checking_data.options.no_error_any_propagation {
+				if let VariableIdentifier::Standard(ref name, _) = name {
+					checking_data.diagnostics_container.add_warning(
+						TypeCheckWarning::AnyFromError {
+							variable: name.clone(),
+							position: get_position.with_source(environment.get_source()),
+						},
+					);
+				}
+			}
+
 			if let Some(mutability) = exported {
+				let position = get_position.with_source(environment.get_source());
 				if let crate::Scope::Module { ref mut exported, .. } =
 					environment.context_type.scope
 				{
@@ -220,6 +390,7 @@ fn assign_initial_to_fields<T: crate::ReadFromFS>(
 						VariableIdentifier::Standard(ref name, _) => name.to_owned(),
 						VariableIdentifier::Marker(_, _) => "?".to_owned(),
 					};
+					exported.positions.push((name.clone(), position));
 					exported.named.push((name, (id, mutability)));
 				} else {
 					checking_data.diagnostics_container.add_error(
@@ -230,43 +401,120 @@ fn assign_initial_to_fields<T: crate::ReadFromFS>(
 				}
 			}
 		}
-		VariableField::Array(_items, pos) => {
-			checking_data.raise_unimplemented_error(
-				"destructuring array (needs iterator)",
-				pos.with_source(environment.get_source()),
-			);
-			// for (idx, item) in items.iter().enumerate() {
-			// 	match item.get_ast_ref() {
-			// 		ArrayDestructuringField::Spread(_, _) => todo!(),
-			// 		ArrayDestructuringField::Name(variable_field, _) => {
-			// 			let idx = PropertyKey::from_usize(idx);
-
-			// 			let value = environment.get_property(
-			// 				value,
-			// 				Publicity::Public,
-			// 				idx,
-			// 				&mut checking_data.types,
-			// 				None,
-			// 				*variable_field.get_position(),
-			// 				&checking_data.options,
-			// 			);
-
-			// 			if let Some((_, value)) = value {
-			// 				assign_to_fields(
-			// 					variable_field,
-			// 					environment,
-			// 					checking_data,
-			// 					value,
-			// 					exported,
-			// 				);
-			// 			}
-			// 		ArrayDestructuringField::Comment { .. } | ArrayDestructuringField::None => {}
-			//   }
+		VariableField::Array(items, pos) => {
+			let position = pos.with_source(environment.get_source());
+			let iterated = get_iterated_items(value, position, environment, checking_data);
+			let tuple = tuples::get_tuple(value, &checking_data.types).cloned();
+
+			for (idx, item) in items.iter().enumerate() {
+				match item.get_ast_ref() {
+					ArrayDestructuringField::Spread(variable_field, spread_position) => {
+						let spread_position = spread_position.with_source(environment.get_source());
+						let rest = match (&tuple, &iterated) {
+							(Some(tuple), _) => tuples::new_tuple_type(
+								tuple.slice_from(idx),
+								spread_position,
+								environment,
+								&mut checking_data.types,
+							),
+							(None, IteratedItems::Known(values)) => {
+								let mut basis = ObjectBuilder::new(
+									Some(TypeId::ARRAY_TYPE),
+									&mut checking_data.types,
+									&mut environment.info,
+								);
+								let rest = values.get(idx..).unwrap_or_default();
+								for (idx, value) in rest.iter().enumerate() {
+									basis.append(
+										environment,
+										Publicity::Public,
+										PropertyKey::from_usize(idx),
+										PropertyValue::Value(*value),
+										Some(spread_position),
+									);
+								}
+								let length = checking_data.types.new_constant_type(
+									Constant::Number((rest.len() as f64).try_into().unwrap()),
+								);
+								basis.append(
+									environment,
+									Publicity::Public,
+									PropertyKey::String("length".into()),
+									PropertyValue::Value(length),
+									None,
+								);
+								basis.build_object()
+							}
+							(None, IteratedItems::Unknown(item)) => {
+								checking_data.types.new_array_type(*item, spread_position)
+							}
+						};
+						assign_initial_to_fields(
+							variable_field,
+							environment,
+							checking_data,
+							rest,
+							exported,
+						);
+					}
+					ArrayDestructuringField::Name(variable_field, default_value) => {
+						let value = match (&tuple, &iterated) {
+							(Some(tuple), _) => tuple.get_element(idx, &mut checking_data.types),
+							(None, IteratedItems::Known(values)) => {
+								values.get(idx).copied().unwrap_or(TypeId::UNDEFINED_TYPE)
+							}
+							(None, IteratedItems::Unknown(item)) => *item,
+						};
+						let value = if let Some(default_value) = default_value {
+							value_with_default(value, default_value, environment, checking_data)
+						} else {
+							value
+						};
+						assign_initial_to_fields(
+							variable_field,
+							environment,
+							checking_data,
+							value,
+							exported,
+						);
+					}
+					ArrayDestructuringField::Comment { .. } | ArrayDestructuringField::None => {}
+				}
+			}
 		}
 		VariableField::Object(items, _) => {
+			// Keys of the previous fields, which are not part of the rest. (the rest must be last)
+			let mut taken = Vec::new();
+
 			for item in items {
 				match item.get_ast_ref() {
-					ObjectDestructuringField::Spread(_, _) => todo!(),
+					ObjectDestructuringField::Spread(name, position) => {
+						let id =
+							crate::VariableId(environment.get_source(), name.get_position().start);
+
+						let properties =
+							get_properties_on_type(value, &checking_data.types, environment);
+
+						let mut rest = ObjectBuilder::new(
+							None,
+							&mut checking_data.types,
+							&mut environment.info,
+						);
+						for (publicity, key, value) in properties {
+							if publicity == Publicity::Public && !taken.contains(&key) {
+								rest.append(
+									environment,
+									Publicity::Public,
+									key,
+									PropertyValue::Value(value),
+									Some(position.with_source(environment.get_source())),
+								);
+							}
+						}
+
+						environment
+							.register_initial_variable_declaration_value(id, rest.build_object());
+					}
 					ObjectDestructuringField::Name(name, default_value, _) => {
 						let id =
 							crate::VariableId(environment.get_source(), name.get_position().start);
@@ -289,22 +537,21 @@ fn assign_initial_to_fields<T: crate::ReadFromFS>(
 							name.get_position(),
 							&checking_data.options,
 						);
-						let value = match property {
-							Some((_, value)) => value,
-							None => {
-								// TODO non decidable error
-								if let Some(else_expression) = default_value {
-									synthesise_expression(
-										else_expression,
-										environment,
-										checking_data,
-										TypeId::ANY_TYPE,
-									)
-								} else {
-									// TODO emit error
-									TypeId::ERROR_TYPE
-								}
+						taken.push(key_ty);
+						let value = match (property, default_value) {
+							(Some((_, value)), None) => value,
+							(Some((_, value)), Some(default_value)) => {
+								value_with_default(value, default_value, environment, checking_data)
 							}
+							// TODO non decidable error
+							(None, Some(default_value)) => synthesise_expression(
+								default_value,
+								environment,
+								checking_data,
+								TypeId::ANY_TYPE,
+							),
+							// TODO emit error
+							(None, None) => TypeId::ERROR_TYPE,
 						};
 
 						environment.register_initial_variable_declaration_value(id, value);
@@ -329,23 +576,22 @@ fn assign_initial_to_fields<T: crate::ReadFromFS>(
 							*position,
 							&checking_data.options,
 						);
+						taken.push(key_ty);
 
-						let value = match property_value {
-							Some((_, value)) => value,
-							None => {
-								// TODO non decidable error
-								if let Some(default_value) = default_value {
-									synthesise_expression(
-										default_value,
-										environment,
-										checking_data,
-										TypeId::ANY_TYPE,
-									)
-								} else {
-									// TODO emit error
-									TypeId::ERROR_TYPE
-								}
+						let value = match (property_value, default_value) {
+							(Some((_, value)), None) => value,
+							(Some((_, value)), Some(default_value)) => {
+								value_with_default(value, default_value, environment, checking_data)
 							}
+							// TODO non decidable error
+							(None, Some(default_value)) => synthesise_expression(
+								default_value,
+								environment,
+								checking_data,
+								TypeId::ANY_TYPE,
+							),
+							// TODO emit error
+							(None, None) => TypeId::ERROR_TYPE,
 						};
 
 						assign_initial_to_fields(
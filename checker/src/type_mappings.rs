@@ -5,7 +5,7 @@ use source_map::{SourceId, SpanWithSource};
 use super::range_map::RangeMap;
 
 use crate::{
-	features::variables::VariableWithValue,
+	features::{semantic_tokens::SemanticTokenKind, variables::VariableWithValue},
 	types::{TypeId, TypeStore},
 	GeneralContext, VariableId,
 };
@@ -31,6 +31,8 @@ pub struct TypeMappings {
 	pub variable_restrictions: HashMap<(SourceId, u32), (TypeId, SpanWithSource)>,
 	/// Temp
 	pub special_expressions: RangeMap<SpecialExpressions>,
+	/// See [`crate::features::semantic_tokens`]
+	pub semantic_tokens: RangeMap<SemanticTokenKind>,
 }
 
 #[derive(Debug)]
@@ -3,24 +3,28 @@ use std::vec;
 
 use crate::{
 	context::{
-		information::InformationChain,
+		information::{get_property_unbound, InformationChain},
 		invocation::{CheckThings, InvocationContext},
 		CallCheckingBehavior, Environment, Logical, Missing, PossibleLogical,
 	},
 	diagnostics::{TypeCheckError, TypeStringRepresentation, TDZ},
-	events::{application::ErrorsAndInfo, apply_event, ApplicationResult, Event, RootReference},
+	events::{
+		application::ErrorsAndInfo, apply_event, ApplicationResult, CallingTiming, Event,
+		FinalEvent, RootReference,
+	},
 	features::{
 		constant_functions::{
 			call_constant_function, CallSiteTypeArguments, ConstantFunctionError, ConstantOutput,
 		},
 		functions::{ClosedOverVariables, FunctionBehavior, ThisValue},
+		iteration::{get_iterated_items, IteratedItems},
 		objects::{ObjectBuilder, SpecialObjects},
 	},
 	subtyping::{type_is_subtype, type_is_subtype_with_generics, BasicEquality, SubTypeResult},
 	types::{
-		functions::SynthesisedArgument, get_structure_arguments_based_on_object_constraint,
-		substitute, FunctionEffect, FunctionType, GenericChainLink, ObjectNature,
-		StructureGenerics, Type,
+		functions::{SynthesisedArgument, SynthesisedParameters},
+		get_structure_arguments_based_on_object_constraint, substitute, FunctionEffect,
+		FunctionType, GenericChainLink, ObjectNature, StructureGenerics, Type,
 	},
 	FunctionId, GenericTypeParameters, ReadFromFS, SpecialExpressions, TypeId,
 };
@@ -89,7 +93,7 @@ pub fn call_type_handle_errors<T: crate::ReadFromFS, A: crate::ASTImplementation
 						environment,
 						&checking_data.types,
 					) {
-						crate::utils::notify!("Here :)");
+						crate::utils::notify!(Trace; "Here :)");
 						Some(args)
 					} else {
 						let prototype = environment
@@ -100,7 +104,7 @@ pub fn call_type_handle_errors<T: crate::ReadFromFS, A: crate::ASTImplementation
 						if prototype.is_some_and(|prototype| {
 							checking_data.types.lookup_generic_map.contains_key(&prototype)
 						}) {
-							crate::utils::notify!("Registering lookup for calling");
+							crate::utils::notify!(Trace; "Registering lookup for calling");
 
 							Some(StructureGenericArguments::LookUp { on: this_passed })
 						} else {
@@ -122,6 +126,7 @@ pub fn call_type_handle_errors<T: crate::ReadFromFS, A: crate::ASTImplementation
 				environment,
 				checking_data,
 			);
+			let arguments = expand_spread_arguments(arguments, environment, checking_data);
 
 			let mut check_things = CheckThings { debug_types: checking_data.options.debug_types };
 			let result = call_logical(
@@ -154,6 +159,17 @@ pub fn call_type_handle_errors<T: crate::ReadFromFS, A: crate::ASTImplementation
 						);
 					}
 
+					// Code after calling a function that returns `never` is unreachable. Functions
+					// that unconditionally throw have already ended control flow from their events
+					// (the result of calling a dependent function is an image with a `never` constraint)
+					let returns_never =
+						crate::types::get_constraint(returned_type, &checking_data.types)
+							.unwrap_or(returned_type)
+							== TypeId::NEVER_TYPE;
+					if returns_never && !environment.context_type.state.is_it_so_over() {
+						environment.throw_value(TypeId::NEVER_TYPE, call_site);
+					}
+
 					(returned_type, special)
 				}
 				Err(errors) => {
@@ -220,13 +236,23 @@ fn get_logical_callable_from_type(
 	// crate::utils::notify!("ty1={:?} ({:?})", le_ty, ty);
 
 	match le_ty {
-		Type::Class { .. } | Type::Interface { .. } | Type::Constant(_) | Type::Object(_) => {
-			Err(Missing::None)
+		Type::Interface { .. } | Type::Object(_) => {
+			// `new (...): T` members
+			if let Some(function) = types.get_construct_signature(ty) {
+				let from = Some(from.unwrap_or(ty));
+				Ok(Logical::Pure(FunctionLike { from, function, this_value: ThisValue::UseParent }))
+			} else {
+				Err(Missing::None)
+			}
 		}
+		Type::Class { .. } | Type::Constant(_) => Err(Missing::None),
 		Type::And(_, _) => todo!(),
 		Type::Or(left, right) => {
 			let left = get_logical_callable_from_type(*left, on, from, types);
 			let right = get_logical_callable_from_type(*right, on, from, types);
+			if let (Err(Missing::None), Err(Missing::None)) = (&left, &right) {
+				return Err(Missing::None);
+			}
 			Ok(Logical::Or {
 				based_on: TypeId::BOOLEAN_TYPE,
 				left: Box::new(left),
@@ -266,7 +292,7 @@ fn get_logical_callable_from_type(
 		},
 		Type::Constructor(Constructor::StructureGenerics(generic)) => {
 			get_logical_callable_from_type(generic.on, on, from, types).map(|res| {
-				crate::utils::notify!("Calling found {:?}", generic.arguments);
+				crate::utils::notify!(Trace; "Calling found {:?}", generic.arguments);
 				Logical::Implies { on: Box::new(res), antecedent: generic.arguments.clone() }
 			})
 		}
@@ -274,7 +300,7 @@ fn get_logical_callable_from_type(
 			// bind_this from #98
 			// Bind does not happen for theses calls, so done here *conditionally on `bind_this`*
 
-			crate::utils::notify!("Passing {:?}", on);
+			crate::utils::notify!(Trace; "Passing {:?}", on);
 
 			let result = get_logical_callable_from_type(
 				*result,
@@ -299,7 +325,7 @@ fn get_logical_callable_from_type(
 		}
 		Type::RootPolyType(_) | Type::Constructor(_) => {
 			let constraint = get_constraint(ty, types).unwrap();
-			crate::utils::notify!("Calling constructor / root poly type! {:?}", constraint);
+			crate::utils::notify!(Trace; "Calling constructor / root poly type! {:?}", constraint);
 			get_logical_callable_from_type(constraint, on, Some(ty), types)
 		}
 	}
@@ -374,20 +400,22 @@ fn call_logical<E: CallCheckingBehavior>(
 				// is poly
 				if matches!(
 					function_type.effect,
-					FunctionEffect::Unknown | FunctionEffect::InputOutput(..)
+					FunctionEffect::Unknown
+						| FunctionEffect::InputOutput(..)
+						| FunctionEffect::Declared { .. }
 				) || (matches!(function_type.effect, FunctionEffect::Constant(..))
 					&& !result.result_was_const_computation)
 				{
 					let on = function.from.unwrap();
 
-					// if function_type.effect
 					// This should be okay, constant or IO functions don't mutate their arguments...?
-					if !matches!(
+					// Declared effects say which arguments are mutated
+					find_possible_mutations(
+						&arguments,
 						&function_type.effect,
-						FunctionEffect::Constant(..) | FunctionEffect::InputOutput(..)
-					) {
-						find_possible_mutations(&arguments, types, top_environment);
-					}
+						types,
+						top_environment,
+					);
 
 					let with = arguments.clone().into_boxed_slice();
 					let reflects_dependency = if is_type_constant(result.returned_type, types) {
@@ -420,8 +448,46 @@ fn call_logical<E: CallCheckingBehavior>(
 				panic!()
 			}
 		}
-		Logical::Or { .. } => {
-			todo!("environment conditional?");
+		Logical::Or { left, right, based_on: _ } => {
+			// Each side is called. Sides that are not callable (such as `undefined` from an optional
+			// parameter that has been checked) are skipped. At least one side is callable
+			let mut result: Option<FunctionCallResult> = None;
+			for side in [*left, *right] {
+				let side_result = match side {
+					Ok(logical) => call_logical(
+						logical,
+						called_with_new,
+						call_site,
+						explicit_type_arguments.clone(),
+						structure_generics.clone(),
+						arguments.clone(),
+						top_environment,
+						types,
+						behavior,
+					)?,
+					Err(Missing::None) => continue,
+					Err(Missing::Error) => FunctionCallResult::from_type(TypeId::ERROR_TYPE),
+					Err(Missing::Infer { .. }) => FunctionCallResult::from_type(TypeId::ANY_TYPE),
+				};
+				result = Some(match result {
+					Some(mut existing) => FunctionCallResult {
+						called: existing
+							.called
+							.filter(|called| Some(*called) == side_result.called),
+						returned_type: types
+							.new_or_type(existing.returned_type, side_result.returned_type),
+						warnings: {
+							existing.warnings.extend(side_result.warnings);
+							existing.warnings
+						},
+						special: None,
+						result_was_const_computation: existing.result_was_const_computation
+							&& side_result.result_was_const_computation,
+					},
+					None => side_result,
+				});
+			}
+			Ok(result.unwrap_or_else(|| FunctionCallResult::from_type(TypeId::ERROR_TYPE)))
 		}
 		Logical::Implies { on, antecedent } => call_logical(
 			*on,
@@ -439,11 +505,17 @@ fn call_logical<E: CallCheckingBehavior>(
 }
 
 fn find_possible_mutations(
-	arguments: &Vec<SynthesisedArgument>,
+	arguments: &[SynthesisedArgument],
+	effect: &FunctionEffect,
 	types: &mut TypeStore,
 	top_environment: &mut Environment,
 ) {
-	for argument in arguments {
+	for (idx, argument) in arguments.iter().enumerate() {
+		// Spread arguments could be in any position
+		if !(argument.spread || effect.may_mutate_argument(idx)) {
+			continue;
+		}
+
 		// TODO need to do in a function
 		// All properties
 		// Functions free variables etc
@@ -456,22 +528,22 @@ fn find_possible_mutations(
 			| Type::Object(ObjectNature::AnonymousTypeAnnotation)
 			| Type::FunctionReference(_)
 			| Type::Or(_, _) => {
-				crate::utils::notify!("Unreachable");
+				crate::utils::notify!(Trace; "Unreachable");
 			}
 			Type::Constant(_) => {}
 			Type::RootPolyType(_) | Type::Constructor(_) => {
 				// All dependent anyway
-				crate::utils::notify!("TODO if any properties set etc");
+				crate::utils::notify!(Trace; "TODO if any properties set etc");
 			}
 			Type::SpecialObject(SpecialObjects::Function(_, _)) => {
-				crate::utils::notify!("TODO record that function could be called");
+				crate::utils::notify!(Trace; "TODO record that function could be called");
 			}
 			Type::Object(ObjectNature::RealDeal) => {
 				top_environment.possibly_mutated_objects.insert(argument.value);
-				crate::utils::notify!("TODO record methods could be called here as well");
+				crate::utils::notify!(Trace; "TODO record methods could be called here as well");
 			}
 			Type::SpecialObject(_) => {
-				crate::utils::notify!("TODO record stuff if mutable");
+				crate::utils::notify!(Trace; "TODO record stuff if mutable");
 			}
 		}
 	}
@@ -485,6 +557,8 @@ pub enum FunctionCallingError {
 		argument_position: SpanWithSource,
 		parameter_position: SpanWithSource,
 		restriction: Option<(SpanWithSource, TypeStringRepresentation)>,
+		/// For parameters with a generic type that is inferred (rather than set by `restriction`)
+		inferred: Option<InferredGeneric>,
 	},
 	MissingArgument {
 		parameter_position: SpanWithSource,
@@ -494,6 +568,11 @@ pub enum FunctionCallingError {
 		count: usize,
 		position: SpanWithSource,
 	},
+	/// A spread argument of unknown length is passed to a required parameter
+	SpreadMayNotCoverParameter {
+		parameter_position: SpanWithSource,
+		argument_position: SpanWithSource,
+	},
 	NotCallable {
 		calling: TypeStringRepresentation,
 		call_site: SpanWithSource,
@@ -511,6 +590,12 @@ pub enum FunctionCallingError {
 		/// Should be set
 		call_site: Option<SpanWithSource>,
 	},
+	/// The function assigns to a (free) variable that has not been declared yet
+	AssignmentTDZ {
+		error: TDZ,
+		/// Should be set
+		call_site: Option<SpanWithSource>,
+	},
 	SetPropertyConstraint {
 		property_type: TypeStringRepresentation,
 		value_type: TypeStringRepresentation,
@@ -526,6 +611,14 @@ pub enum FunctionCallingError {
 	},
 }
 
+/// Why an argument for a parameter with a generic type (`T`) is invalid
+pub struct InferredGeneric {
+	/// The constraint of `T` that the argument does not meet
+	pub constraint: TypeStringRepresentation,
+	/// The type `T` was inferred as from an earlier argument and the position of that argument
+	pub inferred_from: Option<(TypeStringRepresentation, SpanWithSource)>,
+}
+
 pub struct InfoDiagnostic(pub String);
 
 /// TODO *result* name bad
@@ -540,6 +633,19 @@ pub struct FunctionCallResult {
 	// pub unconditional_exception: Option<TypeId>,
 }
 
+impl FunctionCallResult {
+	/// For a call that is not to a known function
+	fn from_type(returned_type: TypeId) -> Self {
+		Self {
+			called: None,
+			returned_type,
+			warnings: Vec::new(),
+			special: None,
+			result_was_const_computation: false,
+		}
+	}
+}
+
 #[derive(Debug, Default, Clone, Copy, binary_serialize_derive::BinarySerializable)]
 pub enum CalledWithNew {
 	New {
@@ -572,7 +678,7 @@ impl FunctionType {
 	) -> Result<FunctionCallResult, Vec<FunctionCallingError>> {
 		// TODO check that parameters vary
 		if behavior.in_recursive_cycle(self.id) {
-			crate::utils::notify!("Encountered recursion");
+			crate::utils::notify!(Trace; "Encountered recursion");
 			return Ok(FunctionCallResult {
 				called: Some(self.id),
 				returned_type: TypeId::ERROR_TYPE,
@@ -591,20 +697,35 @@ impl FunctionType {
 
 		if let (FunctionEffect::Constant(ref const_fn_ident), true) = (&self.effect, call_constant)
 		{
+			if let Some(result) = call_function_prototype_method(
+				const_fn_ident,
+				this_value,
+				call_site,
+				arguments,
+				environment,
+				behavior,
+				types,
+			) {
+				return result;
+			}
+
 			let has_dependent_argument =
 				arguments.iter().any(|arg| types.get_type_by_id(arg.value).is_dependent());
 
 			// || matches!(this_value, ThisValue::Passed(ty) if types.get_type_by_id(ty).is_dependent());
 
-			let call_anyway = matches!(
-				const_fn_ident.as_str(),
-				"debug_type"
-					| "debug_type_rust" | "print_type"
-					| "print_and_debug_type"
-					| "debug_effects" | "debug_effects_rust"
-					| "satisfies" | "is_dependent"
-					| "bind" | "create_proxy"
-			);
+			let call_anyway =
+				matches!(
+					const_fn_ident.as_str(),
+					"debug_type"
+						| "debug_type_rust"
+						| "print_type" | "print_and_debug_type"
+						| "debug_effects" | "debug_effects_rust"
+						| "satisfies" | "is_dependent"
+						| "bind" | "create_proxy"
+						| "create_symbol" | "array_constructor"
+						| "array_of"
+				);
 
 			// TODO just for debugging. These have their constant things called every time AND queue an event
 			let is_independent = const_fn_ident.ends_with("independent");
@@ -655,7 +776,7 @@ impl FunctionType {
 						return Err(vec![item]);
 					}
 					Err(ConstantFunctionError::BadCall) => {
-						crate::utils::notify!(
+						crate::utils::notify!(Trace;
 							"Constant function calling failed, non constant params"
 						);
 					}
@@ -677,7 +798,7 @@ impl FunctionType {
 				);
 
 				if let Err(ref _err) = call {
-					crate::utils::notify!("Calling function with dependent argument failed");
+					crate::utils::notify!(Trace; "Calling function with dependent argument failed");
 				}
 
 				let result = call?.returned_type;
@@ -707,7 +828,7 @@ impl FunctionType {
 			call_site,
 		);
 
-		let local_arguments = self.assign_arguments_to_parameters::<E>(
+		let mut local_arguments = self.assign_arguments_to_parameters::<E>(
 			arguments,
 			type_arguments,
 			call_site_type_arguments,
@@ -719,6 +840,19 @@ impl FunctionType {
 			behavior,
 		);
 
+		// The arguments of the function a mixin class was created in, for the dependent `super` call
+		if let (
+			FunctionBehavior::Constructor { .. },
+			Some(StructureGenericArguments::ExplicitRestrictions(restrictions)),
+		) = (&self.behavior, &structure_generics)
+		{
+			for (on, (argument, _)) in restrictions.iter() {
+				if !local_arguments.contains_key(on) {
+					local_arguments.insert(*on, *argument);
+				}
+			}
+		}
+
 		let mut type_arguments = FunctionTypeArguments {
 			local_arguments,
 			closure_ids: if let Some(StructureGenericArguments::Closure(cs)) = structure_generics {
@@ -780,13 +914,13 @@ impl FunctionType {
 		} else {
 			type_arguments.local_arguments.remove(&TypeId::NEW_TARGET_ARG);
 
-			crate::utils::notify!("Substituting return type (no return) {:?}", type_arguments);
+			crate::utils::notify!(Trace; "Substituting return type (no return) {:?}", type_arguments);
 
 			substitute(self.return_type, &mut type_arguments, environment, types)
 		};
 
 		if !errors.errors.is_empty() {
-			crate::utils::notify!("Got {} application errors", errors.errors.len());
+			crate::utils::notify!(Trace; "Got {} application errors", errors.errors.len());
 			return Err(errors.errors);
 		}
 
@@ -810,19 +944,19 @@ impl FunctionType {
 					});
 				}
 				FunctionBehavior::Constructor { non_super_prototype: _, this_object_type } => {
-					crate::utils::notify!("Registered this {:?}", this_object_type);
-					let new_instance_type = type_arguments
-						.local_arguments
-						.remove(&this_object_type)
-						.expect("no this argument?");
-
-					return Ok(FunctionCallResult {
-						returned_type: new_instance_type,
-						warnings: errors.warnings,
-						called: Some(self.id),
-						special: None,
-						result_was_const_computation: false,
-					});
+					crate::utils::notify!(Trace; "Registered this {:?}", this_object_type);
+					// Construct signatures (which have no body) return their return type
+					if let Some(new_instance_type) =
+						type_arguments.local_arguments.remove(&this_object_type)
+					{
+						return Ok(FunctionCallResult {
+							returned_type: new_instance_type,
+							warnings: errors.warnings,
+							called: Some(self.id),
+							special: None,
+							result_was_const_computation: false,
+						});
+					}
 				}
 			}
 		}
@@ -878,6 +1012,7 @@ impl FunctionType {
 			// Adjust call sites. (because they aren't currently passed down)
 			for d in &mut errors.errors[current_errors..] {
 				if let FunctionCallingError::TDZ { call_site: ref mut c, .. }
+				| FunctionCallingError::AssignmentTDZ { call_site: ref mut c, .. }
 				| FunctionCallingError::SetPropertyConstraint {
 					call_site: ref mut c, ..
 				}
@@ -901,7 +1036,7 @@ impl FunctionType {
 		}
 
 		if let Some(closure_id) = this_closure_id {
-			crate::utils::notify!("Setting closure variables");
+			crate::utils::notify!(Trace; "Setting closure variables");
 
 			// Set closed over values
 			// TODO `this`
@@ -912,7 +1047,7 @@ impl FunctionType {
 					.closure_current_values
 					.insert((closure_id, RootReference::Variable(*variable)), value);
 
-				crate::utils::notify!("in {:?} set {:?} to {:?}", closure_id, variable, value);
+				crate::utils::notify!(Trace; "in {:?} set {:?} to {:?}", closure_id, variable, value);
 			});
 		}
 
@@ -937,13 +1072,13 @@ impl FunctionType {
 				let value_of_this = if let Some(value) = this_value.get_passed() {
 					value
 				} else {
-					crate::utils::notify!(
+					crate::utils::notify!(Trace;
 						"method has no 'this' passed :?. Passing `undefined` here"
 					);
 					TypeId::UNDEFINED_TYPE
 				};
 
-				crate::utils::notify!(
+				crate::utils::notify!(Trace;
 					"free this id {:?} & value of this {:?}",
 					free_this_id,
 					value_of_this
@@ -954,7 +1089,7 @@ impl FunctionType {
 			FunctionBehavior::Function { is_async: _, is_generator: _, free_this_id } => {
 				match called_with_new {
 					CalledWithNew::New { on: _ } => {
-						crate::utils::notify!("TODO set prototype");
+						crate::utils::notify!(Trace; "TODO set prototype");
 						// if let Some(prototype) = non_super_prototype {
 						// let this_ty = environment.create_this(prototype, types);
 						let value_of_this =
@@ -974,7 +1109,7 @@ impl FunctionType {
 				}
 			}
 			FunctionBehavior::Constructor { non_super_prototype: _, this_object_type } => {
-				crate::utils::notify!("Here {:?}", called_with_new);
+				crate::utils::notify!(Trace; "Here {:?}", called_with_new);
 				match called_with_new {
 					CalledWithNew::None => {
 						errors
@@ -985,7 +1120,7 @@ impl FunctionType {
 						// TODO is this okay?
 					}
 					CalledWithNew::SpecialSuperCall { this_type } => {
-						crate::utils::notify!(
+						crate::utils::notify!(Trace;
 							"Setting this_object {:?} to {:?}",
 							this_object_type,
 							this_type
@@ -1000,7 +1135,7 @@ impl FunctionType {
 			let new_target_value = match called_with_new {
 				CalledWithNew::New { on } => on,
 				CalledWithNew::SpecialSuperCall { .. } => {
-					crate::utils::notify!("Get this type for super new.target");
+					crate::utils::notify!(Trace; "Get this type for super new.target");
 					TypeId::ERROR_TYPE
 					// let ty = this_value.0;
 					// let on = crate::types::printing::print_type(
@@ -1033,60 +1168,57 @@ impl FunctionType {
 		call_site: source_map::BaseSpan<SourceId>,
 		behavior: &E,
 	) -> TypeArguments {
-		for (parameter_idx, parameter) in self.parameters.parameters.iter().enumerate() {
-			// TODO temp
+		// After a spread argument (of unknown length) the indexes of arguments are unknown
+		let first_spread = arguments.iter().position(|argument| argument.spread);
+		// Only raise one error for required parameters that a spread might not cover
+		let mut reported_uncovered = false;
+		// The position of the argument each generic was first inferred from
+		let mut inferred_from = map_vec::Map::new();
 
-			// This handles if the argument is missing but allowing elided arguments
-			let argument = arguments.get(parameter_idx);
+		for (parameter_idx, parameter) in self.parameters.parameters.iter().enumerate() {
+			// A spread argument covers all the following parameters
+			let argument = match first_spread {
+				Some(spread_idx) if parameter_idx >= spread_idx => arguments.get(spread_idx),
+				_ => arguments.get(parameter_idx),
+			};
 
-			if let Some(SynthesisedArgument { spread, value, position }) = argument {
-				if *spread {
-					crate::utils::notify!("TODO spread arguments");
-				}
+			if let Some(argument) = argument {
+				let spread_argument;
+				let argument = if argument.spread {
+					if parameter.is_optional {
+						// The spread might not reach this parameter
+						let value = types.new_or_type(argument.value, TypeId::UNDEFINED_TYPE);
+						spread_argument = SynthesisedArgument { value, ..argument.clone() };
+						&spread_argument
+					} else {
+						if E::CHECK_PARAMETERS && !reported_uncovered {
+							errors.errors.push(FunctionCallingError::SpreadMayNotCoverParameter {
+								parameter_position: parameter.position,
+								argument_position: argument.position,
+							});
+							reported_uncovered = true;
+						}
+						argument
+					}
+				} else {
+					argument
+				};
 
 				if E::CHECK_PARAMETERS {
-					let result = check_parameter_type(
-						parameter.ty,
+					check_argument(
+						(parameter.ty, parameter.ty, parameter.position),
+						argument,
 						call_site_type_arguments.as_ref(),
 						parent,
-						*value,
-						&mut type_arguments,
+						(&mut type_arguments, &mut inferred_from),
 						environment,
 						types,
+						errors,
+						behavior,
 					);
-
-					if let SubTypeResult::IsNotSubType(_reasons) = result {
-						let type_arguments = Some(GenericChainLink::FunctionRoot {
-							parent,
-							call_site_type_arguments: call_site_type_arguments.as_ref(),
-							type_arguments: &type_arguments,
-						});
-
-						// crate::utils::notify!("Type arguments are {:?}", type_arguments);
-
-						errors.errors.push(FunctionCallingError::InvalidArgumentType {
-							parameter_type: TypeStringRepresentation::from_type_id_with_generics(
-								parameter.ty,
-								type_arguments,
-								environment,
-								types,
-								behavior.debug_types(),
-							),
-							argument_type: TypeStringRepresentation::from_type_id_with_generics(
-								*value,
-								type_arguments,
-								environment,
-								types,
-								behavior.debug_types(),
-							),
-							parameter_position: parameter.position,
-							argument_position: *position,
-							restriction: None,
-						});
-					}
 				} else {
 					// Already checked so can set
-					type_arguments.insert(parameter.ty, *value);
+					type_arguments.insert(parameter.ty, argument.value);
 				}
 			} else if parameter.is_optional {
 				type_arguments.insert(parameter.ty, TypeId::UNDEFINED_TYPE);
@@ -1098,95 +1230,162 @@ impl FunctionType {
 			}
 		}
 
+		let parameters_count = self.parameters.parameters.len();
+		// The spread argument (if it covered parameters) is also part of the rest
+		let rest_start = first_spread
+			.map_or(parameters_count, |idx| idx.min(parameters_count))
+			.min(arguments.len());
+		let rest_arguments = &arguments[rest_start..];
+
 		// Spread parameters here
-		if self.parameters.parameters.len() < arguments.len() {
-			if let Some(ref rest_parameter) = self.parameters.rest_parameter {
-				// TODO reuse synthesise_array literal logic (especially for spread items)
-				let mut basis =
-					ObjectBuilder::new(Some(TypeId::ARRAY_TYPE), types, &mut environment.info);
-
-				let mut count = 0;
-
-				for argument in arguments.iter().skip(self.parameters.parameters.len()) {
-					if E::CHECK_PARAMETERS {
-						let result = check_parameter_type(
-							rest_parameter.item_type,
-							call_site_type_arguments.as_ref(),
-							parent,
-							argument.value,
-							&mut type_arguments,
-							environment,
-							types,
-						);
+		if let Some(ref rest_parameter) = self.parameters.rest_parameter {
+			let constraint = get_constraint(rest_parameter.ty, types).unwrap_or(rest_parameter.ty);
+			// Tuple rest parameters are checked element-wise
+			let tuple_elements = get_tuple_elements(constraint, environment, types);
 
-						// TODO different diagnostic?
-						if let SubTypeResult::IsNotSubType(_reasons) = result {
-							let type_arguments = Some(GenericChainLink::FunctionRoot {
-								parent,
-								call_site_type_arguments: call_site_type_arguments.as_ref(),
-								type_arguments: &type_arguments,
-							});
+			// TODO reuse synthesise_array literal logic (especially for spread items)
+			let mut basis =
+				ObjectBuilder::new(Some(TypeId::ARRAY_TYPE), types, &mut environment.info);
+
+			// `None` after a spread argument of unknown length
+			let mut count = Some(0);
 
-							errors.errors.push(FunctionCallingError::InvalidArgumentType {
-								parameter_type:
-									TypeStringRepresentation::from_type_id_with_generics(
-										rest_parameter.ty,
-										type_arguments,
-										environment,
-										types,
-										behavior.debug_types(),
-									),
-								argument_type: TypeStringRepresentation::from_type_id_with_generics(
-									argument.value,
-									type_arguments,
+			for argument in rest_arguments {
+				if E::CHECK_PARAMETERS {
+					match (&tuple_elements, count) {
+						(Some(elements), Some(idx)) if !argument.spread => {
+							if let Some(element) = elements.get(idx) {
+								check_argument(
+									(*element, *element, rest_parameter.position),
+									argument,
+									call_site_type_arguments.as_ref(),
+									parent,
+									(&mut type_arguments, &mut inferred_from),
+									environment,
+									types,
+									errors,
+									behavior,
+								);
+							} else {
+								errors.errors.push(FunctionCallingError::ExcessArguments {
+									count: 1,
+									position: argument.position,
+								});
+							}
+						}
+						(Some(elements), count) => {
+							let skip = count.unwrap_or_default();
+							for element in elements.iter().skip(skip) {
+								check_argument(
+									(*element, *element, rest_parameter.position),
+									argument,
+									call_site_type_arguments.as_ref(),
+									parent,
+									(&mut type_arguments, &mut inferred_from),
 									environment,
 									types,
-									behavior.debug_types(),
+									errors,
+									behavior,
+								);
+							}
+						}
+						(None, _) => {
+							// TODO different diagnostic?
+							check_argument(
+								(
+									rest_parameter.item_type,
+									rest_parameter.ty,
+									rest_parameter.position,
 								),
-								parameter_position: rest_parameter.position,
-								argument_position: argument.position,
-								restriction: None,
-							});
+								argument,
+								call_site_type_arguments.as_ref(),
+								parent,
+								(&mut type_arguments, &mut inferred_from),
+								environment,
+								types,
+								errors,
+								behavior,
+							);
 						}
 					}
+				}
 
-					{
-						let key = PropertyKey::from_usize(count);
-
-						basis.append(
-							environment,
-							crate::context::information::Publicity::Public,
-							key,
-							crate::types::properties::PropertyValue::Value(argument.value),
-							None,
-						);
-					}
+				let key = match count {
+					Some(idx) if !argument.spread => PropertyKey::from_usize(idx),
+					_ => PropertyKey::Type(TypeId::NUMBER_TYPE),
+				};
 
-					count += 1;
+				basis.append(
+					environment,
+					crate::context::information::Publicity::Public,
+					key,
+					crate::types::properties::PropertyValue::Value(argument.value),
+					None,
+				);
+
+				count = match count {
+					Some(idx) if !argument.spread => Some(idx + 1),
+					_ => None,
+				};
+			}
+
+			if let (true, Some(elements), Some(count)) =
+				(E::CHECK_PARAMETERS, &tuple_elements, count)
+			{
+				if count < elements.len() {
+					errors.errors.push(FunctionCallingError::MissingArgument {
+						parameter_position: rest_parameter.position,
+						call_site,
+					});
 				}
+			}
 
-				{
-					let length = types.new_constant_type(crate::Constant::Number(
+			{
+				let length = match count {
+					Some(count) => types.new_constant_type(crate::Constant::Number(
 						(count as f64).try_into().unwrap(),
-					));
+					)),
+					None => TypeId::NUMBER_TYPE,
+				};
+
+				basis.append(
+					environment,
+					crate::context::information::Publicity::Public,
+					PropertyKey::String("length".into()),
+					crate::types::properties::PropertyValue::Value(length),
+					None,
+				);
+			}
+
+			let rest_parameter_array_type = basis.build_object();
 
-					basis.append(
+			// TODO only if no error
+			type_arguments.insert(rest_parameter.ty, rest_parameter_array_type);
+
+			// Generic rest parameters (`...args: T`) are inferred from the arguments
+			if let Type::RootPolyType(PolyNature::FunctionGeneric { .. }) =
+				types.get_type_by_id(constraint)
+			{
+				if E::CHECK_PARAMETERS {
+					let _ = check_parameter_type(
+						constraint,
+						call_site_type_arguments.as_ref(),
+						parent,
+						rest_parameter_array_type,
+						&mut type_arguments,
 						environment,
-						crate::context::information::Publicity::Public,
-						PropertyKey::String("length".into()),
-						crate::types::properties::PropertyValue::Value(length),
-						None,
+						types,
 					);
+				} else {
+					type_arguments.insert(constraint, rest_parameter_array_type);
 				}
-
-				let rest_parameter_array_type = basis.build_object();
-
-				// TODO only if no error
-				type_arguments.insert(rest_parameter.ty, rest_parameter_array_type);
-			} else {
-				// TODO types.options.allow_extra_arguments
-				let mut left_over = arguments.iter().skip(self.parameters.parameters.len());
-				let first = left_over.next().unwrap();
+			}
+		} else {
+			// TODO types.options.allow_extra_arguments
+			// Spread arguments of unknown length may be empty
+			let mut left_over =
+				arguments.iter().skip(parameters_count).filter(|argument| !argument.spread);
+			if let Some(first) = left_over.next() {
 				let mut count = 1;
 				let mut end = None;
 				while let arg @ Some(_) = left_over.next() {
@@ -1217,10 +1416,162 @@ impl FunctionType {
 			}
 		}
 
+		// Type parameters that were neither passed nor inferred take their default. Defaults can
+		// reference earlier parameters
+		for parameter in self.type_parameters.iter().flat_map(|parameters| &parameters.0) {
+			if let (Some(default), None) = (parameter.default, type_arguments.get(&parameter.id)) {
+				let mut arguments = FunctionTypeArguments {
+					local_arguments: type_arguments.clone(),
+					closure_ids: Default::default(),
+					call_site,
+				};
+				let default = substitute(default, &mut arguments, environment, types);
+				type_arguments.insert(parameter.id, default);
+			}
+		}
+
 		type_arguments
 	}
 }
 
+/// Checks an argument against a parameter, adding an error if it is not assignable. `printed_parameter_ty`
+/// is the type in the diagnostic (for rest parameters this is the array rather than the item).
+/// `inferred_from` records the argument that each generic was first inferred from, so that errors
+/// for later arguments can point to it
+#[allow(clippy::too_many_arguments)]
+fn check_argument<E: CallCheckingBehavior>(
+	(parameter_ty, printed_parameter_ty, parameter_position): (TypeId, TypeId, SpanWithSource),
+	argument: &SynthesisedArgument,
+	call_site_type_arguments: Option<&CallSiteTypeArguments>,
+	parent: Option<&StructureGenericArguments>,
+	(type_arguments, inferred_from): (
+		&mut TypeArguments,
+		&mut map_vec::Map<TypeId, SpanWithSource>,
+	),
+	environment: &mut Environment,
+	types: &mut TypeStore,
+	errors: &mut ErrorsAndInfo,
+	behavior: &E,
+) {
+	let result = check_parameter_type(
+		parameter_ty,
+		call_site_type_arguments,
+		parent,
+		argument.value,
+		type_arguments,
+		environment,
+		types,
+	);
+
+	if let SubTypeResult::IsNotSubType(_reasons) = result {
+		let explicit = call_site_type_arguments.and_then(|arguments| arguments.get(&parameter_ty));
+		let is_generic = matches!(
+			types.get_type_by_id(parameter_ty),
+			Type::RootPolyType(PolyNature::FunctionGeneric { .. })
+		);
+
+		let print = |ty: TypeId, types: &TypeStore| {
+			TypeStringRepresentation::from_type_id(ty, environment, types, behavior.debug_types())
+		};
+		let restriction = explicit.map(|(ty, position)| (*position, print(*ty, types)));
+		let inferred = (is_generic && explicit.is_none())
+			.then(|| get_constraint(parameter_ty, types))
+			.flatten()
+			.map(|constraint| InferredGeneric {
+				constraint: print(constraint, types),
+				inferred_from: inferred_from.get(&parameter_ty).and_then(|position| {
+					let inferred = type_arguments.get(&parameter_ty)?;
+					Some((print(*inferred, types), *position))
+				}),
+			});
+
+		let type_arguments = Some(GenericChainLink::FunctionRoot {
+			parent,
+			call_site_type_arguments,
+			type_arguments,
+		});
+
+		// A generic parameter is printed as `T`, with the inferred type in the labels
+		let parameter_type = if inferred.is_some() {
+			print(printed_parameter_ty, types)
+		} else {
+			TypeStringRepresentation::from_type_id_with_generics(
+				printed_parameter_ty,
+				type_arguments,
+				environment,
+				types,
+				behavior.debug_types(),
+			)
+		};
+
+		errors.errors.push(FunctionCallingError::InvalidArgumentType {
+			parameter_type,
+			argument_type: TypeStringRepresentation::from_type_id_with_generics(
+				argument.value,
+				type_arguments,
+				environment,
+				types,
+				behavior.debug_types(),
+			),
+			parameter_position,
+			argument_position: argument.position,
+			restriction,
+			inferred,
+		});
+	} else {
+		for (on, _) in type_arguments.iter() {
+			inferred_from.entry(*on).or_insert(argument.position);
+		}
+	}
+}
+
+/// The element types of a tuple type (from a `[string, number]` annotation). Returns `None` for
+/// other types
+pub(crate) fn get_tuple_elements(
+	ty: TypeId,
+	info: &impl InformationChain,
+	types: &TypeStore,
+) -> Option<Vec<TypeId>> {
+	fn get_value(
+		ty: TypeId,
+		key: &PropertyKey,
+		info: &impl InformationChain,
+		types: &TypeStore,
+	) -> Option<TypeId> {
+		let property = get_property_unbound(
+			ty,
+			crate::context::information::Publicity::Public,
+			key,
+			types,
+			info,
+		);
+		// Array literals have a prototype, so their properties are `Implies`
+		let property = match property.ok()? {
+			Logical::Implies { on, .. } => *on,
+			property => property,
+		};
+		if let Logical::Pure(crate::types::properties::PropertyValue::Value(value)) = property {
+			Some(value)
+		} else {
+			None
+		}
+	}
+
+	if !matches!(types.get_type_by_id(ty), Type::Object(_)) {
+		return None;
+	}
+
+	let length = get_value(ty, &PropertyKey::String("length".into()), info, types)?;
+	let Type::Constant(crate::Constant::Number(length)) = types.get_type_by_id(length) else {
+		return None;
+	};
+
+	#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+	let length = length.into_inner() as usize;
+
+	(0..length).map(|idx| get_value(ty, &PropertyKey::from_usize(idx), info, types)).collect()
+}
+
 fn check_parameter_type(
 	parameter_ty: TypeId,
 	call_site_type_arguments: Option<&CallSiteTypeArguments>,
@@ -1230,7 +1581,7 @@ fn check_parameter_type(
 	environment: &mut Environment,
 	types: &mut TypeStore,
 ) -> SubTypeResult {
-	crate::utils::notify!("Value is {:?}, parent generics {:?}", value, parent_generics);
+	crate::utils::notify!(Trace; "Value is {:?}, parent generics {:?}", value, parent_generics);
 
 	// TODO properties
 	let mut contributions = Contributions {
@@ -1261,7 +1612,7 @@ fn check_parameter_type(
 	let Contributions { staging_covariant, staging_contravariant, .. } = contributions;
 
 	for (_res, _pos) in staging_contravariant {
-		crate::utils::notify!("TODO merge? pick highest?");
+		crate::utils::notify!(Trace; "TODO merge? pick highest?");
 	}
 
 	// TODO WIP
@@ -1288,6 +1639,214 @@ fn check_parameter_type(
 	result
 }
 
+/// Spread arguments of iterables with known items are expanded into separate arguments. For the
+/// remaining spread arguments the value is the type of the items
+/// `Function.prototype.call`, `Function.prototype.apply` and `Function.prototype.bind` (with arguments
+/// to partially apply). Returns `None` for other methods or if the function `this` is not known
+fn call_function_prototype_method<E: CallCheckingBehavior>(
+	method: &str,
+	this_value: ThisValue,
+	call_site: SpanWithSource,
+	arguments: &[SynthesisedArgument],
+	environment: &mut Environment,
+	behavior: &mut E,
+	types: &mut TypeStore,
+) -> Option<Result<FunctionCallResult, Vec<FunctionCallingError>>> {
+	if !matches!(method, "call" | "apply" | "bind") {
+		return None;
+	}
+
+	let on = this_value.get_passed()?;
+	let (this_argument, arguments) = match arguments.split_first() {
+		Some((first, rest)) if !first.spread => (first.value, rest),
+		Some(_) => return None,
+		None => (TypeId::UNDEFINED_TYPE, arguments),
+	};
+
+	let arguments = match method {
+		"call" => arguments.to_vec(),
+		"apply" => arguments
+			.first()
+			.map_or_else(Vec::new, |list| get_apply_arguments(list, environment, types)),
+		_ => {
+			// Binding just `this` is handled by the constant function
+			if arguments.is_empty() {
+				return None;
+			}
+			let returned_type =
+				create_bound_function(on, this_argument, arguments, call_site, types)?;
+			return Some(Ok(FunctionCallResult {
+				called: None,
+				returned_type,
+				warnings: Vec::new(),
+				special: None,
+				result_was_const_computation: true,
+			}));
+		}
+	};
+
+	let input = CallingInput {
+		called_with_new: CalledWithNew::None,
+		call_site,
+		call_site_type_arguments: None,
+	};
+	let callable =
+		get_logical_callable_from_type(on, Some(ThisValue::Passed(this_argument)), Some(on), types)
+			.ok();
+
+	let result =
+		try_call_logical(callable, &input, arguments, None, environment, types, behavior, on);
+
+	// Any events have been registered by calling `on`, so don't record this call as well
+	Some(result.map(|result| FunctionCallResult { result_was_const_computation: true, ..result }))
+}
+
+/// The arguments from the array passed to `Function.prototype.apply`
+fn get_apply_arguments(
+	list: &SynthesisedArgument,
+	environment: &Environment,
+	types: &TypeStore,
+) -> Vec<SynthesisedArgument> {
+	let SynthesisedArgument { value, position, .. } = *list;
+	if matches!(value, TypeId::UNDEFINED_TYPE | TypeId::NULL_TYPE) {
+		return Vec::new();
+	}
+
+	if let Some(elements) = get_tuple_elements(value, environment, types) {
+		return elements
+			.into_iter()
+			.map(|value| SynthesisedArgument { spread: false, value, position })
+			.collect();
+	}
+
+	let item = get_constraint(value, types)
+		.and_then(|constraint| {
+			if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+				on: TypeId::ARRAY_TYPE,
+				arguments,
+			})) = types.get_type_by_id(constraint)
+			{
+				arguments.get_structure_restriction(TypeId::T_TYPE)
+			} else {
+				None
+			}
+		})
+		.unwrap_or(TypeId::ANY_TYPE);
+
+	vec![SynthesisedArgument { spread: true, value: item, position }]
+}
+
+/// Creates the function returned from `Function.prototype.bind` when `bound` arguments are given. It
+/// has the remaining parameters and calling it calls `on` through [`Event::CallsType`], so the result
+/// is the same as calling `on` with `this_argument` and all the arguments directly
+fn create_bound_function(
+	on: TypeId,
+	this_argument: TypeId,
+	bound: &[SynthesisedArgument],
+	call_site: SpanWithSource,
+	types: &mut TypeStore,
+) -> Option<TypeId> {
+	let (Type::SpecialObject(SpecialObjects::Function(function_id, _))
+	| Type::FunctionReference(function_id)) = types.get_type_by_id(on)
+	else {
+		return None;
+	};
+	let function_id = *function_id;
+	let target = types.functions.get(&function_id)?.clone();
+
+	let on = types.register_type(Type::SpecialObject(SpecialObjects::Function(
+		function_id,
+		ThisValue::Passed(this_argument),
+	)));
+
+	let skip = bound.len().min(target.parameters.parameters.len());
+	let parameters = SynthesisedParameters {
+		parameters: target.parameters.parameters[skip..].to_vec(),
+		rest_parameter: target.parameters.rest_parameter.clone(),
+	};
+
+	// The remaining parameters are passed through by their (generic) parameter types
+	let mut with = bound.to_vec();
+	with.extend(parameters.parameters.iter().map(|parameter| SynthesisedArgument {
+		spread: false,
+		value: parameter.ty,
+		position: parameter.position,
+	}));
+	if let Some(ref rest_parameter) = parameters.rest_parameter {
+		crate::utils::notify!(Trace; "TODO pass through individual rest items");
+		with.push(SynthesisedArgument {
+			spread: true,
+			value: rest_parameter.item_type,
+			position: rest_parameter.position,
+		});
+	}
+	let with = with.into_boxed_slice();
+
+	let result = types.register_type(Type::Constructor(Constructor::Image {
+		on,
+		with: with.clone(),
+		result: target.return_type,
+	}));
+
+	let events = vec![
+		Event::CallsType {
+			on,
+			with,
+			reflects_dependency: Some(result),
+			timing: CallingTiming::Synchronous,
+			called_with_new: CalledWithNew::None,
+			position: call_site,
+		},
+		Event::FinalEvent(FinalEvent::Return { returned: result, returned_position: call_site }),
+	];
+
+	let function_type = FunctionType {
+		// Different to the start of the call site which could be the start of a function expression
+		id: FunctionId(call_site.source, call_site.end),
+		type_parameters: target.type_parameters,
+		parameters,
+		return_type: target.return_type,
+		// `this` cannot be changed
+		behavior: FunctionBehavior::ArrowFunction { is_async: false },
+		effect: FunctionEffect::SideEffects {
+			events,
+			free_variables: Default::default(),
+			closed_over_variables: Default::default(),
+		},
+	};
+
+	Some(types.new_function_type(function_type))
+}
+
+fn expand_spread_arguments<T: ReadFromFS, A: crate::ASTImplementation>(
+	arguments: Vec<SynthesisedArgument>,
+	environment: &mut Environment,
+	checking_data: &mut crate::CheckingData<T, A>,
+) -> Vec<SynthesisedArgument> {
+	let mut expanded = Vec::with_capacity(arguments.len());
+	for argument in arguments {
+		if !argument.spread {
+			expanded.push(argument);
+			continue;
+		}
+
+		let SynthesisedArgument { value, position, .. } = argument;
+		match get_iterated_items(value, position, environment, checking_data) {
+			IteratedItems::Known(items) => {
+				expanded.extend(items.into_iter().map(|value| SynthesisedArgument {
+					spread: false,
+					value,
+					position,
+				}));
+			}
+			IteratedItems::Unknown(item) => {
+				expanded.push(SynthesisedArgument { spread: true, value: item, position });
+			}
+		}
+	}
+	expanded
+}
+
 fn synthesise_arguments_for_parameter<T: ReadFromFS, A: crate::ASTImplementation>(
 	callable: &Logical<FunctionLike>,
 	arguments: &[UnsynthesisedArgument<A>],
@@ -1302,7 +1861,7 @@ fn synthesise_arguments_for_parameter<T: ReadFromFS, A: crate::ASTImplementation
 		types: &crate::types::TypeStore,
 		environment: &mut Environment,
 	) -> TypeRestrictions {
-		crate::utils::notify!("call_site_type_arguments {:?}", call_site_type_arguments);
+		crate::utils::notify!(Trace; "call_site_type_arguments {:?}", call_site_type_arguments);
 
 		type_parameters
 			.0
@@ -1366,7 +1925,7 @@ fn synthesise_arguments_for_parameter<T: ReadFromFS, A: crate::ASTImplementation
 					let expected_type = parameters.get_parameter_type_at_index(idx).map_or(
 						TypeId::ANY_TYPE,
 						|(parameter_type, _)| {
-							crate::utils::notify!("Here {:?}", parameter_type);
+							crate::utils::notify!(Trace; "Here {:?}", parameter_type);
 
 							let ty = checking_data.types.get_type_by_id(parameter_type);
 							let parameter_type = if let Type::RootPolyType(
@@ -1375,7 +1934,7 @@ fn synthesise_arguments_for_parameter<T: ReadFromFS, A: crate::ASTImplementation
 							{
 								*fixed_to
 							} else {
-								crate::utils::notify!(
+								crate::utils::notify!(Trace;
 									"Parameter is not `PolyNature::Parameter`? {:?}",
 									ty
 								);
@@ -1404,7 +1963,7 @@ fn synthesise_arguments_for_parameter<T: ReadFromFS, A: crate::ASTImplementation
 										),
 									};
 
-								crate::utils::notify!("{:?} with {:?}", parameter_type, arguments);
+								crate::utils::notify!(Trace; "{:?} with {:?}", parameter_type, arguments);
 
 								checking_data.types.register_type(Type::Constructor(
 									Constructor::StructureGenerics(StructureGenerics {
@@ -1413,7 +1972,7 @@ fn synthesise_arguments_for_parameter<T: ReadFromFS, A: crate::ASTImplementation
 									}),
 								))
 							} else {
-								crate::utils::notify!("No generics");
+								crate::utils::notify!(Trace; "No generics");
 								parameter_type
 							}
 						},
@@ -17,14 +17,20 @@ pub(crate) fn cast_as_number(cst: &Constant, strict_casts: bool) -> Result<f64,
 			}
 		},
 		Constant::Boolean(val) => Ok(if *val { 1f64 } else { 0f64 }),
+		#[allow(clippy::cast_precision_loss)]
+		Constant::BigInt(value) => Ok(*value as f64),
 		Constant::NaN | Constant::Undefined => Ok(f64::NAN),
 		Constant::Null => Ok(0f64),
-		Constant::Symbol { key: _ } => todo!(),
+		// Throws a `TypeError`
+		Constant::Symbol { key: _ } => Err(()),
 	}
 }
 
 pub(crate) fn cast_as_string(cst: &Constant, strict_casts: bool) -> Result<String, ()> {
-	if strict_casts && !matches!(cst, Constant::String(_)) {
+	// Implicit conversion of symbols throws a `TypeError`
+	if (strict_casts && !matches!(cst, Constant::String(_)))
+		|| matches!(cst, Constant::Symbol { .. })
+	{
 		return Err(());
 	}
 	Ok(cst.as_js_string())
@@ -42,7 +48,8 @@ pub(crate) fn cast_as_boolean(cst: &Constant, strict_casts: bool) -> Result<bool
 		Constant::Number(number) => number.into_inner() != 0.,
 		Constant::String(value) => !value.is_empty(),
 		Constant::Boolean(value) => *value,
+		Constant::BigInt(value) => *value != 0,
 		Constant::NaN | Constant::Undefined | Constant::Null => false,
-		Constant::Symbol { key: _ } => todo!(),
+		Constant::Symbol { key: _ } => true,
 	})
 }
@@ -17,6 +17,9 @@ pub struct ClassValue<'a, A: crate::ASTImplementation> {
 	/// Created eagerly, don't specialise
 	pub key: PropertyKey<'static>,
 	pub value: Option<&'a A::Expression<'a>>,
+	/// The annotation of a property without a value on a `declare` class, which is not initialised
+	/// here
+	pub ambient_type: Option<TypeId>,
 }
 
 pub struct SynthesisedClassValue {
@@ -83,7 +86,7 @@ pub(crate) fn register_properties_into_environment<
 	checking_data: &mut CheckingData<T, A>,
 	ClassPropertiesToRegister { properties }: ClassPropertiesToRegister<A>,
 ) {
-	for ClassValue { publicity, key, value } in properties {
+	for ClassValue { publicity, key, value, ambient_type } in properties {
 		let value = if let Some(expression) = value {
 			PropertyValue::Value(A::synthesise_expression(
 				expression,
@@ -92,7 +95,7 @@ pub(crate) fn register_properties_into_environment<
 				checking_data,
 			))
 		} else {
-			PropertyValue::Value(TypeId::UNDEFINED_TYPE)
+			PropertyValue::Value(ambient_type.unwrap_or(TypeId::UNDEFINED_TYPE))
 		};
 		environment.info.register_property(on, publicity, key, value, true, None);
 	}
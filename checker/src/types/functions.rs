@@ -48,28 +48,75 @@ pub enum FunctionEffect {
 	},
 	Constant(String),
 	InputOutput(String),
+	/// From `@pure` or `@mutates(...)` on a declared function. Unlike [`FunctionEffect::Unknown`] only
+	/// these arguments are assumed to be mutated by a call
+	Declared {
+		/// Indexes of parameters
+		mutated_parameters: Vec<u32>,
+		/// Arguments from this index onwards (those for the rest parameter) are mutated
+		mutated_rest_from: Option<u32>,
+	},
 	Unknown,
 }
 
+impl FunctionEffect {
+	/// Whether a call might mutate the argument at `index`
+	pub(crate) fn may_mutate_argument(&self, index: usize) -> bool {
+		match self {
+			FunctionEffect::Declared { mutated_parameters, mutated_rest_from } => {
+				mutated_parameters.iter().any(|parameter| *parameter as usize == index)
+					|| mutated_rest_from.is_some_and(|from| from as usize <= index)
+			}
+			FunctionEffect::Unknown => true,
+			FunctionEffect::SideEffects { .. }
+			| FunctionEffect::Constant(..)
+			| FunctionEffect::InputOutput(..) => false,
+		}
+	}
+}
+
 #[derive(Debug)]
 pub enum InternalFunctionEffect {
 	Constant(String),
 	InputOutput(String),
+	/// `@pure`
+	Pure,
+	/// `@mutates(a, b)`. Names of parameters
+	Mutates(Vec<String>),
 }
 
-impl From<InternalFunctionEffect> for FunctionEffect {
-	fn from(value: InternalFunctionEffect) -> Self {
-		match value {
+impl InternalFunctionEffect {
+	pub(crate) fn into_function_effect(self, parameters: &SynthesisedParameters) -> FunctionEffect {
+		match self {
 			InternalFunctionEffect::Constant(identifier) => FunctionEffect::Constant(identifier),
 			InternalFunctionEffect::InputOutput(identifier) => {
 				FunctionEffect::InputOutput(identifier)
 			}
+			InternalFunctionEffect::Pure => {
+				FunctionEffect::Declared { mutated_parameters: Vec::new(), mutated_rest_from: None }
+			}
+			InternalFunctionEffect::Mutates(names) => {
+				let mutated_parameters = parameters
+					.parameters
+					.iter()
+					.enumerate()
+					.filter(|(_, parameter)| names.contains(&parameter.name))
+					.map(|(idx, _)| u32::try_from(idx).unwrap())
+					.collect();
+				let mutated_rest_from = parameters
+					.rest_parameter
+					.as_ref()
+					.filter(|rest_parameter| names.contains(&rest_parameter.name))
+					.map(|_| u32::try_from(parameters.parameters.len()).unwrap());
+				FunctionEffect::Declared { mutated_parameters, mutated_rest_from }
+			}
 		}
 	}
 }
 
 impl FunctionType {
 	pub(crate) fn new_auto_constructor<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+		class_position: SpanWithSource,
 		class_prototype: TypeId,
 		extends: Option<TypeId>,
 		properties: ClassPropertiesToRegister<A>,
@@ -131,7 +178,8 @@ impl FunctionType {
 
 		let (info, _free_variables) = env_data.unwrap();
 		Self {
-			id: crate::FunctionId::AUTO_CONSTRUCTOR,
+			// Unique per class, so that constructors of different classes are not overwritten
+			id: FunctionId(class_position.source, class_position.start),
 			type_parameters: None,
 			parameters: SynthesisedParameters::default(),
 			return_type: on,
@@ -222,6 +270,8 @@ impl SynthesisedParameters {
 
 #[derive(Clone, Debug, binary_serialize_derive::BinarySerializable)]
 pub struct SynthesisedArgument {
+	/// After synthesis, spread arguments of known iterables are expanded. If this is `true` then
+	/// `value` is the item type of an iterable of unknown length
 	pub(crate) spread: bool,
 	pub(crate) value: TypeId,
 	pub(crate) position: SpanWithSource,
@@ -9,6 +9,7 @@ pub mod properties;
 pub mod store;
 pub mod subtyping;
 mod terms;
+pub mod tuples;
 
 use derive_debug_extras::DebugExtras;
 
@@ -94,12 +95,21 @@ impl TypeId {
 	/// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/new.target>
 	pub const NEW_TARGET_ARG: Self = Self(22);
 
+	/// Well known symbols. Used for protocols (`for of`, coercion and `instanceof`)
 	pub const SYMBOL_TO_PRIMITIVE: Self = Self(23);
+	pub const SYMBOL_ITERATOR: Self = Self(24);
+	pub const SYMBOL_HAS_INSTANCE: Self = Self(25);
 
-	pub const LITERAL_RESTRICTION: Self = Self(24);
-	pub const READONLY_RESTRICTION: Self = Self(25);
+	pub const LITERAL_RESTRICTION: Self = Self(26);
+	pub const READONLY_RESTRICTION: Self = Self(27);
+	/// `Nominal<T, Name>`. See [`TypeStore::new_nominal_type`]
+	pub const NOMINAL_RESTRICTION: Self = Self(28);
+	/// `Not<T>`. Values that do not overlap with `T`
+	pub const NOT_RESTRICTION: Self = Self(29);
 
-	pub(crate) const INTERNAL_TYPE_COUNT: usize = 26;
+	pub const BIGINT_TYPE: Self = Self(30);
+
+	pub(crate) const INTERNAL_TYPE_COUNT: usize = 31;
 }
 
 #[derive(Clone, Debug, binary_serialize_derive::BinarySerializable)]
@@ -182,7 +192,10 @@ impl PolyNature {
 // TODO
 #[must_use]
 pub fn is_primitive(ty: TypeId, _types: &TypeStore) -> bool {
-	if matches!(ty, TypeId::BOOLEAN_TYPE | TypeId::NUMBER_TYPE | TypeId::STRING_TYPE) {
+	if matches!(
+		ty,
+		TypeId::BOOLEAN_TYPE | TypeId::NUMBER_TYPE | TypeId::STRING_TYPE | TypeId::BIGINT_TYPE
+	) {
 		return true;
 	}
 	false
@@ -300,6 +313,9 @@ pub enum Constructor {
 	/// **e.g `Array<string>`
 	/// Might not be best place but okay.
 	StructureGenerics(StructureGenerics),
+	/// For template literal types that contain non-literal parts (e.g. `` `on${Capitalize<T>}` ``).
+	/// Unions are distributed, so no part is a union
+	TemplateLiteral(Vec<crate::features::template_literal::TemplateLiteralTypePart>),
 }
 
 impl Constructor {
@@ -318,7 +334,8 @@ impl Constructor {
 			| Constructor::TypeOperator(_)
 			| Constructor::TypeRelationOperator(_)
 			| Constructor::Property { .. }
-			| Constructor::StructureGenerics(_) => None,
+			| Constructor::StructureGenerics(_)
+			| Constructor::TemplateLiteral(_) => None,
 		}
 	}
 }
@@ -348,6 +365,36 @@ pub(crate) fn new_logical_or_type(lhs: TypeId, rhs: TypeId, types: &mut TypeStor
 	types.new_conditional_type(lhs, lhs, rhs)
 }
 
+/// Returns `ty` without `undefined` (which may be `never`). Returns `None` if `ty` cannot be `undefined`.
+///
+/// For default values in destructuring
+pub(crate) fn remove_undefined(ty: TypeId, types: &mut TypeStore) -> Option<TypeId> {
+	if ty == TypeId::UNDEFINED_TYPE {
+		return Some(TypeId::NEVER_TYPE);
+	}
+	match types.get_type_by_id(ty) {
+		Type::Or(lhs, rhs) => {
+			let (lhs, rhs) = (*lhs, *rhs);
+			let (lhs_defined, rhs_defined) =
+				(remove_undefined(lhs, types), remove_undefined(rhs, types));
+			if lhs_defined.is_none() && rhs_defined.is_none() {
+				return None;
+			}
+			Some(match (lhs_defined.unwrap_or(lhs), rhs_defined.unwrap_or(rhs)) {
+				(TypeId::NEVER_TYPE, other) | (other, TypeId::NEVER_TYPE) => other,
+				(lhs, rhs) => types.new_or_type(lhs, rhs),
+			})
+		}
+		// The value is unknown, so the result is based on the constraint
+		Type::RootPolyType(PolyNature::Parameter { fixed_to: constraint })
+		| Type::Constructor(Constructor::Property { result: constraint, .. }) => {
+			let constraint = *constraint;
+			remove_undefined(constraint, types)
+		}
+		_ => None,
+	}
+}
+
 #[must_use]
 pub fn is_type_truthy_falsy(id: TypeId, types: &TypeStore) -> Decidable<bool> {
 	// These first two branches are just shortcuts.
@@ -547,6 +594,8 @@ pub enum NonEqualityReason {
 	/// TODO more information
 	MissingParameter,
 	GenericParameterMismatch,
+	/// The RHS has none of the properties of a type where all properties are optional
+	NoCommonProperties,
 }
 
 #[derive(Debug)]
@@ -592,12 +641,14 @@ pub(crate) fn get_constraint(on: TypeId, types: &TypeStore) -> Option<TypeId> {
 		),
 		Type::Constructor(constructor) => match constructor.clone() {
 			Constructor::BinaryOperator { lhs, operator, rhs } => {
+				let lhs = get_larger_type(lhs, types);
+				let rhs = get_larger_type(rhs, types);
 				if let MathematicalAndBitwise::Add = operator {
-					let lhs = get_larger_type(lhs, types);
-					let rhs = get_larger_type(rhs, types);
 					// TODO these need to be generated
 					if let (TypeId::NUMBER_TYPE, TypeId::NUMBER_TYPE) = (lhs, rhs) {
 						Some(TypeId::NUMBER_TYPE)
+					} else if let (TypeId::BIGINT_TYPE, TypeId::BIGINT_TYPE) = (lhs, rhs) {
+						Some(TypeId::BIGINT_TYPE)
 					} else if let (TypeId::STRING_TYPE, _) | (_, TypeId::STRING_TYPE) = (lhs, rhs) {
 						Some(TypeId::STRING_TYPE)
 					} else {
@@ -605,27 +656,17 @@ pub(crate) fn get_constraint(on: TypeId, types: &TypeStore) -> Option<TypeId> {
 						crate::utils::notify!("TODO use existing conditional");
 						Some(TypeId::NUMBER_TYPE)
 					}
+				} else if let (TypeId::BIGINT_TYPE, _) | (_, TypeId::BIGINT_TYPE) = (lhs, rhs) {
+					Some(TypeId::BIGINT_TYPE)
 				} else {
 					Some(TypeId::NUMBER_TYPE)
 				}
 			}
-			Constructor::UnaryOperator { operand: _, operator: _ } => {
-				todo!()
-				// if *constraint == TypeId::ANY_TYPE && mutable_context {
-				// 	let (operand, operator) = (operand.clone(), operator.clone());
-				// 	let constraint = to(self, data);
-				// 	self.modify_type(
-				// 		on,
-				// 		Some(Type::Constructor(Constructor::UnaryOperator {
-				// 			operator,
-				// 			operand,
-				// 								// 		})),
-				// 	);
-				// 	Some(constraint)
-				// } else {
-				// 	Some(*constraint)
-				// }
-			}
+			Constructor::UnaryOperator { operand: _, operator } => match operator {
+				PureUnary::LogicalNot => Some(TypeId::BOOLEAN_TYPE),
+				// TODO bigint
+				PureUnary::Negation | PureUnary::BitwiseNot => Some(TypeId::NUMBER_TYPE),
+			},
 			Constructor::Awaited { on: _, result }
 			| Constructor::Image { on: _, with: _, result } => Some(result),
 			Constructor::Property { on: _, under: _, result, bind_this: _ } => {
@@ -636,6 +677,8 @@ pub(crate) fn get_constraint(on: TypeId, types: &TypeStore) -> Option<TypeId> {
 				// TODO dynamic and open poly
 				Some(result_union)
 			}
+			Constructor::TypeOperator(TypeOperator::TypeOf(_))
+			| Constructor::TemplateLiteral(_) => Some(TypeId::STRING_TYPE),
 			Constructor::TypeOperator(_) | Constructor::CanonicalRelationOperator { .. } => {
 				// TODO open poly
 				Some(TypeId::BOOLEAN_TYPE)
@@ -654,7 +697,23 @@ pub(crate) fn get_constraint(on: TypeId, types: &TypeStore) -> Option<TypeId> {
 	}
 }
 
-fn get_larger_type(on: TypeId, types: &TypeStore) -> TypeId {
+/// The `T` of `Readonly<T>` (which `readonly T[]` and `ReadonlyArray<T>` are). Looks through aliases,
+/// intersections and constraints
+pub(crate) fn get_readonly_inner(on: TypeId, types: &TypeStore) -> Option<TypeId> {
+	match types.get_type_by_id(on) {
+		Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::READONLY_RESTRICTION,
+			arguments,
+		})) => arguments.get_structure_restriction(TypeId::T_TYPE),
+		Type::AliasTo { to, parameters: None, .. } => get_readonly_inner(*to, types),
+		Type::And(left, right) => {
+			get_readonly_inner(*left, types).or_else(|| get_readonly_inner(*right, types))
+		}
+		_ => get_constraint(on, types).and_then(|constraint| get_readonly_inner(constraint, types)),
+	}
+}
+
+pub(crate) fn get_larger_type(on: TypeId, types: &TypeStore) -> TypeId {
 	if let Some(poly_base) = get_constraint(on, types) {
 		poly_base
 	} else if let Type::Constant(cst) = types.get_type_by_id(on) {
@@ -76,7 +76,7 @@ impl<'a> Contributions<'a> {
 
 		// TODO staging_contravariant
 		if let Some(constraint) = self.get_standard_restriction(under) {
-			crate::utils::notify!("Constraint is {:?}", constraint);
+			crate::utils::notify!(Trace; "Constraint is {:?}", constraint);
 			type_is_subtype_with_generics(
 				constraint,
 				GenericChain::None,
@@ -91,7 +91,7 @@ impl<'a> Contributions<'a> {
 		} else {
 			// TODO not sure
 			let constraint = crate::types::get_constraint(under, types).unwrap();
-			crate::utils::notify!("Here, constraint={:?}", constraint);
+			crate::utils::notify!(Trace; "Here, constraint={:?}", constraint);
 			type_is_subtype_with_generics(
 				constraint,
 				GenericChain::None,
@@ -147,8 +147,8 @@ impl<'a> Contributions<'a> {
 		types: &TypeStore,
 		already_checked: &mut AlreadyChecked,
 	) -> SubTypeResult {
-		crate::utils::notify!("TODO assert it meets existing_covariant and staging_covariant");
-		crate::utils::notify!("TODO add to staging_covariant");
+		crate::utils::notify!(Trace; "TODO assert it meets existing_covariant and staging_covariant");
+		crate::utils::notify!(Trace; "TODO add to staging_covariant");
 
 		if let Some(under) = self.get_standard_restriction(on) {
 			type_is_subtype_with_generics(
@@ -170,7 +170,7 @@ impl<'a> Contributions<'a> {
 
 impl<'a> SubTypeBehavior<'a> for Contributions<'a> {
 	fn add_object_mutation_constraint(&mut self, _on: TypeId, _constraint: TypeId) {
-		crate::utils::notify!("TODO");
+		crate::utils::notify!(Trace; "TODO");
 	}
 
 	fn add_function_restriction(
@@ -3,8 +3,15 @@
 
 use crate::{
 	context::information::{InformationChain, LocalInformation},
-	features::functions::{ClosureChain, ClosureId},
-	types::{StructureGenerics, TypeArguments, TypeRestrictions, TypeStore},
+	events::Event,
+	features::{
+		functions::{ClosureChain, ClosureId},
+		objects::SpecialObjects,
+	},
+	types::{
+		calling::CalledWithNew, FunctionEffect, StructureGenerics, TypeArguments, TypeRestrictions,
+		TypeStore,
+	},
 	Type, TypeId,
 };
 
@@ -21,6 +28,27 @@ impl FromIterator<GenericStructureTypeArgument> for GenericStructureTypeArgument
 	}
 }
 
+/// Whether `on` is a class whose constructor calls `super` on a dependent type, e.g.
+/// `class extends Base {}` in a mixin function with a `Base` parameter
+fn extends_dependent_base(on: TypeId, types: &TypeStore) -> bool {
+	let Type::SpecialObject(SpecialObjects::ClassConstructor { constructor, .. }) =
+		types.get_type_by_id(on)
+	else {
+		return false;
+	};
+	let FunctionEffect::SideEffects { events, .. } =
+		&types.get_function_from_id(*constructor).effect
+	else {
+		return false;
+	};
+	events.iter().any(|event| {
+		matches!(
+			event,
+			Event::CallsType { called_with_new: CalledWithNew::SpecialSuperCall { .. }, .. }
+		)
+	})
+}
+
 /// For when a function is called
 #[derive(Debug)]
 pub(crate) struct FunctionTypeArguments {
@@ -89,7 +117,23 @@ impl TypeArgumentStore for FunctionTypeArguments {
 			types.get_type_by_id(on)
 		{
 			if self.closure_ids.is_empty() {
-				on
+				// Mixins (classes that extend a dependent base) need the arguments to call the base
+				// constructor with
+				if !self.local_arguments.is_empty() && extends_dependent_base(on, types) {
+					types.register_type(Type::Constructor(
+						crate::types::Constructor::StructureGenerics(StructureGenerics {
+							on,
+							arguments: StructureGenericArguments::ExplicitRestrictions(
+								self.local_arguments
+									.iter()
+									.map(|(on, arg)| (*on, (*arg, self.call_site)))
+									.collect(),
+							),
+						}),
+					))
+				} else {
+					on
+				}
 			} else {
 				types.register_type(Type::Constructor(
 					crate::types::Constructor::StructureGenerics(StructureGenerics {
@@ -1,6 +1,12 @@
 use std::{fmt::Debug, iter::FromIterator};
 
-use crate::TypeId;
+use source_map::SpanWithSource;
+
+use crate::{
+	diagnostics::{TypeCheckError, TypeStringRepresentation},
+	subtyping::{type_is_subtype, BasicEquality, SubTypeResult},
+	CheckingData, Environment, TypeId,
+};
 
 // Encompasses both generic types
 #[derive(Default, Debug, Clone, binary_serialize_derive::BinarySerializable)]
@@ -97,3 +103,46 @@ impl PartialEq for GenericTypeParameter {
 //         buf.push_str(&format!("T{:?}", self.id))
 //     }
 // }
+
+/// Checks that the default of a type parameter is assignable to its constraint (`<T extends C = D>`)
+pub(crate) fn check_type_parameter_default<T: crate::ReadFromFS, A: crate::ASTImplementation>(
+	parameter: &str,
+	constraint: TypeId,
+	default: TypeId,
+	position: SpanWithSource,
+	environment: &Environment,
+	checking_data: &mut CheckingData<T, A>,
+) {
+	let mut behavior = BasicEquality {
+		add_property_restrictions: false,
+		position,
+		object_constraints: Default::default(),
+		allow_errors: true,
+	};
+	let result =
+		type_is_subtype(constraint, default, &mut behavior, environment, &checking_data.types);
+
+	if let SubTypeResult::IsNotSubType(_) = result {
+		let debug_types = checking_data.options.debug_types;
+		let default = TypeStringRepresentation::from_type_id(
+			default,
+			environment,
+			&checking_data.types,
+			debug_types,
+		);
+		let constraint = TypeStringRepresentation::from_type_id(
+			constraint,
+			environment,
+			&checking_data.types,
+			debug_types,
+		);
+		checking_data.diagnostics_container.add_error(
+			TypeCheckError::InvalidDefaultTypeParameter {
+				parameter: parameter.to_owned(),
+				default,
+				constraint,
+				position,
+			},
+		);
+	}
+}
@@ -8,6 +8,7 @@ use crate::{
 			evaluate_equality_inequality_operation, evaluate_mathematical_operation,
 			evaluate_pure_unary_operator,
 		},
+		template_literal::{new_template_literal_type, TemplateLiteralTypePart},
 	},
 	types::{
 		get_constraint, get_larger_type, is_type_truthy_falsy, Constructor, ObjectNature,
@@ -37,7 +38,7 @@ pub(crate) fn substitute(
 		}
 		// This works for both objects and `AnonymousTypeAnnotation`s
 		Type::Object(ObjectNature::RealDeal | ObjectNature::AnonymousTypeAnnotation) => {
-			crate::utils::notify!("Here!!!");
+			crate::utils::notify!(Trace; "Here!!!");
 			arguments.curry_arguments(types, id)
 		}
 		Type::SpecialObject(SpecialObjects::Function(f, t)) => {
@@ -82,12 +83,12 @@ pub(crate) fn substitute(
 			} else if let PolyNature::FunctionGeneric { .. } | PolyNature::StructureGeneric { .. } =
 				nature
 			{
-				crate::utils::notify!("Could not find argument for explicit generic");
+				crate::utils::notify!(Trace; "Could not find argument for explicit generic");
 				id
 			} else {
 				// Other root poly types cases handled by the early return
 				let on = crate::types::printing::print_type(id, types, environment, true);
-				crate::utils::notify!("Could not find argument for {}", on);
+				crate::utils::notify!(Trace; "Could not find argument for {}", on);
 				TypeId::ERROR_TYPE
 			}
 		}
@@ -158,7 +159,7 @@ pub(crate) fn substitute(
 						substitute(otherwise_result, arguments, environment, types)
 					}
 				} else {
-					crate::utils::notify!("{:?} is undecidable", condition);
+					crate::utils::notify!(Trace; "{:?} is undecidable", condition);
 					let truthy_result = substitute(truthy_result, arguments, environment, types);
 					let otherwise_result =
 						substitute(otherwise_result, arguments, environment, types);
@@ -183,7 +184,7 @@ pub(crate) fn substitute(
 				{
 					// Try get the constant
 					if under.as_number(types).is_some() {
-						crate::utils::notify!("Temp array index property get");
+						crate::utils::notify!(Trace; "Temp array index property get");
 						let value = arguments.get_structure_restriction(TypeId::T_TYPE).unwrap();
 						types.new_or_type(value, TypeId::UNDEFINED_TYPE)
 					} else {
@@ -195,7 +196,7 @@ pub(crate) fn substitute(
 							environment,
 							types,
 						);
-						crate::utils::notify!(
+						crate::utils::notify!(Trace;
 							"Specialising the constraint {:?} to {:?} using {:?} (which is strange)",
 							result,
 							new_result,
@@ -305,14 +306,18 @@ pub(crate) fn substitute(
 					}
 				}
 			}
-			Constructor::TypeOperator(..) => todo!(),
+			Constructor::TypeOperator(crate::types::TypeOperator::TypeOf(on)) => {
+				let on = substitute(on, arguments, environment, types);
+				crate::features::type_of_operator(on, types)
+			}
+			Constructor::TypeOperator(crate::types::TypeOperator::PrototypeOf(..)) => todo!(),
 			Constructor::TypeRelationOperator(op) => match op {
 				crate::types::TypeRelationOperator::Extends { ty, extends } => {
 					let ty = substitute(ty, arguments, environment, types);
 					let extends = substitute(extends, arguments, environment, types);
 
 					let does_extend = get_larger_type(ty, types) == extends;
-					crate::utils::notify!("Extends result {:?}", does_extend);
+					crate::utils::notify!(Trace; "Extends result {:?}", does_extend);
 					if does_extend {
 						TypeId::TRUE
 					} else {
@@ -351,6 +356,19 @@ pub(crate) fn substitute(
 				}
 			},
 			Constructor::Awaited { .. } => todo!("should have effect result"),
+			Constructor::TemplateLiteral(parts) => {
+				let parts = parts
+					.into_iter()
+					.map(|part| match part {
+						TemplateLiteralTypePart::Dynamic { on, intrinsic } => {
+							let on = substitute(on, arguments, environment, types);
+							TemplateLiteralTypePart::Dynamic { on, intrinsic }
+						}
+						part @ TemplateLiteralTypePart::Static(_) => part,
+					})
+					.collect();
+				new_template_literal_type(parts, types)
+			}
 		},
 	}
 }
@@ -1,7 +1,10 @@
 use iterator_endiate::EndiateIteratorExt;
 use std::collections::HashSet;
 
-use super::{properties::PropertyKey, GenericChain, PolyNature, Type, TypeId, TypeStore};
+use super::{
+	properties::PropertyKey, tuples::TupleElementKind, GenericChain, PolyNature, Type, TypeId,
+	TypeStore,
+};
 use crate::{
 	context::{
 		information::{
@@ -11,10 +14,16 @@ use crate::{
 		Logical,
 	},
 	events::{Event, FinalEvent},
-	features::{functions::ThisValue, objects::SpecialObjects},
+	features::{
+		functions::ThisValue,
+		modules::{ExportKind, ModuleExport},
+		objects::SpecialObjects,
+		template_literal::TemplateLiteralTypePart,
+	},
 	types::{
 		get_constraint, poly_types::generic_type_arguments::StructureGenericArguments, Constructor,
-		FunctionEffect, GenericChainLink, ObjectNature, StructureGenerics, TypeRelationOperator,
+		FunctionEffect, FunctionType, GenericChainLink, ObjectNature, StructureGenerics,
+		SynthesisedParameters, TypeRelationOperator,
 	},
 	Constant, PropertyValue,
 };
@@ -60,6 +69,697 @@ pub fn print_type_with_type_arguments(
 	buf
 }
 
+/// Options for [`TypeStore::type_to_ts_string`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TypeToStringOptions {
+	/// Print the primitive of a literal (`number` rather than `4`). For the types of mutable
+	/// declarations
+	pub widen_literals: bool,
+}
+
+/// Printing types as valid TypeScript annotations (rather than the debug-ish format of
+/// [`print_type`]). Used for hover, the REPL and anything that writes annotations back into source
+impl TypeStore {
+	/// Prints `id` as TypeScript annotation syntax. Where a type cannot be represented (cycles,
+	/// unknown array items etc) this falls back to `any` or `unknown`
+	#[must_use]
+	pub fn type_to_ts_string(
+		&self,
+		id: TypeId,
+		info_chain: &impl InformationChain,
+		options: TypeToStringOptions,
+	) -> String {
+		let mut buf = String::new();
+		let mut printer = AnnotationPrinter {
+			buf: &mut buf,
+			cycles: HashSet::new(),
+			types: self,
+			info_chain,
+			options,
+		};
+		printer.print(id, GenericChain::None, AnnotationPosition::Top);
+		buf
+	}
+
+	/// Prints the exports of a module (from [`crate::CheckOutput::get_exports`]) as the contents
+	/// of a `.d.ts` file. Variables, functions and classes are `declare`d and then exported
+	/// together at the end. Declarations are ended by new lines (rather than `;`, which the parser
+	/// reads as an empty statement after ambient declarations)
+	#[must_use]
+	pub fn exports_to_ts_declarations(
+		&self,
+		exports: &[ModuleExport],
+		info_chain: &impl InformationChain,
+	) -> String {
+		let mut buf = String::new();
+		let mut printer = AnnotationPrinter {
+			buf: &mut buf,
+			cycles: HashSet::new(),
+			types: self,
+			info_chain,
+			options: TypeToStringOptions::default(),
+		};
+		let mut exported_names = Vec::new();
+
+		// In the order they are declared
+		let mut exports = exports.iter().collect::<Vec<_>>();
+		exports.sort_by_key(|export| export.position.map(|position| position.start));
+
+		for export in exports.iter().copied() {
+			let name = export.name.as_str();
+			match export.kind {
+				ExportKind::Default => {
+					printer.buf.push_str("declare const _default: ");
+					printer.print(export.ty, GenericChain::None, AnnotationPosition::Top);
+					printer.buf.push_str(";\nexport default _default;\n");
+					continue;
+				}
+				ExportKind::Variable { constant } => {
+					printer.buf.push_str(if constant { "declare const " } else { "declare let " });
+					printer.buf.push_str(name);
+					printer.buf.push_str(": ");
+					printer.print(export.ty, GenericChain::None, AnnotationPosition::Top);
+					printer.buf.push('\n');
+				}
+				ExportKind::Function => {
+					let (Type::FunctionReference(function)
+					| Type::SpecialObject(SpecialObjects::Function(function, _))) = self.get_type_by_id(export.ty)
+					else {
+						unreachable!()
+					};
+					printer.buf.push_str("declare function ");
+					printer.buf.push_str(name);
+					printer.print_signature(self.get_function_from_id(*function), true);
+					printer.buf.push('\n');
+				}
+				ExportKind::Class => {
+					// Classes are also exported as (instance) types
+					let instance = exports
+						.iter()
+						.find(|export| export.kind == ExportKind::Type && export.name == name)
+						.map(|export| export.ty);
+					printer.print_class_declaration(name, export.ty, instance);
+				}
+				ExportKind::Type => {
+					if exports
+						.iter()
+						.any(|export| export.kind == ExportKind::Class && export.name == name)
+					{
+						continue;
+					}
+					match self.get_type_by_id(export.ty) {
+						Type::Interface { parameters, .. } => {
+							printer.buf.push_str("export interface ");
+							printer.buf.push_str(name);
+							printer.print_structure_parameters(parameters.as_deref());
+							printer.buf.push_str(" {\n");
+							if let Some(function) = self.get_construct_signature(export.ty) {
+								printer.buf.push_str("\tnew ");
+								printer.print_signature(self.get_function_from_id(function), true);
+								printer.buf.push_str(";\n");
+							}
+							printer.print_members(export.ty, false);
+							printer.buf.push_str("}\n");
+						}
+						Type::AliasTo { to, parameters, .. } => {
+							printer.buf.push_str("export type ");
+							printer.buf.push_str(name);
+							printer.print_structure_parameters(parameters.as_deref());
+							printer.buf.push_str(" = ");
+							printer.print(*to, GenericChain::None, AnnotationPosition::Top);
+							printer.buf.push('\n');
+						}
+						_ => {
+							printer.buf.push_str("export type ");
+							printer.buf.push_str(name);
+							printer.buf.push_str(" = ");
+							printer.print(export.ty, GenericChain::None, AnnotationPosition::Top);
+							printer.buf.push('\n');
+						}
+					}
+					continue;
+				}
+			}
+			exported_names.push(name);
+		}
+
+		if !exported_names.is_empty() {
+			buf.push_str("export { ");
+			buf.push_str(&exported_names.join(", "));
+			buf.push_str(" };\n");
+		}
+		buf
+	}
+}
+
+/// Where a type annotation is being printed. Used to decide whether parentheses are needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AnnotationPosition {
+	Top,
+	/// Member of a union
+	Union,
+	/// Member of an intersection
+	Intersection,
+}
+
+struct AnnotationPrinter<'a, C: InformationChain> {
+	buf: &'a mut String,
+	cycles: HashSet<TypeId>,
+	types: &'a TypeStore,
+	info_chain: &'a C,
+	options: TypeToStringOptions,
+}
+
+impl<C: InformationChain> AnnotationPrinter<'_, C> {
+	fn print(&mut self, ty: TypeId, args: GenericChain, position: AnnotationPosition) {
+		if ty == TypeId::ERROR_TYPE || !self.cycles.insert(ty) {
+			self.buf.push_str("any");
+			return;
+		}
+
+		let types = self.types;
+		match types.get_type_by_id(ty) {
+			Type::And(a, b) => {
+				let parenthesize = position > AnnotationPosition::Intersection;
+				self.parenthesized(parenthesize, |printer| {
+					printer.print(*a, args, AnnotationPosition::Intersection);
+					printer.buf.push_str(" & ");
+					printer.print(*b, args, AnnotationPosition::Intersection);
+				});
+			}
+			Type::Or(a, b) => {
+				let parenthesize = position > AnnotationPosition::Union;
+				self.parenthesized(parenthesize, |printer| {
+					printer.print(*a, args, AnnotationPosition::Union);
+					printer.buf.push_str(" | ");
+					printer.print(*b, args, AnnotationPosition::Union);
+				});
+			}
+			Type::RootPolyType(nature) => match nature {
+				PolyNature::FunctionGeneric { name, .. }
+				| PolyNature::StructureGeneric { name, .. } => {
+					if let Some(structure_args) =
+						args.and_then(|args| args.get_argument(ty, self.info_chain, types))
+					{
+						let parenthesize =
+							structure_args.len() > 1 && position > AnnotationPosition::Union;
+						self.parenthesized(parenthesize, |printer| {
+							for (more, arg) in structure_args.iter().nendiate() {
+								printer.print(*arg, args, AnnotationPosition::Union);
+								if more {
+									printer.buf.push_str(" | ");
+								}
+							}
+						});
+					} else if is_valid_identifier(name) {
+						self.buf.push_str(name);
+					} else if let PolyNature::FunctionGeneric { eager_fixed, .. } = nature {
+						self.print(*eager_fixed, args, position);
+					} else {
+						self.buf.push_str("unknown");
+					}
+				}
+				PolyNature::FreeVariable { based_on: to, .. }
+				| PolyNature::Parameter { fixed_to: to }
+				| PolyNature::Open(to) => {
+					self.print(*to, args, position);
+				}
+				PolyNature::RecursiveFunction(..) => self.buf.push_str("any"),
+			},
+			Type::Constructor(constructor) => match constructor {
+				Constructor::ConditionalResult { truthy_result, otherwise_result, .. } => {
+					let parenthesize = position > AnnotationPosition::Union;
+					self.parenthesized(parenthesize, |printer| {
+						printer.print(*truthy_result, args, AnnotationPosition::Union);
+						printer.buf.push_str(" | ");
+						printer.print(*otherwise_result, args, AnnotationPosition::Union);
+					});
+				}
+				Constructor::StructureGenerics(StructureGenerics { on, arguments }) => {
+					if let Type::Class { .. } | Type::Interface { .. } | Type::AliasTo { .. } =
+						types.get_type_by_id(*on)
+					{
+						self.print(*on, args, position);
+						if let StructureGenericArguments::ExplicitRestrictions(restrictions) =
+							arguments
+						{
+							self.buf.push('<');
+							for (more, (arg, _)) in restrictions.values().nendiate() {
+								self.print(*arg, args, AnnotationPosition::Top);
+								if more {
+									self.buf.push_str(", ");
+								}
+							}
+							self.buf.push('>');
+						}
+					} else {
+						let args = GenericChainLink::append(args.as_ref(), arguments);
+						self.print(*on, args, position);
+					}
+				}
+				Constructor::Property { on, under, result, bind_this: _ } => {
+					if crate::types::is_explicit_generic(*on, types) {
+						self.print(*on, args, AnnotationPosition::Intersection);
+						self.buf.push('[');
+						match under {
+							PropertyKey::String(s) => push_string_literal(self.buf, s),
+							PropertyKey::Type(t) => self.print(*t, args, AnnotationPosition::Top),
+						}
+						self.buf.push(']');
+					} else if let Some(Type::Constructor(Constructor::StructureGenerics(sgs))) =
+						get_constraint(*on, types).map(|ty| types.get_type_by_id(ty))
+					{
+						let args = GenericChainLink::append(args.as_ref(), &sgs.arguments);
+						self.print(*result, args, position);
+					} else {
+						self.print(*result, args, position);
+					}
+				}
+				Constructor::Awaited { on: _, result } => self.print(*result, args, position),
+				Constructor::TemplateLiteral(parts) => {
+					if let [TemplateLiteralTypePart::Dynamic { on, intrinsic: Some(intrinsic) }] =
+						parts.as_slice()
+					{
+						self.buf.push_str(intrinsic.name());
+						self.buf.push('<');
+						self.print(*on, args, AnnotationPosition::Top);
+						self.buf.push('>');
+						self.cycles.remove(&ty);
+						return;
+					}
+					self.buf.push('`');
+					for part in parts {
+						match part {
+							TemplateLiteralTypePart::Static(value) => {
+								push_template_literal_text(self.buf, value);
+							}
+							TemplateLiteralTypePart::Dynamic { on, intrinsic } => {
+								self.buf.push_str("${");
+								if let Some(intrinsic) = intrinsic {
+									self.buf.push_str(intrinsic.name());
+									self.buf.push('<');
+									self.print(*on, args, AnnotationPosition::Top);
+									self.buf.push('>');
+								} else {
+									self.print(*on, args, AnnotationPosition::Top);
+								}
+								self.buf.push('}');
+							}
+						}
+					}
+					self.buf.push('`');
+				}
+				_ => {
+					let base = get_constraint(ty, types).unwrap_or(TypeId::ANY_TYPE);
+					self.print(base, args, position);
+				}
+			},
+			Type::Class { name, .. }
+			| Type::Interface { name, .. }
+			| Type::AliasTo { name, .. } => {
+				self.buf.push_str(name);
+			}
+			Type::Constant(constant) => {
+				if self.options.widen_literals {
+					match constant {
+						Constant::Number(_) | Constant::NaN => self.buf.push_str("number"),
+						Constant::String(_) => self.buf.push_str("string"),
+						Constant::Boolean(_) => self.buf.push_str("boolean"),
+						Constant::BigInt(_) => self.buf.push_str("bigint"),
+						Constant::Symbol { .. } => self.buf.push_str("symbol"),
+						Constant::Undefined => self.buf.push_str("undefined"),
+						Constant::Null => self.buf.push_str("null"),
+					}
+				} else {
+					match constant {
+						Constant::NaN => self.buf.push_str("number"),
+						Constant::String(value) => push_string_literal(self.buf, value),
+						Constant::Symbol { .. } => self.buf.push_str("symbol"),
+						constant => self.buf.push_str(&constant.as_type_name()),
+					}
+				}
+			}
+			Type::FunctionReference(func_id)
+			| Type::SpecialObject(SpecialObjects::Function(func_id, _)) => {
+				let func = types.functions.get(func_id).unwrap();
+				let parenthesize = position > AnnotationPosition::Top;
+				self.parenthesized(parenthesize, |printer| {
+					if let Some(ref parameters) = func.type_parameters {
+						printer.buf.push('<');
+						for (more, parameter) in parameters.0.iter().nendiate() {
+							printer.buf.push_str(&parameter.name);
+							if more {
+								printer.buf.push_str(", ");
+							}
+						}
+						printer.buf.push('>');
+					}
+					printer.print_parameters(&func.parameters, args);
+					printer.buf.push_str(" => ");
+					printer.print(func.return_type, args, AnnotationPosition::Top);
+				});
+			}
+			Type::Object(_) => {
+				let info_chain = self.info_chain;
+				let prototype = info_chain
+					.get_chain_of_info()
+					.find_map(|info| info.prototypes.get(&ty).copied());
+
+				if let Some(tuple) = types.get_tuple(ty) {
+					self.buf.push('[');
+					for (more, element) in tuple.elements.iter().nendiate() {
+						if let TupleElementKind::Rest = element.kind {
+							self.buf.push_str("...");
+						}
+						if let Some(ref label) = element.label {
+							self.buf.push_str(label);
+							if let TupleElementKind::Optional = element.kind {
+								self.buf.push('?');
+							}
+							self.buf.push_str(": ");
+						}
+						if let TupleElementKind::Rest = element.kind {
+							self.buf.push_str("Array<");
+							self.print(element.ty, args, AnnotationPosition::Top);
+							self.buf.push('>');
+						} else {
+							self.print(element.ty, args, AnnotationPosition::Top);
+						}
+						if element.label.is_none() && element.kind == TupleElementKind::Optional {
+							self.buf.push('?');
+						}
+						if more {
+							self.buf.push_str(", ");
+						}
+					}
+					self.buf.push(']');
+				} else if let Some(TypeId::ARRAY_TYPE) = prototype {
+					if let Some(n) = get_array_length(info_chain, ty, types) {
+						self.buf.push('[');
+						for i in 0..(n.into_inner() as usize) {
+							if i != 0 {
+								self.buf.push_str(", ");
+							}
+							let key = PropertyKey::from_usize(i);
+							if let Some(value) = get_simple_value(info_chain, ty, &key, types) {
+								self.print(value, args, AnnotationPosition::Top);
+							} else {
+								self.buf.push_str("unknown");
+							}
+						}
+						self.buf.push(']');
+					} else {
+						self.buf.push_str("Array<unknown>");
+					}
+				} else {
+					let properties = get_properties_on_type(ty, types, info_chain);
+					// Private properties cannot be written in an object literal type
+					let mut properties = properties
+						.into_iter()
+						.filter(|(publicity, ..)| matches!(publicity, Publicity::Public))
+						.peekable();
+
+					if properties.peek().is_none() {
+						self.buf.push_str("{}");
+					} else {
+						self.buf.push_str("{ ");
+						while let Some((_, key, value)) = properties.next() {
+							match key {
+								PropertyKey::String(ref s) if is_valid_identifier(s) => {
+									self.buf.push_str(s);
+								}
+								PropertyKey::String(ref s) => push_string_literal(self.buf, s),
+								PropertyKey::Type(t) => {
+									self.buf.push('[');
+									self.print(t, args, AnnotationPosition::Top);
+									self.buf.push(']');
+								}
+							}
+							self.buf.push_str(": ");
+							self.print(value, args, AnnotationPosition::Top);
+							if properties.peek().is_some() {
+								self.buf.push_str(", ");
+							}
+						}
+						self.buf.push_str(" }");
+					}
+				}
+			}
+			Type::SpecialObject(special_object) => match special_object {
+				SpecialObjects::Promise { events: () } => self.buf.push_str("Promise<unknown>"),
+				SpecialObjects::Generator { position: () } => {
+					self.buf.push_str("Generator<unknown>");
+				}
+				SpecialObjects::Proxy { handler: _, over } => self.print(*over, args, position),
+				SpecialObjects::Import(exports) => {
+					if exports.named.is_empty() {
+						self.buf.push_str("{}");
+					} else {
+						self.buf.push_str("{ ");
+						for (more, (key, (variable, mutability))) in exports.named.iter().nendiate()
+						{
+							if is_valid_identifier(key) {
+								self.buf.push_str(key);
+							} else {
+								push_string_literal(self.buf, key);
+							}
+							self.buf.push_str(": ");
+							let value = match mutability {
+								crate::features::variables::VariableMutability::Constant => {
+									get_value_of_constant_import_variable(
+										*variable,
+										self.info_chain,
+									)
+								}
+								crate::features::variables::VariableMutability::Mutable {
+									reassignment_constraint,
+								} => reassignment_constraint.unwrap_or(TypeId::ANY_TYPE),
+							};
+							self.print(value, args, AnnotationPosition::Top);
+							if more {
+								self.buf.push_str(", ");
+							}
+						}
+						self.buf.push_str(" }");
+					}
+				}
+				SpecialObjects::Regexp(_) => self.buf.push_str("RegExp"),
+				SpecialObjects::Function(..) => unreachable!(),
+				SpecialObjects::ClassConstructor { name, constructor: _ } => {
+					self.buf.push_str("typeof ");
+					self.buf.push_str(name);
+				}
+			},
+		}
+
+		self.cycles.remove(&ty);
+	}
+
+	fn parenthesized(&mut self, parenthesize: bool, cb: impl FnOnce(&mut Self)) {
+		if parenthesize {
+			self.buf.push('(');
+		}
+		cb(self);
+		if parenthesize {
+			self.buf.push(')');
+		}
+	}
+
+	fn print_parameters(&mut self, parameters: &SynthesisedParameters, args: GenericChain) {
+		self.buf.push('(');
+		for (more, parameter) in parameters.parameters.iter().nendiate() {
+			self.buf.push_str(&parameter.name);
+			if parameter.is_optional {
+				self.buf.push('?');
+			}
+			self.buf.push_str(": ");
+			self.print(parameter.ty, args, AnnotationPosition::Top);
+			if parameters.rest_parameter.is_some() || more {
+				self.buf.push_str(", ");
+			}
+		}
+		if let Some(ref rest_parameter) = parameters.rest_parameter {
+			self.buf.push_str("...");
+			self.buf.push_str(&rest_parameter.name);
+			self.buf.push_str(": ");
+			self.print(rest_parameter.ty, args, AnnotationPosition::Top);
+		}
+		self.buf.push(')');
+	}
+
+	/// `T extends C = D`
+	fn print_type_parameter(&mut self, name: &str, parameter: TypeId, default: Option<TypeId>) {
+		let types = self.types;
+		let constraint = if let Type::RootPolyType(PolyNature::StructureGeneric { .. }) =
+			types.get_type_by_id(parameter)
+		{
+			types.get_interface_type_parameter_constraint(parameter)
+		} else {
+			get_constraint(parameter, types)
+		};
+		self.buf.push_str(name);
+		if let Some(constraint) = constraint.filter(|constraint| *constraint != TypeId::ANY_TYPE) {
+			self.buf.push_str(" extends ");
+			self.print(constraint, GenericChain::None, AnnotationPosition::Top);
+		}
+		if let Some(default) = default {
+			self.buf.push_str(" = ");
+			self.print(default, GenericChain::None, AnnotationPosition::Top);
+		}
+	}
+
+	/// The parameters of an interface, type alias or class
+	fn print_structure_parameters(&mut self, parameters: Option<&[TypeId]>) {
+		let Some(parameters) = parameters else { return };
+		let types = self.types;
+		self.buf.push('<');
+		for (more, parameter) in parameters.iter().nendiate() {
+			// Interface parameters are registered as function generics
+			if let Type::RootPolyType(
+				PolyNature::StructureGeneric { name, .. }
+				| PolyNature::FunctionGeneric { name, .. },
+			) = types.get_type_by_id(*parameter)
+			{
+				let default = types.get_type_parameter_default(*parameter);
+				self.print_type_parameter(name, *parameter, default);
+			}
+			if more {
+				self.buf.push_str(", ");
+			}
+		}
+		self.buf.push('>');
+	}
+
+	/// `<T>(a: A): R` for a function declaration or method. Constructors do not have a return type
+	fn print_signature(&mut self, function: &FunctionType, with_return_type: bool) {
+		if let Some(ref parameters) = function.type_parameters {
+			self.buf.push('<');
+			for (more, parameter) in parameters.0.iter().nendiate() {
+				self.print_type_parameter(&parameter.name, parameter.id, parameter.default);
+				if more {
+					self.buf.push_str(", ");
+				}
+			}
+			self.buf.push('>');
+		}
+		self.print_parameters(&function.parameters, GenericChain::None);
+		if with_return_type {
+			self.buf.push_str(": ");
+			self.print(function.return_type, GenericChain::None, AnnotationPosition::Top);
+		}
+	}
+
+	/// The public properties of `on`, one per line. Functions are printed as methods
+	fn print_members(&mut self, on: TypeId, is_static: bool) {
+		let types = self.types;
+		for (publicity, key, value) in get_properties_on_type(on, types, self.info_chain) {
+			// `prototype` is added to every class
+			if !matches!(publicity, Publicity::Public)
+				|| (is_static && matches!(key, PropertyKey::String(ref key) if key == "prototype"))
+			{
+				continue;
+			}
+			self.buf.push('\t');
+			if is_static {
+				self.buf.push_str("static ");
+			}
+			match key {
+				PropertyKey::String(ref key) if is_valid_identifier(key) => self.buf.push_str(key),
+				PropertyKey::String(ref key) => push_string_literal(self.buf, key),
+				PropertyKey::Type(key) => {
+					self.buf.push_str("[key: ");
+					self.print(key, GenericChain::None, AnnotationPosition::Top);
+					self.buf.push(']');
+				}
+			}
+			if let Type::FunctionReference(function)
+			| Type::SpecialObject(SpecialObjects::Function(function, _)) = types.get_type_by_id(value)
+			{
+				self.print_signature(types.get_function_from_id(*function), true);
+			} else {
+				self.buf.push_str(": ");
+				self.print(value, GenericChain::None, AnnotationPosition::Top);
+			}
+			self.buf.push_str(";\n");
+		}
+	}
+
+	fn print_class_declaration(
+		&mut self,
+		name: &str,
+		constructor: TypeId,
+		instance: Option<TypeId>,
+	) {
+		let types = self.types;
+		self.buf.push_str("declare class ");
+		self.buf.push_str(name);
+		if let Some(instance) = instance {
+			if let Type::Class { parameters, .. } = types.get_type_by_id(instance) {
+				self.print_structure_parameters(parameters.as_deref());
+			}
+			let base = self
+				.info_chain
+				.get_chain_of_info()
+				.find_map(|info| info.prototypes.get(&instance).copied());
+			if let Some(base) = base {
+				self.buf.push_str(" extends ");
+				self.print(base, GenericChain::None, AnnotationPosition::Top);
+			}
+		}
+		self.buf.push_str(" {\n");
+		if let Type::SpecialObject(SpecialObjects::ClassConstructor { constructor, .. }) =
+			types.get_type_by_id(constructor)
+		{
+			let function = types.get_function_from_id(*constructor);
+			if !(function.parameters.parameters.is_empty()
+				&& function.parameters.rest_parameter.is_none())
+			{
+				self.buf.push_str("\tconstructor");
+				self.print_signature(function, false);
+				self.buf.push_str(";\n");
+			}
+		}
+		if let Some(instance) = instance {
+			self.print_members(instance, false);
+		}
+		self.print_members(constructor, true);
+		self.buf.push_str("}\n");
+	}
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+	let mut chars = name.chars();
+	chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+		&& chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+fn push_template_literal_text(buf: &mut String, value: &str) {
+	let mut chars = value.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'`' => buf.push_str("\\`"),
+			'\\' => buf.push_str("\\\\"),
+			'$' if chars.peek() == Some(&'{') => buf.push_str("\\$"),
+			c => buf.push(c),
+		}
+	}
+}
+
+fn push_string_literal(buf: &mut String, value: &str) {
+	buf.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => buf.push_str("\\\""),
+			'\\' => buf.push_str("\\\\"),
+			'\n' => buf.push_str("\\n"),
+			'\r' => buf.push_str("\\r"),
+			'\t' => buf.push_str("\\t"),
+			c => buf.push(c),
+		}
+	}
+	buf.push('"');
+}
+
 /// Recursion safe + reuses buffer
 fn print_type_into_buf<C: InformationChain>(
 	ty: TypeId,
@@ -209,6 +909,41 @@ fn print_type_into_buf<C: InformationChain>(
 					);
 				}
 			}
+			Constructor::TemplateLiteral(parts) => {
+				// `Uppercase<T>` etc on their own are not printed as template literals
+				if let [TemplateLiteralTypePart::Dynamic { on, intrinsic: Some(intrinsic) }] =
+					parts.as_slice()
+				{
+					buf.push_str(intrinsic.name());
+					buf.push('<');
+					print_type_into_buf(*on, buf, cycles, args, types, info_chain, debug);
+					buf.push('>');
+				} else {
+					buf.push('`');
+					for part in parts {
+						match part {
+							TemplateLiteralTypePart::Static(value) => {
+								push_template_literal_text(buf, value);
+							}
+							TemplateLiteralTypePart::Dynamic { on, intrinsic } => {
+								buf.push_str("${");
+								if let Some(intrinsic) = intrinsic {
+									buf.push_str(intrinsic.name());
+									buf.push('<');
+								}
+								print_type_into_buf(
+									*on, buf, cycles, args, types, info_chain, debug,
+								);
+								if intrinsic.is_some() {
+									buf.push('>');
+								}
+								buf.push('}');
+							}
+						}
+					}
+					buf.push('`');
+				}
+			}
 			constructor if debug => match constructor {
 				Constructor::BinaryOperator { lhs, operator, rhs } => {
 					print_type_into_buf(*lhs, buf, cycles, args, types, info_chain, debug);
@@ -260,7 +995,9 @@ fn print_type_into_buf<C: InformationChain>(
 					buf.push_str(" = ");
 					print_type_into_buf(*result, buf, cycles, args, types, info_chain, debug);
 				}
-				Constructor::StructureGenerics { .. } | Constructor::ConditionalResult { .. } => {
+				Constructor::StructureGenerics { .. }
+				| Constructor::ConditionalResult { .. }
+				| Constructor::TemplateLiteral(_) => {
 					unreachable!()
 				}
 				Constructor::Awaited { on, result } => {
@@ -421,7 +1158,39 @@ fn print_type_into_buf<C: InformationChain>(
 			let prototype =
 				info_chain.get_chain_of_info().find_map(|info| info.prototypes.get(&ty).copied());
 
-			if let Some(TypeId::ARRAY_TYPE) = prototype {
+			if let Some(tuple) = types.get_tuple(ty) {
+				buf.push('[');
+				for (more, element) in tuple.elements.iter().nendiate() {
+					if let TupleElementKind::Rest = element.kind {
+						buf.push_str("...");
+					}
+					if let Some(ref label) = element.label {
+						buf.push_str(label);
+						if let TupleElementKind::Optional = element.kind {
+							buf.push('?');
+						}
+						buf.push_str(": ");
+					}
+					if let TupleElementKind::Rest = element.kind {
+						buf.push_str("Array<");
+						print_type_into_buf(
+							element.ty, buf, cycles, args, types, info_chain, debug,
+						);
+						buf.push('>');
+					} else {
+						print_type_into_buf(
+							element.ty, buf, cycles, args, types, info_chain, debug,
+						);
+					}
+					if element.label.is_none() && element.kind == TupleElementKind::Optional {
+						buf.push('?');
+					}
+					if more {
+						buf.push_str(", ");
+					}
+				}
+				buf.push(']');
+			} else if let Some(TypeId::ARRAY_TYPE) = prototype {
 				if let Some(n) = get_array_length(info_chain, ty, types) {
 					buf.push('[');
 					for i in 0..(n.into_inner() as usize) {
@@ -507,6 +1276,7 @@ fn print_type_into_buf<C: InformationChain>(
 			}
 			SpecialObjects::Function(..) => unreachable!(),
 			SpecialObjects::ClassConstructor { name, constructor: _ } => {
+				buf.push_str("typeof ");
 				buf.push_str(name);
 			}
 		},
@@ -588,18 +1358,31 @@ pub(crate) fn print_property_key_into_buf<C: InformationChain>(
 	}
 }
 
+/// Prints each event on a line. Events under conditions and iteration are indented
 pub fn debug_effects<C: InformationChain>(
 	buf: &mut String,
 	events: &[Event],
 	types: &TypeStore,
 	info: &C,
 	debug: bool,
+) {
+	debug_effects_at_depth(buf, events, types, info, debug, 0);
+}
+
+fn debug_effects_at_depth<C: InformationChain>(
+	buf: &mut String,
+	events: &[Event],
+	types: &TypeStore,
+	info: &C,
+	debug: bool,
+	depth: usize,
 ) {
 	use std::fmt::Write;
 
 	let args = GenericChain::None;
 
 	for event in events {
+		buf.push_str(&"  ".repeat(depth));
 		match event {
 			Event::ReadsReference { reference, reflects_dependency, position: _ } => {
 				write!(buf, "read '{reference:?}' into {reflects_dependency:?}").unwrap();
@@ -691,12 +1474,15 @@ pub fn debug_effects<C: InformationChain>(
 			} => {
 				buf.push_str("if ");
 				print_type_into_buf(*condition, buf, &mut HashSet::new(), args, types, info, debug);
-				buf.push_str(" then ");
-				debug_effects(buf, events_if_truthy, types, info, debug);
+				buf.push_str(" then\n");
+				debug_effects_at_depth(buf, events_if_truthy, types, info, debug, depth + 1);
 				if !else_events.is_empty() {
-					buf.push_str(" else ");
-					debug_effects(buf, else_events, types, info, debug);
+					buf.push_str(&"  ".repeat(depth));
+					buf.push_str("else\n");
+					debug_effects_at_depth(buf, else_events, types, info, debug, depth + 1);
 				}
+				buf.push_str(&"  ".repeat(depth));
+				buf.push_str("end");
 			}
 
 			Event::CreateObject {
@@ -713,7 +1499,8 @@ pub fn debug_effects<C: InformationChain>(
 			}
 			Event::Iterate { iterate_over, initial: _, kind: _ } => {
 				buf.push_str("iterate\n");
-				debug_effects(buf, iterate_over, types, info, debug);
+				debug_effects_at_depth(buf, iterate_over, types, info, debug, depth + 1);
+				buf.push_str(&"  ".repeat(depth));
 				buf.push_str("end");
 			}
 			Event::FinalEvent(FinalEvent::Throw { thrown, .. }) => {
@@ -734,3 +1521,32 @@ pub fn debug_effects<C: InformationChain>(
 		buf.push('\n');
 	}
 }
+
+/// [`debug_effects`] for a function. Functions without side effects print the kind of effect
+pub fn debug_function_effect<C: InformationChain>(
+	buf: &mut String,
+	effect: &FunctionEffect,
+	types: &TypeStore,
+	info: &C,
+	debug: bool,
+) {
+	use std::fmt::Write;
+
+	match effect {
+		FunctionEffect::SideEffects { events, .. } => {
+			debug_effects(buf, events, types, info, debug);
+		}
+		FunctionEffect::Constant(identifier) => write!(buf, "Constant: {identifier}").unwrap(),
+		FunctionEffect::InputOutput(identifier) => {
+			write!(buf, "InputOutput: {identifier}").unwrap();
+		}
+		FunctionEffect::Declared { mutated_parameters, mutated_rest_from } => {
+			write!(
+				buf,
+				"Declared: mutates {mutated_parameters:?}, rest from {mutated_rest_from:?}"
+			)
+			.unwrap();
+		}
+		FunctionEffect::Unknown => buf.push_str("unknown"),
+	}
+}
@@ -77,7 +77,8 @@ impl<'a> PropertyKey<'a> {
 				}
 				Constant::String(s) => PropertyKey::String(Cow::Owned(s.to_owned())),
 				Constant::Boolean(_) => todo!(),
-				Constant::Symbol { key: _ } => todo!(),
+				Constant::BigInt(value) => PropertyKey::String(Cow::Owned(value.to_string())),
+				Constant::Symbol { key: _ } => PropertyKey::Type(ty),
 				Constant::Undefined => todo!(),
 				Constant::Null => todo!(),
 				Constant::NaN => todo!(),
@@ -297,10 +298,10 @@ fn get_from_an_object<E: CallCheckingBehavior>(
 							| Type::Interface { .. }
 							| Type::And(_, _)
 							| Type::Or(_, _) => {
-								crate::utils::notify!(
-								    "property was {:?} {:?}, which should be NOT be able to be returned from a function",
-								    property, ty
-							    );
+								crate::utils::notify!(Trace;
+									"property was {:?} {:?}, which should be NOT be able to be returned from a function",
+									property, ty
+								);
 
 								let value = types.register_type(Type::RootPolyType(
 									crate::types::PolyNature::Open(value),
@@ -385,12 +386,46 @@ fn get_from_an_object<E: CallCheckingBehavior>(
 		}
 	}
 
+	// Keys that are a union of literals (for example `"a" | "b"`) give the union of the properties
+	// under each of them
+	if let PropertyKey::Type(key) = under {
+		let key = get_constraint(*key, types).unwrap_or(*key);
+		if let Some(keys) = get_literal_union_members(key, types) {
+			let mut result = None;
+			for key in keys {
+				let key = PropertyKey::from_type(key, types);
+				let (_, value) =
+					get_from_an_object(on, publicity, &key, environment, behavior, types)?;
+				result = Some(result.map_or(value, |acc| types.new_or_type(acc, value)));
+			}
+			return result.map(|value| (PropertyKind::Direct, value));
+		}
+	}
+
 	// ? is okay here
 	let result = get_property_unbound(on, publicity, under, types, environment).ok()?;
 
 	resolve_property_on_logical(result, on, None, environment, types, behavior)
 }
 
+/// `None` if `ty` is not a union or has members that are not string or number constants
+fn get_literal_union_members(ty: TypeId, types: &TypeStore) -> Option<Vec<TypeId>> {
+	fn collect(ty: TypeId, types: &TypeStore, members: &mut Vec<TypeId>) -> bool {
+		match types.get_type_by_id(ty) {
+			Type::Or(lhs, rhs) => collect(*lhs, types, members) && collect(*rhs, types, members),
+			Type::Constant(Constant::String(_) | Constant::Number(_)) => {
+				members.push(ty);
+				true
+			}
+			_ => false,
+		}
+	}
+
+	let mut members = Vec::new();
+	let is_union = matches!(types.get_type_by_id(ty), Type::Or(..));
+	(is_union && collect(ty, types, &mut members)).then_some(members)
+}
+
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::needless_pass_by_value)]
 fn evaluate_get_on_poly<E: CallCheckingBehavior>(
@@ -423,7 +458,7 @@ fn evaluate_get_on_poly<E: CallCheckingBehavior>(
 							let result = if let Some(arguments) = arguments {
 								substitute(value, &mut arguments.clone(), environment, types)
 							} else {
-								crate::utils::notify!("Here, getting property on {:?}", t);
+								crate::utils::notify!(Trace; "Here, getting property on {:?}", t);
 								value
 							};
 
@@ -471,7 +506,30 @@ fn evaluate_get_on_poly<E: CallCheckingBehavior>(
 					PropertyValue::Setter(_) => todo!(),
 					// Very important
 					PropertyValue::Deleted => return None,
-					PropertyValue::Dependent { .. } => todo!(),
+					PropertyValue::Dependent { truthy, otherwise, .. } => {
+						// Optional properties (where `otherwise` is `Deleted`) can be `undefined`
+						let as_value = |value: &PropertyValue| {
+							if let PropertyValue::Deleted = value {
+								TypeId::UNDEFINED_TYPE
+							} else {
+								value.as_get_type()
+							}
+						};
+						let (truthy, otherwise) = (as_value(&truthy), as_value(&otherwise));
+						let value = types.new_or_type(truthy, otherwise);
+						let result = if let Some(arguments) = arguments {
+							substitute(value, &mut arguments.clone(), environment, types)
+						} else {
+							value
+						};
+
+						types.register_type(Type::Constructor(Constructor::Property {
+							on,
+							under: under.into_owned(),
+							result,
+							bind_this: true,
+						}))
+					}
 				})
 			}
 			Logical::Or { based_on, left, right } => {
@@ -502,7 +560,7 @@ fn evaluate_get_on_poly<E: CallCheckingBehavior>(
 						resolve_logical_with_poly(rhs, on, under, arguments, environment, types)?;
 					Some(types.new_conditional_type(based_on, lhs, rhs))
 				} else {
-					crate::utils::notify!("TODO emit some diagnostic about missing");
+					crate::utils::notify!(Trace; "TODO emit some diagnostic about missing");
 					None
 				}
 			}
@@ -523,7 +581,21 @@ fn evaluate_get_on_poly<E: CallCheckingBehavior>(
 		}
 	}
 
-	let fact = get_property_unbound(on, publicity, &under, types, top_environment).ok()?;
+	// `undefined` is falsy, so it is not looked up in a branch conditional on the value. `on` is kept
+	// for the dependent result
+	let lookup_on = if top_environment.is_antecedent(on) {
+		crate::types::remove_undefined(on, types).unwrap_or(on)
+	} else {
+		on
+	};
+	let fact = match get_property_unbound(lookup_on, publicity, &under, types, top_environment) {
+		Ok(fact) => fact,
+		// Any property could be on a value constrained to `any`
+		Err(_) if get_constraint(on, types) == Some(TypeId::ANY_TYPE) => {
+			Logical::Pure(PropertyValue::Value(TypeId::ANY_TYPE))
+		}
+		Err(_) => return None,
+	};
 
 	// crate::utils::notify!("unbound is is {:?}", fact);
 
@@ -554,10 +626,10 @@ pub(crate) fn set_property<E: CallCheckingBehavior>(
 	types: &mut TypeStore,
 	setter_position: Option<SpanWithSource>,
 ) -> Result<Option<TypeId>, SetPropertyError> {
-	// TODO
-	// if environment.is_not_writeable(on, under) {
-	// 	return Err(SetPropertyError::NotWriteable);
-	// }
+	// From `Object.freeze`, `as const` and `Readonly<T>`
+	if crate::features::objects::is_readonly(on, environment, types) {
+		return Err(SetPropertyError::NotWriteable);
+	}
 
 	// if E::CHECK_PARAMETERS {
 	if let Some(constraint) = environment.get_object_constraint(on) {
@@ -679,7 +751,7 @@ pub(crate) fn set_property<E: CallCheckingBehavior>(
 			),
 			Logical::Or { .. } => todo!(),
 			Logical::Implies { on: _implies_on, antecedent: _ } => {
-				crate::utils::notify!("Check that `implies_on` could be a setter here");
+				crate::utils::notify!(Trace; "Check that `implies_on` could be a setter here");
 				let info = behavior.get_latest_info(environment);
 				info.current_properties.entry(on).or_default().push((
 					publicity,
@@ -9,6 +9,7 @@ use crate::{
 		Logical, PossibleLogical,
 	},
 	features::{
+		constant_functions::ConstantFunctionRegistry,
 		functions::{ClosureId, FunctionBehavior},
 		objects::SpecialObjects,
 	},
@@ -16,13 +17,13 @@ use crate::{
 		get_structure_arguments_based_on_object_constraint, FunctionType, GenericChain,
 		GenericChainLink, PolyNature, Type,
 	},
-	Environment, FunctionId, LocalInformation, TypeId,
+	CancellationToken, Environment, FunctionId, LocalInformation, TypeId,
 };
 
 use super::{
 	get_constraint, poly_types::generic_type_arguments::StructureGenericArguments,
-	properties::PropertyKey, Constructor, LookUpGeneric, LookUpGenericMap, StructureGenerics,
-	TypeRelationOperator,
+	properties::PropertyKey, tuples::TupleType, Constructor, LookUpGeneric, LookUpGenericMap,
+	StructureGenerics, TypeRelationOperator,
 };
 
 /// Holds all the types. Eventually may be split across modules
@@ -41,6 +42,24 @@ pub struct TypeStore {
 	/// Set after the interface [`Type`] is created, so here
 	interface_type_parameter_extends: HashMap<TypeId, TypeId>,
 
+	/// (base, name) of types created by `Nominal`
+	nominal_types: HashMap<(TypeId, String), TypeId>,
+
+	/// Elements of tuple types. See [`super::tuples`]
+	tuples: HashMap<TypeId, TupleType>,
+
+	/// Defaults of the type parameters of interfaces, classes and aliases. Set after the
+	/// parameters are created, as defaults can reference earlier parameters
+	type_parameter_defaults: HashMap<TypeId, TypeId>,
+
+	/// `new (...): T` members of interfaces and object literal types
+	construct_signatures: HashMap<TypeId, FunctionId>,
+
+	/// So that identical unions, intersections and constants share a [`TypeId`]. See
+	/// [`TypeStore::register_type`]. They are shared between modules, so do not belong to the
+	/// module that first created them
+	interned: HashMap<InternedType, TypeId>,
+
 	/// Contains all the function types
 	///
 	/// TODO is there a faster alternative to a [`HashMap`] like how [`Type`]s are stored in a [`Vec`]
@@ -56,6 +75,12 @@ pub struct TypeStore {
 
 	/// TODO not best place but is passed through everything so
 	pub(crate) closure_counter: u32,
+
+	/// From [`crate::TypeCheckOptions::constant_functions`]. Here as it is passed through everything
+	pub(crate) constant_functions: ConstantFunctionRegistry,
+
+	/// From [`crate::TypeCheckOptions::cancellation`], for the same reason
+	pub(crate) cancellation: CancellationToken,
 }
 
 impl Default for TypeStore {
@@ -82,7 +107,7 @@ impl Default for TypeStore {
 			Type::Interface { name: "object".to_owned(), parameters: None, nominal: false },
 			Type::Class { name: "Function".to_owned(), parameters: None },
 			Type::Class { name: "RegExp".to_owned(), parameters: None },
-			Type::Or(TypeId::STRING_TYPE, TypeId::NUMBER_TYPE),
+			Type::Class { name: "symbol".to_owned(), parameters: None },
 			// true
 			Type::Constant(crate::Constant::Boolean(true)),
 			// false
@@ -103,12 +128,9 @@ impl Default for TypeStore {
 				// TODO
 				eager_fixed: TypeId::ANY_TYPE,
 			}),
-			// TODO Symbols, needs Constant::Symbol
-			Type::AliasTo {
-				name: "SymbolToPrimitive".into(),
-				to: TypeId::ANY_TYPE,
-				parameters: None,
-			},
+			Type::Constant(crate::Constant::Symbol { key: "Symbol.toPrimitive".into() }),
+			Type::Constant(crate::Constant::Symbol { key: "Symbol.iterator".into() }),
+			Type::Constant(crate::Constant::Symbol { key: "Symbol.hasInstance".into() }),
 			// TODO WIP
 			Type::AliasTo {
 				name: "Literal".into(),
@@ -120,11 +142,31 @@ impl Default for TypeStore {
 				to: TypeId::T_TYPE,
 				parameters: Some(vec![TypeId::T_TYPE]),
 			},
+			Type::AliasTo {
+				name: "Nominal".into(),
+				to: TypeId::T_TYPE,
+				parameters: Some(vec![TypeId::T_TYPE]),
+			},
+			Type::AliasTo {
+				name: "Not".into(),
+				to: TypeId::T_TYPE,
+				parameters: Some(vec![TypeId::T_TYPE]),
+			},
+			Type::Class { name: "bigint".to_owned(), parameters: None },
 		];
 
 		// Check that above is correct, TODO eventually a macro
 		assert_eq!(types.len(), TypeId::INTERNAL_TYPE_COUNT);
 
+		let interned = types
+			.iter()
+			.enumerate()
+			.filter_map(|(idx, ty)| {
+				let id = TypeId(u16::try_from(idx).expect("too many types!"));
+				Some((InternedType::new(ty)?, id))
+			})
+			.collect();
+
 		let lookup_generic_map = HashMap::from_iter([(
 			TypeId::ARRAY_TYPE,
 			SmallMap::from_iter([(TypeId::T_TYPE, LookUpGeneric::NumberPropertyOfSelf)]),
@@ -140,6 +182,41 @@ impl Default for TypeStore {
 			closure_counter: 0,
 			interface_extends: Default::default(),
 			interface_type_parameter_extends: Default::default(),
+			nominal_types: Default::default(),
+			tuples: Default::default(),
+			type_parameter_defaults: Default::default(),
+			construct_signatures: Default::default(),
+			interned,
+			constant_functions: Default::default(),
+			cancellation: Default::default(),
+		}
+	}
+}
+
+/// The types and functions of a [`TypeStore`] at a point. See [`TypeStore::restore`]
+pub(crate) struct TypeStoreSnapshot {
+	types: usize,
+	functions: HashSet<FunctionId>,
+	called_functions: HashSet<FunctionId>,
+}
+
+/// Types that are equal by their structure (rather than where they were created)
+#[derive(PartialEq, Eq, Hash, Debug, binary_serialize_derive::BinarySerializable)]
+enum InternedType {
+	Or(TypeId, TypeId),
+	And(TypeId, TypeId),
+	Constant(crate::Constant),
+}
+
+impl InternedType {
+	fn new(ty: &Type) -> Option<Self> {
+		match ty {
+			Type::Or(lhs, rhs) => Some(Self::Or(*lhs, *rhs)),
+			Type::And(lhs, rhs) => Some(Self::And(*lhs, *rhs)),
+			// Symbols are unique
+			Type::Constant(crate::Constant::Symbol { .. }) => None,
+			Type::Constant(constant) => Some(Self::Constant(constant.clone())),
+			_ => None,
 		}
 	}
 }
@@ -168,12 +245,54 @@ impl TypeStore {
 		}
 	}
 
+	/// Unions, intersections and (non symbol) constants that have already been registered return the
+	/// existing [`TypeId`]
 	pub(crate) fn register_type(&mut self, ty: Type) -> TypeId {
+		let interned = InternedType::new(&ty);
+		if let Some(existing) = interned.as_ref().and_then(|interned| self.interned.get(interned)) {
+			return *existing;
+		}
+
 		let id = TypeId(self.types.len().try_into().expect("too many types!"));
 		self.types.push(ty);
+		if let Some(interned) = interned {
+			self.interned.insert(interned, id);
+		}
 		id
 	}
 
+	pub(crate) fn snapshot(&self) -> TypeStoreSnapshot {
+		TypeStoreSnapshot {
+			types: self.types.len(),
+			functions: self.functions.keys().copied().collect(),
+			called_functions: self.called_functions.clone(),
+		}
+	}
+
+	/// Removes the types and functions created after `snapshot`. Changes to existing types and
+	/// functions are not undone
+	pub(crate) fn restore(&mut self, snapshot: TypeStoreSnapshot) {
+		let TypeStoreSnapshot { types, functions, called_functions } = snapshot;
+		let existing = |id: &TypeId| usize::from(id.0) < types;
+
+		self.types.truncate(types);
+		self.lookup_generic_map.retain(|id, _| existing(id));
+		self.interface_extends.retain(|id, _| existing(id));
+		self.interface_type_parameter_extends.retain(|id, _| existing(id));
+		self.nominal_types.retain(|_, id| existing(id));
+		self.tuples.retain(|id, _| existing(id));
+		self.type_parameter_defaults.retain(|id, _| existing(id));
+		self.construct_signatures.retain(|id, _| existing(id));
+		self.interned.retain(|_, id| existing(id));
+		self.functions.retain(|id, _| functions.contains(id));
+		self.called_functions = called_functions;
+	}
+
+	#[must_use]
+	pub fn count_of_types(&self) -> usize {
+		self.types.len()
+	}
+
 	#[must_use]
 	pub fn get_type_by_id(&self, id: TypeId) -> &Type {
 		&self.types[id.0 as usize]
@@ -209,6 +328,15 @@ impl TypeStore {
 			return Err(());
 		}
 
+		// `"a" & Not<"b">` is `"a"` and `"a" & Not<"a">` is `never`
+		for (constant, other) in [(lhs, rhs), (rhs, lhs)] {
+			if let (Type::Constant(_), Some(negated)) =
+				(self.get_type_by_id(constant), self.get_negated(other))
+			{
+				return if constant == negated { Err(()) } else { Ok(constant) };
+			}
+		}
+
 		// (left and right) distributivity.
 		let result = if let Type::Or(or_lhs, or_rhs) = left_ty {
 			let (or_lhs, or_rhs) = (*or_lhs, *or_rhs);
@@ -228,6 +356,35 @@ impl TypeStore {
 		Ok(result)
 	}
 
+	/// For `Not<T>` returns `T`
+	#[must_use]
+	pub fn get_negated(&self, ty: TypeId) -> Option<TypeId> {
+		if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::NOT_RESTRICTION,
+			arguments,
+		})) = self.get_type_by_id(ty)
+		{
+			arguments.get_structure_restriction(TypeId::T_TYPE)
+		} else {
+			None
+		}
+	}
+
+	/// For `Promise<T>` (or a value constrained to it) returns `T`
+	#[must_use]
+	pub fn get_promise_value(&self, ty: TypeId) -> Option<TypeId> {
+		let constraint = get_constraint(ty, self).unwrap_or(ty);
+		if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::PROMISE_TYPE,
+			arguments,
+		})) = self.get_type_by_id(constraint)
+		{
+			arguments.get_structure_restriction(TypeId::T_TYPE)
+		} else {
+			None
+		}
+	}
+
 	/// TODO temp
 	#[must_use]
 	pub fn into_vec_temp(self) -> Vec<(TypeId, Type)> {
@@ -433,6 +590,14 @@ impl TypeStore {
 					})
 					.ok_or(crate::context::Missing::None)
 			}
+			// `Readonly<T>` has the properties of `T`
+			Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+				on: TypeId::READONLY_RESTRICTION,
+				arguments,
+			})) => {
+				let inner = arguments.get_structure_restriction(TypeId::T_TYPE).unwrap();
+				self.get_fact_about_type(info_chain, inner, on_type_arguments, resolver, data)
+			}
 			Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
 				on: base,
 				arguments,
@@ -626,6 +791,10 @@ impl TypeStore {
 				.map(Logical::Pure)
 				.or_else(|| {
 					let backing_type = cst.get_backing_type_id();
+					// `undefined` and `null` are their own backing type
+					if backing_type == on {
+						return None;
+					}
 					self.get_fact_about_type(
 						info_chain,
 						backing_type,
@@ -709,7 +878,7 @@ impl TypeStore {
 				Logical::Implies { .. } => todo!(),
 			}
 		} else {
-			crate::utils::notify!("Error: no index on type annotation");
+			crate::utils::notify!(Warn; "Error: no index on type annotation");
 			TypeId::ERROR_TYPE
 		}
 	}
@@ -743,6 +912,27 @@ impl TypeStore {
 		self.register_type(ty)
 	}
 
+	pub fn new_promise_type(&mut self, value: TypeId, position: SpanWithSource) -> TypeId {
+		let ty = Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::PROMISE_TYPE,
+			arguments: StructureGenericArguments::ExplicitRestrictions(FromIterator::from_iter([
+				(TypeId::T_TYPE, (value, position)),
+			])),
+		}));
+		self.register_type(ty)
+	}
+
+	/// `Readonly<T>`. For `readonly T[]` and `ReadonlyArray<T>`, `on` is an array type
+	pub fn new_readonly_type(&mut self, on: TypeId, position: SpanWithSource) -> TypeId {
+		let ty = Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::READONLY_RESTRICTION,
+			arguments: StructureGenericArguments::ExplicitRestrictions(FromIterator::from_iter([
+				(TypeId::T_TYPE, (on, position)),
+			])),
+		}));
+		self.register_type(ty)
+	}
+
 	/// TODO WIP
 	pub fn new_open_type(&mut self, base: TypeId) -> TypeId {
 		self.register_type(Type::RootPolyType(PolyNature::Open(base)))
@@ -757,7 +947,67 @@ impl TypeStore {
 		self.interface_type_parameter_extends.insert(ty, constraint);
 	}
 
+	/// From [`Self::modify_interface_type_parameter_constraint`]
+	#[must_use]
+	pub fn get_interface_type_parameter_constraint(&self, ty: TypeId) -> Option<TypeId> {
+		self.interface_type_parameter_extends.get(&ty).copied()
+	}
+
 	/// *Dangerous* type modifying types. TODO this might be modified in the future
+	/// For `Nominal<T, Name>`. A nominal interface that extends `base`, so values of `base` are
+	/// not assignable to it (without a cast) but it is assignable to `base`. Uses of the same base
+	/// and name give the same type
+	pub(crate) fn new_nominal_type(&mut self, base: TypeId, name: String) -> TypeId {
+		if let Some(existing) = self.nominal_types.get(&(base, name.clone())) {
+			return *existing;
+		}
+		let ty = self.register_type(Type::Interface {
+			name: name.clone(),
+			parameters: None,
+			nominal: true,
+		});
+		self.interface_extends.insert(ty, base);
+		self.nominal_types.insert((base, name), ty);
+		ty
+	}
+
+	/// The base of a type created by [`Self::new_nominal_type`] (or an alias to one)
+	pub(crate) fn get_nominal_base(&self, mut ty: TypeId) -> Option<TypeId> {
+		while let Type::AliasTo { to, parameters: None, .. } = self.get_type_by_id(ty) {
+			ty = *to;
+		}
+		self.nominal_types.iter().find_map(|((base, _), nominal)| (*nominal == ty).then_some(*base))
+	}
+
+	pub(crate) fn register_tuple(&mut self, ty: TypeId, tuple: TupleType) {
+		self.tuples.insert(ty, tuple);
+	}
+
+	#[must_use]
+	pub fn get_tuple(&self, ty: TypeId) -> Option<&TupleType> {
+		self.tuples.get(&ty)
+	}
+
+	pub(crate) fn set_type_parameter_default(&mut self, parameter: TypeId, default: TypeId) {
+		self.type_parameter_defaults.insert(parameter, default);
+	}
+
+	#[must_use]
+	pub fn get_type_parameter_default(&self, parameter: TypeId) -> Option<TypeId> {
+		self.type_parameter_defaults.get(&parameter).copied()
+	}
+
+	pub(crate) fn set_construct_signature(&mut self, on: TypeId, signature: FunctionType) {
+		let id = signature.id;
+		self.functions.insert(id, signature);
+		self.construct_signatures.insert(on, id);
+	}
+
+	#[must_use]
+	pub fn get_construct_signature(&self, on: TypeId) -> Option<FunctionId> {
+		self.construct_signatures.get(&on).copied()
+	}
+
 	pub(crate) fn set_extends_on_interface(&mut self, interface_type: TypeId, extends: TypeId) {
 		self.interface_extends.insert(interface_type, extends);
 	}
@@ -4,12 +4,18 @@ use source_map::SpanWithSource;
 
 use crate::{
 	context::{
-		information::{get_property_unbound, InformationChain, Publicity},
+		information::{get_properties_on_type, get_property_unbound, InformationChain, Publicity},
 		Environment, GeneralContext, Logical,
 	},
-	features::objects::SpecialObjects,
+	features::{
+		objects::{is_array_like, SpecialObjects},
+		template_literal::template_literal_matches_string,
+	},
 	types::{
-		poly_types::generic_type_arguments::StructureGenericArguments, printing::print_type,
+		calling::get_tuple_elements,
+		poly_types::generic_type_arguments::StructureGenericArguments,
+		printing::print_type,
+		tuples::{self, TupleElementKind, TupleType},
 		GenericChainLink, TypeStore,
 	},
 	PropertyValue, TypeId,
@@ -82,6 +88,101 @@ pub fn type_is_subtype<'a, T: SubTypeBehavior<'a>>(
 	)
 }
 
+/// Assignability questions for tools built on the checker. These do not add restrictions or
+/// diagnostics
+impl TypeStore {
+	/// Whether a value of type `ty` can be assigned to something of type `base` (`ty <: base`).
+	/// Equivalent to `ty satisfies base`
+	#[must_use]
+	pub fn is_subtype(&self, ty: TypeId, base: TypeId, environment: &Environment) -> bool {
+		if ty == TypeId::ERROR_TYPE || base == TypeId::ERROR_TYPE {
+			return false;
+		}
+		let mut behavior = BasicEquality {
+			add_property_restrictions: false,
+			position: source_map::Nullable::NULL,
+			object_constraints: Default::default(),
+			allow_errors: false,
+		};
+		let result = type_is_subtype(base, ty, &mut behavior, environment, self);
+		matches!(result, SubTypeResult::IsSubType)
+	}
+
+	/// Whether no value can be of both types (e.g. `string` and `number` or `"a"` and `"b"`). Only
+	/// `true` when that is known, so (non subtype) object types are not disjoint as a value could
+	/// have the properties of both
+	#[must_use]
+	pub fn is_disjoint(&self, lhs: TypeId, rhs: TypeId, environment: &Environment) -> bool {
+		if lhs == TypeId::NEVER_TYPE || rhs == TypeId::NEVER_TYPE {
+			return true;
+		}
+		let unknown = [TypeId::ANY_TYPE, TypeId::ERROR_TYPE];
+		if lhs == rhs || unknown.contains(&lhs) || unknown.contains(&rhs) {
+			return false;
+		}
+		// Dependent types are not valid on the left of subtyping, so they are compared through
+		// their constraint. If it is not known then they are not disjoint
+		let is_dependent =
+			|ty| matches!(self.get_type_by_id(ty), Type::RootPolyType(_) | Type::Constructor(_));
+		if is_dependent(lhs) {
+			return self
+				.get_known_constraint(lhs)
+				.is_some_and(|on| on != lhs && self.is_disjoint(on, rhs, environment));
+		}
+		if is_dependent(rhs) {
+			return self.is_disjoint(rhs, lhs, environment);
+		}
+		if self.is_subtype(lhs, rhs, environment) || self.is_subtype(rhs, lhs, environment) {
+			return false;
+		}
+
+		match (self.get_type_by_id(lhs), self.get_type_by_id(rhs)) {
+			(Type::Or(left, right), _) => {
+				self.is_disjoint(*left, rhs, environment)
+					&& self.is_disjoint(*right, rhs, environment)
+			}
+			(Type::And(left, right), _) => {
+				self.is_disjoint(*left, rhs, environment)
+					|| self.is_disjoint(*right, rhs, environment)
+			}
+			(Type::AliasTo { to, .. }, _) => self.is_disjoint(*to, rhs, environment),
+			(_, Type::Or(..) | Type::And(..) | Type::AliasTo { .. }) => {
+				self.is_disjoint(rhs, lhs, environment)
+			}
+			// As neither is a subtype of the other, different primitives or different constants.
+			// Objects cannot be primitives
+			_ => self.get_primitive(lhs).is_some() || self.get_primitive(rhs).is_some(),
+		}
+	}
+
+	fn get_primitive(&self, ty: TypeId) -> Option<TypeId> {
+		match self.get_type_by_id(ty) {
+			Type::Constant(constant) => Some(constant.get_backing_type_id()),
+			_ => [
+				TypeId::BOOLEAN_TYPE,
+				TypeId::NUMBER_TYPE,
+				TypeId::STRING_TYPE,
+				TypeId::UNDEFINED_TYPE,
+				TypeId::NULL_TYPE,
+				TypeId::SYMBOL_TYPE,
+			]
+			.contains(&ty)
+			.then_some(ty),
+		}
+	}
+
+	/// [`get_constraint`] without the unimplemented case
+	fn get_known_constraint(&self, ty: TypeId) -> Option<TypeId> {
+		if let Type::RootPolyType(PolyNature::StructureGeneric { constrained: true, .. }) =
+			self.get_type_by_id(ty)
+		{
+			None
+		} else {
+			get_constraint(ty, self)
+		}
+	}
+}
+
 /// Vec as it needs to do a sequential removal
 pub type AlreadyChecked = Vec<(TypeId, TypeId)>;
 
@@ -99,7 +200,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 ) -> SubTypeResult {
 	{
 		let debug = true;
-		crate::utils::notify!(
+		crate::utils::notify!(Trace;
 			"Checking {} :>= {}",
 			print_type(base_type, types, environment, debug),
 			print_type(ty, types, environment, debug)
@@ -110,6 +211,11 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 		return SubTypeResult::IsSubType;
 	}
 
+	// Assumed to be a subtype, so that no errors are raised for the rest of the check
+	if types.cancellation.is_cancelled() {
+		return SubTypeResult::IsSubType;
+	}
+
 	if base_type == TypeId::ANY_TYPE || ty == TypeId::NEVER_TYPE {
 		return SubTypeResult::IsSubType;
 	}
@@ -134,7 +240,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 	match right_ty {
 		Type::Or(left, right) => {
 			let right = *right;
-			crate::utils::notify!("OR RHS: left and right");
+			crate::utils::notify!(Trace; "OR RHS: left and right");
 			let left_result = type_is_subtype_with_generics(
 				base_type,
 				base_structure_arguments,
@@ -194,6 +300,40 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 		// 		)
 		// 	};
 		// }
+		Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+			on: TypeId::READONLY_RESTRICTION,
+			arguments,
+		})) => {
+			let right = arguments.get_structure_restriction(TypeId::T_TYPE).unwrap();
+			// `readonly T[]` cannot be used where the array could be modified
+			return if let Some(left) = super::get_readonly_inner(base_type, types) {
+				type_is_subtype_with_generics(
+					left,
+					base_structure_arguments,
+					right,
+					ty_structure_arguments,
+					behavior,
+					environment,
+					types,
+					mode,
+					already_checked,
+				)
+			} else if is_array_like(right, environment, types) {
+				SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
+			} else {
+				type_is_subtype_with_generics(
+					base_type,
+					base_structure_arguments,
+					right,
+					ty_structure_arguments,
+					behavior,
+					environment,
+					types,
+					mode,
+					already_checked,
+				)
+			};
+		}
 		Type::Constructor(Constructor::StructureGenerics(..)) => {}
 		Type::RootPolyType(..) | Type::Constructor(..) => {
 			if let Some(args) =
@@ -270,6 +410,62 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 		_ => (),
 	}
 
+	if let Some(left_constructor) = types.get_construct_signature(base_type) {
+		// Only classes and other types with construct signatures can be called with `new`
+		let mut right = get_constraint(ty, types).unwrap_or(ty);
+		// Classes returned from functions (for example mixins) have their arguments curried
+		if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics { on, .. })) =
+			types.get_type_by_id(right)
+		{
+			right = *on;
+		}
+		let right_constructor = match types.get_type_by_id(right) {
+			Type::SpecialObject(SpecialObjects::ClassConstructor { constructor, .. }) => {
+				let prototype = PropertyKey::String("prototype".into());
+				let instance =
+					get_property_unbound(right, Publicity::Public, &prototype, types, environment);
+				if let Ok(Logical::Pure(PropertyValue::Value(instance))) = instance {
+					Some((*constructor, Some(instance)))
+				} else {
+					None
+				}
+			}
+			Type::Interface { .. } | Type::Object(..) => {
+				types.get_construct_signature(right).map(|signature| (signature, None))
+			}
+			_ => None,
+		};
+		let Some(right_constructor) = right_constructor else {
+			return SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch);
+		};
+		let result = subtype_function_types(
+			left_constructor,
+			base_structure_arguments,
+			right_constructor,
+			ty_structure_arguments,
+			behavior,
+			environment,
+			types,
+			mode,
+			already_checked,
+		);
+		if let SubTypeResult::IsNotSubType(_) = result {
+			return result;
+		}
+		// Static members
+		return subtype_properties(
+			base_type,
+			base_structure_arguments,
+			ty,
+			ty_structure_arguments,
+			behavior,
+			environment,
+			types,
+			mode,
+			already_checked,
+		);
+	}
+
 	match left_ty {
 		Type::FunctionReference(left_func)
 		| Type::SpecialObject(SpecialObjects::Function(left_func, _)) => subtype_function(
@@ -284,18 +480,33 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 		),
 		Type::Constant(lhs) => {
 			if let Type::Constant(rhs) = right_ty {
-				if lhs == rhs {
+				// Symbols are unique, so different types are never equal
+				if lhs == rhs && !matches!(lhs, crate::Constant::Symbol { .. }) {
 					SubTypeResult::IsSubType
 				} else {
 					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 				}
 			} else {
 				// TODO what about if LHS has inferred constraint
-				crate::utils::notify!("Constant {:?} against RHS {:#?}", lhs, right_ty);
+				crate::utils::notify!(Trace; "Constant {:?} against RHS {:#?}", lhs, right_ty);
 				SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 			}
 		}
 		Type::Object(..) => {
+			if let Some(tuple) = types.get_tuple(base_type) {
+				return subtype_tuple(
+					tuple,
+					base_structure_arguments,
+					ty,
+					ty_structure_arguments,
+					behavior,
+					environment,
+					types,
+					mode,
+					already_checked,
+				);
+			}
+
 			let result = subtype_properties(
 				base_type,
 				base_structure_arguments,
@@ -319,7 +530,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 		}
 		Type::And(left, right) => {
 			let right = *right;
-			crate::utils::notify!("AND: Checking left and right");
+			crate::utils::notify!(Trace; "AND: Checking left and right");
 			let left_result = type_is_subtype_with_generics(
 				*left,
 				base_structure_arguments,
@@ -449,7 +660,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 				// TODO what does this do
 				// TODO temp fix
 				if let Type::Constructor(c) = right_ty {
-					crate::utils::notify!("TODO right hand side maybe okay");
+					crate::utils::notify!(Trace; "TODO right hand side maybe okay");
 					if let Some(to) = c.get_base() {
 						if to == base_type {
 							return SubTypeResult::IsSubType;
@@ -474,7 +685,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 					};
 				}
 
-				crate::utils::notify!(
+				crate::utils::notify!(Trace;
 					"Subtyping LHS={:?} against RHS, without setting type arguments",
 					nature
 				);
@@ -498,6 +709,60 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 			on,
 			arguments,
 		})) => {
+			if let Some(negated) = types.get_negated(base_type) {
+				return if types.is_disjoint(negated, ty, environment) {
+					SubTypeResult::IsSubType
+				} else {
+					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
+				};
+			}
+
+			// Mutable values can be used as readonly ones
+			if let TypeId::READONLY_RESTRICTION = *on {
+				let inner = arguments.get_structure_restriction(TypeId::T_TYPE).unwrap();
+				return type_is_subtype_with_generics(
+					inner,
+					base_structure_arguments,
+					ty,
+					ty_structure_arguments,
+					behavior,
+					environment,
+					types,
+					mode,
+					already_checked,
+				);
+			}
+
+			// Tuples, including the annotation of a parameter or declared variable, are checked
+			// element-wise
+			if let TypeId::ARRAY_TYPE = *on {
+				let mut tuple_type = get_constraint(ty, types).unwrap_or(ty);
+				while let Type::AliasTo { to, .. } = types.get_type_by_id(tuple_type) {
+					tuple_type = *to;
+				}
+				if let Some(tuple) = types.get_tuple(tuple_type) {
+					let backing_type = arguments
+						.get_structure_restriction(TypeId::T_TYPE)
+						.expect("array T argument not set ?");
+					for element in &tuple.elements {
+						let result = type_is_subtype_with_generics(
+							backing_type,
+							base_structure_arguments,
+							element.ty,
+							ty_structure_arguments,
+							behavior,
+							environment,
+							types,
+							mode,
+							already_checked,
+						);
+						if let e @ SubTypeResult::IsNotSubType(_) = result {
+							return e;
+						}
+					}
+				}
+			}
+
 			if let Some(lookup) = types.lookup_generic_map.get(on) {
 				fn get_structure_generics_on(
 					r#type: &Type,
@@ -516,13 +781,13 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 				// TODO a bit of a mess
 
 				return if let Some(_sgs) = get_structure_generics_on(right_ty, *on) {
-					crate::utils::notify!("TODO here");
+					crate::utils::notify!(Trace; "TODO here");
 					SubTypeResult::IsSubType
 				} else if let Type::Object(super::ObjectNature::RealDeal) = right_ty {
 					let prototype =
 						environment.get_chain_of_info().find_map(|info| info.prototypes.get(&ty));
 
-					crate::utils::notify!("prototype is {:?}", prototype);
+					crate::utils::notify!(Trace; "prototype is {:?}", prototype);
 
 					if prototype.is_some_and(|prototype| prototype == on) {
 						for (argument, lookup) in lookup.iter() {
@@ -544,11 +809,11 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 						}
 						SubTypeResult::IsSubType
 					} else {
-						crate::utils::notify!("Here");
+						crate::utils::notify!(Trace; "Here");
 						SubTypeResult::IsSubType
 					}
 				} else {
-					crate::utils::notify!("Here");
+					crate::utils::notify!(Trace; "Here");
 					SubTypeResult::IsSubType
 				};
 			}
@@ -558,7 +823,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 					.get_structure_restriction(TypeId::T_TYPE)
 					.expect("array T argument not set ?");
 
-				crate::utils::notify!(
+				crate::utils::notify!(Trace;
 					"Array type is {}",
 					print_type(backing_type, types, environment, false)
 				);
@@ -588,7 +853,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 					let right_arg =
 						right_arguments.get_structure_restriction(TypeId::T_TYPE).unwrap();
 
-					crate::utils::notify!("{:?} :> {:?}", left_arg, right_arg);
+					crate::utils::notify!(Trace; "{:?} :> {:?}", left_arg, right_arg);
 
 					// TODO unsure about arguments here
 					type_is_subtype_with_generics(
@@ -603,7 +868,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 						already_checked,
 					)
 				} else {
-					crate::utils::notify!("Not array-ish {:?}", right_ty);
+					crate::utils::notify!(Trace; "Not array-ish {:?}", right_ty);
 					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 				}
 			} else {
@@ -657,11 +922,46 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 			}
 			Constructor::StructureGenerics(_) => unreachable!(),
 			Constructor::Awaited { .. } => todo!(),
+			Constructor::TemplateLiteral(parts) => match right_ty {
+				Type::Constant(crate::Constant::String(value)) => {
+					if template_literal_matches_string(parts, value, types) {
+						SubTypeResult::IsSubType
+					} else {
+						SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
+					}
+				}
+				Type::Constructor(Constructor::TemplateLiteral(right_parts)) => {
+					if parts == right_parts {
+						SubTypeResult::IsSubType
+					} else {
+						SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
+					}
+				}
+				Type::RootPolyType(_) | Type::Constructor(_) => {
+					// Skipped by the eager dependent RHS lookup above
+					if let Some(constraint) = get_constraint(ty, types) {
+						type_is_subtype_with_generics(
+							base_type,
+							base_structure_arguments,
+							constraint,
+							ty_structure_arguments,
+							behavior,
+							environment,
+							types,
+							mode,
+							already_checked,
+						)
+					} else {
+						SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
+					}
+				}
+				_ => SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch),
+			},
 		},
 		// TODO aliasing might work differently
 		Type::AliasTo { to, parameters, name: _ } => {
 			if base_type == TypeId::LITERAL_RESTRICTION {
-				crate::utils::notify!("Here");
+				crate::utils::notify!(Trace; "Here");
 				return if let Type::Constant(rhs_constant) = right_ty {
 					type_is_subtype_with_generics(
 						*to,
@@ -682,7 +982,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 			}
 
 			let base_structure_arguments = if let Some(parameters) = parameters {
-				crate::utils::notify!("Skipping looking at parameters {:?}", parameters);
+				crate::utils::notify!(Trace; "Skipping looking at parameters {:?}", parameters);
 				base_structure_arguments
 			} else {
 				base_structure_arguments
@@ -709,6 +1009,54 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 				}
 			}
+			// For example `string & Not<"">`
+			Type::And(left, right) => {
+				let right = *right;
+				let left_result = type_is_subtype_with_generics(
+					base_type,
+					base_structure_arguments,
+					*left,
+					ty_structure_arguments,
+					behavior,
+					environment,
+					types,
+					mode,
+					already_checked,
+				);
+				if let SubTypeResult::IsSubType = left_result {
+					left_result
+				} else {
+					type_is_subtype_with_generics(
+						base_type,
+						base_structure_arguments,
+						right,
+						ty_structure_arguments,
+						behavior,
+						environment,
+						types,
+						mode,
+						already_checked,
+					)
+				}
+			}
+			// Types from `Nominal` are subtypes of their base
+			Type::Interface { .. } | Type::AliasTo { .. } => {
+				if let Some(nominal_base) = types.get_nominal_base(ty) {
+					type_is_subtype_with_generics(
+						base_type,
+						base_structure_arguments,
+						nominal_base,
+						ty_structure_arguments,
+						behavior,
+						environment,
+						types,
+						mode,
+						already_checked,
+					)
+				} else {
+					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
+				}
+			}
 			_ => SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch),
 		},
 		Type::Interface { nominal: base_type_nominal, .. } => {
@@ -720,12 +1068,13 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 				&& !matches!(
 					right_ty,
 					Type::RootPolyType(..)
-						| Type::Constructor(..) | Type::Constant(..)
+						| Type::Constructor(..)
+						| Type::Constant(..)
 						| Type::Or(..) | Type::And(..)
 				);
 
 			if skip_nominal_branch {
-				crate::utils::notify!(
+				crate::utils::notify!(Trace;
 					"Short circuited {:?} is nominal and RHS={:?}",
 					left_ty,
 					right_ty
@@ -741,11 +1090,26 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 				Type::Constant(constant) => {
 					if constant.get_backing_type_id() == base_type {
 						SubTypeResult::IsSubType
+					} else if !*base_type_nominal && matches!(constant, crate::Constant::String(_))
+					{
+						// Strings have properties (from `String`), for example for `ArrayLike<T>`
+						subtype_properties(
+							base_type,
+							base_structure_arguments,
+							ty,
+							ty_structure_arguments,
+							behavior,
+							environment,
+							types,
+							mode,
+							already_checked,
+						)
 					} else {
 						SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 					}
 				}
-				Type::Object(..) => subtype_properties(
+				// `Type::Class` is the instance type (from a construct signature)
+				Type::Object(..) | Type::Class { .. } => subtype_properties(
 					base_type,
 					base_structure_arguments,
 					ty,
@@ -757,12 +1121,12 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 					already_checked,
 				),
 				Type::SpecialObject(SpecialObjects::Function(..)) => {
-					crate::utils::notify!("TODO implement function checking");
+					crate::utils::notify!(Trace; "TODO implement function checking");
 					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 				}
 				Type::And(a, b) => {
 					// TODO more
-					crate::utils::notify!("Here LHS interface, RHS and");
+					crate::utils::notify!(Trace; "Here LHS interface, RHS and");
 					if *a == base_type || *b == base_type {
 						SubTypeResult::IsSubType
 					} else {
@@ -810,7 +1174,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 					already_checked,
 				),
 				Type::AliasTo { .. } | Type::Interface { .. } => {
-					crate::utils::notify!("lhs={:?} rhs={:?}", left_ty, right_ty);
+					crate::utils::notify!(Trace; "lhs={:?} rhs={:?}", left_ty, right_ty);
 					// TODO
 					SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch)
 				}
@@ -841,7 +1205,7 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 						let to = get_constraint(ty, types).unwrap();
 
 						if to == TypeId::ANY_TYPE {
-							crate::utils::notify!("Modify constraint for equality");
+							crate::utils::notify!(Trace; "Modify constraint for equality");
 						}
 
 						type_is_subtype_with_generics(
@@ -859,7 +1223,6 @@ pub(crate) fn type_is_subtype_with_generics<'a, T: SubTypeBehavior<'a>>(
 				}
 				Type::FunctionReference(_) => todo!(),
 				Type::SpecialObject(_) => todo!(),
-				Type::Class { .. } => todo!(),
 			}
 		}
 		Type::SpecialObject(_) => todo!(),
@@ -877,7 +1240,7 @@ fn subtype_function<'a, T: SubTypeBehavior<'a>>(
 	mode: SubTypingMode,
 	already_checked: &mut AlreadyChecked,
 ) -> SubTypeResult {
-	crate::utils::notify!("Subtyping a function");
+	crate::utils::notify!(Trace; "Subtyping a function");
 
 	let right_func = if let Type::FunctionReference(right_func)
 	| Type::SpecialObject(SpecialObjects::Function(right_func, _)) = right_ty
@@ -891,53 +1254,99 @@ fn subtype_function<'a, T: SubTypeBehavior<'a>>(
 		{
 			right_func
 		} else {
-			crate::utils::notify!("Not function after constraint!! {:?}", right_ty);
+			crate::utils::notify!(Trace; "Not function after constraint!! {:?}", right_ty);
 			return SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch);
 		}
 	} else {
-		crate::utils::notify!("Not function!! {:?}", right_ty);
+		crate::utils::notify!(Trace; "Not function!! {:?}", right_ty);
 		return SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch);
 	};
 
+	subtype_function_types(
+		left_func,
+		base_type_arguments,
+		(*right_func, None),
+		right_type_arguments,
+		behavior,
+		environment,
+		types,
+		mode,
+		already_checked,
+	)
+}
+
+/// Parameters are checked contravariantly and the return type covariantly. The return type of
+/// `right_func` can be overridden, as class constructors return their instance rather than the
+/// result of the constructor body
+#[allow(clippy::too_many_arguments)]
+fn subtype_function_types<'a, T: SubTypeBehavior<'a>>(
+	left_func: crate::FunctionId,
+	base_type_arguments: GenericChain,
+	(right_func, right_return_type): (crate::FunctionId, Option<TypeId>),
+	right_type_arguments: GenericChain,
+	behavior: &mut T,
+	environment: &Environment,
+	types: &TypeStore,
+	mode: SubTypingMode,
+	already_checked: &mut AlreadyChecked,
+) -> SubTypeResult {
+	/// The annotation of a parameter, if it is not generic
+	fn fixed_annotation(ty: TypeId, types: &TypeStore) -> Option<TypeId> {
+		if let Type::RootPolyType(PolyNature::Parameter { fixed_to }) = types.get_type_by_id(ty) {
+			let is_generic = matches!(
+				types.get_type_by_id(*fixed_to),
+				Type::RootPolyType(_) | Type::Constructor(_)
+			);
+			(!is_generic).then_some(*fixed_to)
+		} else {
+			None
+		}
+	}
+
 	let left_func = types.functions.get(&left_func).unwrap();
-	let right_func = types.functions.get(right_func).unwrap();
+	let right_func = types.functions.get(&right_func).unwrap();
 
 	for (idx, lhs_param) in left_func.parameters.parameters.iter().enumerate() {
-		match right_func.parameters.get_parameter_type_at_index(idx) {
-			Some((right_param_ty, position)) => {
-				// Reverse is important
-				let result = type_is_subtype_with_generics(
-					right_param_ty,
+		// A function with fewer parameters can be called with more arguments
+		if let Some((right_param_ty, position)) =
+			right_func.parameters.get_parameter_type_at_index(idx)
+		{
+			// Against an annotated parameter, parameters are compared by their annotations
+			// (rather than being inferred)
+			let (right_param_ty_or_annotation, lhs_param_ty) =
+				if let Some(lhs_annotation) = fixed_annotation(lhs_param.ty, types) {
+					let right_annotation =
+						fixed_annotation(right_param_ty, types).unwrap_or(right_param_ty);
+					(right_annotation, lhs_annotation)
+				} else {
+					(right_param_ty, lhs_param.ty)
+				};
+			// Reverse is important
+			let result = type_is_subtype_with_generics(
+				right_param_ty_or_annotation,
+				right_type_arguments,
+				lhs_param_ty,
+				base_type_arguments,
+				behavior,
+				environment,
+				types,
+				// !!!
+				SubTypingMode::Covariant { position },
+				already_checked,
+			);
+
+			if let err @ SubTypeResult::IsNotSubType(_) = result {
+				let lhs = print_type(right_param_ty, types, environment, true);
+				let rhs = print_type(lhs_param.ty, types, environment, true);
+				crate::utils::notify!(Trace;
+					"Parameter invalid rhs ({:?} {:?}) <- lhs ({:?} {:?})",
+					rhs,
 					right_type_arguments,
-					lhs_param.ty,
-					base_type_arguments,
-					behavior,
-					environment,
-					types,
-					// !!!
-					SubTypingMode::Covariant { position },
-					already_checked,
+					lhs,
+					base_type_arguments
 				);
-
-				if let err @ SubTypeResult::IsNotSubType(_) = result {
-					let lhs = print_type(right_param_ty, types, environment, true);
-					let rhs = print_type(lhs_param.ty, types, environment, true);
-					crate::utils::notify!(
-						"Parameter invalid rhs ({:?} {:?}) <- lhs ({:?} {:?})",
-						rhs,
-						right_type_arguments,
-						lhs,
-						base_type_arguments
-					);
-					// TODO don't short circuit
-					return err;
-				}
-			}
-			None => {
-				if !lhs_param.is_optional {
-					crate::utils::notify!("Expected parameter, for non optional parameter");
-					return SubTypeResult::IsNotSubType(NonEqualityReason::MissingParameter);
-				}
+				// TODO don't short circuit
+				return err;
 			}
 		}
 	}
@@ -950,7 +1359,7 @@ fn subtype_function<'a, T: SubTypeBehavior<'a>>(
 		let type_is_subtype_with_generics = type_is_subtype_with_generics(
 			left_func.return_type,
 			base_type_arguments,
-			right_func.return_type,
+			right_return_type.unwrap_or(right_func.return_type),
 			right_type_arguments,
 			behavior,
 			environment,
@@ -960,7 +1369,7 @@ fn subtype_function<'a, T: SubTypeBehavior<'a>>(
 		);
 
 		if let SubTypeResult::IsNotSubType(_) = type_is_subtype_with_generics {
-			crate::utils::notify!("return type invalid");
+			crate::utils::notify!(Trace; "return type invalid");
 		}
 
 		type_is_subtype_with_generics
@@ -988,11 +1397,13 @@ fn subtype_properties<'a, T: SubTypeBehavior<'a>>(
 		.flatten();
 
 	for (publicity, key, lhs_property) in reversed_flattened_properties {
-		crate::utils::notify!("key {:?} with {:?}", key, base_type_arguments);
+		crate::utils::notify!(Trace; "key {:?} with {:?}", key, base_type_arguments);
 
 		let key = match key {
 			PropertyKey::Type(ty) => PropertyKey::from_type(
-				base_type_arguments.unwrap().get_single_argument(*ty).unwrap_or(*ty),
+				base_type_arguments
+					.and_then(|arguments| arguments.get_single_argument(*ty))
+					.unwrap_or(*ty),
 				types,
 			),
 			PropertyKey::String(_) => key.clone(),
@@ -1017,6 +1428,11 @@ fn subtype_properties<'a, T: SubTypeBehavior<'a>>(
 		}
 	}
 
+	if property_errors.is_empty() && has_no_properties_in_common(base_type, ty, environment, types)
+	{
+		return SubTypeResult::IsNotSubType(NonEqualityReason::NoCommonProperties);
+	}
+
 	if property_errors.is_empty() {
 		// TODO type arguments
 		behavior.add_object_mutation_constraint(ty, base_type);
@@ -1043,6 +1459,135 @@ fn subtype_properties<'a, T: SubTypeBehavior<'a>>(
 	}
 }
 
+/// Whether `base_type` is a *weak type* (an object type with only optional properties, such as an
+/// options object) and the object `ty` has properties but none of them are on `base_type`. Such
+/// objects pass the property checks but are almost always a mistake
+fn has_no_properties_in_common(
+	base_type: TypeId,
+	ty: TypeId,
+	environment: &Environment,
+	types: &TypeStore,
+) -> bool {
+	let is_optional = |value: &PropertyValue| matches!(value, PropertyValue::Dependent { otherwise, .. } if matches!(**otherwise, PropertyValue::Deleted));
+	let base_properties = environment
+		.get_chain_of_info()
+		.filter_map(|info| info.current_properties.get(&base_type))
+		.flatten()
+		.collect::<Vec<_>>();
+
+	let is_weak = !base_properties.is_empty()
+		&& base_properties
+			.iter()
+			.all(|(_, key, value)| matches!(key, PropertyKey::String(_)) && is_optional(value))
+		&& !types.interface_extends.contains_key(&base_type);
+
+	if !(is_weak && matches!(types.get_type_by_id(ty), Type::Object(..))) {
+		return false;
+	}
+
+	let properties = get_properties_on_type(ty, types, environment);
+	!properties.is_empty()
+		&& properties
+			.iter()
+			.all(|(_, key, _)| base_properties.iter().all(|(_, base_key, _)| base_key != key))
+}
+
+/// Element-wise checking of a tuple against another tuple, an array literal or `Array<T>`
+#[allow(clippy::too_many_arguments)]
+fn subtype_tuple<'a, T: SubTypeBehavior<'a>>(
+	base: &TupleType,
+	base_type_arguments: GenericChain,
+	ty: TypeId,
+	right_type_arguments: GenericChain,
+	behavior: &mut T,
+	environment: &Environment,
+	types: &TypeStore,
+	mode: SubTypingMode,
+	already_checked: &mut AlreadyChecked,
+) -> SubTypeResult {
+	let Some(rhs) = types
+		.get_tuple(ty)
+		.cloned()
+		.or_else(|| tuples::array_as_tuple(ty, types))
+		.or_else(|| tuples::array_object_as_tuple(ty, environment, types))
+	else {
+		return SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch);
+	};
+
+	let mode = mode.one_deeper();
+	let lhs_fixed = base.fixed().collect::<Vec<_>>();
+	let rhs_fixed = rhs.fixed().collect::<Vec<_>>();
+	let mut property_errors = Vec::new();
+
+	let mut check_element = |idx: Option<usize>, lhs: TypeId, rhs: TypeId| -> Option<_> {
+		let result = type_is_subtype_with_generics(
+			lhs,
+			base_type_arguments,
+			rhs,
+			right_type_arguments,
+			behavior,
+			environment,
+			types,
+			mode,
+			already_checked,
+		);
+		if let SubTypeResult::IsNotSubType(mismatch) = result {
+			let key = idx.map_or(PropertyKey::Type(TypeId::NUMBER_TYPE), PropertyKey::from_usize);
+			let error = PropertyError::Invalid {
+				expected: TypeId::UNIMPLEMENTED_ERROR_TYPE,
+				found: TypeId::UNIMPLEMENTED_ERROR_TYPE,
+				mismatch,
+			};
+			Some((key, error))
+		} else {
+			None
+		}
+	};
+
+	for (idx, rhs_element) in rhs_fixed.iter().enumerate() {
+		if let Some(lhs_element) = lhs_fixed.get(idx) {
+			if lhs_element.kind == TupleElementKind::Required
+				&& rhs_element.kind == TupleElementKind::Optional
+			{
+				property_errors.push((PropertyKey::from_usize(idx), PropertyError::Missing));
+			} else {
+				property_errors.extend(check_element(Some(idx), lhs_element.ty, rhs_element.ty));
+			}
+		} else if let Some(lhs_rest) = base.rest() {
+			property_errors.extend(check_element(Some(idx), lhs_rest, rhs_element.ty));
+		} else {
+			// Too many elements
+			return SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch);
+		}
+	}
+
+	let rhs_rest = rhs.rest();
+	for (idx, lhs_element) in lhs_fixed.iter().enumerate().skip(rhs_fixed.len()) {
+		if lhs_element.kind == TupleElementKind::Required {
+			property_errors.push((PropertyKey::from_usize(idx), PropertyError::Missing));
+		} else if let Some(rhs_rest) = rhs_rest {
+			property_errors.extend(check_element(Some(idx), lhs_element.ty, rhs_rest));
+		}
+	}
+
+	if let Some(rhs_rest) = rhs_rest {
+		if let Some(lhs_rest) = base.rest() {
+			property_errors.extend(check_element(None, lhs_rest, rhs_rest));
+		} else {
+			// Could have any number of elements
+			return SubTypeResult::IsNotSubType(NonEqualityReason::Mismatch);
+		}
+	}
+
+	if property_errors.is_empty() {
+		SubTypeResult::IsSubType
+	} else {
+		SubTypeResult::IsNotSubType(NonEqualityReason::PropertiesInvalid {
+			errors: property_errors,
+		})
+	}
+}
+
 #[allow(clippy::too_many_arguments)]
 fn check_lhs_property_is_super_type_of_rhs<'a, T: SubTypeBehavior<'a>>(
 	key: &PropertyKey<'_>,
@@ -1058,9 +1603,38 @@ fn check_lhs_property_is_super_type_of_rhs<'a, T: SubTypeBehavior<'a>>(
 	already_checked: &mut AlreadyChecked,
 ) -> Result<(), PropertyError> {
 	match lhs_property {
+		// A number index signature (for example from `ArrayLike<T>`) is checked against each of the
+		// elements of array literals
+		PropertyValue::Value(lhs_value)
+			if matches!(key, PropertyKey::Type(TypeId::NUMBER_TYPE))
+				&& get_tuple_elements(ty, environment, types).is_some() =>
+		{
+			let elements = get_tuple_elements(ty, environment, types).unwrap();
+			for element in elements {
+				let result = type_is_subtype_with_generics(
+					*lhs_value,
+					base_type_arguments,
+					element,
+					right_type_arguments,
+					behavior,
+					environment,
+					types,
+					mode,
+					already_checked,
+				);
+				if let SubTypeResult::IsNotSubType(mismatch) = result {
+					return Err(PropertyError::Invalid {
+						expected: *lhs_value,
+						found: element,
+						mismatch,
+					});
+				}
+			}
+			Ok(())
+		}
 		PropertyValue::Value(lhs_value) => {
 			let rhs_property = get_property_unbound(ty, publicity, key, types, environment);
-			crate::utils::notify!("looking for {:?} found {:?}", key, rhs_property);
+			crate::utils::notify!(Trace; "looking for {:?} found {:?}", key, rhs_property);
 
 			match rhs_property {
 				Ok(rhs_property) => {
@@ -1150,7 +1724,8 @@ fn check_logical_property<'a, T: SubTypeBehavior<'a>>(
 ) -> SubTypeResult {
 	match rhs_property {
 		Logical::Pure(rhs_property) => {
-			let rhs_type = rhs_property.as_set_type();
+			// The value read from the property (so the return type of getters)
+			let rhs_type = rhs_property.as_get_type();
 			// crate::utils::notify!(
 			// 	"Checking {} with {}, against {}, left={:?}",
 			// 	print_type(key, types, environment, true),
@@ -1247,6 +1822,7 @@ impl NonEqualityReason {
 		match self {
 			NonEqualityReason::GenericParameterMismatch
 			| NonEqualityReason::MissingParameter
+			| NonEqualityReason::NoCommonProperties
 			| NonEqualityReason::Mismatch => Vec::new(),
 			NonEqualityReason::PropertiesInvalid { errors } => {
 				errors.into_iter().map(|error| format!("{error:?}")).collect()
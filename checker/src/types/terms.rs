@@ -3,7 +3,7 @@ use super::TypeId;
 /// Terms
 /// TODO:
 /// - `IntoProof`
-/// - `BigInt` (<https://github.com/rust-num/num-bigint>)
+/// - Arbitrary precision `BigInt` (<https://github.com/rust-num/num-bigint>)
 /// - Separate `NotNull` term, and implement js subtyping
 ///
 /// TODO unsure about some of these
@@ -12,6 +12,10 @@ pub enum Constant {
 	Number(ordered_float::NotNan<f64>),
 	String(String),
 	Boolean(bool),
+	/// `123n`. Values outside of [`i128`] are not constant (they are [`TypeId::BIGINT_TYPE`])
+	BigInt(i128),
+	/// Each `Symbol()` call (and `unique symbol` annotation) registers a new type, so symbols
+	/// are only equal by [`TypeId`]. `key` is the description
 	Symbol {
 		key: String,
 	},
@@ -30,6 +34,7 @@ impl Constant {
 			Constant::Number(value) => value.to_string(),
 			Constant::String(value) => value.clone(),
 			Constant::Boolean(value) => if *value { "true" } else { "false" }.to_owned(),
+			Constant::BigInt(value) => value.to_string(),
 			Constant::Symbol { key } => format!("Symbol({key})"),
 			Constant::Undefined => "undefined".to_owned(),
 			Constant::Null => "null".to_owned(),
@@ -45,6 +50,7 @@ impl Constant {
 			Constant::Number(value) => value.to_string(),
 			Constant::String(value) => format!("\"{value}\""),
 			Constant::Boolean(value) => if *value { "true" } else { "false" }.to_owned(),
+			Constant::BigInt(value) => format!("{value}n"),
 			Constant::Symbol { key } => format!("Symbol({key})"),
 			Constant::Undefined => "undefined".to_owned(),
 			Constant::Null => "null".to_owned(),
@@ -58,9 +64,10 @@ impl Constant {
 			Constant::Number(_) | Constant::NaN => TypeId::NUMBER_TYPE,
 			Constant::String(_) => TypeId::STRING_TYPE,
 			Constant::Boolean(_) => TypeId::BOOLEAN_TYPE,
+			Constant::BigInt(_) => TypeId::BIGINT_TYPE,
 			Constant::Undefined => TypeId::UNDEFINED_TYPE,
 			Constant::Null => TypeId::NULL_TYPE,
-			Constant::Symbol { .. } => todo!(),
+			Constant::Symbol { .. } => TypeId::SYMBOL_TYPE,
 		}
 	}
 }
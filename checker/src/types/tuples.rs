@@ -0,0 +1,225 @@
+//! Tuple types, e.g. `[name: string, age?: number, ...rest: boolean[]]`
+//!
+//! A tuple is an object with the `Array` prototype, with a property for each element and
+//! `length`, so property access and iteration work as they do for array values. The elements are
+//! also recorded in [`TypeStore`] so that optional and rest elements can be checked
+
+use source_map::SpanWithSource;
+
+use crate::{
+	context::information::{InformationChain, Publicity},
+	features::objects::ObjectBuilder,
+	types::{
+		get_constraint,
+		properties::{PropertyKey, PropertyValue},
+		Constructor, StructureGenerics, Type, TypeStore,
+	},
+	Constant, Environment, TypeId,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, binary_serialize_derive::BinarySerializable)]
+pub enum TupleElementKind {
+	Required,
+	/// `[T?]`. May be missing (and so `undefined`)
+	Optional,
+	/// `[...T[]]`. Any number of `T`s. Only the last element can be a rest element
+	Rest,
+}
+
+#[derive(Clone, Debug, binary_serialize_derive::BinarySerializable)]
+pub struct TupleElement {
+	/// For [`TupleElementKind::Rest`] this is the item type (the `T` of `...T[]`)
+	pub ty: TypeId,
+	pub kind: TupleElementKind,
+	pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, binary_serialize_derive::BinarySerializable)]
+pub struct TupleType {
+	pub elements: Vec<TupleElement>,
+}
+
+impl TupleType {
+	/// Elements that are not rest elements
+	pub fn fixed(&self) -> impl Iterator<Item = &TupleElement> + '_ {
+		self.elements.iter().filter(|element| element.kind != TupleElementKind::Rest)
+	}
+
+	#[must_use]
+	pub fn rest(&self) -> Option<TypeId> {
+		self.elements
+			.iter()
+			.find(|element| element.kind == TupleElementKind::Rest)
+			.map(|element| element.ty)
+	}
+
+	#[must_use]
+	pub fn minimum_length(&self) -> usize {
+		self.fixed().filter(|element| element.kind == TupleElementKind::Required).count()
+	}
+
+	/// `None` if there is a rest element
+	#[must_use]
+	pub fn maximum_length(&self) -> Option<usize> {
+		self.rest().is_none().then(|| self.fixed().count())
+	}
+
+	/// The type of `tuple[index]`. `undefined` if `index` is out of bounds
+	pub fn get_element(&self, index: usize, types: &mut TypeStore) -> TypeId {
+		match self.fixed().nth(index) {
+			Some(TupleElement { ty, kind: TupleElementKind::Optional, .. }) => {
+				types.new_or_type(*ty, TypeId::UNDEFINED_TYPE)
+			}
+			Some(element) => element.ty,
+			None => self.rest().map_or(TypeId::UNDEFINED_TYPE, |rest| {
+				types.new_or_type(rest, TypeId::UNDEFINED_TYPE)
+			}),
+		}
+	}
+
+	/// The elements after `index`, for `[_, ...rest] = tuple`
+	#[must_use]
+	pub fn slice_from(&self, index: usize) -> Self {
+		let fixed = self.fixed().skip(index).cloned();
+		let rest = self.elements.iter().filter(|element| element.kind == TupleElementKind::Rest);
+		Self { elements: fixed.chain(rest.cloned()).collect() }
+	}
+}
+
+/// Creates the object for `tuple` and registers it as a tuple
+pub fn new_tuple_type(
+	tuple: TupleType,
+	position: SpanWithSource,
+	environment: &mut Environment,
+	types: &mut TypeStore,
+) -> TypeId {
+	let mut object = ObjectBuilder::new(Some(TypeId::ARRAY_TYPE), types, &mut environment.info);
+
+	for (idx, element) in tuple.fixed().enumerate() {
+		let value = if let TupleElementKind::Optional = element.kind {
+			types.new_or_type(element.ty, TypeId::UNDEFINED_TYPE)
+		} else {
+			element.ty
+		};
+		object.append(
+			environment,
+			Publicity::Public,
+			PropertyKey::from_usize(idx),
+			PropertyValue::Value(value),
+			Some(position),
+		);
+	}
+
+	if let Some(rest) = tuple.rest() {
+		object.append(
+			environment,
+			Publicity::Public,
+			PropertyKey::Type(TypeId::NUMBER_TYPE),
+			PropertyValue::Value(rest),
+			Some(position),
+		);
+	}
+
+	// The union of the possible lengths
+	let length = if let Some(maximum) = tuple.maximum_length() {
+		let mut length = None;
+		for value in tuple.minimum_length()..=maximum {
+			#[allow(clippy::cast_precision_loss)]
+			let value = types.new_constant_type(Constant::Number((value as f64).try_into().unwrap()));
+			length = Some(length.map_or(value, |length| types.new_or_type(length, value)));
+		}
+		length.unwrap()
+	} else {
+		TypeId::NUMBER_TYPE
+	};
+	object.append(
+		environment,
+		Publicity::Public,
+		PropertyKey::String("length".into()),
+		PropertyValue::Value(length),
+		None,
+	);
+
+	let ty = object.build_object();
+	types.register_tuple(ty, tuple);
+	ty
+}
+
+/// The tuple of `on` or, if it is dependent, its constraint
+pub(crate) fn get_tuple(on: TypeId, types: &TypeStore) -> Option<&TupleType> {
+	types.get_tuple(on).or_else(|| get_constraint(on, types).and_then(|on| types.get_tuple(on)))
+}
+
+/// `[...T[]]` for `Array<T>`. `None` if `on` is not an array type
+pub(crate) fn array_as_tuple(on: TypeId, types: &TypeStore) -> Option<TupleType> {
+	if let Type::Constructor(Constructor::StructureGenerics(StructureGenerics {
+		on: TypeId::ARRAY_TYPE,
+		arguments,
+	})) = types.get_type_by_id(on)
+	{
+		let item = arguments.get_structure_restriction(TypeId::T_TYPE)?;
+		Some(TupleType {
+			elements: vec![TupleElement { ty: item, kind: TupleElementKind::Rest, label: None }],
+		})
+	} else {
+		None
+	}
+}
+
+/// The elements of an array object (for example from an array literal). Items after a spread of
+/// unknown length become a rest element
+pub(crate) fn array_object_as_tuple(
+	on: TypeId,
+	info: &impl InformationChain,
+	types: &TypeStore,
+) -> Option<TupleType> {
+	let is_array = info
+		.get_chain_of_info()
+		.find_map(|info| info.prototypes.get(&on))
+		.is_some_and(|prototype| *prototype == TypeId::ARRAY_TYPE);
+	if !(is_array && matches!(types.get_type_by_id(on), Type::Object(_))) {
+		return None;
+	}
+
+	let mut items = Vec::<(usize, TypeId)>::new();
+	let mut rest = None;
+	// Properties from later (and inner) information override earlier ones
+	let properties = info
+		.get_chain_of_info()
+		.filter_map(|info| {
+			info.current_properties.get(&on).map(|properties| properties.iter().rev())
+		})
+		.flatten();
+
+	for (_, key, value) in properties {
+		let PropertyValue::Value(value) = value else { return None };
+		match key {
+			PropertyKey::String(key) => {
+				if let Ok(idx) = key.parse::<usize>() {
+					if !items.iter().any(|(existing, _)| *existing == idx) {
+						items.push((idx, *value));
+					}
+				}
+			}
+			PropertyKey::Type(_) => {
+				rest = rest.or(Some(*value));
+			}
+		}
+	}
+
+	items.sort_unstable_by_key(|(idx, _)| *idx);
+	let length = items.last().map_or(0, |(idx, _)| idx + 1);
+	let mut elements = (0..length)
+		.map(|idx| {
+			let ty = items
+				.iter()
+				.find_map(|(at, ty)| (*at == idx).then_some(*ty))
+				.unwrap_or(TypeId::UNDEFINED_TYPE);
+			TupleElement { ty, kind: TupleElementKind::Required, label: None }
+		})
+		.collect::<Vec<_>>();
+	if let Some(rest) = rest {
+		elements.push(TupleElement { ty: rest, kind: TupleElementKind::Rest, label: None });
+	}
+	Some(TupleType { elements })
+}
@@ -1,37 +1,24 @@
-static IS_DEBUG_MODE: std::sync::Mutex<Option<bool>> = std::sync::Mutex::new(None);
-
-#[allow(clippy::manual_is_variant_and)]
-pub(crate) fn is_debug_mode() -> bool {
-	*IS_DEBUG_MODE.lock().unwrap().get_or_insert_with(|| {
-		std::env::var("EZNO_DEBUG").map(|value| !value.is_empty()).unwrap_or_default()
-	})
-}
-
-/// For `notify!` macro below
-pub fn shorten(s: &str) -> &str {
-	&s[s.find("src").expect("file not under 'src' folder")..]
-}
-
+/// Logging to the `module_path!()` target, at debug level unless a level is given before a `;`
+/// (e.g. `notify!(Trace; "{:?}", ty)`). See [`crate::logging`] for enabling
 macro_rules! notify {
-    () => {
-		if crate::utils::is_debug_mode() {
-			#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
-			eprintln!("[{}:{}]", crate::utils::shorten(file!()), line!())
+    ($level:ident; $($arguments:tt)*) => {
+		if (crate::logging::Level::$level as u8) <= (crate::logging::STATIC_MAXIMUM_LEVEL as u8)
+			&& crate::logging::enabled(module_path!(), crate::logging::Level::$level)
+		{
+			crate::logging::log(module_path!(), crate::logging::Level::$level, file!(), line!(), format_args!($($arguments)*))
 		}
     };
 
+    () => {
+		crate::utils::notify!(Debug; "")
+    };
+
     ($content:expr) => {
-		if crate::utils::is_debug_mode() {
-			#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
-			eprintln!("[{}:{}] {}", crate::utils::shorten(file!()), line!(), $content)
-		}
+		crate::utils::notify!(Debug; "{}", $content)
     };
 
     ($content:literal, $($es:expr),+) => {
-		if crate::utils::is_debug_mode() {
-			#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
-			eprintln!("[{}:{}] {}", crate::utils::shorten(file!()), line!(), format_args!($content, $($es),+))
-		}
+		crate::utils::notify!(Debug; $content, $($es),+)
     };
 }
 
@@ -0,0 +1,125 @@
+//! Runs the parser over [test262](https://github.com/tc39/test262) tests. Tests with a negative
+//! `parse` or `early` phase should fail to parse, the rest should parse.
+//!
+//! ```shell
+//! cargo run -p ezno-parser --example test262 -- path/to/test262/test/language
+//! ```
+//!
+//! Failures listed in `test262-known-failures.txt` do not fail the run. Pass `--update` to rewrite that
+//! file with the current failures
+
+use std::{
+	collections::BTreeSet,
+	fs,
+	panic::{self, AssertUnwindSafe},
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+use ezno_parser::{ASTNode, Module, ParseOptions};
+
+const KNOWN_FAILURES_PATH: &str =
+	concat!(env!("CARGO_MANIFEST_DIR"), "/test262-known-failures.txt");
+
+fn main() -> ExitCode {
+	let mut args = std::env::args().skip(1);
+	let directory = PathBuf::from(args.next().expect("expected path to test262 tests"));
+	let update = args.any(|arg| arg == "--update");
+
+	let mut files = Vec::new();
+	collect_test_files(&directory, &mut files);
+	files.sort();
+
+	let known_failures = fs::read_to_string(KNOWN_FAILURES_PATH).unwrap_or_default();
+	let known_failures = known_failures
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.collect::<BTreeSet<_>>();
+
+	panic::set_hook(Box::new(|_| {}));
+
+	let mut failures = BTreeSet::new();
+	for file in &files {
+		let Ok(content) = fs::read_to_string(file) else { continue };
+		let name =
+			file.strip_prefix(&directory).unwrap_or(file).display().to_string().replace('\\', "/");
+
+		let expects_error = expects_syntax_error(&content);
+		let options = ParseOptions {
+			jsx: false,
+			type_annotations: false,
+			stack_size: Some(32 * 1024 * 1024),
+			..Default::default()
+		};
+		let result =
+			panic::catch_unwind(AssertUnwindSafe(|| Module::from_string(content, options)));
+		let passed = match result {
+			Ok(result) => result.is_err() == expects_error,
+			Err(_) => false,
+		};
+		if !passed {
+			failures.insert(name);
+		}
+	}
+
+	let _ = panic::take_hook();
+
+	eprintln!("passed {}/{}", files.len() - failures.len(), files.len());
+
+	if update {
+		let mut content = String::from(
+			"# Generated by `cargo run -p ezno-parser --example test262 -- <path> --update`\n",
+		);
+		for failure in &failures {
+			content.push_str(failure);
+			content.push('\n');
+		}
+		fs::write(KNOWN_FAILURES_PATH, content).unwrap();
+		eprintln!("Updated {KNOWN_FAILURES_PATH}");
+		return ExitCode::SUCCESS;
+	}
+
+	let now_passing = known_failures.iter().filter(|name| !failures.contains(**name)).count();
+	if now_passing > 0 {
+		eprintln!("{now_passing} known failures now pass. Run with --update to remove them");
+	}
+
+	let unexpected =
+		failures.iter().filter(|name| !known_failures.contains(name.as_str())).collect::<Vec<_>>();
+	if unexpected.is_empty() {
+		ExitCode::SUCCESS
+	} else {
+		eprintln!("{} unexpected failures:", unexpected.len());
+		for name in unexpected {
+			eprintln!("- {name}");
+		}
+		ExitCode::FAILURE
+	}
+}
+
+fn collect_test_files(directory: &Path, files: &mut Vec<PathBuf>) {
+	let Ok(entries) = fs::read_dir(directory) else { return };
+	for entry in entries.flatten() {
+		let path = entry.path();
+		if path.is_dir() {
+			collect_test_files(&path, files);
+		} else if path.extension().is_some_and(|ext| ext == "js")
+			&& !path.to_string_lossy().ends_with("_FIXTURE.js")
+		{
+			files.push(path);
+		}
+	}
+}
+
+/// From the `negative` field in the front matter
+fn expects_syntax_error(content: &str) -> bool {
+	let Some(start) = content.find("/*---") else { return false };
+	let Some(length) = content[start..].find("---*/") else { return false };
+	let front_matter = &content[start..(start + length)];
+
+	let mut lines =
+		front_matter.lines().skip_while(|line| !line.trim_start().starts_with("negative:"));
+	lines.next().is_some()
+		&& lines.take(2).any(|line| matches!(line.trim(), "phase: parse" | "phase: early"))
+}
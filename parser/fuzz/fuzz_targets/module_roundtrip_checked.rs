@@ -0,0 +1,64 @@
+#![no_main]
+
+use ezno_checker::{synthesis::EznoParser, TypeCheckOptions};
+use ezno_parser::{ASTNode, Module, ParseOptions, ToStringOptions};
+use libfuzzer_sys::{fuzz_target, Corpus};
+use pretty_assertions::assert_eq;
+use std::path::{Path, PathBuf};
+
+/// Like `module_roundtrip_naive`, but also checks that printing did not change semantics
+/// (for example ASI hazards or missing parentheses):
+/// - the parse trees of the input and printed output are equal
+/// - the checker produces the same diagnostics for both
+fn do_fuzz(data: &str) -> Corpus {
+	let input = data.trim_start();
+
+	const STACK_SIZE_MB: usize = 32;
+	let parse_options = ParseOptions {
+		stack_size: Some(STACK_SIZE_MB * 1024 * 1024),
+		jsx: false,
+		type_annotations: false,
+		..Default::default()
+	};
+	let Ok(module1) = Module::from_string(input.to_owned(), parse_options) else {
+		return Corpus::Reject;
+	};
+
+	let to_string_options = ToStringOptions::default();
+
+	let output1 = module1.to_string(&to_string_options);
+
+	let Ok(module2) = Module::from_string(output1.to_owned(), parse_options) else {
+		panic!("input: `{input}`\noutput1: `{output1}`\n\nThis parse should not error because it was just parsed above");
+	};
+
+	assert_eq!(module1, module2, "parse trees different\ninput: `{input}`\noutput1: `{output1}`");
+
+	let output2 = module2.to_string(&to_string_options);
+
+	assert_eq!(output1, output2, "outputs different");
+
+	assert_eq!(
+		check_diagnostics(input),
+		check_diagnostics(&output1),
+		"diagnostics different\ninput: `{input}`\noutput1: `{output1}`"
+	);
+
+	Corpus::Keep
+}
+
+/// Reasons only, as positions change with printing
+fn check_diagnostics(source: &str) -> Vec<String> {
+	let output = ezno_checker::check_project::<_, EznoParser>(
+		vec![PathBuf::from("main.js")],
+		std::iter::once(ezno_checker::INTERNAL_DEFINITION_FILE_PATH.into()).collect(),
+		&|path: &Path| (path == Path::new("main.js")).then(|| source.to_owned()),
+		TypeCheckOptions::default(),
+		Default::default(),
+	);
+	output.diagnostics.into_iter().map(|diagnostic| diagnostic.reason().to_owned()).collect()
+}
+
+fuzz_target!(|data: &str| {
+	do_fuzz(data);
+});
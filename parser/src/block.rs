@@ -405,6 +405,14 @@ pub fn statements_and_declarations_to_string<T: source_map::ToString>(
 				Declaration::Class(item) if item.on.name.declare => {
 					continue;
 				}
+				// Ambient declarations and types have no output
+				Declaration::Interface(..)
+				| Declaration::TypeAlias(..)
+				| Declaration::DeclareVariable(..)
+				| Declaration::DeclareGlobal(..)
+				| Declaration::DeclareModule(..) => {
+					continue;
+				}
 				_ => {}
 			}
 		}
@@ -138,7 +138,7 @@ impl<T: ASTNode> ASTNode for WithComment<T> {
 		match self {
 			Self::None(ast) => ast.to_string_from_buffer(buf, options, local),
 			Self::PrefixComment(comment, ast, _) => {
-				if options.should_add_comment(comment.starts_with('*')) {
+				if options.should_add_comment(comment.starts_with('*'), comment) {
 					buf.push_str("/*");
 					buf.push_str_contains_new_line(comment.as_str());
 					buf.push_str("*/ ");
@@ -147,7 +147,7 @@ impl<T: ASTNode> ASTNode for WithComment<T> {
 			}
 			Self::PostfixComment(ast, comment, _) => {
 				ast.to_string_from_buffer(buf, options, local);
-				if options.should_add_comment(comment.starts_with('*')) {
+				if options.should_add_comment(comment.starts_with('*'), comment) {
 					buf.push_str(" /*");
 					buf.push_str_contains_new_line(comment.as_str());
 					buf.push_str("*/");
@@ -156,3 +156,36 @@ impl<T: ASTNode> ASTNode for WithComment<T> {
 		}
 	}
 }
+
+/// A `/// <reference ... />` comment at the start of a file, for a dependency on another file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TripleSlashDirective {
+	/// `/// <reference path="..." />`, relative to the file
+	Path(String),
+	/// `/// <reference types="..." />`, the definitions of a package
+	Types(String),
+	/// `/// <reference lib="..." />`, a built-in library (for example `es2015`)
+	Lib(String),
+}
+
+impl TripleSlashDirective {
+	/// From the content of a single line comment (after the `//`). Directives with unknown
+	/// attributes (for example `no-default-lib`) return `None`
+	#[must_use]
+	pub fn from_comment(comment: &str) -> Option<Self> {
+		let element = comment.strip_prefix('/')?.trim();
+		let attributes = element.strip_prefix("<reference")?.strip_suffix("/>")?;
+		let (name, value) = attributes.trim().split_once('=')?;
+		let value = value.trim();
+		let quote = value.chars().next().filter(|quote| matches!(quote, '"' | '\''))?;
+		// Other attributes (for example `resolution-mode`) are skipped
+		let (value, _) = value[1..].split_once(quote)?;
+		let value = value.to_owned();
+		match name.trim_end() {
+			"path" => Some(Self::Path(value)),
+			"types" => Some(Self::Types(value)),
+			"lib" => Some(Self::Lib(value)),
+			_ => None,
+		}
+	}
+}
@@ -211,7 +211,7 @@ impl ASTNode for ClassMember {
 				block.to_string_from_buffer(buf, options, local.next_level());
 			}
 			Self::Comment(c, is_multiline, _) => {
-				if options.should_add_comment(c.starts_with('.')) {
+				if options.should_add_comment(c.starts_with('.'), c) {
 					if *is_multiline {
 						buf.push_str("/*");
 						buf.push_str(c);
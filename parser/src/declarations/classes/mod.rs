@@ -143,6 +143,12 @@ impl<U: ExpressionOrStatementPosition> ClassDeclaration<U> {
 		options: &crate::ToStringOptions,
 		local: crate::LocalToStringInformation,
 	) {
+		if self.name.is_declare() {
+			if !options.include_type_annotations {
+				return;
+			}
+			buf.push_str("declare ");
+		}
 		buf.push_str("class ");
 		if let Some(name) = self.name.as_option_str() {
 			buf.push_str(name);
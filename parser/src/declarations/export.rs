@@ -53,6 +53,8 @@ pub enum Exportable {
 	Parts(Vec<ExportPart>),
 	ImportAll { r#as: Option<VariableIdentifier>, from: ImportLocation },
 	ImportParts { parts: Vec<ExportPart>, from: ImportLocation, type_definitions_only: bool },
+	#[cfg(feature = "full-typescript")]
+	Namespace(crate::types::namespace::Namespace),
 }
 
 impl ASTNode for ExportDeclaration {
@@ -253,6 +255,13 @@ impl ASTNode for ExportDeclaration {
 					))
 				}
 			}
+			#[cfg(feature = "full-typescript")]
+			Token(TSXToken::Keyword(TSXKeyword::Namespace), _) => {
+				let namespace =
+					crate::types::namespace::Namespace::from_reader(reader, state, options)?;
+				let position = start.union(namespace.get_position());
+				Ok(Self::Variable { exported: Exportable::Namespace(namespace), position })
+			}
 			Token(TSXToken::Keyword(kw), _) if kw.is_in_function_header() => {
 				let function_declaration = StatementFunction::from_reader(reader, state, options)?;
 				let position = start.union(function_declaration.get_position());
@@ -346,6 +355,10 @@ impl ASTNode for ExportDeclaration {
 						from.to_string_from_buffer(buf);
 						buf.push('"');
 					}
+					#[cfg(feature = "full-typescript")]
+					Exportable::Namespace(namespace) => {
+						namespace.to_string_from_buffer(buf, options, local);
+					}
 				}
 			}
 			ExportDeclaration::Default { expression, position: _ } => {
@@ -475,7 +488,7 @@ impl ASTNode for ExportPart {
 				}
 			}
 			ExportPart::PrefixComment(comment, inner, _) => {
-				if options.should_add_comment(comment.starts_with('.')) {
+				if options.should_add_comment(comment.starts_with('.'), comment) {
 					buf.push_str("/*");
 					buf.push_str(comment);
 					buf.push_str("*/");
@@ -489,7 +502,7 @@ impl ASTNode for ExportPart {
 			}
 			ExportPart::PostfixComment(inner, comment, _) => {
 				inner.to_string_from_buffer(buf, options, local);
-				if options.should_add_comment(comment.starts_with('.')) {
+				if options.should_add_comment(comment.starts_with('.'), comment) {
 					buf.push_str("/*");
 					buf.push_str(comment);
 					buf.push_str("*/ ");
@@ -421,7 +421,7 @@ impl ASTNode for ImportPart {
 				buf.push_str(name);
 			}
 			ImportPart::PrefixComment(comment, inner, _) => {
-				if options.should_add_comment(comment.starts_with('.')) {
+				if options.should_add_comment(comment.starts_with('.'), comment) {
 					buf.push_str("/*");
 					buf.push_str(comment);
 					buf.push_str("*/");
@@ -435,7 +435,7 @@ impl ASTNode for ImportPart {
 			}
 			ImportPart::PostfixComment(inner, comment, _) => {
 				inner.to_string_from_buffer(buf, options, local);
-				if options.should_add_comment(comment.starts_with('.')) {
+				if options.should_add_comment(comment.starts_with('.'), comment) {
 					buf.push_str("/*");
 					buf.push_str(comment);
 					buf.push_str("*/ ");
@@ -35,6 +35,8 @@ pub mod import;
 pub mod variable;
 
 pub use super::types::{
+	declare_global::DeclareGlobalDeclaration,
+	declare_module::DeclareModuleDeclaration,
 	declare_variable::*,
 	enum_declaration::{EnumDeclaration, EnumMember},
 	interface::InterfaceDeclaration,
@@ -58,6 +60,8 @@ pub enum Declaration {
 	TypeAlias(TypeAlias),
 	// Special TS only
 	DeclareVariable(DeclareVariableDeclaration),
+	DeclareGlobal(DeclareGlobalDeclaration),
+	DeclareModule(DeclareModuleDeclaration),
 	#[cfg(feature = "full-typescript")]
 	Namespace(crate::types::namespace::Namespace),
 	// Top level only
@@ -72,6 +76,14 @@ impl Declaration {
 		reader: &mut impl tokenizer_lib::TokenReader<crate::TSXToken, crate::TokenStart>,
 		options: &ParseOptions,
 	) -> bool {
+		// `import(...)` and `import.meta` are expressions
+		if let Some(Token(TSXToken::Keyword(TSXKeyword::Import), _)) = reader.peek() {
+			return !matches!(
+				reader.peek_n(1),
+				Some(Token(TSXToken::OpenParentheses | TSXToken::Dot, _))
+			);
+		}
+
 		let Some(Token(token, _)) = reader.peek() else { return false };
 		let result = matches!(
 			token,
@@ -91,6 +103,11 @@ impl Declaration {
 				let TSXToken::Keyword(token) = *token else { return false };
 				let Some(Token(after, _)) = reader.peek_n(1) else { return false };
 
+				// `module X {}` (but not `module.exports`)
+				if let (TSXKeyword::Module, TSXToken::Identifier(_)) = (token, after) {
+					return cfg!(feature = "full-typescript");
+				}
+
 				matches!(
 					token,
 					TSXKeyword::Declare | TSXKeyword::Interface
@@ -194,7 +211,7 @@ impl crate::ASTNode for Declaration {
 		match reader.peek().ok_or_else(parse_lexing_error)?.0 {
 			// Const can be either variable declaration or const enum
 			TSXToken::Keyword(TSXKeyword::Const) => {
-				let after_const = reader.peek_n(2);
+				let after_const = reader.peek_n(1);
 				if let Some(Token(TSXToken::Keyword(TSXKeyword::Enum), _)) = after_const {
 					EnumDeclaration::from_reader(reader, state, options)
 						.map(|on| Declaration::Enum(Decorated::new(decorators, on)))
@@ -242,6 +259,10 @@ impl crate::ASTNode for Declaration {
 			}
 			TSXToken::Keyword(TSXKeyword::Declare) if options.type_annotations => {
 				let Token(_, start) = reader.next().unwrap();
+				// `declare module X {}` (rather than a quoted name) is a namespace
+				#[cfg(all(feature = "extras", feature = "full-typescript"))]
+				let unquoted_module =
+					!matches!(reader.peek_n(1), Some(Token(TSXToken::StringLiteral(..), _)));
 				match reader.peek().ok_or_else(parse_lexing_error)?.0 {
 					TSXToken::Keyword(TSXKeyword::Let | TSXKeyword::Const | TSXKeyword::Var) => {
 						DeclareVariableDeclaration::from_reader_sub_declare(
@@ -273,6 +294,44 @@ impl crate::ASTNode for Declaration {
 						alias.position.start = start.0;
 						Ok(Declaration::TypeAlias(alias))
 					}
+					#[cfg(feature = "full-typescript")]
+					TSXToken::Keyword(TSXKeyword::Namespace) => {
+						let mut namespace = crate::types::namespace::Namespace::from_reader(
+							reader, state, options,
+						)?;
+						namespace.is_declare = true;
+						namespace.position.start = start.0;
+						Ok(Declaration::Namespace(namespace))
+					}
+					TSXToken::Identifier(ref name) if name == "global" => {
+						DeclareGlobalDeclaration::from_reader_sub_declare(
+							reader, state, options, start,
+						)
+						.map(Into::into)
+					}
+					#[cfg(all(feature = "extras", feature = "full-typescript"))]
+					TSXToken::Keyword(TSXKeyword::Module) if unquoted_module => {
+						let mut namespace = crate::types::namespace::Namespace::from_reader(
+							reader, state, options,
+						)?;
+						namespace.is_declare = true;
+						namespace.position.start = start.0;
+						Ok(Declaration::Namespace(namespace))
+					}
+					#[cfg(feature = "extras")]
+					TSXToken::Keyword(TSXKeyword::Module) => {
+						DeclareModuleDeclaration::from_reader_sub_declare(
+							reader, state, options, start,
+						)
+						.map(Into::into)
+					}
+					#[cfg(not(feature = "extras"))]
+					TSXToken::Identifier(ref name) if name == "module" => {
+						DeclareModuleDeclaration::from_reader_sub_declare(
+							reader, state, options, start,
+						)
+						.map(Into::into)
+					}
 					_ => throw_unexpected_token_with_token(
 						reader.next().ok_or_else(parse_lexing_error)?,
 						&[
@@ -282,6 +341,8 @@ impl crate::ASTNode for Declaration {
 							TSXToken::Keyword(TSXKeyword::Function),
 							TSXToken::Keyword(TSXKeyword::Class),
 							TSXToken::Keyword(TSXKeyword::Type),
+							TSXToken::Identifier("global".to_owned()),
+							TSXToken::Identifier("module".to_owned()),
 						],
 					),
 				}
@@ -295,6 +356,11 @@ impl crate::ASTNode for Declaration {
 				crate::types::namespace::Namespace::from_reader(reader, state, options)
 					.map(Into::into)
 			}
+			#[cfg(all(feature = "extras", feature = "full-typescript"))]
+			TSXToken::Keyword(TSXKeyword::Module) => {
+				crate::types::namespace::Namespace::from_reader(reader, state, options)
+					.map(Into::into)
+			}
 			_ => throw_unexpected_token_with_token(
 				reader.next().ok_or_else(parse_lexing_error)?,
 				&[
@@ -331,6 +397,8 @@ impl crate::ASTNode for Declaration {
 			Declaration::TypeAlias(ta) => ta.to_string_from_buffer(buf, options, local),
 			Declaration::Enum(r#enum) => r#enum.to_string_from_buffer(buf, options, local),
 			Declaration::DeclareVariable(dvd) => dvd.to_string_from_buffer(buf, options, local),
+			Declaration::DeclareGlobal(dgd) => dgd.to_string_from_buffer(buf, options, local),
+			Declaration::DeclareModule(dmd) => dmd.to_string_from_buffer(buf, options, local),
 			#[cfg(feature = "full-typescript")]
 			Declaration::Namespace(ns) => ns.to_string_from_buffer(buf, options, local),
 		}
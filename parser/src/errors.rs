@@ -29,6 +29,7 @@ pub enum ParseErrors<'a> {
 	CannotUseLeadingParameterHere,
 	ExpectedIdentifier,
 	ExpectedNumberLiteral,
+	ReadonlyOnlyOnArrayOrTuple,
 }
 
 #[allow(missing_docs)]
@@ -191,6 +192,9 @@ impl<'a> Display for ParseErrors<'a> {
 			ParseErrors::ExpectedNumberLiteral => {
 				write!(f, "Expected number literal")
 			}
+			ParseErrors::ReadonlyOnlyOnArrayOrTuple => {
+				write!(f, "'readonly' can only be used on array and tuple types")
+			}
 		}
 	}
 }
@@ -115,6 +115,8 @@ pub enum Expression {
 	SuperExpression(SuperReference, Span),
 	/// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/new.target
 	NewTarget(Span),
+	/// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/import.meta>
+	ImportMeta(Span),
 	DynamicImport {
 		path: Box<Expression>,
 		options: Option<Box<Expression>>,
@@ -287,6 +289,13 @@ impl Expression {
 			t @ Token(TSXToken::Keyword(TSXKeyword::This), _) => {
 				Expression::ThisReference(t.get_span())
 			}
+			Token(TSXToken::Keyword(TSXKeyword::Import), start)
+				if matches!(reader.peek(), Some(Token(TSXToken::Dot, _))) =>
+			{
+				reader.expect_next(TSXToken::Dot)?;
+				let end = reader.expect_next(TSXToken::Identifier("meta".into()))?;
+				Expression::ImportMeta(start.union(end.get_end_after("meta".len())))
+			}
 			Token(TSXToken::Keyword(TSXKeyword::Import), start) => {
 				let _ = reader.expect_next(TSXToken::OpenParentheses)?;
 				let path = Expression::from_reader(reader, state, options)?;
@@ -301,7 +310,7 @@ impl Expression {
 				} else {
 					None
 				};
-				let end = reader.expect_next(TSXToken::OpenParentheses)?;
+				let end = reader.expect_next(TSXToken::CloseParentheses)?;
 				Expression::DynamicImport {
 					path: Box::new(path),
 					options,
@@ -1209,6 +1218,7 @@ impl Expression {
 			| Self::ThisReference(..)
 			| Self::SuperExpression(..)
 			| Self::NewTarget(..)
+			| Self::ImportMeta(..)
 			| Self::ClassExpression(..)
 			| Self::DynamicImport { .. }
 			| Self::Marker { .. } => PARENTHESIZED_EXPRESSION_AND_LITERAL_PRECEDENCE,
@@ -1274,7 +1284,8 @@ impl Expression {
 				}
 			}
 			Self::BinaryOperation { lhs, operator, rhs, .. } => {
-				lhs.to_string_using_precedence(
+				lhs.to_string_parenthesized_if(
+					binary_operand_requires_parenthesis(*operator, lhs, false),
 					buf,
 					options,
 					local,
@@ -1332,7 +1343,8 @@ impl Expression {
 					) {
 					buf.push(' ');
 				}
-				rhs.to_string_using_precedence(
+				rhs.to_string_parenthesized_if(
+					binary_operand_requires_parenthesis(*operator, rhs, true),
 					buf,
 					options,
 					local,
@@ -1448,8 +1460,18 @@ impl Expression {
 				{
 					buf.push(' ');
 				}
+				// `yield` is followed by an expression on the same line (like `return`)
+				let requires_parenthesis = operand.get_precedence() < self_precedence
+					|| (matches!(operator, UnaryOperator::Yield | UnaryOperator::DelegatedYield)
+						&& operand.starts_with_line_terminator_comment());
 				let right_argument = local2.with_precedence(self_precedence).on_right();
-				operand.to_string_using_precedence(buf, options, local, right_argument);
+				operand.to_string_parenthesized_if(
+					requires_parenthesis,
+					buf,
+					options,
+					local,
+					right_argument,
+				);
 			}
 			Self::Assignment { lhs, rhs, .. } => {
 				let require_parenthesis =
@@ -1492,6 +1514,9 @@ impl Expression {
 			Self::NewTarget(..) => {
 				buf.push_str("new.target");
 			}
+			Self::ImportMeta(..) => {
+				buf.push_str("import.meta");
+			}
 			Self::DynamicImport { path, .. } => {
 				buf.push_str("import(");
 				path.to_string_from_buffer(buf, options, local);
@@ -1507,6 +1532,10 @@ impl Expression {
 					buf.push('(');
 					parent.to_string_from_buffer(buf, options, local);
 					buf.push(')');
+				} else if parent.get_precedence() < MEMBER_ACCESS_PRECEDENCE {
+					buf.push('(');
+					parent.to_string_from_buffer(buf, options, local);
+					buf.push(')');
 				} else {
 					parent.to_string_from_buffer(buf, options, local);
 				}
@@ -1541,7 +1570,13 @@ impl Expression {
 				}
 			}
 			Self::Index { indexee: expression, indexer, is_optional, .. } => {
-				expression.to_string_using_precedence(buf, options, local, local2);
+				expression.to_string_parenthesized_if(
+					expression.get_precedence() < INDEX_PRECEDENCE,
+					buf,
+					options,
+					local,
+					local2,
+				);
 				if *is_optional {
 					buf.push_str("?.");
 				}
@@ -1556,7 +1591,13 @@ impl Expression {
 					return;
 				}
 
-				function.to_string_using_precedence(buf, options, local, local2);
+				function.to_string_parenthesized_if(
+					function.get_precedence() < FUNCTION_CALL_PRECEDENCE,
+					buf,
+					options,
+					local,
+					local2,
+				);
 
 				if *is_optional {
 					buf.push_str("?.");
@@ -1576,11 +1617,9 @@ impl Expression {
 				{
 					to_string_bracketed(type_arguments, ('<', '>'), buf, options, local);
 				}
+				// Arguments are kept when empty, as `new X().y` is not `new X.y`
 				if let Some(arguments) = arguments {
-					// Constructor calls can drop arguments if none
-					if !arguments.is_empty() || options.pretty {
-						arguments_to_string(arguments, buf, options, local);
-					}
+					arguments_to_string(arguments, buf, options, local);
 				}
 			}
 			Self::ArrayLiteral(values, _) => {
@@ -1625,7 +1664,7 @@ impl Expression {
 				}
 			}
 			Self::Comment { content, on, is_multiline, prefix, position: _ } => {
-				if *prefix && options.should_add_comment(content.starts_with('*')) {
+				if *prefix && options.should_add_comment(content.starts_with('*'), content) {
 					if *is_multiline {
 						buf.push_str("/*");
 						buf.push_str_contains_new_line(content);
@@ -1637,7 +1676,7 @@ impl Expression {
 					}
 				}
 				on.to_string_using_precedence(buf, options, local, local2);
-				if !prefix && options.should_add_comment(content.starts_with('*')) {
+				if !prefix && options.should_add_comment(content.starts_with('*'), content) {
 					if *is_multiline {
 						buf.push_str("/*");
 						buf.push_str_contains_new_line(content);
@@ -1669,7 +1708,8 @@ impl Expression {
 				buf.push('`');
 			}
 			Self::ConditionalTernary { condition, truthy_result, falsy_result, .. } => {
-				condition.to_string_using_precedence(
+				condition.to_string_parenthesized_if(
+					condition.get_precedence() <= CONDITIONAL_TERNARY_PRECEDENCE,
 					buf,
 					options,
 					local,
@@ -1715,6 +1755,95 @@ impl Expression {
 		// 	buf.push(')');
 		// }
 	}
+
+	fn to_string_parenthesized_if<T: source_map::ToString>(
+		&self,
+		parenthesize: bool,
+		buf: &mut T,
+		options: &crate::ToStringOptions,
+		local: crate::LocalToStringInformation,
+		local2: ExpressionToStringArgument,
+	) {
+		if parenthesize {
+			buf.push('(');
+			self.to_string_using_precedence(
+				buf,
+				options,
+				local,
+				ExpressionToStringArgument { on_left: false, parent_precedence: u8::MAX },
+			);
+			buf.push(')');
+		} else {
+			self.to_string_using_precedence(buf, options, local, local2);
+		}
+	}
+
+	/// Whether the output starts with a comment containing a line terminator. After `return`, `throw`
+	/// and `yield` this would cause automatic semicolon insertion
+	pub(crate) fn starts_with_line_terminator_comment(&self) -> bool {
+		match self {
+			Self::Comment { content, on, is_multiline, prefix, .. } => {
+				if *prefix {
+					!is_multiline || content.contains('\n')
+				} else {
+					on.starts_with_line_terminator_comment()
+				}
+			}
+			Self::BinaryOperation { lhs: on, .. }
+			| Self::SpecialOperators(SpecialOperators::InstanceOf { lhs: on, .. }, _)
+			| Self::PropertyAccess { parent: on, .. }
+			| Self::Index { indexee: on, .. }
+			| Self::FunctionCall { function: on, .. }
+			| Self::ConditionalTernary { condition: on, .. } => on.starts_with_line_terminator_comment(),
+			_ => false,
+		}
+	}
+}
+
+/// Whether `operand` needs parentheses to be printed as an operand of `operator`. Parsed sources retain
+/// their parentheses as [`Expression::ParenthesizedExpression`], so this is only needed for ASTs that have
+/// been built or transformed
+pub(crate) fn binary_operand_requires_parenthesis(
+	operator: BinaryOperator,
+	operand: &Expression,
+	on_right: bool,
+) -> bool {
+	let operand_precedence = operand.get_precedence();
+	let precedence = operator.precedence();
+
+	let mut operand = operand;
+	while let Expression::Comment { on, .. } = operand {
+		operand = on;
+	}
+
+	// `??` cannot be mixed with `&&` or `||` without parentheses
+	if let Expression::BinaryOperation { operator: operand_operator, .. } = operand {
+		let is_logical = |operator: &BinaryOperator| {
+			matches!(operator, BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr)
+		};
+		if (matches!(operator, BinaryOperator::NullCoalescing) && is_logical(operand_operator))
+			|| (is_logical(&operator) && matches!(operand_operator, BinaryOperator::NullCoalescing))
+		{
+			return true;
+		}
+	}
+
+	// `-a ** b` is a syntax error
+	if let (BinaryOperator::Exponent, false, Expression::UnaryOperation { .. }) =
+		(operator, on_right, operand)
+	{
+		return true;
+	}
+
+	match operand_precedence.cmp(&precedence) {
+		std::cmp::Ordering::Less => true,
+		// Not using `is_associative` as `+` is not associative for strings
+		std::cmp::Ordering::Equal => match operator.associativity_direction() {
+			AssociativityDirection::RightToLeft => !on_right,
+			AssociativityDirection::NA | AssociativityDirection::LeftToRight => on_right,
+		},
+		std::cmp::Ordering::Greater => false,
+	}
 }
 
 fn function_header_ish(
@@ -1765,6 +1894,13 @@ impl MultipleExpression {
 		}
 	}
 
+	pub(crate) fn starts_with_line_terminator_comment(&self) -> bool {
+		match self {
+			MultipleExpression::Multiple { lhs, .. } => lhs.starts_with_line_terminator_comment(),
+			MultipleExpression::Single(inner) => inner.starts_with_line_terminator_comment(),
+		}
+	}
+
 	pub(crate) fn to_string_on_left<T: source_map::ToString>(
 		&self,
 		buf: &mut T,
@@ -2085,7 +2221,7 @@ impl ASTNode for FunctionArgument {
 				expression.to_string_from_buffer(buf, options, local);
 			}
 			FunctionArgument::Comment { content, is_multiline, position: _ } => {
-				if options.should_add_comment(*is_multiline && content.starts_with('*')) {
+				if options.should_add_comment(*is_multiline && content.starts_with('*'), content) {
 					buf.push_str("/*");
 					buf.push_str(content);
 					buf.push_str("*/");
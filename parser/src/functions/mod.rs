@@ -331,6 +331,9 @@ impl<T: ExpressionOrStatementPosition> FunctionBased for GeneralFunctionBase<T>
 		options: &crate::ToStringOptions,
 		local: crate::LocalToStringInformation,
 	) {
+		if name.is_declare() {
+			buf.push_str("declare ");
+		}
 		header.to_string_from_buffer(buf, options, local);
 		if let Some(name) = name.as_option_str() {
 			buf.push_str(name);
@@ -338,6 +338,9 @@ pub fn lex_script(
 					'n' if matches!(
 						literal_type,
 						NumberLiteralType::Decimal { fractional: false }
+							| NumberLiteralType::BinaryLiteral
+							| NumberLiteralType::OctalLiteral
+							| NumberLiteralType::HexadecimalLiteral
 					) =>
 					{
 						*literal_type = NumberLiteralType::BigInt;
@@ -409,9 +412,13 @@ pub fn lex_script(
 				}
 			},
 			LexingState::String { ref mut double_quoted, ref mut escaped } => match chr {
-				'\n' => {
+				'\n' | '\r' if !*escaped => {
 					return_err!(LexingErrors::NewLineInStringLiteral);
 				}
+				// Line continuation. Stays escaped for a CRLF so that the `\n` is also skipped
+				'\r' => {
+					*escaped = script[idx + 1..].starts_with('\n');
+				}
 				'\'' if !*double_quoted && !*escaped => {
 					push_token!(TSXToken::StringLiteral(
 						script[(start + 1)..idx].to_owned(),
@@ -441,10 +448,9 @@ pub fn lex_script(
 			},
 			LexingState::Comment => {
 				if let '\n' = chr {
-					if matches!(options.comments, Comments::All) {
-						push_token!(TSXToken::Comment(
-							script[(start + 2)..idx].trim_end().to_owned()
-						),);
+					let comment = script[(start + 2)..idx].trim_end();
+					if options.comments.include(false, comment) {
+						push_token!(TSXToken::Comment(comment.to_owned()),);
 					}
 					set_state!(LexingState::None);
 					continue;
@@ -453,10 +459,7 @@ pub fn lex_script(
 			LexingState::MultiLineComment { ref mut last_char_was_star } => match chr {
 				'/' if *last_char_was_star => {
 					let comment = &script[(start + 2)..(idx - 1)];
-					let include = matches!(options.comments, Comments::All)
-						|| (matches!(options.comments, Comments::JustDocumentation)
-							&& comment.starts_with('*'));
-					if include {
+					if options.comments.include(comment.starts_with('*'), comment) {
 						push_token!(TSXToken::MultiLineComment(comment.to_owned()));
 					}
 					set_state!(LexingState::None);
@@ -1081,10 +1084,13 @@ pub fn lex_script(
 			}
 		}
 		LexingState::Comment => {
-			sender.push(Token(
-				TSXToken::Comment(script[(start + 2)..].trim_end().to_owned()),
-				TokenStart::new(start as u32 + offset),
-			));
+			let comment = script[(start + 2)..].trim_end();
+			if options.comments.include(false, comment) {
+				sender.push(Token(
+					TSXToken::Comment(comment.to_owned()),
+					TokenStart::new(start as u32 + offset),
+				));
+			}
 		}
 		LexingState::String { .. } => {
 			sender.push(Token(TSXToken::EOS, current_position!()));
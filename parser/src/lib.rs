@@ -12,6 +12,7 @@ pub mod generator_helpers;
 mod lexer;
 pub mod marker;
 mod modules;
+pub mod outline;
 pub mod property_key;
 pub mod statements;
 mod tokens;
@@ -20,7 +21,7 @@ mod variable_fields;
 pub mod visiting;
 
 pub use block::{Block, BlockLike, BlockLikeMut, BlockOrSingleStatement, StatementOrDeclaration};
-pub use comments::WithComment;
+pub use comments::{TripleSlashDirective, WithComment};
 pub use declarations::Declaration;
 use functions::FunctionBody;
 pub use marker::Marker;
@@ -241,9 +242,8 @@ impl ToStringOptions {
 	}
 
 	/// Whether to include comment in source
-	pub(crate) fn should_add_comment(&self, is_document_comment: bool) -> bool {
-		matches!(self.comments, Comments::All)
-			|| (matches!(self.comments, Comments::JustDocumentation) && is_document_comment)
+	pub(crate) fn should_add_comment(&self, is_document_comment: bool, content: &str) -> bool {
+		self.comments.include(is_document_comment, content)
 	}
 
 	pub(crate) fn add_indent<T: source_map::ToString>(&self, indent: u8, buf: &mut T) {
@@ -270,11 +270,30 @@ impl ToStringOptions {
 pub enum Comments {
 	#[default]
 	All,
-	/// Only multiline comments starting with `/**`
+	/// Only multiline comments starting with `/**` (and legal comments)
 	JustDocumentation,
+	/// Only comments starting with `!` or containing `@license` or `@preserve`
+	Legal,
 	None,
 }
 
+impl Comments {
+	/// `content` is the comment without the `//` or `/*` and `*/`
+	pub(crate) fn include(self, is_document_comment: bool, content: &str) -> bool {
+		let is_legal_comment = || {
+			content.starts_with('!')
+				|| content.contains("@license")
+				|| content.contains("@preserve")
+		};
+		match self {
+			Comments::All => true,
+			Comments::JustDocumentation => is_document_comment || is_legal_comment(),
+			Comments::Legal => is_legal_comment(),
+			Comments::None => false,
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct LocalToStringInformation {
 	under: SourceId,
@@ -764,7 +783,9 @@ impl std::ops::Neg for NumberRepresentation {
 			}
 			NumberRepresentation::Number(n) => NumberRepresentation::Number(n.neg()),
 			NumberRepresentation::Exponential { .. } => todo!(),
-			NumberRepresentation::BigInt(_, _) => todo!(),
+			NumberRepresentation::BigInt(sign, value) => {
+				NumberRepresentation::BigInt(sign.neg(), value)
+			}
 		}
 	}
 }
@@ -62,6 +62,28 @@ impl Module {
 		buf.build(fs)
 	}
 
+	/// The `/// <reference ... />` directives at the start of the module (before any statements
+	/// or declarations). Comments must be included by [`ParseOptions::comments`]
+	#[must_use]
+	pub fn triple_slash_directives(&self) -> Vec<(crate::TripleSlashDirective, Span)> {
+		self.items
+			.iter()
+			.map_while(|item| match item {
+				StatementOrDeclaration::Statement(statement) if statement.is_comment() => {
+					Some(statement)
+				}
+				_ => None,
+			})
+			.filter_map(|statement| match statement {
+				crate::Statement::Comment(comment, position) => {
+					crate::TripleSlashDirective::from_comment(comment)
+						.map(|directive| (directive, *position))
+				}
+				_ => None,
+			})
+			.collect()
+	}
+
 	// #[must_use]
 	// pub fn length(&self, options: &crate::ToStringOptions) -> usize {
 	// 	let mut buf = source_map::Counter::new();
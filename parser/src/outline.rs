@@ -0,0 +1,369 @@
+//! The hierarchy of declarations in a module (classes and their members, functions, variables,
+//! types and exports). For document symbols in editors and the AST explorer's tree view
+
+use crate::{
+	declarations::{
+		classes::ClassMember, export::Exportable, ClassDeclaration, ExportDeclaration,
+		InterfaceDeclaration, StatementFunction, VariableDeclaration,
+	},
+	functions::MethodHeader,
+	property_key::PropertyKeyKind,
+	types::{enum_declaration::EnumMember, interface::InterfaceMember},
+	ASTNode, ArrayDestructuringField, Declaration, Expression, ExpressionOrStatementPosition,
+	Module, ObjectDestructuringField, PropertyKey, Span, StatementOrDeclaration, ToStringOptions,
+	VariableField,
+};
+
+/// What a [`Symbol`] declares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+#[cfg_attr(target_family = "wasm", derive(tsify::Tsify))]
+pub enum SymbolKind {
+	Variable,
+	Function,
+	Class,
+	Constructor,
+	Method,
+	Getter,
+	Setter,
+	Property,
+	Interface,
+	TypeAlias,
+	Enum,
+	EnumMember,
+	Namespace,
+	/// `declare module "..." { ... }`
+	Module,
+}
+
+/// A declaration in an outline
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+#[cfg_attr(target_family = "wasm", derive(tsify::Tsify))]
+pub struct Symbol {
+	pub name: String,
+	pub kind: SymbolKind,
+	/// Of the whole declaration
+	pub position: Span,
+	/// Of the name. The same as `position` for declarations where the name is not written (for
+	/// example `export default`)
+	pub name_position: Span,
+	/// Exported from the module (or namespace)
+	pub is_exported: bool,
+	pub children: Vec<Symbol>,
+}
+
+impl Symbol {
+	fn new(name: String, kind: SymbolKind, position: Span, name_position: Span) -> Self {
+		Self { name, kind, position, name_position, is_exported: false, children: Vec::new() }
+	}
+}
+
+impl Module {
+	/// Declarations in the module, in source order. Imports and re-exports are not included
+	#[must_use]
+	pub fn outline(&self) -> Vec<Symbol> {
+		outline_items(&self.items)
+	}
+}
+
+fn outline_items(items: &[StatementOrDeclaration]) -> Vec<Symbol> {
+	let mut symbols = Vec::new();
+	for item in items {
+		if let StatementOrDeclaration::Declaration(declaration) = item {
+			outline_declaration(declaration, &mut symbols);
+		}
+	}
+	symbols
+}
+
+fn outline_declaration(declaration: &Declaration, symbols: &mut Vec<Symbol>) {
+	match declaration {
+		Declaration::Variable(declaration) => outline_variable_declaration(declaration, symbols),
+		Declaration::DeclareVariable(declaration) => {
+			for item in &declaration.declarations {
+				outline_variable_field(item.name.get_ast_ref(), None, item.position, symbols);
+			}
+		}
+		Declaration::Function(function) => symbols.push(function_symbol(&function.on)),
+		Declaration::Class(class) => symbols.push(class_symbol(&class.on)),
+		Declaration::Enum(declaration) => {
+			let declaration = &declaration.on;
+			let mut symbol = Symbol::new(
+				declaration.name.clone(),
+				SymbolKind::Enum,
+				declaration.position,
+				declaration.position,
+			);
+			symbol.children = declaration
+				.members
+				.iter()
+				.map(|EnumMember::Variant { name, position, .. }| {
+					Symbol::new(name.clone(), SymbolKind::EnumMember, *position, *position)
+				})
+				.collect();
+			symbols.push(symbol);
+		}
+		Declaration::Interface(interface) => symbols.push(interface_symbol(&interface.on)),
+		Declaration::TypeAlias(alias) => {
+			symbols.push(named_symbol(&alias.name, SymbolKind::TypeAlias, alias.position));
+		}
+		Declaration::DeclareGlobal(global) => symbols.extend(outline_items(&global.inner.0)),
+		Declaration::DeclareModule(module) => {
+			let mut symbol = Symbol::new(
+				module.name.clone(),
+				SymbolKind::Module,
+				module.position,
+				module.position,
+			);
+			symbol.children = outline_items(&module.inner.0);
+			symbols.push(symbol);
+		}
+		#[cfg(feature = "full-typescript")]
+		#[cfg(feature = "full-typescript")]
+		Declaration::Namespace(namespace) => symbols.push(namespace_symbol(namespace)),
+		Declaration::Import(_) => {}
+		Declaration::Export(export) => {
+			let start = symbols.len();
+			outline_export(&export.on, symbols);
+			for symbol in &mut symbols[start..] {
+				symbol.is_exported = true;
+			}
+		}
+	}
+}
+
+fn outline_export(export: &ExportDeclaration, symbols: &mut Vec<Symbol>) {
+	match export {
+		ExportDeclaration::Variable { exported, .. } => match exported {
+			Exportable::Class(class) => symbols.push(class_symbol(class)),
+			Exportable::Function(function) => symbols.push(function_symbol(function)),
+			Exportable::Variable(declaration) => outline_variable_declaration(declaration, symbols),
+			Exportable::Interface(interface) => symbols.push(interface_symbol(interface)),
+			Exportable::TypeAlias(alias) => {
+				symbols.push(named_symbol(&alias.name, SymbolKind::TypeAlias, alias.position));
+			}
+			#[cfg(feature = "full-typescript")]
+			Exportable::Namespace(namespace) => symbols.push(namespace_symbol(namespace)),
+			Exportable::Parts(_)
+			| Exportable::ImportAll { .. }
+			| Exportable::ImportParts { .. } => {}
+		},
+		ExportDeclaration::Default { expression, position } => {
+			symbols.push(expression_symbol("default".to_owned(), expression, *position, *position));
+		}
+		ExportDeclaration::DefaultFunction { identifier, position, .. } => {
+			let (name, name_position) = identifier
+				.as_ref()
+				.and_then(|identifier| {
+					Some((identifier.as_option_str()?.to_owned(), identifier.get_position()))
+				})
+				.unwrap_or_else(|| ("default".to_owned(), *position));
+			symbols.push(Symbol::new(name, SymbolKind::Function, *position, name_position));
+		}
+	}
+}
+
+fn outline_variable_declaration(declaration: &VariableDeclaration, symbols: &mut Vec<Symbol>) {
+	match declaration {
+		VariableDeclaration::ConstDeclaration { declarations, .. } => {
+			for item in declarations {
+				let field = item.name.get_ast_ref();
+				outline_variable_field(field, Some(&item.expression), item.position, symbols);
+			}
+		}
+		VariableDeclaration::LetDeclaration { declarations, .. } => {
+			for item in declarations {
+				let field = item.name.get_ast_ref();
+				outline_variable_field(field, item.expression.as_ref(), item.position, symbols);
+			}
+		}
+	}
+}
+
+/// Each name in a destructuring pattern is a symbol
+fn outline_variable_field(
+	field: &VariableField,
+	value: Option<&Expression>,
+	position: Span,
+	symbols: &mut Vec<Symbol>,
+) {
+	match field {
+		VariableField::Name(identifier) => {
+			let name = identifier.as_option_str().unwrap_or_default().to_owned();
+			let symbol = match value {
+				Some(value) => expression_symbol(name, value, position, identifier.get_position()),
+				None => {
+					Symbol::new(name, SymbolKind::Variable, position, identifier.get_position())
+				}
+			};
+			symbols.push(symbol);
+		}
+		VariableField::Array(fields, _) => {
+			for field in fields {
+				match field.get_ast_ref() {
+					ArrayDestructuringField::Spread(field, _)
+					| ArrayDestructuringField::Name(field, _) => {
+						outline_variable_field(field, None, position, symbols);
+					}
+					ArrayDestructuringField::Comment { .. } | ArrayDestructuringField::None => {}
+				}
+			}
+		}
+		VariableField::Object(fields, _) => {
+			for field in fields {
+				match field.get_ast_ref() {
+					ObjectDestructuringField::Name(identifier, ..)
+					| ObjectDestructuringField::Spread(identifier, _) => {
+						let name = identifier.as_option_str().unwrap_or_default().to_owned();
+						let name_position = identifier.get_position();
+						symbols.push(Symbol::new(
+							name,
+							SymbolKind::Variable,
+							position,
+							name_position,
+						));
+					}
+					ObjectDestructuringField::Map { name, .. } => {
+						outline_variable_field(name.get_ast_ref(), None, position, symbols);
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Functions and classes assigned to variables are outlined as functions and classes
+fn expression_symbol(
+	name: String,
+	value: &Expression,
+	position: Span,
+	name_position: Span,
+) -> Symbol {
+	match value {
+		Expression::ArrowFunction(_) | Expression::ExpressionFunction(_) => {
+			Symbol::new(name, SymbolKind::Function, position, name_position)
+		}
+		Expression::ClassExpression(class) => Symbol {
+			children: class_member_symbols(class),
+			..Symbol::new(name, SymbolKind::Class, position, name_position)
+		},
+		_ => Symbol::new(name, SymbolKind::Variable, position, name_position),
+	}
+}
+
+fn named_symbol<T: ExpressionOrStatementPosition>(
+	name: &T,
+	kind: SymbolKind,
+	position: Span,
+) -> Symbol {
+	let name_position =
+		name.as_option_variable_identifier().map_or(position, ASTNode::get_position);
+	Symbol::new(name.as_option_str().unwrap_or_default().to_owned(), kind, position, name_position)
+}
+
+fn function_symbol(function: &StatementFunction) -> Symbol {
+	named_symbol(&function.name, SymbolKind::Function, function.position)
+}
+
+fn class_symbol<T: ExpressionOrStatementPosition>(class: &ClassDeclaration<T>) -> Symbol {
+	Symbol {
+		children: class_member_symbols(class),
+		..named_symbol(&class.name, SymbolKind::Class, class.position)
+	}
+}
+
+fn class_member_symbols<T: ExpressionOrStatementPosition>(
+	class: &ClassDeclaration<T>,
+) -> Vec<Symbol> {
+	class
+		.members
+		.iter()
+		.filter_map(|member| match &member.on {
+			ClassMember::Constructor(constructor) => Some(Symbol::new(
+				"constructor".to_owned(),
+				SymbolKind::Constructor,
+				constructor.position,
+				constructor.position,
+			)),
+			ClassMember::Method(_, method) => {
+				let key = method.name.get_ast_ref();
+				let kind = method_kind(&method.header);
+				Some(Symbol::new(property_key_name(key), kind, method.position, key.get_position()))
+			}
+			ClassMember::Property(_, property) => {
+				let key = property.key.get_ast_ref();
+				Some(Symbol::new(
+					property_key_name(key),
+					SymbolKind::Property,
+					property.position,
+					key.get_position(),
+				))
+			}
+			ClassMember::StaticBlock(_)
+			| ClassMember::Indexer { .. }
+			| ClassMember::Comment(..) => None,
+		})
+		.collect()
+}
+
+fn interface_symbol(interface: &InterfaceDeclaration) -> Symbol {
+	Symbol {
+		children: interface
+			.members
+			.iter()
+			.filter_map(|member| interface_member_symbol(&member.get_ast_ref().on))
+			.collect(),
+		..named_symbol(&interface.name, SymbolKind::Interface, interface.position)
+	}
+}
+
+fn interface_member_symbol(member: &InterfaceMember) -> Option<Symbol> {
+	match member {
+		InterfaceMember::Method { header, name, position, .. } => Some(Symbol::new(
+			property_key_name(name),
+			method_kind(header),
+			*position,
+			name.get_position(),
+		)),
+		InterfaceMember::Property { name, position, .. } => Some(Symbol::new(
+			property_key_name(name),
+			SymbolKind::Property,
+			*position,
+			name.get_position(),
+		)),
+		_ => None,
+	}
+}
+
+#[cfg(feature = "full-typescript")]
+fn namespace_symbol(namespace: &crate::types::namespace::Namespace) -> Symbol {
+	Symbol {
+		children: outline_items(&namespace.inner.0),
+		..Symbol::new(
+			namespace.name.clone(),
+			SymbolKind::Namespace,
+			namespace.position,
+			namespace.position,
+		)
+	}
+}
+
+fn method_kind(header: &MethodHeader) -> SymbolKind {
+	match header {
+		MethodHeader::Get => SymbolKind::Getter,
+		MethodHeader::Set => SymbolKind::Setter,
+		MethodHeader::Regular { .. } => SymbolKind::Method,
+	}
+}
+
+/// Private names start with `#`. Computed keys are printed
+fn property_key_name<T: PropertyKeyKind>(key: &PropertyKey<T>) -> String {
+	match key {
+		PropertyKey::Ident(name, ..) if key.is_private() => format!("#{name}"),
+		PropertyKey::Ident(name, ..) | PropertyKey::StringLiteral(name, ..) => name.clone(),
+		PropertyKey::NumberLiteral(..) | PropertyKey::Computed(..) => {
+			key.to_string(&ToStringOptions::minified())
+		}
+	}
+}
@@ -221,7 +221,15 @@ impl ASTNode for Statement {
 				buf.push_str("return");
 				if let Some(expression) = expression {
 					buf.push(' ');
+					// Prevents automatic semicolon insertion after `return`
+					let requires_parenthesis = expression.starts_with_line_terminator_comment();
+					if requires_parenthesis {
+						buf.push('(');
+					}
 					expression.to_string_from_buffer(buf, options, local);
+					if requires_parenthesis {
+						buf.push(')');
+					}
 				}
 			}
 			Statement::If(is) => is.to_string_from_buffer(buf, options, local),
@@ -231,13 +239,17 @@ impl ASTNode for Statement {
 			Statement::DoWhileLoop(dws) => dws.to_string_from_buffer(buf, options, local),
 			Statement::TryCatch(tcs) => tcs.to_string_from_buffer(buf, options, local),
 			Statement::Comment(comment, _) => {
-				if options.should_add_comment(false) {
+				if options.should_add_comment(false, comment) {
 					buf.push_str("//");
 					buf.push_str_contains_new_line(comment.as_str().trim_end());
+					// Else the next item would be part of the comment
+					if !options.pretty {
+						buf.push_new_line();
+					}
 				}
 			}
 			Statement::MultiLineComment(comment, _) => {
-				if options.should_add_comment(comment.starts_with('*')) {
+				if options.should_add_comment(comment.starts_with('*'), comment) {
 					buf.push_str("/*");
 					buf.push_str_contains_new_line(comment.as_str());
 					buf.push_str("*/");
@@ -276,7 +288,14 @@ impl ASTNode for Statement {
 			}
 			Statement::Throw(ThrowStatement(thrown_expression, _)) => {
 				buf.push_str("throw ");
+				let requires_parenthesis = thrown_expression.starts_with_line_terminator_comment();
+				if requires_parenthesis {
+					buf.push('(');
+				}
 				thrown_expression.to_string_from_buffer(buf, options, local);
+				if requires_parenthesis {
+					buf.push(')');
+				}
 			}
 			Statement::VarVariable(var_stmt) => var_stmt.to_string_from_buffer(buf, options, local),
 		}
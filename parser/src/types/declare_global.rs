@@ -0,0 +1,57 @@
+use get_field_by_type::GetFieldByType;
+use source_map::Span;
+use tokenizer_lib::sized_tokens::TokenStart;
+
+use crate::{derive_ASTNode, ASTNode, Block, ParseOptions, ParseResult, TSXToken, TokenReader};
+
+/// A `declare global { ... }` block. Items in `inner` augment the global scope
+/// (for example adding members to the `Window` interface)
+#[apply(derive_ASTNode)]
+#[derive(Debug, Clone, PartialEq, get_field_by_type::GetFieldByType)]
+#[get_field_by_type_target(Span)]
+pub struct DeclareGlobalDeclaration {
+	pub inner: Block,
+	pub position: Span,
+}
+
+impl ASTNode for DeclareGlobalDeclaration {
+	fn from_reader(
+		reader: &mut impl TokenReader<TSXToken, crate::TokenStart>,
+		state: &mut crate::ParsingState,
+		options: &ParseOptions,
+	) -> ParseResult<Self> {
+		let start = state.expect_keyword(reader, crate::TSXKeyword::Declare)?;
+		Self::from_reader_sub_declare(reader, state, options, start)
+	}
+
+	fn to_string_from_buffer<T: source_map::ToString>(
+		&self,
+		buf: &mut T,
+		options: &crate::ToStringOptions,
+		local: crate::LocalToStringInformation,
+	) {
+		if options.include_type_annotations {
+			buf.push_str("declare global ");
+			self.inner.to_string_from_buffer(buf, options, local.next_level());
+		}
+	}
+
+	fn get_position(&self) -> Span {
+		*self.get()
+	}
+}
+
+impl DeclareGlobalDeclaration {
+	/// After `declare`. Expects `global` identifier next
+	pub(crate) fn from_reader_sub_declare(
+		reader: &mut impl TokenReader<TSXToken, crate::TokenStart>,
+		state: &mut crate::ParsingState,
+		options: &ParseOptions,
+		start: TokenStart,
+	) -> ParseResult<Self> {
+		reader.expect_next(TSXToken::Identifier("global".to_owned()))?;
+		let inner = Block::from_reader(reader, state, options)?;
+		let position = start.union(inner.get_position());
+		Ok(Self { inner, position })
+	}
+}
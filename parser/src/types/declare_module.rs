@@ -0,0 +1,75 @@
+use get_field_by_type::GetFieldByType;
+use source_map::Span;
+use tokenizer_lib::{sized_tokens::TokenStart, Token};
+
+use crate::{
+	derive_ASTNode, errors::parse_lexing_error, ASTNode, Block, ParseError, ParseErrors,
+	ParseOptions, ParseResult, Quoted, TSXToken, TokenReader,
+};
+
+/// A `declare module "react" { ... }` block. Used for augmenting the exports of
+/// an (already imported) module
+#[apply(derive_ASTNode)]
+#[derive(Debug, Clone, PartialEq, get_field_by_type::GetFieldByType)]
+#[get_field_by_type_target(Span)]
+pub struct DeclareModuleDeclaration {
+	pub name: String,
+	pub quoted: Quoted,
+	pub inner: Block,
+	pub position: Span,
+}
+
+impl ASTNode for DeclareModuleDeclaration {
+	fn from_reader(
+		reader: &mut impl TokenReader<TSXToken, crate::TokenStart>,
+		state: &mut crate::ParsingState,
+		options: &ParseOptions,
+	) -> ParseResult<Self> {
+		let start = state.expect_keyword(reader, crate::TSXKeyword::Declare)?;
+		Self::from_reader_sub_declare(reader, state, options, start)
+	}
+
+	fn to_string_from_buffer<T: source_map::ToString>(
+		&self,
+		buf: &mut T,
+		options: &crate::ToStringOptions,
+		local: crate::LocalToStringInformation,
+	) {
+		if options.include_type_annotations {
+			buf.push_str("declare module ");
+			buf.push(self.quoted.as_char());
+			buf.push_str(&self.name);
+			buf.push(self.quoted.as_char());
+			buf.push(' ');
+			self.inner.to_string_from_buffer(buf, options, local.next_level());
+		}
+	}
+
+	fn get_position(&self) -> Span {
+		*self.get()
+	}
+}
+
+impl DeclareModuleDeclaration {
+	/// After `declare`. Skips the `module` keyword (which is only a keyword under `extras`)
+	pub(crate) fn from_reader_sub_declare(
+		reader: &mut impl TokenReader<TSXToken, crate::TokenStart>,
+		state: &mut crate::ParsingState,
+		options: &ParseOptions,
+		start: TokenStart,
+	) -> ParseResult<Self> {
+		let _module_keyword = reader.next().ok_or_else(parse_lexing_error)?;
+		let (name, quoted) = match reader.next().ok_or_else(parse_lexing_error)? {
+			Token(TSXToken::StringLiteral(name, quoted), _) => (name, quoted),
+			Token(token, position) => {
+				return Err(ParseError::new(
+					ParseErrors::ExpectedStringLiteral { found: token },
+					position.with_length(0),
+				));
+			}
+		};
+		let inner = Block::from_reader(reader, state, options)?;
+		let position = start.union(inner.get_position());
+		Ok(Self { name, quoted, inner, position })
+	}
+}
@@ -73,6 +73,8 @@ impl DeclareVariableDeclaration {
 		}
 
 		let position = start.union(declarations.last().unwrap().get_position());
+		// Skipped here rather than parsed as an empty statement, which would be left in JS output
+		reader.conditional_next(|token| matches!(token, TSXToken::SemiColon));
 
 		Ok(DeclareVariableDeclaration { keyword, declarations, position, decorators })
 	}
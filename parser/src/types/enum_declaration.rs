@@ -82,10 +82,98 @@ impl ASTNode for EnumDeclaration {
 				buf.push_new_line();
 			}
 			buf.push('}');
+		} else {
+			self.lowered_to_string_from_buffer(buf, options, local);
 		}
 	}
 }
 
+impl EnumDeclaration {
+	/// As a frozen object, created in a function so that members can reference earlier members.
+	/// Members that are not strings have a reverse mapping (value to name) unless the enum is `const`
+	/// ```js
+	/// const Direction = (() => {
+	///     const Up = 0;
+	///     const Down = Up + 1;
+	///     return Object.freeze({ Up, Down, [Up]: "Up", [Down]: "Down" });
+	/// })();
+	/// ```
+	fn lowered_to_string_from_buffer<T: source_map::ToString>(
+		&self,
+		buf: &mut T,
+		options: &crate::ToStringOptions,
+		local: crate::LocalToStringInformation,
+	) {
+		let inner = crate::LocalToStringInformation { depth: local.depth + 1, ..local };
+		let push_new_line = |buf: &mut T| {
+			if options.pretty {
+				buf.push_new_line();
+				options.add_indent(inner.depth, buf);
+			}
+		};
+
+		buf.add_mapping(&self.position.with_source(local.under));
+		buf.push_str("const ");
+		buf.push_str(&self.name);
+		buf.push_str(if options.pretty { " = (() => {" } else { "=(()=>{" });
+		let mut previous: Option<&str> = None;
+		for member in &self.members {
+			let EnumMember::Variant { name, value, .. } = member;
+			push_new_line(buf);
+			buf.push_str("const ");
+			buf.push_str(name);
+			buf.push_str(if options.pretty { " = " } else { "=" });
+			match (value, previous) {
+				(Some(value), _) => value.to_string_from_buffer(buf, options, inner),
+				(None, Some(previous)) => {
+					buf.push_str(previous);
+					buf.push_str(if options.pretty { " + 1" } else { "+1" });
+				}
+				(None, None) => buf.push('0'),
+			}
+			buf.push(';');
+			previous = Some(name);
+		}
+		push_new_line(buf);
+		buf.push_str("return Object.freeze({");
+		let mut first = true;
+		let mut push_separator = |buf: &mut T| {
+			if !first {
+				buf.push(',');
+			}
+			options.push_gap_optionally(buf);
+			first = false;
+		};
+		for EnumMember::Variant { name, .. } in &self.members {
+			push_separator(buf);
+			buf.push_str(name);
+		}
+		if !self.is_constant {
+			for EnumMember::Variant { name, value, .. } in &self.members {
+				if let Some(Expression::StringLiteral(..) | Expression::TemplateLiteral(..)) = value {
+					continue;
+				}
+				push_separator(buf);
+				buf.push('[');
+				buf.push_str(name);
+				buf.push_str(if options.pretty { "]: \"" } else { "]:\"" });
+				buf.push_str(name);
+				buf.push('"');
+			}
+		}
+		if !self.members.is_empty() {
+			options.push_gap_optionally(buf);
+		}
+		buf.push_str("});");
+		if options.pretty {
+			buf.push_new_line();
+			options.add_indent(local.depth, buf);
+		}
+		// Always ends with a semicolon as `requires_semi_colon` is for the TypeScript form
+		buf.push_str("})();");
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Visitable)]
 #[apply(derive_ASTNode)]
 pub enum EnumMember {
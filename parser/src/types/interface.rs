@@ -147,6 +147,9 @@ impl ASTNode for InterfaceDeclaration {
 					buf.push_new_line();
 				}
 			}
+			if !self.members.is_empty() {
+				options.add_indent(local.depth, buf);
+			}
 			buf.push('}');
 		}
 	}
@@ -254,6 +257,7 @@ impl ASTNode for InterfaceMember {
 			}
 			// Caller self with generic parameters
 			TSXToken::OpenChevron => {
+				reader.next();
 				let (type_parameters, _start_pos) =
 					parse_bracketed(reader, state, options, None, TSXToken::CloseChevron)?;
 				let parameters =
@@ -610,8 +614,36 @@ impl ASTNode for InterfaceMember {
 				options.push_gap_optionally(buf);
 				return_type.to_string_from_buffer(buf, options, local);
 			}
-			InterfaceMember::Constructor { .. } => todo!(),
-			InterfaceMember::Caller { .. } => todo!(),
+			InterfaceMember::Constructor {
+				parameters,
+				type_parameters,
+				return_type,
+				is_readonly,
+				..
+			}
+			| InterfaceMember::Caller {
+				parameters,
+				type_parameters,
+				return_type,
+				is_readonly,
+				..
+			} => {
+				if *is_readonly {
+					buf.push_str("readonly ");
+				}
+				if let InterfaceMember::Constructor { .. } = self {
+					buf.push_str("new ");
+				}
+				if let Some(type_parameters) = &type_parameters {
+					to_string_bracketed(type_parameters, ('<', '>'), buf, options, local);
+				}
+				parameters.to_string_from_buffer(buf, options, local);
+				if let Some(return_type) = return_type {
+					buf.push(':');
+					options.push_gap_optionally(buf);
+					return_type.to_string_from_buffer(buf, options, local);
+				}
+			}
 			InterfaceMember::Rule { .. } => todo!(),
 			InterfaceMember::Comment(_, _is_multiline, _) => todo!(),
 		}
@@ -1,5 +1,7 @@
 //! Includes type annotations + syntax added by TypeScript (and Ezno) such as `declare` declarations
 
+pub mod declare_global;
+pub mod declare_module;
 pub mod declare_variable;
 pub mod enum_declaration;
 pub mod interface;
@@ -1,15 +1,19 @@
 use get_field_by_type::GetFieldByType;
 use source_map::Span;
+use tokenizer_lib::Token;
 
-use crate::{derive_ASTNode, Block};
+use crate::{derive_ASTNode, Block, TSXKeyword, TSXToken};
 
 /// While `Block`, only some items are allowed
 #[apply(derive_ASTNode)]
-#[derive(Debug, Clone, PartialEq, get_field_by_type::GetFieldByType)]
+#[derive(Debug, Clone, PartialEq, Eq, get_field_by_type::GetFieldByType)]
 #[get_field_by_type_target(Span)]
 pub struct Namespace {
+	/// Dotted (`A.B`) for a namespace nested in the namespaces before it
 	pub name: String,
 	pub inner: Block,
+	/// `declare namespace ...`
+	pub is_declare: bool,
 	pub position: Span,
 }
 
@@ -19,14 +23,35 @@ impl crate::ASTNode for Namespace {
 		state: &mut crate::ParsingState,
 		options: &crate::ParseOptions,
 	) -> crate::ParseResult<Self> {
-		let start = reader.expect_next(crate::TSXToken::Keyword(crate::TSXKeyword::Namespace))?;
-		let (name, _) = crate::tokens::token_as_identifier(
+		// `module X {}` is the same as `namespace X {}`
+		let start = match reader.next().ok_or_else(crate::errors::parse_lexing_error)? {
+			Token(TSXToken::Keyword(TSXKeyword::Namespace), start) => start,
+			#[cfg(feature = "extras")]
+			Token(TSXToken::Keyword(TSXKeyword::Module), start) => start,
+			#[cfg(not(feature = "extras"))]
+			Token(TSXToken::Identifier(ref name), start) if name == "module" => start,
+			token => {
+				return crate::throw_unexpected_token_with_token(
+					token,
+					&[TSXToken::Keyword(TSXKeyword::Namespace)],
+				)
+			}
+		};
+		let (mut name, _) = crate::tokens::token_as_identifier(
 			reader.next().ok_or_else(crate::errors::parse_lexing_error)?,
 			"namespace name",
 		)?;
+		while reader.conditional_next(|token| *token == TSXToken::Dot).is_some() {
+			let (member, _) = crate::tokens::token_as_identifier(
+				reader.next().ok_or_else(crate::errors::parse_lexing_error)?,
+				"namespace name",
+			)?;
+			name.push('.');
+			name.push_str(&member);
+		}
 		let inner = Block::from_reader(reader, state, options)?;
 		let position = start.union(inner.get_position());
-		Ok(Self { name, inner, position })
+		Ok(Self { name, inner, is_declare: false, position })
 	}
 
 	fn to_string_from_buffer<T: source_map::ToString>(
@@ -36,10 +61,13 @@ impl crate::ASTNode for Namespace {
 		local: crate::LocalToStringInformation,
 	) {
 		if options.include_type_annotations {
+			if self.is_declare {
+				buf.push_str("declare ");
+			}
 			buf.push_str("namespace ");
 			buf.push_str(&self.name);
 			buf.push(' ');
-			self.inner.to_string_from_buffer(buf, options, local);
+			self.inner.to_string_from_buffer(buf, options, local.next_level());
 		}
 	}
 
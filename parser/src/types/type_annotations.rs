@@ -31,7 +31,7 @@ pub enum TypeAnnotation {
 	/// A name e.g. `IPost`
 	Name(String, Span),
 	CommonName(CommonTypes, Span),
-	/// A name e.g. `Intl.IPost`. TODO can there be more than 2 members
+	/// A name e.g. `Intl.IPost`. The namespace is dotted for nested namespaces (`A.B.T`)
 	NamespacedName(String, String, Span),
 	/// A name with generics e.g. `Array<number>`
 	NameWithGenericArguments(String, Vec<TypeAnnotation>, Span),
@@ -171,6 +171,7 @@ pub enum CommonTypes {
 	String,
 	Number,
 	Boolean,
+	BigInt,
 }
 
 impl TypeCondition {
@@ -278,6 +279,7 @@ impl ASTNode for TypeAnnotation {
 				CommonTypes::String => "string",
 				CommonTypes::Number => "number",
 				CommonTypes::Boolean => "boolean",
+				CommonTypes::BigInt => "bigint",
 			}),
 			Self::Decorated(decorator, on_type_annotation, _) => {
 				decorator.to_string_from_buffer(buf, options, local);
@@ -324,7 +326,11 @@ impl ASTNode for TypeAnnotation {
 					}
 				}
 			}
-			Self::NamespacedName(..) => todo!(),
+			Self::NamespacedName(namespace, member, _) => {
+				buf.push_str(namespace);
+				buf.push('.');
+				buf.push_str(member);
+			}
 			Self::ObjectLiteral(members, _) => {
 				buf.push('{');
 				for (at_end, member) in members.iter().endiate() {
@@ -337,11 +343,26 @@ impl ASTNode for TypeAnnotation {
 			}
 			Self::TupleLiteral(members, _) => {
 				buf.push('[');
-				for (at_end, (spread, member)) in members.iter().endiate() {
-					if matches!(spread, TupleElementKind::Spread) {
-						buf.push_str("...");
+				for (at_end, (kind, member)) in members.iter().endiate() {
+					match (kind, member) {
+						(
+							TupleElementKind::Optional,
+							AnnotationWithBinder::Annotated { name, ty, position: _ },
+						) => {
+							buf.push_str(name);
+							buf.push_str("?: ");
+							ty.to_string_from_buffer(buf, options, local);
+						}
+						(kind, member) => {
+							if matches!(kind, TupleElementKind::Spread) {
+								buf.push_str("...");
+							}
+							member.to_string_from_buffer(buf, options, local);
+							if matches!(kind, TupleElementKind::Optional) {
+								buf.push('?');
+							}
+						}
 					}
-					member.to_string_from_buffer(buf, options, local);
 					if !at_end {
 						buf.push_str(", ");
 					}
@@ -436,7 +457,8 @@ impl TypeAnnotation {
 				TSXToken::CloseParentheses
 					| TSXToken::CloseBracket
 					| TSXToken::CloseBrace
-					| TSXToken::Comma | TSXToken::OpenChevron
+					| TSXToken::Comma
+					| TSXToken::OpenChevron
 			) || peek.is_assignment()
 				|| (start.map_or(false, |start| {
 					peek.is_statement_or_declaration_start()
@@ -510,7 +532,7 @@ impl TypeAnnotation {
 						},
 					);
 				Self::Symbol {
-					unique: false,
+					unique: true,
 					position,
 					#[cfg(feature = "extras")]
 					name,
@@ -612,21 +634,38 @@ impl TypeAnnotation {
 			Token(TSXToken::OpenBracket, start_pos) => {
 				let mut members = Vec::new();
 				loop {
-					if let Some(Token(TSXToken::CloseBrace, _)) = reader.peek() {
+					if let Some(Token(TSXToken::CloseBracket, _)) = reader.peek() {
 						break;
 					}
 					let is_spread = reader
 						.conditional_next(|token| matches!(token, TSXToken::Spread))
 						.is_some();
 
-					let annotation_with_binder =
-						AnnotationWithBinder::from_reader(reader, state, options)?;
+					// `name?: T` (where `?:` is a single token)
+					let is_optional_named =
+						matches!(reader.peek_n(1), Some(Token(TSXToken::OptionalMember, _)));
+					let annotation_with_binder = if is_optional_named {
+						let (name, pos) = token_as_identifier(
+							reader.next().unwrap(),
+							"tuple literal named item",
+						)?;
+						reader.next();
+						let ty = TypeAnnotation::from_reader(reader, state, options)?;
+						AnnotationWithBinder::Annotated {
+							position: pos.union(ty.get_position()),
+							name,
+							ty,
+						}
+					} else {
+						AnnotationWithBinder::from_reader(reader, state, options)?
+					};
 
 					let kind = if is_spread {
 						TupleElementKind::Spread
-					} else if reader
-						.conditional_next(|token| matches!(token, TSXToken::QuestionMark))
-						.is_some()
+					} else if is_optional_named
+						|| reader
+							.conditional_next(|token| matches!(token, TSXToken::QuestionMark))
+							.is_some()
 					{
 						TupleElementKind::Optional
 					} else {
@@ -670,9 +709,22 @@ impl TypeAnnotation {
 				Self::TemplateLiteral(parts, start.union(end.unwrap()))
 			}
 			Token(TSXToken::Keyword(TSXKeyword::Readonly), start) => {
-				let readonly_type = TypeAnnotation::from_reader(reader, state, options)?;
+				// `readonly string[] | undefined` is `(readonly string[]) | undefined`
+				let readonly_type = TypeAnnotation::from_reader_with_config(
+					reader,
+					state,
+					options,
+					Some(TypeOperatorKind::Query),
+					Some(start),
+				)?;
 				let position = start.union(readonly_type.get_position());
-				return Ok(TypeAnnotation::Readonly(Box::new(readonly_type), position));
+				if !matches!(
+					readonly_type,
+					TypeAnnotation::ArrayLiteral(..) | TypeAnnotation::TupleLiteral(..)
+				) {
+					return Err(ParseError::new(ParseErrors::ReadonlyOnlyOnArrayOrTuple, position));
+				}
+				TypeAnnotation::Readonly(Box::new(readonly_type), position)
 			}
 			Token(TSXToken::Keyword(TSXKeyword::KeyOf), start) => {
 				let key_of_type = TypeAnnotation::from_reader(reader, state, options)?;
@@ -706,21 +758,32 @@ impl TypeAnnotation {
 					"string" => Self::CommonName(CommonTypes::String, pos),
 					"number" => Self::CommonName(CommonTypes::Number, pos),
 					"boolean" => Self::CommonName(CommonTypes::Boolean, pos),
+					"bigint" => Self::CommonName(CommonTypes::BigInt, pos),
 					_ => Self::Name(name, pos),
 				}
 			}
 		};
 		// Namespaced name
 		if let Some(Token(TSXToken::Dot, _)) = reader.peek() {
-			let Self::Name(name, start) = reference else { return Ok(reference) };
+			let Self::Name(mut name, start) = reference else { return Ok(reference) };
 			reader.next();
-			let (namespace_member, end) =
+			let (mut namespace_member, mut end) =
 				token_as_identifier(reader.next().unwrap(), "namespace name")?;
+			// Nested namespaces (`A.B.C`) are under the dotted name
+			while reader.conditional_next(|token| *token == TSXToken::Dot).is_some() {
+				name.push('.');
+				name.push_str(&namespace_member);
+				(namespace_member, end) =
+					token_as_identifier(reader.next().unwrap(), "namespace name")?;
+			}
 			let position = start.union(end);
-			return Ok(TypeAnnotation::NamespacedName(name, namespace_member, position));
+			reference = TypeAnnotation::NamespacedName(name, namespace_member, position);
 		}
-		// Generics arguments:
-		if let Some(Token(TSXToken::OpenChevron, _position)) = reader.peek() {
+		// Generics arguments. A `<` on a new line starts a call signature (in an interface) instead
+		if let Some(Token(TSXToken::OpenChevron, _)) = reader.peek().filter(|token| {
+			let (end, at) = (reference.get_position().end as usize, token.1 .0 as usize);
+			at < end || !state.line_starts.byte_indexes_on_different_lines(end, at)
+		}) {
 			// Assert its a Self::Name
 			let Self::Name(name, start_span) = reference else {
 				let position = reader.next().unwrap().get_span();
@@ -840,7 +903,8 @@ impl TypeAnnotation {
 					parent_kind,
 					Some(
 						TypeOperatorKind::Union
-							| TypeOperatorKind::Query | TypeOperatorKind::Function
+							| TypeOperatorKind::Query
+							| TypeOperatorKind::Function
 					)
 				) {
 					return Ok(reference);
@@ -236,7 +236,7 @@ impl ASTNode for ArrayDestructuringField {
 				}
 			}
 			Self::Comment { content, is_multiline, position: _ } => {
-				if options.should_add_comment(*is_multiline && content.starts_with('*')) {
+				if options.should_add_comment(*is_multiline && content.starts_with('*'), content) {
 					buf.push_str("/*");
 					buf.push_str(content);
 					buf.push_str("*/");
@@ -243,6 +243,8 @@ mod ast {
 		crate::types::InterfaceDeclaration,
 		crate::types::type_alias::TypeAlias,
 		crate::types::declare_variable::DeclareVariableDeclaration,
+		crate::types::declare_global::DeclareGlobalDeclaration,
+		crate::types::declare_module::DeclareModuleDeclaration,
 		crate::VariableIdentifier,
 		crate::PropertyReference,
 		crate::Quoted,
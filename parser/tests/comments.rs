@@ -1,4 +1,4 @@
-use ezno_parser::{ASTNode, Module};
+use ezno_parser::{ASTNode, Comments, Module, ParseOptions, ToStringOptions, TripleSlashDirective};
 
 #[test]
 fn random_comments() {
@@ -12,3 +12,64 @@ fn random_comments() {
 	// let output = module.to_string(&ToStringOptions::typescript());
 	// pretty_assertions::assert_eq!(output, input);
 }
+
+#[test]
+fn legal_comments() {
+	let input = r#"
+/*! Library v1.0 */
+// normal comment
+/** Documentation */
+/* @license MIT */
+//! Also kept
+/* normal block comment */
+const x = 2;
+"#
+	.trim_start();
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+	let output = module
+		.to_string(&ToStringOptions { comments: Comments::Legal, ..ToStringOptions::minified() });
+	pretty_assertions::assert_eq!(
+		output,
+		"/*! Library v1.0 *//* @license MIT *///! Also kept\nconst x=2"
+	);
+
+	// Legal comments are also kept when only parsing documentation comments
+	let options = ParseOptions { comments: Comments::JustDocumentation, ..Default::default() };
+	let module = Module::from_string(input.to_owned(), options).unwrap();
+	let output = module.to_string(&ToStringOptions::default());
+	pretty_assertions::assert_eq!(
+		output,
+		"/*! Library v1.0 */\n/** Documentation */\n/* @license MIT */\n//! Also kept\nconst x = 2"
+	);
+}
+
+#[test]
+fn triple_slash_directives() {
+	let input = r#"
+/// <reference path="./globals.d.ts" />
+/// <reference types='node' />
+/// <reference lib="es2015" />
+/// <reference no-default-lib="true" />
+/// Not a directive
+declare const x: number;
+/// <reference path="./after.d.ts" />
+"#
+	.trim_start();
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+	let directives = module
+		.triple_slash_directives()
+		.into_iter()
+		.map(|(directive, _)| directive)
+		.collect::<Vec<_>>();
+
+	pretty_assertions::assert_eq!(
+		directives,
+		[
+			TripleSlashDirective::Path("./globals.d.ts".to_owned()),
+			TripleSlashDirective::Types("node".to_owned()),
+			TripleSlashDirective::Lib("es2015".to_owned()),
+		]
+	);
+}
@@ -103,6 +103,33 @@ const d = /in a set[=/]/
 }
 
 #[cfg(feature = "extras")]
+#[test]
+fn big_int_literals() {
+	let input = r"
+const a = 123n;
+const b = 0x1fn;
+let c: bigint = 2n
+    "
+	.trim();
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+	let output = module.to_string(&ezno_parser::ToStringOptions::typescript());
+	assert_eq!(output, input);
+}
+
+#[test]
+fn import_meta() {
+	let input = r"
+const url = import.meta.url;
+import.meta.env.MODE
+    "
+	.trim();
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+	let output = module.to_string(&ezno_parser::ToStringOptions::typescript());
+	assert_eq!(output, input);
+}
+
 #[test]
 fn jsx() {
 	// note the parser supports self closing tags with `<img>` and HTML comments
@@ -133,3 +160,24 @@ function Component(item) {
 
 	assert_eq!(output, input);
 }
+
+#[test]
+fn crlf_line_endings() {
+	let input = "const a = 2;\r\nconst b = \"line \\\r\ncontinued\";\r\nconst c = 4";
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+
+	let output = module.to_string(&ezno_parser::ToStringOptions::typescript());
+	assert_eq!(output, "const a = 2;\nconst b = \"line \\\r\ncontinued\";\nconst c = 4");
+}
+
+#[test]
+fn lone_carriage_return_line_continuation() {
+	// The `"` after the continuation ends the string rather than being escaped
+	let input = "const b = \"line \\\r\";\nconst c = 4";
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+
+	let output = module.to_string(&ezno_parser::ToStringOptions::typescript());
+	assert_eq!(output, input);
+}
@@ -0,0 +1,101 @@
+use ezno_parser::{
+	outline::{Symbol, SymbolKind},
+	ASTNode, Module,
+};
+
+/// `(name, kind, is_exported, children)`
+fn simplify(symbols: &[Symbol]) -> Vec<(String, SymbolKind, bool, Vec<(String, SymbolKind)>)> {
+	symbols
+		.iter()
+		.map(|symbol| {
+			let children =
+				symbol.children.iter().map(|child| (child.name.clone(), child.kind)).collect();
+			(symbol.name.clone(), symbol.kind, symbol.is_exported, children)
+		})
+		.collect()
+}
+
+#[test]
+fn module_outline() {
+	let input = r#"
+import { x } from "./x";
+
+export class Point {
+	x: number;
+	#secret = 2;
+	constructor() {}
+	get length() { return 0 }
+	scale(by: number) {}
+}
+
+function helper() {
+	const inner = 2;
+}
+
+const a = 1, { b, c: [d] } = {}, add = (a, b) => a + b;
+
+export interface Shape {
+	area(): number;
+	name: string
+}
+
+type Alias = string;
+
+enum Direction { Up, Down }
+
+export default 4;
+"#;
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+	let outline = module.outline();
+
+	let owned = |items: &[(&str, SymbolKind)]| {
+		items.iter().map(|(name, kind)| ((*name).to_owned(), *kind)).collect::<Vec<_>>()
+	};
+
+	assert_eq!(
+		simplify(&outline),
+		vec![
+			(
+				"Point".to_owned(),
+				SymbolKind::Class,
+				true,
+				owned(&[
+					("x", SymbolKind::Property),
+					("#secret", SymbolKind::Property),
+					("constructor", SymbolKind::Constructor),
+					("length", SymbolKind::Getter),
+					("scale", SymbolKind::Method)
+				])
+			),
+			("helper".to_owned(), SymbolKind::Function, false, Vec::new()),
+			("a".to_owned(), SymbolKind::Variable, false, Vec::new()),
+			("b".to_owned(), SymbolKind::Variable, false, Vec::new()),
+			("d".to_owned(), SymbolKind::Variable, false, Vec::new()),
+			("add".to_owned(), SymbolKind::Function, false, Vec::new()),
+			(
+				"Shape".to_owned(),
+				SymbolKind::Interface,
+				true,
+				owned(&[("area", SymbolKind::Method), ("name", SymbolKind::Property)])
+			),
+			("Alias".to_owned(), SymbolKind::TypeAlias, false, Vec::new()),
+			(
+				"Direction".to_owned(),
+				SymbolKind::Enum,
+				false,
+				owned(&[("Up", SymbolKind::EnumMember), ("Down", SymbolKind::EnumMember)])
+			),
+			("default".to_owned(), SymbolKind::Variable, true, Vec::new()),
+		]
+	);
+
+	// Positions are of the source
+	let point = &outline[0];
+	assert_eq!(
+		&input[point.name_position.start as usize..point.name_position.end as usize],
+		"Point"
+	);
+	assert!(input[point.position.start as usize..point.position.end as usize]
+		.starts_with("class Point"));
+}
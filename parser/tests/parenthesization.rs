@@ -0,0 +1,150 @@
+use ezno_parser::{
+	expressions::{
+		operators::{BinaryOperator, Operator, UnaryOperator},
+		MultipleExpression,
+	},
+	source_map::Nullable,
+	statements::ReturnStatement,
+	ASTNode, Expression, ParseOptions, Span, Statement, ToStringOptions,
+};
+use pretty_assertions::assert_eq;
+
+const BINARY_OPERATORS: &[BinaryOperator] = &[
+	BinaryOperator::Add,
+	BinaryOperator::Subtract,
+	BinaryOperator::Multiply,
+	BinaryOperator::Divide,
+	BinaryOperator::Modulo,
+	BinaryOperator::Exponent,
+	BinaryOperator::BitwiseShiftLeft,
+	BinaryOperator::BitwiseShiftRight,
+	BinaryOperator::BitwiseShiftRightUnsigned,
+	BinaryOperator::BitwiseAnd,
+	BinaryOperator::BitwiseXOr,
+	BinaryOperator::BitwiseOr,
+	BinaryOperator::StrictEqual,
+	BinaryOperator::StrictNotEqual,
+	BinaryOperator::Equal,
+	BinaryOperator::NotEqual,
+	BinaryOperator::GreaterThan,
+	BinaryOperator::LessThan,
+	BinaryOperator::LessThanEqual,
+	BinaryOperator::GreaterThanEqual,
+	BinaryOperator::LogicalAnd,
+	BinaryOperator::LogicalOr,
+	BinaryOperator::NullCoalescing,
+];
+
+fn reference(name: &str) -> Expression {
+	Expression::VariableReference(name.to_owned(), Span::NULL)
+}
+
+fn binary(lhs: Expression, operator: BinaryOperator, rhs: Expression) -> Expression {
+	Expression::BinaryOperation {
+		lhs: Box::new(lhs),
+		operator,
+		rhs: Box::new(rhs),
+		position: Span::NULL,
+	}
+}
+
+/// Structure of the expression, ignoring any parentheses in the AST
+fn fully_parenthesized(expression: &Expression) -> String {
+	match expression {
+		Expression::BinaryOperation { lhs, operator, rhs, .. } => format!(
+			"({} {} {})",
+			fully_parenthesized(lhs),
+			operator.to_str(),
+			fully_parenthesized(rhs)
+		),
+		Expression::UnaryOperation { operator, operand, .. } => {
+			format!("({}{})", operator.to_str(), fully_parenthesized(operand))
+		}
+		Expression::ParenthesizedExpression(inner, _) => match &**inner {
+			MultipleExpression::Single(inner) => fully_parenthesized(inner),
+			MultipleExpression::Multiple { .. } => panic!("unexpected comma expression"),
+		},
+		Expression::VariableReference(name, _) => name.clone(),
+		expression => panic!("unexpected expression {expression:?}"),
+	}
+}
+
+/// Printing a built AST and parsing it again should give the same structure
+fn assert_round_trips(expression: &Expression) {
+	let parse_options = ParseOptions { type_annotations: false, ..Default::default() };
+	for to_string_options in [ToStringOptions::default(), ToStringOptions::minified()] {
+		let output = expression.to_string(&to_string_options);
+		let reparsed = Expression::from_string(output.clone(), parse_options)
+			.unwrap_or_else(|err| panic!("could not parse {output:?}: {err:?}"));
+		assert_eq!(
+			fully_parenthesized(&reparsed),
+			fully_parenthesized(expression),
+			"printed as {output:?}"
+		);
+	}
+}
+
+#[test]
+fn binary_operator_pairs() {
+	for &outer in BINARY_OPERATORS {
+		for &inner in BINARY_OPERATORS {
+			assert_round_trips(&binary(
+				binary(reference("a"), inner, reference("b")),
+				outer,
+				reference("c"),
+			));
+			assert_round_trips(&binary(
+				reference("a"),
+				outer,
+				binary(reference("b"), inner, reference("c")),
+			));
+		}
+	}
+}
+
+#[test]
+fn unary_operands() {
+	for &operator in BINARY_OPERATORS {
+		let negated = |on: Expression| Expression::UnaryOperation {
+			operator: UnaryOperator::Negation,
+			operand: Box::new(on),
+			position: Span::NULL,
+		};
+		assert_round_trips(&negated(binary(reference("a"), operator, reference("b"))));
+		assert_round_trips(&binary(negated(reference("a")), operator, reference("b")));
+		assert_round_trips(&binary(reference("a"), operator, negated(reference("b"))));
+	}
+}
+
+#[test]
+fn nullish_coalescing_with_logical_operators() {
+	let expression = binary(
+		binary(reference("a"), BinaryOperator::NullCoalescing, reference("b")),
+		BinaryOperator::LogicalOr,
+		reference("c"),
+	);
+	assert_eq!(expression.to_string(&ToStringOptions::minified()), "(a??b)||c");
+}
+
+#[test]
+fn return_with_leading_line_comment() {
+	let expression = Expression::Comment {
+		content: " comment".to_owned(),
+		on: Box::new(reference("a")),
+		position: Span::NULL,
+		is_multiline: false,
+		prefix: true,
+	};
+	let statement = Statement::Return(ReturnStatement(
+		Some(MultipleExpression::Single(expression)),
+		Span::NULL,
+	));
+
+	let output = statement.to_string(&ToStringOptions::default());
+	let reparsed = Statement::from_string(output.clone(), ParseOptions::default()).unwrap();
+
+	assert!(
+		matches!(reparsed, Statement::Return(ReturnStatement(Some(_), _))),
+		"return value lost by automatic semicolon insertion in {output:?}"
+	);
+}
@@ -118,3 +118,180 @@ export default function (a: string): string
 
 	assert_eq!(output, input.clone());
 }
+
+#[test]
+fn declare_global() {
+	let input = r#"
+declare global {
+    interface Window {
+        myThing: Foo
+    }
+}
+"#
+	.trim()
+	.replace("    ", "\t");
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input.clone());
+}
+
+#[test]
+fn declare_module() {
+	let input = r#"
+declare module "react" {
+    interface Props {
+        myThing: Foo
+    }
+}
+"#
+	.trim()
+	.replace("    ", "\t");
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input.clone());
+}
+
+#[test]
+fn ambient_declarations() {
+	let input = r#"
+declare const x: number
+declare function y(a: string): string
+declare class Z {}
+const a = x
+"#
+	.trim()
+	.replace("    ", "\t");
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+
+	let output = module.to_string(&ToStringOptions::typescript());
+	assert_eq!(output, input.clone());
+
+	let output = module.to_string(&ToStringOptions::default());
+	assert_eq!(output, "const a = x");
+}
+
+#[test]
+fn namespaces() {
+	let input = r#"
+namespace A {
+    export const a = 2
+}
+declare namespace B {
+    interface Props {
+        b: string
+    }
+}
+export namespace C {
+    export namespace D {
+        export type E = string
+    }
+}
+const x: B.Props = A.a
+"#
+	.trim()
+	.replace("    ", "\t");
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input.clone());
+}
+
+#[test]
+fn construct_and_call_signatures() {
+	let input = r#"
+interface Ctor {
+    new (x: string): Foo
+    new <T>(x: T): Bar<T>
+    (y: number): string
+    <T>(y: T): T
+}
+"#
+	.trim()
+	.replace("    ", "\t");
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input);
+}
+
+#[test]
+fn tuple_types() {
+	let input = r#"
+type Empty = [];
+type Pair = [string, number?];
+type Named = [first: string, second?: number, ...rest: boolean[]]
+"#
+	.trim()
+	.to_owned();
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input);
+}
+
+#[test]
+fn readonly_types() {
+	let input = r#"
+type A = readonly string[];
+type B = readonly [string, number] | undefined
+"#
+	.trim()
+	.to_owned();
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input);
+
+	assert!(Module::from_string("type C = readonly string".to_owned(), Default::default()).is_err());
+}
+
+#[test]
+fn namespaced_names_and_const_enums() {
+	let input = "const x: Flags.Read | Flags.Write = 1".to_owned();
+
+	let module = Module::from_string(input.clone(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	assert_eq!(output, input);
+
+	let module =
+		Module::from_string("const enum Flags { Read }".to_owned(), Default::default()).unwrap();
+	let output =
+		module.to_string(&ToStringOptions { pretty: false, ..ToStringOptions::typescript() });
+
+	assert_eq!(output, "const enum Flags{Read}");
+}
+
+#[test]
+fn module_blocks_and_dotted_namespaces() {
+	let input = r#"
+module X {}
+namespace A.B {}
+declare module Y {}
+declare module "z" {}
+const t: A.B.T = 1
+    "#
+	.trim_start();
+
+	let module = Module::from_string(input.to_owned(), Default::default()).unwrap();
+	let output = module.to_string(&ToStringOptions::typescript());
+
+	let expected = r#"
+namespace X {}
+namespace A.B {}
+declare namespace Y {}
+declare module "z" {}
+const t: A.B.T = 1"#
+		.trim_start();
+
+	assert_eq!(output, expected);
+}
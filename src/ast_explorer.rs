@@ -31,8 +31,9 @@ impl ExplorerArguments {
 		cli_input_resolver: U,
 	) {
 		if let Some(ref file) = self.file {
-			let content = fs_resolver.get_content_at_path(file);
+			let content = fs_resolver.read_file(file);
 			if let Some(content) = content {
+				let content = String::from_utf8_lossy(&content).into_owned();
 				self.nested.run(content, Some(file.to_owned()));
 			} else {
 				eprintln!("Could not find file at {}", file.display());
@@ -78,6 +79,7 @@ impl ExplorerArguments {
 pub(crate) enum ExplorerSubCommand {
 	AST(ASTArgs),
 	FullAST(FullASTArgs),
+	Outline(OutlineArgs),
 	Prettifier(PrettyArgs),
 	Uglifier(UglifierArgs),
 	Lexer(LexerArgs),
@@ -101,6 +103,15 @@ pub(crate) struct FullASTArgs {
 	json: bool,
 }
 
+/// Prints the declarations in a module as a tree
+#[derive(FromArgs, Debug, Default)]
+#[argh(subcommand, name = "outline")]
+pub(crate) struct OutlineArgs {
+	/// print results as json
+	#[argh(switch)]
+	json: bool,
+}
+
 /// Prettifies source code (full whitespace)
 #[derive(FromArgs, Debug, Default)]
 #[argh(subcommand, name = "prettifier")]
@@ -159,6 +170,26 @@ impl ExplorerSubCommand {
 					Err(err) => emit_ezno_diagnostic((err, source_id).into(), &fs).unwrap(),
 				}
 			}
+			ExplorerSubCommand::Outline(cfg) => {
+				let mut fs =
+					parser::source_map::MapFileStore::<parser::source_map::NoPathMap>::default();
+				let source_id = fs.new_source_id(path.unwrap_or_default(), input.clone());
+				let res = Module::from_string(input, parser::ParseOptions::all_features());
+				match res {
+					Ok(module) => {
+						let outline = module.outline();
+						if cfg.json {
+							print_to_cli(format_args!(
+								"{}",
+								serde_json::to_string_pretty(&outline).unwrap()
+							));
+						} else {
+							print_outline(&outline, 0);
+						}
+					}
+					Err(err) => emit_ezno_diagnostic((err, source_id).into(), &fs).unwrap(),
+				}
+			}
 			ExplorerSubCommand::Prettifier(_) | ExplorerSubCommand::Uglifier(_) => {
 				let mut fs =
 					parser::source_map::MapFileStore::<parser::source_map::NoPathMap>::default();
@@ -201,3 +232,18 @@ impl ExplorerSubCommand {
 		}
 	}
 }
+
+fn print_outline(symbols: &[parser::outline::Symbol], depth: usize) {
+	for symbol in symbols {
+		let exported = if symbol.is_exported { "export " } else { "" };
+		print_to_cli(format_args!(
+			"{}{exported}{:?} {} ({}..{})",
+			"\t".repeat(depth),
+			symbol.kind,
+			symbol.name,
+			symbol.position.start,
+			symbol.position.end
+		));
+		print_outline(&symbol.children, depth + 1);
+	}
+}
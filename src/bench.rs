@@ -0,0 +1,91 @@
+//! Checking a project several times and summarising the [`checker::Chronometer`] measurements.
+//! Used by `ezno experimental bench` and the benchmarks in `benches`
+
+use std::{path::PathBuf, time::Duration};
+
+use checker::TypeCheckOptions;
+
+/// Measurements from a single check
+#[derive(Debug, Clone)]
+pub struct Run {
+	pub definition_files: Duration,
+	/// Summed over modules
+	pub parse: Duration,
+	/// Summed over modules
+	pub check: Duration,
+	pub modules: usize,
+	pub types: usize,
+	pub diagnostics: usize,
+}
+
+impl Run {
+	#[must_use]
+	pub fn total(&self) -> Duration {
+		self.definition_files + self.parse + self.check
+	}
+
+	/// Durations are in milliseconds
+	#[must_use]
+	pub fn to_json(&self, name: &str) -> serde_json::Value {
+		let milliseconds = |duration: Duration| duration.as_secs_f64() * 1000.0;
+		serde_json::json!({
+			"name": name,
+			"definition_files": milliseconds(self.definition_files),
+			"parse": milliseconds(self.parse),
+			"check": milliseconds(self.check),
+			"total": milliseconds(self.total()),
+			"modules": self.modules,
+			"types": self.types,
+			"diagnostics": self.diagnostics,
+		})
+	}
+}
+
+/// Checks `entry_points` `iterations` times (at least once)
+pub fn benchmark<T: crate::ReadFromFS>(
+	entry_points: &[PathBuf],
+	read_file: &T,
+	definition_files: &[PathBuf],
+	iterations: usize,
+) -> Vec<Run> {
+	(0..iterations.max(1))
+		.map(|_| {
+			let options = TypeCheckOptions { measure_time: true, ..Default::default() };
+			let output = crate::check(entry_points.to_vec(), read_file, definition_files, options);
+			let chronometer = output.chronometer;
+			Run {
+				definition_files: chronometer.definition_files,
+				parse: chronometer.modules.iter().map(|module| module.parse).sum(),
+				check: chronometer.modules.iter().map(|module| module.check).sum(),
+				modules: chronometer.modules.len(),
+				types: chronometer.types,
+				diagnostics: output.diagnostics.iter().count(),
+			}
+		})
+		.collect()
+}
+
+/// The run with the median total duration (so that numbers are comparable between commits)
+#[must_use]
+pub fn median(mut runs: Vec<Run>) -> Option<Run> {
+	runs.sort_by_key(Run::total);
+	let middle = runs.len() / 2;
+	runs.into_iter().nth(middle)
+}
+
+pub const TABLE_HEADER: &str =
+	"name                     definitions      parse      check      total  modules    types";
+
+#[must_use]
+pub fn table_row(name: &str, run: &Run) -> String {
+	let milliseconds = |duration: Duration| format!("{:.2}ms", duration.as_secs_f64() * 1000.0);
+	format!(
+		"{name:<24} {:>12} {:>10} {:>10} {:>10} {:>8} {:>8}",
+		milliseconds(run.definition_files),
+		milliseconds(run.parse),
+		milliseconds(run.check),
+		milliseconds(run.total()),
+		run.modules,
+		run.types
+	)
+}
@@ -1,18 +1,28 @@
 use std::{
+	collections::{HashMap, HashSet},
 	mem,
 	path::{Path, PathBuf},
 };
 
-use checker::{DiagnosticsContainer, TypeCheckOptions};
+use checker::{
+	synthesis::{EznoParser, PreCheckVisitors},
+	CheckOutput, DiagnosticsContainer, TypeCheckOptions,
+};
 use parser::{
-	source_map::{MapFileStore, WithPathMap},
-	ToStringOptions,
+	source_map::{FileSystem, MapFileStore, WithPathMap},
+	ASTNode, Module, ParseOptions, SourceId, ToStringOptions,
+};
+
+use crate::{
+	bundle::{self, ModuleGraph},
+	transformers::{define::Define, type_imports::remove_type_only_imports_and_exports},
 };
 
 #[cfg_attr(target_family = "wasm", derive(serde::Serialize, tsify::Tsify))]
 pub struct Output {
 	pub output_path: PathBuf,
 	pub content: String,
+	/// Source map (JSON) for `content`. Empty unless [`BuildConfig::source_maps`]
 	pub mappings: String,
 }
 
@@ -35,10 +45,46 @@ pub struct FailedBuildOutput {
 	pub fs: MapFileStore<WithPathMap>,
 }
 
+#[derive(Default)]
 #[cfg_attr(target_family = "wasm", derive(serde::Deserialize))]
 pub struct BuildConfig {
 	#[cfg_attr(target_family = "wasm", serde(default))]
 	pub strip_whitespace: bool,
+	/// Output a chunk for each entry point and dynamically imported module into the output path (as a
+	/// directory), along with a `manifest.json` describing them
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub bundle: bool,
+	/// Move modules shared between chunks into their own chunks. Requires `bundle`
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub split: bool,
+	/// Remove bare imports (`import "./a"`) of modules without side effects. Side effects are found from
+	/// the checked module bodies and `sideEffects` in `package.json`. Requires `bundle`
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub tree_shake: bool,
+	/// (name, value) pairs. References to `name` (e.g. `process.env.NODE_ENV`) are replaced with
+	/// `value` before checking
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub define: Vec<(String, String)>,
+	/// Added to the start of each output (e.g. a license comment)
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub banner: Option<String>,
+	/// Added to the end of each output
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub footer: Option<String>,
+	/// See [`TypeCheckOptions::paths`]
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub paths: Vec<(String, Vec<String>)>,
+	/// Also output a `.d.ts` file for each entry point, declaring the types of its exports. See
+	/// [`declaration_outputs`]
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub declaration: bool,
+	/// Output a source map alongside each output, referenced by a `//# sourceMappingURL` comment. Not
+	/// yet supported with `bundle`
+	#[cfg_attr(target_family = "wasm", serde(default))]
+	pub source_maps: bool,
+	/// See [`TypeCheckOptions::module_progress`]
+	#[cfg_attr(target_family = "wasm", serde(skip))]
+	pub module_progress: checker::ModuleProgress,
 }
 
 pub type EznoParsePostCheckVisitors =
@@ -65,16 +111,49 @@ impl CheckingOutputWithoutDiagnostics {
 pub fn build<T: crate::ReadFromFS>(
 	input_paths: Vec<PathBuf>,
 	fs_resolver: &T,
-	type_definition_module: Option<&Path>,
+	type_definition_files: &[PathBuf],
 	output_path: &Path,
 	config: &BuildConfig,
 	transformers: Option<EznoParsePostCheckVisitors>,
 ) -> Result<BuildOutput, FailedBuildOutput> {
 	// TODO parse options + non_standard_library & non_standard_syntax
-	let type_check_options =
-		TypeCheckOptions { store_expression_type_mappings: true, ..Default::default() };
+	let type_check_options = TypeCheckOptions {
+		store_expression_type_mappings: true,
+		paths: config.paths.clone(),
+		module_progress: config.module_progress.clone(),
+		..Default::default()
+	};
+
+	let define = match Define::new(&config.define) {
+		Ok(define) => define,
+		Err(reason) => {
+			let mut diagnostics = DiagnosticsContainer::new();
+			diagnostics.add_error(checker::Diagnostic::Global {
+				reason,
+				kind: checker::DiagnosticKind::Error,
+			});
+			return Err(FailedBuildOutput { diagnostics, fs: Default::default() });
+		}
+	};
+	let mut pre_check_visitors = PreCheckVisitors::default();
+	if !define.is_empty() {
+		pre_check_visitors.expression_visitors_mut.push(Box::new(define));
+	}
 
-	let result = crate::check(input_paths, fs_resolver, type_definition_module, type_check_options);
+	let entries = input_paths.clone();
+	let result = crate::check_with_visitors(
+		input_paths,
+		fs_resolver,
+		type_definition_files,
+		type_check_options,
+		pre_check_visitors,
+	);
+
+	let declarations = if config.declaration && !result.diagnostics.has_error() {
+		declaration_outputs(&entries, &result, output_path, config.bundle, fs_resolver)
+	} else {
+		Vec::new()
+	};
 
 	let mut data = CheckingOutputWithoutDiagnostics {
 		module_contents: result.module_contents,
@@ -83,6 +162,21 @@ pub fn build<T: crate::ReadFromFS>(
 	};
 
 	if !result.diagnostics.has_error() {
+		if config.bundle {
+			let mut diagnostics = result.diagnostics;
+			let mut outputs = bundle_outputs(
+				&entries,
+				fs_resolver,
+				&mut data,
+				output_path,
+				config,
+				transformers,
+				&mut diagnostics,
+			);
+			outputs.extend(declarations);
+			return Ok(BuildOutput { outputs, diagnostics, fs: data.module_contents });
+		}
+
 		// TODO For all modules
 		let keys = data.modules.keys().cloned().collect::<Vec<_>>();
 
@@ -108,25 +202,271 @@ pub fn build<T: crate::ReadFromFS>(
 				&parser::visiting::VisitOptions::default(),
 				source,
 			);
+			remove_type_only_imports_and_exports(
+				&mut module,
+				source,
+				&data,
+				fs_resolver,
+				&config.paths,
+			);
 
-			let to_string_options = if config.strip_whitespace {
-				ToStringOptions::minified()
+			let to_string_options = get_to_string_options(config);
+			let (content, mappings) = if config.source_maps {
+				let (content, source_map) = module.to_string_with_source_map(
+					&to_string_options,
+					source,
+					&data.module_contents,
+				);
+				(content, source_map_json(source_map, config, &data.module_contents))
 			} else {
-				ToStringOptions::default()
+				(parser::ASTNode::to_string(&module, &to_string_options), String::new())
 			};
 
-			let content = parser::ASTNode::to_string(&module, &to_string_options);
+			let mut content = add_banner_and_footer(content, config);
+			if !mappings.is_empty() {
+				if let Some(name) = output_path.file_name() {
+					content.push_str("\n//# sourceMappingURL=");
+					content.push_str(&name.to_string_lossy());
+					content.push_str(".map");
+				}
+			}
 
-			outputs.push(Output {
-				output_path: output_path.to_path_buf(),
-				content,
-				// TODO module.to_string_with_map
-				mappings: String::new(),
-			})
+			outputs.push(Output { output_path: output_path.to_path_buf(), content, mappings })
 		}
 
+		outputs.extend(declarations);
 		Ok(BuildOutput { outputs, diagnostics: result.diagnostics, fs: data.module_contents })
 	} else {
 		Err(FailedBuildOutput { diagnostics: result.diagnostics, fs: data.module_contents })
 	}
 }
+
+fn bundle_outputs(
+	entries: &[PathBuf],
+	fs_resolver: &impl crate::ReadFromFS,
+	data: &mut CheckingOutputWithoutDiagnostics,
+	output_directory: &Path,
+	config: &BuildConfig,
+	transformers: Option<EznoParsePostCheckVisitors>,
+	diagnostics: &mut DiagnosticsContainer,
+) -> Vec<Output> {
+	let null_module =
+		parser::Module { items: Default::default(), span: parser::source_map::Nullable::NULL };
+	let mut transformers = transformers.unwrap_or_default();
+
+	let sources = data.modules.keys().copied().collect::<Vec<_>>();
+	let mut modules = HashMap::new();
+	for source in sources {
+		let mut module =
+			mem::replace(&mut data.modules.get_mut(&source).unwrap().content, null_module.clone());
+		module.visit_mut::<CheckingOutputWithoutDiagnostics>(
+			&mut transformers,
+			data,
+			&parser::visiting::VisitOptions::default(),
+			source,
+		);
+		remove_type_only_imports_and_exports(&mut module, source, data, fs_resolver, &config.paths);
+		modules.insert(source, module);
+	}
+
+	let files = &data.module_contents;
+	let entry_sources = get_entry_sources(entries, files, fs_resolver);
+	let base = entries.first().and_then(|path| path.parent());
+
+	let mut packages = bundle::PackageSideEffects::default();
+	let side_effect_free = modules
+		.keys()
+		.copied()
+		.filter(|source| packages.is_side_effect_free(&files.get_file_path(*source), fs_resolver))
+		.collect::<HashSet<_>>();
+	let mut graph = ModuleGraph::new(
+		&modules,
+		files,
+		fs_resolver,
+		&config.paths,
+		|source| data.modules[&source].has_top_level_await,
+		|source| data.modules[&source].has_top_level_side_effects(source),
+		|source| side_effect_free.contains(&source),
+	);
+	if config.tree_shake {
+		graph.remove_side_effect_free_imports();
+	}
+	let chunks = bundle::create_chunks(&entry_sources, &graph, files, base, config.split);
+	let wrapped = bundle::colliding_modules(
+		&chunks,
+		&graph,
+		&modules,
+		|source| data.modules[&source].is_common_js,
+		|source| data.modules[&source].has_top_level_await,
+		diagnostics,
+	);
+	let common_js = bundle::common_js_names(&chunks, |source| {
+		data.modules[&source].is_common_js || wrapped.contains(&source)
+	});
+	let imported_across_chunks = bundle::imported_across_chunks(&chunks, &graph);
+
+	// As modules are concatenated
+	let to_string_options =
+		ToStringOptions { trailing_semicolon: true, ..get_to_string_options(config) };
+	let separator = if config.strip_whitespace { "" } else { "\n" };
+
+	let mut outputs = chunks
+		.iter()
+		.enumerate()
+		.map(|(idx, chunk)| {
+			let content = chunk
+				.modules
+				.iter()
+				.map(|source| {
+					let mut module = modules[source].clone();
+					bundle::link_module(
+						&mut module,
+						*source,
+						idx,
+						&chunks,
+						&graph,
+						&common_js,
+						diagnostics,
+					);
+					if wrapped.contains(source) {
+						bundle::remove_exports(&mut module, true);
+					} else if chunk.root != Some(*source)
+						&& !imported_across_chunks.contains(source)
+					{
+						bundle::remove_exports(&mut module, false);
+					}
+					let content = parser::ASTNode::to_string(&module, &to_string_options);
+					if let Some(name) = common_js.get(source) {
+						bundle::wrap_common_js(
+							&content,
+							name,
+							imported_across_chunks.contains(source),
+							chunk.root == Some(*source),
+							config.strip_whitespace,
+						)
+					} else {
+						content
+					}
+				})
+				.collect::<Vec<_>>()
+				.join(separator);
+
+			Output {
+				output_path: output_directory.join(&chunk.file_name),
+				content: add_banner_and_footer(content, config),
+				// TODO module.to_string_with_map
+				mappings: String::new(),
+			}
+		})
+		.collect::<Vec<_>>();
+
+	outputs.push(Output {
+		output_path: output_directory.join("manifest.json"),
+		content: bundle::manifest(&chunks, files, base),
+		mappings: String::new(),
+	});
+
+	outputs
+}
+
+fn get_entry_sources(
+	entries: &[PathBuf],
+	files: &MapFileStore<WithPathMap>,
+	fs: &impl crate::ReadFromFS,
+) -> Vec<SourceId> {
+	// Entry paths are registered by the checker in their canonical form
+	entries
+		.iter()
+		.filter_map(|path| {
+			let normalised = checker::features::modules::normalise_path(path);
+			let canonical = checker::features::modules::canonicalise_path(&normalised, false, fs);
+			files.get_source_at_path(&canonical).or_else(|| files.get_source_at_path(&normalised))
+		})
+		.collect()
+}
+
+/// A `.d.ts` file for each entry point. The declarations are printed from the types of the exports
+/// (see [`checker::types::TypeStore::exports_to_ts_declarations`]) and then parsed, so that they
+/// are output in the same format as the parser prints type definitions.
+///
+/// With `bundle` these are named after the entry (like the entry chunks), otherwise after the
+/// output file
+fn declaration_outputs(
+	entries: &[PathBuf],
+	result: &CheckOutput<EznoParser>,
+	output_path: &Path,
+	bundle: bool,
+	fs: &impl crate::ReadFromFS,
+) -> Vec<Output> {
+	let files = &result.module_contents;
+	let sources = get_entry_sources(entries, files, fs);
+	let parse_options = ParseOptions { type_definition_module: true, ..Default::default() };
+
+	sources
+		.iter()
+		.filter_map(|source| {
+			let exports = result.get_exports(*source)?;
+			let info = [&result.modules[source].info, &result.top_level_information];
+			let declarations = result.types.exports_to_ts_declarations(&exports, &info);
+			let module = Module::from_string(declarations, parse_options)
+				.expect("printed declarations do not parse");
+
+			let path = files.get_file_path(*source);
+			let stem = path.file_stem()?.to_string_lossy();
+			let output_path = if bundle {
+				output_path.join(format!("{stem}.d.ts"))
+			} else if sources.len() == 1 {
+				output_path.with_extension("d.ts")
+			} else {
+				output_path.with_file_name(format!("{stem}.d.ts"))
+			};
+			Some(Output {
+				output_path,
+				content: module.to_string(&ToStringOptions::typescript()),
+				mappings: String::new(),
+			})
+		})
+		.collect()
+}
+
+fn get_to_string_options(config: &BuildConfig) -> ToStringOptions {
+	if config.strip_whitespace {
+		// Keep `@license` etc comments which are required when distributing
+		ToStringOptions { comments: parser::Comments::Legal, ..ToStringOptions::minified() }
+	} else {
+		ToStringOptions::default()
+	}
+}
+
+/// Lines are offset by the banner (added after printing)
+fn source_map_json(
+	source_map: Option<parser::source_map::SourceMap>,
+	config: &BuildConfig,
+	files: &MapFileStore<WithPathMap>,
+) -> String {
+	let Some(mut source_map) = source_map else { return String::new() };
+	if let Some(ref banner) = config.banner {
+		let lines = banner.matches('\n').count() + 1;
+		source_map.mappings.insert_str(0, &";".repeat(lines));
+	}
+	source_map.to_json(files)
+}
+
+fn add_banner_and_footer(content: String, config: &BuildConfig) -> String {
+	if config.banner.is_none() && config.footer.is_none() {
+		return content;
+	}
+	let mut output = String::new();
+	if let Some(ref banner) = config.banner {
+		output.push_str(banner);
+		output.push('\n');
+	}
+	output.push_str(&content);
+	if let Some(ref footer) = config.footer {
+		if !output.ends_with('\n') {
+			output.push('\n');
+		}
+		output.push_str(footer);
+	}
+	output
+}
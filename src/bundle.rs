@@ -0,0 +1,1210 @@
+//! Bundling for `build --bundle`. Modules are grouped into chunks, one for each entry point and one for
+//! each dynamically imported module. With `split`, modules that would be in several chunks are instead
+//! moved into shared chunks, which are named by a hash of their contents (so names are stable between
+//! builds when nothing changes).
+//!
+//! Modules in a chunk are concatenated. Imports between modules in the same chunk are removed, so renamed,
+//! default and namespace imports between them are not yet supported (these are reported as warnings).
+//! Exports of modules that are not the root of their chunk (and are not imported from other chunks) are
+//! removed. When top level names of modules in a chunk collide, the modules other than the root are
+//! wrapped like CommonJS modules (see [`colliding_modules`])
+//!
+//! CommonJS modules are wrapped in a function that is passed their own `module` object (see
+//! [`wrap_common_js`]) and imports of them become reads of `module.exports`. `require` calls are left as is
+//!
+//! Modules with side effects (see [`checker::features::modules::SynthesisedModule::has_top_level_side_effects`]
+//! and [`PackageSideEffects`]) are output in the order they are imported. With tree shaking, bare imports
+//! of modules without side effects are removed
+
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	path::{Component, Path, PathBuf},
+};
+
+use checker::{
+	features::{
+		modules::canonicalise_path,
+		resolution::{candidate_paths, resolve_path_alias},
+	},
+	Diagnostic, DiagnosticKind, DiagnosticsContainer,
+};
+use parser::{
+	declarations::{
+		export::{ExportDeclaration, ExportPart, Exportable},
+		import::{ImportExportName, ImportPart, ImportedItems},
+		ImportDeclaration, ImportLocation, VariableDeclaration,
+	},
+	source_map::{FileSystem, MapFileStore, WithPathMap},
+	visiting::{Chain, VisitOptions, VisitorMutReceiver, VisitorReceiver},
+	ASTNode, ArrayDestructuringField, Declaration, Decorated, Expression, Module,
+	ObjectDestructuringField, SourceId, Statement, StatementOrDeclaration, VariableField,
+	VariableIdentifier,
+};
+
+pub struct ModuleImport {
+	pub specifier: String,
+	pub module: SourceId,
+	pub dynamic: bool,
+	/// `import "./a"`. Only imported for its side effects
+	pub bare: bool,
+}
+
+/// Imports (that resolve to checked modules) for each module
+#[derive(Default)]
+pub struct ModuleGraph {
+	pub imports: HashMap<SourceId, Vec<ModuleImport>>,
+	/// Modules that use top level `await` or (statically) import a module that does
+	pub async_modules: HashSet<SourceId>,
+	/// Modules whose body has side effects or that (statically) import a module that does
+	pub side_effect_modules: HashSet<SourceId>,
+	/// Specifiers of bare imports removed by [`ModuleGraph::remove_side_effect_free_imports`]
+	pub removed_imports: HashMap<SourceId, HashSet<String>>,
+}
+
+impl ModuleGraph {
+	/// Modules marked with `side_effect_free` (from `"sideEffects": false`) are treated as without side
+	/// effects, even if they import modules with side effects. Imports are resolved with `paths` (see
+	/// [`checker::TypeCheckOptions::paths`])
+	#[must_use]
+	pub fn new(
+		modules: &HashMap<SourceId, Module>,
+		files: &MapFileStore<WithPathMap>,
+		fs: &impl crate::ReadFromFS,
+		paths: &[(String, Vec<String>)],
+		has_top_level_await: impl Fn(SourceId) -> bool,
+		has_side_effects: impl Fn(SourceId) -> bool,
+		side_effect_free: impl Fn(SourceId) -> bool,
+	) -> Self {
+		let mut imports = HashMap::new();
+		for (source, module) in modules {
+			let from = files.get_file_path(*source);
+			let mut module_imports = Vec::new();
+
+			for item in &module.items {
+				if let Some(specifier) = static_import_specifier(item) {
+					if let Some(module) = resolve(&from, specifier, files, fs, paths) {
+						module_imports.push(ModuleImport {
+							specifier: specifier.to_owned(),
+							module,
+							dynamic: false,
+							bare: is_bare_import(item),
+						});
+					}
+				}
+			}
+
+			let mut dynamic = DynamicImports::default();
+			module.visit(&mut dynamic, &mut (), &VisitOptions::default(), *source);
+			for specifier in dynamic.0 {
+				if let Some(module) = resolve(&from, &specifier, files, fs, paths) {
+					module_imports.push(ModuleImport {
+						specifier,
+						module,
+						dynamic: true,
+						bare: false,
+					});
+				}
+			}
+
+			imports.insert(*source, module_imports);
+		}
+
+		let mut graph = Self { imports, ..Default::default() };
+		graph.async_modules = graph.importers_of(
+			modules.keys().copied().filter(|source| has_top_level_await(*source)).collect(),
+			|_| true,
+		);
+		graph.side_effect_modules = graph.importers_of(
+			modules
+				.keys()
+				.copied()
+				.filter(|source| has_side_effects(*source) && !side_effect_free(*source))
+				.collect(),
+			|source| !side_effect_free(source),
+		);
+		graph
+	}
+
+	/// Removes bare imports of modules without side effects (as they do nothing). Modules that are then
+	/// not imported are not output
+	pub fn remove_side_effect_free_imports(&mut self) {
+		for (source, imports) in &mut self.imports {
+			imports.retain(|import| {
+				let remove = import.bare && !self.side_effect_modules.contains(&import.module);
+				if remove {
+					self.removed_imports
+						.entry(*source)
+						.or_default()
+						.insert(import.specifier.clone());
+				}
+				!remove
+			});
+		}
+	}
+
+	/// `modules` and the modules that (transitively, statically) import them
+	fn importers_of(
+		&self,
+		mut modules: HashSet<SourceId>,
+		include: impl Fn(SourceId) -> bool,
+	) -> HashSet<SourceId> {
+		// Until no more modules are found
+		loop {
+			let importers = self
+				.imports
+				.keys()
+				.copied()
+				.filter(|source| {
+					!modules.contains(source)
+						&& include(*source)
+						&& self.static_imports(*source).any(|import| modules.contains(&import))
+				})
+				.collect::<Vec<_>>();
+			if importers.is_empty() {
+				break;
+			}
+			modules.extend(importers);
+		}
+		modules
+	}
+
+	fn static_imports(&self, module: SourceId) -> impl Iterator<Item = SourceId> + '_ {
+		self.imports.get(&module).into_iter().flatten().filter(|i| !i.dynamic).map(|i| i.module)
+	}
+
+	fn dynamic_imports(&self, module: SourceId) -> impl Iterator<Item = SourceId> + '_ {
+		self.imports.get(&module).into_iter().flatten().filter(|i| i.dynamic).map(|i| i.module)
+	}
+
+	/// `root` and every module it (transitively) statically imports
+	fn static_closure(&self, root: SourceId) -> Vec<SourceId> {
+		let mut found = vec![root];
+		let mut idx = 0;
+		while let Some(module) = found.get(idx).copied() {
+			for import in self.static_imports(module) {
+				if !found.contains(&import) {
+					found.push(import);
+				}
+			}
+			idx += 1;
+		}
+		found
+	}
+}
+
+/// `sideEffects` fields of `package.json` files. A module is side effect free if the nearest
+/// `package.json` has `"sideEffects": false` or has a list of files with side effects which does not
+/// include it
+#[derive(Default)]
+pub struct PackageSideEffects {
+	/// Directory to the `sideEffects` field of its `package.json`. `None` if there is no `package.json`
+	packages: HashMap<PathBuf, Option<SideEffectsField>>,
+}
+
+enum SideEffectsField {
+	All(bool),
+	/// Globs (relative to the package)
+	Files(Vec<String>),
+	Unset,
+}
+
+impl PackageSideEffects {
+	/// From the nearest `package.json` above `path`
+	pub fn is_side_effect_free(
+		&mut self,
+		path: &Path,
+		fs_resolver: &impl crate::ReadFromFS,
+	) -> bool {
+		for directory in path.ancestors().skip(1) {
+			let field = self.packages.entry(directory.to_path_buf()).or_insert_with(|| {
+				let content = fs_resolver.read_file(&directory.join("package.json"))?;
+				let package: serde_json::Value = serde_json::from_slice(&content).ok()?;
+				Some(match package.get("sideEffects") {
+					Some(serde_json::Value::Bool(value)) => SideEffectsField::All(*value),
+					Some(serde_json::Value::Array(files)) => SideEffectsField::Files(
+						files
+							.iter()
+							.filter_map(|file| file.as_str().map(ToOwned::to_owned))
+							.collect(),
+					),
+					_ => SideEffectsField::Unset,
+				})
+			});
+			match field {
+				Some(SideEffectsField::All(value)) => return !*value,
+				Some(SideEffectsField::Files(files)) => {
+					let relative = path.strip_prefix(directory).unwrap_or(path);
+					let relative = relative.to_string_lossy().replace('\\', "/");
+					return !files.iter().any(|file| {
+						let file = file.strip_prefix("./").unwrap_or(file);
+						// Like bundlers, patterns without a `/` match files in any directory
+						let pattern =
+							if file.contains('/') { file.to_owned() } else { format!("**/{file}") };
+						checker::features::modules::glob_matches(
+							pattern.as_bytes(),
+							relative.as_bytes(),
+						)
+					});
+				}
+				Some(SideEffectsField::Unset) => return false,
+				None => {}
+			}
+		}
+		false
+	}
+}
+
+pub struct Chunk {
+	pub file_name: String,
+	/// The entry point or dynamically imported module. `None` for shared chunks
+	pub root: Option<SourceId>,
+	pub is_entry: bool,
+	/// In the order they are output (imported modules before the modules that import them)
+	pub modules: Vec<SourceId>,
+	/// Chunks this chunk statically imports (indexes into the chunk list)
+	pub imports: Vec<usize>,
+	/// Chunks this chunk dynamically imports
+	pub dynamic_imports: Vec<usize>,
+}
+
+/// Entry point chunks are first (in the order of `entries`), then dynamically imported chunks and then
+/// shared chunks
+#[must_use]
+pub fn create_chunks(
+	entries: &[SourceId],
+	graph: &ModuleGraph,
+	files: &MapFileStore<WithPathMap>,
+	base: Option<&Path>,
+	split: bool,
+) -> Vec<Chunk> {
+	// Entries and then dynamically imported modules
+	let mut roots: Vec<SourceId> = Vec::new();
+	for entry in entries {
+		if !roots.contains(entry) {
+			roots.push(*entry);
+		}
+	}
+	let mut closures = Vec::new();
+	let mut idx = 0;
+	while let Some(root) = roots.get(idx).copied() {
+		let closure = graph.static_closure(root);
+		for module in &closure {
+			for import in graph.dynamic_imports(*module) {
+				if !roots.contains(&import) {
+					roots.push(import);
+				}
+			}
+		}
+		closures.push(closure);
+		idx += 1;
+	}
+
+	let order = dependency_order(&roots, graph);
+
+	let mut groups: Vec<(Vec<usize>, Vec<SourceId>)> =
+		closures.iter().enumerate().map(|(idx, closure)| (vec![idx], closure.clone())).collect();
+
+	if split {
+		// Group modules by the roots that (statically) reach them
+		let mut reached_by: HashMap<SourceId, Vec<usize>> = HashMap::new();
+		for (root_idx, closure) in closures.iter().enumerate() {
+			for module in closure {
+				reached_by.entry(*module).or_default().push(root_idx);
+			}
+		}
+		let mut shared: BTreeMap<Vec<usize>, Vec<SourceId>> = BTreeMap::new();
+		for (_, modules) in &mut groups {
+			modules.clear();
+		}
+		for module in &order {
+			let Some(reached_by) = reached_by.remove(module) else { continue };
+			// Entry points and dynamically imported modules stay in their own chunk
+			if let Some(root) = roots.iter().position(|root| root == module) {
+				groups[root].1.push(*module);
+			} else if let [root] = reached_by.as_slice() {
+				groups[*root].1.push(*module);
+			} else {
+				shared.entry(reached_by).or_default().push(*module);
+			}
+		}
+		groups.extend(shared);
+	}
+
+	let mut chunks: Vec<Chunk> = groups
+		.into_iter()
+		.map(|(chunk_roots, mut modules)| {
+			modules.sort_by_key(|module| order.iter().position(|m| m == module));
+
+			let hash = content_hash(&modules, files, base);
+			let root = if let [root] = chunk_roots.as_slice() { Some(roots[*root]) } else { None };
+			let is_entry = root.is_some_and(|root| entries.contains(&root));
+			let file_name = if let Some(root) = root {
+				let stem = files
+					.get_file_path(root)
+					.file_stem()
+					.map_or_else(|| "chunk".to_owned(), |stem| stem.to_string_lossy().into_owned());
+				if is_entry {
+					format!("{stem}.js")
+				} else {
+					format!("{stem}-{hash}.js")
+				}
+			} else {
+				format!("chunk-{hash}.js")
+			};
+			Chunk {
+				file_name,
+				root,
+				is_entry,
+				modules,
+				imports: Vec::new(),
+				dynamic_imports: Vec::new(),
+			}
+		})
+		.collect();
+
+	// Link chunks
+	for idx in 0..chunks.len() {
+		let mut imports = Vec::new();
+		let mut dynamic_imports = Vec::new();
+		for module in &chunks[idx].modules {
+			for import in graph.imports.get(module).into_iter().flatten() {
+				let Some(target) = chunk_of(&chunks, idx, import) else { continue };
+				let list = if import.dynamic { &mut dynamic_imports } else { &mut imports };
+				if target != idx && !list.contains(&target) {
+					list.push(target);
+				}
+			}
+		}
+		chunks[idx].imports = imports;
+		chunks[idx].dynamic_imports = dynamic_imports;
+	}
+
+	chunks
+}
+
+/// Dynamically imported modules are always loaded from the chunk they are the root of
+fn chunk_of(chunks: &[Chunk], current: usize, import: &ModuleImport) -> Option<usize> {
+	if import.dynamic {
+		chunks.iter().position(|chunk| chunk.root == Some(import.module))
+	} else if chunks[current].modules.contains(&import.module) {
+		Some(current)
+	} else {
+		chunks.iter().position(|chunk| chunk.modules.contains(&import.module))
+	}
+}
+
+/// Post order, so imported modules come first. As modules are concatenated, imports that
+/// (transitively) use top level `await` but do not have side effects are put last so that other
+/// imports do not wait for them (the same as when they are evaluated as separate modules). Imports with
+/// side effects keep the order they are imported in (for example polyfills)
+fn dependency_order(roots: &[SourceId], graph: &ModuleGraph) -> Vec<SourceId> {
+	fn visit(
+		module: SourceId,
+		graph: &ModuleGraph,
+		seen: &mut HashSet<SourceId>,
+		order: &mut Vec<SourceId>,
+	) {
+		if seen.insert(module) {
+			let (deferred, imports): (Vec<_>, Vec<_>) =
+				graph.static_imports(module).partition(|import| {
+					graph.async_modules.contains(import)
+						&& !graph.side_effect_modules.contains(import)
+				});
+			for import in imports.into_iter().chain(deferred) {
+				visit(import, graph, seen, order);
+			}
+			order.push(module);
+		}
+	}
+
+	let mut seen = HashSet::new();
+	let mut order = Vec::new();
+	for root in roots {
+		visit(*root, graph, &mut seen, &mut order);
+	}
+	order
+}
+
+/// FNV-1a of the (relative) paths and contents of the modules. Eight hex characters
+fn content_hash(
+	modules: &[SourceId],
+	files: &MapFileStore<WithPathMap>,
+	base: Option<&Path>,
+) -> String {
+	const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+	const PRIME: u64 = 0x0100_0000_01b3;
+
+	let mut hash = OFFSET;
+	let mut write = |bytes: &[u8]| {
+		for byte in bytes {
+			hash ^= u64::from(*byte);
+			hash = hash.wrapping_mul(PRIME);
+		}
+	};
+	for module in modules {
+		let path = files.get_file_path(*module);
+		let path = base.and_then(|base| path.strip_prefix(base).ok()).unwrap_or(&path);
+		write(path.to_string_lossy().replace('\\', "/").as_bytes());
+		write(files.get_file_content(*module).as_bytes());
+	}
+	format!("{:08x}", hash >> 32)
+}
+
+/// Names for the `module` objects of CommonJS modules (and ES modules that are wrapped, see
+/// [`colliding_modules`]), in the order they are output
+#[must_use]
+pub fn common_js_names(
+	chunks: &[Chunk],
+	is_wrapped: impl Fn(SourceId) -> bool,
+) -> HashMap<SourceId, String> {
+	chunks
+		.iter()
+		.flat_map(|chunk| chunk.modules.iter().copied())
+		.filter(|module| is_wrapped(*module))
+		.enumerate()
+		.map(|(idx, module)| (module, format!("__cjs_module_{idx}")))
+		.collect()
+}
+
+/// Modules that are imported from other chunks (so their exports, or `module` object if they are
+/// wrapped, have to be exported)
+#[must_use]
+pub fn imported_across_chunks(chunks: &[Chunk], graph: &ModuleGraph) -> HashSet<SourceId> {
+	let mut imported = HashSet::new();
+	for (idx, chunk) in chunks.iter().enumerate() {
+		for module in &chunk.modules {
+			for import in graph.imports.get(module).into_iter().flatten() {
+				if !import.dynamic && chunk_of(chunks, idx, import) != Some(idx) {
+					imported.insert(import.module);
+				}
+			}
+		}
+	}
+	imported
+}
+
+/// ES modules that are wrapped like CommonJS modules (see [`wrap_common_js`]) as top level names of
+/// modules in their chunk collide. In a chunk with a collision every module other than the root is
+/// wrapped, so only the names of the root are left in the scope of the chunk. Modules that use top
+/// level `await` cannot be wrapped (these are reported)
+pub fn colliding_modules(
+	chunks: &[Chunk],
+	graph: &ModuleGraph,
+	modules: &HashMap<SourceId, Module>,
+	is_common_js: impl Fn(SourceId) -> bool,
+	has_top_level_await: impl Fn(SourceId) -> bool,
+	diagnostics: &mut DiagnosticsContainer,
+) -> HashSet<SourceId> {
+	let mut wrapped = HashSet::new();
+	for (idx, chunk) in chunks.iter().enumerate() {
+		let mut declared_by: HashMap<String, usize> = HashMap::new();
+		for module in chunk.modules.iter().filter(|module| !is_common_js(**module)) {
+			let mut names =
+				top_level_names(&modules[module], *module, idx, chunks, graph, &is_common_js);
+			names.sort_unstable();
+			names.dedup();
+			for name in names {
+				*declared_by.entry(name).or_default() += 1;
+			}
+		}
+		if declared_by.values().all(|count| *count == 1) {
+			continue;
+		}
+
+		for module in &chunk.modules {
+			if is_common_js(*module) || chunk.root == Some(*module) {
+				continue;
+			}
+			if has_top_level_await(*module) {
+				add_warning(
+					diagnostics,
+					"Modules with top level await cannot be wrapped, so their names may collide with other modules in the chunk",
+					modules[module].span.with_source(*module),
+				);
+			} else {
+				wrapped.insert(*module);
+			}
+		}
+	}
+	wrapped
+}
+
+/// Names declared at the top level of `module` that are left after it is linked. Imports of modules
+/// in the same chunk are removed (the name is from the module it is imported from), while imports of
+/// CommonJS modules become `const` declarations
+fn top_level_names(
+	module: &Module,
+	source: SourceId,
+	chunk_idx: usize,
+	chunks: &[Chunk],
+	graph: &ModuleGraph,
+	is_common_js: impl Fn(SourceId) -> bool,
+) -> Vec<String> {
+	let mut names = Vec::new();
+	for item in &module.items {
+		let Some(specifier) = static_import_specifier(item) else {
+			declared_names(item, &mut names);
+			continue;
+		};
+		let import = graph
+			.imports
+			.get(&source)
+			.into_iter()
+			.flatten()
+			.find(|import| !import.dynamic && import.specifier == specifier);
+		let is_common_js = import.is_some_and(|import| is_common_js(import.module));
+		let in_chunk =
+			import.is_some_and(|import| chunk_of(chunks, chunk_idx, import) == Some(chunk_idx));
+		match item {
+			StatementOrDeclaration::Declaration(Declaration::Import(import))
+				if is_common_js || !in_chunk =>
+			{
+				import_names(import, &mut names);
+			}
+			StatementOrDeclaration::Declaration(Declaration::Export(Decorated {
+				on: ExportDeclaration::Variable { exported, .. },
+				..
+			})) if is_common_js && in_chunk => {
+				re_exported_names(exported, &mut names);
+			}
+			_ => {}
+		}
+	}
+	names
+}
+
+fn declared_names(item: &StatementOrDeclaration, names: &mut Vec<String>) {
+	let StatementOrDeclaration::Declaration(declaration) = item else {
+		if let StatementOrDeclaration::Statement(Statement::VarVariable(statement)) = item {
+			for declaration in &statement.declarations {
+				variable_field_names(declaration.name.get_ast_ref(), names);
+			}
+		}
+		return;
+	};
+	let name = match declaration {
+		Declaration::Variable(declaration) => {
+			variable_declaration_names(declaration, names);
+			return;
+		}
+		Declaration::Function(function) => function.on.name.identifier.as_option_str(),
+		Declaration::Class(class) => class.on.name.identifier.as_option_str(),
+		Declaration::Export(export) => match &export.on {
+			ExportDeclaration::Variable { exported: Exportable::Variable(declaration), .. } => {
+				variable_declaration_names(declaration, names);
+				return;
+			}
+			ExportDeclaration::Variable { exported: Exportable::Function(function), .. } => {
+				function.name.identifier.as_option_str()
+			}
+			ExportDeclaration::Variable { exported: Exportable::Class(class), .. } => {
+				class.name.identifier.as_option_str()
+			}
+			_ => None,
+		},
+		_ => None,
+	};
+	names.extend(name.map(ToOwned::to_owned));
+}
+
+fn variable_declaration_names(declaration: &VariableDeclaration, names: &mut Vec<String>) {
+	match declaration {
+		VariableDeclaration::ConstDeclaration { declarations, .. } => {
+			for declaration in declarations {
+				variable_field_names(declaration.name.get_ast_ref(), names);
+			}
+		}
+		VariableDeclaration::LetDeclaration { declarations, .. } => {
+			for declaration in declarations {
+				variable_field_names(declaration.name.get_ast_ref(), names);
+			}
+		}
+	}
+}
+
+fn variable_field_names(field: &VariableField, names: &mut Vec<String>) {
+	match field {
+		VariableField::Name(name) => names.extend(name.as_option_str().map(ToOwned::to_owned)),
+		VariableField::Array(members, _) => {
+			for member in members {
+				if let ArrayDestructuringField::Spread(field, _)
+				| ArrayDestructuringField::Name(field, _) = member.get_ast_ref()
+				{
+					variable_field_names(field, names);
+				}
+			}
+		}
+		VariableField::Object(members, _) => {
+			for member in members {
+				match member.get_ast_ref() {
+					ObjectDestructuringField::Name(name, ..)
+					| ObjectDestructuringField::Spread(name, _) => {
+						names.extend(name.as_option_str().map(ToOwned::to_owned));
+					}
+					ObjectDestructuringField::Map { name, .. } => {
+						variable_field_names(name.get_ast_ref(), names);
+					}
+				}
+			}
+		}
+	}
+}
+
+fn import_names(import: &ImportDeclaration, names: &mut Vec<String>) {
+	fn part_name(part: &ImportPart) -> Option<&str> {
+		match part {
+			ImportPart::Name(name) => name.as_option_str(),
+			ImportPart::NameWithAlias { name, .. } => Some(name),
+			ImportPart::PrefixComment(_, None, _) => None,
+			ImportPart::PrefixComment(_, Some(part), _)
+			| ImportPart::PostfixComment(part, _, _) => part_name(part),
+		}
+	}
+
+	names.extend(
+		import.default.as_ref().and_then(VariableIdentifier::as_option_str).map(ToOwned::to_owned),
+	);
+	match &import.items {
+		ImportedItems::All { under } => {
+			names.extend(under.as_option_str().map(ToOwned::to_owned));
+		}
+		ImportedItems::Parts(parts) => {
+			names.extend(parts.iter().flatten().filter_map(part_name).map(ToOwned::to_owned));
+		}
+	}
+}
+
+fn re_exported_names(exported: &Exportable, names: &mut Vec<String>) {
+	match exported {
+		Exportable::ImportAll { r#as: Some(name), .. } => {
+			names.extend(name.as_option_str().map(ToOwned::to_owned));
+		}
+		Exportable::ImportParts { parts, .. } => {
+			names.extend(
+				parts.iter().filter_map(export_part_names).map(|(_, exported)| exported.to_owned()),
+			);
+		}
+		_ => {}
+	}
+}
+
+/// (local name, exported name). `None` for names that are quoted
+fn export_part_names(part: &ExportPart) -> Option<(&str, &str)> {
+	match part {
+		ExportPart::Name(name) => name.as_option_str().map(|name| (name, name)),
+		ExportPart::NameWithAlias { name, alias: ImportExportName::Reference(alias), .. } => {
+			Some((name, alias))
+		}
+		ExportPart::NameWithAlias { .. } | ExportPart::PrefixComment(_, None, _) => None,
+		ExportPart::PrefixComment(_, Some(part), _) | ExportPart::PostfixComment(part, _, _) => {
+			export_part_names(part)
+		}
+	}
+}
+
+/// Removes the `export`s of a module that is not the root of its chunk. For modules that are wrapped,
+/// `assign` instead assigns the exported values to `exports` (marked with `__esModule`, so default
+/// imports are linked to `exports.default`)
+pub fn remove_exports(module: &mut Module, assign: bool) {
+	let mut assignments = String::new();
+	if assign {
+		assignments.push_str("exports.__esModule = true;\n");
+	}
+
+	let items = std::mem::take(&mut module.items);
+	for item in items {
+		let StatementOrDeclaration::Declaration(Declaration::Export(export)) = item else {
+			module.items.push(item);
+			continue;
+		};
+		let Decorated { decorators, on, position } = export;
+		let mut names = Vec::new();
+		let declaration = match on {
+			ExportDeclaration::Variable { exported: Exportable::Variable(declaration), .. } => {
+				variable_declaration_names(&declaration, &mut names);
+				Some(Declaration::Variable(declaration))
+			}
+			ExportDeclaration::Variable { exported: Exportable::Function(function), .. } => {
+				names.extend(function.name.identifier.as_option_str().map(ToOwned::to_owned));
+				Some(Declaration::Function(Decorated::new(decorators, function)))
+			}
+			ExportDeclaration::Variable { exported: Exportable::Class(class), .. } => {
+				names.extend(class.name.identifier.as_option_str().map(ToOwned::to_owned));
+				Some(Declaration::Class(Decorated::new(decorators, class)))
+			}
+			ExportDeclaration::Variable { exported: Exportable::Parts(parts), .. } => {
+				for (local, exported) in parts.iter().filter_map(export_part_names) {
+					assignments.push_str(&format!("exports.{exported} = {local};\n"));
+				}
+				None
+			}
+			ExportDeclaration::Default { expression, .. } if assign => {
+				let assignment = format!(
+					"exports.default = {};",
+					expression.to_string(&parser::ToStringOptions::default())
+				);
+				if let Ok(assignment) =
+					Module::from_string(assignment, parser::ParseOptions::default())
+				{
+					module.items.extend(assignment.items);
+				}
+				None
+			}
+			on => {
+				module.items.push(StatementOrDeclaration::Declaration(Declaration::Export(
+					Decorated { decorators, on, position },
+				)));
+				continue;
+			}
+		};
+		if let Some(declaration) = declaration {
+			module.items.push(StatementOrDeclaration::Declaration(declaration));
+		}
+		for name in names {
+			assignments.push_str(&format!("exports.{name} = {name};\n"));
+		}
+	}
+
+	if assign {
+		if let Ok(assignments) = Module::from_string(assignments, parser::ParseOptions::default()) {
+			module.items.extend(assignments.items);
+		}
+	}
+}
+
+/// Removes imports of modules in the same chunk and points imports of modules in other chunks to that chunk
+pub fn link_module(
+	module: &mut Module,
+	source: SourceId,
+	chunk_idx: usize,
+	chunks: &[Chunk],
+	graph: &ModuleGraph,
+	common_js: &HashMap<SourceId, String>,
+	diagnostics: &mut DiagnosticsContainer,
+) {
+	if !common_js.is_empty() {
+		link_common_js_imports(module, source, chunk_idx, chunks, graph, common_js);
+	}
+
+	// specifier -> (is in this chunk, chunk file name)
+	let mut locations = HashMap::new();
+	for import in graph.imports.get(&source).into_iter().flatten() {
+		if let Some(target) = chunk_of(chunks, chunk_idx, import) {
+			let same_chunk = target == chunk_idx && !import.dynamic;
+			locations.insert(
+				import.specifier.clone(),
+				(same_chunk, format!("./{}", chunks[target].file_name)),
+			);
+		}
+	}
+
+	let is_chunk_root = chunks[chunk_idx].root == Some(source);
+	let removed_imports = graph.removed_imports.get(&source);
+
+	module.items.retain_mut(|item| {
+		if let Some(removed_imports) = removed_imports {
+			if is_bare_import(item)
+				&& static_import_specifier(item).is_some_and(|s| removed_imports.contains(s))
+			{
+				return false;
+			}
+		}
+		let StatementOrDeclaration::Declaration(declaration) = item else { return true };
+		match declaration {
+			Declaration::Import(import) => {
+				let ImportLocation::Quoted(ref mut specifier, _) = import.from else { return true };
+				let Some((same_chunk, location)) = locations.get(specifier.as_str()) else {
+					return true;
+				};
+				if !same_chunk {
+					location.clone_into(specifier);
+					return true;
+				}
+				let supported = import.default.is_none()
+					&& match &import.items {
+						ImportedItems::Parts(parts) => {
+							parts.iter().flatten().all(import_part_keeps_name)
+						}
+						ImportedItems::All { .. } => false,
+					};
+				if !supported {
+					add_warning(
+						diagnostics,
+						"Renamed, default and namespace imports are not supported between modules in the same chunk",
+						import.position.with_source(source),
+					);
+				}
+				false
+			}
+			Declaration::Export(export) => match &mut export.on {
+				ExportDeclaration::Variable { exported, position } => {
+					let (Exportable::ImportAll {
+						from: ImportLocation::Quoted(specifier, _), ..
+					}
+					| Exportable::ImportParts {
+						from: ImportLocation::Quoted(specifier, _),
+						..
+					}) = exported
+					else {
+						return true;
+					};
+					let Some((same_chunk, location)) = locations.get(specifier.as_str()) else {
+						return true;
+					};
+					if !same_chunk {
+						location.clone_into(specifier);
+						return true;
+					}
+					// Re-exports become exports of the (now in scope) binding
+					if let Exportable::ImportParts { parts, .. } = exported {
+						*exported = Exportable::Parts(std::mem::take(parts));
+						true
+					} else {
+						add_warning(
+							diagnostics,
+							"Namespace re-exports are not supported between modules in the same chunk",
+							position.with_source(source),
+						);
+						false
+					}
+				}
+				ExportDeclaration::Default { position, .. }
+					if !is_chunk_root && !common_js.contains_key(&source) =>
+				{
+					add_warning(
+						diagnostics,
+						"Default exports are only supported for the root module of a chunk",
+						position.with_source(source),
+					);
+					true
+				}
+				_ => true,
+			},
+			_ => true,
+		}
+	});
+
+	let mut dynamic_locations: HashMap<String, String> =
+		locations.into_iter().map(|(specifier, (_, location))| (specifier, location)).collect();
+	module.visit_mut(
+		&mut DynamicImportsMut,
+		&mut dynamic_locations,
+		&VisitOptions::default(),
+		source,
+	);
+}
+
+/// Replaces imports of CommonJS modules with reads from their `module.exports`
+fn link_common_js_imports(
+	module: &mut Module,
+	source: SourceId,
+	chunk_idx: usize,
+	chunks: &[Chunk],
+	graph: &ModuleGraph,
+	common_js: &HashMap<SourceId, String>,
+) {
+	// specifier -> (name of `module` object, chunk file name if in a different chunk)
+	let mut locations = HashMap::new();
+	for import in graph.imports.get(&source).into_iter().flatten().filter(|import| !import.dynamic)
+	{
+		let (Some(name), Some(target)) =
+			(common_js.get(&import.module), chunk_of(chunks, chunk_idx, import))
+		else {
+			continue;
+		};
+		let location = (target != chunk_idx).then(|| format!("./{}", chunks[target].file_name));
+		locations.insert(import.specifier.as_str(), (name.as_str(), location));
+	}
+	if locations.is_empty() {
+		return;
+	}
+
+	let items = std::mem::take(&mut module.items);
+	for item in items {
+		let bindings = match (static_import_specifier(&item), &item) {
+			(Some(specifier), StatementOrDeclaration::Declaration(Declaration::Import(import))) => {
+				locations.get(specifier).map(|(name, location)| {
+					(*name, location.as_deref(), common_js_import_bindings(import, name))
+				})
+			}
+			(
+				Some(specifier),
+				StatementOrDeclaration::Declaration(Declaration::Export(Decorated {
+					on: ExportDeclaration::Variable { exported, .. },
+					..
+				})),
+			) => locations.get(specifier).and_then(|(name, location)| {
+				common_js_re_export_bindings(exported, name)
+					.map(|bindings| (*name, location.as_deref(), bindings))
+			}),
+			_ => None,
+		};
+		if let Some((name, location, bindings)) = bindings {
+			let mut replacement = String::new();
+			if let Some(location) = location {
+				replacement.push_str(&format!("import {{ {name} }} from {location:?};\n"));
+			}
+			replacement.push_str(&bindings);
+			if let Ok(replacement) =
+				Module::from_string(replacement, parser::ParseOptions::default())
+			{
+				module.items.extend(replacement.items);
+				continue;
+			}
+		}
+		module.items.push(item);
+	}
+}
+
+/// `const` declarations for the bindings of an import. The default import follows `__esModule`
+/// (like the checker does)
+fn common_js_import_bindings(import: &ImportDeclaration, name: &str) -> String {
+	let exports = format!("{name}.exports");
+	let mut bindings = String::new();
+	if let Some(default) = import.default.as_ref().and_then(VariableIdentifier::as_option_str) {
+		bindings.push_str(&format!(
+			"const {default} = {exports}.__esModule ? {exports}.default : {exports};\n"
+		));
+	}
+	match &import.items {
+		ImportedItems::All { under } => {
+			if let Some(under) = under.as_option_str() {
+				bindings.push_str(&format!("const {under} = {exports};\n"));
+			}
+		}
+		ImportedItems::Parts(parts) => {
+			let fields = parts.iter().flatten().filter_map(destructuring_field).collect::<Vec<_>>();
+			if !fields.is_empty() {
+				bindings.push_str(&format!("const {{ {} }} = {exports};\n", fields.join(", ")));
+			}
+		}
+	}
+	bindings
+}
+
+/// `export const` declarations for re-exports. `None` for `export * from` and re-exports under quoted
+/// names (which cannot be declared)
+fn common_js_re_export_bindings(exported: &Exportable, name: &str) -> Option<String> {
+	let exports = format!("{name}.exports");
+	match exported {
+		Exportable::ImportAll { r#as: Some(under), .. } => {
+			under.as_option_str().map(|under| format!("export const {under} = {exports};\n"))
+		}
+		Exportable::ImportParts { parts, .. } => {
+			let mut bindings = String::new();
+			for part in parts {
+				let (imported, exported) = export_part_names(part)?;
+				let value = if imported == "default" {
+					format!("{exports}.__esModule ? {exports}.default : {exports}")
+				} else {
+					format!("{exports}.{imported}")
+				};
+				bindings.push_str(&format!("export const {exported} = {value};\n"));
+			}
+			Some(bindings)
+		}
+		_ => None,
+	}
+}
+
+fn destructuring_field(part: &ImportPart) -> Option<String> {
+	match part {
+		ImportPart::Name(name) => name.as_option_str().map(ToOwned::to_owned),
+		ImportPart::NameWithAlias {
+			name, alias: ImportExportName::Reference(imported), ..
+		} => Some(format!("{imported}: {name}")),
+		ImportPart::NameWithAlias {
+			name, alias: ImportExportName::Quoted(imported, _), ..
+		} => Some(format!("{imported:?}: {name}")),
+		ImportPart::NameWithAlias { alias: ImportExportName::Marker(_), .. }
+		| ImportPart::PrefixComment(_, None, _) => None,
+		ImportPart::PrefixComment(_, Some(part), _) | ImportPart::PostfixComment(part, _, _) => {
+			destructuring_field(part)
+		}
+	}
+}
+
+/// Gives the module its own `module` and `exports` (under `name`). Exported if `export` (used from
+/// other chunks). The root module of a chunk also default exports `module.exports`
+#[must_use]
+pub fn wrap_common_js(
+	content: &str,
+	name: &str,
+	export: bool,
+	is_root: bool,
+	minify: bool,
+) -> String {
+	let export = if export { "export " } else { "" };
+	let mut buf = if minify {
+		format!("{export}var {name}={{exports:{{}}}};(function(module,exports){{{content}\n}})({name},{name}.exports);")
+	} else {
+		format!("{export}var {name} = {{ exports: {{}} }};\n(function (module, exports) {{\n{content}\n}})({name}, {name}.exports);")
+	};
+	if is_root {
+		let exports = format!("{name}.exports");
+		if !minify {
+			buf.push('\n');
+		}
+		buf.push_str(&format!(
+			"export default {exports}.__esModule ? {exports}.default : {exports};"
+		));
+	}
+	buf
+}
+
+/// Files for the chunks and how they link together
+#[must_use]
+pub fn manifest(
+	chunks: &[Chunk],
+	files: &MapFileStore<WithPathMap>,
+	base: Option<&Path>,
+) -> String {
+	let list = |indexes: &[usize]| {
+		indexes
+			.iter()
+			.map(|idx| format!("{:?}", chunks[*idx].file_name))
+			.collect::<Vec<_>>()
+			.join(", ")
+	};
+	let path = |source: SourceId| {
+		let path = files.get_file_path(source);
+		let path = base.and_then(|base| path.strip_prefix(base).ok()).unwrap_or(&path);
+		format!("{:?}", path.to_string_lossy().replace('\\', "/"))
+	};
+
+	let mut buf = String::from("{\n\t\"chunks\": [");
+	for (idx, chunk) in chunks.iter().enumerate() {
+		if idx > 0 {
+			buf.push(',');
+		}
+		let entry = chunk.root.filter(|_| chunk.is_entry).map_or_else(|| "null".to_owned(), path);
+		let modules = chunk.modules.iter().copied().map(path).collect::<Vec<_>>().join(", ");
+		buf.push_str(&format!(
+			"\n\t\t{{\n\t\t\t\"file\": {:?},\n\t\t\t\"entry\": {entry},\n\t\t\t\"modules\": [{modules}],\n\t\t\t\"imports\": [{}],\n\t\t\t\"dynamicImports\": [{}]\n\t\t}}",
+			chunk.file_name,
+			list(&chunk.imports),
+			list(&chunk.dynamic_imports),
+		));
+	}
+	buf.push_str("\n\t]\n}\n");
+	buf
+}
+
+fn import_part_keeps_name(part: &ImportPart) -> bool {
+	match part {
+		ImportPart::Name(_) => true,
+		ImportPart::NameWithAlias { .. } => false,
+		ImportPart::PrefixComment(_, None, _) => true,
+		ImportPart::PrefixComment(_, Some(part), _) | ImportPart::PostfixComment(part, _, _) => {
+			import_part_keeps_name(part)
+		}
+	}
+}
+
+fn add_warning(
+	diagnostics: &mut DiagnosticsContainer,
+	reason: &str,
+	position: parser::source_map::SpanWithSource,
+) {
+	diagnostics.add_warning(Diagnostic::Position {
+		reason: reason.to_owned(),
+		position,
+		kind: DiagnosticKind::Warning,
+	});
+}
+
+fn static_import_specifier(item: &StatementOrDeclaration) -> Option<&str> {
+	let StatementOrDeclaration::Declaration(declaration) = item else { return None };
+	let from = match declaration {
+		Declaration::Import(import) => &import.from,
+		Declaration::Export(export) => match &export.on {
+			ExportDeclaration::Variable {
+				exported: Exportable::ImportAll { from, .. } | Exportable::ImportParts { from, .. },
+				..
+			} => from,
+			_ => return None,
+		},
+		_ => return None,
+	};
+	if let ImportLocation::Quoted(specifier, _) = from {
+		Some(specifier)
+	} else {
+		None
+	}
+}
+
+/// `import "./a"` or `import {} from "./a"`
+fn is_bare_import(item: &StatementOrDeclaration) -> bool {
+	matches!(
+		item,
+		StatementOrDeclaration::Declaration(Declaration::Import(ImportDeclaration {
+			default: None,
+			items: ImportedItems::Parts(parts),
+			..
+		})) if matches!(parts.as_deref(), None | Some([]))
+	)
+}
+
+/// Relative specifiers and aliases from `paths`. Others (packages) are left as imports
+pub(crate) fn resolve(
+	from: &Path,
+	specifier: &str,
+	files: &MapFileStore<WithPathMap>,
+	fs: &impl crate::ReadFromFS,
+	paths: &[(String, Vec<String>)],
+) -> Option<SourceId> {
+	if !specifier.starts_with('.') {
+		// Aliased modules are registered by the checker in their canonical form
+		return resolve_path_alias(specifier, paths, Path::new("")).iter().find_map(|target| {
+			candidate_paths(target).iter().find_map(|candidate| {
+				files
+					.get_source_at_path(&canonicalise_path(candidate, false, fs))
+					.or_else(|| files.get_source_at_path(candidate))
+			})
+		});
+	}
+	let mut path = PathBuf::new();
+	for component in from.parent()?.join(specifier).components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				path.pop();
+			}
+			component => path.push(component),
+		}
+	}
+	candidate_paths(&path).iter().find_map(|candidate| files.get_source_at_path(candidate))
+}
+
+#[derive(Default)]
+struct DynamicImports(Vec<String>);
+
+impl VisitorReceiver<()> for DynamicImports {
+	fn visit_expression(&mut self, expression: &Expression, _data: &mut (), _chain: &Chain) {
+		if let Expression::DynamicImport { path, .. } = expression {
+			if let Expression::StringLiteral(specifier, ..) = &**path {
+				self.0.push(specifier.clone());
+			}
+		}
+	}
+}
+
+/// Rewrites `import("...")` specifiers to their chunk
+struct DynamicImportsMut;
+
+impl VisitorMutReceiver<HashMap<String, String>> for DynamicImportsMut {
+	fn visit_expression_mut(
+		&mut self,
+		expression: &mut Expression,
+		locations: &mut HashMap<String, String>,
+		_chain: &Chain,
+	) {
+		if let Expression::DynamicImport { path, .. } = expression {
+			if let Expression::StringLiteral(specifier, ..) = &mut **path {
+				if let Some(location) = locations.get(specifier.as_str()) {
+					location.clone_into(specifier);
+				}
+			}
+		}
+	}
+}
@@ -1,23 +1,40 @@
-use checker::CheckOutput;
-use std::{
-	collections::HashSet,
-	path::{Path, PathBuf},
-};
+use checker::{synthesis::PreCheckVisitors, CheckOutput};
+use std::{collections::HashSet, path::PathBuf};
 
 pub fn check<T: crate::ReadFromFS>(
 	entry_points: Vec<PathBuf>,
 	read_from_filesystem: &T,
-	type_definition_module: Option<&Path>,
+	type_definition_files: &[PathBuf],
 	type_check_options: checker::TypeCheckOptions,
 ) -> CheckOutput<checker::synthesis::EznoParser> {
-	let definitions = if let Some(tdm) = type_definition_module {
-		HashSet::from_iter(std::iter::once(tdm.into()))
-	} else {
+	check_with_visitors(
+		entry_points,
+		read_from_filesystem,
+		type_definition_files,
+		type_check_options,
+		Default::default(),
+	)
+}
+
+/// [`check`] with transformers that are run on each module before it is checked
+pub fn check_with_visitors<T: crate::ReadFromFS>(
+	entry_points: Vec<PathBuf>,
+	read_from_filesystem: &T,
+	type_definition_files: &[PathBuf],
+	type_check_options: checker::TypeCheckOptions,
+	pre_check_visitors: PreCheckVisitors,
+) -> CheckOutput<checker::synthesis::EznoParser> {
+	let definitions = if type_definition_files.is_empty() {
 		HashSet::from_iter(std::iter::once(checker::INTERNAL_DEFINITION_FILE_PATH.into()))
+	} else {
+		type_definition_files.iter().cloned().collect()
 	};
 
-	let read_from_fs =
-		|path: &Path| read_from_filesystem.get_content_at_path(path).map(String::into_bytes);
-
-	checker::check_project(entry_points, definitions, read_from_fs, type_check_options, ())
+	checker::check_project(
+		entry_points,
+		definitions,
+		read_from_filesystem,
+		type_check_options,
+		pre_check_visitors,
+	)
 }
@@ -1,5 +1,6 @@
 #[allow(unused)]
 use std::{
+	cell::RefCell,
 	collections::HashSet,
 	env, fs,
 	path::{Path, PathBuf},
@@ -11,23 +12,47 @@ use crate::{
 	build::{build, BuildOutput, FailedBuildOutput},
 	build::{BuildConfig, EznoParsePostCheckVisitors},
 	check::check,
+	config::{parse_lib, parse_path_alias, Config},
 	error_handling::emit_ezno_diagnostic,
-	utilities::print_to_cli,
+	file_system::{get_entry_points, RecordingFileSystem},
+	progress::Progress,
+	reporting::Reporter,
+	transformers::define::parse_definition,
+	utilities::{
+		print_to_cli, print_to_cli_without_newline, set_color_choice, timings_to_json, ColorChoice,
+		ErrorOn,
+	},
 };
 use argh::FromArgs;
-use checker::CheckOutput;
+use base64::Engine;
+use checker::{incremental::CheckCache, libraries::Lib, CheckOutput};
+use parser::source_map::{FileSystem, MapFileStore, WithPathMap};
 
 /// Ezno Compiler
 #[derive(FromArgs, Debug)]
 struct TopLevel {
 	#[argh(subcommand)]
 	nested: CompilerSubCommand,
+	/// trace checker internals. Filter of `target=level` items (e.g. `types::subtyping=debug,info`).
+	/// `debug` and `trace` are only logged by debug builds
+	#[argh(option)]
+	log: Option<String>,
+	/// write logs to a file rather than stderr
+	#[argh(option)]
+	log_file: Option<PathBuf>,
+	/// when to use colors in output: auto (default), always or never
+	#[argh(option, default = "ColorChoice::Auto")]
+	color: ColorChoice,
+	/// JSON file of translated diagnostic messages
+	#[argh(option)]
+	locale: Option<PathBuf>,
 }
 
 #[derive(FromArgs, Debug)]
 #[argh(subcommand)]
 enum CompilerSubCommand {
 	Info(Info),
+	Explain(ExplainArguments),
 	ASTExplorer(crate::ast_explorer::ExplorerArguments),
 	Check(CheckArguments),
 	Experimental(ExperimentalArguments),
@@ -42,6 +67,15 @@ enum CompilerSubCommand {
 #[argh(subcommand, name = "info")]
 struct Info {}
 
+/// Explain a diagnostic code (for example `ezno explain EZ1002`)
+#[derive(FromArgs, Debug)]
+#[argh(subcommand, name = "explain")]
+pub(crate) struct ExplainArguments {
+	/// diagnostic code. Lists the codes if left out
+	#[argh(positional)]
+	code: Option<String>,
+}
+
 /// Experimental Ezno features
 #[derive(FromArgs, Debug)]
 #[argh(subcommand, name = "experimental")]
@@ -54,24 +88,76 @@ pub(crate) struct ExperimentalArguments {
 #[argh(subcommand)]
 pub(crate) enum ExperimentalSubcommand {
 	Build(BuildArguments),
+	Bench(BenchArguments),
+	Events(EventsArguments),
+}
+
+/// Check project several times and display how long parsing and checking took
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "bench")]
+pub(crate) struct BenchArguments {
+	/// path to input file (or glob of input files)
+	#[argh(positional)]
+	pub input: PathBuf,
+	/// paths to definition files (can be repeated). Defaults to the built-in definitions
+	#[argh(option, short = 'd')]
+	pub definition_file: Vec<PathBuf>,
+	/// number of times to check (the median is displayed). Defaults to 5
+	#[argh(option, default = "5")]
+	pub iterations: usize,
+	/// print measurements as JSON
+	#[argh(switch)]
+	pub json: bool,
+}
+
+/// Print the events (reads, writes, calls, conditionals and throws) of each function in a file
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand, name = "events")]
+pub(crate) struct EventsArguments {
+	/// path to input file
+	#[argh(positional)]
+	pub input: PathBuf,
+	/// paths to definition files (can be repeated). Defaults to the built-in definitions
+	#[argh(option, short = 'd')]
+	pub definition_file: Vec<PathBuf>,
 }
 
 /// Build project
-/// TODO definition file as list
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "build")]
 // TODO: Can be refactored with bit to reduce memory
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct BuildArguments {
-	/// path to input file
+	/// path to input file (or glob of input files). Defaults to `entry` in the configuration file
 	#[argh(positional)]
-	pub input: PathBuf,
+	pub input: Option<PathBuf>,
 	/// path to output
-	#[argh(positional)]
+	#[argh(option, short = 'o')]
 	pub output: Option<PathBuf>,
-	/// paths to definition files
+	/// paths to definition files (can be repeated). Defaults to the built-in definitions
 	#[argh(option, short = 'd')]
-	pub definition_file: Option<PathBuf>,
+	pub definition_file: Vec<PathBuf>,
+	/// built-in libraries to use instead of the built-in definitions, e.g. es2015 or dom (can be
+	/// repeated)
+	#[argh(option, from_str_fn(parse_lib))]
+	pub lib: Vec<Lib>,
+
+	/// additional entry points or globs of entry points (can be repeated)
+	#[argh(option)]
+	pub entry: Vec<PathBuf>,
+	/// output a chunk for each entry point and dynamic import (output is then a directory)
+	#[argh(switch)]
+	pub bundle: bool,
+	/// with --bundle, move modules shared between chunks into separate chunks
+	#[argh(switch)]
+	pub split: bool,
+	/// replace references to a name with a value before checking, e.g.
+	/// `process.env.NODE_ENV="production"` (can be repeated)
+	#[argh(option, from_str_fn(parse_definition))]
+	pub define: Vec<(String, String)>,
+	/// resolve imports matching a pattern to a path, e.g. `@app/*=src/*` (can be repeated)
+	#[argh(option, from_str_fn(parse_path_alias))]
+	pub paths: Vec<(String, String)>,
 
 	/// whether to minify build output
 	#[argh(switch, short = 'm')]
@@ -79,9 +165,18 @@ pub(crate) struct BuildArguments {
 	/// whether to include comments in the output
 	#[argh(switch)]
 	pub no_comments: bool,
+	/// text to add to the start of each output (e.g. a license comment)
+	#[argh(option)]
+	pub banner: Option<String>,
+	/// text to add to the end of each output
+	#[argh(option)]
+	pub footer: Option<String>,
 	/// build source maps
 	#[argh(switch)]
 	pub source_maps: bool,
+	/// also output a `.d.ts` file declaring the exports of each entry point
+	#[argh(switch)]
+	pub declaration: bool,
 
 	/// enable non standard syntax
 	#[argh(switch)]
@@ -89,7 +184,8 @@ pub(crate) struct BuildArguments {
 	/// enable non standard library
 	#[argh(switch)]
 	pub non_standard_library: bool,
-	/// enable optimising transforms (warning can break code)
+	/// enable optimising transforms (warning can break code). With --bundle, also removes bare
+	/// imports of modules without side effects
 	#[argh(switch)]
 	pub optimise: bool,
 
@@ -97,30 +193,120 @@ pub(crate) struct BuildArguments {
 	/// whether to display compile times
 	#[argh(switch)]
 	pub timings: bool,
-	// /// whether to re-build on file changes
-	// #[argh(switch)]
-	// watch: bool,
+	/// print diagnostics in the order they were found rather than by file and position
+	#[argh(switch)]
+	pub preserve_diagnostic_order: bool,
+	/// do not display progress while building
+	#[argh(switch)]
+	pub no_progress: bool,
+	/// which diagnostics fail the build: warnings, errors (default) or never
+	#[argh(option)]
+	pub error_on: Option<ErrorOn>,
+	/// how diagnostics are printed: terminal (default), json or sarif. Not used with `--watch`
+	#[argh(option)]
+	pub reporter: Option<Reporter>,
+	/// whether to re-build on file changes
+	#[argh(switch)]
+	pub watch: bool,
 }
 
 /// Type check project
 #[derive(FromArgs, PartialEq, Debug)]
 #[argh(subcommand, name = "check")]
 pub(crate) struct CheckArguments {
-	/// path to input file
+	/// path to input file (or glob of input files). Defaults to `entry` in the configuration file
 	#[argh(positional)]
-	pub input: PathBuf,
-	/// paths to definition files
+	pub input: Option<PathBuf>,
+	/// paths to definition files (can be repeated). Defaults to the built-in definitions
 	#[argh(option, short = 'd')]
-	pub definition_file: Option<PathBuf>,
+	pub definition_file: Vec<PathBuf>,
+	/// built-in libraries to use instead of the built-in definitions, e.g. es2015 or dom (can be
+	/// repeated)
+	#[argh(option, from_str_fn(parse_lib))]
+	pub lib: Vec<Lib>,
 	/// whether to re-check on file changes
 	#[argh(switch)]
 	pub watch: bool,
 	/// whether to display check time
 	#[argh(switch)]
 	pub timings: bool,
+	/// write durations of checking stages and of each module (and the number of types) as JSON to
+	/// a file
+	#[argh(option)]
+	pub timings_json: Option<PathBuf>,
 	/// whether to print all diagnostics
 	#[argh(switch)]
 	pub count_diagnostics: bool,
+	/// print diagnostics in the order they were found rather than by file and position
+	#[argh(switch)]
+	pub preserve_diagnostic_order: bool,
+	/// do not display progress while checking
+	#[argh(switch)]
+	pub no_progress: bool,
+	/// treat symlinked files as separate modules rather than resolving them to their target
+	#[argh(switch)]
+	pub preserve_symlinks: bool,
+	/// clear the screen before each re-check in watch mode
+	#[argh(switch)]
+	pub clear_screen: bool,
+	/// warn on variables that are any because of a previous error
+	#[argh(switch)]
+	pub no_error_any_propagation: bool,
+	/// warn on imports that are any because their module could not be found
+	#[argh(switch)]
+	pub no_missing_import_any: bool,
+	/// warn on explicit any annotations
+	#[argh(switch)]
+	pub no_explicit_any: bool,
+	/// warn on destructured bindings that are never read
+	#[argh(switch)]
+	pub no_unused_bindings: bool,
+	/// warn on promises that are not awaited or handled
+	#[argh(switch)]
+	pub no_floating_promises: bool,
+	/// evaluate `&`, `|` and `^` on unions of numbers (such as flags), narrow on masks and warn on
+	/// masks that are always 0
+	#[argh(switch)]
+	pub advanced_numbers: bool,
+	/// warn on assignments that are overwritten before being read and on unused expressions
+	#[argh(switch)]
+	pub no_dead_stores: bool,
+	/// function whose returned promise can be left floating (can be repeated)
+	#[argh(option)]
+	pub allow_floating_promise: Vec<String>,
+	/// glob of files to not report diagnostics for (can be repeated)
+	#[argh(option)]
+	pub skip_checking: Vec<String>,
+	/// glob of files to check even if they match a `--skip-checking` glob (can be repeated)
+	#[argh(option)]
+	pub always_check: Vec<String>,
+	/// resolve imports matching a pattern to a path, e.g. `@app/*=src/*` (can be repeated)
+	#[argh(option, from_str_fn(parse_path_alias))]
+	pub paths: Vec<(String, String)>,
+	/// type the default export of imports with an extension as a type, without reading them, e.g.
+	/// `css=string` (can be repeated)
+	#[argh(option, from_str_fn(parse_definition))]
+	pub asset_module: Vec<(String, String)>,
+	/// which diagnostics fail the check: warnings, errors (default) or never
+	#[argh(option)]
+	pub error_on: Option<ErrorOn>,
+	/// print diagnostics as JSON (with their file, offsets, lines and columns) rather than for a
+	/// terminal. Same as `--reporter json`. Not used with `--watch`
+	#[argh(switch)]
+	pub json: bool,
+	/// how diagnostics are printed: terminal (default), json or sarif. Not used with `--watch`
+	#[argh(option)]
+	pub reporter: Option<Reporter>,
+	/// reuse the diagnostics of the last check (stored in `.ezno-cache`) if no files have changed
+	#[argh(switch)]
+	pub cache: bool,
+	/// number of threads to parse entry points on (checking is still done on one thread). Defaults to
+	/// the number of available cores
+	#[argh(option)]
+	pub threads: Option<usize>,
+	/// skip source files over this many bytes (with a warning). Defaults to 10MiB
+	#[argh(option)]
+	pub maximum_file_size: Option<usize>,
 }
 
 // /// Run project using Deno
@@ -152,104 +338,680 @@ fn file_system_resolver(path: &Path) -> Option<String> {
 	}
 }
 
+/// Overrides `EZNO_LOG` if either are set
+fn set_up_logging(filter: Option<&str>, log_file: Option<&Path>) -> Result<(), String> {
+	use checker::logging::{self, LogFilter, LogOutput};
+
+	if filter.is_none() && log_file.is_none() {
+		logging::init(LogFilter::from_environment()?, LogOutput::Stderr);
+		return Ok(());
+	}
+
+	// Writing to a file with no filter logs everything at debug level
+	let filter = LogFilter::parse(filter.unwrap_or("debug"))?;
+	let output = if let Some(log_file) = log_file {
+		LogOutput::file(log_file)
+			.map_err(|err| format!("Could not create log file {}: {err}", log_file.display()))?
+	} else {
+		LogOutput::Stderr
+	};
+	logging::init(filter, output);
+	Ok(())
+}
+
+/// For `check --cache`. The [`CheckCache`] is encoded as base64, as [`crate::WriteToFS`] writes text
+const CHECK_CACHE_PATH: &str = ".ezno-cache";
+
+/// The diagnostics, files and paths of the files of a check
+type RestoredCheck = (checker::DiagnosticsContainer, MapFileStore<WithPathMap>, Vec<PathBuf>);
+
+/// The last check (from [`CHECK_CACHE_PATH`]), if nothing has changed
+fn load_check_cache(key: u64, fs: &impl crate::ReadFromFS) -> Option<RestoredCheck> {
+	let content = fs.read_file(Path::new(CHECK_CACHE_PATH))?;
+	let bytes = base64::engine::general_purpose::STANDARD.decode(content).ok()?;
+	restore_check_cache(&bytes, key, fs)
+}
+
+/// The check cached in `bytes`, if nothing has changed
+fn restore_check_cache(
+	bytes: &[u8],
+	key: u64,
+	fs: &impl crate::ReadFromFS,
+) -> Option<RestoredCheck> {
+	let cache = CheckCache::from_bytes(bytes, key)?;
+	cache.restore(&|path: &Path| fs.read_file(path))
+}
+
+/// For `check --threads`. `available_parallelism` is not supported under WASM
+fn default_threads() -> usize {
+	std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+}
+
+/// Defaults if there is no configuration file
+fn load_config(fs: &impl crate::ReadFromFS) -> Result<Config, String> {
+	Config::load(fs).map(Option::unwrap_or_default)
+}
+
+/// Paths or globs of `inputs`, else of `entry` in the configuration file
+fn get_inputs_entry_points(
+	inputs: &[PathBuf],
+	config: &Config,
+	fs: &impl crate::ReadFromFS,
+) -> Result<Vec<PathBuf>, String> {
+	let inputs = if inputs.is_empty() { &config.entry } else { inputs };
+	if inputs.is_empty() {
+		return Err(format!(
+			"No input given. Pass a path or add `entry` to {}",
+			crate::config::CONFIG_FILE
+		));
+	}
+	let entry_points =
+		inputs.iter().map(|input| get_entry_points(input, fs)).collect::<Result<Vec<_>, _>>()?;
+	Ok(entry_points.into_iter().flatten().collect())
+}
+
+/// Result of [`run_cli`]. Each has a distinct exit code so that scripts can branch on them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CLIOutcome {
+	Success,
+	/// Diagnostics that `--error-on` fails on were found
+	DiagnosticsFound,
+	/// Invalid arguments, or inputs that could not be found
+	BadArguments,
+	/// The compiler panicked
+	InternalError,
+}
+
+impl CLIOutcome {
+	#[must_use]
+	pub fn exit_code(self) -> u8 {
+		match self {
+			Self::Success => 0,
+			Self::DiagnosticsFound => 1,
+			Self::BadArguments => 2,
+			Self::InternalError => 3,
+		}
+	}
+}
+
+impl From<CLIOutcome> for std::process::ExitCode {
+	fn from(outcome: CLIOutcome) -> Self {
+		Self::from(outcome.exit_code())
+	}
+}
+
 pub fn run_cli<T: crate::ReadFromFS, U: crate::WriteToFS, V: crate::CLIInputResolver>(
 	cli_arguments: &[&str],
 	read_file: &T,
 	write_file: U,
 	cli_input_resolver: V,
-) {
+) -> CLIOutcome {
 	let command = match FromArgs::from_args(&["ezno-cli"], cli_arguments) {
-		Ok(TopLevel { nested }) => nested,
+		Ok(TopLevel { nested, log, log_file, color, locale }) => {
+			set_color_choice(color);
+			if let Err(reason) = set_up_logging(log.as_deref(), log_file.as_deref()) {
+				print_to_cli(format_args!("{reason}"));
+				return CLIOutcome::BadArguments;
+			}
+			let catalog = match locale {
+				Some(path) => {
+					let catalog = read_file
+						.read_file(&path)
+						.ok_or_else(|| format!("Could not read locale file '{}'", path.display()))
+						.and_then(|content| {
+							crate::locale::MessageCatalog::from_json(&String::from_utf8_lossy(
+								&content,
+							))
+						});
+					match catalog {
+						Ok(catalog) => Some(catalog),
+						Err(reason) => {
+							print_to_cli(format_args!("{reason}"));
+							return CLIOutcome::BadArguments;
+						}
+					}
+				}
+				None => None,
+			};
+			crate::locale::set_message_catalog(catalog);
+			nested
+		}
 		Err(err) => {
 			print_to_cli(format_args!("{}", err.output));
-			return;
+			// `--help` is also an "error"
+			return if err.status.is_ok() { CLIOutcome::Success } else { CLIOutcome::BadArguments };
 		}
 	};
 
 	match command {
 		CompilerSubCommand::Info(_) => {
 			crate::utilities::print_info();
+			CLIOutcome::Success
+		}
+		CompilerSubCommand::Explain(ExplainArguments { code: None }) => {
+			for explanation in checker::explanations::EXPLANATIONS {
+				print_to_cli(format_args!("{} {}", explanation.code, explanation.title));
+			}
+			CLIOutcome::Success
+		}
+		CompilerSubCommand::Explain(ExplainArguments { code: Some(code) }) => {
+			if let Some(explanation) = checker::explanations::get_explanation(&code) {
+				print_to_cli(format_args!("{}: {}\n", explanation.code, explanation.title));
+				print_to_cli(format_args!("{}\n", explanation.explanation));
+				print_to_cli(format_args!("For example\n\n{}", explanation.example));
+				CLIOutcome::Success
+			} else {
+				print_to_cli(format_args!(
+					"No explanation for '{code}'. Run `ezno explain` for a list"
+				));
+				CLIOutcome::BadArguments
+			}
 		}
 		CompilerSubCommand::Check(check_arguments) => {
-			let CheckArguments { input, watch: _, definition_file, timings, count_diagnostics } =
-				check_arguments;
-			let entry_points = vec![input];
+			// Everything that changes the result other than the files
+			let arguments_description = format!("{check_arguments:?}");
+			let CheckArguments {
+				input,
+				watch,
+				definition_file,
+				lib,
+				timings,
+				timings_json,
+				count_diagnostics,
+				preserve_diagnostic_order,
+				no_progress,
+				preserve_symlinks,
+				clear_screen,
+				no_error_any_propagation,
+				no_missing_import_any,
+				no_explicit_any,
+				no_unused_bindings,
+				no_floating_promises,
+				advanced_numbers,
+				no_dead_stores,
+				allow_floating_promise,
+				skip_checking,
+				always_check,
+				paths,
+				asset_module,
+				error_on,
+				json,
+				reporter,
+				cache,
+				threads,
+				maximum_file_size,
+			} = check_arguments;
 
-			#[cfg(not(target_family = "wasm"))]
-			let start = timings.then(std::time::Instant::now);
+			let mut config = match load_config(read_file) {
+				Ok(config) => config,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
+			};
+			config.add_paths(paths);
+			let inputs = Vec::from_iter(input);
+			let libs = match config.lib_definition_files(&lib) {
+				Ok(libs) => libs,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
+			};
+			let definition_files = libs
+				.into_iter()
+				.chain(definition_file)
+				.chain(config.definition_files.clone())
+				.collect::<Vec<_>>();
+			let check_config = &config.check;
+			let preserve_symlinks = preserve_symlinks || check_config.preserve_symlinks;
+			let no_error_any_propagation =
+				no_error_any_propagation || check_config.no_error_any_propagation;
+			let no_missing_import_any = no_missing_import_any || check_config.no_missing_import_any;
+			let no_explicit_any = no_explicit_any || check_config.no_explicit_any;
+			let no_unused_bindings = no_unused_bindings || check_config.no_unused_bindings;
+			let no_floating_promises = no_floating_promises || check_config.no_floating_promises;
+			let advanced_numbers = advanced_numbers || check_config.advanced_numbers;
+			let no_dead_stores = no_dead_stores || check_config.no_dead_stores;
+			let allow_floating_promise =
+				[allow_floating_promise, check_config.allow_floating_promise.clone()].concat();
+			let skip_checking = [skip_checking, check_config.skip_checking.clone()].concat();
+			let always_check = [always_check, check_config.always_check.clone()].concat();
+			let asset_modules = asset_module
+				.into_iter()
+				.chain(check_config.asset_modules.clone())
+				.collect::<Vec<_>>();
 
-			let type_check_options = Default::default();
+			let reporter = if json { Reporter::Json } else { reporter.unwrap_or_default() };
+			let threads = threads.unwrap_or_else(default_threads);
+			let maximum_file_size = maximum_file_size
+				.or(check_config.maximum_file_size)
+				.or(checker::TypeCheckOptions::default().maximum_file_size);
 
-			let CheckOutput { diagnostics, module_contents, .. } =
-				check(entry_points, read_file, definition_file.as_deref(), type_check_options);
+			let error_on = match ErrorOn::resolve(error_on) {
+				Ok(error_on) => error_on,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
+			};
+			let mut outcome = CLIOutcome::Success;
 
+			// In watch mode only changes are printed
 			#[cfg(not(target_family = "wasm"))]
-			if let Some(start) = start {
-				eprintln!("Checked in {:?}", start.elapsed());
-			};
+			let mut diagnostics_diff = watch.then(crate::watch::DiagnosticsDiff::default);
+			let mut first_run = true;
+			let mut last_check: Option<Vec<u8>> = None;
 
-			if diagnostics.has_error() {
-				let diagnostics = diagnostics.into_iter();
-				if count_diagnostics {
-					let count = diagnostics.count();
-					print_to_cli(format_args!("Found {count} type errors and warnings 😬",))
-				} else {
-					for diagnostic in diagnostics {
-						emit_ezno_diagnostic(diagnostic, &module_contents).unwrap();
+			// Returns the files that were checked (for watching)
+			let mut run_check = |cancellation: checker::CancellationToken| {
+				if clear_screen && !first_run {
+					print_to_cli_without_newline(format_args!("\x1b[2J\x1b[H"));
+				}
+				first_run = false;
+
+				let entry_points = match get_inputs_entry_points(&inputs, &config, read_file) {
+					Ok(entry_points) => entry_points,
+					Err(reason) => {
+						print_to_cli(format_args!("{reason}"));
+						outcome = CLIOutcome::BadArguments;
+						return Vec::new();
 					}
+				};
+
+				#[cfg(not(target_family = "wasm"))]
+				let start = (timings || watch).then(std::time::Instant::now);
+
+				let type_check_options = checker::TypeCheckOptions {
+					strict_casts: check_config.strict_casts,
+					preserve_symlinks,
+					no_error_any_propagation,
+					no_missing_import_any,
+					no_explicit_any,
+					no_unused_bindings,
+					no_floating_promises,
+					advanced_numbers,
+					no_dead_stores,
+					floating_promise_allowed_functions: allow_floating_promise.clone(),
+					skip_checking: skip_checking.clone(),
+					always_check: always_check.clone(),
+					asset_modules: asset_modules.clone(),
+					measure_time: timings || timings_json.is_some(),
+					paths: config.paths(),
+					threads,
+					maximum_file_size,
+					cancellation,
+					..Default::default()
+				};
+
+				let cache_key = checker::incremental::cache_key(&format!(
+					"{arguments_description}{config:?}{entry_points:?}"
+				));
+				// In watch mode the last run is also kept in memory
+				let cached = if cache { load_check_cache(cache_key, read_file) } else { None }
+					.or_else(|| {
+						let bytes = last_check.as_deref()?;
+						restore_check_cache(bytes, cache_key, read_file)
+					});
+
+				// The chronometer is `None` for a restored check (as nothing was timed)
+				let (mut diagnostics, module_contents, files_read, chronometer) =
+					if let Some((diagnostics, module_contents, files_read)) = cached {
+						(diagnostics, module_contents, files_read, None)
+					} else {
+						let progress = Progress::new("Checking", !no_progress);
+						// Including failed reads, so the cache is invalidated if they are created
+						let files_read = RefCell::new(Vec::new());
+						let read_file = RecordingFileSystem::new(read_file, |path, content| {
+							progress.on_file(path);
+							let content_hash = content.map(checker::incremental::hash_content);
+							files_read.borrow_mut().push((path.to_path_buf(), content_hash));
+						});
+						let type_check_options = checker::TypeCheckOptions {
+							module_progress: progress.module_progress(),
+							..type_check_options
+						};
+
+						let output =
+							check(entry_points, &read_file, &definition_files, type_check_options);
+
+						progress.finish();
+
+						let files_read = files_read.into_inner();
+						// Cancelled by a change in watch mode. It is run again, so nothing is printed
+						if output.cancelled {
+							return files_read.into_iter().map(|(path, _)| path).collect();
+						}
+						if let (true, Some(check_cache)) =
+							(cache || watch, CheckCache::new(&output, &files_read))
+						{
+							let bytes = check_cache.to_bytes(cache_key);
+							if cache {
+								write_file(
+									Path::new(CHECK_CACHE_PATH),
+									base64::engine::general_purpose::STANDARD.encode(&bytes),
+								);
+							}
+							if watch {
+								last_check = Some(bytes);
+							}
+						}
+						let CheckOutput { diagnostics, module_contents, chronometer, .. } = output;
+						let files_read = files_read.into_iter().map(|(path, _)| path).collect();
+						(diagnostics, module_contents, files_read, Some(chronometer))
+					};
+
+				if let (Some(path), Some(chronometer)) = (&timings_json, &chronometer) {
+					write_file(path.as_path(), timings_to_json(chronometer, &module_contents));
 				}
+
+				if !preserve_diagnostic_order {
+					diagnostics.sort(&module_contents);
+				}
+
+				#[cfg(not(target_family = "wasm"))]
+				if let Some(ref mut diagnostics_diff) = diagnostics_diff {
+					let elapsed = start.map(|start| start.elapsed());
+					let (errors, warnings) =
+						diagnostics_diff.emit(diagnostics.get_diagnostics(), &module_contents);
+					let elapsed =
+						elapsed.map(|elapsed| format!(" in {elapsed:?}")).unwrap_or_default();
+					print_to_cli(format_args!(
+						"{errors} errors and {warnings} warnings. Checked{elapsed}, watching for changes..."
+					));
+					return files_read;
+				}
+
+				#[cfg(not(target_family = "wasm"))]
+				if let Some(start) = start {
+					eprintln!("Checked in {:?}", start.elapsed());
+					if let Some(chronometer) = chronometer {
+						eprintln!(
+							"Definition files {:?}, {} modules in {:?}, {} types",
+							chronometer.definition_files,
+							chronometer.modules.len(),
+							chronometer.modules_total,
+							chronometer.types
+						);
+					} else {
+						eprintln!("Restored from the check cache");
+					}
+				};
+
+				let failed = error_on.fails(&diagnostics);
+				outcome = if failed { CLIOutcome::DiagnosticsFound } else { CLIOutcome::Success };
+
+				if reporter != Reporter::Terminal {
+					reporter.report(diagnostics, &module_contents);
+				} else if diagnostics.has_error() || failed {
+					let diagnostics = diagnostics.into_iter();
+					if count_diagnostics {
+						let count = diagnostics.count();
+						print_to_cli(format_args!("Found {count} type errors and warnings 😬",))
+					} else {
+						for diagnostic in diagnostics {
+							emit_ezno_diagnostic(diagnostic, &module_contents).unwrap();
+						}
+					}
+				} else {
+					print_to_cli(format_args!("No type errors found 🎉"))
+				}
+
+				files_read
+			};
+
+			if watch {
+				#[cfg(not(target_family = "wasm"))]
+				crate::watch::watch_files(run_check);
+				#[cfg(target_family = "wasm")]
+				print_to_cli(format_args!("--watch is not supported under WASM"));
 			} else {
-				print_to_cli(format_args!("No type errors found 🎉"))
+				let _ = run_check(checker::CancellationToken::default());
 			}
+			outcome
 		}
 		CompilerSubCommand::Experimental(ExperimentalArguments {
 			nested: ExperimentalSubcommand::Build(build_config),
 		}) => {
-			let output_path = build_config.output.unwrap_or("ezno_output.js".into());
-
-			// TODO
-			let default_builders = EznoParsePostCheckVisitors {
-				expression_visitors_mut: vec![Box::new(
-					crate::transformers::optimisations::ExpressionOptimiser,
-				)],
-				statement_visitors_mut: vec![Box::new(
-					crate::transformers::optimisations::StatementOptimiser,
-				)],
-				variable_visitors_mut: Default::default(),
-				block_visitors_mut: Default::default(),
+			let output_path = build_config.output.unwrap_or_else(|| {
+				if build_config.bundle { "ezno_output" } else { "ezno_output.js" }.into()
+			});
+			let mut file_config = match load_config(read_file) {
+				Ok(config) => config,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
+			};
+			file_config.add_paths(build_config.paths);
+			let inputs =
+				build_config.input.iter().chain(&build_config.entry).cloned().collect::<Vec<_>>();
+			let libs = match file_config.lib_definition_files(&build_config.lib) {
+				Ok(libs) => libs,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
+			};
+			let definition_files = libs
+				.into_iter()
+				.chain(build_config.definition_file)
+				.chain(file_config.definition_files.clone())
+				.collect::<Vec<_>>();
+			let preserve_diagnostic_order = build_config.preserve_diagnostic_order;
+			let reporter = build_config.reporter.unwrap_or_default();
+			let error_on = match ErrorOn::resolve(build_config.error_on) {
+				Ok(error_on) => error_on,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
 			};
 
-			let input_paths = vec![build_config.input];
-
-			let output = build(
-				input_paths,
-				read_file,
-				build_config.definition_file.as_deref(),
-				&output_path,
-				&BuildConfig { strip_whitespace: build_config.minify },
-				Some(default_builders),
-			);
-
-			match output {
-				Ok(BuildOutput { diagnostics, fs, outputs }) => {
-					for output in outputs {
-						write_file(output.output_path.as_path(), output.content);
+			let mut config = BuildConfig {
+				strip_whitespace: build_config.minify || file_config.build.minify,
+				bundle: build_config.bundle || file_config.build.bundle,
+				split: build_config.split || file_config.build.split,
+				tree_shake: build_config.optimise || file_config.build.optimise,
+				define: build_config
+					.define
+					.into_iter()
+					.chain(file_config.build.define.clone())
+					.collect(),
+				banner: build_config.banner.or(file_config.build.banner.clone()),
+				footer: build_config.footer.or(file_config.build.footer.clone()),
+				paths: file_config.paths(),
+				declaration: build_config.declaration || file_config.build.declaration,
+				source_maps: build_config.source_maps,
+				// Set for each run
+				module_progress: Default::default(),
+			};
+			let watch = build_config.watch;
+			#[cfg(not(target_family = "wasm"))]
+			let timings = build_config.timings;
+			let mut outcome = CLIOutcome::Success;
+
+			// In watch mode only changes are printed
+			#[cfg(not(target_family = "wasm"))]
+			let mut diagnostics_diff = watch.then(crate::watch::DiagnosticsDiff::default);
+
+			// Returns the files that were read (for watching)
+			let mut run_build = || {
+				// TODO
+				let default_builders = EznoParsePostCheckVisitors {
+					expression_visitors_mut: vec![Box::new(
+						crate::transformers::optimisations::ExpressionOptimiser,
+					)],
+					statement_visitors_mut: vec![Box::new(
+						crate::transformers::optimisations::StatementOptimiser,
+					)],
+					variable_visitors_mut: Default::default(),
+					block_visitors_mut: Default::default(),
+				};
+
+				let input_paths = match get_inputs_entry_points(&inputs, &file_config, read_file) {
+					Ok(input_paths) => input_paths,
+					Err(reason) => {
+						print_to_cli(format_args!("{reason}"));
+						outcome = CLIOutcome::BadArguments;
+						return Vec::new();
 					}
-					for diagnostic in diagnostics {
-						emit_ezno_diagnostic(diagnostic, &fs).unwrap();
+				};
+
+				#[cfg(not(target_family = "wasm"))]
+				let start = (timings || watch).then(std::time::Instant::now);
+
+				let progress = Progress::new("Building", !build_config.no_progress);
+				config.module_progress = progress.module_progress();
+				let files_read = RefCell::new(Vec::new());
+				let read_file = RecordingFileSystem::new(read_file, |path, content| {
+					progress.on_file(path);
+					if content.is_some() {
+						files_read.borrow_mut().push(path.to_path_buf());
 					}
+				});
 
-					print_to_cli(format_args!("Project built successfully 🎉"))
-				}
-				Err(FailedBuildOutput { fs, diagnostics }) => {
-					for diagnostic in diagnostics {
-						emit_ezno_diagnostic(diagnostic, &fs).unwrap();
+				let output = build(
+					input_paths,
+					&read_file,
+					&definition_files,
+					&output_path,
+					&config,
+					Some(default_builders),
+				);
+
+				progress.finish();
+
+				let (mut diagnostics, fs, built) = match output {
+					Ok(BuildOutput { diagnostics, fs, outputs }) => {
+						for output in outputs {
+							if !output.mappings.is_empty() {
+								let mut path = output.output_path.clone().into_os_string();
+								path.push(".map");
+								write_file(Path::new(&path), output.mappings);
+							}
+							write_file(output.output_path.as_path(), output.content);
+						}
+						(diagnostics, fs, true)
 					}
+					Err(FailedBuildOutput { fs, diagnostics }) => (diagnostics, fs, false),
+				};
+				if !preserve_diagnostic_order {
+					diagnostics.sort(&fs);
+				}
+
+				#[cfg(not(target_family = "wasm"))]
+				if let Some(ref mut diagnostics_diff) = diagnostics_diff {
+					let elapsed = start.map(|start| start.elapsed());
+					let (errors, warnings) =
+						diagnostics_diff.emit(diagnostics.get_diagnostics(), &fs);
+					let result = if built { "Built" } else { "Failed to build" };
+					let elapsed =
+						elapsed.map(|elapsed| format!(" in {elapsed:?}")).unwrap_or_default();
+					print_to_cli(format_args!(
+						"{errors} errors and {warnings} warnings. {result}{elapsed}, watching for changes..."
+					));
+					return files_read.into_inner();
+				}
+
+				let failed =
+					if built { error_on.fails(&diagnostics) } else { error_on != ErrorOn::Never };
+				outcome = if failed { CLIOutcome::DiagnosticsFound } else { CLIOutcome::Success };
+				reporter.report(diagnostics, &fs);
+
+				if built && reporter == Reporter::Terminal {
+					print_to_cli(format_args!("Project built successfully 🎉"));
+				}
+
+				#[cfg(not(target_family = "wasm"))]
+				if let Some(start) = start {
+					eprintln!("Built in {:?}", start.elapsed());
+				}
+
+				files_read.into_inner()
+			};
+
+			if watch {
+				#[cfg(not(target_family = "wasm"))]
+				// Builds are not cancelled, as a partial build would be output
+				crate::watch::watch_files(|_cancellation| run_build());
+				#[cfg(target_family = "wasm")]
+				print_to_cli(format_args!("--watch is not supported under WASM"));
+			} else {
+				let _ = run_build();
+			}
+			outcome
+		}
+		CompilerSubCommand::Experimental(ExperimentalArguments {
+			nested:
+				ExperimentalSubcommand::Bench(BenchArguments {
+					input,
+					definition_file,
+					iterations,
+					json,
+				}),
+		}) => {
+			let entry_points = match get_entry_points(&input, read_file) {
+				Ok(entry_points) => entry_points,
+				Err(reason) => {
+					print_to_cli(format_args!("{reason}"));
+					return CLIOutcome::BadArguments;
+				}
+			};
+			let runs =
+				crate::bench::benchmark(&entry_points, read_file, &definition_file, iterations);
+			let run = crate::bench::median(runs).unwrap();
+			let name = input.display().to_string();
+			if json {
+				print_to_cli(format_args!("{}", run.to_json(&name)));
+			} else {
+				print_to_cli(format_args!("{}", crate::bench::TABLE_HEADER));
+				print_to_cli(format_args!("{}", crate::bench::table_row(&name, &run)));
+			}
+			#[cfg(target_family = "wasm")]
+			print_to_cli(format_args!("Durations are not measured under WASM"));
+			CLIOutcome::Success
+		}
+		CompilerSubCommand::Experimental(ExperimentalArguments {
+			nested: ExperimentalSubcommand::Events(EventsArguments { input, definition_file }),
+		}) => {
+			let mut output =
+				check(vec![input.clone()], read_file, &definition_file, Default::default());
+
+			if output.diagnostics.has_error() {
+				for diagnostic in std::mem::take(&mut output.diagnostics) {
+					emit_ezno_diagnostic(diagnostic, &output.module_contents).unwrap();
+				}
+			}
+
+			// Entry paths are registered by the checker in their canonical form
+			let normalised = checker::features::modules::normalise_path(&input);
+			let canonical =
+				checker::features::modules::canonicalise_path(&normalised, false, read_file);
+			let files = &output.module_contents;
+			let Some(source) = files
+				.get_source_at_path(&canonical)
+				.or_else(|| files.get_source_at_path(&normalised))
+			else {
+				print_to_cli(format_args!("Could not find '{}'", input.display()));
+				return CLIOutcome::BadArguments;
+			};
+
+			let path = input.display();
+			let functions = output.get_function_events(source);
+			let content = files.get_file_content(source);
+			for (start, events) in functions {
+				let before = &content[..(start as usize).min(content.len())];
+				let line = before.matches('\n').count() + 1;
+				let column =
+					before.rfind('\n').map_or(before.len(), |idx| before.len() - idx - 1) + 1;
+				print_to_cli(format_args!("function at {path}:{line}:{column}"));
+				for event in events.lines() {
+					print_to_cli(format_args!("  {event}"));
 				}
 			}
+			CLIOutcome::Success
 		}
-		CompilerSubCommand::ASTExplorer(mut repl) => repl.run(read_file, cli_input_resolver),
-		CompilerSubCommand::Repl(argument) => crate::repl::run_repl(cli_input_resolver, argument),
 		// CompilerSubCommand::Run(run_arguments) => {
 		// 	let build_arguments = BuildArguments {
 		// 		input: run_arguments.input,
@@ -286,5 +1048,13 @@ pub fn run_cli<T: crate::ReadFromFS, U: crate::WriteToFS, V: crate::CLIInputReso
 		// 	let _root_ctx = checker::root_context_from_bytes(file);
 		// 	println!("Registered {} types", _root_ctx.types.len());
 		// }
+		CompilerSubCommand::ASTExplorer(mut repl) => {
+			repl.run(read_file, cli_input_resolver);
+			CLIOutcome::Success
+		}
+		CompilerSubCommand::Repl(argument) => {
+			crate::repl::run_repl(cli_input_resolver, argument);
+			CLIOutcome::Success
+		}
 	}
 }
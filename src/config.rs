@@ -0,0 +1,279 @@
+//! Options from a configuration file in the current directory, so they do not have to be passed
+//! on every run. `ezno.toml` is used if it exists, else a subset of `tsconfig.json`
+//!
+//! ```toml
+//! entry = ["src/main.ts"]
+//! definition-files = ["definitions.d.ts"]
+//! lib = ["es2015", "dom"]
+//!
+//! [paths]
+//! "@/*" = ["src/*"]
+//!
+//! [check]
+//! no-unused-bindings = true
+//! asset-modules = { css = "string", svg = "string" }
+//!
+//! [build]
+//! define = { "process.env.NODE_ENV" = "\"production\"" }
+//! ```
+//!
+//! CLI arguments take precedence. Switches are enabled if they are set in either, lists are
+//! combined and `--paths` patterns replace the same patterns in `paths`
+
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+};
+
+use checker::libraries::Lib;
+use serde::Deserialize;
+
+pub const CONFIG_FILE: &str = "ezno.toml";
+pub const TSCONFIG_FILE: &str = "tsconfig.json";
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+	/// Paths or globs of entry points. Used when no input is passed
+	pub entry: Vec<PathBuf>,
+	/// Replace the built-in definitions. `definition-file` with a single path is also accepted
+	#[serde(alias = "definition-file", deserialize_with = "one_or_many")]
+	pub definition_files: Vec<PathBuf>,
+	/// Names of built-in libraries (see [`checker::libraries::Lib`]) to use instead of the
+	/// built-in definitions
+	pub lib: Vec<String>,
+	/// See [`checker::TypeCheckOptions::paths`]
+	pub paths: BTreeMap<String, Vec<String>>,
+	pub check: CheckConfig,
+	pub build: BuildConfig,
+}
+
+/// Switches of `check`
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CheckConfig {
+	pub strict_casts: bool,
+	pub preserve_symlinks: bool,
+	pub no_error_any_propagation: bool,
+	pub no_missing_import_any: bool,
+	pub no_explicit_any: bool,
+	pub no_unused_bindings: bool,
+	pub no_floating_promises: bool,
+	pub advanced_numbers: bool,
+	pub no_dead_stores: bool,
+	pub allow_floating_promise: Vec<String>,
+	pub skip_checking: Vec<String>,
+	pub always_check: Vec<String>,
+	/// See [`checker::TypeCheckOptions::asset_modules`]
+	pub asset_modules: BTreeMap<String, String>,
+	/// See [`checker::TypeCheckOptions::maximum_file_size`]
+	pub maximum_file_size: Option<usize>,
+}
+
+/// Options of `build`
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct BuildConfig {
+	pub minify: bool,
+	pub bundle: bool,
+	pub split: bool,
+	pub optimise: bool,
+	pub define: BTreeMap<String, String>,
+	pub banner: Option<String>,
+	pub footer: Option<String>,
+	pub declaration: bool,
+}
+
+impl Config {
+	/// From `ezno.toml`, else `tsconfig.json`, in the current directory. `None` if there are
+	/// neither
+	pub fn load(fs: &impl crate::ReadFromFS) -> Result<Option<Self>, String> {
+		let read = |path: &str| {
+			fs.read_file(Path::new(path))
+				.map(|content| String::from_utf8_lossy(&content).into_owned())
+		};
+		if let Some(source) = read(CONFIG_FILE) {
+			Self::from_toml(&source).map(Some)
+		} else if let Some(source) = read(TSCONFIG_FILE) {
+			Self::from_tsconfig(&source).map(Some)
+		} else {
+			Ok(None)
+		}
+	}
+
+	pub fn from_toml(source: &str) -> Result<Self, String> {
+		toml::from_str(source).map_err(|err| format!("Invalid {CONFIG_FILE}: {err}"))
+	}
+
+	/// Reads `files` and `include` (as entry points, `.d.ts` files as definition files) and
+	/// `baseUrl`, `paths`, `lib`, `strict` (as [`CheckConfig::strict_casts`]), `noUnusedLocals`,
+	/// `preserveSymlinks` and `declaration` from `compilerOptions`. Other options are ignored.
+	/// Directories in `include` are expanded with the globs, by
+	/// [`crate::file_system::get_entry_points`]
+	pub fn from_tsconfig(source: &str) -> Result<Self, String> {
+		let invalid = |err: serde_json::Error| format!("Invalid {TSCONFIG_FILE}: {err}");
+		let tsconfig: serde_json::Value =
+			serde_json::from_str(&strip_json_comments(source)).map_err(invalid)?;
+
+		let strings = |value: &serde_json::Value| {
+			value
+				.as_array()
+				.map(|items| {
+					items
+						.iter()
+						.filter_map(serde_json::Value::as_str)
+						.map(ToOwned::to_owned)
+						.collect::<Vec<_>>()
+				})
+				.unwrap_or_default()
+		};
+
+		let mut config = Self::default();
+		for file in strings(&tsconfig["files"]).into_iter().chain(strings(&tsconfig["include"])) {
+			if file.ends_with(".d.ts") {
+				config.definition_files.push(file.into());
+			} else {
+				config.entry.push(file.into());
+			}
+		}
+
+		let options = &tsconfig["compilerOptions"];
+		let flag = |name: &str| options[name].as_bool().unwrap_or_default();
+		config.check.strict_casts = flag("strict");
+		config.check.no_unused_bindings = flag("noUnusedLocals");
+		config.check.preserve_symlinks = flag("preserveSymlinks");
+		config.build.declaration = flag("declaration");
+		config.lib = strings(&options["lib"]);
+
+		let base = Path::new(options["baseUrl"].as_str().unwrap_or("."));
+		if let Some(paths) = options["paths"].as_object() {
+			for (pattern, targets) in paths {
+				let targets = strings(targets)
+					.into_iter()
+					.map(|target| base.join(target).to_string_lossy().into_owned())
+					.collect();
+				config.paths.insert(pattern.clone(), targets);
+			}
+		}
+
+		Ok(config)
+	}
+
+	/// For [`checker::TypeCheckOptions::paths`]
+	#[must_use]
+	pub fn paths(&self) -> Vec<(String, Vec<String>)> {
+		self.paths.iter().map(|(pattern, targets)| (pattern.clone(), targets.clone())).collect()
+	}
+
+	/// The definition files of the libraries in `lib` and from the CLI (which come first). Errors on
+	/// unknown names
+	pub fn lib_definition_files(&self, from_cli: &[Lib]) -> Result<Vec<PathBuf>, String> {
+		let mut paths = from_cli.iter().map(|lib| lib.path()).collect::<Vec<_>>();
+		for name in &self.lib {
+			paths.push(parse_lib(name)?.path());
+		}
+		Ok(paths)
+	}
+
+	/// Adds `(pattern, target)` aliases from the CLI. They replace the targets of the same pattern in
+	/// the file, and a pattern that is repeated has each of its targets (in order)
+	pub fn add_paths(&mut self, aliases: Vec<(String, String)>) {
+		let mut replaced = Vec::new();
+		for (pattern, target) in aliases {
+			let targets = self.paths.entry(pattern.clone()).or_default();
+			if !replaced.contains(&pattern) {
+				targets.clear();
+				replaced.push(pattern);
+			}
+			targets.push(target);
+		}
+	}
+}
+
+/// Splits `pattern=target` (from the CLI)
+pub fn parse_path_alias(alias: &str) -> Result<(String, String), String> {
+	alias
+		.split_once('=')
+		.map(|(pattern, target)| (pattern.trim().to_owned(), target.trim().to_owned()))
+		.ok_or_else(|| format!("expected 'pattern=target' path alias, found '{alias}'"))
+}
+
+/// The name of a built-in library (from the CLI)
+pub fn parse_lib(name: &str) -> Result<Lib, String> {
+	Lib::from_name(name).ok_or_else(|| {
+		let names = Lib::ALL.map(Lib::name).join(", ");
+		format!("unknown library '{name}', expected one of {names} or esnext")
+	})
+}
+
+/// A single item or a list of items
+fn one_or_many<'de, D: serde::Deserializer<'de>, T: Deserialize<'de>>(
+	deserializer: D,
+) -> Result<Vec<T>, D::Error> {
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum OneOrMany<T> {
+		One(T),
+		Many(Vec<T>),
+	}
+
+	Ok(match OneOrMany::deserialize(deserializer)? {
+		OneOrMany::One(item) => vec![item],
+		OneOrMany::Many(items) => items,
+	})
+}
+
+/// `tsconfig.json` allows comments and trailing commas
+fn strip_json_comments(source: &str) -> String {
+	let mut without_comments = String::with_capacity(source.len());
+	let mut chars = source.chars().peekable();
+	while let Some(chr) = chars.next() {
+		match (chr, chars.peek()) {
+			('"', _) => {
+				without_comments.push(chr);
+				push_rest_of_string(&mut chars, &mut without_comments);
+			}
+			('/', Some('/')) => while chars.next_if(|chr| *chr != '\n').is_some() {},
+			('/', Some('*')) => {
+				chars.next();
+				let mut last = None;
+				for chr in chars.by_ref() {
+					if last == Some('*') && chr == '/' {
+						break;
+					}
+					last = Some(chr);
+				}
+			}
+			_ => without_comments.push(chr),
+		}
+	}
+
+	let mut result = String::with_capacity(without_comments.len());
+	let mut chars = without_comments.chars();
+	while let Some(chr) = chars.next() {
+		match chr {
+			'"' => {
+				result.push(chr);
+				push_rest_of_string(&mut chars, &mut result);
+			}
+			// Trailing if the next character (that is not whitespace) closes the object or array
+			',' if matches!(chars.clone().find(|chr| !chr.is_whitespace()), Some('}' | ']')) => {}
+			_ => result.push(chr),
+		}
+	}
+	result
+}
+
+/// Up to and including the closing quote
+fn push_rest_of_string(chars: &mut impl Iterator<Item = char>, result: &mut String) {
+	while let Some(chr) = chars.next() {
+		result.push(chr);
+		match chr {
+			'\\' => result.extend(chars.next()),
+			'"' => return,
+			_ => {}
+		}
+	}
+}
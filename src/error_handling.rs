@@ -8,10 +8,13 @@ use codespan_reporting::{
 use parser::source_map::FileSystem;
 
 pub(crate) fn emit_ezno_diagnostic(
-	diagnostic: checker::Diagnostic,
+	mut diagnostic: checker::Diagnostic,
 	fs: &impl FileSystem,
 ) -> Result<(), codespan_reporting::files::Error> {
-	let diagnostic = match diagnostic {
+	// Before translating as codes are found from the message
+	let code = diagnostic.code();
+	crate::locale::translate_diagnostic(&mut diagnostic);
+	let mut diagnostic = match diagnostic {
 		checker::Diagnostic::Global { reason, kind } => Diagnostic {
 			severity: ezno_diagnostic_to_severity(&kind),
 			code: None,
@@ -45,6 +48,11 @@ pub(crate) fn emit_ezno_diagnostic(
 		}
 	};
 
+	if let Some(code) = code {
+		diagnostic.code = Some(code.to_owned());
+		diagnostic.notes.push(format!("run `ezno explain {code}` for more information"));
+	}
+
 	emit_diagnostic(&diagnostic, &fs.into_code_span_store())
 }
 
@@ -66,7 +74,8 @@ fn emit_diagnostic<'files, F: Files<'files>>(
 
 	let config = Config::default();
 
-	let mut buffer = Buffer::ansi();
+	let mut buffer =
+		if crate::utilities::colors_enabled() { Buffer::ansi() } else { Buffer::no_color() };
 	emit(&mut buffer, &config, files, diagnostic).unwrap();
 	let output = String::from_utf8(buffer.into_inner()).expect("invalid string from diagnostic");
 	print_to_cli(format_args!("{output}"));
@@ -82,7 +91,9 @@ fn emit_diagnostic<'files, F: Files<'files>>(
 
 	let config = Config::default();
 
-	let writer = StandardStream::stderr(ColorChoice::Auto);
+	let color_choice =
+		if crate::utilities::colors_enabled() { ColorChoice::Always } else { ColorChoice::Never };
+	let writer = StandardStream::stderr(color_choice);
 	let mut lock = writer.lock();
 	emit(&mut lock, &config, files, diagnostic)
 }
@@ -0,0 +1,250 @@
+//! Implementations of [`ReadFromFS`]. Closures also implement it (with default `exists` etc)
+
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+};
+
+use crate::ReadFromFS;
+
+pub use checker::FileMetadata;
+
+/// Reads from disk
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+#[cfg(not(target_family = "wasm"))]
+impl ReadFromFS for RealFileSystem {
+	fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+		std::fs::read(long_path(path)).ok()
+	}
+
+	fn exists(&self, path: &Path) -> bool {
+		long_path(path).exists()
+	}
+
+	fn read_directory(&self, path: &Path) -> Option<Vec<PathBuf>> {
+		let entries = std::fs::read_dir(long_path(path)).ok()?;
+		Some(entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect())
+	}
+
+	fn metadata(&self, path: &Path) -> Option<FileMetadata> {
+		let metadata = std::fs::metadata(long_path(path)).ok()?;
+		Some(FileMetadata {
+			is_directory: metadata.is_dir(),
+			size: metadata.len(),
+			modified: metadata.modified().ok(),
+		})
+	}
+
+	/// Paths that do not exist are left as is
+	fn canonicalize(&self, path: &Path) -> PathBuf {
+		std::fs::canonicalize(long_path(path)).unwrap_or_else(|_| path.to_path_buf())
+	}
+}
+
+/// Paths over `MAX_PATH` need the verbatim prefix (which also requires `\` separators) to be opened
+#[cfg(target_family = "windows")]
+fn long_path(path: &Path) -> PathBuf {
+	const MAX_PATH: usize = 260;
+
+	let path_string = path.to_string_lossy();
+	if path.is_absolute() && path_string.len() >= MAX_PATH && !path_string.starts_with(r"\\?\") {
+		if let Some(unc) = path_string.strip_prefix(r"\\") {
+			format!(r"\\?\UNC\{}", unc.replace('/', "\\")).into()
+		} else {
+			format!(r"\\?\{}", path_string.replace('/', "\\")).into()
+		}
+	} else {
+		path.to_path_buf()
+	}
+}
+
+#[cfg(all(not(target_family = "windows"), not(target_family = "wasm")))]
+fn long_path(path: &Path) -> &Path {
+	path
+}
+
+/// In-memory files (for example unsaved editor buffers) on top of another file system
+pub struct OverlayFileSystem<T: ReadFromFS> {
+	base: T,
+	overlays: HashMap<PathBuf, String>,
+}
+
+impl<T: ReadFromFS> OverlayFileSystem<T> {
+	#[must_use]
+	pub fn new(base: T) -> Self {
+		Self { base, overlays: HashMap::new() }
+	}
+
+	/// Replaces the content of the file at `path` (or adds it if it does not exist in the base)
+	pub fn set_overlay(&mut self, path: PathBuf, content: String) {
+		self.overlays.insert(path, content);
+	}
+
+	/// Reverts to the content in the base
+	pub fn remove_overlay(&mut self, path: &Path) -> Option<String> {
+		self.overlays.remove(path)
+	}
+
+	pub fn overlaid_paths(&self) -> impl Iterator<Item = &Path> {
+		self.overlays.keys().map(PathBuf::as_path)
+	}
+}
+
+impl<T: ReadFromFS> ReadFromFS for OverlayFileSystem<T> {
+	fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+		if let Some(content) = self.overlays.get(path) {
+			Some(content.clone().into_bytes())
+		} else {
+			self.base.read_file(path)
+		}
+	}
+
+	fn exists(&self, path: &Path) -> bool {
+		self.overlays.contains_key(path) || self.base.exists(path)
+	}
+
+	fn read_directory(&self, path: &Path) -> Option<Vec<PathBuf>> {
+		let base = self.base.read_directory(path);
+		let mut overlaid =
+			self.overlays.keys().filter(|overlay| overlay.parent() == Some(path)).peekable();
+
+		if base.is_none() && overlaid.peek().is_none() {
+			return None;
+		}
+
+		let mut entries = base.unwrap_or_default();
+		for overlay in overlaid {
+			if !entries.contains(overlay) {
+				entries.push(overlay.clone());
+			}
+		}
+		Some(entries)
+	}
+
+	fn metadata(&self, path: &Path) -> Option<FileMetadata> {
+		if let Some(content) = self.overlays.get(path) {
+			Some(FileMetadata { is_directory: false, size: content.len() as u64, modified: None })
+		} else {
+			self.base.metadata(path)
+		}
+	}
+
+	/// Overlaid paths are not resolved, as they may not exist in the base
+	fn canonicalize(&self, path: &Path) -> PathBuf {
+		if self.overlays.contains_key(path) {
+			path.to_path_buf()
+		} else {
+			self.base.canonicalize(path)
+		}
+	}
+}
+
+/// Calls `on_read` with each path that is read (and the content, `None` if it could not be read).
+/// Otherwise the same as `base` (unlike wrapping it in a closure, which loses its `canonicalize` etc)
+pub struct RecordingFileSystem<'a, T: ReadFromFS, F: Fn(&Path, Option<&[u8]>)> {
+	base: &'a T,
+	on_read: F,
+}
+
+impl<'a, T: ReadFromFS, F: Fn(&Path, Option<&[u8]>)> RecordingFileSystem<'a, T, F> {
+	pub fn new(base: &'a T, on_read: F) -> Self {
+		Self { base, on_read }
+	}
+}
+
+impl<T: ReadFromFS, F: Fn(&Path, Option<&[u8]>)> ReadFromFS for RecordingFileSystem<'_, T, F> {
+	fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+		let content = self.base.read_file(path);
+		(self.on_read)(path, content.as_deref());
+		content
+	}
+
+	fn exists(&self, path: &Path) -> bool {
+		self.base.exists(path)
+	}
+
+	fn read_directory(&self, path: &Path) -> Option<Vec<PathBuf>> {
+		self.base.read_directory(path)
+	}
+
+	fn metadata(&self, path: &Path) -> Option<FileMetadata> {
+		self.base.metadata(path)
+	}
+
+	fn canonicalize(&self, path: &Path) -> PathBuf {
+		self.base.canonicalize(path)
+	}
+}
+
+/// Expands `input` if it is a glob (see [`checker::features::modules::glob_matches`]) by walking
+/// directories with [`ReadFromFS::read_directory`]. So works the same under WASM given a file system
+/// that can list directories. `node_modules` is only walked if the pattern mentions it. A directory
+/// is expanded to the source files (excluding definition files) under it, like `include` in a
+/// `tsconfig.json`. Sorted
+pub fn get_entry_points(input: &Path, fs: &impl ReadFromFS) -> Result<Vec<PathBuf>, String> {
+	fn walk(
+		directory: &Path,
+		fs: &impl ReadFromFS,
+		include_node_modules: bool,
+		files: &mut Vec<PathBuf>,
+	) -> Option<()> {
+		for entry in fs.read_directory(directory)? {
+			let is_node_modules = entry.file_name().is_some_and(|name| name == "node_modules");
+			if is_node_modules && !include_node_modules {
+				continue;
+			}
+			// Not everything supports `metadata`, so directories are entries that can be listed
+			if walk(&entry, fs, include_node_modules, files).is_none() {
+				files.push(entry);
+			}
+		}
+		Some(())
+	}
+
+	const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+	let mut pattern = input.to_string_lossy().replace('\\', "/");
+	let is_glob_part = |part: &str| part.contains(['*', '?']);
+	let is_directory = !is_glob_part(&pattern) && fs.read_directory(input).is_some();
+	if is_directory {
+		pattern = format!("{}/**/*", pattern.trim_end_matches('/'));
+	} else if !is_glob_part(&pattern) {
+		return Ok(vec![input.to_path_buf()]);
+	}
+
+	let base =
+		pattern.split('/').take_while(|part| !is_glob_part(part)).collect::<Vec<_>>().join("/");
+	let base = match base.as_str() {
+		"" if pattern.starts_with('/') => "/",
+		"" => ".",
+		base => base,
+	};
+
+	let mut files = Vec::new();
+	walk(Path::new(base), fs, pattern.contains("node_modules"), &mut files)
+		.ok_or_else(|| format!("cannot expand '{pattern}', could not list '{base}'"))?;
+
+	let mut entry_points = files
+		.into_iter()
+		.filter(|file| {
+			let path = file.to_string_lossy().replace('\\', "/");
+			let path = if base == "." { path.strip_prefix("./").unwrap_or(&path) } else { &path };
+			checker::features::modules::glob_matches(pattern.as_bytes(), path.as_bytes())
+		})
+		.filter(|file| {
+			!is_directory
+				|| (file.extension().is_some_and(|extension| {
+					SOURCE_EXTENSIONS.iter().any(|source| extension == *source)
+				}) && !file.to_string_lossy().ends_with(".d.ts"))
+		})
+		.collect::<Vec<_>>();
+
+	if entry_points.is_empty() {
+		return Err(format!("no files match '{pattern}'"));
+	}
+	entry_points.sort();
+	Ok(entry_points)
+}
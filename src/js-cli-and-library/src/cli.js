@@ -1,7 +1,7 @@
 #!/usr/bin/env node
 
 import { initSync, run_cli } from "../build/ezno_lib.js";
-import { readFileSync, writeFileSync } from "node:fs";
+import { readdirSync, readFileSync, writeFileSync } from "node:fs";
 
 const wasmPath = new URL("./shared/ezno_lib_bg.wasm", import.meta.url);
 if (wasmPath.protocol === "https:") {
@@ -17,6 +17,14 @@ function readFile(path) {
     return readFileSync(path).toString();
 }
 
+function readDirectory(path) {
+    try {
+        return readdirSync(path);
+    } catch {
+        return undefined;
+    }
+}
+
 function writeFile(path, content) {
     writeFileSync(path, content)
 }
@@ -30,4 +38,9 @@ function readFromCLI(prompt_msg) {
     }
 }
 
-run_cli(cliArguments, readFile, writeFile, readFromCLI);
+const exitCode = run_cli(cliArguments, readFile, writeFile, readFromCLI, readDirectory);
+if (onDeno) {
+    Deno.exit(exitCode);
+} else {
+    process.exitCode = exitCode;
+}
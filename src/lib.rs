@@ -1,16 +1,26 @@
 mod ast_explorer;
 mod build;
+mod bundle;
 mod check;
 mod error_handling;
+mod progress;
 mod repl;
+#[cfg(not(target_family = "wasm"))]
+mod watch;
 
 pub(crate) mod utilities;
 
+pub mod bench;
 pub mod cli;
+pub mod config;
+pub mod file_system;
+pub mod lint;
+pub mod locale;
+pub mod reporting;
 pub mod transformers;
 
-pub use build::build;
-pub use check::check;
+pub use build::{build, BuildConfig, BuildOutput, EznoParsePostCheckVisitors, FailedBuildOutput};
+pub use check::{check, check_with_visitors};
 pub use checker::{Diagnostic, DiagnosticKind};
 
 pub use parser::{source_map, ASTNode, ToStringOptions};
@@ -21,18 +31,8 @@ pub fn prettifier(input: String) -> Result<String, ParseError> {
 	Ok(module.to_string(&ToStringOptions::default()))
 }
 
-pub trait ReadFromFS {
-	fn get_content_at_path(&self, path: &std::path::Path) -> Option<String>;
-}
-
-impl<T> ReadFromFS for T
-where
-	T: Fn(&std::path::Path) -> Option<String>,
-{
-	fn get_content_at_path(&self, path: &std::path::Path) -> Option<String> {
-		(self)(path)
-	}
-}
+/// A (possibly virtual) file system, shared with the checker. See [`file_system`] for implementations.
+pub use checker::ReadFromFS;
 
 /// prompt -> response
 pub trait CLIInputResolver: Fn(&str) -> Option<String> {}
@@ -0,0 +1,209 @@
+//! Type aware lint rules. Rules are run over the AST of each checked module (after checking) and can
+//! query the types of expressions through [`RuleContext`].
+//!
+//! Rules need the expression type mappings, so checking should be run with
+//! [`checker::TypeCheckOptions::store_expression_type_mappings`]. [`no_floating_promises`] and
+//! [`no_unchecked_indexed_access`] are the built in rules
+
+use checker::{
+	synthesis::EznoParser, types::TypeStore, CheckOutput, Diagnostic, DiagnosticKind,
+	DiagnosticsContainer, Instance, Type, TypeId,
+};
+use parser::{
+	source_map::FileSystem,
+	visiting::{BlockItem, Chain, VisitOptions, VisitorReceiver},
+	ASTNode, Expression, SourceId, Span, Statement, StatementOrDeclaration,
+};
+
+pub trait Rule {
+	/// Added to the end of diagnostics from this rule
+	fn name(&self) -> &'static str;
+
+	#[allow(unused_variables)]
+	fn check_expression(&mut self, expression: &Expression, context: &mut RuleContext) {}
+
+	#[allow(unused_variables)]
+	fn check_statement(&mut self, statement: &Statement, context: &mut RuleContext) {}
+}
+
+/// Checker results for the module being linted
+pub struct RuleContext<'a> {
+	output: &'a CheckOutput<EznoParser>,
+	source: SourceId,
+	rule: &'static str,
+	diagnostics: &'a mut DiagnosticsContainer,
+}
+
+impl RuleContext<'_> {
+	#[must_use]
+	pub fn types(&self) -> &TypeStore {
+		&self.output.types
+	}
+
+	/// `None` if the expression was not checked
+	#[must_use]
+	pub fn get_type_of(&self, expression: &Expression) -> Option<TypeId> {
+		self.get_instance(expression.get_position()).map(Instance::get_value_on_ref)
+	}
+
+	/// The variable an identifier expression references
+	#[must_use]
+	pub fn get_variable_of(&self, expression: &Expression) -> Option<checker::VariableId> {
+		self.get_instance(expression.get_position()).and_then(Instance::get_variable_id)
+	}
+
+	/// Printed as a TypeScript annotation
+	#[must_use]
+	pub fn print_type(&self, ty: TypeId) -> String {
+		self.output.types.type_to_ts_string(
+			ty,
+			&self.output.top_level_information,
+			checker::types::printing::TypeToStringOptions::default(),
+		)
+	}
+
+	/// Adds a warning
+	pub fn report(&mut self, message: &str, position: Span) {
+		self.diagnostics.add_warning(Diagnostic::Position {
+			reason: format!("{message} ({})", self.rule),
+			position: position.with_source(self.source),
+			kind: DiagnosticKind::Warning,
+		});
+	}
+
+	fn get_instance(&self, position: Span) -> Option<&Instance> {
+		self.output
+			.modules
+			.get(&self.source)?
+			.mappings
+			.expressions_to_instances
+			.get_exact(position.start..position.end)
+	}
+}
+
+/// Runs `rules` over every checked module. The diagnostics are in source order
+pub fn lint(output: &CheckOutput<EznoParser>, rules: &mut [Box<dyn Rule>]) -> DiagnosticsContainer {
+	let mut diagnostics = DiagnosticsContainer::new();
+	let mut sources = output.modules.keys().copied().collect::<Vec<_>>();
+	sources.sort_unstable_by_key(|source| output.module_contents.get_file_path(*source));
+
+	for source in sources {
+		let module = &output.modules[&source].content;
+		let mut visitor = RuleVisitor { rules: &mut *rules, output, diagnostics: &mut diagnostics };
+		module.visit(&mut visitor, &mut (), &VisitOptions::default(), source);
+	}
+	// The visitor does not visit items in the order they are written
+	diagnostics.sort(&output.module_contents);
+	diagnostics
+}
+
+struct RuleVisitor<'a, 'b> {
+	rules: &'b mut [Box<dyn Rule>],
+	output: &'a CheckOutput<EznoParser>,
+	diagnostics: &'b mut DiagnosticsContainer,
+}
+
+impl RuleVisitor<'_, '_> {
+	fn run(&mut self, source: SourceId, mut check: impl FnMut(&mut dyn Rule, &mut RuleContext)) {
+		for rule in self.rules.iter_mut() {
+			let mut context = RuleContext {
+				output: self.output,
+				source,
+				rule: rule.name(),
+				diagnostics: &mut *self.diagnostics,
+			};
+			check(rule.as_mut(), &mut context);
+		}
+	}
+}
+
+impl VisitorReceiver<()> for RuleVisitor<'_, '_> {
+	fn visit_expression(&mut self, expression: &Expression, _data: &mut (), chain: &Chain) {
+		self.run(chain.get_module(), |rule, context| rule.check_expression(expression, context));
+	}
+
+	fn visit_statement(&mut self, item: BlockItem, _data: &mut (), chain: &Chain) {
+		let statement = match item {
+			BlockItem::StatementOrDeclaration(StatementOrDeclaration::Statement(statement))
+			| BlockItem::SingleStatement(statement) => statement,
+			BlockItem::StatementOrDeclaration(_) => return,
+		};
+		self.run(chain.get_module(), |rule, context| rule.check_statement(statement, context));
+	}
+}
+
+/// Promises that are not awaited, returned or stored (as an expression statement). Errors in them
+/// are not handled. `void` marks a promise as intentionally not awaited. Promises with a rejection
+/// handler and results of `allowed_functions` are allowed. The same as
+/// [`checker::TypeCheckOptions::no_floating_promises`]
+#[must_use]
+pub fn no_floating_promises(allowed_functions: Vec<String>) -> Box<dyn Rule> {
+	struct NoFloatingPromises {
+		allowed_functions: Vec<String>,
+	}
+
+	impl Rule for NoFloatingPromises {
+		fn name(&self) -> &'static str {
+			"no-floating-promises"
+		}
+
+		fn check_statement(&mut self, statement: &Statement, context: &mut RuleContext) {
+			let Statement::Expression(expression) = statement else {
+				return;
+			};
+			let parser::expressions::MultipleExpression::Single(single) = expression else {
+				return;
+			};
+			let Some(ty) = context.get_type_of(single) else {
+				return;
+			};
+			if checker::synthesis::statements::is_floating_promise(
+				expression,
+				ty,
+				context.types(),
+				&self.allowed_functions,
+			) {
+				context.report("Promise is not awaited", expression.get_position());
+			}
+		}
+	}
+
+	Box::new(NoFloatingPromises { allowed_functions })
+}
+
+/// Index access with a key that is not known (for example `array[idx]`) could be `undefined` if
+/// there is no property under the key. Keys that are constants (or unions of constants) that are
+/// all properties of the object are known (see [`CheckOutput::is_known_key`])
+#[must_use]
+pub fn no_unchecked_indexed_access() -> Box<dyn Rule> {
+	struct NoUncheckedIndexedAccess;
+
+	impl Rule for NoUncheckedIndexedAccess {
+		fn name(&self) -> &'static str {
+			"no-unchecked-indexed-access"
+		}
+
+		fn check_expression(&mut self, expression: &Expression, context: &mut RuleContext) {
+			let Expression::Index { indexee, indexer, position, is_optional: false } = expression
+			else {
+				return;
+			};
+			let parser::expressions::MultipleExpression::Single(indexer) = &**indexer else {
+				return;
+			};
+			let (Some(on), Some(key)) =
+				(context.get_type_of(indexee), context.get_type_of(indexer))
+			else {
+				return;
+			};
+			let is_constant = matches!(context.types().get_type_by_id(key), Type::Constant(_));
+			if !is_constant && !context.output.is_known_key(context.source, on, key) {
+				let message =
+					format!("Indexing with {} could be undefined", context.print_type(key));
+				context.report(&message, *position);
+			}
+		}
+	}
+
+	Box::new(NoUncheckedIndexedAccess)
+}
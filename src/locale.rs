@@ -0,0 +1,101 @@
+//! Translated diagnostic messages, from `--locale <file>`.
+//!
+//! A locale file is a JSON object from (English) diagnostic messages to their translation. `{}` in
+//! a message matches any text, which is placed at `{0}`, `{1}`, ... in the translation
+//!
+//! ```json
+//! { "Type {} is not assignable to type {}": "Le type {0} n'est pas assignable au type {1}" }
+//! ```
+
+use std::{collections::BTreeMap, sync::RwLock};
+
+static MESSAGE_CATALOG: RwLock<Option<MessageCatalog>> = RwLock::new(None);
+
+pub struct MessageCatalog {
+	/// Message split at each `{}` and the translation
+	messages: Vec<(Vec<String>, String)>,
+}
+
+impl MessageCatalog {
+	pub fn from_json(source: &str) -> Result<Self, String> {
+		let messages: BTreeMap<String, String> =
+			serde_json::from_str(source).map_err(|err| format!("Invalid locale file: {err}"))?;
+		let mut messages = messages
+			.into_iter()
+			.map(|(message, translation)| {
+				(message.split("{}").map(ToOwned::to_owned).collect::<Vec<_>>(), translation)
+			})
+			.collect::<Vec<_>>();
+		// Messages with more fixed text are more specific, so are tried first
+		messages.sort_by_key(|(parts, _)| {
+			std::cmp::Reverse(parts.iter().map(String::len).sum::<usize>())
+		});
+		Ok(Self { messages })
+	}
+
+	/// Returns `None` if there is no translation for `message`
+	#[must_use]
+	pub fn translate(&self, message: &str) -> Option<String> {
+		self.messages.iter().find_map(|(parts, translation)| {
+			let holes = match_message(parts, message)?;
+			Some(fill_holes(translation, &holes))
+		})
+	}
+}
+
+/// Used by the diagnostics emitted by the CLI
+pub(crate) fn set_message_catalog(catalog: Option<MessageCatalog>) {
+	*MESSAGE_CATALOG.write().unwrap() = catalog;
+}
+
+/// Translates the reason and labels of `diagnostic` with the catalog from [`set_message_catalog`]
+pub(crate) fn translate_diagnostic(diagnostic: &mut checker::Diagnostic) {
+	if let Some(catalog) = MESSAGE_CATALOG.read().unwrap().as_ref() {
+		diagnostic.map_messages(|message| {
+			if let Some(translation) = catalog.translate(message) {
+				*message = translation;
+			}
+		});
+	}
+}
+
+/// The text under each `{}` if `message` matches
+fn match_message<'a>(parts: &[String], message: &'a str) -> Option<Vec<&'a str>> {
+	let (first, rest) = parts.split_first()?;
+	let mut remaining = message.strip_prefix(first.as_str())?;
+	let mut holes = Vec::new();
+	for (idx, part) in rest.iter().enumerate() {
+		let end = if idx + 1 == rest.len() {
+			// The last part has to end the message
+			remaining.strip_suffix(part.as_str())?.len()
+		} else {
+			remaining.find(part.as_str())?
+		};
+		holes.push(&remaining[..end]);
+		remaining = &remaining[end + part.len()..];
+	}
+	remaining.is_empty().then_some(holes)
+}
+
+/// Replaces `{0}`, `{1}`, ... in `translation`. Other braces are left as is
+fn fill_holes(translation: &str, holes: &[&str]) -> String {
+	let mut result = String::new();
+	let mut rest = translation;
+	while let Some(start) = rest.find('{') {
+		result.push_str(&rest[..start]);
+		rest = &rest[start..];
+		let hole = rest.find('}').and_then(|end| {
+			let idx = rest[1..end].parse::<usize>().ok()?;
+			holes.get(idx).map(|hole| (end, *hole))
+		});
+		if let Some((end, hole)) = hole {
+			result.push_str(hole);
+			rest = &rest[end + 1..];
+		} else {
+			result.push('{');
+			rest = &rest[1..];
+		}
+	}
+	result.push_str(rest);
+	result
+}
@@ -14,8 +14,11 @@
 )]
 #![warn(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 
-use ezno_lib::cli::run_cli;
-use std::io;
+use ezno_lib::{
+	cli::{run_cli, CLIOutcome},
+	file_system::RealFileSystem,
+};
+use std::{io, process::ExitCode};
 
 #[cfg(target_family = "windows")]
 pub(crate) fn cli_input_resolver(prompt: &str) -> String {
@@ -38,17 +41,20 @@ pub(crate) fn cli_input_resolver(prompt: &str) -> String {
 	input
 }
 
-fn main() {
-	fn read_from_file(path: &std::path::Path) -> Option<String> {
-		std::fs::read_to_string(path).ok()
-	}
-
+fn main() -> ExitCode {
 	fn write_to_file(path: &std::path::Path, content: String) {
+		if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+			std::fs::create_dir_all(parent).unwrap();
+		}
 		std::fs::write(path, content).unwrap();
 	}
 
 	let arguments = std::env::args().skip(1).collect::<Vec<_>>();
 	let arguments = arguments.iter().map(String::as_str).collect::<Vec<_>>();
 
-	run_cli(&arguments, &read_from_file, write_to_file, |p| Some(cli_input_resolver(p)));
+	// The panic message is still printed by the default hook
+	let outcome = std::panic::catch_unwind(|| {
+		run_cli(&arguments, &RealFileSystem, write_to_file, |p| Some(cli_input_resolver(p)))
+	});
+	outcome.unwrap_or(CLIOutcome::InternalError).into()
 }
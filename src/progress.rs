@@ -0,0 +1,136 @@
+//! A single, redrawn status line on stderr while files are being read and modules are checked
+
+use std::path::Path;
+
+#[cfg(not(target_family = "wasm"))]
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+
+use checker::{ModuleEvent, ModuleProgress};
+
+#[cfg(not(target_family = "wasm"))]
+const REDRAW_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Clones share the same line
+#[derive(Clone)]
+pub(crate) struct Progress {
+	#[cfg(not(target_family = "wasm"))]
+	state: Option<Arc<State>>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+struct State {
+	action: &'static str,
+	start: Instant,
+	counts: Mutex<Counts>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+struct Counts {
+	last_draw: Instant,
+	files: usize,
+	checked: usize,
+	last_file: PathBuf,
+	/// Modules being synthesised. Imported modules are synthesised during their importer, so the
+	/// current one is last
+	modules: Vec<PathBuf>,
+}
+
+impl Progress {
+	/// Only displays if `enabled` and stderr is a terminal (so piped and CI output is left alone)
+	pub(crate) fn new(action: &'static str, enabled: bool) -> Self {
+		#[cfg(not(target_family = "wasm"))]
+		{
+			let enabled = enabled && std::io::IsTerminal::is_terminal(&std::io::stderr());
+			let state = enabled.then(|| {
+				let now = Instant::now();
+				let counts = Counts {
+					last_draw: now,
+					files: 0,
+					checked: 0,
+					last_file: PathBuf::new(),
+					modules: Vec::new(),
+				};
+				Arc::new(State { action, start: now, counts: Mutex::new(counts) })
+			});
+			Self { state }
+		}
+		#[cfg(target_family = "wasm")]
+		{
+			let _ = (action, enabled);
+			Self {}
+		}
+	}
+
+	/// Called each time a file is requested
+	pub(crate) fn on_file(&self, path: &Path) {
+		#[cfg(not(target_family = "wasm"))]
+		if let Some(ref state) = self.state {
+			let mut counts = state.counts.lock().unwrap();
+			counts.files += 1;
+			path.clone_into(&mut counts.last_file);
+			state.draw(&mut counts);
+		}
+		#[cfg(target_family = "wasm")]
+		let _ = path;
+	}
+
+	/// For [`checker::TypeCheckOptions::module_progress`], so that the line shows the module being
+	/// checked (rather than stopping while modules are synthesised)
+	pub(crate) fn module_progress(&self) -> ModuleProgress {
+		let progress = self.clone();
+		ModuleProgress::new(move |event| progress.on_module(event))
+	}
+
+	fn on_module(&self, event: ModuleEvent) {
+		#[cfg(not(target_family = "wasm"))]
+		if let Some(ref state) = self.state {
+			let mut counts = state.counts.lock().unwrap();
+			match event {
+				ModuleEvent::Started(path) => counts.modules.push(path.to_path_buf()),
+				ModuleEvent::Finished(_) => {
+					counts.modules.pop();
+					counts.checked += 1;
+				}
+			}
+			state.draw(&mut counts);
+		}
+		#[cfg(target_family = "wasm")]
+		let _ = event;
+	}
+
+	/// Clears the line so that diagnostics start on a fresh line
+	pub(crate) fn finish(&self) {
+		#[cfg(not(target_family = "wasm"))]
+		if self.state.is_some() {
+			eprint!("\r\x1b[2K");
+		}
+	}
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl State {
+	fn draw(&self, counts: &mut Counts) {
+		let now = Instant::now();
+		if now.duration_since(counts.last_draw) < REDRAW_INTERVAL {
+			return;
+		}
+		counts.last_draw = now;
+
+		let line = format!(
+			"{} {} files, {} checked ({:.1}s) {}",
+			self.action,
+			counts.files,
+			counts.checked,
+			self.start.elapsed().as_secs_f32(),
+			counts.modules.last().unwrap_or(&counts.last_file).display()
+		);
+		// Truncate to avoid wrapping, which breaks clearing the line
+		let width = console::Term::stderr().size().1 as usize;
+		let line: String = line.chars().take(width.saturating_sub(1)).collect();
+		eprint!("\r\x1b[2K{line}");
+	}
+}
@@ -37,7 +37,9 @@ pub(crate) fn run_repl<U: crate::CLIInputResolver>(
 	cli_input_resolver: U,
 	ReplArguments { const_as_let, type_definition_module }: ReplArguments,
 ) {
-	print_to_cli(format_args!("Entering REPL. Exit with `close()`"));
+	print_to_cli(format_args!(
+		"Entering REPL. Exit with `close()`. `:type <expression>` prints the type of an expression"
+	));
 
 	let definitions = if let Some(tdm) = type_definition_module {
 		std::iter::once(tdm).collect()
@@ -73,6 +75,13 @@ pub(crate) fn run_repl<U: crate::CLIInputResolver>(
 			continue;
 		};
 
+		// `:type <expression>` prints the type of the expression as an annotation
+		let (input, type_only) = if let Some(rest) = input.trim_start().strip_prefix(":type ") {
+			(rest.to_owned(), true)
+		} else {
+			(input, false)
+		};
+
 		let (from_index, _) = state.get_fs_mut().append_to_file(source, &input);
 
 		let options = Default::default();
@@ -108,7 +117,7 @@ pub(crate) fn run_repl<U: crate::CLIInputResolver>(
 			);
 		}
 
-		let result = state.check_item(&item);
+		let result = if type_only { state.check_item_type(&item) } else { state.check_item(&item) };
 
 		match result {
 			Ok((last_ty, diagnostics)) => {
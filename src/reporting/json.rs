@@ -0,0 +1,42 @@
+use checker::Diagnostic;
+use parser::source_map::{FileSystem, MapFileStore, WithPathMap};
+
+/// An array of diagnostics with their message, severity, code, file and start and end positions
+/// (byte offset and one based line and column)
+pub fn diagnostics_to_json(
+	diagnostics: impl IntoIterator<Item = Diagnostic>,
+	files: &MapFileStore<WithPathMap>,
+) -> String {
+	let diagnostics = diagnostics
+		.into_iter()
+		.map(|mut diagnostic| {
+			let code = diagnostic.code();
+			crate::locale::translate_diagnostic(&mut diagnostic);
+			let severity = match diagnostic.kind() {
+				checker::DiagnosticKind::Error => "error",
+				checker::DiagnosticKind::Warning => "warning",
+				checker::DiagnosticKind::Info => "info",
+			};
+			let mut value = serde_json::json!({
+				"message": diagnostic.reason(),
+				"severity": severity,
+				"code": code,
+			});
+			if let Some(position) = diagnostic.position() {
+				let content = files.get_file_content(position.source);
+				let location = |offset: u32| {
+					let offset = (offset as usize).min(content.len());
+					let (line, line_start) = super::line_of(&content, offset);
+					let column = offset - line_start + 1;
+					serde_json::json!({ "offset": offset, "line": line, "column": column })
+				};
+				value["file"] = files.get_file_path(position.source).display().to_string().into();
+				value["start"] = location(position.start);
+				value["end"] = location(position.end);
+			}
+			value
+		})
+		.collect::<Vec<_>>();
+
+	serde_json::to_string_pretty(&diagnostics).unwrap()
+}
@@ -0,0 +1,72 @@
+//! Machine readable output of diagnostics, for `--reporter` on `check` and `build`
+
+pub mod json;
+pub mod sarif;
+
+use std::str::FromStr;
+
+use checker::DiagnosticsContainer;
+use parser::source_map::{MapFileStore, WithPathMap};
+
+use crate::{error_handling::emit_ezno_diagnostic, utilities::print_to_cli};
+
+/// How diagnostics are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reporter {
+	/// Rendered with the source for a terminal
+	#[default]
+	Terminal,
+	/// See [`json::diagnostics_to_json`]
+	Json,
+	/// See [`sarif::diagnostics_to_sarif`]
+	Sarif,
+}
+
+impl Reporter {
+	pub(crate) fn report(
+		self,
+		diagnostics: DiagnosticsContainer,
+		files: &MapFileStore<WithPathMap>,
+	) {
+		match self {
+			Self::Terminal => {
+				for diagnostic in diagnostics {
+					emit_ezno_diagnostic(diagnostic, files).unwrap();
+				}
+			}
+			Self::Json => {
+				print_to_cli(format_args!("{}", json::diagnostics_to_json(diagnostics, files)));
+			}
+			Self::Sarif => {
+				print_to_cli(format_args!("{}", sarif::diagnostics_to_sarif(diagnostics, files)));
+			}
+		}
+	}
+}
+
+impl FromStr for Reporter {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"terminal" => Ok(Self::Terminal),
+			"json" => Ok(Self::Json),
+			"sarif" => Ok(Self::Sarif),
+			s => Err(format!("expected 'terminal', 'json' or 'sarif', found '{s}'")),
+		}
+	}
+}
+
+/// One based line and the byte offset of the start of that line. `\r\n` and a lone `\r` are both
+/// a single line break
+pub(crate) fn line_of(content: &str, offset: usize) -> (usize, usize) {
+	let bytes = content.as_bytes();
+	let (mut line, mut line_start) = (1, 0);
+	for (idx, byte) in bytes[..offset].iter().enumerate() {
+		if *byte == b'\n' || (*byte == b'\r' && bytes.get(idx + 1) != Some(&b'\n')) {
+			line += 1;
+			line_start = idx + 1;
+		}
+	}
+	(line, line_start)
+}
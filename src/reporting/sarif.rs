@@ -0,0 +1,133 @@
+//! [SARIF 2.1](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) output, which
+//! can be uploaded to GitHub code scanning
+
+use std::{collections::BTreeMap, path::Path};
+
+use checker::{Diagnostic, DiagnosticKind};
+use parser::source_map::{FileSystem, MapFileStore, SpanWithSource, WithPathMap};
+use serde_json::{json, Value};
+
+/// A SARIF log with one run. The rule id of a diagnostic is its explanation code (e.g. `EZ1001`),
+/// else one of `error`, `warning` or `info` from its kind
+pub fn diagnostics_to_sarif(
+	diagnostics: impl IntoIterator<Item = Diagnostic>,
+	files: &MapFileStore<WithPathMap>,
+) -> String {
+	let current_directory = std::env::current_dir().ok();
+	let location = |position: SpanWithSource| {
+		let path = files.get_file_path(position.source);
+		let path = current_directory
+			.as_deref()
+			.and_then(|directory| path.strip_prefix(directory).ok())
+			.unwrap_or(path.as_path());
+		json!({
+			"physicalLocation": {
+				"artifactLocation": { "uri": path_to_uri(path) },
+				"region": region(&files.get_file_content(position.source), position),
+			}
+		})
+	};
+
+	// Ordered so the output is stable
+	let mut rules = BTreeMap::new();
+	let results = diagnostics
+		.into_iter()
+		.map(|mut diagnostic| {
+			let kind = diagnostic.kind();
+			let rule_id = diagnostic.code().unwrap_or(match kind {
+				DiagnosticKind::Error => "error",
+				DiagnosticKind::Warning => "warning",
+				DiagnosticKind::Info => "info",
+			});
+			rules.entry(rule_id).or_insert_with(|| rule(rule_id));
+
+			crate::locale::translate_diagnostic(&mut diagnostic);
+			let level = match kind {
+				DiagnosticKind::Error => "error",
+				DiagnosticKind::Warning => "warning",
+				DiagnosticKind::Info => "note",
+			};
+			let mut result = json!({
+				"ruleId": rule_id,
+				"level": level,
+				"message": { "text": diagnostic.reason() },
+			});
+			if let Some(position) = diagnostic.position() {
+				result["locations"] = json!([location(position)]);
+			}
+			if let Diagnostic::PositionWithAdditionalLabels { labels, .. } = &diagnostic {
+				let related = labels
+					.iter()
+					.filter_map(|(message, position)| {
+						let mut related = location((*position)?);
+						related["message"] = json!({ "text": message });
+						Some(related)
+					})
+					.collect::<Vec<_>>();
+				if !related.is_empty() {
+					result["relatedLocations"] = Value::Array(related);
+				}
+			}
+			result
+		})
+		.collect::<Vec<_>>();
+
+	let log = json!({
+		"$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+		"version": "2.1.0",
+		"runs": [{
+			"tool": {
+				"driver": {
+					"name": "ezno",
+					"version": env!("CARGO_PKG_VERSION"),
+					"informationUri": env!("CARGO_PKG_REPOSITORY"),
+					"rules": rules.into_values().collect::<Vec<_>>(),
+				}
+			},
+			"columnKind": "utf16CodeUnits",
+			"results": results,
+		}]
+	});
+	serde_json::to_string_pretty(&log).unwrap()
+}
+
+fn rule(id: &str) -> Value {
+	if let Some(explanation) = checker::explanations::get_explanation(id) {
+		json!({
+			"id": id,
+			"shortDescription": { "text": explanation.title },
+			"fullDescription": { "text": explanation.explanation },
+		})
+	} else {
+		json!({ "id": id, "shortDescription": { "text": format!("Type checking {id}") } })
+	}
+}
+
+/// Lines and columns are one based. Columns are in UTF-16 code units
+fn region(content: &str, position: SpanWithSource) -> Value {
+	let location = |offset: u32| {
+		let offset = (offset as usize).min(content.len());
+		let (line, line_start) = super::line_of(content, offset);
+		(line, content[line_start..offset].encode_utf16().count() + 1)
+	};
+	let (start_line, start_column) = location(position.start);
+	let (end_line, end_column) = location(position.end);
+	json!({
+		"startLine": start_line,
+		"startColumn": start_column,
+		"endLine": end_line,
+		"endColumn": end_column,
+		"byteOffset": position.start,
+		"byteLength": position.end.saturating_sub(position.start),
+	})
+}
+
+/// Relative paths are left relative (to the directory it is run in)
+fn path_to_uri(path: &Path) -> String {
+	let path = path.to_string_lossy().replace('\\', "/");
+	if path.starts_with('/') {
+		format!("file://{path}")
+	} else {
+		path
+	}
+}
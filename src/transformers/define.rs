@@ -0,0 +1,91 @@
+use parser::{
+	expressions::PropertyReference,
+	visiting::{Chain, VisitorMut},
+	ASTNode, Expression, ParseOptions,
+};
+
+/// A transformer that replaces identifiers and member expressions (e.g. `DEBUG`,
+/// `process.env.NODE_ENV` or `import.meta.env.MODE`) with the constant expression they are defined as. Run before checking
+/// so that the checker (and so the optimisations) can see branches that are never taken.
+///
+/// TODO does not look at scope, so local variables with the same name are also replaced
+pub struct Define {
+	/// (path, source of replacement)
+	definitions: Vec<(Vec<String>, String)>,
+}
+
+impl Define {
+	/// From `name`, `value` pairs. Errors if `name` is not a (dotted) identifier or if `value` is
+	/// not an expression
+	pub fn new(definitions: &[(String, String)]) -> Result<Self, String> {
+		let definitions = definitions
+			.iter()
+			.map(|(name, value)| {
+				let path = name.split('.').map(ToOwned::to_owned).collect::<Vec<_>>();
+				if !path.iter().all(|part| is_identifier(part)) {
+					return Err(format!("cannot define '{name}', expected identifier or member"));
+				}
+				Expression::from_string(value.clone(), ParseOptions::default()).map_err(|err| {
+					format!("cannot define '{name}', invalid value: {}", err.reason)
+				})?;
+				Ok((path, value.clone()))
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self { definitions })
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.definitions.is_empty()
+	}
+}
+
+/// Splits `name=value` (from the CLI)
+pub fn parse_definition(definition: &str) -> Result<(String, String), String> {
+	definition
+		.split_once('=')
+		.map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+		.ok_or_else(|| format!("expected 'name=value' definition, found '{definition}'"))
+}
+
+impl<T> VisitorMut<Expression, T> for Define {
+	fn visit_mut(&mut self, item: &mut Expression, _data: &mut T, _chain: &Chain) {
+		let Some((_, value)) = self.definitions.iter().find(|(path, _)| matches_path(item, path))
+		else {
+			return;
+		};
+
+		// Parsed with the offset so that positions point at the replaced expression
+		let offset = item.get_position().start;
+		if let Ok((replacement, _)) = Expression::from_string_with_options(
+			value.clone(),
+			ParseOptions::default(),
+			Some(offset),
+		) {
+			*item = replacement;
+		}
+	}
+}
+
+fn matches_path(expression: &Expression, path: &[String]) -> bool {
+	match (expression, path) {
+		(Expression::VariableReference(name, _), [identifier]) => name == identifier,
+		(Expression::ImportMeta(_), [import, meta]) => import == "import" && meta == "meta",
+		(
+			Expression::PropertyAccess {
+				parent,
+				property: PropertyReference::Standard { property, is_private: false },
+				..
+			},
+			[parent_path @ .., last],
+		) => property == last && matches_path(parent, parent_path),
+		_ => false,
+	}
+}
+
+fn is_identifier(name: &str) -> bool {
+	let mut chars = name.chars();
+	chars.next().is_some_and(|first| first.is_alphabetic() || matches!(first, '_' | '$'))
+		&& chars.all(|chr| chr.is_alphanumeric() || matches!(chr, '_' | '$'))
+}
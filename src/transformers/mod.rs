@@ -1,4 +1,6 @@
+pub mod define;
 pub mod optimisations;
+pub mod type_imports;
 
 use parser::{visiting::BlockItemMut, Declaration, Module, StatementOrDeclaration};
 
@@ -114,6 +114,8 @@ impl VisitorMut<BlockItemMut<'_>, CheckingOutputWithoutDiagnostics> for Statemen
 				| parser::Declaration::Interface(_)
 				| parser::Declaration::TypeAlias(_)
 				| parser::Declaration::DeclareVariable(_)
+				| parser::Declaration::DeclareGlobal(_)
+				| parser::Declaration::DeclareModule(_)
 				| parser::Declaration::Namespace(_) => {}
 			}
 		}
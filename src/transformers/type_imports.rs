@@ -0,0 +1,134 @@
+//! Types have no value at runtime, so imports and exports of them are removed from the output
+//! (otherwise the output would reference modules that only contain types)
+
+use checker::features::modules::Exported;
+use parser::{
+	declarations::{
+		export::{ExportDeclaration, ExportPart, Exportable},
+		import::{ImportExportName, ImportPart, ImportedItems},
+		ImportLocation,
+	},
+	source_map::FileSystem,
+	Declaration, Module, SourceId, StatementOrDeclaration, VariableIdentifier,
+};
+
+use crate::build::CheckingOutputWithoutDiagnostics;
+
+/// Removes
+/// - `import type ...` and `export type { ... } from ...`
+/// - exported interfaces and type aliases
+/// - imported and exported names that are only types (in checked modules). Imports with nothing left
+///   are removed, rather than becoming imports for side effects
+///
+/// `paths` are the aliases used when checking (see [`checker::TypeCheckOptions::paths`]) and `fs` is
+/// the file system that was checked (for resolving symlinks)
+pub fn remove_type_only_imports_and_exports(
+	module: &mut Module,
+	source: SourceId,
+	data: &CheckingOutputWithoutDiagnostics,
+	fs: &impl crate::ReadFromFS,
+	paths: &[(String, Vec<String>)],
+) {
+	let path = data.module_contents.get_file_path(source);
+	let exported_from = |specifier: &str| {
+		let source = crate::bundle::resolve(&path, specifier, &data.module_contents, fs, paths)?;
+		data.modules.get(&source).map(|module| &module.exported)
+	};
+	let own_exports = data.modules.get(&source).map(|module| &module.exported);
+
+	module.items.retain_mut(|item| {
+		let StatementOrDeclaration::Declaration(declaration) = item else { return true };
+		match declaration {
+			Declaration::Import(import) => {
+				if import.is_type_annotation_import_only {
+					return false;
+				}
+				let ImportLocation::Quoted(ref specifier, _) = import.from else { return true };
+				let Some(exported) = exported_from(specifier) else { return true };
+				let ImportedItems::Parts(Some(ref mut parts)) = import.items else { return true };
+				// `import {} from "./a"` is kept for its side effects
+				if parts.is_empty() {
+					return true;
+				}
+				parts.retain(|part| {
+					!imported_name(part).is_some_and(|name| is_only_type(exported, name))
+				});
+				if parts.is_empty() {
+					import.items = ImportedItems::Parts(None);
+					import.default.is_some()
+				} else {
+					true
+				}
+			}
+			Declaration::Export(export) => {
+				let ExportDeclaration::Variable { ref mut exported, .. } = export.on else {
+					return true;
+				};
+				match exported {
+					Exportable::Interface(_)
+					| Exportable::TypeAlias(_)
+					| Exportable::ImportParts { type_definitions_only: true, .. } => false,
+					Exportable::ImportParts {
+						parts,
+						from: ImportLocation::Quoted(specifier, _),
+						..
+					} => {
+						let Some(exported) = exported_from(specifier) else { return true };
+						parts.retain(|part| {
+							!exported_part_names(part)
+								.is_some_and(|(name, _)| is_only_type(exported, name))
+						});
+						!parts.is_empty()
+					}
+					Exportable::Parts(parts) => {
+						let Some(exported) = own_exports else { return true };
+						parts.retain(|part| {
+							!exported_part_names(part)
+								.is_some_and(|(_, alias)| is_only_type(exported, alias))
+						});
+						!parts.is_empty()
+					}
+					_ => true,
+				}
+			}
+			_ => true,
+		}
+	});
+}
+
+/// Exported as a type but not as a variable (classes are both)
+fn is_only_type(exported: &Exported, name: &str) -> bool {
+	exported.named_types.iter().any(|(exported, _)| exported == name)
+		&& !exported.named.iter().any(|(exported, _)| exported == name)
+}
+
+/// The name of the part in the module it is imported from
+fn imported_name(part: &ImportPart) -> Option<&str> {
+	match part {
+		ImportPart::Name(VariableIdentifier::Standard(name, _)) => Some(name),
+		ImportPart::NameWithAlias {
+			alias: ImportExportName::Reference(alias) | ImportExportName::Quoted(alias, _),
+			..
+		} => Some(alias),
+		ImportPart::PrefixComment(_, Some(part), _) | ImportPart::PostfixComment(part, _, _) => {
+			imported_name(part)
+		}
+		_ => None,
+	}
+}
+
+/// (name in this module, exported name)
+fn exported_part_names(part: &ExportPart) -> Option<(&str, &str)> {
+	match part {
+		ExportPart::Name(VariableIdentifier::Standard(name, _)) => Some((name, name)),
+		ExportPart::NameWithAlias {
+			name,
+			alias: ImportExportName::Reference(alias) | ImportExportName::Quoted(alias, _),
+			..
+		} => Some((name, alias)),
+		ExportPart::PrefixComment(_, Some(part), _) | ExportPart::PostfixComment(part, _, _) => {
+			exported_part_names(part)
+		}
+		_ => None,
+	}
+}
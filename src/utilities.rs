@@ -1,4 +1,11 @@
-use std::fmt::Arguments;
+use checker::{Chronometer, DiagnosticKind, DiagnosticsContainer};
+use parser::source_map::{FileSystem, MapFileStore, WithPathMap};
+use std::{
+	fmt::Arguments,
+	str::FromStr,
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 
 const SPONSORS_PATH: &str = "https://github.com/sponsors/kaleidawave";
 const SPONSORS: Option<&'static str> = option_env!("SPONSORS");
@@ -53,3 +60,138 @@ pub(crate) fn print_to_cli_without_newline(arguments: Arguments) {
 	print!("{arguments}");
 	io::Write::flush(&mut io::stdout()).unwrap();
 }
+
+/// Whether to use ANSI colors in diagnostics and other output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ColorChoice {
+	/// Decided by `NO_COLOR`, `FORCE_COLOR` and whether stderr is a terminal
+	#[default]
+	Auto,
+	Always,
+	Never,
+}
+
+impl FromStr for ColorChoice {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"auto" => Ok(Self::Auto),
+			"always" => Ok(Self::Always),
+			"never" => Ok(Self::Never),
+			s => Err(format!("expected 'auto', 'always' or 'never', found '{s}'")),
+		}
+	}
+}
+
+/// Which diagnostics make `check` and `build` exit with [`crate::cli::CLIOutcome::DiagnosticsFound`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ErrorOn {
+	Warnings,
+	#[default]
+	Errors,
+	Never,
+}
+
+impl ErrorOn {
+	/// From the `--error-on` option, else the `EZNO_ERROR_ON` environment variable
+	pub(crate) fn resolve(option: Option<Self>) -> Result<Self, String> {
+		match (option, std::env::var("EZNO_ERROR_ON")) {
+			(Some(error_on), _) => Ok(error_on),
+			(None, Ok(value)) if !value.is_empty() => {
+				value.parse().map_err(|reason| format!("Invalid EZNO_ERROR_ON: {reason}"))
+			}
+			(None, _) => Ok(Self::default()),
+		}
+	}
+
+	pub(crate) fn fails(self, diagnostics: &DiagnosticsContainer) -> bool {
+		match self {
+			Self::Warnings => diagnostics.iter().any(|diagnostic| {
+				matches!(diagnostic.kind(), DiagnosticKind::Error | DiagnosticKind::Warning)
+			}),
+			Self::Errors => diagnostics.has_error(),
+			Self::Never => false,
+		}
+	}
+}
+
+impl FromStr for ErrorOn {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"warnings" => Ok(Self::Warnings),
+			"errors" => Ok(Self::Errors),
+			"never" => Ok(Self::Never),
+			s => Err(format!("expected 'warnings', 'errors' or 'never', found '{s}'")),
+		}
+	}
+}
+
+/// For `--timings-json`. Durations are in milliseconds
+pub(crate) fn timings_to_json(
+	chronometer: &Chronometer,
+	files: &MapFileStore<WithPathMap>,
+) -> String {
+	let milliseconds = |duration: Duration| duration.as_secs_f64() * 1000.0;
+	let modules = chronometer
+		.modules
+		.iter()
+		.map(|module| {
+			serde_json::json!({
+				"path": files.get_file_path(module.source).display().to_string(),
+				"parse": milliseconds(module.parse),
+				"check": milliseconds(module.check),
+			})
+		})
+		.collect::<Vec<_>>();
+
+	let timings = serde_json::json!({
+		"stages": {
+			"definition_files": milliseconds(chronometer.definition_files),
+			"modules": milliseconds(chronometer.modules_total),
+		},
+		"modules": modules,
+		"types": chronometer.types,
+	});
+	serde_json::to_string_pretty(&timings).unwrap()
+}
+
+static COLORS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Resolves [`ColorChoice::Auto`] and sets it for diagnostics and [`console`] styling
+pub(crate) fn set_color_choice(choice: ColorChoice) {
+	let enabled = match choice {
+		ColorChoice::Always => true,
+		ColorChoice::Never => false,
+		ColorChoice::Auto => {
+			let is_set = |name| std::env::var(name).is_ok_and(|value| !value.is_empty());
+			if is_set("NO_COLOR") {
+				false
+			} else if is_set("FORCE_COLOR") {
+				std::env::var("FORCE_COLOR").is_ok_and(|value| value != "0")
+			} else {
+				stderr_is_terminal()
+			}
+		}
+	};
+	COLORS_ENABLED.store(enabled, Ordering::Relaxed);
+	console::set_colors_enabled(enabled);
+	console::set_colors_enabled_stderr(enabled);
+}
+
+pub(crate) fn colors_enabled() -> bool {
+	COLORS_ENABLED.load(Ordering::Relaxed)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn stderr_is_terminal() -> bool {
+	std::io::IsTerminal::is_terminal(&std::io::stderr())
+}
+
+/// Output is passed to JS which is assumed to handle ANSI codes
+#[cfg(target_family = "wasm")]
+fn stderr_is_terminal() -> bool {
+	true
+}
@@ -35,9 +35,9 @@ pub fn experimental_build_wasm(
 	let result = crate::build::build(
 		vec![entry_path.into()],
 		&fs_resolver,
-		None,
+		&[],
 		Path::new("out.js"),
-		&crate::build::BuildConfig { strip_whitespace: minify },
+		&crate::build::BuildConfig { strip_whitespace: minify, ..Default::default() },
 		None,
 	);
 
@@ -63,6 +63,24 @@ impl WASMCheckOutput {
 	pub fn get_type_at_position(&self, path: &str, pos: u32) -> String {
 		self.0.get_type_at_position(path, pos, false).unwrap_or_default()
 	}
+
+	/// Requires `store_expression_type_mappings` (from `check_with_options`)
+	pub fn get_semantic_tokens(&self, path: &str) -> JsValue {
+		let tokens = self.0.get_semantic_tokens(path).unwrap_or_default();
+		serde_wasm_bindgen::to_value(&tokens).unwrap()
+	}
+
+	/// Hints at call sites and for variables require `store_expression_type_mappings`
+	pub fn get_inlay_hints(&self, path: &str) -> JsValue {
+		let hints = self.0.get_inlay_hints(path).unwrap_or_default();
+		serde_wasm_bindgen::to_value(&hints).unwrap()
+	}
+
+	/// Properties after a `.` require `store_expression_type_mappings`
+	pub fn get_completions_at_position(&self, path: &str, pos: u32) -> JsValue {
+		let completions = self.0.get_completions_at_position(path, pos).unwrap_or_default();
+		serde_wasm_bindgen::to_value(&completions).unwrap()
+	}
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -82,7 +100,7 @@ pub fn check_wasm(entry_path: String, fs_resolver_js: &js_sys::Function) -> WASM
 	WASMCheckOutput(crate::check::check(
 		vec![entry_path.into()],
 		&fs_resolver,
-		None,
+		&[],
 		checker::TypeCheckOptions::default(),
 	))
 }
@@ -107,16 +125,47 @@ pub fn check_wasm_with_options(
 
 		res.ok().and_then(|res| res.as_string())
 	};
-	WASMCheckOutput(crate::check::check(vec![entry_path.into()], &fs_resolver, None, options))
+	WASMCheckOutput(crate::check::check(vec![entry_path.into()], &fs_resolver, &[], options))
 }
+/// Files from JS callbacks. Listing directories is optional (it is only needed for globs)
+struct JSFileSystem<'a> {
+	read_file: &'a js_sys::Function,
+	/// Returns the names of the entries in the directory
+	read_directory: Option<&'a js_sys::Function>,
+}
+
+impl crate::ReadFromFS for JSFileSystem<'_> {
+	fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+		let res =
+			self.read_file.call1(&JsValue::null(), &JsValue::from(path.display().to_string()));
+		res.ok().and_then(|res| res.as_string()).map(String::into_bytes)
+	}
+
+	fn read_directory(&self, path: &Path) -> Option<Vec<std::path::PathBuf>> {
+		let res = self
+			.read_directory?
+			.call1(&JsValue::null(), &JsValue::from(path.display().to_string()))
+			.ok()?;
+		let entries = res.dyn_into::<js_sys::Array>().ok()?;
+		Some(
+			entries
+				.iter()
+				.filter_map(|entry| entry.as_string())
+				.map(|name| path.join(name))
+				.collect(),
+		)
+	}
+}
+
 #[wasm_bindgen(typescript_custom_section)]
 const TYPES_RUN_CLI: &str = r#"
 export function run_cli(
 	cli_arguments: string[],
 	read_from_file: (path: string) => string | undefined,
 	write_to_file: (path: string, content: string) => void,
-	cli_input_resolver: (prompt: string) => string | undefined
-): void
+	cli_input_resolver: (prompt: string) => string | undefined,
+	read_directory?: (path: string) => string[] | undefined
+): number
 "#;
 #[wasm_bindgen(js_name = run_cli, skip_typescript)]
 pub fn run_cli_wasm(
@@ -124,17 +173,15 @@ pub fn run_cli_wasm(
 	read_from_file: &js_sys::Function,
 	write_to_file: &js_sys::Function,
 	cli_input_resolver_js: &js_sys::Function,
-) {
+	read_directory: Option<js_sys::Function>,
+) -> u8 {
 	std::panic::set_hook(Box::new(console_error_panic_hook::hook));
 
 	let arguments = cli_arguments.into_iter().flat_map(JsValue::as_string).collect::<Vec<_>>();
 	let arguments = arguments.iter().map(String::as_str).collect::<Vec<_>>();
 
-	let read_from_file = |path: &std::path::Path| {
-		let res =
-			read_from_file.call1(&JsValue::null(), &JsValue::from(path.display().to_string()));
-		res.ok().and_then(|res| res.as_string())
-	};
+	let read_from_file =
+		JSFileSystem { read_file: read_from_file, read_directory: read_directory.as_ref() };
 
 	let write_to_file = |path: &std::path::Path, content: String| {
 		write_to_file
@@ -154,7 +201,7 @@ pub fn run_cli_wasm(
 			.and_then(JsValue::as_string)
 	};
 
-	crate::run_cli(&arguments, &read_from_file, write_to_file, cli_input_resolver);
+	crate::run_cli(&arguments, &read_from_file, write_to_file, cli_input_resolver).exit_code()
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -0,0 +1,187 @@
+//! Re-running on file changes. Watches every file that was read in the last run (not just the
+//! entry points), so that newly added imports are picked up
+
+use std::{
+	collections::{HashMap, HashSet},
+	path::PathBuf,
+	sync::{
+		mpsc::{channel, Receiver, RecvTimeoutError},
+		Arc, Mutex,
+	},
+	time::Duration,
+};
+
+use checker::{CancellationToken, Diagnostic, DiagnosticKind};
+use notify::{EventKind, RecursiveMode, Watcher};
+use parser::source_map::{FileSystem, MapFileStore, SourceId, WithPathMap};
+
+/// Editors (and formatters on save) can write several times in quick succession
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// `run` returns the files that were read. If one of the files read in the last run changes while
+/// `run` is running, its [`CancellationToken`] is cancelled and it is run again straight after.
+/// Does not return unless the watcher stops
+pub(crate) fn watch_files(mut run: impl FnMut(CancellationToken) -> Vec<PathBuf>) {
+	let (sender, receiver) = channel();
+	let watched = Arc::new(Mutex::new(HashSet::<PathBuf>::new()));
+	let running = Arc::new(Mutex::new(CancellationToken::new()));
+
+	// Events are handled on the watcher's thread, so this can cancel a run
+	let handler = {
+		let (watched, running) = (watched.clone(), running.clone());
+		move |event: notify::Result<notify::Event>| {
+			if let Ok(ref event) = event {
+				if is_change(event, &watched.lock().unwrap()) {
+					running.lock().unwrap().cancel();
+				}
+			}
+			let _ = sender.send(event);
+		}
+	};
+	let mut watcher = match notify::recommended_watcher(handler) {
+		Ok(watcher) => watcher,
+		Err(err) => {
+			eprintln!("Could not start watching files: {err}");
+			return;
+		}
+	};
+
+	let mut previous = HashSet::<PathBuf>::new();
+	loop {
+		let cancellation = CancellationToken::new();
+		*running.lock().unwrap() = cancellation.clone();
+
+		// Canonical as events have absolute paths
+		let files: HashSet<PathBuf> = run(cancellation.clone())
+			.into_iter()
+			.filter_map(|path| std::fs::canonicalize(path).ok())
+			.collect();
+
+		for removed in previous.difference(&files) {
+			let _ = watcher.unwatch(removed);
+		}
+		// Re-add existing ones as editors that save by replacing the file drop the watch
+		for path in &files {
+			if let Err(err) = watcher.watch(path, RecursiveMode::NonRecursive) {
+				eprintln!("Could not watch {}: {err}", path.display());
+			}
+		}
+		watched.lock().unwrap().clone_from(&files);
+		previous = files;
+
+		let changed = if cancellation.is_cancelled() {
+			debounce(&receiver)
+		} else {
+			wait_for_change(&receiver, &previous)
+		};
+		if !changed {
+			return;
+		}
+	}
+}
+
+fn is_change(event: &notify::Event, watched: &HashSet<PathBuf>) -> bool {
+	matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_))
+		&& event.paths.iter().any(|path| watched.contains(path))
+}
+
+/// Returns `false` if the watcher has stopped
+fn wait_for_change(
+	receiver: &Receiver<notify::Result<notify::Event>>,
+	watched: &HashSet<PathBuf>,
+) -> bool {
+	loop {
+		match receiver.recv() {
+			Ok(Ok(event)) => {
+				if is_change(&event, watched) {
+					return debounce(receiver);
+				}
+			}
+			Ok(Err(err)) => {
+				eprintln!("Error watching files: {err}");
+			}
+			Err(_) => return false,
+		}
+	}
+}
+
+/// Drains the events that follow straight after. Returns `false` if the watcher has stopped
+fn debounce(receiver: &Receiver<notify::Result<notify::Event>>) -> bool {
+	loop {
+		match receiver.recv_timeout(DEBOUNCE) {
+			Ok(_) => {}
+			Err(RecvTimeoutError::Timeout) => return true,
+			Err(RecvTimeoutError::Disconnected) => return false,
+		}
+	}
+}
+
+/// Diagnostics from the previous run, so that only the ones that changed are printed
+#[derive(Default)]
+pub(crate) struct DiagnosticsDiff {
+	previous: Option<HashSet<String>>,
+}
+
+impl DiagnosticsDiff {
+	/// Prints new diagnostics (everything on the first run) and a summary of the fixed ones.
+	/// Returns the number of (errors, warnings)
+	pub(crate) fn emit(
+		&mut self,
+		diagnostics: Vec<Diagnostic>,
+		files: &MapFileStore<WithPathMap>,
+	) -> (usize, usize) {
+		let mut contents = HashMap::<SourceId, String>::new();
+		let mut current = HashSet::new();
+		let (mut errors, mut warnings) = (0, 0);
+
+		for diagnostic in diagnostics {
+			match diagnostic.kind() {
+				DiagnosticKind::Error => errors += 1,
+				DiagnosticKind::Warning => warnings += 1,
+				DiagnosticKind::Info => {}
+			}
+
+			let key = diagnostic_key(&diagnostic, files, &mut contents);
+			let is_new = self.previous.as_ref().map_or(true, |previous| !previous.contains(&key));
+			if is_new {
+				if self.previous.is_some() {
+					crate::utilities::print_to_cli(format_args!("[new] {key}"));
+				}
+				crate::error_handling::emit_ezno_diagnostic(diagnostic, files).unwrap();
+			}
+			current.insert(key);
+		}
+
+		if let Some(previous) = &self.previous {
+			for fixed in previous.difference(&current) {
+				crate::utilities::print_to_cli(format_args!("[fixed] {fixed}"));
+			}
+		}
+
+		self.previous = Some(current);
+		(errors, warnings)
+	}
+}
+
+/// `path:line:column reason`. Positions are one based
+fn diagnostic_key(
+	diagnostic: &Diagnostic,
+	files: &MapFileStore<WithPathMap>,
+	contents: &mut HashMap<SourceId, String>,
+) -> String {
+	let Some(position) = diagnostic.position() else {
+		return diagnostic.reason().to_owned();
+	};
+
+	let content =
+		contents.entry(position.source).or_insert_with(|| files.get_file_content(position.source));
+	let offset = (position.start as usize).min(content.len());
+	let (line, line_start) = crate::reporting::line_of(content, offset);
+	let column = offset - line_start + 1;
+
+	format!(
+		"{}:{line}:{column} {}",
+		files.get_file_path(position.source).display(),
+		diagnostic.reason()
+	)
+}
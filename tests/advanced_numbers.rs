@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+
+const MAIN: &str = "const Flags = { Read: 1, Write: 2, Execute: 4 };
+declare function getPermissions(): 1 | 3;
+
+function canExecute(mode: 1 | 2) {
+	return (mode & 4) !== 0;
+}
+
+const permissions = getPermissions();
+(permissions | Flags.Write) satisfies 3;
+(permissions & Flags.Write) satisfies 2;";
+
+#[test]
+fn flags() {
+	let options = TypeCheckOptions { advanced_numbers: true, ..Default::default() };
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+
+	assert_eq!(
+		reasons,
+		[
+			"1 | 2 has no bits in common with mask 4, so the result is always 0",
+			"Expected 2, found 0 | 2"
+		]
+	);
+}
+
+#[test]
+fn narrowing_from_mask() {
+	const MAIN: &str = "const enum Permission { Read = 1, Write = 2, Execute = 4 }
+
+function describe(flags: 1 | 3 | 5) {
+	if (flags & Permission.Write) {
+		flags satisfies 3;
+	} else {
+		flags satisfies 1 | 5;
+	}
+	if ((flags & Permission.Execute) === 0) {
+		flags satisfies 1;
+	}
+}";
+
+	let options = TypeCheckOptions { advanced_numbers: true, ..Default::default() };
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+
+	assert_eq!(reasons, ["Expected 1, found 1 | 3"]);
+}
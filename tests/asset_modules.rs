@@ -0,0 +1,43 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+
+const MAIN: &str = "import styles from \"./main.css\";
+import icon from \"./icon.svg?url\";
+import logo from \"./logo.png\";
+
+const a: string = styles;
+const b: number = icon;";
+
+#[test]
+fn asset_imports_have_configured_type() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let options = TypeCheckOptions {
+		asset_modules: vec![
+			("css".to_owned(), "string".to_owned()),
+			(".svg".to_owned(), "string".to_owned()),
+		],
+		..Default::default()
+	};
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+	// `.png` is not configured
+	assert_eq!(
+		reasons,
+		vec![
+			"Cannot find file".to_owned(),
+			"Type string is not assignable to type number".to_owned()
+		]
+	);
+}
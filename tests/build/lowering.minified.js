@@ -0,0 +1 @@
+const Direction=(()=>{const Up=0;const Down=Up+2;const Left="LEFT";return Object.freeze({Up,Down,Left,[Up]:"Up",[Down]:"Down"});})();const Flags=(()=>{const Read=1;const Write=Read+1;return Object.freeze({Read,Write});})();function first(items,fallback){return items[0]??fallback}const point={x:1};const pair=[1,2];const value=first([point.x,pair[0]]);class Counter{count=0;increment(){return ++this.count}}console.log(value,Direction.Down,Direction[0],Flags.Write,new Counter().increment())
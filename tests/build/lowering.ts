@@ -0,0 +1,21 @@
+interface Point { x: number }
+type Pair = [number, number];
+declare const external: string;
+
+enum Direction { Up, Down = Up + 2, Left = "LEFT" }
+const enum Flags { Read = 1, Write }
+
+function first<T>(items: Array<T>, fallback?: T): T | undefined {
+    return items[0] ?? fallback
+}
+
+const point: Point = { x: 1 };
+const pair = [1, 2] satisfies Pair;
+const value = first<number>([point.x, pair[0]]);
+
+class Counter {
+    count: number = 0;
+    increment(): number { return ++this.count }
+}
+
+console.log(value, Direction.Down, Direction[0], Flags.Write, new Counter().increment());
@@ -0,0 +1,13 @@
+function used(a) {
+	return a * 2
+}
+function unused(a) {
+	return a * 3
+}
+const unusedArrow = a => a * 4;
+const object = { used() {
+	return 1
+}, unused() {
+	return 2
+} };
+console.log(used(2), object.used())
@@ -0,0 +1,9 @@
+function used(a) {
+	return a * 2
+}
+;
+const unusedArrow = null;
+const object = { used() {
+	return 1
+}, unused: null };
+console.log(used(2), object.used())
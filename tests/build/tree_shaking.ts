@@ -0,0 +1,16 @@
+function used(a: number) {
+    return a * 2
+}
+
+function unused(a: number) {
+    return a * 3
+}
+
+const unusedArrow = (a: number) => a * 4;
+
+const object = {
+    used() { return 1 },
+    unused() { return 2 }
+};
+
+console.log(used(2), object.used());
@@ -0,0 +1,3 @@
+const a = 2;
+const b = a + 1
+//# sourceMappingURL=output.js.map
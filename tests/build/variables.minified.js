@@ -0,0 +1 @@
+const a=2;const b=a+1
@@ -0,0 +1,2 @@
+const a: number = 2;
+const b = a + 1;
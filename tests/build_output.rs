@@ -0,0 +1,462 @@
+//! Builds each `tests/build/*.ts` fixture with several configurations and compares the emitted
+//! JavaScript with `<name>.<configuration>.js` (and source map with `.js.map` when not empty).
+//! TypeScript syntax is lowered (removed) in every configuration.
+//! See [`common::check_snapshot`] for updating snapshots
+
+mod common;
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use ezno_lib::{
+	build,
+	transformers::optimisations::{ExpressionOptimiser, StatementOptimiser},
+	BuildConfig, BuildOutput, EznoParsePostCheckVisitors, FailedBuildOutput,
+};
+
+/// (name, minify, tree shake, source maps)
+const CONFIGURATIONS: &[(&str, bool, bool, bool)] = &[
+	("default", false, false, false),
+	("minified", true, false, false),
+	("optimised", false, true, false),
+	("mapped", false, false, true),
+];
+
+#[test]
+fn build_output() {
+	let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/build");
+
+	let mut fixtures = fs::read_dir(&directory)
+		.unwrap()
+		.map(|entry| entry.unwrap().path())
+		.filter(|path| path.extension().is_some_and(|ext| ext == "ts"))
+		.collect::<Vec<_>>();
+	fixtures.sort();
+
+	let mut failures = Vec::new();
+	for fixture in fixtures {
+		let content = fs::read_to_string(&fixture).unwrap();
+		let name = fixture.file_stem().unwrap().to_str().unwrap();
+
+		for &(configuration, minify, optimise, source_maps) in CONFIGURATIONS {
+			let read_file = |path: &Path| (path == fixture).then(|| content.clone());
+
+			let transformers = optimise.then(|| EznoParsePostCheckVisitors {
+				expression_visitors_mut: vec![Box::new(ExpressionOptimiser)],
+				statement_visitors_mut: vec![Box::new(StatementOptimiser)],
+				variable_visitors_mut: Default::default(),
+				block_visitors_mut: Default::default(),
+			});
+
+			let output = build(
+				vec![fixture.clone()],
+				&read_file,
+				&[],
+				&PathBuf::from("output.js"),
+				&BuildConfig { strip_whitespace: minify, source_maps, ..Default::default() },
+				transformers,
+			);
+
+			let output = match output {
+				Ok(BuildOutput { mut outputs, .. }) => outputs.remove(0),
+				Err(FailedBuildOutput { diagnostics, .. }) => {
+					let reasons = diagnostics
+						.into_iter()
+						.map(|diagnostic| diagnostic.reason().to_owned())
+						.collect::<Vec<_>>();
+					failures.push(format!("{name} ({configuration}) failed to build: {reasons:?}"));
+					continue;
+				}
+			};
+
+			let snapshot_path = directory.join(format!("{name}.{configuration}.js"));
+			if let Err(failure) = common::check_snapshot(&snapshot_path, &output.content) {
+				failures.push(failure);
+			}
+			if !output.mappings.is_empty() {
+				let snapshot_path = directory.join(format!("{name}.{configuration}.js.map"));
+				// Sources are absolute
+				let mappings = output.mappings.replace(&format!("{}/", directory.display()), "");
+				if let Err(failure) = common::check_snapshot(&snapshot_path, &mappings) {
+					failures.push(failure);
+				}
+			}
+		}
+	}
+
+	assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn bundle_with_split_chunks() {
+	let files = [
+		(
+			"/project/a.ts",
+			"import { shared } from \"./shared\";\nconst lazy = import(\"./lazy\");\nshared(1);",
+		),
+		("/project/b.ts", "import { shared } from \"./shared\";\nshared(2);"),
+		("/project/shared.ts", "export function shared(a: number) { return a }"),
+		("/project/lazy.ts", "export const value = 2;"),
+	];
+	let read_file = |path: &Path| {
+		files
+			.iter()
+			.find(|(name, _)| Path::new(name) == path)
+			.map(|(_, content)| (*content).to_owned())
+	};
+
+	let output = build(
+		vec![PathBuf::from("/project/a.ts"), PathBuf::from("/project/b.ts")],
+		&read_file,
+		&[],
+		&PathBuf::from("output"),
+		&BuildConfig { bundle: true, split: true, ..Default::default() },
+		None,
+	);
+
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("bundle failed to build") };
+	let names = outputs
+		.iter()
+		.map(|output| output.output_path.file_name().unwrap().to_str().unwrap().to_owned())
+		.collect::<Vec<_>>();
+
+	assert_eq!(&names[..2], ["a.js", "b.js"], "entry chunks first in {names:?}");
+	assert!(names.iter().any(|name| name.starts_with("lazy-")), "no dynamic chunk in {names:?}");
+	assert_eq!(names.last().map(String::as_str), Some("manifest.json"));
+
+	let shared_chunk = names
+		.iter()
+		.find(|name| name.starts_with("chunk-"))
+		.unwrap_or_else(|| panic!("no shared chunk in {names:?}"));
+	let entry = &outputs[0].content;
+	assert!(
+		entry.contains(&format!("\"./{shared_chunk}\"")),
+		"shared chunk not imported in {entry}"
+	);
+	assert!(!entry.contains("function shared"), "shared module duplicated into entry chunk");
+}
+
+#[test]
+fn define_replaces_before_checking() {
+	let path = PathBuf::from("/project/main.ts");
+	let content =
+		"if (process.env.NODE_ENV !== \"production\") {\n\tconsole.log(\"development\")\n}";
+	let read_file = |requested: &Path| (requested == path).then(|| content.to_owned());
+
+	let config = BuildConfig {
+		define: vec![("process.env.NODE_ENV".to_owned(), "\"production\"".to_owned())],
+		..Default::default()
+	};
+	let output =
+		build(vec![path.clone()], &read_file, &[], &PathBuf::from("output.js"), &config, None);
+
+	// `process` is not defined, so this only builds if replaced before checking
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("failed to build with define") };
+	let content = &outputs[0].content;
+	assert!(!content.contains("process"), "not replaced in {content}");
+	assert!(content.contains("\"production\" !== \"production\""), "not replaced in {content}");
+}
+
+#[test]
+fn define_replaces_import_meta() {
+	let path = PathBuf::from("/project/main.ts");
+	let content =
+		"if (import.meta.env.MODE === \"development\") {\n\tconsole.log(import.meta.url)\n}";
+	let read_file = |requested: &Path| (requested == path).then(|| content.to_owned());
+
+	let config = BuildConfig {
+		define: vec![("import.meta.env.MODE".to_owned(), "\"production\"".to_owned())],
+		..Default::default()
+	};
+	let output =
+		build(vec![path.clone()], &read_file, &[], &PathBuf::from("output.js"), &config, None);
+
+	// `env` is not on `ImportMeta`, so this only builds if replaced before checking
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("failed to build with define") };
+	let content = &outputs[0].content;
+	assert!(!content.contains("import.meta.env"), "not replaced in {content}");
+	assert!(content.contains("import.meta.url"), "{content}");
+}
+
+#[test]
+fn banner_footer_and_legal_comments() {
+	let path = PathBuf::from("/project/main.ts");
+	let content = "/*! @license MIT */\n/* not kept */\nconsole.log(\"hi\")";
+	let read_file = |requested: &Path| (requested == path).then(|| content.to_owned());
+
+	let config = BuildConfig {
+		strip_whitespace: true,
+		banner: Some("// banner".to_owned()),
+		footer: Some("// footer".to_owned()),
+		..Default::default()
+	};
+	let output =
+		build(vec![path.clone()], &read_file, &[], &PathBuf::from("output.js"), &config, None);
+
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("failed to build") };
+	let content = &outputs[0].content;
+	assert!(content.starts_with("// banner\n/*! @license MIT */"), "{content}");
+	assert!(content.ends_with("\n// footer"), "{content}");
+	assert!(!content.contains("not kept"), "{content}");
+}
+
+#[test]
+fn bundle_common_js_module() {
+	let files = [
+		(
+			"/project/main.ts",
+			"import legacy, { value } from \"./legacy.js\";\nconsole.log(legacy, value);",
+		),
+		("/project/legacy.js", "exports.value = 1;"),
+	];
+	let read_file = |path: &Path| {
+		files
+			.iter()
+			.find(|(name, _)| Path::new(name) == path)
+			.map(|(_, content)| (*content).to_owned())
+	};
+
+	let output = build(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[],
+		&PathBuf::from("output"),
+		&BuildConfig { bundle: true, ..Default::default() },
+		None,
+	);
+
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("bundle failed to build") };
+	let content = &outputs[0].content;
+	assert!(content.contains("(function (module, exports) {"), "module not wrapped in {content}");
+	assert!(content.contains("= __cjs_module_0.exports"), "import not linked in {content}");
+	assert!(!content.contains("import"), "import left in {content}");
+}
+
+#[test]
+fn bundle_orders_top_level_await_modules_last() {
+	let files = [
+		(
+			"/project/main.ts",
+			"import { config } from \"./config\";\nimport { log } from \"./log\";\nlog(config);",
+		),
+		("/project/config.ts", "export const config = await 2;"),
+		("/project/log.ts", "export function log(a: number) { return a }"),
+	];
+	let read_file = |path: &Path| {
+		files
+			.iter()
+			.find(|(name, _)| Path::new(name) == path)
+			.map(|(_, content)| (*content).to_owned())
+	};
+
+	let output = build(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[],
+		&PathBuf::from("output"),
+		&BuildConfig { bundle: true, ..Default::default() },
+		None,
+	);
+
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("bundle failed to build") };
+	let content = &outputs[0].content;
+	let (Some(log), Some(config)) = (content.find("function log"), content.find("await 2")) else {
+		panic!("modules missing from {content}")
+	};
+	// `log.ts` does not wait for `config.ts`
+	assert!(log < config, "module with top level await not last in {content}");
+}
+
+fn bundle_files(files: &[(&str, &str)], config: &BuildConfig) -> String {
+	let read_file = |path: &Path| {
+		files
+			.iter()
+			.find(|(name, _)| Path::new(name) == path)
+			.map(|(_, content)| (*content).to_owned())
+	};
+	let output = build(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[],
+		&PathBuf::from("output"),
+		config,
+		None,
+	);
+	let Ok(BuildOutput { mut outputs, .. }) = output else { panic!("bundle failed to build") };
+	outputs.remove(0).content
+}
+
+#[test]
+fn bundle_keeps_order_of_side_effect_imports() {
+	let files = [
+		(
+			"/project/main.ts",
+			"import \"./setup\";\nimport { value } from \"./value\";\nconsole.log(value);",
+		),
+		("/project/setup.ts", "console.log(\"setup\");\nawait 2;"),
+		("/project/value.ts", "export const value = 2;"),
+	];
+	let content = bundle_files(&files, &BuildConfig { bundle: true, ..Default::default() });
+
+	let (Some(setup), Some(value)) = (content.find("\"setup\""), content.find("value = 2")) else {
+		panic!("modules missing from {content}")
+	};
+	// `setup.ts` uses top level `await` but has side effects, so is not moved last
+	assert!(setup < value, "side effect import moved in {content}");
+}
+
+#[test]
+fn bundle_wraps_modules_with_colliding_names() {
+	let files = [
+		(
+			"/project/main.ts",
+			"import { a } from \"./a\";\nimport { b } from \"./b\";\nexport { b as bee } from \"./b\";\nexport const total = a + b;",
+		),
+		("/project/a.ts", "const helper = 1;\nexport const a = helper;"),
+		("/project/b.ts", "const helper = 2;\nexport const b = helper;"),
+	];
+	let content = bundle_files(&files, &BuildConfig { bundle: true, ..Default::default() });
+
+	assert_eq!(
+		content.matches("(function (module, exports) {").count(),
+		2,
+		"not wrapped in {content}"
+	);
+	assert!(content.contains("exports.a = a;"), "export not assigned in {content}");
+	assert!(content.contains("export const bee = "), "re-export not linked in {content}");
+	assert!(content.contains("export const total"), "root export removed in {content}");
+	assert!(!content.contains("export const a = helper"), "export left in {content}");
+}
+
+#[test]
+fn bundle_removes_exports_of_imported_modules() {
+	let files = [
+		("/project/main.ts", "import { a } from \"./a\";\nconsole.log(a);"),
+		("/project/a.ts", "export const a = 1;\nexport function unused() {}"),
+	];
+	let content = bundle_files(&files, &BuildConfig { bundle: true, ..Default::default() });
+
+	assert!(content.contains("const a = 1"), "module missing from {content}");
+	assert!(!content.contains("export"), "export left in {content}");
+	assert!(
+		!content.contains("(function (module, exports) {"),
+		"wrapped without collision in {content}"
+	);
+}
+
+#[test]
+fn bundle_resolves_path_aliases() {
+	let files = [
+		("/project/main.ts", "import { value } from \"@app/value\";\nconsole.log(value);"),
+		("/project/src/value.ts", "export const value = 2;"),
+	];
+	let paths = vec![("@app/*".to_owned(), vec!["/project/src/*".to_owned()])];
+	let content = bundle_files(&files, &BuildConfig { bundle: true, paths, ..Default::default() });
+
+	assert!(content.contains("value = 2"), "aliased module missing from {content}");
+	assert!(!content.contains("@app"), "aliased import left in {content}");
+}
+
+#[test]
+fn tree_shake_removes_side_effect_free_imports() {
+	let files = [
+		(
+			"/project/main.ts",
+			"import \"./unused\";\nimport \"./polyfill\";\nimport \"./lib/setup\";\nconsole.log(2);",
+		),
+		("/project/unused.ts", "const unused = 2;"),
+		("/project/polyfill.ts", "console.log(\"polyfill\");"),
+		// Has side effects, but the package says it does not
+		("/project/lib/setup.ts", "console.log(\"lib\");"),
+		("/project/lib/package.json", "{ \"sideEffects\": false }"),
+	];
+	let content =
+		bundle_files(&files, &BuildConfig { bundle: true, tree_shake: true, ..Default::default() });
+
+	assert!(content.contains("\"polyfill\""), "module with side effects removed from {content}");
+	assert!(!content.contains("unused"), "module without side effects left in {content}");
+	assert!(!content.contains("\"lib\""), "`sideEffects: false` module left in {content}");
+	assert!(!content.contains("import"), "import left in {content}");
+}
+
+#[test]
+fn type_only_imports_and_exports_removed() {
+	let files = [
+		(
+			"/project/main.ts",
+			"import type { Options } from \"./options\";\nimport { Point, origin } from \"./point\";\nimport { Shape } from \"./shape\";\nexport type { Options } from \"./options\";\nexport { Shape };\nconst point: Point = origin;\nconsole.log(point);",
+		),
+		("/project/options.ts", "export interface Options { a: number }"),
+		(
+			"/project/point.ts",
+			"export type Point = { x: number };\nexport const origin: Point = { x: 0 };",
+		),
+		("/project/shape.ts", "export interface Shape { sides: number }"),
+	];
+	let read_file = |path: &Path| {
+		files
+			.iter()
+			.find(|(name, _)| Path::new(name) == path)
+			.map(|(_, content)| (*content).to_owned())
+	};
+
+	let output = build(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[],
+		&PathBuf::from("output.js"),
+		&BuildConfig::default(),
+		None,
+	);
+
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("failed to build") };
+	// Each module is output
+	let content = outputs
+		.iter()
+		.map(|output| &output.content)
+		.find(|content| content.contains("console.log"))
+		.expect("no output for main.ts");
+	assert!(content.contains("import { origin } from \"./point\""), "{content}");
+	assert!(!content.contains("Point"), "type import left in {content}");
+	assert!(!content.contains("./options"), "type only module referenced in {content}");
+	assert!(!content.contains("Shape"), "type only module referenced in {content}");
+}
+
+#[test]
+fn declaration_file() {
+	let path = PathBuf::from("/project/main.ts");
+	let content = "export interface Point<T = number> { x: T }
+export type Pair = [number, number];
+export const origin: Point = { x: 0 };
+export function distance(a: Point, b?: Point): number { return a.x }
+export class Vector {
+	x: number;
+	constructor(x: number) { this.x = x }
+	length(): number { return this.x }
+}";
+	let read_file = |requested: &Path| (requested == path).then(|| content.to_owned());
+
+	let config = BuildConfig { declaration: true, ..Default::default() };
+	let output =
+		build(vec![path.clone()], &read_file, &[], &PathBuf::from("output.js"), &config, None);
+
+	let Ok(BuildOutput { outputs, .. }) = output else { panic!("failed to build") };
+	let declaration = outputs
+		.iter()
+		.find(|output| output.output_path == Path::new("output.d.ts"))
+		.expect("no declaration file");
+	let content = &declaration.content;
+
+	assert!(content.contains("export interface Point<T = number>"), "{content}");
+	assert!(content.contains("export type Pair = [number, number]"), "{content}");
+	assert!(content.contains("declare const origin: Point"), "{content}");
+	assert!(content.contains("declare function distance(a: Point"), "{content}");
+	assert!(content.contains("): number"), "{content}");
+	assert!(content.contains("declare class Vector"), "{content}");
+	assert!(content.contains("constructor(x: number)"), "{content}");
+	assert!(content.contains("length(): number"), "{content}");
+	assert!(content.contains("export { origin, distance, Vector }"), "{content}");
+	assert!(!content.contains("return"), "bodies left in {content}");
+}
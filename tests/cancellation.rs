@@ -0,0 +1,54 @@
+use std::path::{Path, PathBuf};
+
+use checker::{incremental::CheckCache, CancellationToken, TypeCheckOptions};
+
+const MAIN: &str = "import \"./other\";
+const a: string = 2;";
+
+/// Reading `other.ts` cancels the check, so `main.ts` is not checked after the import
+fn check(cancel_on_import: bool) -> Vec<String> {
+	let cancellation = CancellationToken::new();
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			"other.ts" => {
+				if cancel_on_import {
+					cancellation.cancel();
+				}
+				Some("export const b = 3;".to_owned())
+			}
+			_ => None,
+		}
+	};
+
+	let options = TypeCheckOptions { cancellation: cancellation.clone(), ..Default::default() };
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+	output.diagnostics.into_iter().map(|diagnostic| diagnostic.reason().to_owned()).collect()
+}
+
+#[test]
+fn cancelled_check_stops() {
+	assert_eq!(check(false), vec!["Type 2 is not assignable to type string".to_owned()]);
+	assert_eq!(check(true), Vec::<String>::new());
+}
+
+#[test]
+fn cancelled_check_is_marked_and_not_cached() {
+	let cancellation = CancellationToken::new();
+	cancellation.cancel();
+	let read_file = |path: &Path| (path == Path::new("main.ts")).then(|| MAIN.to_owned());
+
+	let options = TypeCheckOptions { cancellation, ..Default::default() };
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+	assert!(output.cancelled);
+	assert!(CheckCache::new(&output, &[]).is_none());
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	assert!(!output.cancelled);
+	assert!(CheckCache::new(&output, &[]).is_some());
+}
@@ -0,0 +1,32 @@
+use std::{fs, path::Path};
+
+/// Compares `output` with the file at `snapshot_path`.
+///
+/// With `UPDATE_SNAPSHOTS` set, the snapshot is (re)written. Missing snapshots are also written
+/// unless running under `CI`
+pub fn check_snapshot(snapshot_path: &Path, output: &str) -> Result<(), String> {
+	let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+	let in_ci = std::env::var_os("CI").is_some();
+
+	match fs::read_to_string(snapshot_path) {
+		Ok(snapshot) if !update => {
+			// Line endings can be changed by git on checkout
+			let snapshot = snapshot.replace("\r\n", "\n");
+			if snapshot.trim_end() == output.trim_end() {
+				Ok(())
+			} else {
+				Err(format!(
+					"{}:\n{}",
+					snapshot_path.display(),
+					pretty_assertions::StrComparison::new(snapshot.trim_end(), output.trim_end())
+				))
+			}
+		}
+		Err(_) if in_ci && !update => Err(format!("missing snapshot {}", snapshot_path.display())),
+		_ => {
+			fs::write(snapshot_path, format!("{}\n", output.trim_end())).unwrap();
+			eprintln!("Wrote {}", snapshot_path.display());
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use checker::{libraries::Lib, TypeCheckOptions};
+use ezno_lib::config::Config;
+
+#[test]
+fn from_toml() {
+	let config = Config::from_toml(
+		r#"
+entry = ["src/*.ts"]
+definition-file = "definitions.d.ts"
+
+[paths]
+"@/*" = ["src/*"]
+
+[check]
+no-unused-bindings = true
+skip-checking = ["vendor/**"]
+maximum-file-size = 1048576
+
+[build]
+minify = true
+define = { "process.env.NODE_ENV" = "\"production\"" }
+"#,
+	)
+	.unwrap();
+
+	assert_eq!(config.entry, vec![PathBuf::from("src/*.ts")]);
+	assert_eq!(config.definition_files, vec![PathBuf::from("definitions.d.ts")]);
+	assert_eq!(config.paths(), vec![("@/*".to_owned(), vec!["src/*".to_owned()])]);
+	assert!(config.check.no_unused_bindings);
+	assert!(!config.check.no_explicit_any);
+	assert_eq!(config.check.skip_checking, vec!["vendor/**".to_owned()]);
+	assert_eq!(config.check.maximum_file_size, Some(1024 * 1024));
+	assert!(config.build.minify);
+	assert_eq!(config.build.define["process.env.NODE_ENV"], "\"production\"");
+
+	assert!(Config::from_toml("unknown = true").is_err());
+
+	let config = Config::from_toml(r#"definition-files = ["dom.d.ts", "node.d.ts"]"#).unwrap();
+	assert_eq!(
+		config.definition_files,
+		vec![PathBuf::from("dom.d.ts"), PathBuf::from("node.d.ts")]
+	);
+}
+
+#[test]
+fn from_tsconfig() {
+	let config = Config::from_tsconfig(
+		r#"{
+	// Comments and trailing commas are allowed
+	"compilerOptions": {
+		"strict": true,
+		"noUnusedLocals": true,
+		"declaration": true,
+		"lib": ["ES2015", "DOM"],
+		"baseUrl": "./src",
+		"paths": { "@/*": ["lib/*"], },
+		/* Ignored */
+		"target": "ES2022",
+	},
+	"include": ["types.d.ts", "src/main.ts", "src/**/*.ts"],
+}"#,
+	)
+	.unwrap();
+
+	assert_eq!(config.entry, vec![PathBuf::from("src/main.ts"), PathBuf::from("src/**/*.ts")]);
+	assert_eq!(config.definition_files, vec![PathBuf::from("types.d.ts")]);
+	assert_eq!(config.paths(), vec![("@/*".to_owned(), vec!["./src/lib/*".to_owned()])]);
+	assert!(config.check.strict_casts);
+	assert!(config.check.no_unused_bindings);
+	assert!(!config.check.preserve_symlinks);
+	assert!(config.build.declaration);
+	assert_eq!(config.lib, vec!["ES2015".to_owned(), "DOM".to_owned()]);
+	assert_eq!(config.lib_definition_files(&[]), Ok(vec![Lib::ES2015.path(), Lib::DOM.path()]));
+}
+
+#[test]
+fn unknown_lib() {
+	let config = Config::from_toml("lib = [\"es2015\", \"es3\"]").unwrap();
+	assert!(config.lib_definition_files(&[Lib::DOM]).unwrap_err().contains("'es3'"));
+}
+
+#[test]
+fn cli_paths_replace_config_paths() {
+	let mut config = Config::from_toml(
+		r#"
+[paths]
+"@/*" = ["src/*"]
+"~/*" = ["lib/*"]
+"#,
+	)
+	.unwrap();
+	config.add_paths(vec![
+		("@/*".to_owned(), "app/*".to_owned()),
+		("@/*".to_owned(), "shared/*".to_owned()),
+	]);
+
+	assert_eq!(
+		config.paths(),
+		vec![
+			("@/*".to_owned(), vec!["app/*".to_owned(), "shared/*".to_owned()]),
+			("~/*".to_owned(), vec!["lib/*".to_owned()]),
+		]
+	);
+}
+
+#[test]
+fn path_aliases() {
+	let read_file = |path: &Path| match path.to_str()? {
+		"/project/main.ts" => {
+			Some("import { a } from \"@/util\";\nconst b: string = a;".to_owned())
+		}
+		"/project/src/util.ts" => Some("export const a = 2;".to_owned()),
+		_ => None,
+	};
+
+	let options = TypeCheckOptions {
+		// The longest prefix is used, whatever the order
+		paths: vec![
+			("@/*".to_owned(), vec!["/project/other/*".to_owned()]),
+			("@/util*".to_owned(), vec!["/project/src/util*".to_owned()]),
+		],
+		..Default::default()
+	};
+	let output = ezno_lib::check(vec![PathBuf::from("/project/main.ts")], &read_file, &[], options);
+
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+	assert_eq!(reasons, vec!["Type 2 is not assignable to type string".to_owned()]);
+}
@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use checker::{
+	features::{
+		constant_functions::{ConstantFunctionError, ConstantOutput},
+		functions::ThisValue,
+	},
+	types::{functions::SynthesisedArgument, TypeStore},
+	Constant, DiagnosticKind, Environment, Type, TypeCheckOptions,
+};
+
+const DEFINITIONS: &str = "@constant(\"uppercase\")\ndeclare function shout(s: string): string;";
+
+const MAIN: &str = "const a: \"HELLO\" = shout(\"hello\");\nconst b: \"hello\" = shout(\"hello\");";
+
+/// Uppercases a string constant
+fn uppercase(
+	_this: ThisValue,
+	arguments: &[SynthesisedArgument],
+	types: &mut TypeStore,
+	_environment: &mut Environment,
+) -> Result<ConstantOutput, ConstantFunctionError> {
+	let argument = arguments.first().ok_or(ConstantFunctionError::BadCall)?;
+	let argument = argument.non_spread_type().map_err(|()| ConstantFunctionError::BadCall)?;
+	if let Type::Constant(Constant::String(value)) = types.get_type_by_id(argument) {
+		let value = Constant::String(value.to_uppercase());
+		Ok(ConstantOutput::Value(types.new_constant_type(value)))
+	} else {
+		Err(ConstantFunctionError::BadCall)
+	}
+}
+
+#[test]
+fn registered_constant_function() {
+	let mut options = TypeCheckOptions::default();
+	options.constant_functions.register("uppercase", uppercase);
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"definitions.d.ts" => Some(DEFINITIONS.to_owned()),
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[PathBuf::from("definitions.d.ts")],
+		options,
+	);
+
+	let errors = output
+		.diagnostics
+		.iter()
+		.filter(|diagnostic| matches!(diagnostic.kind(), DiagnosticKind::Error))
+		.collect::<Vec<_>>();
+
+	// Only `b` is not assignable
+	assert_eq!(errors.len(), 1, "{errors:?}");
+}
@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+use checker::{DiagnosticKind, TypeCheckOptions};
+
+const MAIN: &str = "let a = 0, b = 0;
+declare function effect(): number;
+declare const condition: boolean;
+
+a = 1;
+a = 2;
+a satisfies 2;
+
+b = effect();
+b = 3;
+
+const object = { x: 0 };
+object.x = 1;
+object.x = object.x + 1;
+object.x = 3;
+object.x = 4;
+
+if (condition) {
+	b = 4;
+}
+b = 5;
+
+a + b;
+\"use strict\";";
+
+#[test]
+fn dead_stores_and_unused_expressions() {
+	let options = TypeCheckOptions { no_dead_stores: true, ..Default::default() };
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let warnings = output
+		.diagnostics
+		.iter()
+		.filter(|diagnostic| matches!(diagnostic.kind(), DiagnosticKind::Warning))
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+
+	// `b = effect()` is kept for the call. `b = 4` is conditional and `object.x = 1` is read
+	assert_eq!(
+		warnings,
+		[
+			"Value assigned to 'a' is never read",
+			"Value assigned to 'object.x' is never read",
+			"Value of expression is not used"
+		],
+		"{warnings:?}"
+	);
+}
@@ -0,0 +1,59 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+
+fn read_file(path: &Path) -> Option<String> {
+	let content = match path.to_str()? {
+		"/project/definitions.d.ts" => {
+			"/// <reference path=\"./globals.d.ts\" />
+/// <reference types=\"library\" />
+/// <reference lib=\"es2015\" />
+declare const total: Count;"
+		}
+		// Referenced again, but only added once
+		"/project/globals.d.ts" => {
+			"/// <reference path=\"./definitions.d.ts\" />\ntype Count = number;"
+		}
+		"/project/node_modules/@types/library/package.json" => r#"{ "types": "index.d.ts" }"#,
+		"/project/node_modules/@types/library/index.d.ts" => "declare const version: string;",
+		"/project/main.ts" => "const a: string = total;\nconst b: number = version;",
+		"/project/missing.d.ts" => "/// <reference path=\"./other.d.ts\" />",
+		_ => return None,
+	};
+	Some(content.to_owned())
+}
+
+#[test]
+fn references_are_added_before_the_definition_file() {
+	let output = ezno_lib::check(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[PathBuf::from("/project/definitions.d.ts")],
+		TypeCheckOptions::default(),
+	);
+
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+
+	// Only the assignments (the types and variables from the referenced files are found)
+	assert_eq!(reasons.len(), 2, "{reasons:?}");
+	assert!(reasons.iter().all(|reason| reason.contains("is not assignable to type")));
+}
+
+#[test]
+fn missing_reference() {
+	let output = ezno_lib::check(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[PathBuf::from("/project/missing.d.ts")],
+		TypeCheckOptions::default(),
+	);
+
+	assert!(output
+		.diagnostics
+		.into_iter()
+		.any(|diagnostic| diagnostic.reason() == "could not find ./other.d.ts"));
+}
@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+
+use ezno_lib::{file_system::get_entry_points, ReadFromFS};
+
+/// Like the file systems given from JS (no `metadata`)
+struct VirtualFileSystem(&'static [&'static str]);
+
+impl ReadFromFS for VirtualFileSystem {
+	fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+		self.0.iter().any(|file| Path::new(file) == path).then(Vec::new)
+	}
+
+	fn read_directory(&self, path: &Path) -> Option<Vec<PathBuf>> {
+		let mut entries = self
+			.0
+			.iter()
+			.filter_map(|file| {
+				let rest = Path::new(file).strip_prefix(path).ok()?;
+				Some(path.join(rest.components().next()?))
+			})
+			.collect::<Vec<_>>();
+		entries.dedup();
+		(!entries.is_empty()).then_some(entries)
+	}
+}
+
+const FILES: VirtualFileSystem = VirtualFileSystem(&[
+	"/project/src/main.ts",
+	"/project/src/utilities/string.ts",
+	"/project/src/utilities/string.test.ts",
+	"/project/src/utilities/types.d.ts",
+	"/project/src/data.json",
+	"/project/node_modules/package/index.ts",
+	"/project/README.md",
+]);
+
+#[test]
+fn glob_entry_points() {
+	let entry_points = get_entry_points(Path::new("/project/**/*.ts"), &FILES).unwrap();
+	assert_eq!(
+		entry_points,
+		[
+			"/project/src/main.ts",
+			"/project/src/utilities/string.test.ts",
+			"/project/src/utilities/string.ts",
+			"/project/src/utilities/types.d.ts"
+		]
+		.map(PathBuf::from)
+	);
+
+	let entry_points = get_entry_points(Path::new("/project/src/*.ts"), &FILES).unwrap();
+	assert_eq!(entry_points, [PathBuf::from("/project/src/main.ts")]);
+}
+
+#[test]
+fn non_glob_and_unmatched_entry_points() {
+	let entry_points = get_entry_points(Path::new("/project/other.ts"), &FILES).unwrap();
+	assert_eq!(entry_points, [PathBuf::from("/project/other.ts")]);
+
+	assert!(get_entry_points(Path::new("/project/**/*.js"), &FILES).is_err());
+}
+
+#[test]
+fn directory_entry_points() {
+	let expected = [
+		"/project/src/main.ts",
+		"/project/src/utilities/string.test.ts",
+		"/project/src/utilities/string.ts",
+	]
+	.map(PathBuf::from);
+	assert_eq!(get_entry_points(Path::new("/project/src"), &FILES).unwrap(), expected);
+	assert_eq!(get_entry_points(Path::new("/project/src/"), &FILES).unwrap(), expected);
+}
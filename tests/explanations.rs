@@ -0,0 +1,39 @@
+use std::{
+	collections::HashSet,
+	path::{Path, PathBuf},
+};
+
+use checker::{
+	explanations::{get_explanation, EXPLANATIONS},
+	TypeCheckOptions,
+};
+
+#[test]
+fn codes_are_unique() {
+	let mut codes = HashSet::new();
+	for explanation in EXPLANATIONS {
+		assert!(codes.insert(explanation.code), "{} is used twice", explanation.code);
+	}
+	assert!(get_explanation("ez1002").is_some());
+	assert!(get_explanation("EZ9999").is_none());
+}
+
+#[test]
+fn diagnostics_have_codes() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some("const a: number = \"hello\";\n2 satisfies string;".to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+
+	let codes = output.diagnostics.iter().map(checker::Diagnostic::code).collect::<Vec<_>>();
+	assert_eq!(codes, [Some("EZ1002"), Some("EZ1006")]);
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+use ezno_lib::{file_system::OverlayFileSystem, ReadFromFS};
+
+fn base(path: &Path) -> Option<String> {
+	match path.to_str()? {
+		"/project/main.ts" => {
+			Some("import { a } from \"./utilities\";\nconst b: number = a;".to_owned())
+		}
+		"/project/utilities.ts" => Some("export const a: number = 2;".to_owned()),
+		_ => None,
+	}
+}
+
+#[test]
+fn overlay_shadows_base_file() {
+	let mut fs = OverlayFileSystem::new(base);
+	fs.set_overlay(
+		PathBuf::from("/project/utilities.ts"),
+		"export const a: string = \"x\";".to_owned(),
+	);
+
+	let content = fs.read_file(Path::new("/project/utilities.ts")).unwrap();
+	assert_eq!(content, b"export const a: string = \"x\";");
+	assert_eq!(fs.metadata(Path::new("/project/utilities.ts")).unwrap().size, 29);
+	// Not overlaid
+	assert_eq!(
+		fs.read_file(Path::new("/project/main.ts")),
+		base(Path::new("/project/main.ts")).map(String::into_bytes)
+	);
+
+	// The checker reads through the same file system
+	let output = ezno_lib::check(
+		vec![PathBuf::from("/project/main.ts")],
+		&fs,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+	assert_eq!(reasons, ["Type \"x\" is not assignable to type number"]);
+
+	assert!(fs.remove_overlay(Path::new("/project/utilities.ts")).is_some());
+	let output = ezno_lib::check(
+		vec![PathBuf::from("/project/main.ts")],
+		&fs,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	assert_eq!(output.diagnostics.into_iter().count(), 0);
+}
+
+#[test]
+fn overlay_adds_files() {
+	let mut fs = OverlayFileSystem::new(base);
+	assert!(!fs.exists(Path::new("/project/new.ts")));
+
+	fs.set_overlay(PathBuf::from("/project/new.ts"), "export {}".to_owned());
+	assert!(fs.exists(Path::new("/project/new.ts")));
+	assert_eq!(
+		fs.read_directory(Path::new("/project")),
+		Some(vec![PathBuf::from("/project/new.ts")])
+	);
+	assert_eq!(fs.overlaid_paths().collect::<Vec<_>>(), [Path::new("/project/new.ts")]);
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use checker::{DiagnosticKind, TypeCheckOptions};
+
+const MAIN: &str = "declare function save(): Promise<void>;
+declare function navigate(to: string): Promise<boolean>;
+
+fetch(\"/a\");
+fetch(\"/b\").catch(() => {});
+fetch(\"/c\").then(() => {}, () => {});
+void fetch(\"/d\");
+const response = fetch(\"/e\");
+save();
+navigate(\"/home\");";
+
+#[test]
+fn floating_promises() {
+	let options = TypeCheckOptions {
+		no_floating_promises: true,
+		floating_promise_allowed_functions: vec!["navigate".to_owned()],
+		..Default::default()
+	};
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let floating = output
+		.diagnostics
+		.iter()
+		.filter(|diagnostic| {
+			matches!(diagnostic.kind(), DiagnosticKind::Warning)
+				&& diagnostic.reason() == "Promise is not awaited or handled"
+		})
+		.filter_map(|diagnostic| diagnostic.position())
+		.map(|position| &MAIN[position.start as usize..position.end as usize])
+		.collect::<Vec<_>>();
+
+	assert_eq!(floating, ["fetch(\"/a\")", "save()"]);
+}
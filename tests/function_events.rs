@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use checker::{synthesis::EznoParser, CheckOutput, TypeCheckOptions};
+
+const MAIN: &str = "function func(obj: { a: number }, x: boolean) {
+	if (x) {
+		obj.a = 2;
+	} else {
+		throw \"error\";
+	}
+}";
+
+#[test]
+fn function_events_are_indented() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output: CheckOutput<EznoParser> = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+
+	let source = output.module_contents.get_source_at_path(Path::new("main.ts")).unwrap();
+	let functions = output.get_function_events(source);
+	assert_eq!(functions.len(), 1, "{functions:?}");
+
+	let (start, events) = &functions[0];
+	assert_eq!(*start, 0);
+
+	// After the events that create the `arguments` object
+	let lines = events.lines().skip_while(|line| !line.starts_with("if ")).collect::<Vec<_>>();
+	// Events under the condition are indented and the condition is closed with `end`
+	assert!(lines.first().is_some_and(|line| line.starts_with("if ")), "{events}");
+	assert!(lines.iter().any(|line| line.starts_with("  ") && line.ends_with("= 2")), "{events}");
+	assert!(lines.contains(&"else"), "{events}");
+	assert!(lines.contains(&"  throw \"error\""), "{events}");
+	assert_eq!(lines.last(), Some(&"end"), "{events}");
+}
@@ -0,0 +1,46 @@
+use std::path::{Path, PathBuf};
+
+use checker::{Diagnostic, TypeCheckOptions};
+
+const MAIN: &str = "function first<T extends number>(a: T, b: T) {
+	return a
+}
+
+first(1, \"2\");";
+
+#[test]
+fn constraint_failure_labels_inferred_argument() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+
+	let diagnostics = output.diagnostics.into_iter().collect::<Vec<_>>();
+	let [Diagnostic::PositionWithAdditionalLabels { reason, labels, .. }] = diagnostics.as_slice()
+	else {
+		panic!("expected one diagnostic with labels, found {diagnostics:?}");
+	};
+
+	assert_eq!(reason, "Argument of type \"2\" is not assignable to parameter of type T");
+	let texts = labels.iter().map(|(label, position)| (label.as_str(), position.is_some()));
+	assert_eq!(
+		texts.collect::<Vec<_>>(),
+		[
+			("Parameter has type T", true),
+			("T is constrained to number", false),
+			("T was inferred as 1 from this argument", true)
+		]
+	);
+
+	let first_argument = MAIN.find("first(1").unwrap() + "first(".len();
+	assert_eq!(labels[2].1.map(|position| position.start as usize), Some(first_argument));
+}
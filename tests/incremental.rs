@@ -0,0 +1,81 @@
+use std::{
+	cell::RefCell,
+	path::{Path, PathBuf},
+};
+
+use checker::{
+	incremental::{hash_content, CheckCache},
+	TypeCheckOptions,
+};
+
+/// Checks `main.ts`, recording the files read like the CLI does
+fn check_and_cache(
+	read_file: &impl Fn(&Path) -> Option<String>,
+	key: u64,
+) -> (checker::DiagnosticsContainer, Vec<u8>) {
+	let files_read = RefCell::new(Vec::new());
+	let recording_read_file = |path: &Path| {
+		let content = read_file(path);
+		let content_hash = content.as_deref().map(|content| hash_content(content.as_bytes()));
+		files_read.borrow_mut().push((path.to_path_buf(), content_hash));
+		content
+	};
+	let output = ezno_lib::check(
+		vec![PathBuf::from("/project/main.ts")],
+		&recording_read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	let bytes = CheckCache::new(&output, &files_read.borrow()).unwrap().to_bytes(key);
+	(output.diagnostics, bytes)
+}
+
+#[test]
+fn cache_is_used_only_if_files_are_unchanged() {
+	let project = |main: &'static str| {
+		move |path: &Path| match path.to_str()? {
+			"/project/main.ts" => Some(main.to_owned()),
+			"/project/utilities.ts" => Some("export const a: number = 2;".to_owned()),
+			_ => None,
+		}
+	};
+	let read_file = project("import { a } from \"./utilities\";\nconst b: string = a;");
+
+	let key = checker::incremental::cache_key("");
+	let (diagnostics, bytes) = check_and_cache(&read_file, key);
+	let reasons = |diagnostics: checker::DiagnosticsContainer| {
+		diagnostics.into_iter().map(|diagnostic| diagnostic.reason().to_owned()).collect::<Vec<_>>()
+	};
+
+	let expected = reasons(diagnostics);
+	assert_eq!(expected.len(), 1);
+
+	let (diagnostics, files, restored_paths) =
+		CheckCache::from_bytes(&bytes, key).unwrap().restore(&read_file).unwrap();
+	assert_eq!(reasons(diagnostics), expected);
+	assert!(files.get_source_at_path(Path::new("/project/utilities.ts")).is_some());
+	assert!(restored_paths.contains(&PathBuf::from("/project/utilities.ts")));
+
+	let changed = project("import { a } from \"./utilities\";\nconst b: number = a;");
+	assert!(CheckCache::from_bytes(&bytes, key).unwrap().restore(&changed).is_none());
+
+	let other_key = checker::incremental::cache_key("--no-explicit-any");
+	assert!(CheckCache::from_bytes(&bytes, other_key).is_none());
+}
+
+#[test]
+fn cache_is_invalidated_when_a_missing_file_is_created() {
+	let read_file = |path: &Path| match path.to_str()? {
+		"/project/main.ts" => Some("import { a } from \"./utilities\";".to_owned()),
+		_ => None,
+	};
+	let key = checker::incremental::cache_key("");
+	let (_, bytes) = check_and_cache(&read_file, key);
+	assert!(CheckCache::from_bytes(&bytes, key).unwrap().restore(&read_file).is_some());
+
+	let created = |path: &Path| match path.to_str()? {
+		"/project/utilities.ts" => Some("export const a = 2;".to_owned()),
+		_ => read_file(path),
+	};
+	assert!(CheckCache::from_bytes(&bytes, key).unwrap().restore(&created).is_none());
+}
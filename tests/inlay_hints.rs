@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use checker::{synthesis::inlay_hints::InlayHintKind, TypeCheckOptions};
+
+const SOURCE: &str = "function add(a: number, b: number) { return a + b }
+const b = 2;
+const total = add(1, b);
+const double = (x: number) => x * 2;
+let annotated: number = 4;";
+
+fn read_file(path: &Path) -> Option<String> {
+	(path.to_str()? == "main.ts").then(|| SOURCE.to_owned())
+}
+
+fn hints(options: TypeCheckOptions) -> Vec<(u32, String, InlayHintKind)> {
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+	let hints = output.get_inlay_hints("main.ts").unwrap();
+	hints.into_iter().map(|hint| (hint.position.start, hint.label, hint.kind)).collect()
+}
+
+fn offset_after(text: &str) -> u32 {
+	(SOURCE.find(text).unwrap() + text.len()).try_into().unwrap()
+}
+
+#[test]
+fn parameter_names_and_types() {
+	let options = TypeCheckOptions { store_expression_type_mappings: true, ..Default::default() };
+	let hints = hints(options);
+
+	assert!(hints.contains(&(
+		offset_after("function add(a: number, b: number)"),
+		": number".to_owned(),
+		InlayHintKind::Type
+	)));
+	assert!(hints.contains(&(offset_after("const b"), ": 2".to_owned(), InlayHintKind::Type)));
+	assert!(hints.contains(&(
+		offset_after("total = add("),
+		"a:".to_owned(),
+		InlayHintKind::Parameter
+	)));
+	assert!(hints.contains(&(
+		offset_after("const double = (x: number)"),
+		": number".to_owned(),
+		InlayHintKind::Type
+	)));
+
+	// Not for arguments that have the name of the parameter, functions or annotated variables
+	assert!(!hints.iter().any(|(_, label, _)| label == "b:"));
+	assert!(!hints.iter().any(|(at, _, _)| *at == offset_after("const double")));
+	assert!(!hints.iter().any(|(at, _, _)| *at == offset_after("let annotated")));
+}
+
+#[test]
+fn return_types_without_type_mappings() {
+	let hints = hints(TypeCheckOptions::default());
+	assert_eq!(
+		hints,
+		vec![
+			(
+				offset_after("function add(a: number, b: number)"),
+				": number".to_owned(),
+				InlayHintKind::Type
+			),
+			(
+				offset_after("const double = (x: number)"),
+				": number".to_owned(),
+				InlayHintKind::Type
+			)
+		]
+	);
+}
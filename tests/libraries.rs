@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+use checker::{libraries::Lib, TypeCheckOptions};
+
+fn read_file(path: &Path) -> Option<String> {
+	let content = match path.to_str()? {
+		"/project/main.ts" => "const title: string = document.title;\nconst p = new Promise();",
+		"/project/definitions.d.ts" => {
+			"/// <reference lib=\"dom\" />\ndeclare const version: string;"
+		}
+		"/project/uses-definitions.ts" => {
+			"const title: string = document.title;\nconst v: string = version;"
+		}
+		_ => return None,
+	};
+	Some(content.to_owned())
+}
+
+fn check_reasons(entry: &str, definition_files: &[PathBuf]) -> Vec<String> {
+	let output = ezno_lib::check(
+		vec![PathBuf::from(entry)],
+		&read_file,
+		definition_files,
+		TypeCheckOptions::default(),
+	);
+	output.diagnostics.into_iter().map(|diagnostic| diagnostic.reason().to_owned()).collect()
+}
+
+#[test]
+fn dom_includes_es2015() {
+	let reasons = check_reasons("/project/main.ts", &[Lib::DOM.path()]);
+	assert!(reasons.is_empty(), "{reasons:?}");
+}
+
+#[test]
+fn es5_does_not_include_dom_or_promise() {
+	let reasons = check_reasons("/project/main.ts", &[Lib::ES5.path()]);
+	assert!(reasons.iter().any(|reason| reason.contains("document")), "{reasons:?}");
+	assert!(reasons.iter().any(|reason| reason.contains("Promise")), "{reasons:?}");
+}
+
+#[test]
+fn referenced_from_definition_file() {
+	let reasons = check_reasons(
+		"/project/uses-definitions.ts",
+		&[PathBuf::from("/project/definitions.d.ts")],
+	);
+	assert!(reasons.is_empty(), "{reasons:?}");
+}
+
+#[test]
+fn names() {
+	assert_eq!(Lib::from_name("DOM"), Some(Lib::DOM));
+	assert_eq!(Lib::from_name("esnext"), Some(Lib::ES2021));
+	assert_eq!(Lib::from_name("es3"), None);
+	assert!(Lib::ALL.into_iter().all(|lib| Lib::from_name(lib.name()) == Some(lib)));
+}
@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+use ezno_lib::lint::{lint, no_floating_promises, no_unchecked_indexed_access};
+
+const MAIN: &str = "declare function fetchData(): Promise<number>;
+declare function startServer(): Promise<void>;
+fetchData();
+void fetchData();
+const pending = fetchData();
+fetchData().catch(() => {});
+startServer();
+
+function get(array: Array<number>, idx: number) {
+	return array[idx] + array[0];
+}";
+
+#[test]
+fn built_in_rules() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let options = TypeCheckOptions { store_expression_type_mappings: true, ..Default::default() };
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let mut rules =
+		vec![no_floating_promises(vec!["startServer".to_owned()]), no_unchecked_indexed_access()];
+	let diagnostics = lint(&output, &mut rules);
+
+	let reasons =
+		diagnostics.iter().map(|diagnostic| diagnostic.reason().to_owned()).collect::<Vec<_>>();
+
+	assert_eq!(reasons.len(), 2, "{reasons:?}");
+	assert_eq!(reasons[0], "Promise is not awaited (no-floating-promises)");
+	assert!(
+		reasons[1].starts_with("Indexing with ")
+			&& reasons[1].ends_with("could be undefined (no-unchecked-indexed-access)"),
+		"{reasons:?}"
+	);
+}
+
+#[test]
+fn indexing_with_known_keys() {
+	const INDEXES: &str = "function func(
+	record: { a: number, b: number },
+	key: \"a\" | \"b\",
+	pair: [number, string],
+	valid: 0 | 1,
+	invalid: 0 | 2,
+	name: string
+) {
+	const a = record[key];
+	const b = pair[valid];
+	const c = pair[invalid];
+	const d = record[name];
+	const e = record[\"a\"];
+}";
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(INDEXES.to_owned()),
+			_ => None,
+		}
+	};
+
+	let options = TypeCheckOptions { store_expression_type_mappings: true, ..Default::default() };
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let mut rules = vec![no_unchecked_indexed_access()];
+	let diagnostics = lint(&output, &mut rules);
+
+	let reasons =
+		diagnostics.iter().map(|diagnostic| diagnostic.reason().to_owned()).collect::<Vec<_>>();
+	assert_eq!(
+		reasons,
+		[
+			"Indexing with 0 | 2 could be undefined (no-unchecked-indexed-access)",
+			"Indexing with string could be undefined (no-unchecked-indexed-access)"
+		]
+	);
+}
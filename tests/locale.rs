@@ -0,0 +1,30 @@
+use ezno_lib::locale::MessageCatalog;
+
+const LOCALE: &str = r#"{
+	"Type {} is not assignable to type {}": "Le type {0} n'est pas assignable au type {1}",
+	"Expected {}, found {}": "Trouvé {1}, attendu {0}",
+	"Missing argument": "Argument manquant"
+}"#;
+
+#[test]
+fn translates_messages() {
+	let catalog = MessageCatalog::from_json(LOCALE).unwrap();
+
+	assert_eq!(
+		catalog.translate("Type \"hello\" is not assignable to type number").as_deref(),
+		Some("Le type \"hello\" n'est pas assignable au type number")
+	);
+	// Holes can be reordered
+	assert_eq!(
+		catalog.translate("Expected string, found 2").as_deref(),
+		Some("Trouvé 2, attendu string")
+	);
+	assert_eq!(catalog.translate("Missing argument").as_deref(), Some("Argument manquant"));
+	assert_eq!(catalog.translate("Missing argument here"), None);
+	assert_eq!(catalog.translate("Excess argument"), None);
+}
+
+#[test]
+fn invalid_locale_file() {
+	assert!(MessageCatalog::from_json("[]").is_err());
+}
@@ -0,0 +1,22 @@
+use checker::logging::{Level, LogFilter};
+
+#[test]
+fn filters() {
+	let filter = LogFilter::parse("types::subtyping=trace,info").unwrap();
+	assert!(filter.enabled("types::subtyping", Level::Trace));
+	assert!(filter.enabled("types::subtyping::inner", Level::Debug));
+	assert!(!filter.enabled("types::subtyping_other", Level::Debug));
+	assert!(filter.enabled("synthesis", Level::Info));
+	assert!(!filter.enabled("synthesis", Level::Debug));
+
+	assert!(LogFilter::parse("types=loud").is_err());
+}
+
+/// Invalid filters are returned as errors (for the CLI to report) rather than printed
+#[test]
+fn invalid_environment_filter() {
+	std::env::set_var("EZNO_LOG", "types=loud");
+	let result = LogFilter::from_environment();
+	std::env::remove_var("EZNO_LOG");
+	assert_eq!(result.unwrap_err(), "Invalid EZNO_LOG filter: unknown log level 'loud'");
+}
@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+use checker::{
+	features::modules::ExportKind, synthesis::EznoParser, CheckOutput, TypeCheckOptions,
+};
+
+const FILES: &[(&str, &str)] = &[
+	(
+		"/project/main.ts",
+		"export const a = 2;
+export let b: string = \"hi\";
+export function func() {}
+export interface Options { a: number }
+export { shared } from \"./shared\";
+export default 4;",
+	),
+	("/project/shared.ts", "export const shared = true;"),
+];
+
+#[test]
+fn module_exports() {
+	let read_file = |path: &Path| {
+		FILES
+			.iter()
+			.find(|(name, _)| Path::new(name) == path)
+			.map(|(_, content)| (*content).to_owned())
+	};
+
+	let output: CheckOutput<EznoParser> = ezno_lib::check(
+		vec![PathBuf::from("/project/main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+
+	let main = output.module_contents.get_source_at_path(Path::new("/project/main.ts")).unwrap();
+	let shared =
+		output.module_contents.get_source_at_path(Path::new("/project/shared.ts")).unwrap();
+	let exports = output.get_exports(main).unwrap();
+
+	let get = |name: &str| exports.iter().find(|export| export.name == name).unwrap();
+
+	assert_eq!(get("default").kind, ExportKind::Default);
+	assert_eq!(get("a").kind, ExportKind::Variable { constant: true });
+	assert_eq!(get("b").kind, ExportKind::Variable { constant: false });
+	assert_eq!(get("b").ty, checker::TypeId::STRING_TYPE);
+	assert_eq!(get("func").kind, ExportKind::Function);
+	assert_eq!(get("Options").kind, ExportKind::Type);
+
+	let re_exported = get("shared");
+	assert_eq!(re_exported.re_exported_from, Some(shared));
+	assert_eq!(re_exported.position.map(|position| position.source), Some(main));
+
+	// Positions point at the declaration
+	let position = get("a").position.unwrap();
+	assert_eq!(&FILES[0].1[position.start as usize..position.end as usize], "a");
+}
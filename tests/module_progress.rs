@@ -0,0 +1,38 @@
+use std::{
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
+
+use checker::{ModuleEvent, ModuleProgress, TypeCheckOptions};
+
+#[test]
+fn imported_modules_are_checked_during_their_importer() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some("import { a } from \"./other\";\nconst b = a;".to_owned()),
+			"other.ts" => Some("export const a = 2;".to_owned()),
+			_ => None,
+		}
+	};
+
+	let events = Arc::new(Mutex::new(Vec::new()));
+	let module_progress = {
+		let events = events.clone();
+		ModuleProgress::new(move |event| {
+			let event = match event {
+				ModuleEvent::Started(path) => format!("started {}", path.display()),
+				ModuleEvent::Finished(path) => format!("finished {}", path.display()),
+			};
+			events.lock().unwrap().push(event);
+		})
+	};
+
+	let options = TypeCheckOptions { module_progress, ..Default::default() };
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+	assert!(!output.diagnostics.has_error());
+
+	assert_eq!(
+		*events.lock().unwrap(),
+		["started main.ts", "started other.ts", "finished other.ts", "finished main.ts"]
+	);
+}
@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+
+use checker::{
+	features::resolution::{resolve_package, ResolvedPackage},
+	TypeCheckOptions,
+};
+
+fn read_file(path: &Path) -> Option<String> {
+	let content = match path.to_str()? {
+		"/project/src/main.ts" => {
+			"import { a } from \"exported\";
+import { b } from \"exported/feature\";
+import { c } from \"@scope/main-field\";
+import { d } from \"./utilities\";
+const w: string = a, x: string = b, y: string = c, z: string = d;"
+		}
+		"/project/node_modules/exported/package.json" => {
+			r#"{ "exports": { ".": { "types": "./types/index.d.ts", "import": "./src/index.ts" }, "./*": "./src/*.ts" } }"#
+		}
+		"/project/node_modules/exported/src/index.ts" => "export const a = 1;",
+		"/project/node_modules/exported/src/feature.ts" => "export const b = 2;",
+		"/project/node_modules/@scope/main-field/package.json" => r#"{ "main": "lib/entry" }"#,
+		"/project/node_modules/@scope/main-field/lib/entry.ts" => "export const c = 3;",
+		"/project/node_modules/@types/untyped/package.json" => r#"{ "types": "index.d.ts" }"#,
+		"/project/src/utilities/index.ts" => "export const d = 4;",
+		_ => return None,
+	};
+	Some(content.to_owned())
+}
+
+#[test]
+fn node_modules_and_extensions() {
+	let output = ezno_lib::check(
+		vec![PathBuf::from("/project/src/main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+
+	let reasons = output
+		.diagnostics
+		.into_iter()
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+
+	assert_eq!(
+		reasons,
+		(1..=4)
+			.map(|value| format!("Type {value} is not assignable to type string"))
+			.collect::<Vec<_>>()
+	);
+}
+
+#[test]
+fn package_entries() {
+	let from = Path::new("/project/src/main.ts");
+	let cwd = Path::new("/project");
+
+	assert_eq!(
+		resolve_package("exported", from, cwd, &read_file),
+		Some(ResolvedPackage {
+			path: PathBuf::from("/project/node_modules/exported/src/index.ts"),
+			definition_file: Some(PathBuf::from("/project/node_modules/exported/types/index.d.ts")),
+		})
+	);
+	assert_eq!(
+		resolve_package("exported/feature", from, cwd, &read_file).map(|package| package.path),
+		Some(PathBuf::from("/project/node_modules/exported/src/feature.ts"))
+	);
+	assert_eq!(
+		resolve_package("untyped", from, cwd, &read_file).map(|package| package.path),
+		Some(PathBuf::from("/project/node_modules/@types/untyped/index.d.ts"))
+	);
+	assert_eq!(resolve_package("missing", from, cwd, &read_file), None);
+}
+
+/// `/project/link.ts` is a symlink to `/project/real.ts`
+struct SymlinkFileSystem;
+
+impl checker::ReadFromFS for SymlinkFileSystem {
+	fn read_file(&self, path: &Path) -> Option<Vec<u8>> {
+		let content = match path.to_str()? {
+			"/project/main.ts" => {
+				"import { a } from \"./link\";\nimport { a as b } from \"./real\";"
+			}
+			"/project/link.ts" | "/project/real.ts" => "export const a = 1;",
+			_ => return None,
+		};
+		Some(content.into())
+	}
+
+	fn canonicalize(&self, path: &Path) -> PathBuf {
+		if path == Path::new("/project/link.ts") {
+			PathBuf::from("/project/real.ts")
+		} else {
+			path.to_path_buf()
+		}
+	}
+}
+
+#[test]
+fn symlinked_module_checked_once() {
+	let check = |preserve_symlinks| {
+		ezno_lib::check(
+			vec![PathBuf::from("/project/main.ts")],
+			&SymlinkFileSystem,
+			&[],
+			TypeCheckOptions { preserve_symlinks, ..Default::default() },
+		)
+	};
+
+	let output = check(false);
+	assert_eq!(output.modules.len(), 2);
+	assert!(output.module_contents.get_source_at_path(Path::new("/project/link.ts")).is_none());
+
+	assert_eq!(check(true).modules.len(), 3);
+}
+
+#[cfg(unix)]
+#[test]
+fn real_symlinks_are_resolved() {
+	use ezno_lib::{file_system::RealFileSystem, ReadFromFS};
+
+	let directory = std::env::temp_dir().join(format!("ezno-symlink-test-{}", std::process::id()));
+	std::fs::create_dir_all(&directory).unwrap();
+	let real = directory.join("real.ts");
+	let link = directory.join("link.ts");
+	std::fs::write(&real, "export const a = 1;").unwrap();
+	let _ = std::fs::remove_file(&link);
+	std::os::unix::fs::symlink(&real, &link).unwrap();
+
+	assert_eq!(RealFileSystem.canonicalize(&link), std::fs::canonicalize(&real).unwrap());
+	// Paths that do not exist are left as is
+	let missing = directory.join("missing.ts");
+	assert_eq!(RealFileSystem.canonicalize(&missing), missing);
+
+	std::fs::remove_dir_all(&directory).unwrap();
+}
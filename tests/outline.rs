@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+use parser::outline::SymbolKind;
+
+#[test]
+fn outline_with_types() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(
+				"const value = 4;\nexport class Counter { count: number = 0; increment() {} }"
+					.to_owned(),
+			),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+
+	let outline = output.get_outline("main.ts").unwrap();
+	let [value, counter] = outline.as_slice() else {
+		panic!("expected two symbols, found {outline:?}");
+	};
+
+	assert_eq!((value.name.as_str(), value.kind), ("value", SymbolKind::Variable));
+	assert_eq!(value.r#type.as_deref(), Some("4"));
+
+	assert_eq!((counter.name.as_str(), counter.kind), ("Counter", SymbolKind::Class));
+	assert!(counter.is_exported);
+	let members =
+		counter.children.iter().map(|child| (child.name.as_str(), child.kind)).collect::<Vec<_>>();
+	assert_eq!(members, [("count", SymbolKind::Property), ("increment", SymbolKind::Method)]);
+
+	assert!(output.get_outline("other.ts").is_none());
+}
@@ -0,0 +1,33 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+
+#[test]
+fn same_diagnostics_with_threads() {
+	let read_file = |path: &Path| match path.to_str()? {
+		"/project/a.ts" => Some("import { c } from \"./c\";\nconst a: string = c;".to_owned()),
+		"/project/b.ts" => Some("const b: number = \"b\";".to_owned()),
+		"/project/c.ts" => Some("export const c = 4;\nconst d: string = c;".to_owned()),
+		"/project/d.ts" => Some("const e = ;".to_owned()),
+		_ => None,
+	};
+	let entry_points = ["a", "b", "c", "d"]
+		.iter()
+		.map(|name| PathBuf::from(format!("/project/{name}.ts")))
+		.collect::<Vec<_>>();
+
+	let check = |threads: usize| {
+		let options = TypeCheckOptions { threads, ..Default::default() };
+		let output = ezno_lib::check(entry_points.clone(), &read_file, &[], options);
+		output
+			.diagnostics
+			.into_iter()
+			.map(|diagnostic| diagnostic.reason().to_owned())
+			.collect::<Vec<_>>()
+	};
+
+	let expected = check(1);
+	// `c.ts` is only checked once, when imported by `a.ts`
+	assert_eq!(expected.len(), 4, "{expected:?}");
+	assert_eq!(check(3), expected);
+}
@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use checker::{Diagnostic, TypeCheckOptions};
+
+const MAIN: &str = "const base = 2;
+interface Point { x: number }
+
+function scale(point: Point): number {
+	return point.x * base
+}";
+
+#[test]
+fn check_function_with_edited_body() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let mut output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	assert!(!output.diagnostics.has_error());
+
+	let start = MAIN.find("function scale").unwrap() as u32;
+
+	// Uses the interface and variable from the module
+	let edited = "function scale(point: Point): number {\n\treturn point.x + base * 2\n}";
+	let diagnostics = output
+		.check_function(
+			Path::new("main.ts"),
+			start,
+			edited,
+			&read_file,
+			TypeCheckOptions::default(),
+		)
+		.unwrap();
+	assert_eq!(diagnostics.into_iter().count(), 0);
+
+	let edited = "function scale(point: Point): string {\n\treturn point.x * base\n}";
+	let diagnostics = output
+		.check_function(
+			Path::new("main.ts"),
+			start,
+			edited,
+			&read_file,
+			TypeCheckOptions::default(),
+		)
+		.unwrap()
+		.into_iter()
+		.collect::<Vec<_>>();
+
+	let [Diagnostic::PositionWithAdditionalLabels { reason, position, .. }] =
+		diagnostics.as_slice()
+	else {
+		panic!("expected one diagnostic, found {diagnostics:?}");
+	};
+	assert_eq!(reason, "Cannot return number because the function is expected to return string");
+	// As when checking the module, the diagnostic is on the return statement
+	let returned = edited.find("return").unwrap() as u32;
+	assert_eq!(position.start, start + returned);
+
+	assert!(output
+		.check_function(Path::new("other.ts"), 0, edited, &read_file, TypeCheckOptions::default())
+		.is_none());
+}
+
+#[test]
+fn check_function_does_not_grow_type_store() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let mut output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	let count = output.types.count_of_types();
+	let start = MAIN.find("function scale").unwrap() as u32;
+
+	for edited in [
+		"function scale(point: Point): number {\n\treturn point.x * base + 1\n}",
+		"function scale(point: Point): string {\n\treturn `${point.x}`\n}",
+	] {
+		let diagnostics = output.check_function(
+			Path::new("main.ts"),
+			start,
+			edited,
+			&read_file,
+			TypeCheckOptions::default(),
+		);
+		assert!(diagnostics.is_some());
+		assert_eq!(output.types.count_of_types(), count);
+	}
+
+	// The function type is the one from checking the project
+	let unedited = "function scale(point: Point): number {\n\treturn point.x * base\n}";
+	let diagnostics = output
+		.check_function(
+			Path::new("main.ts"),
+			start,
+			unedited,
+			&read_file,
+			TypeCheckOptions::default(),
+		)
+		.unwrap();
+	assert_eq!(diagnostics.into_iter().count(), 0);
+}
+
+#[test]
+fn check_nested_function() {
+	const NESTED: &str = "function outer(a: number) {
+	function inner(): number {
+		return a
+	}
+	return inner()
+}";
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(NESTED.to_owned()),
+			_ => None,
+		}
+	};
+
+	let mut output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	assert!(!output.diagnostics.has_error());
+
+	// `a` is not in the scope of the module
+	let start = NESTED.find("function inner").unwrap() as u32;
+	let edited = "function inner(): number {\n\t\treturn a + 1\n\t}";
+	assert!(output
+		.check_function(
+			Path::new("main.ts"),
+			start,
+			edited,
+			&read_file,
+			TypeCheckOptions::default()
+		)
+		.is_none());
+}
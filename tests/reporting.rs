@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use checker::TypeCheckOptions;
+use ezno_lib::reporting::{json::diagnostics_to_json, sarif::diagnostics_to_sarif};
+use serde_json::Value;
+
+#[test]
+fn sarif_results_and_rules() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some("const a: number = 2;\nconst b: string = a;".to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	let sarif = diagnostics_to_sarif(output.diagnostics, &output.module_contents);
+	let sarif: Value = serde_json::from_str(&sarif).unwrap();
+
+	assert_eq!(sarif["version"], "2.1.0");
+	let run = &sarif["runs"][0];
+	assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "EZ1002");
+
+	let result = &run["results"][0];
+	assert_eq!(result["ruleId"], "EZ1002");
+	assert_eq!(result["level"], "error");
+	let location = &result["locations"][0]["physicalLocation"];
+	assert_eq!(location["artifactLocation"]["uri"], "main.ts");
+	assert_eq!(location["region"]["startLine"], 2);
+}
+
+#[test]
+fn crlf_and_lone_carriage_return_line_breaks() {
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some("const a: number = 2;\r\n\rconst b: string = a;".to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(
+		vec![PathBuf::from("main.ts")],
+		&read_file,
+		&[],
+		TypeCheckOptions::default(),
+	);
+	let json = diagnostics_to_json(output.diagnostics, &output.module_contents);
+	let json: Value = serde_json::from_str(&json).unwrap();
+
+	let start = &json[0]["start"];
+	assert_eq!(start["line"], 3);
+	assert_eq!(start["column"], 19);
+}
@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use checker::{features::semantic_tokens::SemanticTokenKind, TypeCheckOptions};
+
+const SOURCE: &str = "type Count = number;
+enum Direction { Up, Down }
+function add(a: Count, b: number) { return a + b + total }
+const frozen = Object.freeze({ x: 1 });
+const obj = { z: 2 };
+const values = [frozen.x, obj.z, add];
+let c: Missing;";
+
+fn read_file(path: &Path) -> Option<String> {
+	(path.to_str()? == "main.ts").then(|| SOURCE.to_owned())
+}
+
+fn tokens(options: TypeCheckOptions) -> Option<Vec<(String, SemanticTokenKind)>> {
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+	let tokens = output.get_semantic_tokens("main.ts")?;
+	Some(
+		tokens
+			.into_iter()
+			.map(|token| {
+				let text = &SOURCE[token.position.start as usize..token.position.end as usize];
+				(text.to_owned(), token.kind)
+			})
+			.collect(),
+	)
+}
+
+#[test]
+fn classifies_identifiers() {
+	let options = TypeCheckOptions { store_expression_type_mappings: true, ..Default::default() };
+	let tokens = tokens(options).unwrap();
+
+	let expected = [
+		("Up", SemanticTokenKind::EnumMember),
+		("Down", SemanticTokenKind::EnumMember),
+		("a", SemanticTokenKind::Parameter),
+		("Count", SemanticTokenKind::TypeAlias),
+		("b", SemanticTokenKind::Parameter),
+		("a", SemanticTokenKind::Parameter),
+		("b", SemanticTokenKind::Parameter),
+		("total", SemanticTokenKind::Unresolved),
+		("freeze", SemanticTokenKind::Property),
+		("frozen", SemanticTokenKind::Variable),
+		("x", SemanticTokenKind::ReadonlyProperty),
+		("obj", SemanticTokenKind::Variable),
+		("z", SemanticTokenKind::Property),
+		("add", SemanticTokenKind::Function),
+		("Missing", SemanticTokenKind::Unresolved),
+	];
+	for (text, kind) in expected {
+		assert!(
+			tokens.iter().any(|(t, k)| t == text && *k == kind),
+			"expected {text} to be {kind:?} in {tokens:?}"
+		);
+	}
+}
+
+#[test]
+fn requires_type_mappings() {
+	assert_eq!(tokens(TypeCheckOptions::default()), Some(Vec::new()));
+}
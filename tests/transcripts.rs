@@ -0,0 +1,117 @@
+//! Replays request transcripts (in `tests/transcripts/*.json`) against the checker and compares the
+//! responses with the `*.snap.json` file next to it.
+//!
+//! A transcript has `files` (path to content), an optional `entry` (defaults to `main.ts`) and `requests`. Requests are
+//! `{ "method": "diagnostics" }`, `{ "method": "hover", "path": "main.ts", "offset": 4 }` or
+//! `{ "method": "completions", "path": "main.ts", "offset": 4 }`.
+//!
+//! See [`common::check_snapshot`] for updating snapshots
+
+mod common;
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use checker::{synthesis::EznoParser, CheckOutput, TypeCheckOptions};
+use parser::source_map::FileSystem;
+use serde_json::{json, Value};
+
+#[test]
+fn transcripts() {
+	let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/transcripts");
+
+	let mut transcripts = fs::read_dir(&directory)
+		.unwrap()
+		.filter_map(|entry| {
+			let path = entry.unwrap().path();
+			let is_transcript = path.extension().is_some_and(|ext| ext == "json")
+				&& !path.to_string_lossy().ends_with(".snap.json");
+			is_transcript.then_some(path)
+		})
+		.collect::<Vec<_>>();
+	transcripts.sort();
+
+	let mut failures = Vec::new();
+	for path in transcripts {
+		let transcript: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+		let responses = Value::Array(run_transcript(&transcript));
+		let output = serde_json::to_string_pretty(&responses).unwrap();
+
+		if let Err(failure) = common::check_snapshot(&path.with_extension("snap.json"), &output) {
+			failures.push(failure);
+		}
+	}
+
+	assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+fn run_transcript(transcript: &Value) -> Vec<Value> {
+	let files = transcript["files"].as_object().expect("transcript has no files");
+	let entry_point = PathBuf::from(transcript["entry"].as_str().unwrap_or("main.ts"));
+
+	let read_file = |path: &Path| -> Option<String> {
+		files.get(path.to_str()?).and_then(Value::as_str).map(ToOwned::to_owned)
+	};
+
+	// `lsp_mode` for partial syntax, such as completions after `x.`
+	let options = TypeCheckOptions {
+		store_expression_type_mappings: true,
+		lsp_mode: true,
+		..Default::default()
+	};
+	let output: CheckOutput<EznoParser> =
+		ezno_lib::check(vec![entry_point], &read_file, &[], options);
+
+	transcript["requests"]
+		.as_array()
+		.expect("transcript has no requests")
+		.iter()
+		.map(|request| match request["method"].as_str() {
+			Some("diagnostics") => diagnostics(&output),
+			Some("hover") => {
+				let path = request["path"].as_str().expect("hover needs path");
+				let offset = request["offset"].as_u64().expect("hover needs offset");
+				let ty = output.get_type_at_position(path, offset.try_into().unwrap(), false);
+				json!({ "type": ty })
+			}
+			Some("completions") => {
+				let path = request["path"].as_str().expect("completions needs path");
+				let offset = request["offset"].as_u64().expect("completions needs offset");
+				let completions = output
+					.get_completions_at_position(path, offset.try_into().unwrap())
+					.unwrap_or_default();
+				completions
+					.into_iter()
+					.map(|completion| {
+						let kind = format!("{:?}", completion.kind).to_lowercase();
+						json!({ "label": completion.label, "kind": kind, "type": completion.r#type })
+					})
+					.collect()
+			}
+			method => json!({ "error": format!("unsupported method {method:?}") }),
+		})
+		.collect()
+}
+
+fn diagnostics(output: &CheckOutput<EznoParser>) -> Value {
+	output
+		.diagnostics
+		.iter()
+		.map(|diagnostic| {
+			let kind = format!("{:?}", diagnostic.kind()).to_lowercase();
+			if let Some(position) = diagnostic.position() {
+				json!({
+					"kind": kind,
+					"reason": diagnostic.reason(),
+					"file": output.module_contents.get_file_path(position.source).display().to_string(),
+					"start": position.start,
+					"end": position.end,
+				})
+			} else {
+				json!({ "kind": kind, "reason": diagnostic.reason() })
+			}
+		})
+		.collect()
+}
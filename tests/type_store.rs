@@ -0,0 +1,20 @@
+use checker::{types::TypeStore, Constant};
+
+#[test]
+fn identical_types_are_interned() {
+	let mut types = TypeStore::default();
+
+	let a = types.new_constant_type(Constant::String("a".to_owned()));
+	let two = types.new_constant_type(Constant::Number(2f64.try_into().unwrap()));
+	let union = types.new_or_type(a, two);
+	let count = types.count_of_types();
+
+	assert_eq!(types.new_constant_type(Constant::String("a".to_owned())), a);
+	assert_eq!(types.new_constant_type(Constant::Number(2f64.try_into().unwrap())), two);
+	assert_eq!(types.new_or_type(a, two), union);
+	assert_eq!(types.count_of_types(), count);
+
+	// Each symbol is unique
+	let symbol = Constant::Symbol { key: "a".to_owned() };
+	assert_ne!(types.new_constant_type(symbol.clone()), types.new_constant_type(symbol));
+}
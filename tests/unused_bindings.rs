@@ -0,0 +1,33 @@
+use std::path::{Path, PathBuf};
+
+use checker::{DiagnosticKind, TypeCheckOptions};
+
+const MAIN: &str = "const { a, b, _c } = { a: 1, b: 2, _c: 3 };\nfunction func({ d }: { d: number }) {}\na satisfies 1;";
+
+#[test]
+fn unused_destructured_bindings() {
+	let options = TypeCheckOptions { no_unused_bindings: true, ..Default::default() };
+
+	let read_file = |path: &Path| -> Option<String> {
+		match path.to_str()? {
+			"main.ts" => Some(MAIN.to_owned()),
+			_ => None,
+		}
+	};
+
+	let output = ezno_lib::check(vec![PathBuf::from("main.ts")], &read_file, &[], options);
+
+	let warnings = output
+		.diagnostics
+		.iter()
+		.filter(|diagnostic| matches!(diagnostic.kind(), DiagnosticKind::Warning))
+		.map(|diagnostic| diagnostic.reason().to_owned())
+		.collect::<Vec<_>>();
+
+	// `a` is read and `_c` is ignored
+	assert_eq!(
+		warnings,
+		["'b' is declared but never read", "'d' is declared but never read"],
+		"{warnings:?}"
+	);
+}